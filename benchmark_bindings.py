@@ -0,0 +1,73 @@
+#!/usr/bin/env python3
+"""
+Benchmark the cost of handing extraction results to Python.
+
+Extracts a synthetic 5k-file batch, then measures separately:
+- Extraction (extract_files_batch, Rust side)
+- Wrapping (ExtractionResults.symbols/identifiers/relationships)
+- Light access (name and file_path of every symbol)
+- Full access (to_dict() of every symbol, metadata included)
+
+Run it on two builds to compare them: `maturin develop --release` on each
+checkout, then `python benchmark_bindings.py`.
+"""
+
+import time
+from pathlib import Path
+
+FILES = 5000
+
+
+def synthetic_file(index: int) -> str:
+    """A Python module with a documented class, methods, and calls."""
+    return f'''"""Module {index}."""
+import os
+
+
+class Service{index}(Base):
+    """Handles requests for shard {index}."""
+
+    def load(self, path):
+        """Read the shard from disk."""
+        return os.path.join(path, "shard_{index}")
+
+    def save(self, path, data):
+        self.load(path)
+        return len(data)
+
+
+def helper_{index}(value):
+    return Service{index}().save("/tmp", value)
+'''
+
+
+def timed(label: str, action):
+    start = time.perf_counter()
+    result = action()
+    elapsed = (time.perf_counter() - start) * 1000
+    print(f"{label:28} {elapsed:>10.1f} ms")
+    return result
+
+
+def benchmark_bindings():
+    from miller import miller_core
+
+    files = [(synthetic_file(i), "python", f"pkg/module_{i}.py") for i in range(FILES)]
+    print(f"Batch: {FILES} files, {sum(len(c) for c, _, _ in files) / 1024:.0f} KB\n")
+
+    results = timed(
+        "extract_files_batch", lambda: miller_core.extract_files_batch(files, str(Path.cwd()))
+    )
+    symbols = timed(
+        "wrap symbols", lambda: [s for r in results for s in r.symbols]
+    )
+    timed("wrap identifiers", lambda: [i for r in results for i in r.identifiers])
+    timed("wrap relationships", lambda: [x for r in results for x in r.relationships])
+    timed("name + file_path", lambda: [(s.name, s.file_path) for s in symbols])
+    if symbols and hasattr(symbols[0], "to_dict"):
+        timed("to_dict()", lambda: [s.to_dict() for s in symbols])
+    print(f"\n{len(symbols)} symbols")
+
+
+if __name__ == "__main__":
+    benchmark_bindings()
@@ -0,0 +1,78 @@
+// Extract one file with the plain Rust engine - no Python involved
+//
+// Usage:
+//     cargo run --no-default-features --example extract_file -- src/lib.rs [workspace_root]
+//
+// The path is relative to the workspace root (default "."). Prints the
+// detected language, then each symbol, identifier, relationship, and
+// diagnostic.
+
+use miller_core::engine::{self, EnrichmentOptions, ExtractionLimits};
+use std::path::Path;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let Some(file_path) = args.next() else {
+        eprintln!("usage: extract_file <path> [workspace_root]");
+        return ExitCode::FAILURE;
+    };
+    let workspace_root = args.next().unwrap_or_else(|| ".".to_string());
+
+    let file = match engine::extract_path(
+        &file_path,
+        Path::new(&workspace_root),
+        &EnrichmentOptions::default(),
+        &ExtractionLimits::default(),
+    ) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("{}: {}", file_path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("{} ({}, blake3 {})", file.path, file.language, file.hash);
+    let Some(results) = &file.results else {
+        println!("no extractor for this file type");
+        return ExitCode::SUCCESS;
+    };
+
+    println!("\nsymbols ({}):", results.symbols.len());
+    for symbol in &results.symbols {
+        println!(
+            "  {:?} {} [lines {}-{}]",
+            symbol.kind, symbol.name, symbol.start_line, symbol.end_line
+        );
+    }
+
+    println!("\nidentifiers ({}):", results.identifiers.len());
+    for identifier in &results.identifiers {
+        println!(
+            "  {:?} {} [line {}]",
+            identifier.kind, identifier.name, identifier.start_line
+        );
+    }
+
+    println!("\nrelationships ({}):", results.relationships.len());
+    for relationship in &results.relationships {
+        println!(
+            "  {} -{:?}-> {} [line {}]",
+            relationship.from_symbol_id,
+            relationship.kind,
+            relationship.to_symbol_id,
+            relationship.line_number
+        );
+    }
+
+    for diagnostic in file.diagnostics() {
+        println!(
+            "\n{} [lines {}-{}]: {}",
+            diagnostic.kind.as_str(),
+            diagnostic.start_line,
+            diagnostic.end_line,
+            diagnostic.message
+        );
+    }
+    ExitCode::SUCCESS
+}
@@ -233,3 +233,144 @@ def helper_function(x, y):
 
     # Simple file should have one symbol
     assert len(results[1].symbols) == 1
+
+
+def test_extract_files_batch_merges_partial_classes():
+    """Partial C# classes split across files share a semantic group when asked."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    workspace_root = str(Path.cwd())
+    files = [
+        ("namespace App;\npublic partial class Form1 { void Init() {} }\n", "csharp", "Form1.Designer.cs"),
+        ("namespace App;\npublic partial class Form1 { void Load() {} }\n", "csharp", "Form1.cs"),
+    ]
+
+    plain = miller_core.extract_files_batch(files, workspace_root)
+    assert all(
+        s.semantic_group is None for r in plain for s in r.symbols if s.name == "Form1"
+    )
+
+    results = miller_core.extract_files_batch(files, workspace_root, merge_partials=True)
+    parts = [s for r in results for s in r.symbols if s.name == "Form1"]
+    assert len(parts) == 2
+    assert {s.semantic_group for s in parts} == {"partial:App.Form1"}
+
+    links = [rel for rel in results[1].relationships if rel.to_symbol_id == parts[0].id]
+    assert [rel.from_symbol_id for rel in links] == [parts[1].id]
+
+
+def test_resolve_cross_file_links_require_to_class(tmp_path):
+    """A require() of a workspace file links to the class that file defines."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    user = "class User {}\nmodule.exports = { User };\n"
+    main = "const { User } = require('./user');\nnew User();\n"
+    (tmp_path / "user.js").write_text(user)
+    (tmp_path / "main.js").write_text(main)
+    files = [(user, "javascript", "user.js"), (main, "javascript", "main.js")]
+
+    results = miller_core.extract_files_batch(files, str(tmp_path))
+    assert miller_core.resolve_cross_file(results) == 1
+    assert miller_core.resolve_cross_file(results) == 0
+
+    user_class = next(s for s in results[0].symbols if s.name == "User" and s.kind == "class")
+    imported = next(s for s in results[1].symbols if s.name == "User" and s.kind == "import")
+    links = [rel for rel in results[1].relationships if rel.kind == "imports"]
+    assert [(rel.from_symbol_id, rel.to_symbol_id) for rel in links] == [(imported.id, user_class.id)]
+
+
+def test_resolve_cross_file_links_java_imports_by_package(tmp_path):
+    """Java imports find their class through source roots inferred from packages."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    strings = "package com.acme.util;\n\npublic class Strings {}\n"
+    app = "package com.acme.web;\n\nimport com.acme.util.Strings;\n\npublic class App {}\n"
+    misplaced = "package com.acme.web;\n\npublic class Misplaced {}\n"
+    files = [
+        (strings, "java", "core/src/main/java/com/acme/util/Strings.java"),
+        (app, "java", "web/src/main/java/com/acme/web/App.java"),
+        (misplaced, "java", "web/src/main/java/Misplaced.java"),
+    ]
+
+    results = miller_core.extract_files_batch(files, str(tmp_path))
+    assert miller_core.resolve_cross_file(results) == 1
+
+    strings_class = next(s for s in results[0].symbols if s.name == "Strings")
+    links = [rel for rel in results[1].relationships if rel.kind == "imports"]
+    assert [rel.to_symbol_id for rel in links] == [strings_class.id]
+    assert [d.kind for d in results[2].diagnostics] == ["package_mismatch"]
+
+
+def test_resolve_cross_file_pairs_c_declarations_with_definitions(tmp_path):
+    """A header prototype is paired with its one definition; ambiguity is a diagnostic."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    header = "int open_db(const char *path);\nvoid log_line(const char *msg);\nstatic int cache(void);\n"
+    db = "int open_db(const char* path)\n{\n    return 0;\n}\n\nvoid log_line(const char *msg) {}\n"
+    log = "void log_line(const char *msg) {}\n"
+    files = [(header, "c", "include/db.h"), (db, "c", "src/db.c"), (log, "c", "src/log.c")]
+
+    results = miller_core.extract_files_batch(files, str(tmp_path))
+    assert miller_core.resolve_cross_file(results) == 1
+
+    declaration = next(s for s in results[0].symbols if s.name == "open_db")
+    definition = next(s for s in results[1].symbols if s.name == "open_db")
+    links = [rel for rel in results[1].relationships if rel.kind == "defines"]
+    assert [(rel.from_symbol_id, rel.to_symbol_id) for rel in links] == [
+        (definition.id, declaration.id)
+    ]
+    assert [d.kind for d in results[2].diagnostics] == ["ambiguous_pairing"]
+
+
+def test_extract_files_batch_returns_or_raises_per_file_errors():
+    """A failed file is an error in its results, or raises when asked to."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    workspace_root = str(Path.cwd())
+    deep = "x = " + "[" * 600 + "]" * 600 + "\n"
+    files = [
+        ("def ok(): pass", "python", "ok.py"),
+        (deep, "python", "deep.py"),
+    ]
+
+    results = miller_core.extract_files_batch(files, workspace_root)
+    assert results[0].error is None
+    assert results[1].error_kind == "depth_exceeded"
+
+    with pytest.raises(miller_core.ExtractionError) as exc_info:
+        miller_core.extract_files_batch(files, workspace_root, raise_on_error=True)
+    assert exc_info.value.file_path == "deep.py"
+    assert exc_info.value.language == "python"
+    assert exc_info.value.kind == "depth_exceeded"
+
+
+def test_extract_files_batch_reports_progress_and_cancels():
+    """The progress callback sees every interval; returning False cancels the rest."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    workspace_root = str(Path.cwd())
+    files = [(f"def f{i}(): pass\n", "python", f"m{i}.py") for i in range(60)]
+
+    calls = []
+    results = miller_core.extract_files_batch(
+        files,
+        workspace_root,
+        progress_callback=lambda done, total, path: calls.append((done, total, path)),
+        progress_interval=25,
+    )
+    assert [(done, total) for done, total, _ in calls] == [(25, 60), (50, 60), (60, 60)]
+    assert all(path.endswith(".py") for _, _, path in calls)
+    assert not any(r.cancelled for r in results)
+
+    results = miller_core.extract_files_batch(
+        files * 20, workspace_root, progress_callback=lambda *_: False, progress_interval=1
+    )
+    cancelled = [r for r in results if r.cancelled]
+    assert cancelled and len(cancelled) < len(results)
+    assert all(r.error_kind == "cancelled" and not r.symbols for r in cancelled)
@@ -0,0 +1,66 @@
+import pytest
+from miller import miller_core
+
+
+def test_correlate_endpoints_links_fetch_to_spring_handler():
+    """A TypeScript fetch() reaches the Spring handler whose composed route matches."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    server = miller_core.extract_file(
+        '@RestController\n@RequestMapping("/api/users")\npublic class UserController {\n'
+        '    @GetMapping("/{id}")\n    public User show(@PathVariable long id) { return null; }\n}\n',
+        "java",
+        "UserController.java",
+    )
+    client = miller_core.extract_file(
+        "export async function loadUser(id: number) {\n"
+        "  return fetch(`/api/users/${id}`);\n}\n",
+        "typescript",
+        "api.ts",
+    )
+
+    links = miller_core.correlate_endpoints([server, client])
+
+    assert len(links) == 1
+    link = links[0]
+    assert (link.http_method, link.url, link.route) == ("GET", "/api/users/{id}", "/api/users/{id}")
+    assert link.client_file_path == "api.ts"
+    assert link.server_file_path == "UserController.java"
+    assert link.confidence == pytest.approx(1.0)
+    assert link.provenance == "heuristic"
+    show = next(s for s in server.symbols if s.name == "show")
+    assert link.server_symbol_id == show.id
+
+
+def test_correlate_schema_groups_table_and_sqlalchemy_model():
+    """A SQLAlchemy model and its CREATE TABLE share a semantic group."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    schema = miller_core.extract_file(
+        "CREATE TABLE users (\n  id INTEGER PRIMARY KEY,\n  email_address TEXT\n);\n",
+        "sql",
+        "schema.sql",
+    )
+    models = miller_core.extract_file(
+        "class Account(Base):\n"
+        "    __tablename__ = 'users'\n"
+        "    id = Column(Integer, primary_key=True)\n"
+        "    email = Column('email_address', String)\n",
+        "python",
+        "models.py",
+    )
+
+    links = miller_core.correlate_schema([schema, models])
+
+    assert [(link.table, link.model, link.confidence) for link in links] == [("users", "Account", 1.0)]
+    # __tablename__ names the table; only email names its column
+    assert links[0].provenance == "ast"
+    assert {c.column: c.provenance for c in links[0].columns} == {
+        "id": "name-match",
+        "email_address": "ast",
+    }
+    account = next(s for s in models.symbols if s.name == "Account")
+    table = next(s for s in schema.symbols if s.name == "users")
+    assert account.semantic_group == table.semantic_group == "table:users"
@@ -0,0 +1,84 @@
+import pytest
+from miller import miller_core
+
+
+def test_extract_workspace_walks_and_filters(tmp_path):
+    """Supported files are extracted; ignored directories and large files are not."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / "app.py").write_text("def app(): pass\n")
+    (tmp_path / "src" / "big.py").write_text("x = 1\n" * 100)
+    (tmp_path / "node_modules").mkdir()
+    (tmp_path / "node_modules" / "lib.js").write_text("module.exports = 1;\n")
+
+    results = miller_core.extract_workspace(str(tmp_path), max_file_size=100)
+
+    by_path = {r.path: r for r in results}
+    assert set(by_path) == {"src/app.py", "src/big.py"}
+    assert by_path["src/app.py"].is_success
+    assert "app" in [s.name for s in by_path["src/app.py"].results.symbols]
+    assert not by_path["src/big.py"].is_success
+    assert "max_file_size" in by_path["src/big.py"].error
+
+
+def test_extract_workspace_requires_directory(tmp_path):
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    with pytest.raises(FileNotFoundError):
+        miller_core.extract_workspace(str(tmp_path / "missing"))
+
+
+
+def test_extract_workspace_reports_progress_and_cancels(tmp_path):
+    """The walk's extraction reports progress like a batch; returning False cancels the rest."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    for i in range(30):
+        (tmp_path / f"m{i}.py").write_text(f"def f{i}(): pass\n")
+
+    calls = []
+    results = miller_core.extract_workspace(
+        str(tmp_path),
+        progress_callback=lambda done, total, path: calls.append((done, total, path)),
+        progress_interval=10,
+    )
+    assert [(done, total) for done, total, _ in calls] == [(10, 30), (20, 30), (30, 30)]
+    assert all(r.is_success for r in results)
+
+    for i in range(30, 600):
+        (tmp_path / f"m{i}.py").write_text(f"def f{i}(): pass\n")
+    results = miller_core.extract_workspace(
+        str(tmp_path), progress_callback=lambda *_: False, progress_interval=1
+    )
+    cancelled = [r for r in results if r.cancelled]
+    assert cancelled and len(cancelled) < len(results)
+    assert all(r.error_kind == "cancelled" for r in cancelled)
+
+def test_is_ignored_matches_workspace_walk(tmp_path):
+    """Nested .gitignore negations re-include what the walk then extracts."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    (tmp_path / ".gitignore").write_text("generated/*\n!generated/api/\n")
+    (tmp_path / "generated" / "api").mkdir(parents=True)
+    (tmp_path / "generated" / "api" / "client.py").write_text("def client(): pass\n")
+    (tmp_path / "generated" / "models.py").write_text("class Model: pass\n")
+    (tmp_path / "src").mkdir()
+    (tmp_path / "src" / ".gitignore").write_text("*_test.py\n")
+    (tmp_path / "src" / "app.py").write_text("def app(): pass\n")
+    (tmp_path / "src" / "app_test.py").write_text("def test_app(): pass\n")
+
+    root = str(tmp_path)
+    assert miller_core.is_ignored("generated/models.py", root)
+    assert not miller_core.is_ignored("generated/api/client.py", root)
+    assert miller_core.is_ignored("src/app_test.py", root)
+    assert not miller_core.is_ignored("app_test.py", root)
+    assert miller_core.is_ignored("node_modules/", root)
+    assert not miller_core.is_ignored("src/app_test.py", root, ignore_patterns=["!src/app_test.py"])
+
+    results = miller_core.extract_workspace(root)
+    assert sorted(r.path for r in results) == ["generated/api/client.py", "src/app.py"]
@@ -138,6 +138,18 @@ class TestExtractFileAPI:
         assert "unsupported" in str(exc_info.value).lower() or \
                "invalid" in str(exc_info.value).lower()
 
+    def test_extract_file_errors_are_typed(self):
+        """Unsupported files raise UnsupportedLanguageError with the file attached."""
+        from miller import miller_core
+
+        with pytest.raises(miller_core.UnsupportedLanguageError) as exc_info:
+            miller_core.extract_file(content="test", language="xyz", file_path="notes.xyz")
+
+        assert isinstance(exc_info.value, miller_core.ExtractionError)
+        assert exc_info.value.file_path == "notes.xyz"
+        assert exc_info.value.language is None
+        assert exc_info.value.kind == "unsupported_language"
+
     def test_extract_file_with_syntax_error_returns_empty(self):
         """Test that code with syntax errors returns empty results gracefully."""
         from miller import miller_core
@@ -234,6 +246,19 @@ class TestDetectLanguageAPI:
         lang = miller_core.detect_language("Makefile")
         assert lang is None
 
+    def test_detect_language_from_shebang(self):
+        """Extensionless scripts are detected from their shebang when content is given."""
+        from miller import miller_core
+
+        assert miller_core.detect_language("bin/build", "#!/usr/bin/env python3\n") == "python"
+        assert miller_core.detect_language("deploy", "#!/bin/sh\nset -e\n") == "bash"
+
+    def test_detect_language_content_does_not_override_extension(self):
+        """The extension still wins when it identifies the language."""
+        from miller import miller_core
+
+        assert miller_core.detect_language("main.rs", "#!/bin/bash\n") == "rust"
+
 
 class TestSupportedLanguagesAPI:
     """Test the supported_languages() function."""
@@ -332,3 +357,211 @@ class TestExtractionWithUnicode:
         if result.symbols[0].doc_comment:
             assert "你好" in result.symbols[0].doc_comment or \
                    "Prints" in result.symbols[0].doc_comment
+
+
+class TestSqlDialect:
+    """Test the sql_dialect option of extract_file()."""
+
+    def test_dialect_recorded_on_symbols(self):
+        """An explicit dialect is recorded in every symbol's metadata."""
+        from miller import miller_core
+
+        code = "CREATE TABLE users (id INT);"
+        result = miller_core.extract_file(code, "sql", "schema.sql", sql_dialect="postgres")
+
+        assert result.symbols
+        for symbol in result.symbols:
+            assert symbol.metadata["dialect"] == "postgres"
+
+    def test_dialect_detected_from_content(self):
+        """T-SQL is detected from GO separators and bracketed names."""
+        from miller import miller_core
+
+        code = "CREATE TABLE [dbo].[Users] ([Id] INT)\nGO\n"
+        result = miller_core.extract_file(code, "sql", "schema.sql")
+
+        assert result.symbols
+        assert result.symbols[0].metadata["dialect"] == "tsql"
+
+    def test_unknown_dialect_raises(self):
+        """Unknown dialect names are rejected."""
+        from miller import miller_core
+
+        with pytest.raises(ValueError):
+            miller_core.extract_file("SELECT 1;", "sql", "q.sql", sql_dialect="oracle")
+
+
+class TestRegexTextFallback:
+    """Test filtering of the regex extractor's text fallback."""
+
+    def test_prose_lines_are_not_symbols(self):
+        """Plain lines without regex structure don't become symbols."""
+        from miller import miller_core
+
+        code = "this is not a pattern\nneither is this one\n"
+        result = miller_core.extract_file(code, "regex", "notes.regex")
+
+        assert result.symbols == []
+
+    def test_ast_only_disables_fallback(self):
+        """regex_ast_only=True drops every text-fallback symbol."""
+        from miller import miller_core
+
+        code = "/^[a-z]+$/i\n"
+        result = miller_core.extract_file(code, "regex", "word.regex", regex_ast_only=True)
+
+        assert all(not (s.metadata or {}).get("textFallback") for s in result.symbols)
+
+
+class TestSymbolKindFilter:
+    """Test the symbol_kinds allow-list."""
+
+    CODE = "class Service:\n    retries = 3\n\n    def run(self):\n        pass\n\ndef main():\n    pass\n"
+
+    def test_filtered_parents_become_stubs(self):
+        """A method kept inside a filtered-out class hangs off a stub of the class."""
+        from miller import miller_core
+
+        result = miller_core.extract_file(
+            self.CODE, "python", "service.py", symbol_kinds=["method", "function"]
+        )
+
+        by_name = {s.name: s for s in result.symbols}
+        assert "retries" not in by_name
+        assert by_name["run"].parent_id == by_name["Service"].id
+        assert by_name["Service"].metadata == {"filteredStub": True}
+        assert by_name["Service"].signature is None
+        assert not (by_name["main"].metadata or {}).get("filteredStub")
+
+    def test_without_children_and_unknown_kinds(self):
+        """include_children=False drops nested symbols; unknown kinds raise."""
+        from miller import miller_core
+
+        result = miller_core.extract_file(
+            self.CODE,
+            "python",
+            "service.py",
+            symbol_kinds=["method", "function"],
+            include_children=False,
+        )
+
+        assert [s.name for s in result.symbols] == ["main"]
+        with pytest.raises(ValueError):
+            miller_core.extract_file(self.CODE, "python", "service.py", symbol_kinds=["widget"])
+
+
+class TestDeclarationMacros:
+    """Test C symbols declared through macros."""
+
+    CODE = (
+        "#define REGISTER_HANDLER(path, fn) static int fn(void)\n"
+        "\n"
+        "MODULE_EXPORT(int, start_server)(int port) { return port; }\n"
+        'REGISTER_HANDLER("/health", health_check);\n'
+    )
+
+    def test_default_and_workspace_patterns(self):
+        """Default and caller-supplied patterns name symbols from macro arguments."""
+        from miller import miller_core
+
+        result = miller_core.extract_file(
+            self.CODE,
+            "c",
+            "server.c",
+            declaration_macros=[("REGISTER_HANDLER", "function", "fn")],
+        )
+
+        by_name = {s.name: s for s in result.symbols}
+        server = by_name["start_server"]
+        assert server.kind == "function"
+        assert server.metadata["generatedByMacro"]["macro"] == "MODULE_EXPORT"
+        assert server.confidence < 1.0
+        assert by_name["health_check"].metadata["generatedByMacro"]["invocation"] == (
+            'REGISTER_HANDLER("/health", health_check)'
+        )
+        with pytest.raises(ValueError):
+            miller_core.extract_file(
+                self.CODE, "c", "server.c", declaration_macros=[("X_*", "widget", 0)]
+            )
+
+
+class TestHtmlTemplates:
+    """Test extraction of HTML with template engine placeholders."""
+
+    def test_placeholders_become_symbols_and_keep_their_text(self):
+        """Jinja placeholders are symbols; code_context shows the template as written."""
+        from miller import miller_core
+
+        code = "<ul>\n  {% for user in users %}\n  <li>{{ user.name }}</li>\n  {% endfor %}\n</ul>\n"
+        result = miller_core.extract_file(code, "html", "users.html", include_code_context=True)
+
+        placeholder = next(s for s in result.symbols if s.name == "user.name")
+        assert placeholder.metadata["templateEngine"] == "jinja"
+        assert placeholder.metadata["templateExpression"] == "{{ user.name }}"
+        assert "{{ user.name }}" in placeholder.code_context
+
+
+class TestErbTemplates:
+    """Test extraction of Rails ERB views."""
+
+    def test_erb_is_detected_and_renders_become_imports(self):
+        """An .html.erb file gets a template symbol and an Import per rendered partial."""
+        from miller import miller_core
+
+        assert miller_core.detect_language("app/views/users/show.html.erb") == "erb"
+        assert "erb" in miller_core.supported_languages()
+
+        code = '<div>\n  <%= render "shared/header" %>\n  <p><%= user.name %></p>\n</div>\n'
+        result = miller_core.extract_file(code, "erb", "app/views/users/show.html.erb")
+
+        template = next(s for s in result.symbols if s.name == "show")
+        assert template.metadata["templatePath"] == "users/show"
+        render = next(s for s in result.symbols if s.kind == "import")
+        assert render.name == "shared/header"
+        assert render.metadata["importPath"] == "shared/header"
+        assert all(s.file_path == "app/views/users/show.html.erb" for s in result.symbols)
+
+
+class TestDiagnostics:
+    """Test the diagnostics reported on extraction results."""
+
+    def test_clean_file_is_complete(self):
+        """A file that parses cleanly has no diagnostics."""
+        from miller import miller_core
+
+        result = miller_core.extract_file("def f():\n    return 1\n", "python", "clean.py")
+
+        assert result.diagnostics == []
+        assert result.is_complete is True
+
+
+class TestHashFilesBatch:
+    """Test hash_files_batch() over files on disk."""
+
+    def test_digests_match_hash_content(self, tmp_path):
+        """Streamed digests equal hash_content of the file's text."""
+        from miller import miller_core
+
+        (tmp_path / "app.py").write_text("def foo(): pass\n")
+        results = miller_core.hash_files_batch(["app.py"], str(tmp_path))
+
+        assert results == [("app.py", miller_core.hash_content("def foo(): pass\n"), "ok")]
+
+    def test_per_file_states_in_input_order(self, tmp_path):
+        """Skipped and failed files keep their slot with a status and no digest."""
+        from miller import miller_core
+
+        (tmp_path / "big.py").write_text("x = 1\n" * 100)
+        (tmp_path / "logo.png").write_bytes(b"\x89PNG")
+        results = miller_core.hash_files_batch(
+            ["missing.py", "big.py", "logo.png"],
+            str(tmp_path),
+            max_file_size=100,
+            supported_only=True,
+        )
+
+        assert results == [
+            ("missing.py", None, "missing"),
+            ("big.py", None, "too_large"),
+            ("logo.png", None, "unsupported"),
+        ]
@@ -0,0 +1,103 @@
+import pytest
+from pathlib import Path
+from miller import miller_core
+
+
+@pytest.fixture
+def core():
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+    miller_core.clear_cache()
+    yield miller_core
+    miller_core.configure_cache(max_entries=4096, max_bytes=256 * 1024 * 1024)
+    miller_core.clear_cache()
+
+
+def test_extract_file_cache_hits(core):
+    """Identical content at the same path is served from the cache."""
+    first = core.extract_file("def foo(): pass\n", "python", "foo.py", use_cache=True)
+    second = core.extract_file("def foo(): pass\n", "python", "foo.py", use_cache=True)
+
+    stats = core.cache_stats()
+    assert stats["misses"] == 1
+    assert stats["hits"] == 1
+    assert stats["entries"] == 1
+    assert stats["hit_rate"] == 0.5
+    assert [s.id for s in first.symbols] == [s.id for s in second.symbols]
+
+    # Same content at another path is a different entry
+    core.extract_file("def foo(): pass\n", "python", "bar.py", use_cache=True)
+    assert core.cache_stats()["entries"] == 2
+
+    # Without use_cache the cache is untouched
+    core.extract_file("def foo(): pass\n", "python", "foo.py")
+    assert core.cache_stats()["hits"] == 1
+
+
+def test_cache_is_bounded(core):
+    """Least-recently-used entries are evicted past max_entries."""
+    core.configure_cache(max_entries=2)
+    for name in ["a", "b", "c"]:
+        core.extract_file(f"def {name}(): pass\n", "python", f"{name}.py", use_cache=True)
+
+    stats = core.cache_stats()
+    assert stats["entries"] == 2
+    assert stats["evictions"] == 1
+
+    core.clear_cache()
+    assert core.cache_stats()["entries"] == 0
+
+
+def test_batch_extraction_shares_cache(core):
+    """The parallel batch path reads and fills the same cache."""
+    files = [
+        ("def foo(): pass", "python", "foo.py"),
+        ("function bar() {}", "javascript", "bar.js"),
+    ]
+    core.extract_files_batch(files, str(Path.cwd()), use_cache=True)
+    results = core.extract_files_batch(files, str(Path.cwd()), use_cache=True)
+
+    stats = core.cache_stats()
+    assert stats["hits"] == 2
+    assert "bar" in [s.name for s in results[1].symbols]
+
+
+def test_results_carry_their_extractor_version(core):
+    """Results are tagged with their language's entry in extractor_versions()."""
+    versions = core.extractor_versions()
+    assert set(versions) == set(core.supported_languages())
+    assert versions["sql"].startswith("1.20.0+")
+
+    results = core.extract_file("def foo(): pass\n", "python", "foo.py", use_cache=True)
+    assert results.extractor_version == versions["python"]
+    cached = core.extract_file("def foo(): pass\n", "python", "foo.py", use_cache=True)
+    assert cached.extractor_version == versions["python"]
+
+    batch = core.extract_files_batch([("SELECT 1;", "sql", "q.sql")], str(Path.cwd()))
+    assert batch[0].extractor_version == versions["sql"]
+
+
+def test_cache_is_keyed_by_workspace_root(core, tmp_path):
+    """The same file under two roots keeps each root's import resolution."""
+    content = "from .models import User\n"
+    with_models = tmp_path / "a"
+    without_models = tmp_path / "b"
+    for root in (with_models, without_models):
+        (root / "pkg").mkdir(parents=True)
+        (root / "pkg" / "__init__.py").write_text("")
+    (with_models / "pkg" / "models.py").write_text("class User: pass\n")
+    files = [(content, "python", "pkg/app.py")]
+
+    unresolved = core.extract_files_batch(files, str(without_models), use_cache=True)
+    resolved = core.extract_files_batch(files, str(with_models), use_cache=True)
+
+    def resolved_path(results):
+        imports = [s for s in results[0].symbols if s.kind == "import"]
+        return (imports[0].metadata or {}).get("resolvedPath")
+
+    assert resolved_path(unresolved) is None
+    assert resolved_path(resolved) == "pkg/models.py"
+    # Results that resolved a path are not cached; they would go stale
+    resolved = core.extract_files_batch(files, str(with_models), use_cache=True)
+    assert resolved_path(resolved) == "pkg/models.py"
+    assert core.cache_stats()["entries"] == 1
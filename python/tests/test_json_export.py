@@ -0,0 +1,45 @@
+import json
+
+import pytest
+from miller import miller_core
+
+
+def test_extract_file_to_json(tmp_path):
+    """A file's results serialize with camelCase field names."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    (tmp_path / "app.py").write_text("def foo():\n    return bar()\n")
+    record = json.loads(miller_core.extract_file_to_json("app.py", str(tmp_path)))
+
+    assert record["filePath"] == "app.py"
+    assert record["language"] == "python"
+    symbol = next(s for s in record["symbols"] if s["name"] == "foo")
+    assert symbol["startLine"] == 1
+    assert "start_line" not in symbol
+
+    with pytest.raises(miller_core.FileReadError) as exc_info:
+        miller_core.extract_file_to_json("missing.py", str(tmp_path))
+    assert exc_info.value.file_path == "missing.py"
+    assert exc_info.value.kind == "read_error"
+
+
+def test_extract_files_to_ndjson(tmp_path):
+    """One JSON object per input file, in order; unreadable files carry an error."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    (tmp_path / "a.py").write_text("def a(): pass\n")
+    (tmp_path / "b.rs").write_text("fn b() {}\n")
+    output = tmp_path / "out.ndjson"
+
+    written, failed = miller_core.extract_files_to_ndjson(
+        ["a.py", "missing.py", "b.rs"], str(output), str(tmp_path)
+    )
+    assert (written, failed) == (3, 1)
+
+    lines = [json.loads(line) for line in output.read_text().splitlines()]
+    assert [line["filePath"] for line in lines] == ["a.py", "missing.py", "b.rs"]
+    assert "a" in [s["name"] for s in lines[0]["symbols"]]
+    assert "error" in lines[1]
+    assert "b" in [s["name"] for s in lines[2]["symbols"]]
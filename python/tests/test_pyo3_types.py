@@ -151,6 +151,70 @@ class TestSymbolTypeConversion:
         assert method_sym.parent_id == class_sym.id
         assert method_sym.name == "hello"
 
+    def test_symbol_qualified_name_joins_parent_chain(self):
+        """Test that Symbol.qualified_name includes enclosing classes."""
+        from miller import miller_core
+
+        code = """class Greeter:
+    def hello(self):
+        pass"""
+        result = miller_core.extract_file(code, "python", "test.py")
+
+        by_name = {s.name: s for s in result.symbols}
+        assert by_name["Greeter"].qualified_name == "Greeter"
+        assert by_name["hello"].qualified_name == "Greeter.hello"
+
+    def test_symbol_confidence_is_set_for_parsed_symbols(self):
+        """Test that cleanly parsed symbols carry full confidence."""
+        from miller import miller_core
+
+        result = miller_core.extract_file("def hello(): pass", "python", "test.py")
+
+        assert result.symbols[0].confidence == pytest.approx(1.0)
+
+    def test_symbol_code_context_is_opt_in(self):
+        """Test that code_context holds surrounding lines only when requested."""
+        from miller import miller_core
+
+        code = "import os\n\ndef hello():\n    pass\n"
+        plain = miller_core.extract_file(code, "python", "test.py")
+        with_context = miller_core.extract_file(
+            code, "python", "test.py", include_code_context=True, code_context_lines=(1, 1)
+        )
+
+        hello = next(s for s in with_context.symbols if s.name == "hello")
+        assert hello.code_context == "\ndef hello():\n    pass"
+        assert all(s.code_context is None for s in plain.symbols)
+
+    def test_symbol_metadata_is_a_cached_dict_of_plain_values(self):
+        """Test that metadata values are Python values, converted once."""
+        from miller import miller_core
+
+        code = "CREATE TABLE users (id INT);\n"
+        result = miller_core.extract_file(code, "sql", "schema.sql", sql_dialect="postgres")
+        table = next(s for s in result.symbols if s.name == "users")
+
+        assert table.metadata["dialect"] == "postgres"
+        assert table.metadata is table.metadata
+
+    def test_symbol_to_dict_materializes_every_field(self):
+        """Test that to_dict() returns the same values as the getters."""
+        from miller import miller_core
+
+        code = """class Greeter:
+    def hello(self):
+        pass"""
+        result = miller_core.extract_file(code, "python", "test.py")
+        hello = next(s for s in result.symbols if s.name == "hello")
+
+        data = hello.to_dict()
+        assert data["name"] == "hello"
+        assert data["kind"] == "method"
+        assert data["parent_id"] == hello.parent_id
+        assert data["qualified_name"] == "Greeter.hello"
+        assert data["start_line"] == hello.start_line
+        assert data["metadata"] == hello.metadata
+
     def test_symbol_repr_works(self):
         """Test that Symbol has a useful string representation."""
         from miller import miller_core
@@ -218,6 +282,24 @@ class TestIdentifierTypeConversion:
         # Code context
         _ = identifier.code_context
 
+    def test_identifier_resolves_to_same_file_definition(self):
+        """A call to a function defined earlier in the file links to it."""
+        from miller import miller_core
+
+        code = """
+def helper():
+    return 1
+
+def main():
+    return helper()
+"""
+        result = miller_core.extract_file(code, "python", "test.py")
+
+        helper = next(s for s in result.symbols if s.name == "helper")
+        call = next(i for i in result.identifiers if i.name == "helper")
+        assert call.resolved_symbol_id == helper.id
+        assert call.target_symbol_id == helper.id
+
 
 class TestRelationshipTypeConversion:
     """Test that Rust Relationship struct is accessible from Python."""
@@ -260,13 +342,34 @@ class Derived(Base):
         assert rel.file_path == "test.py"
         assert rel.line_number >= 1
 
-        # Confidence
+        # Confidence and provenance
         assert isinstance(rel.confidence, float)
         assert 0.0 <= rel.confidence <= 1.0
+        assert rel.provenance == "ast"
 
         # Optional metadata
         _ = rel.metadata
 
+    def test_relationship_and_identifier_to_dict(self):
+        """Test that to_dict() works for relationships and identifiers."""
+        from miller import miller_core
+
+        code = """class Base:
+    pass
+
+class Derived(Base):
+    def hello(self):
+        print("hi")"""
+        result = miller_core.extract_file(code, "python", "test.py")
+
+        rel = result.relationships[0]
+        assert rel.to_dict()["from_symbol_id"] == rel.from_symbol_id
+        assert rel.to_dict()["kind"] == rel.kind
+        assert rel.to_dict()["provenance"] == "ast"
+        identifier = result.identifiers[0]
+        assert identifier.to_dict()["name"] == identifier.name
+        assert identifier.to_dict()["containing_symbol_id"] == identifier.containing_symbol_id
+
 
 class TestExtractionResultsContainer:
     """Test that ExtractionResults container works."""
@@ -0,0 +1,52 @@
+import pytest
+from miller import miller_core
+
+CODE = '''
+def load(path):
+    """Read the file at path and return its parsed contents."""
+    return parse(open(path).read())
+
+
+def parse(text):
+    """Split text into stripped, non-empty lines."""
+    return [line.strip() for line in text.splitlines() if line.strip()]
+'''
+
+
+def test_reduce_results_fits_budget_and_reports_drops():
+    """Context and doc comments go before symbols; the original is untouched."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    results = miller_core.extract_file(CODE, "python", "loader.py", include_code_context=True)
+
+    reduced, report = miller_core.reduce_results(results, 10_000)
+    assert not report.truncated
+    assert len(reduced.symbols) == len(results.symbols)
+
+    reduced, report = miller_core.reduce_results(results, report.original_tokens - 1)
+    assert report.truncated
+    assert report.code_contexts >= 1
+    assert report.symbols == 0
+    assert report.estimated_tokens < report.original_tokens
+    assert all(s.code_context is not None for s in results.symbols)
+
+    again, same = miller_core.reduce_results(results, report.original_tokens - 1)
+    assert [s.id for s in again.symbols] == [s.id for s in reduced.symbols]
+    assert repr(same) == repr(report)
+
+
+def test_keep_symbols_strategy_never_drops_symbols():
+    """keep_symbols stops after identifiers even when over budget."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    results = miller_core.extract_file(CODE, "python", "loader.py")
+
+    reduced, report = miller_core.reduce_results(results, 1, strategy="keep_symbols")
+    assert report.symbols == 0
+    assert len(reduced.symbols) == len(results.symbols)
+    assert report.estimated_tokens > 1
+
+    with pytest.raises(ValueError):
+        miller_core.reduce_results(results, 1, strategy="random")
@@ -0,0 +1,55 @@
+import pytest
+from miller import miller_core
+
+SERVICE = '''
+class UserService:
+    def get_user(self, user_id):
+        return None
+
+
+user_service = UserService()
+'''
+
+TEST = '''
+def test_user_service():
+    assert True
+'''
+
+
+def _results():
+    return [
+        miller_core.extract_file(SERVICE, "python", "src/services/user.py"),
+        miller_core.extract_file(TEST, "python", "tests/test_user.py"),
+    ]
+
+
+def test_rank_symbols_orders_by_match_path_and_kind():
+    """The class outranks the variable and the test function for a multi-word query."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    results = _results()
+    names = {s.id: s.name for r in results for s in r.symbols}
+
+    ranked = miller_core.rank_symbols("user service", results)
+
+    assert [names[symbol_id] for symbol_id, _ in ranked[:2]] == ["UserService", "user_service"]
+    scores = [score for _, score in ranked]
+    assert scores == sorted(scores, reverse=True)
+    assert ranked == miller_core.rank_symbols("user service", results)
+
+
+def test_rank_symbols_accepts_results_and_symbol_lists():
+    """One ExtractionResults or a list of Symbols rank the same way."""
+    if miller_core is None:
+        pytest.skip("miller_core not available")
+
+    service = _results()[0]
+
+    from_results = miller_core.rank_symbols("UserService", service, limit=1)
+    from_symbols = miller_core.rank_symbols("UserService", service.symbols, limit=1)
+
+    assert from_results == from_symbols
+    assert len(from_results) == 1
+    with pytest.raises(TypeError):
+        miller_core.rank_symbols("UserService", 42)
@@ -2,11 +2,14 @@
 //
 // These functions provide the public API for Miller's extraction functionality.
 
-use super::{PyBatchFileResult, PyExtractionResults};
-use julie_extractors::{detect_language_from_extension, ExtractionResults, ExtractorManager};
-use pyo3::exceptions::PyValueError;
+use super::errors::extraction_error;
+use super::options::{enrichment_options, extraction_limits, DeclarationMacroSpec};
+use super::progress::run_batch_detached;
+use super::{cache, PyBatchFileResult, PyExtractionResults};
+use crate::engine::{self, EnrichmentOptions, ExtractionError, ExtractionResults};
 use pyo3::prelude::*;
-use std::fs;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::Path;
 
 /// Extract symbols, identifiers, and relationships from source code
@@ -15,57 +18,128 @@ use std::path::Path;
 ///     content (str): Source code content to extract from
 ///     language (str): Programming language (e.g., "python", "javascript", "rust")
 ///     file_path (str): File path (for symbol storage and language detection)
+///     use_cache (bool): Reuse results for identical content from the extraction
+///         cache (see cache_stats / clear_cache). Defaults to False.
+///     sql_dialect (str, optional): "generic", "postgres", "mysql", or "tsql" for
+///         SQL files; detected from the content when omitted
+///     regex_ast_only (bool): For regex files, keep only symbols from the parse
+///         tree and skip the line-by-line text fallback. Defaults to False.
+///     include_code_context (bool): Store the source lines around each symbol
+///         in its code_context. Defaults to False.
+///     code_context_lines (tuple[int, int]): Lines kept (before, after) each
+///         symbol's first line. Defaults to (2, 2).
+///     symbol_kinds (list[str], optional): Return only symbols of these kinds
+///         (e.g. ["class", "function", "method"]); all kinds when omitted.
+///         Identifiers keep the containing_symbol_id of filtered-out symbols.
+///     include_children (bool): With symbol_kinds, keep allowed symbols nested
+///         in filtered-out ones; each filtered-out ancestor is returned as a
+///         stub (no signature or doc comment, metadata {"filteredStub": True})
+///         so parent_id still resolves. When False, a filtered-out symbol's
+///         nested symbols are dropped with it. Defaults to True.
+///     declaration_macros (list[tuple[str, str, int | str]], optional): Extra C
+///         macros whose invocations declare a symbol, checked before the
+///         defaults: (macro pattern, symbol kind, name argument), e.g.
+///         ("REGISTER_*", "function", 1). The pattern is a macro name with an
+///         optional leading or trailing "*"; the name argument is a zero-based
+///         position or a parameter name from the macro's #define in the file.
 ///
 /// Returns:
 ///     ExtractionResults: Container with symbols, identifiers, and relationships
 ///
 /// Raises:
-///     ValueError: If language is not supported
+///     UnsupportedLanguageError: If no extractor handles the file's extension
+///     ParseInitError: If the extractor's parser could not be initialized
+///     ExtractionPanicError: If the extractor panicked
+///     ExtractionError: Base class of the above; instances carry file_path,
+///         language, and kind attributes
+///     ValueError: If the SQL dialect or a symbol kind is unknown
 #[pyfunction]
-#[pyo3(signature = (content, language, file_path))]
-#[allow(unused_variables)]
+#[pyo3(signature = (
+    content,
+    language,
+    file_path,
+    use_cache = false,
+    sql_dialect = None,
+    regex_ast_only = false,
+    include_code_context = false,
+    code_context_lines = (2, 2),
+    symbol_kinds = None,
+    include_children = true,
+    declaration_macros = None
+))]
+#[allow(clippy::too_many_arguments)]
 pub fn extract_file(
     content: &str,
     language: &str,
     file_path: &str,
+    use_cache: bool,
+    sql_dialect: Option<&str>,
+    regex_ast_only: bool,
+    include_code_context: bool,
+    code_context_lines: (usize, usize),
+    symbol_kinds: Option<Vec<String>>,
+    include_children: bool,
+    declaration_macros: Option<Vec<DeclarationMacroSpec>>,
 ) -> PyResult<PyExtractionResults> {
-    // Create extractor manager
-    let manager = ExtractorManager::new();
+    let options = enrichment_options(
+        sql_dialect,
+        regex_ast_only,
+        include_code_context.then_some(code_context_lines),
+        symbol_kinds,
+        include_children,
+        declaration_macros,
+    )?;
 
     // Use current directory as workspace root (Miller doesn't need workspace context for basic extraction)
     let workspace_root = Path::new(".");
 
-    // Extract symbols using Julie's proven extraction logic
-    let symbols = manager
-        .extract_symbols(file_path, content, workspace_root)
-        .map_err(|e| PyValueError::new_err(format!("Extraction failed: {}", e)))?;
-
-    // Extract identifiers (requires symbols to be extracted first)
-    let identifiers = manager
-        .extract_identifiers(file_path, content, &symbols)
-        .map_err(|e| PyValueError::new_err(format!("Identifier extraction failed: {}", e)))?;
+    let extract = || extract_strict(file_path, content, workspace_root, &options);
+    let results = if use_cache {
+        cache::get_or_extract(
+            content,
+            language,
+            file_path,
+            workspace_root,
+            &options,
+            extract,
+        )?
+    } else {
+        extract()?
+    };
 
-    // Extract relationships (requires symbols to be extracted first)
-    let relationships = manager
-        .extract_relationships(file_path, content, &symbols)
-        .map_err(|e| PyValueError::new_err(format!("Relationship extraction failed: {}", e)))?;
+    Ok(PyExtractionResults::from_extraction_results(results).with_language(language))
+}
 
-    // Create ExtractionResults
-    let results = ExtractionResults {
-        symbols,
-        identifiers,
-        relationships,
-        pending_relationships: Vec::new(), // Cross-file resolution not needed for Miller
-        types: std::collections::HashMap::new(),
-    };
+/// `engine::extract` with failures, panics included, raised as ExtractionError subclasses
+pub(super) fn extract_strict(
+    file_path: &str,
+    content: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+) -> PyResult<ExtractionResults> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        engine::extract(file_path, content, workspace_root, options)
+    }))
+    .unwrap_or_else(|payload| {
+        Err(engine::ExtractionError::Panicked(engine::panic_message(
+            &*payload,
+        )))
+    })
+    .map_err(|e| extraction_error(&e, file_path, known_language(file_path, Some(content))))
+}
 
-    Ok(PyExtractionResults::from_extraction_results(results))
+/// Detected language, or None for files no extractor handles
+pub(super) fn known_language(file_path: &str, content: Option<&str>) -> Option<&'static str> {
+    Some(engine::detect_language(Path::new(file_path), content)).filter(|l| *l != "text")
 }
 
-/// Detect programming language from file extension
+/// Detect programming language from file extension, or from content
 ///
 /// Args:
 ///     file_path (str): File path with extension (e.g., "main.rs", "app.py")
+///     content (str, optional): File content. When given, files the extension
+///         does not identify (extensionless scripts, Gemfile, .bashrc) are
+///         detected from well-known names, a shebang, or an emacs/vim modeline.
 ///
 /// Returns:
 ///     str: Language name if detected, "text" for unknown extensions
@@ -74,16 +148,9 @@ pub fn extract_file(
 ///     Never returns None - unknown file types are treated as "text" to ensure
 ///     they remain searchable via full-text search even without symbol extraction.
 #[pyfunction]
-#[pyo3(signature = (file_path))]
-pub fn detect_language(file_path: &str) -> PyResult<String> {
-    // Extract extension from file path
-    let path = Path::new(file_path);
-    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
-
-    // Use Julie's language detection, fallback to "text" for unknown extensions
-    let lang = detect_language_from_extension(extension).unwrap_or("text");
-
-    Ok(lang.to_string())
+#[pyo3(signature = (file_path, content = None))]
+pub fn detect_language(file_path: &str, content: Option<&str>) -> PyResult<String> {
+    Ok(engine::detect_language(Path::new(file_path), content).to_string())
 }
 
 /// Get list of all supported programming languages
@@ -92,52 +159,27 @@ pub fn detect_language(file_path: &str) -> PyResult<String> {
 ///     list[str]: List of supported language names
 #[pyfunction]
 pub fn supported_languages() -> PyResult<Vec<String>> {
-    let manager = ExtractorManager::new();
-    let langs = manager.supported_languages();
-
-    Ok(langs.iter().map(|&s| s.to_string()).collect())
+    Ok(engine::supported_languages()
+        .into_iter()
+        .map(String::from)
+        .collect())
 }
 
-/// Compute blake3 hash of content string
+/// Extractor version tag of every supported language
 ///
-/// Blake3 is ~3x faster than SHA-256 while providing equivalent security.
-/// Used for incremental indexing change detection.
-///
-/// Args:
-///     content (str): Content to hash
+/// A language's tag changes whenever its extractor or Miller's passes for it
+/// change their output, so results cached under an older tag are stale.
+/// ExtractionResults.extractor_version carries the tag of the file's language.
 ///
 /// Returns:
-///     str: 64-character hex digest of blake3 hash
+///     dict[str, str]: Language name -> version tag,
+///         "<julie-extractors>+<pipeline>.<language passes>"
 #[pyfunction]
-pub fn hash_content(content: &str) -> String {
-    let hash = blake3::hash(content.as_bytes());
-    hash.to_hex().to_string()
-}
-
-/// Compute blake3 hashes for multiple content strings in parallel
-///
-/// Efficiently computes hashes for many files using Rayon's parallel iterators.
-/// Releases the GIL to allow true multi-threaded execution.
-///
-/// Args:
-///     contents (list[str]): List of content strings to hash
-///
-/// Returns:
-///     list[str]: List of 64-character hex digests in same order as input
-#[pyfunction]
-pub fn hash_contents_batch(py: Python<'_>, contents: Vec<String>) -> Vec<String> {
-    use rayon::prelude::*;
-
-    // Release GIL for parallel processing
-    py.detach(move || {
-        contents
-            .par_iter()
-            .map(|content| {
-                let hash = blake3::hash(content.as_bytes());
-                hash.to_hex().to_string()
-            })
-            .collect()
-    })
+pub fn extractor_versions() -> BTreeMap<String, String> {
+    engine::extractor_versions()
+        .into_iter()
+        .map(|(language, version)| (language.to_string(), version))
+        .collect()
 }
 
 /// Extract symbols from multiple files in parallel
@@ -154,11 +196,16 @@ pub fn hash_contents_batch(py: Python<'_>, contents: Vec<String>) -> Vec<String>
 /// # Error Handling
 /// - Extraction errors are logged to stderr but do not fail the entire batch
 /// - Files that fail to parse return empty ExtractionResults
-/// - Check individual result lengths to detect failed extractions
+/// - A file whose extractor panics, runs past `timeout`, or nests deeper than
+///   the engine's limit returns empty ExtractionResults with `error` and
+///   `error_kind` ("panicked", "timed_out", "depth_exceeded") set
+/// - With `raise_on_error=True` the first such file (in input order) raises
+///   its ExtractionError subclass instead, after the whole batch has run
 ///
 /// # Thread Safety
 /// - Safe to call concurrently from multiple Python threads
-/// - Each file is processed independently with no shared mutable state
+/// - Each file is processed independently; with use_cache the only shared state
+///   is the extraction cache, which is guarded by a mutex
 ///
 /// Args:
 ///     files (list[tuple[str, str, str]]): List of (content, language, file_path) tuples
@@ -166,11 +213,42 @@ pub fn hash_contents_batch(py: Python<'_>, contents: Vec<String>) -> Vec<String>
 ///         - language: Language identifier (currently unused, language detected from file_path)
 ///         - file_path: Relative path from workspace root
 ///     workspace_root (str): Absolute path to workspace root directory
+///     use_cache (bool): Reuse results for identical content under the same
+///         workspace_root from the shared extraction cache. Results that
+///         resolved an import to another file are not cached. Defaults to False.
+///     sql_dialect (str, optional): Dialect for SQL files (see extract_file)
+///     regex_ast_only (bool): Skip the regex text fallback (see extract_file)
+///     include_code_context (bool): Store surrounding lines (see extract_file)
+///     code_context_lines (tuple[int, int]): Lines kept (see extract_file)
+///     symbol_kinds (list[str], optional): Kinds returned (see extract_file)
+///     include_children (bool): Keep nested symbols of filtered-out ones
+///         under stubs (see extract_file)
+///     declaration_macros (list[tuple], optional): Extra C declaration macros
+///         (see extract_file)
+///     timeout (float, optional): Seconds before a file's extraction is
+///         abandoned. Defaults to 30; None waits indefinitely.
+///     merge_partials (bool): Group C# partial types split across the batch's
+///         files: the parts share semantic_group "partial:<qualified name>"
+///         and each later part references the first. Defaults to False.
+///     raise_on_error (bool): Raise for the first failed file instead of
+///         returning its error in the results. Defaults to False.
+///     progress_callback (callable, optional): Called as
+///         progress_callback(files_done, files_total, current_path) every
+///         progress_interval finished files and after the last one, from one
+///         thread while extraction continues. Returning False, or raising,
+///         cancels the files not started yet: they come back with
+///         cancelled=True (error_kind "cancelled") and the rest are returned
+///         as usual. The exception itself goes to sys.unraisablehook.
+///     progress_interval (int): Files between progress calls. Defaults to 25.
 ///
 /// Returns:
 ///     list[ExtractionResults]: List of results in same order as input
 ///                              (preserves input ordering despite parallel execution)
 ///
+/// Raises:
+///     ExtractionPanicError, ExtractionError: With raise_on_error, for the
+///         first file whose extraction failed
+///
 /// Example:
 ///     >>> files = [
 ///     ...     ("def foo(): pass", "python", "src/foo.py"),
@@ -179,68 +257,112 @@ pub fn hash_contents_batch(py: Python<'_>, contents: Vec<String>) -> Vec<String>
 ///     >>> results = extract_files_batch(files, "/path/to/workspace")
 ///     >>> assert len(results) == 2
 #[pyfunction]
-#[pyo3(signature = (files, workspace_root))]
+#[pyo3(signature = (
+    files,
+    workspace_root,
+    use_cache = false,
+    sql_dialect = None,
+    regex_ast_only = false,
+    include_code_context = false,
+    code_context_lines = (2, 2),
+    symbol_kinds = None,
+    include_children = true,
+    declaration_macros = None,
+    timeout = Some(engine::DEFAULT_TIMEOUT.as_secs_f64()),
+    merge_partials = false,
+    raise_on_error = false,
+    progress_callback = None,
+    progress_interval = engine::DEFAULT_PROGRESS_INTERVAL
+))]
+#[allow(clippy::too_many_arguments)]
 pub fn extract_files_batch(
     py: Python<'_>,
     files: Vec<(String, String, String)>,
     workspace_root: String,
+    use_cache: bool,
+    sql_dialect: Option<&str>,
+    regex_ast_only: bool,
+    include_code_context: bool,
+    code_context_lines: (usize, usize),
+    symbol_kinds: Option<Vec<String>>,
+    include_children: bool,
+    declaration_macros: Option<Vec<DeclarationMacroSpec>>,
+    timeout: Option<f64>,
+    merge_partials: bool,
+    raise_on_error: bool,
+    progress_callback: Option<Py<PyAny>>,
+    progress_interval: usize,
 ) -> PyResult<Vec<PyExtractionResults>> {
-    use rayon::prelude::*;
+    let options = enrichment_options(
+        sql_dialect,
+        regex_ast_only,
+        include_code_context.then_some(code_context_lines),
+        symbol_kinds,
+        include_children,
+        declaration_macros,
+    )?;
+    let limits = extraction_limits(timeout)?;
 
     let workspace_root_path = Path::new(&workspace_root);
 
-    // Release GIL for parallel processing
-    let results = py.detach(move || {
-        files
-            .par_iter()
-            .map(|(content, _language, file_path)| {
-                let manager = ExtractorManager::new();
-
-                // Extract symbols with error logging
-                let symbols = manager
-                    .extract_symbols(file_path, content, workspace_root_path)
-                    .unwrap_or_else(|e| {
-                        eprintln!(
-                            "Warning: Failed to extract symbols from {}: {}",
-                            file_path, e
-                        );
-                        Vec::new()
-                    });
-
-                // Extract identifiers with error logging
-                let identifiers = manager
-                    .extract_identifiers(file_path, content, &symbols)
-                    .unwrap_or_else(|e| {
-                        eprintln!(
-                            "Warning: Failed to extract identifiers from {}: {}",
-                            file_path, e
-                        );
-                        Vec::new()
-                    });
-
-                // Extract relationships with error logging
-                let relationships = manager
-                    .extract_relationships(file_path, content, &symbols)
-                    .unwrap_or_else(|e| {
-                        eprintln!(
-                            "Warning: Failed to extract relationships from {}: {}",
-                            file_path, e
-                        );
-                        Vec::new()
-                    });
-
-                let results = ExtractionResults {
-                    symbols,
-                    identifiers,
-                    relationships,
-                    pending_relationships: Vec::new(),
-                    types: std::collections::HashMap::new(),
-                };
-
-                PyExtractionResults::from_extraction_results(results)
-            })
-            .collect()
-    });
+    let mut results: Vec<_> = run_batch_detached(
+        py,
+        &files,
+        |(_, _, file_path)| file_path,
+        |(content, language, file_path)| {
+            let extract = || {
+                engine::extract_guarded(file_path, content, workspace_root_path, &options, &limits)
+            };
+            // Failures are not cached, so a timed-out file is retried next time
+            if use_cache {
+                cache::get_or_extract(
+                    content,
+                    language,
+                    file_path,
+                    workspace_root_path,
+                    &options,
+                    extract,
+                )
+            } else {
+                extract()
+            }
+        },
+        progress_callback.as_ref(),
+        progress_interval,
+    )
+    .into_iter()
+    .map(|result| result.unwrap_or(Err(ExtractionError::Cancelled)))
+    .collect();
+
+    if merge_partials {
+        let mut extracted: Vec<&mut ExtractionResults> =
+            results.iter_mut().filter_map(|r| r.as_mut().ok()).collect();
+        engine::link_partial_types(&mut extracted);
+    }
+    if raise_on_error {
+        if let Some((error, (content, _, file_path))) = results
+            .iter()
+            .zip(&files)
+            .filter(|(result, _)| !matches!(result, Err(ExtractionError::Cancelled)))
+            .find_map(|(result, file)| Some((result.as_ref().err()?, file)))
+        {
+            return Err(extraction_error(
+                error,
+                file_path,
+                known_language(file_path, Some(content)),
+            ));
+        }
+    }
+    let results = results
+        .into_iter()
+        .zip(&files)
+        .map(|(results, (_, language, _))| match results {
+            Ok(results) => {
+                PyExtractionResults::from_extraction_results(results).with_language(language)
+            }
+            Err(e) => PyExtractionResults::from_error(&e),
+        })
+        .collect();
 
     Ok(results)
 }
@@ -261,11 +383,19 @@ pub fn extract_files_batch(
 /// # Error Handling
 /// - Individual file read errors are captured in the result's `error` field
 /// - Extraction errors result in `results: None` but `content` still populated
+/// - Panics, timeouts, and over-deep nesting are captured like read errors;
+///   `error_kind` tells them apart
 /// - The function never raises - all errors are returned in PyBatchFileResult
 ///
 /// Args:
 ///     file_paths (list[str]): List of relative file paths from workspace root
 ///     workspace_root (str): Absolute path to workspace root directory
+///     timeout (float, optional): Seconds before a file's extraction is
+///         abandoned. Defaults to 30; None waits indefinitely.
+///     progress_callback (callable, optional): Progress and cancellation
+///         (see extract_files_batch); files not started after a cancel come
+///         back with cancelled=True
+///     progress_interval (int): Files between progress calls. Defaults to 25.
 ///
 /// Returns:
 ///     list[BatchFileResult]: Results containing content, hash, language, and extraction data
@@ -277,107 +407,51 @@ pub fn extract_files_batch(
 ///     ...     if r.is_success:
 ///     ...         print(f"{r.path}: {r.language}, {len(r.content)} bytes")
 #[pyfunction]
-#[pyo3(signature = (file_paths, workspace_root))]
+#[pyo3(signature = (
+    file_paths,
+    workspace_root,
+    timeout = Some(engine::DEFAULT_TIMEOUT.as_secs_f64()),
+    progress_callback = None,
+    progress_interval = engine::DEFAULT_PROGRESS_INTERVAL
+))]
 pub fn extract_files_batch_with_io(
     py: Python<'_>,
     file_paths: Vec<String>,
     workspace_root: String,
+    timeout: Option<f64>,
+    progress_callback: Option<Py<PyAny>>,
+    progress_interval: usize,
 ) -> PyResult<Vec<PyBatchFileResult>> {
-    use rayon::prelude::*;
-
     let workspace_root_path = Path::new(&workspace_root);
-
-    // Release GIL for parallel I/O + CPU processing
-    let results = py.detach(move || {
-        file_paths
-            .par_iter()
-            .map(|rel_path| {
-                // 1. Resolve full path
-                let full_path = workspace_root_path.join(rel_path);
-
-                // 2. Read file content
-                let content = match fs::read_to_string(&full_path) {
-                    Ok(c) => c,
-                    Err(e) => {
-                        return PyBatchFileResult::error(
-                            rel_path.clone(),
-                            format!("Read error: {}", e),
-                        );
-                    }
-                };
-
-                // 3. Compute Blake3 hash
-                let hash = blake3::hash(content.as_bytes()).to_hex().to_string();
-
-                // 4. Detect language from extension
-                let extension = full_path
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .unwrap_or("");
-                let language =
-                    detect_language_from_extension(extension).unwrap_or("text");
-
-                // 5. Extract symbols (if not a text file)
-                let results = if language == "text" {
-                    // Text files: no symbol extraction, but we still have content
-                    None
-                } else {
-                    let manager = ExtractorManager::new();
-
-                    // Extract symbols
-                    let symbols = manager
-                        .extract_symbols(rel_path, &content, workspace_root_path)
-                        .unwrap_or_else(|e| {
-                            eprintln!(
-                                "Warning: Failed to extract symbols from {}: {}",
-                                rel_path, e
-                            );
-                            Vec::new()
-                        });
-
-                    // Extract identifiers
-                    let identifiers = manager
-                        .extract_identifiers(rel_path, &content, &symbols)
-                        .unwrap_or_else(|e| {
-                            eprintln!(
-                                "Warning: Failed to extract identifiers from {}: {}",
-                                rel_path, e
-                            );
-                            Vec::new()
-                        });
-
-                    // Extract relationships
-                    let relationships = manager
-                        .extract_relationships(rel_path, &content, &symbols)
-                        .unwrap_or_else(|e| {
-                            eprintln!(
-                                "Warning: Failed to extract relationships from {}: {}",
-                                rel_path, e
-                            );
-                            Vec::new()
-                        });
-
-                    let extraction_results = ExtractionResults {
-                        symbols,
-                        identifiers,
-                        relationships,
-                        pending_relationships: Vec::new(),
-                        types: std::collections::HashMap::new(),
-                    };
-
-                    Some(PyExtractionResults::from_extraction_results(extraction_results))
-                };
-
-                PyBatchFileResult::success(
-                    rel_path.clone(),
-                    content,
-                    language.to_string(),
-                    hash,
-                    results,
-                )
-            })
-            .collect()
-    });
+    let options = EnrichmentOptions::default();
+    let limits = extraction_limits(timeout)?;
+
+    let results = run_batch_detached(
+        py,
+        &file_paths,
+        |rel_path| rel_path,
+        |rel_path| engine::extract_path(rel_path, workspace_root_path, &options, &limits),
+        progress_callback.as_ref(),
+        progress_interval,
+    );
+    let results = results
+        .into_iter()
+        .zip(file_paths)
+        .map(|(result, rel_path)| {
+            let result = result.unwrap_or(Err(ExtractionError::Cancelled));
+            match result {
+                Ok(file) => PyBatchFileResult::success(
+                    file.path,
+                    file.content,
+                    file.language,
+                    file.hash,
+                    file.results
+                        .map(PyExtractionResults::from_extraction_results),
+                ),
+                Err(e) => PyBatchFileResult::error(rel_path, e.kind(), e.to_string()),
+            }
+        })
+        .collect();
 
     Ok(results)
 }
@@ -7,16 +7,13 @@
 // creating a new Python string. For 1MM LOC, this means ~75 million allocations.
 // By building Arrow arrays directly, we create only a handful of large allocations.
 
-use arrow_array::builder::{
-    Float32Builder, StringBuilder, UInt32Builder,
-};
+use crate::engine::{self, EnrichmentOptions, ExtractionLimits};
+use arrow_array::builder::{Float32Builder, StringBuilder, UInt32Builder};
 use arrow_array::RecordBatch;
 use arrow_schema::{DataType, Field, Schema};
-use julie_extractors::{detect_language_from_extension, ExtractorManager};
 use pyo3::prelude::*;
 use pyo3_arrow::PyRecordBatch;
 use rayon::prelude::*;
-use std::fs;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -215,7 +212,9 @@ fn build_symbols_batch(symbols: &[julie_extractors::Symbol]) -> anyhow::Result<R
 }
 
 /// Build identifiers RecordBatch from collected identifiers
-fn build_identifiers_batch(identifiers: &[julie_extractors::Identifier]) -> anyhow::Result<RecordBatch> {
+fn build_identifiers_batch(
+    identifiers: &[julie_extractors::Identifier],
+) -> anyhow::Result<RecordBatch> {
     let mut id_builder = StringBuilder::new();
     let mut name_builder = StringBuilder::new();
     let mut kind_builder = StringBuilder::new();
@@ -288,7 +287,9 @@ fn build_identifiers_batch(identifiers: &[julie_extractors::Identifier]) -> anyh
 }
 
 /// Build relationships RecordBatch from collected relationships
-fn build_relationships_batch(relationships: &[julie_extractors::Relationship]) -> anyhow::Result<RecordBatch> {
+fn build_relationships_batch(
+    relationships: &[julie_extractors::Relationship],
+) -> anyhow::Result<RecordBatch> {
     let mut id_builder = StringBuilder::new();
     let mut from_symbol_id_builder = StringBuilder::new();
     let mut to_symbol_id_builder = StringBuilder::new();
@@ -325,7 +326,9 @@ fn build_relationships_batch(relationships: &[julie_extractors::Relationship]) -
 }
 
 /// Build files RecordBatch from collected file data
-fn build_files_batch(files: &[(String, String, String, String, usize)]) -> anyhow::Result<RecordBatch> {
+fn build_files_batch(
+    files: &[(String, String, String, String, usize)],
+) -> anyhow::Result<RecordBatch> {
     let mut path_builder = StringBuilder::new();
     let mut language_builder = StringBuilder::new();
     let mut content_builder = StringBuilder::new();
@@ -383,6 +386,8 @@ pub fn extract_files_to_arrow(
     workspace_root: String,
 ) -> PyResult<PyArrowExtractionBatch> {
     let workspace_root_path = Path::new(&workspace_root);
+    let options = EnrichmentOptions::default();
+    let limits = ExtractionLimits::default();
 
     // Parallel extraction with GIL released
     let collected = py.allow_threads(|| {
@@ -391,70 +396,30 @@ pub fn extract_files_to_arrow(
             .par_iter()
             .map(|rel_path| {
                 let mut result = CollectedResults::new();
-                let full_path = workspace_root_path.join(rel_path);
 
-                // Read file
-                let content = match fs::read_to_string(&full_path) {
-                    Ok(c) => c,
+                // Read, hash, detect language, and extract (text files are not extracted);
+                // panics, timeouts, and over-deep nesting are reported like read errors
+                let extracted =
+                    engine::extract_path(rel_path, workspace_root_path, &options, &limits);
+                let file = match extracted {
+                    Ok(file) => file,
                     Err(e) => {
                         result.errors.push(format!("{}: {}", rel_path, e));
                         return result;
                     }
                 };
 
-                // Compute hash
-                let hash = blake3::hash(content.as_bytes()).to_hex().to_string();
-                let size = content.len();
-
-                // Detect language
-                let extension = full_path
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .unwrap_or("");
-                let language = detect_language_from_extension(extension).unwrap_or("text");
-
                 // Store file metadata
-                result.files.push((
-                    rel_path.clone(),
-                    language.to_string(),
-                    content.clone(),
-                    hash,
-                    size,
-                ));
-
-                // Skip extraction for text files
-                if language == "text" {
-                    return result;
-                }
+                let size = file.content.len();
+                result
+                    .files
+                    .push((file.path, file.language, file.content, file.hash, size));
 
-                // Extract symbols
-                let manager = ExtractorManager::new();
-                let symbols = manager
-                    .extract_symbols(rel_path, &content, workspace_root_path)
-                    .unwrap_or_else(|e| {
-                        eprintln!("Warning: Symbol extraction failed for {}: {}", rel_path, e);
-                        Vec::new()
-                    });
-
-                // Extract identifiers
-                let identifiers = manager
-                    .extract_identifiers(rel_path, &content, &symbols)
-                    .unwrap_or_else(|e| {
-                        eprintln!("Warning: Identifier extraction failed for {}: {}", rel_path, e);
-                        Vec::new()
-                    });
-
-                // Extract relationships
-                let relationships = manager
-                    .extract_relationships(rel_path, &content, &symbols)
-                    .unwrap_or_else(|e| {
-                        eprintln!("Warning: Relationship extraction failed for {}: {}", rel_path, e);
-                        Vec::new()
-                    });
-
-                result.symbols = symbols;
-                result.identifiers = identifiers;
-                result.relationships = relationships;
+                if let Some(extraction_results) = file.results {
+                    result.symbols = extraction_results.symbols;
+                    result.identifiers = extraction_results.identifiers;
+                    result.relationships = extraction_results.relationships;
+                }
 
                 result
             })
@@ -469,17 +434,27 @@ pub fn extract_files_to_arrow(
     });
 
     // Build Arrow batches
-    let symbols_batch = build_symbols_batch(&collected.symbols)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to build symbols batch: {}", e)))?;
-
-    let identifiers_batch = build_identifiers_batch(&collected.identifiers)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to build identifiers batch: {}", e)))?;
-
-    let relationships_batch = build_relationships_batch(&collected.relationships)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to build relationships batch: {}", e)))?;
-
-    let files_batch = build_files_batch(&collected.files)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to build files batch: {}", e)))?;
+    let symbols_batch = build_symbols_batch(&collected.symbols).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to build symbols batch: {}", e))
+    })?;
+
+    let identifiers_batch = build_identifiers_batch(&collected.identifiers).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!(
+            "Failed to build identifiers batch: {}",
+            e
+        ))
+    })?;
+
+    let relationships_batch = build_relationships_batch(&collected.relationships).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!(
+            "Failed to build relationships batch: {}",
+            e
+        ))
+    })?;
+
+    let files_batch = build_files_batch(&collected.files).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to build files batch: {}", e))
+    })?;
 
     // Convert to PyArrow using pyo3-arrow (zero-copy FFI)
     let symbols_pyarrow = PyRecordBatch::new(symbols_batch).into_pyarrow(py)?;
@@ -524,7 +499,7 @@ mod tests {
     #[test]
     fn test_relationships_schema() {
         let schema = relationships_schema();
-        assert_eq!(schema.fields().len(), 7);  // includes confidence
+        assert_eq!(schema.fields().len(), 7); // includes confidence
         assert!(schema.field_with_name("confidence").is_ok());
     }
 
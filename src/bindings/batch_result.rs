@@ -22,6 +22,8 @@ use pyo3::prelude::*;
 /// - hash: Blake3 hash of content (empty if read failed)
 /// - results: Extraction results (None for text files or on error)
 /// - error: Error message if any step failed
+/// - error_kind: "read_error", "panicked", "timed_out", "depth_exceeded",
+///   "cancelled", ...
 #[pyclass(name = "BatchFileResult")]
 pub struct PyBatchFileResult {
     #[pyo3(get)]
@@ -45,6 +47,9 @@ pub struct PyBatchFileResult {
 
     #[pyo3(get)]
     pub error: Option<String>,
+
+    #[pyo3(get)]
+    pub error_kind: Option<String>,
 }
 
 impl PyBatchFileResult {
@@ -57,6 +62,7 @@ impl PyBatchFileResult {
         results: Option<PyExtractionResults>,
     ) -> Self {
         let size = content.len();
+        let results = results.map(|results| results.with_language(&language));
         PyBatchFileResult {
             path,
             content: Some(content),
@@ -65,11 +71,12 @@ impl PyBatchFileResult {
             size,
             results,
             error: None,
+            error_kind: None,
         }
     }
 
-    /// Create a failed result with error message
-    pub fn error(path: String, error: String) -> Self {
+    /// Create a failed result with error kind and message
+    pub fn error(path: String, kind: &str, error: String) -> Self {
         PyBatchFileResult {
             path,
             content: None,
@@ -78,6 +85,7 @@ impl PyBatchFileResult {
             size: 0,
             results: None,
             error: Some(error),
+            error_kind: Some(kind.to_string()),
         }
     }
 }
@@ -90,6 +98,12 @@ impl PyBatchFileResult {
         self.error.is_none() && self.content.is_some()
     }
 
+    /// Check if the batch was cancelled before this file was processed
+    #[getter]
+    fn cancelled(&self) -> bool {
+        self.error_kind.as_deref() == Some("cancelled")
+    }
+
     /// Check if this file has extractable symbols (not a text file)
     #[getter]
     fn has_symbols(&self) -> bool {
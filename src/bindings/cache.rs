@@ -0,0 +1,299 @@
+// Extraction Cache - in-process LRU of extraction results keyed by content hash
+//
+// Re-indexing a workspace re-extracts files whose content has not changed
+// (branch switches, touch, formatters that rewrite identical bytes). When a
+// caller opts in with use_cache=True, results are stored under the file's
+// blake3 content hash (the same hash hash_content returns), its language, its
+// path, the enrichment options, and the extractor versions (Miller's own and
+// the language's extractor_version), and later calls with the same key skip
+// parsing entirely. Bumping one language module's EXTRACTOR_VERSION therefore
+// invalidates that language's entries and no others.
+//
+// The path is part of the key because results embed it: symbol IDs and
+// file_path fields would be wrong if identical content at another path reused
+// them. The canonical workspace root is too, because import passes (Python,
+// Bash `source`, Dart, Zig, JS barrels and require) resolve workspace-relative
+// paths against it. Results that resolved a path are not cached at all: the
+// target may move or disappear without the importing file changing. Entries are evicted least-recently-used once either the entry limit or
+// the approximate byte limit is exceeded. The cache sits behind a Mutex so the
+// parallel batch path can share it; hits hand out a fresh copy, so nothing a
+// caller does to returned results reaches later hits.
+
+use crate::enrichment::{extractor_version, EnrichmentOptions};
+use julie_extractors::ExtractionResults;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+/// Version tag mixed into every cache key. Keep in sync with the
+/// julie-extractors tag in Cargo.toml; bumping either invalidates old entries.
+pub(super) const EXTRACTOR_VERSION: &str =
+    concat!("julie-extractors-1.20.0+miller-", env!("CARGO_PKG_VERSION"));
+
+const DEFAULT_MAX_ENTRIES: usize = 4096;
+const DEFAULT_MAX_BYTES: usize = 256 * 1024 * 1024;
+
+static CACHE: LazyLock<Mutex<ExtractionCache>> =
+    LazyLock::new(|| Mutex::new(ExtractionCache::new(DEFAULT_MAX_ENTRIES, DEFAULT_MAX_BYTES)));
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    hash: blake3::Hash,
+    language: String,
+    file_path: String,
+    workspace_root: PathBuf,
+    options: EnrichmentOptions,
+    version: &'static str,
+    language_version: String,
+}
+
+struct CacheEntry {
+    results: ExtractionResults,
+    size: usize,
+    tick: u64,
+}
+
+/// Bounded LRU map from cache key to extraction results
+struct ExtractionCache {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Recency index: last-use tick -> key, oldest first
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    bytes: usize,
+    max_entries: usize,
+    max_bytes: usize,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl ExtractionCache {
+    fn new(max_entries: usize, max_bytes: usize) -> Self {
+        ExtractionCache {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            bytes: 0,
+            max_entries,
+            max_bytes,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    fn get(&mut self, key: &CacheKey) -> Option<ExtractionResults> {
+        self.tick += 1;
+        let Some(entry) = self.entries.get_mut(key) else {
+            self.misses += 1;
+            return None;
+        };
+        self.recency.remove(&entry.tick);
+        entry.tick = self.tick;
+        self.recency.insert(self.tick, key.clone());
+        self.hits += 1;
+        Some(entry.results.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, results: ExtractionResults, size: usize) {
+        if self.max_entries == 0 || size > self.max_bytes {
+            return;
+        }
+        self.tick += 1;
+        if let Some(old) = self.entries.remove(&key) {
+            self.recency.remove(&old.tick);
+            self.bytes -= old.size;
+        }
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(
+            key,
+            CacheEntry {
+                results,
+                size,
+                tick: self.tick,
+            },
+        );
+        self.bytes += size;
+        self.evict();
+    }
+
+    /// Drop least-recently-used entries until both limits hold
+    fn evict(&mut self) {
+        while self.entries.len() > self.max_entries || self.bytes > self.max_bytes {
+            let Some((_, key)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&key) {
+                self.bytes -= entry.size;
+                self.evictions += 1;
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+        self.bytes = 0;
+        self.hits = 0;
+        self.misses = 0;
+        self.evictions = 0;
+    }
+}
+
+fn cache() -> MutexGuard<'static, ExtractionCache> {
+    // A panic while holding the lock leaves the map consistent (every
+    // mutation completes before anything can panic), so recover the guard
+    CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn cache_key(
+    content: &str,
+    language: &str,
+    file_path: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+) -> CacheKey {
+    CacheKey {
+        hash: blake3::hash(content.as_bytes()),
+        language: language.to_string(),
+        file_path: file_path.to_string(),
+        workspace_root: workspace_root
+            .canonicalize()
+            .unwrap_or_else(|_| workspace_root.to_path_buf()),
+        options: options.clone(),
+        version: EXTRACTOR_VERSION,
+        language_version: extractor_version(language),
+    }
+}
+
+/// Rough heap footprint of cached results, used for the byte limit
+fn approximate_size(results: &ExtractionResults) -> usize {
+    let optional = |value: &Option<String>| value.as_ref().map_or(0, String::len);
+    let symbols: usize = results
+        .symbols
+        .iter()
+        .map(|s| {
+            std::mem::size_of_val(s)
+                + s.id.len()
+                + s.name.len()
+                + s.file_path.len()
+                + optional(&s.signature)
+                + optional(&s.doc_comment)
+                + optional(&s.parent_id)
+                + optional(&s.code_context)
+                + s.metadata.as_ref().map_or(0, |m| m.len() * 64)
+        })
+        .sum();
+    let identifiers: usize = results
+        .identifiers
+        .iter()
+        .map(|i| {
+            std::mem::size_of_val(i)
+                + i.id.len()
+                + i.name.len()
+                + i.file_path.len()
+                + optional(&i.code_context)
+        })
+        .sum();
+    let relationships: usize = results
+        .relationships
+        .iter()
+        .map(|r| {
+            std::mem::size_of_val(r)
+                + r.id.len()
+                + r.from_symbol_id.len()
+                + r.to_symbol_id.len()
+                + r.metadata.as_ref().map_or(0, |m| m.len() * 64)
+        })
+        .sum();
+    symbols + identifiers + relationships
+}
+
+/// Whether any symbol points at another workspace file (metadata.resolvedPath)
+fn resolves_paths(results: &ExtractionResults) -> bool {
+    results.symbols.iter().any(|s| {
+        s.metadata
+            .as_ref()
+            .is_some_and(|m| m.contains_key("resolvedPath"))
+    })
+}
+
+/// Return cached results for this file, or run `extract` and cache what it returns
+pub(super) fn get_or_extract<E>(
+    content: &str,
+    language: &str,
+    file_path: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+    extract: impl FnOnce() -> Result<ExtractionResults, E>,
+) -> Result<ExtractionResults, E> {
+    let key = cache_key(content, language, file_path, workspace_root, options);
+    if let Some(results) = cache().get(&key) {
+        return Ok(results);
+    }
+    // Extract without holding the lock so parallel misses don't serialize
+    let results = extract()?;
+    if resolves_paths(&results) {
+        return Ok(results);
+    }
+    let size = approximate_size(&results);
+    cache().insert(key, results.clone(), size);
+    Ok(results)
+}
+
+/// Extraction cache statistics
+///
+/// Returns:
+///     dict: hits, misses, evictions, entries, bytes, max_entries, max_bytes,
+///           hit_rate (0.0-1.0), and extractor_version
+#[pyfunction]
+pub fn cache_stats(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let cache = cache();
+    let lookups = cache.hits + cache.misses;
+    let stats = PyDict::new(py);
+    stats.set_item("hits", cache.hits)?;
+    stats.set_item("misses", cache.misses)?;
+    stats.set_item("evictions", cache.evictions)?;
+    stats.set_item("entries", cache.entries.len())?;
+    stats.set_item("bytes", cache.bytes)?;
+    stats.set_item("max_entries", cache.max_entries)?;
+    stats.set_item("max_bytes", cache.max_bytes)?;
+    stats.set_item(
+        "hit_rate",
+        if lookups == 0 {
+            0.0
+        } else {
+            cache.hits as f64 / lookups as f64
+        },
+    )?;
+    stats.set_item("extractor_version", EXTRACTOR_VERSION)?;
+    Ok(stats)
+}
+
+/// Drop every cached extraction result and reset the statistics
+#[pyfunction]
+pub fn clear_cache() {
+    cache().clear();
+}
+
+/// Change the extraction cache limits, evicting entries that no longer fit
+///
+/// Args:
+///     max_entries (int, optional): Maximum number of cached files (0 disables caching)
+///     max_bytes (int, optional): Maximum approximate size of cached results in bytes
+#[pyfunction]
+#[pyo3(signature = (max_entries = None, max_bytes = None))]
+pub fn configure_cache(max_entries: Option<usize>, max_bytes: Option<usize>) {
+    let mut cache = cache();
+    if let Some(max_entries) = max_entries {
+        cache.max_entries = max_entries;
+    }
+    if let Some(max_bytes) = max_bytes {
+        cache.max_bytes = max_bytes;
+    }
+    cache.evict();
+}
@@ -0,0 +1,267 @@
+// Cross-language correlation bindings - run the full-stack linkers over a
+// workspace's extraction results
+//
+// Python passes every file's ExtractionResults in one call; the symbols and
+// identifiers are pooled so links can cross files and languages. See
+// utils::cross_language_intelligence for the matching rules. Schema
+// correlation also writes semantic_group back onto the symbols it matched.
+// Cross-file import resolution is the same-language counterpart: it adds
+// relationships from imports to the symbols they name in other files.
+
+use super::PyExtractionResults;
+use crate::engine;
+use crate::utils::cross_language_intelligence::{self, ColumnLink, EndpointLink, SchemaLink};
+use julie_extractors::ExtractionResults;
+use pyo3::prelude::*;
+
+/// A frontend HTTP call linked to the backend handler it most likely reaches
+#[pyclass(name = "EndpointLink")]
+pub struct PyEndpointLink {
+    #[pyo3(get)]
+    pub client_symbol_id: String,
+
+    #[pyo3(get)]
+    pub client_file_path: String,
+
+    #[pyo3(get)]
+    pub call_identifier_id: Option<String>,
+
+    #[pyo3(get)]
+    pub line: u32,
+
+    #[pyo3(get)]
+    pub http_method: String,
+
+    #[pyo3(get)]
+    pub url: String,
+
+    #[pyo3(get)]
+    pub server_symbol_id: String,
+
+    #[pyo3(get)]
+    pub server_file_path: String,
+
+    #[pyo3(get)]
+    pub route: String,
+
+    #[pyo3(get)]
+    pub confidence: f32,
+
+    /// Always "heuristic": URLs are matched against route templates
+    #[pyo3(get)]
+    pub provenance: &'static str,
+}
+
+impl From<EndpointLink> for PyEndpointLink {
+    fn from(link: EndpointLink) -> Self {
+        PyEndpointLink {
+            client_symbol_id: link.client_symbol_id,
+            client_file_path: link.client_file_path,
+            call_identifier_id: link.call_identifier_id,
+            line: link.line,
+            http_method: link.http_method,
+            url: link.url,
+            server_symbol_id: link.server_symbol_id,
+            server_file_path: link.server_file_path,
+            route: link.route,
+            confidence: link.confidence,
+            provenance: link.provenance.as_str(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyEndpointLink {
+    fn __repr__(&self) -> String {
+        format!(
+            "EndpointLink({} {} -> {} in {}, confidence={:.2})",
+            self.http_method, self.url, self.route, self.server_file_path, self.confidence
+        )
+    }
+}
+
+/// Match client HTTP calls against server route handlers across files
+///
+/// Args:
+///     results (list[ExtractionResults]): Extraction results for every file
+///         to consider, typically a whole workspace
+///
+/// Returns:
+///     list[EndpointLink]: Links ordered by client file and line, best match
+///         first; a call may link to several handlers
+#[pyfunction]
+pub fn correlate_endpoints(
+    py: Python<'_>,
+    results: Vec<PyRef<'_, PyExtractionResults>>,
+) -> Vec<PyEndpointLink> {
+    let mut symbols = Vec::new();
+    let mut identifiers = Vec::new();
+    for file in &results {
+        symbols.extend_from_slice(&file.inner().symbols);
+        identifiers.extend_from_slice(&file.inner().identifiers);
+    }
+    py.detach(|| cross_language_intelligence::correlate_endpoints(&symbols, &identifiers))
+        .into_iter()
+        .map(PyEndpointLink::from)
+        .collect()
+}
+
+/// An ORM model field mapped to a SQL column
+#[pyclass(name = "ColumnLink")]
+#[derive(Clone)]
+pub struct PyColumnLink {
+    #[pyo3(get)]
+    pub column_symbol_id: String,
+
+    #[pyo3(get)]
+    pub column: String,
+
+    #[pyo3(get)]
+    pub field_symbol_id: String,
+
+    #[pyo3(get)]
+    pub field: String,
+
+    #[pyo3(get)]
+    pub confidence: f32,
+
+    /// "ast" when the field and its model name their column and table, else "name-match"
+    #[pyo3(get)]
+    pub provenance: &'static str,
+}
+
+impl From<ColumnLink> for PyColumnLink {
+    fn from(link: ColumnLink) -> Self {
+        PyColumnLink {
+            column_symbol_id: link.column_symbol_id,
+            column: link.column,
+            field_symbol_id: link.field_symbol_id,
+            field: link.field,
+            confidence: link.confidence,
+            provenance: link.provenance.as_str(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyColumnLink {
+    fn __repr__(&self) -> String {
+        format!(
+            "ColumnLink({} -> {}, confidence={:.2})",
+            self.column, self.field, self.confidence
+        )
+    }
+}
+
+/// An ORM model mapped to a SQL table, with its column mappings
+#[pyclass(name = "SchemaLink")]
+pub struct PySchemaLink {
+    #[pyo3(get)]
+    pub table_symbol_id: String,
+
+    #[pyo3(get)]
+    pub table: String,
+
+    #[pyo3(get)]
+    pub model_symbol_id: String,
+
+    #[pyo3(get)]
+    pub model: String,
+
+    #[pyo3(get)]
+    pub model_language: String,
+
+    #[pyo3(get)]
+    pub confidence: f32,
+
+    /// "ast" when the model names its table, else "name-match"
+    #[pyo3(get)]
+    pub provenance: &'static str,
+
+    #[pyo3(get)]
+    pub columns: Vec<PyColumnLink>,
+}
+
+impl From<SchemaLink> for PySchemaLink {
+    fn from(link: SchemaLink) -> Self {
+        PySchemaLink {
+            table_symbol_id: link.table_symbol_id,
+            table: link.table,
+            model_symbol_id: link.model_symbol_id,
+            model: link.model,
+            model_language: link.model_language,
+            confidence: link.confidence,
+            provenance: link.provenance.as_str(),
+            columns: link.columns.into_iter().map(PyColumnLink::from).collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl PySchemaLink {
+    fn __repr__(&self) -> String {
+        format!(
+            "SchemaLink({} -> {} ({}), columns={}, confidence={:.2})",
+            self.table,
+            self.model,
+            self.model_language,
+            self.columns.len(),
+            self.confidence
+        )
+    }
+}
+
+/// Match SQL tables and columns with the ORM models and fields that map them
+///
+/// Matched symbols get semantic_group "table:<table>" (tables and models) or
+/// "column:<table>.<column>" (columns and fields), set on the given results.
+///
+/// Args:
+///     results (list[ExtractionResults]): Extraction results for every file
+///         to consider, typically a whole workspace
+///
+/// Returns:
+///     list[SchemaLink]: Links ordered by table, then model language
+#[pyfunction]
+pub fn correlate_schema(
+    py: Python<'_>,
+    mut results: Vec<PyRefMut<'_, PyExtractionResults>>,
+) -> Vec<PySchemaLink> {
+    let mut symbols = Vec::new();
+    for file in &results {
+        symbols.extend_from_slice(&file.inner().symbols);
+    }
+    let links = py.detach(|| cross_language_intelligence::correlate_schema(&mut symbols));
+
+    let mut groups = symbols.into_iter().map(|s| s.semantic_group);
+    for file in &mut results {
+        for symbol in &mut file.inner_mut().symbols {
+            symbol.semantic_group = groups.next().flatten();
+        }
+    }
+    links.into_iter().map(PySchemaLink::from).collect()
+}
+
+/// Link imports to the symbols they name in other files of the batch
+///
+/// Imports whose metadata records the workspace file they resolve to
+/// (resolvedPath) get an Imports relationship to that file's top-level symbol
+/// of the imported name, added to the importing file's results. Run it after
+/// extract_files_batch with every file's results; unresolved imports are
+/// left untouched and calling it again adds nothing.
+///
+/// Args:
+///     results (list[ExtractionResults]): Extraction results for every file
+///         to consider, typically a whole workspace
+///
+/// Returns:
+///     int: Number of relationships added
+#[pyfunction]
+pub fn resolve_cross_file(
+    py: Python<'_>,
+    mut results: Vec<PyRefMut<'_, PyExtractionResults>>,
+) -> usize {
+    let mut files: Vec<&mut ExtractionResults> =
+        results.iter_mut().map(|file| file.inner_mut()).collect();
+    py.detach(|| engine::resolve_cross_file(&mut files))
+}
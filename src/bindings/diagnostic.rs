@@ -0,0 +1,56 @@
+// PyDiagnostic - PyO3 wrapper for Miller's extraction diagnostics
+//
+// Reports parts of a file's results that came from error recovery or text
+// fallbacks rather than a clean parse.
+
+use crate::enrichment::Diagnostic;
+use pyo3::prelude::*;
+
+/// Python-accessible Diagnostic wrapper
+///
+/// Describes one region of a file whose symbols are best-effort
+#[pyclass(name = "Diagnostic")]
+pub struct PyDiagnostic {
+    inner: Diagnostic,
+}
+
+impl PyDiagnostic {
+    pub fn from_diagnostic(diagnostic: Diagnostic) -> Self {
+        PyDiagnostic { inner: diagnostic }
+    }
+}
+
+#[pymethods]
+impl PyDiagnostic {
+    /// "recovered_declaration", "text_fallback", "package_mismatch", or
+    /// "ambiguous_pairing"
+    #[getter]
+    fn kind(&self) -> &'static str {
+        self.inner.kind.as_str()
+    }
+
+    #[getter]
+    fn message(&self) -> String {
+        self.inner.message.clone()
+    }
+
+    #[getter]
+    fn start_line(&self) -> u32 {
+        self.inner.start_line
+    }
+
+    #[getter]
+    fn end_line(&self) -> u32 {
+        self.inner.end_line
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Diagnostic(kind='{}', start_line={}, end_line={}, message='{}')",
+            self.inner.kind.as_str(),
+            self.inner.start_line,
+            self.inner.end_line,
+            self.inner.message
+        )
+    }
+}
@@ -0,0 +1,106 @@
+// Python exception classes for extraction failures
+//
+// Every failure of a single file's extraction raises a subclass of
+// miller_core.ExtractionError, chosen from the engine's ExtractionError, with
+// the file and language attached so callers can branch without parsing
+// messages:
+//
+//   UnsupportedLanguageError   no extractor for the file's extension
+//   FileReadError              the file could not be read
+//   ParseInitError             the extractor could not set up its parser
+//   PathSecurityError          the path resolves outside the workspace root
+//   ExtractionPanicError       an extractor panicked (caught, not propagated)
+//
+// Timeouts, over-deep nesting, and identifier/relationship failures raise
+// ExtractionError itself. Every instance carries `file_path`, `language`
+// (None when unknown), and `kind`, the engine's stable error kind.
+
+use crate::engine;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+create_exception!(
+    miller_core,
+    ExtractionError,
+    PyException,
+    "A file could not be extracted; carries file_path, language, and kind."
+);
+create_exception!(
+    miller_core,
+    UnsupportedLanguageError,
+    ExtractionError,
+    "No extractor handles the file's extension."
+);
+create_exception!(
+    miller_core,
+    FileReadError,
+    ExtractionError,
+    "The file could not be read."
+);
+create_exception!(
+    miller_core,
+    ParseInitError,
+    ExtractionError,
+    "The extractor could not initialize its parser."
+);
+create_exception!(
+    miller_core,
+    PathSecurityError,
+    ExtractionError,
+    "The path resolves outside the workspace root."
+);
+create_exception!(
+    miller_core,
+    ExtractionPanicError,
+    ExtractionError,
+    "An extractor panicked while processing the file."
+);
+
+/// Register the exception classes on the module
+pub fn register_exceptions(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("ExtractionError", py.get_type::<ExtractionError>())?;
+    m.add(
+        "UnsupportedLanguageError",
+        py.get_type::<UnsupportedLanguageError>(),
+    )?;
+    m.add("FileReadError", py.get_type::<FileReadError>())?;
+    m.add("ParseInitError", py.get_type::<ParseInitError>())?;
+    m.add("PathSecurityError", py.get_type::<PathSecurityError>())?;
+    m.add(
+        "ExtractionPanicError",
+        py.get_type::<ExtractionPanicError>(),
+    )?;
+    Ok(())
+}
+
+/// The exception for `error`, with file_path, language, and kind attached
+///
+/// Callable with or without the GIL held (extraction often runs detached).
+pub(super) fn extraction_error(
+    error: &engine::ExtractionError,
+    file_path: &str,
+    language: Option<&str>,
+) -> PyErr {
+    let message = format!("{}: {}", file_path, error);
+    let err = match error {
+        engine::ExtractionError::UnsupportedLanguage(_) => {
+            UnsupportedLanguageError::new_err(message)
+        }
+        engine::ExtractionError::Read(_) => FileReadError::new_err(message),
+        // Julie's symbol pass only fails before parsing: no extractor or no parser
+        engine::ExtractionError::Symbols(_) => ParseInitError::new_err(message),
+        engine::ExtractionError::PathRejected(_) => PathSecurityError::new_err(message),
+        engine::ExtractionError::Panicked(_) => ExtractionPanicError::new_err(message),
+        _ => ExtractionError::new_err(message),
+    };
+    Python::attach(|py| {
+        let value = err.value(py);
+        // Setting attributes on a fresh exception instance cannot fail
+        let _ = value.setattr("file_path", file_path);
+        let _ = value.setattr("language", language);
+        let _ = value.setattr("kind", error.kind());
+    });
+    err
+}
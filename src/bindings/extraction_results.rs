@@ -1,10 +1,37 @@
 // PyExtractionResults - PyO3 wrapper for Julie's ExtractionResults
 //
-// Container for all extracted symbols, identifiers, and relationships
+// Container for all extracted symbols, identifiers, and relationships, plus
+// diagnostics describing how complete they are. Batch extraction returns an
+// empty container carrying the error when a file's extraction panicked, timed
+// out, or was refused for nesting too deeply. Results also carry the
+// extractor version of their language, so callers caching them can tell when
+// an extractor change makes a cached copy stale.
+//
+// The symbols, identifiers, and relationships lists are built once, on first
+// access, from Arc-shared copies of the items; every later access and every
+// Python wrapper shares them instead of copying each item again.
 
-use super::{PyIdentifier, PyRelationship, PySymbol};
-use julie_extractors::ExtractionResults;
+use super::{PyDiagnostic, PyIdentifier, PyRelationship, PySymbol};
+use crate::engine::{
+    diagnose, extractor_version, ExtractionError, ExtractionResults, Identifier, Relationship,
+    Symbol,
+};
 use pyo3::prelude::*;
+use std::sync::{Arc, OnceLock};
+
+/// Items shared with the Python wrappers, built on first access
+#[derive(Default)]
+struct SharedItems {
+    symbols: OnceLock<Vec<Arc<Symbol>>>,
+    identifiers: OnceLock<Vec<Arc<Identifier>>>,
+    relationships: OnceLock<Vec<Arc<Relationship>>>,
+}
+
+/// `items` as Arcs, shared from the first call on
+fn shared<T: Clone>(cell: &OnceLock<Vec<Arc<T>>>, items: &[T]) -> Vec<Arc<T>> {
+    cell.get_or_init(|| items.iter().cloned().map(Arc::new).collect())
+        .clone()
+}
 
 /// Python-accessible ExtractionResults wrapper
 ///
@@ -12,11 +39,63 @@ use pyo3::prelude::*;
 #[pyclass(name = "ExtractionResults")]
 pub struct PyExtractionResults {
     inner: ExtractionResults,
+    /// (kind, message) when extraction failed and the results are empty
+    error: Option<(&'static str, String)>,
+    /// `engine::extractor_version` of the file's language, when known
+    extractor_version: Option<String>,
+    shared: SharedItems,
 }
 
 impl PyExtractionResults {
     pub fn from_extraction_results(results: ExtractionResults) -> Self {
-        PyExtractionResults { inner: results }
+        PyExtractionResults {
+            inner: results,
+            error: None,
+            extractor_version: None,
+            shared: SharedItems::default(),
+        }
+    }
+
+    /// Record the language whose extractor produced the results
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.extractor_version = Some(extractor_version(language));
+        self
+    }
+
+    /// Other results for the same file, keeping its extractor version
+    pub fn with_results(&self, results: ExtractionResults) -> Self {
+        PyExtractionResults {
+            inner: results,
+            error: None,
+            extractor_version: self.extractor_version.clone(),
+            shared: SharedItems::default(),
+        }
+    }
+
+    /// Empty results recording why extraction failed
+    pub fn from_error(error: &ExtractionError) -> Self {
+        PyExtractionResults {
+            inner: ExtractionResults {
+                symbols: Vec::new(),
+                identifiers: Vec::new(),
+                relationships: Vec::new(),
+                pending_relationships: Vec::new(),
+                types: std::collections::HashMap::new(),
+            },
+            error: Some((error.kind(), error.to_string())),
+            extractor_version: None,
+            shared: SharedItems::default(),
+        }
+    }
+
+    pub fn inner(&self) -> &ExtractionResults {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut ExtractionResults {
+        // The shared lists would go stale
+        self.shared = SharedItems::default();
+        &mut self.inner
     }
 }
 
@@ -24,31 +103,69 @@ impl PyExtractionResults {
 impl PyExtractionResults {
     #[getter]
     fn symbols(&self) -> Vec<PySymbol> {
-        self.inner
-            .symbols
-            .iter()
-            .map(|s| PySymbol::from_symbol(s.clone()))
+        shared(&self.shared.symbols, &self.inner.symbols)
+            .into_iter()
+            .map(PySymbol::from_shared)
             .collect()
     }
 
     #[getter]
     fn identifiers(&self) -> Vec<PyIdentifier> {
-        self.inner
-            .identifiers
-            .iter()
-            .map(|i| PyIdentifier::from_identifier(i.clone()))
+        shared(&self.shared.identifiers, &self.inner.identifiers)
+            .into_iter()
+            .map(PyIdentifier::from_shared)
             .collect()
     }
 
     #[getter]
     fn relationships(&self) -> Vec<PyRelationship> {
-        self.inner
-            .relationships
-            .iter()
-            .map(|r| PyRelationship::from_relationship(r.clone()))
+        shared(&self.shared.relationships, &self.inner.relationships)
+            .into_iter()
+            .map(PyRelationship::from_shared)
+            .collect()
+    }
+
+    /// Best-effort regions of the results (error recovery, text fallbacks)
+    #[getter]
+    fn diagnostics(&self) -> Vec<PyDiagnostic> {
+        diagnose(&self.inner)
+            .into_iter()
+            .map(PyDiagnostic::from_diagnostic)
             .collect()
     }
 
+    /// True when every symbol came from a clean parse (no diagnostics, no error)
+    #[getter]
+    fn is_complete(&self) -> bool {
+        self.error.is_none() && diagnose(&self.inner).is_empty()
+    }
+
+    /// Version tag of the extractor that produced these results (see
+    /// extractor_versions()); None when the language is unknown
+    #[getter]
+    fn extractor_version(&self) -> Option<String> {
+        self.extractor_version.clone()
+    }
+
+    /// Why extraction failed, or None
+    #[getter]
+    fn error(&self) -> Option<String> {
+        self.error.as_ref().map(|(_, message)| message.clone())
+    }
+
+    /// "panicked", "timed_out", "depth_exceeded", "cancelled", or
+    /// "extraction_error"; None on success
+    #[getter]
+    fn error_kind(&self) -> Option<&'static str> {
+        self.error.as_ref().map(|(kind, _)| *kind)
+    }
+
+    /// True when the batch was cancelled before this file was extracted
+    #[getter]
+    fn cancelled(&self) -> bool {
+        self.error_kind() == Some("cancelled")
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "ExtractionResults(symbols={}, identifiers={}, relationships={})",
@@ -1,20 +1,29 @@
 // PyIdentifier - PyO3 wrapper for Julie's Identifier type
 //
 // Represents a usage reference (function call, variable reference, etc.)
+// Shares the Rust Identifier through an Arc; fields convert on access (see
+// PySymbol).
 
 use julie_extractors::Identifier;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::sync::Arc;
 
 /// Python-accessible Identifier wrapper
 ///
 /// Represents an identifier usage (call, variable reference, type usage, etc.)
 #[pyclass(name = "Identifier")]
 pub struct PyIdentifier {
-    inner: Identifier,
+    inner: Arc<Identifier>,
 }
 
 impl PyIdentifier {
     pub fn from_identifier(identifier: Identifier) -> Self {
+        PyIdentifier::from_shared(Arc::new(identifier))
+    }
+
+    /// Wrap an identifier shared with its ExtractionResults, without copying it
+    pub fn from_shared(identifier: Arc<Identifier>) -> Self {
         PyIdentifier { inner: identifier }
     }
 }
@@ -49,7 +58,7 @@ impl PyIdentifier {
 
     #[setter]
     fn set_file_path(&mut self, value: String) {
-        self.inner.file_path = value;
+        Arc::make_mut(&mut self.inner).file_path = value;
     }
 
     #[getter]
@@ -92,6 +101,16 @@ impl PyIdentifier {
         self.inner.target_symbol_id.clone()
     }
 
+    /// Same-file definition this usage resolves to
+    ///
+    /// Julie's target when it set one, otherwise the single best match from
+    /// Miller's same-file resolution; None when unresolved or ambiguous (see the
+    /// containing symbol's metadata["ambiguousReferences"]).
+    #[getter]
+    fn resolved_symbol_id(&self) -> Option<String> {
+        self.inner.target_symbol_id.clone()
+    }
+
     #[getter]
     fn confidence(&self) -> f32 {
         self.inner.confidence
@@ -102,6 +121,29 @@ impl PyIdentifier {
         self.inner.code_context.clone()
     }
 
+    /// Every field as a dict
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let identifier = &*self.inner;
+        let dict = PyDict::new(py);
+        dict.set_item("id", &identifier.id)?;
+        dict.set_item("name", &identifier.name)?;
+        dict.set_item("kind", identifier.kind.to_string())?;
+        dict.set_item("language", &identifier.language)?;
+        dict.set_item("file_path", &identifier.file_path)?;
+        dict.set_item("start_line", identifier.start_line)?;
+        dict.set_item("start_column", identifier.start_column)?;
+        dict.set_item("end_line", identifier.end_line)?;
+        dict.set_item("end_column", identifier.end_column)?;
+        dict.set_item("start_byte", identifier.start_byte)?;
+        dict.set_item("end_byte", identifier.end_byte)?;
+        dict.set_item("containing_symbol_id", &identifier.containing_symbol_id)?;
+        dict.set_item("target_symbol_id", &identifier.target_symbol_id)?;
+        dict.set_item("resolved_symbol_id", &identifier.target_symbol_id)?;
+        dict.set_item("confidence", identifier.confidence)?;
+        dict.set_item("code_context", &identifier.code_context)?;
+        Ok(dict)
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "Identifier(name='{}', kind='{}', file_path='{}', line={})",
@@ -0,0 +1,90 @@
+// JSON export bindings - write extraction results to disk without Python objects
+//
+// Bulk indexing pipelines can dump results as JSON (one file) or NDJSON (one
+// object per file, streamed by Rust) and process them offline. The record
+// format is defined in crate::export.
+
+use super::api::known_language;
+use super::errors::extraction_error;
+use crate::engine::{EnrichmentOptions, ExtractionLimits};
+use crate::export;
+use pyo3::exceptions::{PyOSError, PyValueError};
+use pyo3::prelude::*;
+use std::path::Path;
+
+/// Extract one file and return its results as a JSON string
+///
+/// Field names are camelCase (filePath, startLine, parentId); metadata values
+/// are passed through as written by the extractors.
+///
+/// Args:
+///     path (str): File path relative to workspace_root
+///     workspace_root (str): Workspace root directory (default ".")
+///
+/// Returns:
+///     str: JSON object with filePath, language, hash, symbols, identifiers,
+///          relationships, and diagnostics
+///
+/// Raises:
+///     FileReadError: If the file cannot be read
+///     PathSecurityError: If the path resolves outside workspace_root
+///     ExtractionPanicError: If the extractor panics
+///     ExtractionError: If extraction times out or the file nests too deeply
+#[pyfunction]
+#[pyo3(signature = (path, workspace_root = "."))]
+pub fn extract_file_to_json(py: Python<'_>, path: &str, workspace_root: &str) -> PyResult<String> {
+    let workspace_root = Path::new(workspace_root);
+    let options = EnrichmentOptions::default();
+
+    let file = py
+        .detach(|| {
+            crate::engine::extract_path(
+                path,
+                workspace_root,
+                &options,
+                &ExtractionLimits::default(),
+            )
+        })
+        .map_err(|e| extraction_error(&e, path, known_language(path, None)))?;
+    export::file_to_json(&file)
+        .map_err(|e| PyValueError::new_err(format!("Serialization failed: {}", e)))
+}
+
+/// Extract files in parallel and write one JSON object per line to output_path
+///
+/// Lines follow the order of paths and use the format of extract_file_to_json.
+/// Files that cannot be read or extracted are written as
+/// {"filePath": ..., "error": ..., "errorKind": ...} instead of failing the export.
+///
+/// Args:
+///     paths (list[str]): File paths relative to workspace_root
+///     output_path (str): NDJSON file to create (overwritten if it exists)
+///     workspace_root (str): Workspace root directory (default ".")
+///
+/// Returns:
+///     tuple[int, int]: (files written, files written with an error)
+///
+/// Raises:
+///     OSError: If the output file cannot be written
+#[pyfunction]
+#[pyo3(signature = (paths, output_path, workspace_root = "."))]
+pub fn extract_files_to_ndjson(
+    py: Python<'_>,
+    paths: Vec<String>,
+    output_path: &str,
+    workspace_root: &str,
+) -> PyResult<(usize, usize)> {
+    let options = EnrichmentOptions::default();
+
+    let summary = py
+        .detach(|| {
+            export::extract_files_to_ndjson(
+                &paths,
+                Path::new(workspace_root),
+                Path::new(output_path),
+                &options,
+            )
+        })
+        .map_err(|e| PyOSError::new_err(format!("{}: {}", output_path, e)))?;
+    Ok((summary.files_written, summary.files_failed))
+}
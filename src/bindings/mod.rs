@@ -6,19 +6,42 @@
 mod api;
 mod arrow_extraction;
 mod batch_result;
+mod cache;
+mod correlation;
+mod diagnostic;
+mod errors;
 mod extraction_results;
+mod hashing;
 mod identifier;
+mod json_export;
+mod options;
+mod progress;
+mod ranking;
+mod reduction;
 mod relationship;
 mod symbol;
+mod workspace;
 
 // Re-export for lib.rs
 pub use api::{
     detect_language, extract_file, extract_files_batch, extract_files_batch_with_io,
-    hash_content, hash_contents_batch, supported_languages,
+    extractor_versions, supported_languages,
 };
 pub use arrow_extraction::{extract_files_to_arrow, PyArrowExtractionBatch};
 pub use batch_result::PyBatchFileResult;
+pub use cache::{cache_stats, clear_cache, configure_cache};
+pub use correlation::{
+    correlate_endpoints, correlate_schema, resolve_cross_file, PyColumnLink, PyEndpointLink,
+    PySchemaLink,
+};
+pub use diagnostic::PyDiagnostic;
+pub use errors::register_exceptions;
 pub use extraction_results::PyExtractionResults;
+pub use hashing::{hash_content, hash_contents_batch, hash_files_batch};
 pub use identifier::PyIdentifier;
+pub use json_export::{extract_file_to_json, extract_files_to_ndjson};
+pub use ranking::rank_symbols;
+pub use reduction::{reduce_results, PyReductionReport};
 pub use relationship::PyRelationship;
 pub use symbol::PySymbol;
+pub use workspace::{extract_workspace, is_ignored};
@@ -0,0 +1,101 @@
+// Option parsing - keyword arguments of the extraction functions as engine options
+//
+// Python passes symbol kinds, SQL dialects, and declaration macros as strings
+// and tuples; invalid values are raised as ValueError before any file is read.
+
+use crate::engine::{
+    CodeContextOptions, DeclarationMacro, EnrichmentOptions, ExtractionLimits, MacroArgument,
+    SymbolKindFilter,
+};
+use julie_extractors::SymbolKind;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::time::Duration;
+
+/// Build per-file limits from the `timeout` keyword (seconds; None disables the timeout)
+pub(super) fn extraction_limits(timeout: Option<f64>) -> PyResult<ExtractionLimits> {
+    let timeout = timeout
+        .map(|seconds| {
+            Duration::try_from_secs_f64(seconds)
+                .map_err(|e| PyValueError::new_err(format!("Invalid timeout {}: {}", seconds, e)))
+        })
+        .transpose()?;
+    Ok(ExtractionLimits {
+        timeout,
+        ..ExtractionLimits::default()
+    })
+}
+
+/// Build enrichment options from the optional keyword arguments of the extraction functions
+///
+/// `code_context_lines` is (before, after), or None when code context is off.
+pub(super) fn enrichment_options(
+    sql_dialect: Option<&str>,
+    regex_ast_only: bool,
+    code_context_lines: Option<(usize, usize)>,
+    symbol_kinds: Option<Vec<String>>,
+    include_children: bool,
+    declaration_macros: Option<Vec<DeclarationMacroSpec>>,
+) -> PyResult<EnrichmentOptions> {
+    Ok(EnrichmentOptions {
+        sql_dialect: sql_dialect
+            .map(|dialect| dialect.parse().map_err(PyValueError::new_err))
+            .transpose()?,
+        regex_ast_only,
+        sql_doc_blank_lines: 0,
+        raw_html_templates: false,
+        code_context: code_context_lines.map(|(lines_before, lines_after)| CodeContextOptions {
+            lines_before,
+            lines_after,
+        }),
+        symbol_kinds: symbol_kinds
+            .map(|kinds| {
+                Ok::<_, PyErr>(SymbolKindFilter {
+                    kinds: kinds
+                        .iter()
+                        .map(|kind| symbol_kind(kind))
+                        .collect::<PyResult<_>>()?,
+                    include_children,
+                })
+            })
+            .transpose()?,
+        c_declaration_macros: declaration_macros
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(pattern, kind, argument)| {
+                Ok(DeclarationMacro::new(
+                    &pattern,
+                    symbol_kind(&kind)?,
+                    match argument {
+                        MacroArgumentSpec::Position(position) => MacroArgument::Position(position),
+                        MacroArgumentSpec::Parameter(name) => MacroArgument::Parameter(name),
+                    },
+                ))
+            })
+            .collect::<PyResult<_>>()?,
+    })
+}
+
+/// `(macro pattern, symbol kind, name argument)` as Python passes it
+pub type DeclarationMacroSpec = (String, String, MacroArgumentSpec);
+
+/// A declaration macro's name argument: a position or a parameter name
+#[derive(FromPyObject)]
+pub enum MacroArgumentSpec {
+    Position(usize),
+    Parameter(String),
+}
+
+/// Parse a symbol kind as the bindings spell it ("function", "enum_member")
+fn symbol_kind(name: &str) -> PyResult<SymbolKind> {
+    let kind = SymbolKind::from_string(name);
+    // from_string falls back to a default kind for names it does not know
+    if kind.to_string().eq_ignore_ascii_case(name) {
+        Ok(kind)
+    } else {
+        Err(PyValueError::new_err(format!(
+            "Unknown symbol kind '{}'",
+            name
+        )))
+    }
+}
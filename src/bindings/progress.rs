@@ -0,0 +1,60 @@
+// Progress reporting - run batches with the GIL released while calling back into Python
+//
+// The batch runs on Rayon's pool; the coordinating thread takes the GIL only
+// to call the progress callback, whose answer can cancel the files not
+// started yet.
+
+use crate::engine::{self, BatchProgress};
+use pyo3::prelude::*;
+
+/// `engine::run_batch` with the GIL released, reporting progress to `callback`
+///
+/// Files not started after a cancel are None.
+pub(super) fn run_batch_detached<T, R>(
+    py: Python<'_>,
+    items: &[T],
+    path: impl Fn(&T) -> &str + Sync,
+    work: impl Fn(&T) -> R + Sync,
+    callback: Option<&Py<PyAny>>,
+    every: usize,
+) -> Vec<Option<R>>
+where
+    T: Sync,
+    R: Send,
+{
+    detach_with_progress(py, callback, every, |progress| {
+        engine::run_batch(items, &path, &work, progress).0
+    })
+}
+
+/// Run `batch` with the GIL released, handing it a `BatchProgress` that
+/// reports to `callback` (None without one)
+///
+/// The GIL is taken only for each callback call, on the thread coordinating
+/// the batch. Only an explicit False or an exception cancels.
+pub(super) fn detach_with_progress<R: Send>(
+    py: Python<'_>,
+    callback: Option<&Py<PyAny>>,
+    every: usize,
+    batch: impl FnOnce(Option<BatchProgress<'_>>) -> R + Send,
+) -> R {
+    py.detach(|| {
+        let Some(callback) = callback else {
+            return batch(None);
+        };
+        let mut report = |done: usize, total: usize, current: &str| {
+            Python::attach(|py| match callback.call1(py, (done, total, current)) {
+                // Only an explicit False cancels; None and anything else continue
+                Ok(value) => !matches!(value.bind(py).extract::<bool>(), Ok(false)),
+                Err(err) => {
+                    err.write_unraisable(py, Some(callback.bind(py)));
+                    false
+                }
+            })
+        };
+        batch(Some(BatchProgress {
+            every,
+            report: &mut report,
+        }))
+    })
+}
@@ -0,0 +1,58 @@
+// Symbol ranking bindings - rank extracted symbols against a search query
+//
+// See utils::symbol_ranking for the scoring. Python passes either one file's
+// ExtractionResults, a list of them, or a list of Symbols.
+
+use super::{PyExtractionResults, PySymbol};
+use crate::utils::symbol_ranking;
+use julie_extractors::Symbol;
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+
+/// Rank symbols by how well they match a search query
+///
+/// Name matches (exact, prefix, word-boundary, and fuzzy, over the query's
+/// camelCase/snake_case variants) are weighted by path relevance (tests and
+/// generated files lower) and by kind (types and functions above variables).
+///
+/// Args:
+///     query (str): Search text, e.g. "user service" or "getUserData"
+///     symbols (ExtractionResults | list[ExtractionResults] | list[Symbol]):
+///         Symbols to rank
+///     limit (int): Most results to return (default 20)
+///
+/// Returns:
+///     list[tuple[str, float]]: (symbol id, score) pairs, best first; symbols
+///         whose names do not match are left out
+///
+/// Raises:
+///     TypeError: If symbols is none of the accepted types
+#[pyfunction]
+#[pyo3(signature = (query, symbols, limit = 20))]
+pub fn rank_symbols(
+    py: Python<'_>,
+    query: &str,
+    symbols: &Bound<'_, PyAny>,
+    limit: usize,
+) -> PyResult<Vec<(String, f32)>> {
+    let symbols = collect_symbols(symbols)?;
+    Ok(py.detach(|| symbol_ranking::rank_symbols(query, &symbols, limit)))
+}
+
+fn collect_symbols(value: &Bound<'_, PyAny>) -> PyResult<Vec<Symbol>> {
+    if let Ok(results) = value.extract::<PyRef<'_, PyExtractionResults>>() {
+        return Ok(results.inner().symbols.clone());
+    }
+    if let Ok(results) = value.extract::<Vec<PyRef<'_, PyExtractionResults>>>() {
+        return Ok(results
+            .iter()
+            .flat_map(|r| r.inner().symbols.iter().cloned())
+            .collect());
+    }
+    if let Ok(symbols) = value.extract::<Vec<PyRef<'_, PySymbol>>>() {
+        return Ok(symbols.iter().map(|s| s.inner().clone()).collect());
+    }
+    Err(PyTypeError::new_err(
+        "symbols must be ExtractionResults, a list of ExtractionResults, or a list of Symbol",
+    ))
+}
@@ -0,0 +1,101 @@
+// Result reduction bindings - fit extraction results into a token budget
+//
+// See utils::result_reduction for what is dropped and in which order. The
+// caller's ExtractionResults is left untouched; a reduced copy is returned
+// with a report of what was dropped.
+
+use super::PyExtractionResults;
+use crate::utils::result_reduction::{self, ReductionReport, ReductionStrategy};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Counts of what reduce_results dropped to reach the budget
+#[pyclass(name = "ReductionReport")]
+pub struct PyReductionReport {
+    #[pyo3(get)]
+    pub original_tokens: usize,
+
+    #[pyo3(get)]
+    pub estimated_tokens: usize,
+
+    #[pyo3(get)]
+    pub code_contexts: usize,
+
+    #[pyo3(get)]
+    pub doc_comments: usize,
+
+    #[pyo3(get)]
+    pub identifiers: usize,
+
+    #[pyo3(get)]
+    pub symbols: usize,
+
+    #[pyo3(get)]
+    pub relationships: usize,
+
+    #[pyo3(get)]
+    pub truncated: bool,
+}
+
+impl From<ReductionReport> for PyReductionReport {
+    fn from(report: ReductionReport) -> Self {
+        PyReductionReport {
+            truncated: report.truncated(),
+            original_tokens: report.original_tokens,
+            estimated_tokens: report.estimated_tokens,
+            code_contexts: report.code_contexts,
+            doc_comments: report.doc_comments,
+            identifiers: report.identifiers,
+            symbols: report.symbols,
+            relationships: report.relationships,
+        }
+    }
+}
+
+#[pymethods]
+impl PyReductionReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "ReductionReport({} -> {} tokens, code_contexts={}, doc_comments={}, identifiers={}, symbols={}, relationships={})",
+            self.original_tokens,
+            self.estimated_tokens,
+            self.code_contexts,
+            self.doc_comments,
+            self.identifiers,
+            self.symbols,
+            self.relationships
+        )
+    }
+}
+
+/// Drop lower-value content until results fit in a token budget
+///
+/// Code context goes first, then doc comments, then identifiers, then the
+/// least confident symbols (with relationships that point at them). The same
+/// input always reduces the same way.
+///
+/// Args:
+///     results (ExtractionResults): Results to reduce; left unchanged
+///     max_tokens (int): Token budget for the exported results
+///     strategy (str): "progressive" (default) may drop symbols;
+///         "keep_symbols" stops after identifiers
+///
+/// Returns:
+///     tuple[ExtractionResults, ReductionReport]: The reduced copy and what
+///         was dropped from it
+///
+/// Raises:
+///     ValueError: If strategy is not recognized
+#[pyfunction]
+#[pyo3(signature = (results, max_tokens, strategy = "progressive"))]
+pub fn reduce_results(
+    py: Python<'_>,
+    results: PyRef<'_, PyExtractionResults>,
+    max_tokens: usize,
+    strategy: &str,
+) -> PyResult<(PyExtractionResults, PyReductionReport)> {
+    let strategy: ReductionStrategy = strategy.parse().map_err(PyValueError::new_err)?;
+    let mut reduced = results.inner().clone();
+    let report = py.detach(|| result_reduction::reduce_results(&mut reduced, max_tokens, strategy));
+    Ok((results.with_results(reduced), report.into()))
+}
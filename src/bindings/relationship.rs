@@ -1,23 +1,38 @@
 // PyRelationship - PyO3 wrapper for Julie's Relationship type
 //
 // Represents a relationship between two symbols (calls, extends, implements, etc.)
+// and how far to trust it (confidence, provenance).
+// Shares the Rust Relationship through an Arc; fields convert on access and
+// metadata is built into a dict once (see PySymbol).
 
+use super::symbol::metadata_dict;
+use crate::enrichment::relationship_provenance;
 use julie_extractors::Relationship;
 use pyo3::prelude::*;
-use std::collections::HashMap;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::PyDict;
+use std::sync::Arc;
 
 /// Python-accessible Relationship wrapper
 ///
 /// Represents a relationship between two code symbols
 #[pyclass(name = "Relationship")]
 pub struct PyRelationship {
-    inner: Relationship,
+    inner: Arc<Relationship>,
+    /// metadata as a dict, built on first access
+    metadata: PyOnceLock<Py<PyDict>>,
 }
 
 impl PyRelationship {
     pub fn from_relationship(relationship: Relationship) -> Self {
+        PyRelationship::from_shared(Arc::new(relationship))
+    }
+
+    /// Wrap a relationship shared with its ExtractionResults, without copying it
+    pub fn from_shared(relationship: Arc<Relationship>) -> Self {
         PyRelationship {
             inner: relationship,
+            metadata: PyOnceLock::new(),
         }
     }
 }
@@ -53,7 +68,7 @@ impl PyRelationship {
 
     #[setter]
     fn set_file_path(&mut self, value: String) {
-        self.inner.file_path = value;
+        Arc::make_mut(&mut self.inner).file_path = value;
     }
 
     #[getter]
@@ -66,13 +81,38 @@ impl PyRelationship {
         self.inner.confidence
     }
 
+    /// How the link was made: "ast", "name-match", "heuristic", or "error-recovery"
     #[getter]
-    fn metadata(&self) -> Option<HashMap<String, String>> {
-        // Convert HashMap<String, serde_json::Value> to HashMap<String, String>
-        self.inner
+    fn provenance(&self) -> &'static str {
+        relationship_provenance(&self.inner).as_str()
+    }
+
+    /// Relationship metadata as a dict of plain values (built on first access)
+    #[getter]
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(metadata) = &self.inner.metadata else {
+            return Ok(None);
+        };
+        let dict = self
             .metadata
-            .as_ref()
-            .map(|m| m.iter().map(|(k, v)| (k.clone(), v.to_string())).collect())
+            .get_or_try_init(py, || metadata_dict(py, metadata).map(Bound::unbind))?;
+        Ok(Some(dict.bind(py).clone()))
+    }
+
+    /// Every field as a dict, metadata included
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let relationship = &*self.inner;
+        let dict = PyDict::new(py);
+        dict.set_item("id", &relationship.id)?;
+        dict.set_item("from_symbol_id", &relationship.from_symbol_id)?;
+        dict.set_item("to_symbol_id", &relationship.to_symbol_id)?;
+        dict.set_item("kind", relationship.kind.to_string())?;
+        dict.set_item("file_path", &relationship.file_path)?;
+        dict.set_item("line_number", relationship.line_number)?;
+        dict.set_item("confidence", relationship.confidence)?;
+        dict.set_item("provenance", self.provenance())?;
+        dict.set_item("metadata", self.metadata(py)?)?;
+        Ok(dict)
     }
 
     fn __repr__(&self) -> String {
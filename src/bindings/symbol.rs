@@ -1,28 +1,90 @@
 // PySymbol - PyO3 wrapper for Julie's Symbol type
 //
-// This wrapper allows Python code to access Symbol fields via zero-copy borrowing.
+// The wrapper shares the Rust Symbol through an Arc, so handing a file's
+// symbols to Python copies no strings: each field is converted when its getter
+// is called, and fields nobody reads cost nothing. metadata becomes a dict of
+// plain Python values on first access and that dict is kept for later reads.
+// to_dict() materializes every field at once.
 
+use crate::enrichment::QUALIFIED_NAME;
 use julie_extractors::Symbol;
 use pyo3::prelude::*;
+use pyo3::sync::PyOnceLock;
+use pyo3::types::{PyDict, PyList};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Python-accessible Symbol wrapper
 ///
 /// Represents a code symbol (function, class, variable, etc.) extracted from source code.
-/// All fields are read-only from Python.
+/// All fields except file_path are read-only from Python.
 #[pyclass(name = "Symbol")]
 pub struct PySymbol {
-    // Store the inner Symbol
-    inner: Symbol,
+    inner: Arc<Symbol>,
+    /// metadata as a dict, built on first access
+    metadata: PyOnceLock<Py<PyDict>>,
 }
 
 impl PySymbol {
     /// Create a new PySymbol from Julie's Symbol
     pub fn from_symbol(symbol: Symbol) -> Self {
-        PySymbol { inner: symbol }
+        PySymbol::from_shared(Arc::new(symbol))
+    }
+
+    /// Wrap a symbol shared with its ExtractionResults, without copying it
+    pub fn from_shared(symbol: Arc<Symbol>) -> Self {
+        PySymbol {
+            inner: symbol,
+            metadata: PyOnceLock::new(),
+        }
+    }
+
+    pub fn inner(&self) -> &Symbol {
+        &self.inner
     }
 }
 
+/// `value` as the Python object `json.loads` would produce
+pub(super) fn json_to_py<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        Value::Null => py.None().into_bound(py),
+        Value::Bool(b) => b.into_pyobject(py)?.to_owned().into_any(),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(i), _) => i.into_pyobject(py)?.into_any(),
+            (None, Some(u)) => u.into_pyobject(py)?.into_any(),
+            _ => n.as_f64().unwrap_or(f64::NAN).into_pyobject(py)?.into_any(),
+        },
+        Value::String(s) => s.into_pyobject(py)?.into_any(),
+        Value::Array(items) => {
+            let items = items
+                .iter()
+                .map(|item| json_to_py(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, items)?.into_any()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, item) in map {
+                dict.set_item(key, json_to_py(py, item)?)?;
+            }
+            dict.into_any()
+        }
+    })
+}
+
+/// A symbol's or relationship's metadata as a dict of plain Python values
+pub(super) fn metadata_dict<'py>(
+    py: Python<'py>,
+    metadata: &HashMap<String, Value>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    for (key, value) in metadata {
+        dict.set_item(key, json_to_py(py, value)?)?;
+    }
+    Ok(dict)
+}
+
 #[pymethods]
 impl PySymbol {
     // Required fields (always present)
@@ -55,7 +117,8 @@ impl PySymbol {
 
     #[setter]
     fn set_file_path(&mut self, value: String) {
-        self.inner.file_path = value;
+        // Copies the symbol only while ExtractionResults still shares it
+        Arc::make_mut(&mut self.inner).file_path = value;
     }
 
     #[getter]
@@ -110,13 +173,30 @@ impl PySymbol {
         self.inner.parent_id.clone()
     }
 
+    /// Extractor and enrichment metadata as a dict of plain values (built on
+    /// first access; later reads return the same dict)
     #[getter]
-    fn metadata(&self) -> Option<HashMap<String, String>> {
-        // Convert HashMap<String, serde_json::Value> to HashMap<String, String>
+    fn metadata<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(metadata) = &self.inner.metadata else {
+            return Ok(None);
+        };
+        let dict = self
+            .metadata
+            .get_or_try_init(py, || metadata_dict(py, metadata).map(Bound::unbind))?;
+        Ok(Some(dict.bind(py).clone()))
+    }
+
+    /// Enclosing namespace/class path joined with the language's separator
+    /// (e.g. "com.acme.Invoice.total", "net::http::Client::send")
+    #[getter]
+    fn qualified_name(&self) -> String {
         self.inner
             .metadata
             .as_ref()
-            .map(|m| m.iter().map(|(k, v)| (k.clone(), v.to_string())).collect())
+            .and_then(|m| m.get(QUALIFIED_NAME))
+            .and_then(|v| v.as_str())
+            .unwrap_or(&self.inner.name)
+            .to_string()
     }
 
     #[getter]
@@ -139,6 +219,34 @@ impl PySymbol {
         self.inner.content_type.clone()
     }
 
+    /// Every field as a dict, metadata included
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let symbol = &*self.inner;
+        let dict = PyDict::new(py);
+        dict.set_item("id", &symbol.id)?;
+        dict.set_item("name", &symbol.name)?;
+        dict.set_item("kind", symbol.kind.to_string())?;
+        dict.set_item("language", &symbol.language)?;
+        dict.set_item("file_path", &symbol.file_path)?;
+        dict.set_item("start_line", symbol.start_line)?;
+        dict.set_item("start_column", symbol.start_column)?;
+        dict.set_item("end_line", symbol.end_line)?;
+        dict.set_item("end_column", symbol.end_column)?;
+        dict.set_item("start_byte", symbol.start_byte)?;
+        dict.set_item("end_byte", symbol.end_byte)?;
+        dict.set_item("signature", &symbol.signature)?;
+        dict.set_item("doc_comment", &symbol.doc_comment)?;
+        dict.set_item("visibility", self.visibility())?;
+        dict.set_item("parent_id", &symbol.parent_id)?;
+        dict.set_item("metadata", self.metadata(py)?)?;
+        dict.set_item("qualified_name", self.qualified_name())?;
+        dict.set_item("semantic_group", &symbol.semantic_group)?;
+        dict.set_item("confidence", symbol.confidence)?;
+        dict.set_item("code_context", &symbol.code_context)?;
+        dict.set_item("content_type", &symbol.content_type)?;
+        Ok(dict)
+    }
+
     // Python repr
     fn __repr__(&self) -> String {
         format!(
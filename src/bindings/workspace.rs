@@ -0,0 +1,115 @@
+// Workspace extraction binding - walk and extract a whole tree in one call
+//
+// Replaces Python's enumerate/filter/batch loop with a single FFI call; see
+// crate::workspace for the walk rules. is_ignored exposes the walk's ignore
+// rules for single paths (the watcher, Python-side filtering).
+
+use super::progress::detach_with_progress;
+use super::{PyBatchFileResult, PyExtractionResults};
+use crate::engine;
+use crate::utils::ignore::IgnoreRules;
+use crate::workspace::{self, WorkspaceOptions};
+use pyo3::exceptions::PyFileNotFoundError;
+use pyo3::prelude::*;
+use std::path::Path;
+
+/// Walk a workspace and extract every supported file
+///
+/// The walk skips the default ignores (.git, node_modules, build output, ...),
+/// .gitignore (root and nested) and .julieignore matches, hidden files, and files
+/// is_supported_file rejects. Symlinks are followed without looping.
+///
+/// Args:
+///     workspace_root (str): Workspace root directory
+///     ignore_patterns (list[str], optional): Extra patterns in .julieignore syntax
+///     max_file_size (int, optional): Skip files larger than this many bytes
+///     progress_callback (callable, optional): Called as
+///         progress_callback(files_done, files_total, current_path) while the
+///         discovered files are extracted (see extract_files_batch). Returning
+///         False, or raising, cancels the files not started yet; they are
+///         returned with cancelled=True.
+///     progress_interval (int): Files between progress calls. Defaults to 25.
+///
+/// Returns:
+///     list[BatchFileResult]: One result per extracted file, sorted by path,
+///         followed by error results for files that were skipped; their
+///         error_kind is "too_large", "read_error", "panicked", "timed_out",
+///         "depth_exceeded", or "cancelled"
+///
+/// Raises:
+///     FileNotFoundError: If workspace_root is not a directory
+#[pyfunction]
+#[pyo3(signature = (
+    workspace_root,
+    ignore_patterns = None,
+    max_file_size = None,
+    progress_callback = None,
+    progress_interval = engine::DEFAULT_PROGRESS_INTERVAL
+))]
+pub fn extract_workspace(
+    py: Python<'_>,
+    workspace_root: &str,
+    ignore_patterns: Option<Vec<String>>,
+    max_file_size: Option<u64>,
+    progress_callback: Option<Py<PyAny>>,
+    progress_interval: usize,
+) -> PyResult<Vec<PyBatchFileResult>> {
+    let root = Path::new(workspace_root);
+    if !root.is_dir() {
+        return Err(PyFileNotFoundError::new_err(format!(
+            "Workspace root is not a directory: {}",
+            workspace_root
+        )));
+    }
+    let options = WorkspaceOptions {
+        ignore_patterns: ignore_patterns.unwrap_or_default(),
+        max_file_size,
+        ..WorkspaceOptions::default()
+    };
+
+    let extraction = detach_with_progress(
+        py,
+        progress_callback.as_ref(),
+        progress_interval,
+        |progress| workspace::extract_workspace_with_progress(root, &options, progress),
+    );
+
+    let files = extraction.files.into_iter().map(|file| {
+        PyBatchFileResult::success(
+            file.path,
+            file.content,
+            file.language,
+            file.hash,
+            file.results
+                .map(PyExtractionResults::from_extraction_results),
+        )
+    });
+    let skipped = extraction.skipped.into_iter().map(|skip| {
+        PyBatchFileResult::error(skip.path, skip.reason.kind(), skip.reason.to_string())
+    });
+    Ok(files.chain(skipped).collect())
+}
+
+/// Whether extract_workspace would skip a path under its ignore rules
+///
+/// Applies the default ignores, the root and nested .gitignore files (with
+/// negation, trailing-slash, and ** patterns), and .julieignore, which wins
+/// over the others; hidden entries are ignored. Support for the file's
+/// language and its size are not considered.
+///
+/// Args:
+///     path (str): Path relative to workspace_root (or absolute inside it); a
+///         trailing "/" marks a directory that does not exist on disk
+///     workspace_root (str): Workspace root directory
+///     ignore_patterns (list[str], optional): Extra patterns in .julieignore syntax
+///
+/// Returns:
+///     bool: True when the path is ignored; paths outside the workspace always are
+#[pyfunction]
+#[pyo3(signature = (path, workspace_root, ignore_patterns = None))]
+pub fn is_ignored(path: &str, workspace_root: &str, ignore_patterns: Option<Vec<String>>) -> bool {
+    let root = Path::new(workspace_root);
+    let rules = IgnoreRules::load(root, &ignore_patterns.unwrap_or_default());
+    let is_dir = path.ends_with('/') || root.join(path).is_dir();
+    rules.is_ignored(Path::new(path), is_dir)
+}
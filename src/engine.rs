@@ -0,0 +1,472 @@
+// Extraction engine - Miller's extraction pipeline as a plain Rust API
+//
+// Language detection → Julie's extractors → Miller's enrichment passes, with
+// no Python involvement. The PyO3 bindings (the default `python` feature) are
+// thin wrappers that convert these results to Py* types; with
+// `cargo build --no-default-features` this module is the crate's public
+// surface for Rust callers.
+//
+// Batch callers go through `extract_guarded` (see `guarded`), which isolates
+// one pathological file from the rest, and `run_batch` (see `batch`), which
+// spreads a batch over the rayon pool with progress reports and cancellation.
+// Passes that need more than one file (C# partial types, imports of other
+// files' symbols, C/C++ header declarations and their definitions) run on a
+// batch's results after every file is extracted; see `cross_file`.
+
+mod batch;
+mod c_pairing;
+mod cross_file;
+mod guarded;
+mod hashing;
+mod worker_pool;
+
+pub use batch::{run_batch, BatchProgress};
+pub use cross_file::{link_partial_types, resolve_cross_file};
+pub use guarded::extract_guarded;
+#[cfg(feature = "python")]
+pub(crate) use guarded::panic_message;
+pub use hashing::{hash_content, hash_file, hash_files, FileHash};
+
+use crate::enrichment::base::source_text::SourceText;
+use crate::utils::language::detect_language_from_content;
+use hashing::check_workspace_path;
+use julie_extractors::ExtractorManager;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+pub use crate::enrichment::{
+    default_declaration_macros, diagnose, extractor_version, CodeContextOptions, DeclarationMacro,
+    Diagnostic, DiagnosticKind, EnrichmentOptions, MacroArgument, SqlDialect, SymbolKindFilter,
+};
+pub use julie_extractors::{ExtractionResults, Identifier, Relationship, Symbol};
+
+/// Default wall-clock budget for extracting one file
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default bracket nesting depth above which a file is not extracted
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 512;
+
+/// Default number of finished files between progress reports
+pub const DEFAULT_PROGRESS_INTERVAL: usize = 25;
+
+/// Per-file safety limits for batch extraction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractionLimits {
+    /// Abandon a file's extraction after this long (None waits indefinitely)
+    pub timeout: Option<Duration>,
+    /// Refuse files whose bracket nesting is deeper than this (None disables the check)
+    pub max_nesting_depth: Option<usize>,
+}
+
+impl Default for ExtractionLimits {
+    fn default() -> Self {
+        ExtractionLimits {
+            timeout: Some(DEFAULT_TIMEOUT),
+            max_nesting_depth: Some(DEFAULT_MAX_NESTING_DEPTH),
+        }
+    }
+}
+
+/// A stage of extraction that failed for one file
+#[derive(Debug)]
+pub enum ExtractionError {
+    /// The file could not be read
+    Read(std::io::Error),
+    /// No extractor handles the file's extension
+    UnsupportedLanguage(String),
+    /// The path escapes the workspace root
+    PathRejected(String),
+    /// Julie's symbol extraction failed (unsupported language, parse failure)
+    Symbols(String),
+    /// Identifier extraction failed
+    Identifiers(String),
+    /// Relationship extraction failed
+    Relationships(String),
+    /// An extractor panicked; the payload message is kept
+    Panicked(String),
+    /// Extraction did not finish within the timeout and was abandoned
+    TimedOut(Duration),
+    /// The content nests deeper than the limit and was not extracted
+    DepthExceeded { depth: usize, limit: usize },
+    /// The batch was cancelled before the file was started
+    Cancelled,
+}
+
+impl ExtractionError {
+    /// Stable name for the failure, for callers that branch on it
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ExtractionError::Read(_) => "read_error",
+            ExtractionError::UnsupportedLanguage(_) => "unsupported_language",
+            ExtractionError::PathRejected(_) => "path_rejected",
+            ExtractionError::Symbols(_)
+            | ExtractionError::Identifiers(_)
+            | ExtractionError::Relationships(_) => "extraction_error",
+            ExtractionError::Panicked(_) => "panicked",
+            ExtractionError::TimedOut(_) => "timed_out",
+            ExtractionError::DepthExceeded { .. } => "depth_exceeded",
+            ExtractionError::Cancelled => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for ExtractionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractionError::Read(e) => write!(f, "Read error: {}", e),
+            ExtractionError::UnsupportedLanguage(extension) => {
+                write!(f, "Unsupported file extension: {:?}", extension)
+            }
+            ExtractionError::PathRejected(e) => write!(f, "Path rejected: {}", e),
+            ExtractionError::Symbols(e) => write!(f, "Extraction failed: {}", e),
+            ExtractionError::Identifiers(e) => write!(f, "Identifier extraction failed: {}", e),
+            ExtractionError::Relationships(e) => {
+                write!(f, "Relationship extraction failed: {}", e)
+            }
+            ExtractionError::Panicked(e) => write!(f, "Extractor panicked: {}", e),
+            ExtractionError::TimedOut(timeout) => {
+                write!(f, "Extraction timed out after {:?}", timeout)
+            }
+            ExtractionError::DepthExceeded { depth, limit } => {
+                write!(f, "Nesting depth {} exceeds the limit of {}", depth, limit)
+            }
+            ExtractionError::Cancelled => write!(f, "Batch cancelled before extraction"),
+        }
+    }
+}
+
+impl std::error::Error for ExtractionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExtractionError::Read(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A file read from disk, with its extraction results
+#[derive(Debug, Clone)]
+pub struct FileExtraction {
+    /// Path relative to the workspace root, as given
+    pub path: String,
+    pub content: String,
+    /// Detected language, "text" for files without an extractor
+    pub language: String,
+    /// blake3 hex digest of the content
+    pub hash: String,
+    /// None for "text" files, which are not extracted
+    pub results: Option<ExtractionResults>,
+}
+
+impl FileExtraction {
+    /// Best-effort regions of the results (error recovery, text fallbacks)
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.results.as_ref().map(diagnose).unwrap_or_default()
+    }
+}
+
+/// Detect a file's language from its extension, then from its content when given
+///
+/// Returns "text" for files no extractor handles.
+pub fn detect_language(path: &Path, content: Option<&str>) -> &'static str {
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    crate::enrichment::language_for_extension(extension)
+        .or_else(|| content.and_then(|content| detect_language_from_content(path, content)))
+        .unwrap_or("text")
+}
+
+/// Names of every language with an extractor
+pub fn supported_languages() -> Vec<&'static str> {
+    let mut languages = ExtractorManager::new().supported_languages();
+    languages.extend(crate::enrichment::TEMPLATE_LANGUAGES);
+    languages
+}
+
+/// `extractor_version` of every supported language
+pub fn extractor_versions() -> BTreeMap<&'static str, String> {
+    supported_languages()
+        .into_iter()
+        .map(|language| (language, extractor_version(language)))
+        .collect()
+}
+
+/// Run Julie's extraction followed by Miller's enrichment passes for one file
+///
+/// `file_path` selects the extractor by extension; `workspace_root` is used
+/// to resolve imports and includes.
+pub fn extract(
+    file_path: &str,
+    content: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+) -> Result<ExtractionResults, ExtractionError> {
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+    if crate::enrichment::language_for_extension(extension).is_none() {
+        return Err(ExtractionError::UnsupportedLanguage(extension.to_string()));
+    }
+    let source = SourceText::new(content);
+    let parsed = crate::enrichment::parser_input(file_path, source.text(), options);
+    let content = parsed.as_ref();
+    let julie_path = crate::enrichment::parser_path(file_path);
+    let manager = ExtractorManager::new();
+
+    // Identifiers and relationships both need the symbols extracted first
+    let symbols = manager
+        .extract_symbols(&julie_path, content, workspace_root)
+        .map_err(|e| ExtractionError::Symbols(e.to_string()))?;
+    let identifiers = manager
+        .extract_identifiers(&julie_path, content, &symbols)
+        .map_err(|e| ExtractionError::Identifiers(e.to_string()))?;
+    let relationships = manager
+        .extract_relationships(&julie_path, content, &symbols)
+        .map_err(|e| ExtractionError::Relationships(e.to_string()))?;
+
+    Ok(enriched(
+        file_path,
+        &source,
+        workspace_root,
+        options,
+        symbols,
+        identifiers,
+        relationships,
+    ))
+}
+
+/// `extract` for batch callers: a failing stage is logged and contributes no results
+pub fn extract_lenient(
+    file_path: &str,
+    content: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+) -> ExtractionResults {
+    let never = AtomicBool::new(false);
+    extract_lenient_until(file_path, content, workspace_root, options, &never)
+        .unwrap_or_else(|| unreachable!("extraction cancelled without a cancel request"))
+}
+
+/// `extract_lenient` that stops between stages once `cancelled` is set,
+/// returning None
+fn extract_lenient_until(
+    file_path: &str,
+    content: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+    cancelled: &AtomicBool,
+) -> Option<ExtractionResults> {
+    let proceed = || (!cancelled.load(Ordering::Relaxed)).then_some(());
+    let source = SourceText::new(content);
+    let parsed = crate::enrichment::parser_input(file_path, source.text(), options);
+    let content = parsed.as_ref();
+    let julie_path = crate::enrichment::parser_path(file_path);
+    let manager = ExtractorManager::new();
+    let warn = |what: &str, e: anyhow::Error| {
+        eprintln!(
+            "Warning: Failed to extract {} from {}: {}",
+            what, file_path, e
+        );
+    };
+
+    proceed()?;
+    let symbols = manager
+        .extract_symbols(&julie_path, content, workspace_root)
+        .unwrap_or_else(|e| {
+            warn("symbols", e);
+            Vec::new()
+        });
+    proceed()?;
+    let identifiers = manager
+        .extract_identifiers(&julie_path, content, &symbols)
+        .unwrap_or_else(|e| {
+            warn("identifiers", e);
+            Vec::new()
+        });
+    proceed()?;
+    let relationships = manager
+        .extract_relationships(&julie_path, content, &symbols)
+        .unwrap_or_else(|e| {
+            warn("relationships", e);
+            Vec::new()
+        });
+    proceed()?;
+
+    Some(enriched(
+        file_path,
+        &source,
+        workspace_root,
+        options,
+        symbols,
+        identifiers,
+        relationships,
+    ))
+}
+
+/// Read, hash, detect, and extract a file relative to the workspace root
+///
+/// Extraction goes through `extract_guarded`; files whose extension has no
+/// extractor are returned with `results: None`. Absolute paths and paths with
+/// `..` must resolve inside the workspace root.
+pub fn extract_path(
+    rel_path: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+    limits: &ExtractionLimits,
+) -> Result<FileExtraction, ExtractionError> {
+    check_workspace_path(rel_path, workspace_root).map_err(ExtractionError::PathRejected)?;
+    let full_path = workspace_root.join(rel_path);
+    let content = fs::read_to_string(&full_path).map_err(ExtractionError::Read)?;
+    let hash = hash_content(&content);
+    let language = detect_language(&full_path, None);
+
+    let results = if language == "text" {
+        None
+    } else {
+        Some(extract_guarded(
+            rel_path,
+            &content,
+            workspace_root,
+            options,
+            limits,
+        )?)
+    };
+
+    Ok(FileExtraction {
+        path: rel_path.to_string(),
+        content,
+        language: language.to_string(),
+        hash,
+        results,
+    })
+}
+
+/// Enrich results extracted from `source`'s normalized text, then restore original offsets
+fn enriched(
+    file_path: &str,
+    source: &SourceText,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+    symbols: Vec<Symbol>,
+    identifiers: Vec<Identifier>,
+    relationships: Vec<Relationship>,
+) -> ExtractionResults {
+    let mut results = ExtractionResults {
+        symbols,
+        identifiers,
+        relationships,
+        pending_relationships: Vec::new(), // Cross-file resolution not needed for Miller
+        types: std::collections::HashMap::new(),
+    };
+    crate::enrichment::enrich_results_with_options(
+        file_path,
+        source.text(),
+        workspace_root,
+        options,
+        &mut results,
+    );
+    source.restore_offsets(&mut results);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language_falls_back_to_content_then_text() {
+        let script = Path::new("bin/deploy");
+        assert_eq!(detect_language(script, None), "text");
+        assert_eq!(
+            detect_language(script, Some("#!/usr/bin/env bash\necho hi\n")),
+            "bash"
+        );
+    }
+
+    #[test]
+    fn test_extractor_versions_tag_each_language() {
+        let versions = extractor_versions();
+        assert!(supported_languages()
+            .iter()
+            .all(|language| versions[language] == extractor_version(language)));
+        let sql = extractor_version("sql");
+        assert!(sql.starts_with("1.20.0+4."), "{sql}");
+        // TypeScript shares the JavaScript passes
+        assert_eq!(
+            extractor_version("typescript"),
+            extractor_version("javascript")
+        );
+        assert!(extractor_version("text").ends_with(".0"));
+    }
+
+    #[test]
+    fn test_extract_path_reports_read_errors_and_skips_text() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.unknownext"), "hello").unwrap();
+        let options = EnrichmentOptions::default();
+        let limits = ExtractionLimits::default();
+
+        let file = extract_path("notes.unknownext", dir.path(), &options, &limits).unwrap();
+        assert_eq!(file.language, "text");
+        assert_eq!(file.hash, hash_content("hello"));
+        assert!(file.results.is_none());
+        assert!(file.diagnostics().is_empty());
+
+        let missing = extract_path("missing.py", dir.path(), &options, &limits).unwrap_err();
+        assert!(matches!(missing, ExtractionError::Read(_)));
+        assert!(missing.to_string().starts_with("Read error: "));
+
+        let escaped = extract_path("../outside.py", dir.path(), &options, &limits).unwrap_err();
+        assert_eq!(escaped.kind(), "path_rejected");
+    }
+
+    #[test]
+    fn test_extract_rejects_unsupported_extensions() {
+        let options = EnrichmentOptions::default();
+        let error = extract("notes.xyz", "hello", Path::new("."), &options).unwrap_err();
+        assert_eq!(error.kind(), "unsupported_language");
+        assert_eq!(error.to_string(), "Unsupported file extension: \"xyz\"");
+    }
+
+    #[test]
+    fn test_line_endings_and_bom_keep_names_and_original_offsets() {
+        let fixtures = [
+            (
+                "lib.rs",
+                "lazy_static! {\n    /// Shared settings\n    static ref CONFIG: Config = Config::new();\n}\n",
+            ),
+            (
+                "schema.sql",
+                "CREATE TABLE users (\n  id INT\n);\n\nCREATE VIEW active_users AS\n  SELECT * FROM users;\n",
+            ),
+        ];
+        let options = EnrichmentOptions::default();
+        for (path, lf) in fixtures {
+            let expected = extract(path, lf, Path::new("."), &options).unwrap();
+            assert!(!expected.symbols.is_empty(), "{} yields symbols", path);
+
+            let crlf = lf.replace('\n', "\r\n");
+            for variant in [
+                format!("\u{feff}{}", lf),
+                crlf.clone(),
+                format!("\u{feff}{}", crlf),
+            ] {
+                let results = extract(path, &variant, Path::new("."), &options).unwrap();
+                assert_eq!(results.symbols.len(), expected.symbols.len());
+                for (symbol, lf_symbol) in results.symbols.iter().zip(&expected.symbols) {
+                    assert_eq!(symbol.name, lf_symbol.name);
+                    assert_eq!(symbol.doc_comment, lf_symbol.doc_comment);
+                    assert_eq!(symbol.start_line, lf_symbol.start_line);
+                    // Offsets index the variant's own bytes
+                    let text = &variant[symbol.start_byte as usize..symbol.end_byte as usize];
+                    let lf_text = &lf[lf_symbol.start_byte as usize..lf_symbol.end_byte as usize];
+                    assert_eq!(text.replace("\r\n", "\n"), lf_text);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// Batch runner - parallel work with progress reports and cancellation
+//
+// `run_batch` spreads a batch over the rayon pool and reports progress from
+// the calling thread, which can also cancel the files not yet started.
+
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Progress reporting for `run_batch`
+pub struct BatchProgress<'a> {
+    /// Report after every `every` finished files, and after the last one
+    pub every: usize,
+    /// Called with (files done, files total, path of the last finished file);
+    /// returning false cancels the files not started yet
+    pub report: &'a mut dyn FnMut(usize, usize, &str) -> bool,
+}
+
+/// Run `work` on every item in parallel, returning results in input order
+///
+/// Workers only send each finished index over a channel; `progress.report`
+/// runs on the calling thread, so a slow report never holds up the pool. Once
+/// a report returns false, items not started yet are skipped and come back as
+/// None, and the second value is true.
+pub fn run_batch<T, R, F>(
+    items: &[T],
+    path: impl Fn(&T) -> &str,
+    work: F,
+    progress: Option<BatchProgress<'_>>,
+) -> (Vec<Option<R>>, bool)
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    use rayon::prelude::*;
+
+    let Some(progress) = progress else {
+        return (
+            items.par_iter().map(|item| Some(work(item))).collect(),
+            false,
+        );
+    };
+    let every = progress.every.max(1);
+    let cancelled = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+    let results = thread::scope(|scope| {
+        let workers = scope.spawn(|| {
+            items
+                .par_iter()
+                .enumerate()
+                .map_with(sender, |sender, (index, item)| {
+                    if cancelled.load(Ordering::Relaxed) {
+                        return None;
+                    }
+                    let result = work(item);
+                    // The coordinator outlives the workers
+                    let _ = sender.send(index);
+                    Some(result)
+                })
+                .collect::<Vec<_>>()
+        });
+        // Ends when the last worker's sender is dropped
+        for (done, index) in (1..).zip(receiver) {
+            let due = done % every == 0 || done == items.len();
+            if due
+                && !cancelled.load(Ordering::Relaxed)
+                && !(progress.report)(done, items.len(), path(&items[index]))
+            {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+        }
+        workers
+            .join()
+            .unwrap_or_else(|payload| panic::resume_unwind(payload))
+    });
+    (results, cancelled.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_run_batch_reports_progress_and_cancels_unstarted_items() {
+        let items: Vec<String> = (0..40).map(|i| format!("file_{i}.py")).collect();
+        let mut reports = Vec::new();
+        let mut report = |done: usize, total: usize, _: &str| {
+            reports.push((done, total));
+            true
+        };
+        let progress = BatchProgress {
+            every: 10,
+            report: &mut report,
+        };
+        let (results, cancelled) = run_batch(&items, |p| p, |p| p.len(), Some(progress));
+        assert!(!cancelled);
+        assert!(results.iter().zip(&items).all(|(r, p)| *r == Some(p.len())));
+        assert_eq!(reports, vec![(10, 40), (20, 40), (30, 40), (40, 40)]);
+
+        let mut calls = 0;
+        let mut cancel = |_: usize, _: usize, _: &str| {
+            calls += 1;
+            false
+        };
+        let progress = BatchProgress {
+            every: 1,
+            report: &mut cancel,
+        };
+        let slow = |p: &String| {
+            thread::sleep(Duration::from_millis(5));
+            p.len()
+        };
+        let many: Vec<String> = (0..500).map(|i| format!("file_{i}.py")).collect();
+        let (results, cancelled) = run_batch(&many, |p| p, slow, Some(progress));
+        assert!(cancelled);
+        assert_eq!(calls, 1);
+        assert!(results.iter().any(Option::is_none));
+        assert!(results.iter().any(Option::is_some));
+    }
+}
@@ -0,0 +1,214 @@
+// C/C++ declaration pairing - header declarations and their definitions
+//
+// A function declared in a header and defined in a source file is one
+// function split across a batch. Pairs are matched by name and parameter
+// types; anything that is not one-to-one is flagged rather than guessed.
+
+use crate::enrichment::base::set_metadata;
+use crate::enrichment::{set_provenance, Provenance, NAME_MATCH};
+use julie_extractors::{ExtractionResults, Relationship, RelationshipKind};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+const C_HEADER_EXTENSIONS: &[&str] = &["h", "hh", "hpp", "hxx"];
+const C_SOURCE_EXTENSIONS: &[&str] = &["c", "cc", "cpp", "cxx"];
+
+/// Pair C/C++ function declarations in headers with their definitions in
+/// source files
+///
+/// The C passes record metadata.prototype and metadata.parameterTypes on
+/// every function they can read (c::record_prototypes). Declarations and
+/// definitions are matched by qualified name (else name) and parameter types;
+/// `static` functions (metadata.fileLocal) are file-local and never paired.
+/// When exactly one declaration and one definition match, the definition gets
+/// a Defines relationship to the declaration, stored with the definition's
+/// file (metadata.crossFile, metadata.targetFile, name-match provenance).
+/// When either side has several candidates nothing is guessed: every
+/// candidate gets metadata.ambiguousPairing, reported by `diagnose`. Returns
+/// the number of relationships added.
+pub(super) fn pair_c_declarations(files: &mut [&mut ExtractionResults]) -> usize {
+    let extension_in = |path: &str, extensions: &[&str]| {
+        Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| extensions.contains(&e.to_ascii_lowercase().as_str()))
+    };
+
+    // (name, parameter types) → (declarations, definitions) as (file index, symbol index)
+    type Candidates = (Vec<(usize, usize)>, Vec<(usize, usize)>);
+    let mut functions: BTreeMap<(String, String), Candidates> = BTreeMap::new();
+    for (file_index, results) in files.iter().enumerate() {
+        for (symbol_index, symbol) in results.symbols.iter().enumerate() {
+            let Some(metadata) = &symbol.metadata else {
+                continue;
+            };
+            let (Some(prototype), Some(Value::Array(types))) = (
+                metadata.get("prototype").and_then(Value::as_bool),
+                metadata.get("parameterTypes"),
+            ) else {
+                continue;
+            };
+            if metadata.get("fileLocal") == Some(&Value::Bool(true)) {
+                continue;
+            }
+            let side = if prototype {
+                C_HEADER_EXTENSIONS
+            } else {
+                C_SOURCE_EXTENSIONS
+            };
+            if !extension_in(&symbol.file_path, side) {
+                continue;
+            }
+            let name = metadata
+                .get(crate::enrichment::QUALIFIED_NAME)
+                .and_then(Value::as_str)
+                .unwrap_or(&symbol.name);
+            let types: Vec<&str> = types.iter().filter_map(Value::as_str).collect();
+            let (declarations, definitions) = functions
+                .entry((name.to_string(), types.join(",")))
+                .or_default();
+            let candidates = if prototype { declarations } else { definitions };
+            // A file that declares a function twice still declares it once
+            if !candidates.iter().any(|&(file, _)| file == file_index) {
+                candidates.push((file_index, symbol_index));
+            }
+        }
+    }
+
+    let mut added = 0;
+    for (declarations, definitions) in functions.values() {
+        match (declarations.as_slice(), definitions.as_slice()) {
+            ([], _) | (_, []) => {}
+            (&[(decl_file, decl_symbol)], &[(def_file, def_symbol)]) => {
+                let declaration = &files[decl_file].symbols[decl_symbol];
+                let (target_id, target_path) =
+                    (declaration.id.clone(), declaration.file_path.clone());
+                let results = &mut *files[def_file];
+                let definition = &results.symbols[def_symbol];
+                let kind = RelationshipKind::Defines;
+                let id = format!(
+                    "{}_{}_{:?}_{}",
+                    definition.id, target_id, kind, definition.start_line
+                );
+                if results.relationships.iter().any(|r| r.id == id) {
+                    continue;
+                }
+                let mut relationship = Relationship {
+                    id,
+                    from_symbol_id: definition.id.clone(),
+                    to_symbol_id: target_id,
+                    kind,
+                    file_path: definition.file_path.clone(),
+                    line_number: definition.start_line,
+                    confidence: 1.0,
+                    metadata: Some(HashMap::from([
+                        ("crossFile".to_string(), Value::Bool(true)),
+                        ("targetFile".to_string(), Value::from(target_path)),
+                    ])),
+                };
+                set_provenance(&mut relationship, Provenance::NameMatch, NAME_MATCH);
+                results.relationships.push(relationship);
+                added += 1;
+            }
+            _ => {
+                let paths = |candidates: &[(usize, usize)]| -> Vec<String> {
+                    candidates
+                        .iter()
+                        .map(|&(file, symbol)| files[file].symbols[symbol].file_path.clone())
+                        .collect()
+                };
+                let ambiguity = serde_json::json!({
+                    "declarations": paths(declarations),
+                    "definitions": paths(definitions),
+                });
+                for &(file, symbol) in declarations.iter().chain(definitions) {
+                    set_metadata(
+                        &mut files[file].symbols[symbol],
+                        "ambiguousPairing",
+                        ambiguity.clone(),
+                    );
+                }
+            }
+        }
+    }
+    added
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{diagnose, resolve_cross_file, DiagnosticKind};
+    use crate::enrichment::base::test_results;
+
+    #[test]
+    fn test_c_declarations_pair_with_their_single_definition() {
+        use crate::enrichment::base::{set_metadata, SymbolOptions};
+        use crate::enrichment::EnrichmentContext;
+        use julie_extractors::SymbolKind;
+
+        let file = |path: &str, functions: &[(&str, bool, &[&str])]| {
+            let ctx = EnrichmentContext::new(path, "c", "", Path::new("."));
+            let symbols = functions
+                .iter()
+                .enumerate()
+                .map(|(line, &(name, prototype, types))| {
+                    let mut symbol = ctx.create_symbol(
+                        name,
+                        SymbolKind::Function,
+                        line,
+                        line,
+                        SymbolOptions::default(),
+                    );
+                    set_metadata(&mut symbol, "prototype", prototype);
+                    set_metadata(&mut symbol, "parameterTypes", types.to_vec());
+                    if name == "cache" {
+                        set_metadata(&mut symbol, "fileLocal", true);
+                    }
+                    symbol
+                })
+                .collect();
+            test_results(symbols)
+        };
+        let mut header = file(
+            "include/db.h",
+            &[
+                ("open_db", true, &["const char*"]),
+                ("open_db", true, &["const char*", "int"]),
+                ("log", true, &["const char*"]),
+                ("cache", true, &[]),
+            ],
+        );
+        let mut db = file(
+            "src/db.c",
+            &[
+                ("open_db", false, &["const char*"]),
+                ("log", false, &["const char*"]),
+                ("cache", false, &[]),
+            ],
+        );
+        let mut log = file("src/log.c", &[("log", false, &["const char*"])]);
+
+        assert_eq!(resolve_cross_file(&mut [&mut header, &mut db, &mut log]), 1);
+        assert_eq!(resolve_cross_file(&mut [&mut header, &mut db, &mut log]), 0);
+        assert!(header.relationships.is_empty());
+        assert_eq!(db.relationships.len(), 1);
+        let link = &db.relationships[0];
+        assert_eq!(link.kind, RelationshipKind::Defines);
+        assert_eq!(link.from_symbol_id, db.symbols[0].id);
+        assert_eq!(link.to_symbol_id, header.symbols[0].id);
+        assert_eq!(
+            link.metadata.as_ref().unwrap()["targetFile"],
+            "include/db.h"
+        );
+
+        let diagnostics = diagnose(&log);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::AmbiguousPairing);
+        assert_eq!(
+            diagnostics[0].message,
+            "'log' has declarations in include/db.h and definitions in src/db.c, src/log.c; none was paired"
+        );
+        assert_eq!(diagnose(&header).len(), 1);
+    }
+}
@@ -0,0 +1,448 @@
+// Cross-file passes - links between the files of a batch
+//
+// Per-file extraction can't see other files, so batch callers run these once
+// every file is extracted: C# partial types are grouped, and imports are
+// linked to the top-level symbols they name in other files (Java imports
+// through source roots inferred from package declarations). Header
+// declarations are paired with their definitions in `c_pairing`.
+
+use super::c_pairing::pair_c_declarations;
+use crate::enrichment::base::set_metadata;
+use crate::enrichment::{set_provenance, Provenance, NAME_MATCH};
+use crate::utils::java_roots::{resolve_java_import, JavaWorkspaceIndex};
+use julie_extractors::{
+    ExtractionResults, IdentifierKind, Relationship, RelationshipKind, SymbolKind,
+};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Group partial type declarations split across the files of a batch
+///
+/// Types flagged metadata.isPartial (C# `partial class`/`struct`/`record`)
+/// with the same qualified name are one type: every part gets the
+/// semantic_group "partial:<qualified name>", and each part after the first
+/// (by file path, then position) gets a References relationship to the first,
+/// stored with that part's file (metadata.partial, metadata.partCount).
+/// Per-file extraction can't see the other parts, so batch callers opt in
+/// once every file is extracted. A type with a single part is left alone.
+pub fn link_partial_types(files: &mut [&mut ExtractionResults]) {
+    // (language, qualified name) → (file index, symbol index) of each part
+    let mut groups: BTreeMap<(String, String), Vec<(usize, usize)>> = BTreeMap::new();
+    for (file_index, results) in files.iter().enumerate() {
+        for (symbol_index, symbol) in results.symbols.iter().enumerate() {
+            let Some(metadata) = &symbol.metadata else {
+                continue;
+            };
+            if metadata.get("isPartial") != Some(&Value::Bool(true)) {
+                continue;
+            }
+            let name = metadata
+                .get(crate::enrichment::QUALIFIED_NAME)
+                .and_then(Value::as_str)
+                .unwrap_or(&symbol.name);
+            groups
+                .entry((symbol.language.clone(), name.to_string()))
+                .or_default()
+                .push((file_index, symbol_index));
+        }
+    }
+
+    for ((_, name), mut parts) in groups {
+        if parts.len() < 2 {
+            continue;
+        }
+        parts.sort_by(|&(a_file, a_symbol), &(b_file, b_symbol)| {
+            let a = &files[a_file].symbols[a_symbol];
+            let b = &files[b_file].symbols[b_symbol];
+            (&a.file_path, a.start_byte).cmp(&(&b.file_path, b.start_byte))
+        });
+        let group = format!("partial:{}", name);
+        for &(file_index, symbol_index) in &parts {
+            files[file_index].symbols[symbol_index].semantic_group = Some(group.clone());
+        }
+
+        let (first_file, first_symbol) = parts[0];
+        let first_id = files[first_file].symbols[first_symbol].id.clone();
+        for &(file_index, symbol_index) in &parts[1..] {
+            let part = &files[file_index].symbols[symbol_index];
+            let kind = RelationshipKind::References;
+            let relationship = Relationship {
+                id: format!("{}_{}_{:?}_{}", part.id, first_id, kind, part.start_line),
+                from_symbol_id: part.id.clone(),
+                to_symbol_id: first_id.clone(),
+                kind,
+                file_path: part.file_path.clone(),
+                line_number: part.start_line,
+                confidence: 1.0,
+                metadata: Some(HashMap::from([
+                    ("partial".to_string(), Value::Bool(true)),
+                    ("partCount".to_string(), Value::from(parts.len())),
+                ])),
+            };
+            files[file_index].relationships.push(relationship);
+        }
+    }
+}
+
+/// Link Import symbols to the symbols they import from other files of a batch
+///
+/// Language passes record the workspace-relative file an import names in
+/// metadata.resolvedPath (TypeScript/JavaScript re-exports and require(),
+/// Python, Zig, Dart, Bash). Once every file is extracted, each such import
+/// whose imported name (metadata.originalName or metadata.importedName, else
+/// the symbol's name) is a top-level symbol of that file gets an Imports
+/// relationship to it, stored with the importing file (metadata.crossFile,
+/// metadata.targetFile). Import identifiers inside the import's span and
+/// without a target get the same target_symbol_id. Imports that are
+/// wildcards, name a file outside the batch, or name nothing top-level there
+/// are left untouched. Running it again adds nothing. Returns the number of
+/// relationships added.
+///
+/// Java imports name classes rather than files: their metadata.importPath is
+/// looked up in a `JavaWorkspaceIndex` of the batch's Java files, whose source
+/// roots are inferred from package declarations. A file whose package does
+/// not match its directory is not indexed; its package symbol (or first
+/// top-level type) gets metadata.packageMismatch, reported by `diagnose`.
+/// Those links are name-match relationships, the others ast.
+///
+/// C/C++ function declarations in headers are paired with their definitions
+/// in source files too; see `pair_c_declarations`.
+pub fn resolve_cross_file(files: &mut [&mut ExtractionResults]) -> usize {
+    let java = index_java_files(files);
+    let paired = pair_c_declarations(files);
+
+    // file path → name → (file index, symbol index) of its first top-level declaration
+    let mut exports: HashMap<String, HashMap<String, (usize, usize)>> = HashMap::new();
+    for (file_index, results) in files.iter().enumerate() {
+        for (symbol_index, symbol) in results.symbols.iter().enumerate() {
+            if symbol.parent_id.is_some()
+                || matches!(symbol.kind, SymbolKind::Import | SymbolKind::Export)
+            {
+                continue;
+            }
+            exports
+                .entry(symbol.file_path.clone())
+                .or_default()
+                .entry(symbol.name.clone())
+                .or_insert((file_index, symbol_index));
+        }
+    }
+
+    // (file index, import symbol index, target file index, target symbol index, provenance)
+    let mut links = Vec::new();
+    for (file_index, results) in files.iter().enumerate() {
+        for (symbol_index, symbol) in results.symbols.iter().enumerate() {
+            if symbol.kind != SymbolKind::Import {
+                continue;
+            }
+            let Some(metadata) = &symbol.metadata else {
+                continue;
+            };
+            if metadata.get("wildcard") == Some(&Value::Bool(true)) {
+                continue;
+            }
+            let (path, name, provenance) =
+                if let Some(path) = metadata.get("resolvedPath").and_then(Value::as_str) {
+                    let name = ["originalName", "importedName"]
+                        .iter()
+                        .find_map(|key| metadata.get(*key).and_then(Value::as_str))
+                        .unwrap_or(&symbol.name);
+                    (path, name, Provenance::Ast)
+                } else if let Some(import) = metadata
+                    .get("importPath")
+                    .and_then(Value::as_str)
+                    .and_then(|import_path| resolve_java_import(import_path, &java))
+                {
+                    // Found by class name under inferred source roots
+                    (import.file_path, import.class_name, Provenance::NameMatch)
+                } else {
+                    continue;
+                };
+            if let Some(&(target_file, target_symbol)) =
+                exports.get(path).and_then(|names| names.get(name))
+            {
+                links.push((
+                    file_index,
+                    symbol_index,
+                    target_file,
+                    target_symbol,
+                    provenance,
+                ));
+            }
+        }
+    }
+
+    let mut added = 0;
+    for (file_index, symbol_index, target_file, target_symbol, provenance) in links {
+        let target = &files[target_file].symbols[target_symbol];
+        let (target_id, target_path) = (target.id.clone(), target.file_path.clone());
+        let results = &mut *files[file_index];
+        let import = &results.symbols[symbol_index];
+        let kind = RelationshipKind::Imports;
+        let id = format!(
+            "{}_{}_{:?}_{}",
+            import.id, target_id, kind, import.start_line
+        );
+        let (start, end) = (import.start_byte, import.end_byte);
+        let (import_name, import_id) = (import.name.clone(), import.id.clone());
+        for identifier in &mut results.identifiers {
+            if identifier.kind == IdentifierKind::Import
+                && identifier.target_symbol_id.is_none()
+                && identifier.name == import_name
+                && start <= identifier.start_byte
+                && identifier.end_byte <= end
+            {
+                identifier.target_symbol_id = Some(target_id.clone());
+            }
+        }
+        if results.relationships.iter().any(|r| r.id == id) {
+            continue;
+        }
+        let mut relationship = Relationship {
+            id,
+            from_symbol_id: import_id,
+            to_symbol_id: target_id,
+            kind,
+            file_path: results.symbols[symbol_index].file_path.clone(),
+            line_number: results.symbols[symbol_index].start_line,
+            confidence: 1.0,
+            metadata: Some(HashMap::from([
+                ("crossFile".to_string(), Value::Bool(true)),
+                ("targetFile".to_string(), Value::from(target_path)),
+            ])),
+        };
+        if provenance != Provenance::Ast {
+            set_provenance(&mut relationship, provenance, NAME_MATCH);
+        }
+        results.relationships.push(relationship);
+        added += 1;
+    }
+    added + paired
+}
+
+/// Index the batch's Java files by package, flagging those whose package
+/// doesn't match their directory
+fn index_java_files(files: &mut [&mut ExtractionResults]) -> JavaWorkspaceIndex {
+    // (file index, symbol to flag, file path, package)
+    let packages: Vec<(usize, usize, String, String)> = files
+        .iter()
+        .enumerate()
+        .filter_map(|(file_index, results)| {
+            let (symbol_index, package) = java_package(results)?;
+            let path = results.symbols[symbol_index].file_path.clone();
+            Some((file_index, symbol_index, path, package))
+        })
+        .collect();
+    let index = JavaWorkspaceIndex::build(
+        packages
+            .iter()
+            .map(|(_, _, path, package)| (path.as_str(), package.as_str())),
+    );
+    for mismatch in &index.mismatches {
+        let Some((file_index, symbol_index, _, _)) = packages
+            .iter()
+            .find(|(_, _, path, _)| *path == mismatch.path)
+        else {
+            continue;
+        };
+        set_metadata(
+            &mut files[*file_index].symbols[*symbol_index],
+            "packageMismatch",
+            serde_json::json!({ "package": mismatch.package, "directory": mismatch.directory }),
+        );
+    }
+    index
+}
+
+/// A Java file's package and the symbol that declares it: Julie's top-level
+/// Namespace symbol, else the package part of the first top-level type's
+/// metadata.binaryName
+fn java_package(results: &ExtractionResults) -> Option<(usize, String)> {
+    let top_level = || {
+        results
+            .symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.parent_id.is_none() && s.file_path.ends_with(".java"))
+    };
+    if let Some((index, namespace)) = top_level().find(|(_, s)| s.kind == SymbolKind::Namespace) {
+        return Some((index, namespace.name.clone()));
+    }
+    top_level().find_map(|(index, symbol)| {
+        let binary_name = symbol.metadata.as_ref()?.get("binaryName")?.as_str()?;
+        let package = binary_name.strip_suffix(symbol.name.as_str())?;
+        Some((index, package.trim_end_matches('.').to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{diagnose, DiagnosticKind};
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    #[test]
+    fn test_partial_types_are_linked_across_files() {
+        use crate::enrichment::base::{set_metadata, SymbolOptions};
+        use crate::enrichment::EnrichmentContext;
+
+        let part = |path: &str, content: &str, name: &str, qualified: &str| {
+            let ctx = EnrichmentContext::new(path, "csharp", content, Path::new("."));
+            let start = content.find("public").unwrap();
+            let mut symbol = ctx.create_symbol(
+                name,
+                julie_extractors::SymbolKind::Class,
+                start,
+                content.len(),
+                SymbolOptions::default(),
+            );
+            set_metadata(&mut symbol, "isPartial", true);
+            set_metadata(&mut symbol, crate::enrichment::QUALIFIED_NAME, qualified);
+            test_results(vec![symbol])
+        };
+        let mut designer = part(
+            "Form1.Designer.cs",
+            "namespace App;\npublic partial class Form1 { }\n",
+            "Form1",
+            "App.Form1",
+        );
+        let mut code = part(
+            "Form1.cs",
+            "namespace App;\npublic partial class Form1 : Form { }\n",
+            "Form1",
+            "App.Form1",
+        );
+        let mut other = part(
+            "Other.cs",
+            "namespace Lib;\npublic partial class Form1 { }\n",
+            "Form1",
+            "Lib.Form1",
+        );
+        link_partial_types(&mut [&mut designer, &mut code, &mut other]);
+
+        let group = Some("partial:App.Form1".to_string());
+        assert_eq!(designer.symbols[0].semantic_group, group);
+        assert_eq!(code.symbols[0].semantic_group, group);
+        // A different namespace is a different type, and a lone part stays ungrouped
+        assert_eq!(other.symbols[0].semantic_group, None);
+        assert!(other.relationships.is_empty());
+
+        // Form1.Designer.cs sorts first, so the code-behind part points at it
+        assert!(designer.relationships.is_empty());
+        let link = &code.relationships[0];
+        assert_eq!(link.from_symbol_id, code.symbols[0].id);
+        assert_eq!(link.to_symbol_id, designer.symbols[0].id);
+        assert_eq!(link.file_path, "Form1.cs");
+        assert_eq!(link.metadata.as_ref().unwrap()["partCount"], 2);
+    }
+
+    #[test]
+    fn test_imports_resolve_to_symbols_in_other_files() {
+        use crate::enrichment::base::{set_metadata, SymbolOptions};
+        use crate::enrichment::EnrichmentContext;
+        use julie_extractors::SymbolKind;
+
+        let user_content = "export class User {}\nexport function helper() {}\n";
+        let user_ctx =
+            EnrichmentContext::new("src/user.ts", "typescript", user_content, Path::new("."));
+        let class =
+            user_ctx.create_symbol("User", SymbolKind::Class, 0, 19, SymbolOptions::default());
+        let mut user = test_results(vec![class]);
+
+        let index_content =
+            "export { User as Account } from './user';\nexport { Missing } from './user';\n";
+        let index_ctx =
+            EnrichmentContext::new("src/index.ts", "typescript", index_content, Path::new("."));
+        let import = |name: &str, original: &str, start: usize| {
+            let mut symbol = index_ctx.create_symbol(
+                name,
+                SymbolKind::Import,
+                start,
+                start + 40,
+                SymbolOptions::default(),
+            );
+            set_metadata(&mut symbol, "resolvedPath", "src/user.ts");
+            set_metadata(&mut symbol, "originalName", original);
+            symbol
+        };
+        let account = import("Account", "User", 0);
+        let missing = import("Missing", "Missing", 42);
+        let mut index = ExtractionResults {
+            identifiers: vec![index_ctx.create_identifier(
+                "Account",
+                julie_extractors::IdentifierKind::Import,
+                17,
+                24,
+                None,
+            )],
+            ..test_results(vec![account, missing])
+        };
+
+        assert_eq!(resolve_cross_file(&mut [&mut user, &mut index]), 1);
+        assert_eq!(resolve_cross_file(&mut [&mut user, &mut index]), 0);
+
+        let link = &index.relationships[0];
+        assert_eq!(link.from_symbol_id, index.symbols[0].id);
+        assert_eq!(link.to_symbol_id, user.symbols[0].id);
+        assert_eq!(link.kind, RelationshipKind::Imports);
+        assert_eq!(link.file_path, "src/index.ts");
+        assert_eq!(link.metadata.as_ref().unwrap()["targetFile"], "src/user.ts");
+        assert_eq!(
+            index.identifiers[0].target_symbol_id.as_ref(),
+            Some(&user.symbols[0].id)
+        );
+        assert!(user.relationships.is_empty());
+    }
+
+    #[test]
+    fn test_java_imports_resolve_through_inferred_source_roots() {
+        use crate::enrichment::base::{set_metadata, SymbolOptions};
+        use crate::enrichment::EnrichmentContext;
+        use julie_extractors::SymbolKind;
+
+        let class = |path: &str, name: &str, binary_name: &str| {
+            let content = format!("package p;\npublic class {} {{}}\n", name);
+            let ctx = EnrichmentContext::new(path, "java", &content, Path::new("."));
+            let mut symbol =
+                ctx.create_symbol(name, SymbolKind::Class, 11, 30, SymbolOptions::default());
+            set_metadata(&mut symbol, "binaryName", binary_name);
+            test_results(vec![symbol])
+        };
+        let mut strings = class(
+            "core/src/main/java/com/acme/util/Strings.java",
+            "Strings",
+            "com.acme.util.Strings",
+        );
+        let mut wrong = class("web/src/main/java/Wrong.java", "Wrong", "com.acme.Wrong");
+        let app_path = "web/src/main/java/com/acme/web/App.java";
+        let mut app = class(app_path, "App", "com.acme.web.App");
+        let app_ctx = EnrichmentContext::new(app_path, "java", "", Path::new("."));
+        for (name, path) in [
+            ("join", "com.acme.util.Strings.join"),
+            ("Wrong", "com.acme.Wrong"),
+        ] {
+            let mut import =
+                app_ctx.create_symbol(name, SymbolKind::Import, 0, 0, SymbolOptions::default());
+            set_metadata(&mut import, "importPath", path);
+            app.symbols.push(import);
+        }
+
+        assert_eq!(
+            resolve_cross_file(&mut [&mut strings, &mut wrong, &mut app]),
+            1
+        );
+        let link = &app.relationships[0];
+        assert_eq!(link.from_symbol_id, app.symbols[1].id);
+        assert_eq!(link.to_symbol_id, strings.symbols[0].id);
+
+        assert!(diagnose(&strings).is_empty());
+        let diagnostics = diagnose(&wrong);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::PackageMismatch);
+        assert_eq!(
+            diagnostics[0].message,
+            "package 'com.acme' does not match directory 'web/src/main/java'"
+        );
+    }
+}
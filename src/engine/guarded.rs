@@ -0,0 +1,184 @@
+// Guarded extraction - one file isolated from the rest of a batch
+//
+// A panic becomes an error for that file, extraction runs on the worker pool's
+// large-stack threads and is abandoned after a wall-clock timeout, and files
+// nested deeper than a limit are refused before Julie's recursive tree walks
+// see them. An abandoned file is cancelled: the worker skips it if it has not
+// started, or drops it at the next stage boundary. Julie does not bound its own
+// recursion, so the nesting check counts matched brackets outside strings and
+// comments in the raw text (HTML/XML tag nesting is not counted).
+
+use super::worker_pool::POOL;
+use super::{extract_lenient_until, EnrichmentOptions, ExtractionError, ExtractionLimits};
+use crate::enrichment::base::{CodeBytes, Lexicon};
+use julie_extractors::ExtractionResults;
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+
+/// `extract_lenient` isolated from the caller: panics, timeouts, and deep nesting are errors
+///
+/// On timeout the file is cancelled and its results, if any, are discarded; a
+/// worker inside one of Julie's stages stays busy until that stage returns.
+pub fn extract_guarded(
+    file_path: &str,
+    content: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+    limits: &ExtractionLimits,
+) -> Result<ExtractionResults, ExtractionError> {
+    if let Some(limit) = limits.max_nesting_depth {
+        let language = super::detect_language(Path::new(file_path), Some(content));
+        let depth = nesting_depth(content, crate::enrichment::lexicon(language));
+        if depth > limit {
+            return Err(ExtractionError::DepthExceeded { depth, limit });
+        }
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let worker_cancelled = Arc::clone(&cancelled);
+    let (file_path, content) = (file_path.to_string(), content.to_string());
+    let (workspace_root, options) = (workspace_root.to_path_buf(), options.clone());
+    POOL.submit(Box::new(move || {
+        if worker_cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        let result = match panic::catch_unwind(AssertUnwindSafe(|| {
+            extract_lenient_until(
+                &file_path,
+                &content,
+                &workspace_root,
+                &options,
+                &worker_cancelled,
+            )
+        })) {
+            Ok(Some(results)) => Ok(results),
+            // Cancelled: the caller has already given up on this file
+            Ok(None) => return,
+            Err(payload) => Err(panic_message(&*payload)),
+        };
+        // The receiver is gone if the caller gave up after the last stage
+        let _ = sender.send(result);
+    }))
+    .map_err(|e| ExtractionError::Symbols(format!("could not start worker thread: {}", e)))?;
+
+    let received = match limits.timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+            RecvTimeoutError::Timeout => ExtractionError::TimedOut(timeout),
+            RecvTimeoutError::Disconnected => worker_died(),
+        }),
+        None => receiver.recv().map_err(|_| worker_died()),
+    };
+    if received.is_err() {
+        cancelled.store(true, Ordering::Relaxed);
+    }
+    received?.map_err(ExtractionError::Panicked)
+}
+
+/// Deepest nesting of matched (), [], and {} outside strings and comments
+///
+/// Brackets are paired like a parser would pair them: a closer that does not
+/// match the innermost open bracket is ignored, and an opener that is never
+/// closed adds no depth to what follows it.
+fn nesting_depth(content: &str, lexicon: Lexicon) -> usize {
+    let brackets: Vec<u8> = CodeBytes::new(content, 0, lexicon)
+        .map(|(_, byte)| byte)
+        .filter(|byte| b"()[]{}".contains(byte))
+        .collect();
+
+    let mut matched = vec![false; brackets.len()];
+    let mut open: Vec<usize> = Vec::new();
+    for (index, &byte) in brackets.iter().enumerate() {
+        let opener = match byte {
+            b')' => b'(',
+            b']' => b'[',
+            b'}' => b'{',
+            _ => {
+                open.push(index);
+                continue;
+            }
+        };
+        if let Some(&top) = open.last() {
+            if brackets[top] == opener {
+                open.pop();
+                matched[top] = true;
+                matched[index] = true;
+            }
+        }
+    }
+
+    let mut depth = 0usize;
+    let mut deepest = 0;
+    for (&byte, _) in brackets.iter().zip(&matched).filter(|(_, &m)| m) {
+        if matches!(byte, b'(' | b'[' | b'{') {
+            depth += 1;
+            deepest = deepest.max(depth);
+        } else {
+            depth -= 1;
+        }
+    }
+    deepest
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".to_string())
+}
+
+fn worker_died() -> ExtractionError {
+    ExtractionError::Panicked("extraction worker exited without a result".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guarded_extraction_refuses_deep_nesting() {
+        let limits = ExtractionLimits {
+            max_nesting_depth: Some(3),
+            ..ExtractionLimits::default()
+        };
+        let options = EnrichmentOptions::default();
+        let root = Path::new(".");
+
+        let error = extract_guarded("deep.js", "f([{(x)}]);", root, &options, &limits).unwrap_err();
+        assert!(matches!(
+            error,
+            ExtractionError::DepthExceeded { depth: 4, limit: 3 }
+        ));
+        assert_eq!(error.kind(), "depth_exceeded");
+
+        assert!(extract_guarded("ok.js", "f([{x}]);", root, &options, &limits).is_ok());
+    }
+
+    #[test]
+    fn test_nesting_depth_counts_matched_brackets_in_code() {
+        assert_eq!(nesting_depth("f([{(x)}]);", Lexicon::C_LIKE), 4);
+        // Brackets in strings and comments are not code
+        assert_eq!(
+            nesting_depth("f(\"((((\", /* [[[[ */ x) // {{{{", Lexicon::C_LIKE),
+            1
+        );
+        // Unclosed openers and stray closers add nothing
+        assert_eq!(nesting_depth("(((((( f(x) ]]", Lexicon::C_LIKE), 1);
+        assert_eq!(nesting_depth("a(b[c)d]", Lexicon::C_LIKE), 1);
+        // Markup quotes are prose
+        assert_eq!(nesting_depth("<p>don't (see) it</p>", Lexicon::PLAIN), 1);
+    }
+
+    #[test]
+    fn test_panic_message_keeps_payload_text() {
+        let payload = panic::catch_unwind(|| panic!("bad node at {}", 7)).unwrap_err();
+        assert_eq!(panic_message(&*payload), "bad node at 7");
+        let payload = panic::catch_unwind(|| panic!("static")).unwrap_err();
+        assert_eq!(panic_message(&*payload), "static");
+    }
+}
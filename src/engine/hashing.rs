@@ -0,0 +1,178 @@
+// File hashing - blake3 digests for change detection
+//
+// `hash_files` streams each file through blake3 on the rayon pool without
+// holding it in memory, refusing paths that leave the workspace root and
+// reporting every file's outcome (missing, too large, unsupported) instead
+// of failing the batch.
+
+use crate::utils::file_utils::{is_supported_file, secure_path_resolution};
+use std::fs;
+use std::path::{Component, Path};
+
+/// blake3 hex digest used for change detection
+pub fn hash_content(content: &str) -> String {
+    blake3::hash(content.as_bytes()).to_hex().to_string()
+}
+
+/// Outcome of hashing one file on disk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileHash {
+    /// blake3 hex digest of the file's bytes (equal to `hash_content` of its text)
+    Hashed(String),
+    /// Larger than the size limit; not read
+    TooLarge {
+        size: u64,
+        limit: u64,
+    },
+    /// Rejected by `is_supported_file`; not read
+    Unsupported,
+    /// The path leaves the workspace root; not read
+    PathRejected(String),
+    Missing,
+    PermissionDenied,
+    /// Any other I/O failure
+    Failed(String),
+}
+
+impl FileHash {
+    /// Stable name for the outcome: "ok", "too_large", "unsupported", "missing", ...
+    pub fn status(&self) -> &'static str {
+        match self {
+            FileHash::Hashed(_) => "ok",
+            FileHash::TooLarge { .. } => "too_large",
+            FileHash::Unsupported => "unsupported",
+            FileHash::PathRejected(_) => "path_rejected",
+            FileHash::Missing => "missing",
+            FileHash::PermissionDenied => "permission_denied",
+            FileHash::Failed(_) => "read_error",
+        }
+    }
+
+    pub fn digest(&self) -> Option<&str> {
+        match self {
+            FileHash::Hashed(digest) => Some(digest),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for FileHash {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => FileHash::Missing,
+            std::io::ErrorKind::PermissionDenied => FileHash::PermissionDenied,
+            _ => FileHash::Failed(error.to_string()),
+        }
+    }
+}
+
+/// Hash a file by streaming it through blake3, never holding it in memory
+///
+/// Files above `max_size` bytes are reported as TooLarge without being read.
+pub fn hash_file(path: &Path, max_size: Option<u64>) -> FileHash {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) => return e.into(),
+    };
+    let size = match file.metadata() {
+        Ok(metadata) => metadata.len(),
+        Err(e) => return e.into(),
+    };
+    if let Some(limit) = max_size.filter(|&limit| size > limit) {
+        return FileHash::TooLarge { size, limit };
+    }
+    let mut hasher = blake3::Hasher::new();
+    match hasher.update_reader(file) {
+        Ok(_) => FileHash::Hashed(hasher.finalize().to_hex().to_string()),
+        Err(e) => e.into(),
+    }
+}
+
+/// Reject a relative path that resolves outside `workspace_root`
+///
+/// Only paths with `..`, a root, or a prefix are resolved; plain relative paths
+/// stay inside the root by construction.
+pub(super) fn check_workspace_path(rel_path: &str, workspace_root: &Path) -> Result<(), String> {
+    let escapes = Path::new(rel_path).components().any(|component| {
+        matches!(
+            component,
+            Component::ParentDir | Component::RootDir | Component::Prefix(_)
+        )
+    });
+    if escapes {
+        secure_path_resolution(rel_path, workspace_root).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Hash files relative to `workspace_root` in parallel, in input order
+///
+/// With `supported_only`, files `is_supported_file` rejects are reported as
+/// Unsupported instead of being read. Paths that leave the workspace (`..`,
+/// absolute paths outside it) are PathRejected, as in `extract_path`.
+pub fn hash_files(
+    paths: &[String],
+    workspace_root: &Path,
+    max_size: Option<u64>,
+    supported_only: bool,
+) -> Vec<FileHash> {
+    use rayon::prelude::*;
+
+    paths
+        .par_iter()
+        .map(|path| {
+            if let Err(e) = check_workspace_path(path, workspace_root) {
+                return FileHash::PathRejected(e);
+            }
+            let full_path = workspace_root.join(path);
+            if supported_only && !is_supported_file(&full_path) {
+                return FileHash::Unsupported;
+            }
+            hash_file(&full_path, max_size)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_files_streams_and_reports_per_file_states() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.py"), "def foo(): pass\n").unwrap();
+        std::fs::write(dir.path().join("big.py"), "x = 1\n".repeat(100)).unwrap();
+        std::fs::write(dir.path().join("logo.png"), [0u8, 1, 2]).unwrap();
+        let paths: Vec<String> = ["app.py", "missing.py", "big.py", "logo.png"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+
+        let hashes = hash_files(&paths, dir.path(), Some(100), true);
+        assert_eq!(
+            hashes[0],
+            FileHash::Hashed(hash_content("def foo(): pass\n"))
+        );
+        assert_eq!(hashes[1], FileHash::Missing);
+        assert_eq!(
+            hashes[2],
+            FileHash::TooLarge {
+                size: 600,
+                limit: 100
+            }
+        );
+        assert_eq!(hashes[3].status(), "unsupported");
+
+        // A real file, one level above the workspace
+        let workspace = dir.path().join("workspace");
+        std::fs::create_dir(&workspace).unwrap();
+        let outside = hash_files(&["../app.py".to_string()], &workspace, None, false);
+        assert_eq!(outside[0].status(), "path_rejected");
+
+        let unfiltered = hash_files(&paths[3..], dir.path(), None, false);
+        assert_eq!(
+            unfiltered[0].digest(),
+            Some(blake3::hash(&[0, 1, 2]).to_hex().as_str())
+        );
+    }
+}
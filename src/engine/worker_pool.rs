@@ -0,0 +1,152 @@
+// Worker pool - long-lived, large-stack threads for guarded extraction
+//
+// Julie's visitors recurse once per tree level, so extraction runs on threads
+// with a large stack rather than on the caller's. Workers are started on
+// demand up to a fixed limit and reused for later files; jobs beyond that wait
+// in a queue. A worker still running a job whose caller timed out stays busy
+// until the job reaches a cancellation check, so the limit also bounds how
+// many abandoned extractions can run at once.
+
+use std::collections::VecDeque;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, LazyLock, Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+/// Stack for extraction worker threads; Julie's visitors recurse once per tree level
+const WORKER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Work queued on the pool
+pub(super) type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// The pool guarded extraction runs on: twice rayon's threads, so batch
+/// callers keep running while a few workers finish abandoned files
+pub(super) static POOL: LazyLock<Arc<WorkerPool>> =
+    LazyLock::new(|| Arc::new(WorkerPool::new(rayon::current_num_threads() * 2)));
+
+pub(super) struct WorkerPool {
+    max_workers: usize,
+    state: Mutex<PoolState>,
+    job_queued: Condvar,
+}
+
+#[derive(Default)]
+struct PoolState {
+    jobs: VecDeque<Job>,
+    workers: usize,
+    idle: usize,
+}
+
+impl WorkerPool {
+    pub(super) fn new(max_workers: usize) -> Self {
+        WorkerPool {
+            max_workers: max_workers.max(1),
+            state: Mutex::new(PoolState::default()),
+            job_queued: Condvar::new(),
+        }
+    }
+
+    /// Queue a job, starting a worker when none is idle and the pool is below its limit
+    ///
+    /// Fails only when the pool has no workers and none could be started.
+    pub(super) fn submit(self: &Arc<Self>, job: Job) -> io::Result<()> {
+        let mut state = self.state();
+        state.jobs.push_back(job);
+        if state.idle >= state.jobs.len() || state.workers >= self.max_workers {
+            self.job_queued.notify_one();
+            return Ok(());
+        }
+        let pool = Arc::clone(self);
+        let spawned = thread::Builder::new()
+            .name("miller-extract".to_string())
+            .stack_size(WORKER_STACK_SIZE)
+            .spawn(move || pool.work());
+        match spawned {
+            Ok(_) => state.workers += 1,
+            Err(e) if state.workers == 0 => {
+                state.jobs.pop_back();
+                return Err(e);
+            }
+            // An existing worker picks the job up once it is free
+            Err(_) => self.job_queued.notify_one(),
+        }
+        Ok(())
+    }
+
+    fn work(&self) {
+        loop {
+            let job = {
+                let mut state = self.state();
+                loop {
+                    if let Some(job) = state.jobs.pop_front() {
+                        break job;
+                    }
+                    state.idle += 1;
+                    state = self
+                        .job_queued
+                        .wait(state)
+                        .unwrap_or_else(PoisonError::into_inner);
+                    state.idle -= 1;
+                }
+            };
+            // Jobs report their own panics; this only keeps the worker alive
+            let _ = panic::catch_unwind(AssertUnwindSafe(job));
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        // Nothing panics while holding the lock, so the state is always consistent
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_pool_reuses_a_bounded_number_of_workers() {
+        let pool = Arc::new(WorkerPool::new(2));
+        let (gate, opened) = mpsc::channel::<()>();
+        let opened = Arc::new(Mutex::new(opened));
+        let (sender, receiver) = mpsc::channel();
+        for index in 0..8 {
+            let sender = sender.clone();
+            let opened = Arc::clone(&opened);
+            pool.submit(Box::new(move || {
+                // The first job holds its worker until the gate opens
+                if index == 0 {
+                    opened.lock().unwrap().recv().unwrap();
+                }
+                sender.send((index, thread::current().id())).unwrap();
+            }))
+            .unwrap();
+        }
+
+        // The other seven run on the second worker while the first is busy
+        let early: Vec<usize> = receiver.iter().take(7).map(|(index, _)| index).collect();
+        assert_eq!(early, (1..8).collect::<Vec<_>>());
+        gate.send(()).unwrap();
+        let (last, _) = receiver.recv().unwrap();
+        assert_eq!(last, 0);
+
+        let state = pool.state();
+        assert_eq!(state.workers, 2);
+        assert!(state.jobs.is_empty());
+        drop(state);
+
+        let threads: HashSet<_> = (0..4)
+            .map(|_| {
+                let sender = sender.clone();
+                pool.submit(Box::new(move || {
+                    sender.send((0, thread::current().id())).unwrap()
+                }))
+                .unwrap();
+                receiver.recv().unwrap().1
+            })
+            .collect();
+        assert!(threads.len() <= 2);
+    }
+}
@@ -0,0 +1,215 @@
+// Enrichment Base - Shared context and helpers for Miller's post-extraction passes
+//
+// Plays the role Julie's BaseExtractor plays for the extractors: byte → line/column
+// mapping, symbol/identifier/relationship construction with Julie-compatible fields,
+// and small text scanners that understand nesting, string literals, and comments.
+
+mod builders;
+pub mod dedup;
+pub mod doc_comments;
+pub mod error_recovery;
+mod lexer;
+pub mod source_text;
+pub mod symbol_index;
+
+pub use builders::{append_metadata, set_metadata, SymbolOptions};
+pub use lexer::{
+    code_mask, find_matching_delimiter, paren_depths, split_top_level, top_level_mask,
+    trim_with_offset, CodeBytes, Lexicon,
+};
+
+use super::EnrichmentOptions;
+use crate::utils::file_utils::secure_path_resolution;
+use crate::utils::paths::to_relative_unix_style;
+use julie_extractors::{Identifier, Symbol};
+use std::path::Path;
+
+/// Per-file state shared by every enrichment pass
+pub struct EnrichmentContext<'a> {
+    pub file_path: &'a str,
+    pub language: &'a str,
+    pub content: &'a str,
+    pub workspace_root: &'a Path,
+    pub options: EnrichmentOptions,
+    /// Byte offset of the first character of every line
+    line_starts: Vec<usize>,
+}
+
+impl<'a> EnrichmentContext<'a> {
+    pub fn new(
+        file_path: &'a str,
+        language: &'a str,
+        content: &'a str,
+        workspace_root: &'a Path,
+    ) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(
+            content
+                .bytes()
+                .enumerate()
+                .filter(|(_, b)| *b == b'\n')
+                .map(|(i, _)| i + 1),
+        );
+
+        Self {
+            file_path,
+            language,
+            content,
+            workspace_root,
+            options: EnrichmentOptions::default(),
+            line_starts,
+        }
+    }
+
+    /// Replace the default options
+    pub fn with_options(mut self, options: EnrichmentOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Convert a byte offset into a (1-based line, 0-based byte column) pair,
+    /// matching the convention Julie uses for tree-sitter positions.
+    pub fn position(&self, byte: usize) -> (u32, u32) {
+        let line_index = match self.line_starts.binary_search(&byte) {
+            Ok(index) => index,
+            Err(index) => index - 1,
+        };
+        let column = byte - self.line_starts[line_index];
+        (line_index as u32 + 1, column as u32)
+    }
+
+    /// 1-based line number of a byte offset
+    pub fn line_of(&self, byte: usize) -> u32 {
+        self.position(byte).0
+    }
+
+    /// Slice the file content, returning "" for out-of-range or non-boundary offsets
+    pub fn text(&self, start_byte: usize, end_byte: usize) -> &'a str {
+        let end_byte = end_byte.min(self.content.len());
+        if start_byte > end_byte
+            || !self.content.is_char_boundary(start_byte)
+            || !self.content.is_char_boundary(end_byte)
+        {
+            return "";
+        }
+        &self.content[start_byte..end_byte]
+    }
+
+    /// Source text covered by a symbol
+    pub fn symbol_text(&self, symbol: &Symbol) -> &'a str {
+        self.text(symbol.start_byte as usize, symbol.end_byte as usize)
+    }
+
+    /// Resolve a path written relative to the current file's directory.
+    ///
+    /// Returns the workspace-relative Unix-style path when the target is an existing
+    /// file inside the workspace; traversal outside the workspace resolves to None.
+    pub fn resolve_relative_to_file(&self, target: &str) -> Option<String> {
+        let base_dir = Path::new(self.file_path)
+            .parent()
+            .unwrap_or_else(|| Path::new(""));
+        self.resolve_workspace_path(&base_dir.join(target).to_string_lossy())
+    }
+
+    /// Resolve a workspace-relative (or absolute) path to its workspace-relative
+    /// Unix-style form if it names an existing file inside the workspace
+    pub fn resolve_workspace_path(&self, candidate: &str) -> Option<String> {
+        let absolute = secure_path_resolution(candidate, self.workspace_root).ok()?;
+        if !absolute.is_file() {
+            return None;
+        }
+        let root = self.workspace_root.canonicalize().ok()?;
+        to_relative_unix_style(&absolute, &root).ok()
+    }
+
+    /// Resolve a workspace-relative path to its Unix-style form if it names an
+    /// existing directory inside the workspace
+    pub fn resolve_workspace_directory(&self, candidate: &str) -> Option<String> {
+        let absolute = secure_path_resolution(candidate, self.workspace_root).ok()?;
+        if !absolute.is_dir() {
+            return None;
+        }
+        let root = self.workspace_root.canonicalize().ok()?;
+        to_relative_unix_style(&absolute, &root).ok()
+    }
+}
+
+/// Whitespace runs collapsed to single spaces, trimmed
+pub(crate) fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Innermost symbol whose byte range contains `byte`, optionally restricted by a predicate
+///
+/// Scans every symbol; passes looking up many bytes should build a
+/// symbol_index::SymbolIndex once instead.
+pub fn innermost_symbol_at(
+    symbols: &[Symbol],
+    byte: usize,
+    accept: impl Fn(&Symbol) -> bool,
+) -> Option<&Symbol> {
+    symbols
+        .iter()
+        .filter(|s| (s.start_byte as usize) <= byte && byte < (s.end_byte as usize))
+        .filter(|s| accept(s))
+        .min_by_key(|s| s.end_byte - s.start_byte)
+}
+
+/// Point identifiers starting inside any of `ranges` at their innermost accepted
+/// containing symbol, for passes that add symbols owning code Julie attributed
+/// elsewhere (or to nothing). Returns the indices of identifiers that changed.
+pub fn reattribute_identifiers(
+    identifiers: &mut [Identifier],
+    symbols: &[Symbol],
+    ranges: &[(usize, usize)],
+    accept: impl Fn(&Symbol) -> bool,
+) -> Vec<usize> {
+    let mut changed = Vec::new();
+    if ranges.is_empty() {
+        return changed;
+    }
+    let containers = symbol_index::SymbolIndex::new(symbols);
+    for (index, identifier) in identifiers.iter_mut().enumerate() {
+        let byte = identifier.start_byte as usize;
+        if !ranges
+            .iter()
+            .any(|&(start, end)| start <= byte && byte < end)
+        {
+            continue;
+        }
+        let Some(container) = containers.innermost_at(byte, &accept) else {
+            continue;
+        };
+        if identifier.containing_symbol_id.as_deref() != Some(container.id.as_str()) {
+            identifier.containing_symbol_id = Some(container.id.clone());
+            changed.push(index);
+        }
+    }
+    changed
+}
+
+/// Results holding only `symbols`, for tests of passes over Julie's output
+#[cfg(test)]
+pub(crate) fn test_results(symbols: Vec<Symbol>) -> julie_extractors::ExtractionResults {
+    julie_extractors::ExtractionResults {
+        symbols,
+        identifiers: Vec::new(),
+        relationships: Vec::new(),
+        pending_relationships: Vec::new(),
+        types: std::collections::HashMap::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_is_one_based_line_zero_based_column() {
+        let ctx = EnrichmentContext::new("a.txt", "text", "ab\ncd\n", Path::new("."));
+        assert_eq!(ctx.position(0), (1, 0));
+        assert_eq!(ctx.position(1), (1, 1));
+        assert_eq!(ctx.position(3), (2, 0));
+        assert_eq!(ctx.position(4), (2, 1));
+    }
+}
@@ -0,0 +1,155 @@
+// Enrichment Base - symbol, identifier, and relationship construction
+//
+// Builds Julie-compatible records at byte offsets of the file, so passes never
+// compute line/column positions or IDs themselves.
+
+use super::EnrichmentContext;
+use crate::enrichment::confidence;
+use julie_extractors::{
+    Identifier, IdentifierKind, Relationship, RelationshipKind, Symbol, SymbolKind, Visibility,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Optional fields for symbols created by enrichment passes
+#[derive(Debug, Clone, Default)]
+pub struct SymbolOptions {
+    pub signature: Option<String>,
+    pub visibility: Option<Visibility>,
+    pub parent_id: Option<String>,
+    pub metadata: Option<HashMap<String, Value>>,
+    pub doc_comment: Option<String>,
+    /// Score from enrichment::confidence; confidence::ENRICHED when None,
+    /// lower for symbols made on a recovery path
+    pub confidence: Option<f32>,
+}
+
+impl EnrichmentContext<'_> {
+    /// Generate a symbol ID from file path, name, and position
+    ///
+    /// Provisional: stable_ids replaces every symbol ID once enrichment is done.
+    pub fn generate_id(&self, name: &str, start_byte: usize) -> String {
+        let (line, column) = self.position(start_byte);
+        let key = format!("{}:{}:{}:{}", self.file_path, name, line, column);
+        blake3::hash(key.as_bytes()).to_hex()[..32].to_string()
+    }
+
+    /// Create a symbol spanning `start_byte..end_byte` of the file
+    ///
+    /// Pass-made symbols are heuristic: they score confidence::ENRICHED unless
+    /// `options` gives a score.
+    pub fn create_symbol(
+        &self,
+        name: &str,
+        kind: SymbolKind,
+        start_byte: usize,
+        end_byte: usize,
+        options: SymbolOptions,
+    ) -> Symbol {
+        let (start_line, start_column) = self.position(start_byte);
+        let (end_line, end_column) = self.position(end_byte);
+
+        Symbol {
+            id: self.generate_id(name, start_byte),
+            name: name.to_string(),
+            kind,
+            language: self.language.to_string(),
+            file_path: self.file_path.to_string(),
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+            start_byte: start_byte as u32,
+            end_byte: end_byte as u32,
+            signature: options.signature,
+            doc_comment: options.doc_comment,
+            visibility: options.visibility,
+            parent_id: options.parent_id,
+            metadata: options.metadata,
+            semantic_group: None,
+            confidence: Some(options.confidence.unwrap_or(confidence::ENRICHED)),
+            code_context: None,
+            content_type: None,
+        }
+    }
+
+    /// Create an identifier (usage reference) spanning `start_byte..end_byte`
+    pub fn create_identifier(
+        &self,
+        name: &str,
+        kind: IdentifierKind,
+        start_byte: usize,
+        end_byte: usize,
+        containing_symbol_id: Option<String>,
+    ) -> Identifier {
+        let (start_line, start_column) = self.position(start_byte);
+        let (end_line, end_column) = self.position(end_byte);
+
+        Identifier {
+            id: self.generate_id(name, start_byte),
+            name: name.to_string(),
+            kind,
+            language: self.language.to_string(),
+            file_path: self.file_path.to_string(),
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+            start_byte: start_byte as u32,
+            end_byte: end_byte as u32,
+            containing_symbol_id,
+            target_symbol_id: None,
+            confidence: 1.0,
+            code_context: None,
+        }
+    }
+
+    /// Create a relationship anchored at the line containing `byte`
+    pub fn create_relationship(
+        &self,
+        from_symbol_id: &str,
+        to_symbol_id: &str,
+        kind: RelationshipKind,
+        byte: usize,
+        metadata: Option<HashMap<String, Value>>,
+    ) -> Relationship {
+        let line_number = self.line_of(byte);
+
+        Relationship {
+            id: format!(
+                "{}_{}_{:?}_{}",
+                from_symbol_id, to_symbol_id, kind, line_number
+            ),
+            from_symbol_id: from_symbol_id.to_string(),
+            to_symbol_id: to_symbol_id.to_string(),
+            kind,
+            file_path: self.file_path.to_string(),
+            line_number,
+            confidence: 1.0,
+            metadata,
+        }
+    }
+}
+
+/// Insert (or overwrite) a metadata entry on a symbol
+pub fn set_metadata(symbol: &mut Symbol, key: &str, value: impl Into<Value>) {
+    symbol
+        .metadata
+        .get_or_insert_with(HashMap::new)
+        .insert(key.to_string(), value.into());
+}
+
+/// Append a value to an array metadata entry, creating the array if needed
+pub fn append_metadata(symbol: &mut Symbol, key: &str, value: impl Into<Value>) {
+    let entry = symbol
+        .metadata
+        .get_or_insert_with(HashMap::new)
+        .entry(key.to_string())
+        .or_insert_with(|| Value::Array(Vec::new()));
+    if !entry.is_array() {
+        *entry = Value::Array(vec![entry.take()]);
+    }
+    if let Value::Array(items) = entry {
+        items.push(value.into());
+    }
+}
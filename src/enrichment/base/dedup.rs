@@ -0,0 +1,267 @@
+// Symbol de-duplication - collapse symbols extracted twice
+//
+// Several extractors can emit the same declaration twice: GDScript's var/func
+// branches and their statement wrappers, Go's function-over-field preference,
+// Dart's function_signature inside method_signature, and SQL's ERROR scraper
+// re-extracting a view a later well-formed node also yields. Rather than each
+// extractor guarding itself, every file's final symbols pass through here once.
+// Two symbols of the same kind and name are duplicates when:
+//
+// - their spans are identical, or
+// - one span contains the other and either symbol came from a recovery path
+//   (metadata.extractedFromError or confidence below ENRICHED); two well-formed
+//   nested namesakes, a `def f` inside `def f` or a `<div>` inside a `<div>`,
+//   are real symbols
+//
+// The survivor is the one with the highest confidence, then the tighter span
+// (error paths over-span), then the earlier one. References to a dropped
+// symbol's ID (parents, identifiers, relationships, pending relationships,
+// types) move to the survivor; relationships that would become self-loops are
+// dropped.
+
+use crate::enrichment::confidence;
+use julie_extractors::{ExtractionResults, Symbol};
+use std::collections::{HashMap, HashSet};
+
+/// Collapse duplicate symbols, rewriting references to the dropped ones
+pub fn dedup_symbols(results: &mut ExtractionResults) {
+    let remap = duplicates(&results.symbols);
+    if remap.is_empty() {
+        return;
+    }
+    let rewrite = |id: &mut String| {
+        if let Some(kept) = remap.get(id.as_str()) {
+            *id = kept.clone();
+        }
+    };
+
+    let mut seen = HashSet::new();
+    results.symbols.retain(|s| match remap.get(&s.id) {
+        None => true,
+        Some(kept) if kept != &s.id => false,
+        // Both copies share an ID: keep the first
+        Some(_) => seen.insert(s.id.clone()),
+    });
+    for symbol in &mut results.symbols {
+        if let Some(parent_id) = symbol.parent_id.as_mut() {
+            rewrite(parent_id);
+        }
+    }
+    for identifier in &mut results.identifiers {
+        if let Some(id) = identifier.containing_symbol_id.as_mut() {
+            rewrite(id);
+        }
+        if let Some(id) = identifier.target_symbol_id.as_mut() {
+            rewrite(id);
+        }
+    }
+    results.relationships.retain_mut(|relationship| {
+        let was_loop = relationship.from_symbol_id == relationship.to_symbol_id;
+        rewrite(&mut relationship.from_symbol_id);
+        rewrite(&mut relationship.to_symbol_id);
+        was_loop || relationship.from_symbol_id != relationship.to_symbol_id
+    });
+    for pending in &mut results.pending_relationships {
+        rewrite(&mut pending.from_symbol_id);
+    }
+    for (dropped, kept) in &remap {
+        if let Some(mut info) = results.types.remove(dropped) {
+            if !results.types.contains_key(kept) {
+                info.symbol_id = kept.clone();
+                results.types.insert(kept.clone(), info);
+            }
+        }
+    }
+}
+
+/// Dropped symbol ID → surviving symbol ID
+fn duplicates(symbols: &[Symbol]) -> HashMap<String, String> {
+    let mut groups: HashMap<(String, &str), Vec<usize>> = HashMap::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        groups
+            .entry((symbol.kind.to_string(), symbol.name.as_str()))
+            .or_default()
+            .push(index);
+    }
+
+    let mut remap = HashMap::new();
+    for mut group in groups.into_values().filter(|g| g.len() > 1) {
+        group.sort_by(|&a, &b| {
+            let (a_symbol, b_symbol) = (&symbols[a], &symbols[b]);
+            score(b_symbol)
+                .total_cmp(&score(a_symbol))
+                .then(span_len(a_symbol).cmp(&span_len(b_symbol)))
+                .then(a.cmp(&b))
+        });
+        let mut kept: Vec<usize> = Vec::new();
+        for index in group {
+            let symbol = &symbols[index];
+            match kept.iter().find(|&&k| is_duplicate(&symbols[k], symbol)) {
+                Some(&survivor) => {
+                    remap.insert(symbol.id.clone(), symbols[survivor].id.clone());
+                }
+                None => kept.push(index),
+            }
+        }
+    }
+    remap
+}
+
+fn is_duplicate(a: &Symbol, b: &Symbol) -> bool {
+    if (a.start_byte, a.end_byte) == (b.start_byte, b.end_byte) {
+        return true;
+    }
+    (contains(a, b) || contains(b, a)) && (recovered(a) || recovered(b))
+}
+
+/// Whether the symbol came from text the parser could not handle
+fn recovered(symbol: &Symbol) -> bool {
+    confidence::flagged(symbol, "extractedFromError") || score(symbol) < confidence::ENRICHED
+}
+
+fn contains(outer: &Symbol, inner: &Symbol) -> bool {
+    outer.start_byte <= inner.start_byte && inner.end_byte <= outer.end_byte
+}
+
+fn score(symbol: &Symbol) -> f32 {
+    symbol.confidence.unwrap_or(confidence::PARSED)
+}
+
+fn span_len(symbol: &Symbol) -> u32 {
+    symbol.end_byte.saturating_sub(symbol.start_byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{set_metadata, test_results, EnrichmentContext, SymbolOptions};
+    use crate::enrichment::enrich_results;
+    use julie_extractors::{IdentifierKind, RelationshipKind, SymbolKind};
+    use std::path::Path;
+
+    #[test]
+    fn test_sql_error_scraper_view_collapses_into_parsed_view() {
+        // The broken table leaves an ERROR node that also swallows the view; the
+        // scraper names the view from it, then the well-formed node yields it again
+        let content =
+            "CREATE TABLE users (id INT,;\nCREATE VIEW active_users AS SELECT * FROM users;\n";
+        let ctx = EnrichmentContext::new("schema.sql", "sql", content, Path::new("."));
+        let view_start = content.find("CREATE VIEW").unwrap();
+        let mut scraped = ctx.create_symbol(
+            "active_users",
+            SymbolKind::Interface,
+            0,
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        set_metadata(&mut scraped, "extractedFromError", true);
+        let parsed = ctx.create_symbol(
+            "active_users",
+            SymbolKind::Interface,
+            view_start,
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let query = ctx.create_symbol(
+            "users",
+            SymbolKind::Variable,
+            content.rfind("users").unwrap(),
+            content.len() - 2,
+            SymbolOptions {
+                parent_id: Some(scraped.id.clone()),
+                ..Default::default()
+            },
+        );
+        let mut results = test_results(vec![scraped.clone(), parsed.clone(), query]);
+        results.relationships.push(ctx.create_relationship(
+            &scraped.id,
+            &parsed.id,
+            RelationshipKind::References,
+            0,
+            None,
+        ));
+
+        enrich_results("schema.sql", content, Path::new("."), &mut results);
+
+        let views: Vec<&Symbol> = results
+            .symbols
+            .iter()
+            .filter(|s| s.name == "active_users")
+            .collect();
+        assert_eq!(views.len(), 1);
+        assert_eq!(views[0].start_byte as usize, view_start);
+        let users = results.symbols.iter().find(|s| s.name == "users").unwrap();
+        assert_eq!(users.parent_id.as_ref(), Some(&views[0].id));
+        assert!(results.relationships.is_empty());
+    }
+
+    #[test]
+    fn test_identical_spans_collapse_but_nested_namesakes_stay() {
+        let content = "def f():\n    def f():\n        pass\n    return f\n";
+        let ctx = EnrichmentContext::new("nested.py", "python", content, Path::new("."));
+        let outer = ctx.create_symbol(
+            "f",
+            SymbolKind::Function,
+            0,
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let inner = |confidence| {
+            ctx.create_symbol(
+                "f",
+                SymbolKind::Function,
+                13,
+                34,
+                SymbolOptions {
+                    parent_id: Some(outer.id.clone()),
+                    confidence,
+                    ..Default::default()
+                },
+            )
+        };
+        let mut recovered = inner(Some(confidence::RECOVERED));
+        recovered.id = "recovered".to_string();
+        let mut results = test_results(vec![outer.clone(), inner(None), recovered]);
+        results.identifiers.push(ctx.create_identifier(
+            "f",
+            IdentifierKind::VariableRef,
+            content.rfind('f').unwrap(),
+            content.len() - 1,
+            Some(outer.id.clone()),
+        ));
+        results.identifiers[0].target_symbol_id = Some("recovered".to_string());
+
+        dedup_symbols(&mut results);
+
+        assert_eq!(results.symbols.len(), 2);
+        assert_eq!(results.symbols[1].parent_id.as_ref(), Some(&outer.id));
+        assert_eq!(
+            results.identifiers[0].target_symbol_id.as_ref(),
+            Some(&results.symbols[1].id)
+        );
+    }
+
+    #[test]
+    fn test_nested_parsed_namesakes_without_parent_links_stay() {
+        let content = "<div>\n  <div>inner</div>\n</div>\n";
+        let ctx = EnrichmentContext::new("page.html", "html", content, Path::new("."));
+        let inner_start = content.rfind("<div>").unwrap();
+        let div = |start, end| {
+            ctx.create_symbol(
+                "div",
+                SymbolKind::Class,
+                start,
+                end,
+                SymbolOptions::default(),
+            )
+        };
+        let mut results = test_results(vec![
+            div(0, content.len() - 1),
+            div(inner_start, inner_start + "<div>inner</div>".len()),
+        ]);
+
+        dedup_symbols(&mut results);
+
+        assert_eq!(results.symbols.len(), 2);
+    }
+}
@@ -0,0 +1,270 @@
+// Doc Comments - Configurable doc comment discovery
+//
+// Julie associates a doc comment with a declaration only when the comment node
+// immediately precedes it, so a Javadoc above `@Override`, or a `///` block
+// above `#[derive(...)]`, is lost. Languages describe their conventions with a
+// DocCommentStyle (comment markers, whether docs precede or follow the
+// declaration, which lines are attributes to step over, how many blank lines
+// may separate the two) and fill_doc_comments fills in doc_comment for symbols
+// Julie left without one. Comments are kept verbatim, as Julie reports them;
+// languages whose docs are plain comments rather than a doc syntax (SQL) pass
+// them through strip_comment_markers.
+
+use super::{CodeBytes, EnrichmentContext, Lexicon};
+use julie_extractors::Symbol;
+
+/// Where a language writes the docs for a declaration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocPlacement {
+    /// Comment lines above the declaration (`///`, `/** */`)
+    Preceding,
+    /// A string literal opening the body (Python docstrings)
+    Following,
+}
+
+/// A language's doc comment conventions
+#[derive(Debug, Clone, Copy)]
+pub struct DocCommentStyle {
+    /// Prefixes of doc comment lines (`///`)
+    pub line_prefixes: &'static [&'static str],
+    /// Delimiters of doc block comments (`/**`, `*/`)
+    pub block: Option<(&'static str, &'static str)>,
+    pub placement: DocPlacement,
+    /// Prefixes of attribute/annotation lines between docs and declaration
+    pub attribute_prefixes: &'static [&'static str],
+    /// Blank lines allowed between the docs and the declaration
+    pub max_blank_lines: usize,
+    /// Strings and comments of the language, for finding the body of Following docs
+    pub lexicon: Lexicon,
+}
+
+/// Fill in doc_comment for accepted symbols that have none
+pub fn fill_doc_comments(
+    ctx: &EnrichmentContext,
+    symbols: &mut [Symbol],
+    style: &DocCommentStyle,
+    accept: impl Fn(&Symbol) -> bool,
+) {
+    for symbol in symbols.iter_mut() {
+        if symbol.doc_comment.is_some() || !accept(symbol) {
+            continue;
+        }
+        let start = symbol.start_byte as usize;
+        symbol.doc_comment = match style.placement {
+            DocPlacement::Preceding => preceding_doc(ctx.content, start, style),
+            DocPlacement::Following => following_doc(ctx.content, start, style),
+        };
+    }
+}
+
+/// Doc comment above the line holding `start`, stepping over attributes
+pub fn preceding_doc(content: &str, start: usize, style: &DocCommentStyle) -> Option<String> {
+    let line_start = content[..start.min(content.len())]
+        .rfind('\n')
+        .map_or(0, |i| i + 1);
+    // (start, end) of each line above, nearest first
+    let mut offset = 0;
+    let mut lines: Vec<(usize, usize)> = content[..line_start]
+        .split_inclusive('\n')
+        .map(|line| {
+            offset += line.len();
+            (offset - line.len(), offset - 1)
+        })
+        .collect();
+    lines.reverse();
+    let trimmed = |index: usize| content[lines[index].0..lines[index].1].trim();
+
+    let mut blank_lines = 0;
+    // Unclosed brackets of a multi-line attribute being stepped over
+    let mut depth: isize = 0;
+    for index in 0..lines.len() {
+        let line = trimmed(index);
+        let is_attribute = style.attribute_prefixes.iter().any(|p| line.starts_with(p));
+        let delta = line.bytes().filter(|b| matches!(b, b')' | b']')).count() as isize
+            - line.bytes().filter(|b| matches!(b, b'(' | b'[')).count() as isize;
+        if depth > 0 || is_attribute || delta > 0 {
+            depth = (depth + delta).max(0);
+            if depth == 0 && !is_attribute && delta <= 0 {
+                return None;
+            }
+            blank_lines = 0;
+            continue;
+        }
+        if line.is_empty() {
+            blank_lines += 1;
+            if blank_lines > style.max_blank_lines {
+                return None;
+            }
+            continue;
+        }
+
+        let top = if let Some(prefix) = style.line_prefixes.iter().find(|p| line.starts_with(*p)) {
+            (index..lines.len())
+                .take_while(|&i| trimmed(i).starts_with(prefix))
+                .last()?
+        } else {
+            let (open, close) = style.block?;
+            if !line.ends_with(close) {
+                return None;
+            }
+            (index..lines.len()).find(|&i| trimmed(i).starts_with(open))?
+        };
+        return Some(content[lines[top].0..lines[index].1].trim().to_string());
+    }
+    None
+}
+
+/// Comment text without the style's markers: line prefixes, block delimiters,
+/// and the `*` gutter of block comment lines
+pub fn strip_comment_markers(comment: &str, style: &DocCommentStyle) -> String {
+    let comment = comment.trim();
+    let block = style.block.and_then(|(open, close)| {
+        comment
+            .strip_prefix(open)
+            .and_then(|inner| inner.strip_suffix(close))
+    });
+    let lines: Vec<&str> = match block {
+        Some(inner) => inner
+            .lines()
+            .map(|line| {
+                let line = line.trim();
+                line.strip_prefix('*').map_or(line, str::trim_start)
+            })
+            .collect(),
+        None => comment
+            .lines()
+            .map(|line| {
+                let line = line.trim();
+                let text = style
+                    .line_prefixes
+                    .iter()
+                    .find_map(|prefix| line.strip_prefix(prefix))
+                    .unwrap_or(line);
+                text.strip_prefix(' ').unwrap_or(text).trim_end()
+            })
+            .collect(),
+    };
+    let first = lines
+        .iter()
+        .position(|l| !l.is_empty())
+        .unwrap_or(lines.len());
+    let last = lines
+        .iter()
+        .rposition(|l| !l.is_empty())
+        .map_or(first, |i| i + 1);
+    lines[first..last].join("\n")
+}
+
+/// String literal opening the body of the declaration starting at `start`
+pub fn following_doc(content: &str, start: usize, style: &DocCommentStyle) -> Option<String> {
+    let mut depth = 0usize;
+    let colon = CodeBytes::new(content, start, style.lexicon)
+        .find(|&(_, byte)| {
+            match byte {
+                b'(' | b'[' | b'{' => depth += 1,
+                b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            byte == b':' && depth == 0
+        })?
+        .0;
+
+    let mut rest = &content[colon + 1..];
+    loop {
+        rest = rest.trim_start();
+        match style.lexicon.line_comment {
+            Some(marker) if rest.starts_with(marker) => {
+                rest = &rest[rest.find('\n').unwrap_or(rest.len())..];
+            }
+            _ => break,
+        }
+    }
+
+    let literal = rest.trim_start_matches(['r', 'R', 'u', 'U']);
+    let quote = ["\"\"\"", "'''", "\"", "'"]
+        .into_iter()
+        .find(|q| literal.starts_with(q))?;
+    let prefix_len = rest.len() - literal.len();
+    let body = &literal[quote.len()..];
+    let mut escaped = false;
+    for (index, _) in body.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if body[index..].starts_with('\\') {
+            escaped = true;
+        } else if body[index..].starts_with(quote) {
+            return Some(rest[..prefix_len + quote.len() + index + quote.len()].to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SLASHES: DocCommentStyle = DocCommentStyle {
+        line_prefixes: &["///"],
+        block: Some(("/**", "*/")),
+        placement: DocPlacement::Preceding,
+        attribute_prefixes: &["#[", "@"],
+        max_blank_lines: 0,
+        lexicon: Lexicon::C_LIKE,
+    };
+
+    #[test]
+    fn test_line_docs_above_attributes() {
+        let content = "/// Point in space\n/// with two axes\n#[derive(\n    Debug,\n    Clone,\n)]\n#[serde(default)]\npub struct Point;\n";
+        let start = content.find("pub struct").unwrap();
+        assert_eq!(
+            preceding_doc(content, start, &SLASHES).as_deref(),
+            Some("/// Point in space\n/// with two axes")
+        );
+    }
+
+    #[test]
+    fn test_block_docs_above_annotation_and_blank_line_limit() {
+        let content = "/**\n * Runs it.\n */\n@Override\npublic void run() {}\n";
+        let start = content.find("public").unwrap();
+        assert_eq!(
+            preceding_doc(content, start, &SLASHES).as_deref(),
+            Some("/**\n * Runs it.\n */")
+        );
+
+        let separated = "/// Unrelated\n\nfn f() {}\n";
+        let start = separated.find("fn").unwrap();
+        assert_eq!(preceding_doc(separated, start, &SLASHES), None);
+
+        assert_eq!(
+            strip_comment_markers("/**\n * Runs it.\n *   Twice.\n */", &SLASHES),
+            "Runs it.\nTwice."
+        );
+        assert_eq!(
+            strip_comment_markers("/// Point\n///   x, y", &SLASHES),
+            "Point\n  x, y"
+        );
+    }
+
+    #[test]
+    fn test_following_docstring() {
+        let style = DocCommentStyle {
+            line_prefixes: &[],
+            block: None,
+            placement: DocPlacement::Following,
+            attribute_prefixes: &[],
+            max_blank_lines: 0,
+            lexicon: Lexicon {
+                quotes: b"\"'",
+                escape: Some(b'\\'),
+                line_comment: Some("#"),
+                block_comment: None,
+            },
+        };
+        let content = "def f(x: int = 1) -> dict[str, int]:  # note\n    \"\"\"Docs \\\"here\\\".\"\"\"\n    return {}\n";
+        assert_eq!(
+            following_doc(content, 0, &style).as_deref(),
+            Some("\"\"\"Docs \\\"here\\\".\"\"\"")
+        );
+        assert_eq!(following_doc("def g():\n    return 1\n", 0, &style), None);
+    }
+}
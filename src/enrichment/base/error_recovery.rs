@@ -0,0 +1,330 @@
+// Error Recovery - Shared primitives for recovering declarations Julie missed
+//
+// When tree-sitter cannot parse a construct it wraps it in an ERROR node and the
+// extractor either skips it or scrapes it with its own one-off patterns. By the
+// time results reach Miller the ERROR nodes are gone, but their footprint is
+// not: code that no extracted symbol covers. These primitives give every
+// language the same recovery steps over that text:
+//
+// - unclaimed_regions: byte ranges of code outside every symbol
+// - keyword_names: `keyword Name` pairs inside a region (function Foo, CREATE TABLE t)
+// - recover_symbols: caller-supplied regex -> SymbolKind rules run over a region
+//
+// Recovered symbols get absolute byte offsets (so line/column point at the
+// declaration itself, not the start of the region), extend over a following
+// `(...)` and `{...}` when present, carry metadata.extractedFromError = true
+// and confidence::RECOVERED, and are skipped when a symbol of the same name
+// already covers the range.
+
+use super::{code_mask, find_matching_delimiter, set_metadata, EnrichmentContext, Lexicon};
+use super::{innermost_symbol_at, SymbolOptions};
+use crate::enrichment::confidence;
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// A declaration pattern for recover_symbols
+///
+/// The pattern must capture the declared name in a group called `name` and
+/// should stop before any parameter list or body, which recovery spans itself.
+/// A trailing `{` in the match is taken as the opening brace of the body, for
+/// rules that only apply to declarations with one.
+pub struct RecoveryRule {
+    pub pattern: &'static LazyLock<Regex>,
+    pub kind: SymbolKind,
+}
+
+/// A `keyword Name` pair found by keyword_names (offsets absolute in the file)
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeywordName<'t> {
+    pub keyword: &'t str,
+    pub keyword_start: usize,
+    pub name: &'t str,
+    pub name_start: usize,
+}
+
+static WORD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap());
+
+/// Declared name following a keyword: anything up to whitespace or punctuation,
+/// so `Get-Item` and `dbo.users` stay whole
+static NAME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s+([A-Za-z_\[`\x22][^\s(){};,=]*)").unwrap());
+
+/// Byte ranges of code that no symbol covers
+///
+/// Namespace and Module symbols do not claim their bytes, since Julie emits
+/// them around whole files or blocks whose contents may still be unparsed.
+/// Ranges containing only whitespace and comments are dropped.
+pub fn unclaimed_regions(
+    ctx: &EnrichmentContext,
+    symbols: &[Symbol],
+    lexicon: Lexicon,
+) -> Vec<Range<usize>> {
+    let len = ctx.content.len();
+    let mut claimed = vec![false; len];
+    for symbol in symbols
+        .iter()
+        .filter(|s| !matches!(s.kind, SymbolKind::Namespace | SymbolKind::Module))
+    {
+        let end = (symbol.end_byte as usize).min(len);
+        let start = (symbol.start_byte as usize).min(end);
+        claimed[start..end].fill(true);
+    }
+
+    let code = code_mask(ctx.content, lexicon);
+    let bytes = ctx.content.as_bytes();
+    let mut regions = Vec::new();
+    let mut index = 0;
+    while index < len {
+        if claimed[index] {
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < len && !claimed[index] {
+            index += 1;
+        }
+        if (start..index).any(|i| code[i] && !bytes[i].is_ascii_whitespace()) {
+            regions.push(start..index);
+        }
+    }
+    regions
+}
+
+/// `keyword Name` pairs in a region; keywords match case-insensitively and
+/// only outside strings and comments
+pub fn keyword_names<'t>(
+    content: &'t str,
+    region: Range<usize>,
+    keywords: &[&str],
+    lexicon: Lexicon,
+) -> Vec<KeywordName<'t>> {
+    let text = &content[region.clone()];
+    let code = code_mask(text, lexicon);
+    let bytes = text.as_bytes();
+
+    WORD_RE
+        .find_iter(text)
+        .filter(|word| code[word.start()])
+        .filter(|word| {
+            let before = word.start().checked_sub(1).map(|i| bytes[i]);
+            !matches!(before, Some(b'-' | b'.' | b'$' | b'@'))
+        })
+        .filter(|word| {
+            keywords
+                .iter()
+                .any(|k| k.eq_ignore_ascii_case(word.as_str()))
+        })
+        .filter_map(|word| {
+            let name = NAME_RE.captures(&text[word.end()..])?.get(1)?;
+            Some(KeywordName {
+                keyword: &content[region.start + word.start()..region.start + word.end()],
+                keyword_start: region.start + word.start(),
+                name: &content[region.start + word.end() + name.start()
+                    ..region.start + word.end() + name.end()],
+                name_start: region.start + word.end() + name.start(),
+            })
+        })
+        .collect()
+}
+
+/// Run `rules` over a region, returning symbols for matches that no existing
+/// symbol already covers
+pub fn recover_symbols(
+    ctx: &EnrichmentContext,
+    symbols: &[Symbol],
+    region: Range<usize>,
+    rules: &[RecoveryRule],
+    lexicon: Lexicon,
+) -> Vec<Symbol> {
+    let text = &ctx.content[region.clone()];
+    let code = code_mask(text, lexicon);
+    let mut recovered: Vec<Symbol> = Vec::new();
+
+    for rule in rules {
+        for captures in rule.pattern.captures_iter(text) {
+            let whole = captures.get(0).unwrap();
+            let Some(name) = captures.name("name") else {
+                continue;
+            };
+            if !code[whole.start()] {
+                continue;
+            }
+            let start = region.start + whole.start();
+            let header = whole.as_str();
+            let header_end = start + header.strip_suffix('{').unwrap_or(header).len();
+            let name_range = region.start + name.start()..region.start + name.end();
+            if let Some(symbol) = recovered_symbol(
+                ctx,
+                symbols.iter().chain(&recovered),
+                symbols,
+                RecoveredDeclaration {
+                    name: name.as_str(),
+                    kind: rule.kind.clone(),
+                    start,
+                    header_end,
+                    name_range,
+                },
+                lexicon,
+            ) {
+                recovered.push(symbol);
+            }
+        }
+    }
+    recovered.sort_by_key(|s| s.start_byte);
+    recovered
+}
+
+/// A declaration located in unparsed text
+pub struct RecoveredDeclaration<'t> {
+    pub name: &'t str,
+    pub kind: SymbolKind,
+    /// Start of the declaration (its first keyword or modifier)
+    pub start: usize,
+    /// End of the declaration header, where a parameter list or body may follow
+    pub header_end: usize,
+    pub name_range: Range<usize>,
+}
+
+/// Build the symbol for a recovered declaration, or None if one of `existing`
+/// with the same name already covers it. The parent is the innermost
+/// container in `containers` around the declaration.
+pub fn recovered_symbol<'s>(
+    ctx: &EnrichmentContext,
+    mut existing: impl Iterator<Item = &'s Symbol>,
+    containers: &[Symbol],
+    declaration: RecoveredDeclaration,
+    lexicon: Lexicon,
+) -> Option<Symbol> {
+    let RecoveredDeclaration {
+        name,
+        kind,
+        start,
+        header_end,
+        name_range,
+    } = declaration;
+    let end = declaration_end(ctx.content, header_end, lexicon);
+
+    let duplicate = existing.any(|s| {
+        s.name == name
+            && (s.start_byte as usize) < end.max(name_range.end)
+            && start.min(name_range.start) < (s.end_byte as usize)
+    });
+    if duplicate {
+        return None;
+    }
+
+    let parent_id = innermost_symbol_at(containers, start, |s| {
+        matches!(
+            s.kind,
+            SymbolKind::Class
+                | SymbolKind::Struct
+                | SymbolKind::Interface
+                | SymbolKind::Namespace
+                | SymbolKind::Module
+        ) && (s.start_byte as usize) < start
+    })
+    .map(|s| s.id.clone());
+
+    let signature = ctx.content[start..header_end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut symbol = ctx.create_symbol(
+        name,
+        kind,
+        start,
+        end,
+        SymbolOptions {
+            signature: Some(signature),
+            parent_id,
+            confidence: Some(confidence::RECOVERED),
+            ..Default::default()
+        },
+    );
+    set_metadata(&mut symbol, "extractedFromError", true);
+    Some(symbol)
+}
+
+/// End of a declaration whose header ends at `header_end`: past a following
+/// `(...)` parameter list and `{...}` body when present
+fn declaration_end(content: &str, header_end: usize, lexicon: Lexicon) -> usize {
+    let next_code = |from: usize| {
+        content[from..]
+            .find(|c: char| !c.is_whitespace())
+            .map(|offset| from + offset)
+    };
+
+    let mut end = header_end;
+    if let Some(open) = next_code(end).filter(|&i| content.as_bytes()[i] == b'(') {
+        match find_matching_delimiter(content, open, lexicon) {
+            Some(close) => end = close + 1,
+            None => return end,
+        }
+    }
+    if let Some(open) = next_code(end).filter(|&i| content.as_bytes()[i] == b'{') {
+        if let Some(close) = find_matching_delimiter(content, open, lexicon) {
+            end = close + 1;
+        }
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    static TABLE_RE: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"(?i)\bCREATE\s+TABLE\s+(?P<name>\w+)").unwrap());
+
+    #[test]
+    fn test_unclaimed_regions_skip_symbols_and_comments() {
+        let content = "// header\nfn a() {}\nbroken stuff\n";
+        let ctx = EnrichmentContext::new("a.c", "c", content, Path::new("."));
+        let a = ctx.create_symbol("a", SymbolKind::Function, 10, 19, SymbolOptions::default());
+        let regions = unclaimed_regions(&ctx, &[a], Lexicon::C_LIKE);
+        assert_eq!(regions.len(), 1);
+        assert_eq!(content[regions[0].clone()].trim(), "broken stuff");
+    }
+
+    #[test]
+    fn test_keyword_names_are_case_insensitive_and_skip_strings() {
+        let content = "x = 'function Fake'\nFUNCTION Get-Thing { }\n";
+        let pairs = keyword_names(content, 0..content.len(), &["function"], Lexicon::C_LIKE);
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].name, "Get-Thing");
+        assert_eq!(pairs[0].keyword, "FUNCTION");
+        assert_eq!(pairs[0].name_start, content.find("Get-Thing").unwrap());
+    }
+
+    #[test]
+    fn test_recover_symbols_offsets_and_dedup() {
+        let content = "garbage (\n\nCREATE TABLE users (id int);\nCREATE TABLE posts (id int);\n";
+        let ctx = EnrichmentContext::new("a.sql", "sql", content, Path::new("."));
+        let posts_start = content.find("CREATE TABLE posts").unwrap();
+        let posts = ctx.create_symbol(
+            "posts",
+            SymbolKind::Class,
+            posts_start,
+            posts_start + 28,
+            SymbolOptions::default(),
+        );
+        let rules = [RecoveryRule {
+            pattern: &TABLE_RE,
+            kind: SymbolKind::Class,
+        }];
+        let recovered = recover_symbols(&ctx, &[posts], 0..content.len(), &rules, Lexicon::C_LIKE);
+
+        assert_eq!(recovered.len(), 1);
+        let users = &recovered[0];
+        assert_eq!(users.name, "users");
+        assert_eq!(users.start_line, 3);
+        assert_eq!(ctx.symbol_text(users), "CREATE TABLE users (id int)");
+        assert_eq!(
+            users.metadata.as_ref().unwrap()["extractedFromError"],
+            serde_json::Value::Bool(true)
+        );
+    }
+}
@@ -0,0 +1,261 @@
+// Enrichment Base - text scanning that understands nesting, strings, and comments
+//
+// Regex-driven passes match on raw text; these helpers let them find closing
+// delimiters and top-level separators without tripping over literals.
+
+/// Lexical conventions needed to skip strings and comments while scanning
+#[derive(Debug, Clone, Copy)]
+pub struct Lexicon {
+    /// Bytes that open and close string literals
+    pub quotes: &'static [u8],
+    /// Escape byte inside string literals
+    pub escape: Option<u8>,
+    /// Line comment prefix
+    pub line_comment: Option<&'static str>,
+    /// Block comment delimiters
+    pub block_comment: Option<(&'static str, &'static str)>,
+}
+
+impl Lexicon {
+    /// C-family conventions: "..." / '...' strings, backslash escapes, // and /* */ comments
+    pub const C_LIKE: Lexicon = Lexicon {
+        quotes: b"\"'",
+        escape: Some(b'\\'),
+        line_comment: Some("//"),
+        block_comment: Some(("/*", "*/")),
+    };
+
+    /// CSS: "..." / '...' strings and /* */ comments only (`//` starts no comment)
+    pub const CSS: Lexicon = Lexicon {
+        quotes: b"\"'",
+        escape: Some(b'\\'),
+        line_comment: None,
+        block_comment: Some(("/*", "*/")),
+    };
+
+    /// Markup and plain text: quotes are prose, so nothing is skipped
+    pub const PLAIN: Lexicon = Lexicon {
+        quotes: b"",
+        escape: None,
+        line_comment: None,
+        block_comment: None,
+    };
+}
+
+/// Iterator over the (offset, byte) pairs of a text that are actual code,
+/// skipping string literals and comments.
+pub struct CodeBytes<'t> {
+    bytes: &'t [u8],
+    pos: usize,
+    lexicon: Lexicon,
+}
+
+impl<'t> CodeBytes<'t> {
+    pub fn new(text: &'t str, start: usize, lexicon: Lexicon) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+            pos: start,
+            lexicon,
+        }
+    }
+}
+
+impl Iterator for CodeBytes<'_> {
+    type Item = (usize, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.bytes.len();
+        while self.pos < len {
+            let rest = &self.bytes[self.pos..];
+
+            // Block comments are checked first so "<#" wins over "#" in PowerShell
+            if let Some((open, close)) = self.lexicon.block_comment {
+                if rest.starts_with(open.as_bytes()) {
+                    let body = &self.bytes[self.pos + open.len()..];
+                    self.pos = match find_bytes(body, close.as_bytes()) {
+                        Some(i) => self.pos + open.len() + i + close.len(),
+                        None => len,
+                    };
+                    continue;
+                }
+            }
+
+            if let Some(prefix) = self.lexicon.line_comment {
+                if rest.starts_with(prefix.as_bytes()) {
+                    self.pos = match rest.iter().position(|&b| b == b'\n') {
+                        Some(i) => self.pos + i,
+                        None => len,
+                    };
+                    continue;
+                }
+            }
+
+            let byte = self.bytes[self.pos];
+            if self.lexicon.quotes.contains(&byte) {
+                self.pos += 1;
+                while self.pos < len {
+                    let c = self.bytes[self.pos];
+                    if Some(c) == self.lexicon.escape {
+                        self.pos += 2;
+                        continue;
+                    }
+                    self.pos += 1;
+                    if c == byte {
+                        break;
+                    }
+                }
+                continue;
+            }
+
+            self.pos += 1;
+            return Some((self.pos - 1, byte));
+        }
+        None
+    }
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn closing_delimiter(open: u8) -> Option<u8> {
+    match open {
+        b'(' => Some(b')'),
+        b'[' => Some(b']'),
+        b'{' => Some(b'}'),
+        _ => None,
+    }
+}
+
+/// Find the offset of the delimiter closing the one at `open_index`
+///
+/// Nested (), [], and {} are tracked; strings and comments are skipped.
+pub fn find_matching_delimiter(text: &str, open_index: usize, lexicon: Lexicon) -> Option<usize> {
+    let first = *text.as_bytes().get(open_index)?;
+    closing_delimiter(first)?;
+
+    let mut stack: Vec<u8> = Vec::new();
+    for (index, byte) in CodeBytes::new(text, open_index, lexicon) {
+        if let Some(close) = closing_delimiter(byte) {
+            stack.push(close);
+        } else if matches!(byte, b')' | b']' | b'}') {
+            if stack.pop() != Some(byte) {
+                return None;
+            }
+            if stack.is_empty() {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+/// Parenthesis nesting depth at every byte of `text`
+///
+/// A `(` counts at its own offset, a `)` no longer does; parentheses in
+/// strings and comments are skipped, unbalanced `)` are ignored.
+pub fn paren_depths(text: &str, lexicon: Lexicon) -> Vec<u32> {
+    let mut depths = vec![0u32; text.len()];
+    let mut depth = 0u32;
+    let mut filled = 0;
+    for (index, byte) in CodeBytes::new(text, 0, lexicon) {
+        depths[filled..index].fill(depth);
+        match byte {
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        depths[index] = depth;
+        filled = index + 1;
+    }
+    depths[filled..].fill(depth);
+    depths
+}
+
+/// Split `text` on `separator` bytes that are not nested inside brackets,
+/// strings, or comments. Returns (offset, segment) pairs with untrimmed segments.
+pub fn split_top_level(text: &str, separator: u8, lexicon: Lexicon) -> Vec<(usize, &str)> {
+    let mut segments = Vec::new();
+    let mut depth = 0usize;
+    let mut segment_start = 0;
+
+    for (index, byte) in CodeBytes::new(text, 0, lexicon) {
+        match byte {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            _ if byte == separator && depth == 0 => {
+                segments.push((segment_start, &text[segment_start..index]));
+                segment_start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push((segment_start, &text[segment_start..]));
+    segments
+}
+
+/// Mark which bytes are code (not inside strings or comments)
+pub fn code_mask(text: &str, lexicon: Lexicon) -> Vec<bool> {
+    let mut mask = vec![false; text.len()];
+    for (index, _) in CodeBytes::new(text, 0, lexicon) {
+        mask[index] = true;
+    }
+    mask
+}
+
+/// Mark which bytes are top-level code (not inside brackets, strings, or comments)
+pub fn top_level_mask(text: &str, lexicon: Lexicon) -> Vec<bool> {
+    let mut mask = vec![false; text.len()];
+    let mut depth = 0usize;
+
+    for (index, byte) in CodeBytes::new(text, 0, lexicon) {
+        match byte {
+            b'(' | b'[' | b'{' => {
+                if depth == 0 {
+                    mask[index] = true;
+                }
+                depth += 1;
+            }
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => mask[index] = depth == 0,
+        }
+    }
+    mask
+}
+
+/// Trim whitespace from a segment, returning the adjusted offset alongside it
+pub fn trim_with_offset(offset: usize, segment: &str) -> (usize, &str) {
+    let leading = segment.len() - segment.trim_start().len();
+    (offset + leading, segment.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_matching_delimiter_skips_strings_and_comments() {
+        let text = r#"(a, ")", (b) /* ) */ [c])"#;
+        assert_eq!(
+            find_matching_delimiter(text, 0, Lexicon::C_LIKE),
+            Some(text.len() - 1)
+        );
+    }
+
+    #[test]
+    fn test_split_top_level_ignores_nested_separators() {
+        let parts: Vec<&str> = split_top_level("a, f(b, c), 'd,e'", b',', Lexicon::C_LIKE)
+            .into_iter()
+            .map(|(_, s)| s.trim())
+            .collect();
+        assert_eq!(parts, vec!["a", "f(b, c)", "'d,e'"]);
+    }
+
+    #[test]
+    fn test_top_level_mask_excludes_nested_code() {
+        let mask = top_level_mask("x{y}z", Lexicon::C_LIKE);
+        assert_eq!(mask, vec![true, true, false, false, true]);
+    }
+}
@@ -0,0 +1,139 @@
+// Source text - BOM and line-ending normalization with original offsets
+//
+// A UTF-8 BOM or CRLF line endings throw off everything that slices or scans
+// the content: a `\r` ends up in doc comments and signatures, `(?m)$` never
+// matches before it, and an anchored `^` pattern misses a declaration on the
+// first line behind a BOM. Rather than making every helper CRLF-aware, the
+// engine extracts and enriches the normalized text (BOM stripped, every `\r\n`
+// turned into `\n`) and then maps symbol and identifier offsets back, so
+// start_byte/end_byte index the original bytes on disk. Lines are unchanged;
+// columns on the first line move by the BOM's width. A lone `\r` is not a
+// line break for Miller's line math and is left in place.
+
+use julie_extractors::ExtractionResults;
+use std::borrow::Cow;
+
+const BOM: &str = "\u{feff}";
+
+/// File content as extraction sees it, plus what was removed to get there
+pub struct SourceText<'a> {
+    text: Cow<'a, str>,
+    had_bom: bool,
+    /// Offsets (in `text`) of every `\n` that was preceded by a removed `\r`
+    removed_crs: Vec<usize>,
+}
+
+impl<'a> SourceText<'a> {
+    pub fn new(content: &'a str) -> Self {
+        let had_bom = content.starts_with(BOM);
+        let body = content.strip_prefix(BOM).unwrap_or(content);
+        if !body.contains("\r\n") {
+            return Self {
+                text: Cow::Borrowed(body),
+                had_bom,
+                removed_crs: Vec::new(),
+            };
+        }
+
+        let mut text = String::with_capacity(body.len());
+        let mut removed_crs = Vec::new();
+        for line in body.split_inclusive('\n') {
+            match line.strip_suffix("\r\n") {
+                Some(stripped) => {
+                    text.push_str(stripped);
+                    removed_crs.push(text.len());
+                    text.push('\n');
+                }
+                None => text.push_str(line),
+            }
+        }
+        Self {
+            text: Cow::Owned(text),
+            had_bom,
+            removed_crs,
+        }
+    }
+
+    /// The normalized text: no BOM, LF line endings
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn had_bom(&self) -> bool {
+        self.had_bom
+    }
+
+    /// Offset in the original content of byte `offset` of the normalized text
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let bom = if self.had_bom { BOM.len() } else { 0 };
+        offset + bom + self.removed_crs.partition_point(|&cr| cr < offset)
+    }
+
+    /// Rewrite byte offsets (and first-line columns) to index the original content
+    pub fn restore_offsets(&self, results: &mut ExtractionResults) {
+        if !self.had_bom && self.removed_crs.is_empty() {
+            return;
+        }
+        let bom = if self.had_bom { BOM.len() as u32 } else { 0 };
+        let restore = |start: &mut u32, end: &mut u32, columns: [(&mut u32, u32); 2]| {
+            *start = self.original_offset(*start as usize) as u32;
+            *end = self.original_offset(*end as usize) as u32;
+            for (column, line) in columns {
+                if line == 1 {
+                    *column += bom;
+                }
+            }
+        };
+
+        for symbol in &mut results.symbols {
+            let (start_line, end_line) = (symbol.start_line, symbol.end_line);
+            restore(
+                &mut symbol.start_byte,
+                &mut symbol.end_byte,
+                [
+                    (&mut symbol.start_column, start_line),
+                    (&mut symbol.end_column, end_line),
+                ],
+            );
+        }
+        for identifier in &mut results.identifiers {
+            let (start_line, end_line) = (identifier.start_line, identifier.end_line);
+            restore(
+                &mut identifier.start_byte,
+                &mut identifier.end_byte,
+                [
+                    (&mut identifier.start_column, start_line),
+                    (&mut identifier.end_column, end_line),
+                ],
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalizes_and_maps_offsets_back() {
+        let original = "\u{feff}a\r\nbc\r\n\rd\n";
+        let source = SourceText::new(original);
+        assert_eq!(source.text(), "a\nbc\n\rd\n");
+        assert!(source.had_bom());
+
+        // Every character lands on itself; a CRLF's `\n` lands on its `\r`,
+        // so a span ending at a line break stops before the `\r`
+        for (offset, ch) in source.text().char_indices() {
+            let mapped = source.original_offset(offset);
+            let expected = if offset == 1 || offset == 4 { '\r' } else { ch };
+            assert_eq!(original[mapped..].chars().next(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_plain_content_is_borrowed() {
+        let source = SourceText::new("fn main() {}\n");
+        assert!(matches!(source.text, Cow::Borrowed(_)));
+        assert_eq!(source.original_offset(5), 5);
+    }
+}
@@ -0,0 +1,187 @@
+// Symbol Index - Containing-symbol lookups in O(log n)
+//
+// innermost_symbol_at scans every symbol, which is fine for a handful of
+// lookups but turns passes that look up the owner of every identifier or
+// statement into O(symbols × lookups) work: the dominant cost on a 10k-line SQL
+// dump or a large generated file. SymbolIndex sorts the symbols by start byte
+// once; a lookup binary-searches for the last symbol starting at or before the
+// byte and walks back only while an earlier symbol could still be a tighter
+// fit. Results are identical to innermost_symbol_at, including ties (the
+// earliest symbol in the original order wins).
+
+use julie_extractors::Symbol;
+
+/// Byte-range index over a file's symbols for repeated containing-symbol lookups
+pub struct SymbolIndex<'s> {
+    /// (start, end, original position, symbol), sorted by start then position
+    entries: Vec<(usize, usize, usize, &'s Symbol)>,
+    /// Largest end among entries[..=i], to stop once nothing earlier can contain a byte
+    max_end: Vec<usize>,
+}
+
+impl<'s> SymbolIndex<'s> {
+    pub fn new(symbols: &'s [Symbol]) -> Self {
+        let mut entries: Vec<_> = symbols
+            .iter()
+            .enumerate()
+            .map(|(position, s)| (s.start_byte as usize, s.end_byte as usize, position, s))
+            .collect();
+        entries.sort_by_key(|&(start, _, position, _)| (start, position));
+        let max_end = entries
+            .iter()
+            .scan(0, |max, &(_, end, _, _)| {
+                *max = end.max(*max);
+                Some(*max)
+            })
+            .collect();
+        SymbolIndex { entries, max_end }
+    }
+
+    /// Innermost symbol whose byte range contains `byte`, optionally restricted
+    /// by a predicate; same result as innermost_symbol_at
+    pub fn innermost_at(
+        &self,
+        byte: usize,
+        accept: impl Fn(&Symbol) -> bool,
+    ) -> Option<&'s Symbol> {
+        let candidates = self.entries.partition_point(|&(start, ..)| start <= byte);
+        // (span, original position, symbol) of the best match so far
+        let mut best: Option<(usize, usize, &'s Symbol)> = None;
+
+        for index in (0..candidates).rev() {
+            if self.max_end[index] <= byte {
+                break;
+            }
+            let (start, end, position, symbol) = self.entries[index];
+            if let Some((span, ..)) = best {
+                // Anything starting this early that contains `byte` spans more
+                if start + span <= byte {
+                    break;
+                }
+            }
+            if byte >= end || !accept(symbol) {
+                continue;
+            }
+            let span = end - start;
+            if best.is_none_or(|(best_span, best_position, _)| {
+                (span, position) < (best_span, best_position)
+            }) {
+                best = Some((span, position, symbol));
+            }
+        }
+        best.map(|(.., symbol)| symbol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{innermost_symbol_at, EnrichmentContext, SymbolOptions};
+    use super::*;
+    use julie_extractors::SymbolKind;
+    use std::hint::black_box;
+    use std::path::Path;
+    use std::time::Instant;
+
+    /// Deterministic pseudo-random symbols: nested, overlapping, and empty ranges
+    fn symbols(ctx: &EnrichmentContext, count: usize, len: usize) -> Vec<Symbol> {
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move |bound: usize| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            (seed % bound as u64) as usize
+        };
+        (0..count)
+            .map(|i| {
+                let start = next(len);
+                let end = (start + next(len / 8 + 1)).min(len);
+                let kind = if i % 3 == 0 {
+                    SymbolKind::Function
+                } else {
+                    SymbolKind::Variable
+                };
+                ctx.create_symbol("s", kind, start, end, SymbolOptions::default())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_linear_scan() {
+        let content = "x".repeat(2000);
+        let ctx = EnrichmentContext::new("a.sql", "sql", &content, Path::new("."));
+        let symbols = symbols(&ctx, 300, content.len());
+        let index = SymbolIndex::new(&symbols);
+        let is_function = |s: &Symbol| s.kind == SymbolKind::Function;
+
+        for byte in 0..=content.len() {
+            let expected = innermost_symbol_at(&symbols, byte, |_| true).map(|s| &s.id);
+            assert_eq!(index.innermost_at(byte, |_| true).map(|s| &s.id), expected);
+            let expected = innermost_symbol_at(&symbols, byte, is_function).map(|s| &s.id);
+            assert_eq!(
+                index.innermost_at(byte, is_function).map(|s| &s.id),
+                expected
+            );
+        }
+    }
+
+    /// One statement per line of a 10k-line dump, each owned by a routine
+    fn dump_symbols(ctx: &EnrichmentContext, lines: usize) -> Vec<Symbol> {
+        (0..lines)
+            .map(|line| {
+                let start = line * 26;
+                ctx.create_symbol(
+                    "s",
+                    SymbolKind::Function,
+                    start,
+                    start + 25,
+                    SymbolOptions::default(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dump_lookups_match_linear_scan() {
+        let lines = 10_000;
+        let content = "INSERT INTO t VALUES (1);\n".repeat(lines);
+        let ctx = EnrichmentContext::new("dump.sql", "sql", &content, Path::new("."));
+        let symbols = dump_symbols(&ctx, lines);
+        let index = SymbolIndex::new(&symbols);
+
+        for byte in (0..lines).map(|line| line * 26 + 12) {
+            let expected = innermost_symbol_at(&symbols, byte, |_| true).map(|s| &s.id);
+            assert_eq!(index.innermost_at(byte, |_| true).map(|s| &s.id), expected);
+        }
+    }
+
+    // Timing is machine-dependent; run with `cargo test -- --ignored`
+    #[test]
+    #[ignore]
+    fn test_lookup_benchmark() {
+        let lines = 10_000;
+        let content = "INSERT INTO t VALUES (1);\n".repeat(lines);
+        let ctx = EnrichmentContext::new("dump.sql", "sql", &content, Path::new("."));
+        let symbols = dump_symbols(&ctx, lines);
+        let bytes: Vec<usize> = (0..lines).map(|line| line * 26 + 12).collect();
+
+        let started = Instant::now();
+        for &byte in &bytes {
+            black_box(innermost_symbol_at(&symbols, byte, |_| true));
+        }
+        let linear_time = started.elapsed();
+
+        let started = Instant::now();
+        let index = SymbolIndex::new(&symbols);
+        for &byte in &bytes {
+            black_box(index.innermost_at(byte, |_| true));
+        }
+        let indexed_time = started.elapsed();
+
+        assert!(
+            indexed_time * 10 < linear_time,
+            "indexed {:?} vs linear {:?}",
+            indexed_time,
+            linear_time
+        );
+    }
+}
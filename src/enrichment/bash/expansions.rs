@@ -0,0 +1,406 @@
+// Bash variable expansions
+//
+// Julie extracts variables where they are assigned, so find-references on a
+// shell variable only finds its definitions. This pass adds a VariableRef
+// identifier for every parameter expansion (`$VAR`, `${VAR}`,
+// `${VAR:-default}`, `"${ARR[@]}"`) outside single quotes and comments, named
+// without the `$` and braces and owned by the enclosing function.
+//
+// References are resolved with shell scoping: inside a function a `local` /
+// `declare` / `typeset` variable of that function wins, then a top-level
+// assignment, then an assignment anywhere else in the file. Positional and
+// special parameters ($1, ${10}, $@, $*, $#) are only tracked inside
+// functions, where they refer to the function's own arguments and target
+// Julie's positional-parameter symbols when they exist.
+//
+// Julie identifiers carry no metadata, so expansion operators are recorded on
+// the variable's symbol (or, for variables the file never assigns, such as
+// environment variables, on the enclosing function) as
+// metadata.parameterExpansions entries `{ variable, operator, operand, line }`,
+// with operators named defaultValue, assignDefault, errorIfUnset,
+// alternateValue, removePrefix, removeSuffix, substitution, substring,
+// caseModification, transformation, length, and indirection.
+
+use super::is_word_boundary;
+use crate::enrichment::base::symbol_index::SymbolIndex;
+use crate::enrichment::base::{append_metadata, EnrichmentContext};
+use julie_extractors::{ExtractionResults, IdentifierKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// `local` / `declare` / `typeset` before a variable name on its line
+static LOCAL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:local|declare|typeset)\b[^;\n]*$").unwrap());
+
+/// A parameter expansion found in the script
+#[derive(Debug, PartialEq)]
+struct Expansion {
+    /// Parameter name without `$` or braces
+    name: String,
+    /// Byte offset of the name
+    start: usize,
+    /// Operator name and operand for `${name<op>operand}` forms
+    operator: Option<(&'static str, String)>,
+}
+
+impl Expansion {
+    fn is_positional(&self) -> bool {
+        self.name.bytes().all(|b| b.is_ascii_digit())
+            || matches!(self.name.as_str(), "@" | "*" | "#")
+    }
+}
+
+pub(super) fn extract_expansions(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let known: HashSet<usize> = results
+        .identifiers
+        .iter()
+        .map(|i| i.start_byte as usize)
+        .collect();
+    let functions = SymbolIndex::new(&results.symbols);
+
+    // (expansion, enclosing function, target symbol)
+    let mut references = Vec::new();
+    for expansion in find_expansions(ctx.content) {
+        if known.contains(&expansion.start) || known.contains(&(expansion.start - 1)) {
+            continue;
+        }
+        let function = functions
+            .innermost_at(expansion.start, |s| s.kind == SymbolKind::Function)
+            .map(|s| s.id.clone());
+        if expansion.is_positional() && function.is_none() {
+            continue;
+        }
+        let target = if expansion.is_positional() {
+            results
+                .symbols
+                .iter()
+                .find(|s| {
+                    s.parent_id == function
+                        && (s.name == expansion.name || s.name == format!("${}", expansion.name))
+                })
+                .map(|s| s.id.clone())
+        } else {
+            resolve_variable(ctx, &results.symbols, &expansion.name, function.as_deref())
+        };
+        references.push((expansion, function, target));
+    }
+
+    for (expansion, function, target) in references {
+        let mut identifier = ctx.create_identifier(
+            &expansion.name,
+            IdentifierKind::VariableRef,
+            expansion.start,
+            expansion.start + expansion.name.len(),
+            function.clone(),
+        );
+        identifier.target_symbol_id = target.clone();
+        results.identifiers.push(identifier);
+
+        let Some((operator, operand)) = expansion.operator else {
+            continue;
+        };
+        let Some(owner) = target.or(function) else {
+            continue;
+        };
+        let symbol = results.symbols.iter_mut().find(|s| s.id == owner).unwrap();
+        append_metadata(
+            symbol,
+            "parameterExpansions",
+            json!({
+                "variable": expansion.name,
+                "operator": operator,
+                "operand": operand,
+                "line": ctx.line_of(expansion.start),
+            }),
+        );
+    }
+}
+
+/// The variable symbol a reference inside `function` (or at top level) refers to
+fn resolve_variable(
+    ctx: &EnrichmentContext,
+    symbols: &[Symbol],
+    name: &str,
+    function: Option<&str>,
+) -> Option<String> {
+    let candidates: Vec<&Symbol> = symbols
+        .iter()
+        .filter(|s| s.kind == SymbolKind::Variable && s.name.trim_start_matches('$') == name)
+        .collect();
+    let owner = |symbol: &Symbol| {
+        symbols
+            .iter()
+            .filter(|f| {
+                f.kind == SymbolKind::Function
+                    && f.start_byte <= symbol.start_byte
+                    && symbol.start_byte < f.end_byte
+            })
+            .min_by_key(|f| f.end_byte - f.start_byte)
+            .map(|f| f.id.as_str())
+    };
+    let is_local = |symbol: &Symbol| {
+        let start = symbol.start_byte as usize;
+        let line_start = ctx.content[..start].rfind('\n').map_or(0, |i| i + 1);
+        LOCAL_RE.is_match(&ctx.content[line_start..start])
+    };
+
+    let local = function.and_then(|function| {
+        candidates
+            .iter()
+            .find(|s| owner(s) == Some(function) && is_local(s))
+    });
+    let global = || {
+        candidates
+            .iter()
+            .find(|s| owner(s).is_none())
+            .or_else(|| candidates.iter().find(|s| !is_local(s)))
+    };
+    local.or_else(global).map(|s| s.id.clone())
+}
+
+/// Every `$name` / `${...}` expansion outside single quotes, comments, and escapes
+fn find_expansions(content: &str) -> Vec<Expansion> {
+    let bytes = content.as_bytes();
+    let mut expansions = Vec::new();
+    let mut in_double = false;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 1,
+            b'\'' if !in_double => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'\'' {
+                    index += 1;
+                }
+            }
+            b'"' => in_double = !in_double,
+            b'#' if !in_double && (index == 0 || is_word_boundary(bytes[index - 1])) => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+            }
+            b'$' => {
+                if let Some((expansion, end)) = parse_expansion(content, index) {
+                    expansions.push(expansion);
+                    index = end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    expansions
+}
+
+/// Parse the expansion at the `$` at `dollar`; returns it and the offset after it
+fn parse_expansion(content: &str, dollar: usize) -> Option<(Expansion, usize)> {
+    let bytes = content.as_bytes();
+    let start = dollar + 1;
+    match *bytes.get(start)? {
+        b'{' => parse_braced(content, start),
+        b'0'..=b'9' | b'@' | b'*' | b'#' => Some((
+            Expansion {
+                name: content[start..start + 1].to_string(),
+                start,
+                operator: None,
+            },
+            start + 1,
+        )),
+        b if b == b'_' || b.is_ascii_alphabetic() => {
+            let end = start + name_len(&content[start..]);
+            Some((
+                Expansion {
+                    name: content[start..end].to_string(),
+                    start,
+                    operator: None,
+                },
+                end,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// `${[#!]name[subscript][operator operand]}` starting at the `{`
+fn parse_braced(content: &str, open: usize) -> Option<(Expansion, usize)> {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut close = open;
+    while close < bytes.len() {
+        match bytes[close] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            b'\\' => close += 1,
+            _ => {}
+        }
+        close += 1;
+    }
+    let inner = content.get(open + 1..close)?;
+
+    // `${#name}` is a length, unless it is `${#}` (the argument count)
+    let (prefix, rest) = match inner.as_bytes().first()? {
+        b'#' if inner.len() > 1 => (Some("length"), &inner[1..]),
+        b'!' if inner.len() > 1 => (Some("indirection"), &inner[1..]),
+        _ => (None, inner),
+    };
+    let name_end = match rest.as_bytes().first()? {
+        b'0'..=b'9' => rest.bytes().take_while(u8::is_ascii_digit).count(),
+        b'@' | b'*' | b'#' | b'?' | b'$' | b'!' | b'-' => 1,
+        _ => name_len(rest),
+    };
+    if name_end == 0 {
+        return None;
+    }
+    let name = &rest[..name_end];
+    let mut after = &rest[name_end..];
+    if after.starts_with('[') {
+        after = &after[after.find(']').map_or(after.len(), |i| i + 1)..];
+    }
+
+    let operator = prefix
+        .map(|op| (op, String::new()))
+        .or_else(|| operator(after));
+    let name_start = open + 1 + (inner.len() - rest.len());
+    Some((
+        Expansion {
+            name: name.to_string(),
+            start: name_start,
+            operator,
+        },
+        close + 1,
+    ))
+}
+
+/// Operator name and operand of the text after a braced parameter name
+fn operator(text: &str) -> Option<(&'static str, String)> {
+    const OPERATORS: &[(&str, &str)] = &[
+        (":-", "defaultValue"),
+        (":=", "assignDefault"),
+        (":?", "errorIfUnset"),
+        (":+", "alternateValue"),
+        ("-", "defaultValue"),
+        ("=", "assignDefault"),
+        ("?", "errorIfUnset"),
+        ("+", "alternateValue"),
+        ("##", "removePrefix"),
+        ("#", "removePrefix"),
+        ("%%", "removeSuffix"),
+        ("%", "removeSuffix"),
+        ("//", "substitution"),
+        ("/#", "substitution"),
+        ("/%", "substitution"),
+        ("/", "substitution"),
+        (":", "substring"),
+        ("^^", "caseModification"),
+        ("^", "caseModification"),
+        (",,", "caseModification"),
+        (",", "caseModification"),
+        ("@", "transformation"),
+    ];
+    OPERATORS
+        .iter()
+        .find(|(symbol, _)| text.starts_with(symbol))
+        .map(|(symbol, name)| (*name, text[symbol.len()..].to_string()))
+}
+
+fn name_len(text: &str) -> usize {
+    text.bytes()
+        .take_while(|b| *b == b'_' || b.is_ascii_alphanumeric())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    #[test]
+    fn test_expansion_forms() {
+        let script =
+            "echo $HOME '$NOT' \"${PORT:-8080}\" ${#ARR[@]} ${FILE%.txt} \\$ESCAPED # $COMMENT\n";
+        let found: Vec<(String, Option<(&str, String)>)> = find_expansions(script)
+            .into_iter()
+            .map(|e| (e.name, e.operator))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("HOME".to_string(), None),
+                (
+                    "PORT".to_string(),
+                    Some(("defaultValue", "8080".to_string()))
+                ),
+                ("ARR".to_string(), Some(("length", String::new()))),
+                (
+                    "FILE".to_string(),
+                    Some(("removeSuffix", ".txt".to_string()))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_references_resolve_with_function_scope() {
+        let script = "NAME=global\ngreet() {\n  local NAME=\"$1\"\n  echo \"Hello ${NAME:-world}\" $@\n}\necho $NAME $1\n";
+        let ctx = EnrichmentContext::new("greet.sh", "bash", script, Path::new("."));
+        let function = ctx.create_symbol(
+            "greet",
+            SymbolKind::Function,
+            script.find("greet").unwrap(),
+            script.find("}\n").unwrap() + 1,
+            SymbolOptions::default(),
+        );
+        let global =
+            ctx.create_symbol("NAME", SymbolKind::Variable, 0, 4, SymbolOptions::default());
+        let local_start = script.find("NAME=\"").unwrap();
+        let local = ctx.create_symbol(
+            "NAME",
+            SymbolKind::Variable,
+            local_start,
+            local_start + 4,
+            SymbolOptions {
+                parent_id: Some(function.id.clone()),
+                ..Default::default()
+            },
+        );
+        let (function_id, global_id, local_id) =
+            (function.id.clone(), global.id.clone(), local.id.clone());
+        let mut results = test_results(vec![function, global, local]);
+
+        extract_expansions(&ctx, &mut results);
+
+        let references: Vec<(&str, Option<&str>, Option<&str>)> = results
+            .identifiers
+            .iter()
+            .map(|i| {
+                (
+                    i.name.as_str(),
+                    i.containing_symbol_id.as_deref(),
+                    i.target_symbol_id.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            references,
+            vec![
+                ("1", Some(function_id.as_str()), None),
+                ("NAME", Some(function_id.as_str()), Some(local_id.as_str())),
+                ("@", Some(function_id.as_str()), None),
+                ("NAME", None, Some(global_id.as_str())),
+            ]
+        );
+        let expansions = &results.symbols[2].metadata.as_ref().unwrap()["parameterExpansions"];
+        assert_eq!(expansions[0]["operator"], "defaultValue");
+        assert_eq!(expansions[0]["operand"], "world");
+    }
+}
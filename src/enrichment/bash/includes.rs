@@ -0,0 +1,162 @@
+// Bash include tracking
+//
+// `source ./lib/common.sh` and `. /etc/profile.d/foo.sh` pull other scripts into
+// the current shell. Each include becomes an Import symbol (plus an Import
+// identifier) whose metadata carries the literal argument and, when it resolves
+// to a file inside the workspace, the normalized workspace-relative path. Paths
+// built from variables are recorded verbatim with resolved=false.
+
+use super::{code_mask, parse_shell_word};
+use crate::enrichment::base::{innermost_symbol_at, EnrichmentContext, SymbolOptions};
+use julie_extractors::{ExtractionResults, IdentifierKind, RelationshipKind, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `source` or `.` in command position
+static INCLUDE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)(?:^|[;&|({]|\bthen\b|\bdo\b|\belse\b)[ \t]*(source|\.)[ \t]+").unwrap()
+});
+
+pub(super) fn extract_includes(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let mask = code_mask(ctx.content);
+
+    for captures in INCLUDE_RE.captures_iter(ctx.content) {
+        let command = captures.get(1).unwrap();
+        if !mask.get(command.start()).copied().unwrap_or(false) {
+            continue;
+        }
+
+        let word_start = captures.get(0).unwrap().end();
+        let Some(word) = parse_shell_word(&ctx.content[word_start..]) else {
+            continue;
+        };
+        let word_end = word_start + word.len;
+
+        let resolved_path = if word.has_expansion {
+            None
+        } else {
+            ctx.resolve_relative_to_file(&word.value)
+                .or_else(|| ctx.resolve_workspace_path(&word.value))
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("command".to_string(), Value::from(command.as_str()));
+        metadata.insert("importPath".to_string(), Value::from(word.value.clone()));
+        metadata.insert("resolved".to_string(), Value::Bool(resolved_path.is_some()));
+        if let Some(path) = resolved_path {
+            metadata.insert("resolvedPath".to_string(), Value::from(path));
+        }
+
+        let function_id = innermost_symbol_at(&results.symbols, command.start(), |s| {
+            s.kind == SymbolKind::Function
+        })
+        .map(|s| s.id.clone());
+
+        let import = ctx.create_symbol(
+            &word.value,
+            SymbolKind::Import,
+            command.start(),
+            word_end,
+            SymbolOptions {
+                signature: Some(ctx.text(command.start(), word_end).to_string()),
+                parent_id: function_id.clone(),
+                metadata: Some(metadata.clone()),
+                ..Default::default()
+            },
+        );
+
+        results.identifiers.push(ctx.create_identifier(
+            &word.value,
+            IdentifierKind::Import,
+            word_start,
+            word_end,
+            function_id.clone(),
+        ));
+
+        if let Some(function_id) = function_id {
+            results.relationships.push(ctx.create_relationship(
+                &function_id,
+                &import.id,
+                RelationshipKind::Imports,
+                command.start(),
+                Some(metadata),
+            ));
+        }
+
+        results.symbols.push(import);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn extract(content: &str, root: &Path) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("scripts/deploy.sh", "bash", content, root);
+        let mut results = test_results(Vec::new());
+        extract_includes(&ctx, &mut results);
+        results
+    }
+
+    fn metadata<'r>(results: &'r ExtractionResults, name: &str) -> &'r HashMap<String, Value> {
+        results
+            .symbols
+            .iter()
+            .find(|s| s.name == name)
+            .and_then(|s| s.metadata.as_ref())
+            .unwrap_or_else(|| panic!("no import symbol {}", name))
+    }
+
+    #[test]
+    fn test_source_resolves_relative_to_script() {
+        let workspace = TempDir::new().unwrap();
+        fs::create_dir_all(workspace.path().join("scripts/lib")).unwrap();
+        fs::write(workspace.path().join("scripts/lib/common.sh"), "").unwrap();
+
+        let results = extract("#!/bin/bash\nsource ./lib/common.sh\n", workspace.path());
+        let import = metadata(&results, "./lib/common.sh");
+
+        assert_eq!(import["resolved"], Value::Bool(true));
+        assert_eq!(import["resolvedPath"], Value::from("scripts/lib/common.sh"));
+        assert_eq!(import["command"], Value::from("source"));
+        assert_eq!(results.identifiers.len(), 1);
+        assert_eq!(results.identifiers[0].kind, IdentifierKind::Import);
+    }
+
+    #[test]
+    fn test_dot_include_outside_workspace_is_unresolved() {
+        let workspace = TempDir::new().unwrap();
+        let results = extract("[ -f x ] && . /etc/profile.d/foo.sh\n", workspace.path());
+        let import = metadata(&results, "/etc/profile.d/foo.sh");
+
+        assert_eq!(import["resolved"], Value::Bool(false));
+        assert_eq!(import["command"], Value::from("."));
+        assert!(!import.contains_key("resolvedPath"));
+    }
+
+    #[test]
+    fn test_variable_paths_are_recorded_raw() {
+        let workspace = TempDir::new().unwrap();
+        let results = extract("source \"$SCRIPT_DIR/common.sh\"\n", workspace.path());
+        let import = metadata(&results, "$SCRIPT_DIR/common.sh");
+
+        assert_eq!(import["resolved"], Value::Bool(false));
+        assert_eq!(import["importPath"], Value::from("$SCRIPT_DIR/common.sh"));
+    }
+
+    #[test]
+    fn test_comments_and_strings_are_ignored() {
+        let workspace = TempDir::new().unwrap();
+        let results = extract(
+            "# source ./nope.sh\necho \"source ./nope.sh\"\nls ./dir/.hidden\n",
+            workspace.path(),
+        );
+        assert!(results.symbols.is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+// Bash enrichment passes
+//
+// Julie's Bash extractor handles functions, variables, and commands; these passes
+// add include tracking for `source` / `.` commands, references for variable
+// expansions, and the command-line options a script parses.
+
+mod expansions;
+mod includes;
+mod options;
+
+use super::base::EnrichmentContext;
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 2;
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    includes::extract_includes(ctx, results);
+    expansions::extract_expansions(ctx, results);
+    options::extract_options(ctx, results);
+}
+
+/// Mark which bytes of a shell script are code (outside quotes and comments).
+///
+/// Unlike C-like languages, `#` only starts a comment at the beginning of a word,
+/// so `$#` and `${#var}` stay code.
+fn code_mask(content: &str) -> Vec<bool> {
+    let bytes = content.as_bytes();
+    let mut mask = vec![false; bytes.len()];
+    let mut index = 0;
+
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\'' => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'\'' {
+                    index += 1;
+                }
+            }
+            b'"' => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'"' {
+                    if bytes[index] == b'\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+            }
+            b'\\' => {
+                mask[index] = true;
+                index += 1;
+            }
+            b'#' if index == 0 || is_word_boundary(bytes[index - 1]) => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+                continue;
+            }
+            _ => mask[index] = true,
+        }
+        index += 1;
+    }
+    mask
+}
+
+fn is_word_boundary(byte: u8) -> bool {
+    byte.is_ascii_whitespace() || matches!(byte, b';' | b'|' | b'&' | b'(' | b')')
+}
+
+/// A single shell word parsed from the start of a text
+#[derive(Debug, PartialEq)]
+struct ShellWord {
+    /// Byte length of the word as written (including quotes)
+    len: usize,
+    /// Word value with quotes and escapes removed
+    value: String,
+    /// Whether the word contains parameter/command expansion
+    has_expansion: bool,
+}
+
+/// Parse one shell word, honoring quotes, escapes, `$( )` and `${ }` expansions
+fn parse_shell_word(text: &str) -> Option<ShellWord> {
+    let bytes = text.as_bytes();
+    let mut value = String::new();
+    let mut has_expansion = false;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match byte {
+            b'\'' => {
+                let close = text[index + 1..].find('\'')? + index + 1;
+                value.push_str(&text[index + 1..close]);
+                index = close + 1;
+            }
+            b'"' => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != b'"' {
+                    if bytes[index] == b'$' || bytes[index] == b'`' {
+                        has_expansion = true;
+                    }
+                    if bytes[index] == b'\\' && index + 1 < bytes.len() {
+                        index += 1;
+                    }
+                    let len = utf8_len(bytes[index]);
+                    value.push_str(&text[index..index + len]);
+                    index += len;
+                }
+                index += 1;
+            }
+            b'\\' if index + 1 < bytes.len() => {
+                let len = utf8_len(bytes[index + 1]);
+                value.push_str(&text[index + 1..index + 1 + len]);
+                index += 1 + len;
+            }
+            b'$' if matches!(bytes.get(index + 1), Some(b'(') | Some(b'{')) => {
+                has_expansion = true;
+                let (open, close) = if bytes[index + 1] == b'(' {
+                    (b'(', b')')
+                } else {
+                    (b'{', b'}')
+                };
+                let mut depth = 0usize;
+                let start = index;
+                index += 1;
+                while index < bytes.len() {
+                    if bytes[index] == open {
+                        depth += 1;
+                    } else if bytes[index] == close {
+                        depth -= 1;
+                        if depth == 0 {
+                            index += 1;
+                            break;
+                        }
+                    }
+                    index += 1;
+                }
+                value.push_str(&text[start..index]);
+            }
+            b'$' | b'`' => {
+                has_expansion = true;
+                value.push(byte as char);
+                index += 1;
+            }
+            _ if byte.is_ascii_whitespace()
+                || matches!(byte, b';' | b'&' | b'|' | b')' | b'<' | b'>') =>
+            {
+                break
+            }
+            _ => {
+                let len = utf8_len(byte);
+                value.push_str(&text[index..index + len]);
+                index += len;
+            }
+        }
+    }
+
+    if index == 0 {
+        return None;
+    }
+    Some(ShellWord {
+        len: index.min(text.len()),
+        value,
+        has_expansion,
+    })
+}
+
+fn utf8_len(first_byte: u8) -> usize {
+    match first_byte {
+        0xF0..=0xFF => 4,
+        0xE0..=0xEF => 3,
+        0xC0..=0xDF => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shell_word_quotes_and_expansions() {
+        let word = parse_shell_word(r#""$SCRIPT_DIR/common.sh" rest"#).unwrap();
+        assert_eq!(word.value, "$SCRIPT_DIR/common.sh");
+        assert!(word.has_expansion);
+        assert_eq!(word.len, 23);
+
+        let word = parse_shell_word("'./lib/a b.sh'; echo").unwrap();
+        assert_eq!(word.value, "./lib/a b.sh");
+        assert!(!word.has_expansion);
+
+        let word = parse_shell_word(r#"$(dirname "$0")/lib.sh"#).unwrap();
+        assert_eq!(word.value, r#"$(dirname "$0")/lib.sh"#);
+        assert!(word.has_expansion);
+    }
+
+    #[test]
+    fn test_code_mask_treats_dollar_hash_as_code() {
+        let content = "echo $# # comment";
+        let mask = code_mask(content);
+        assert!(mask[6], "$# is code");
+        assert!(!mask[content.find("comment").unwrap()]);
+    }
+}
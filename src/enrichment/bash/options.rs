@@ -0,0 +1,433 @@
+// Bash command-line options
+//
+// A script's CLI surface lives in its argument parsing, which Julie does not
+// look at. This pass adds a Variable symbol (metadata.isParameter,
+// metadata.cliOption) for every option the script or one of its functions
+// parses:
+//
+// - each letter of a `getopts "f:o:v" opt` option string, named `-f`, with
+//   takesValue when a `:` follows it (a leading `:` only silences errors)
+// - each `case` branch over a variable (`case "$1" in`, `case $arg in`) whose
+//   pattern alternatives are options: `--output|-o)` is `--output` with
+//   aliases ["-o"]. It takes a value when written `--output=*` or when its
+//   body reads `$2`, runs `shift 2`, or shifts and then reads `$1`.
+//
+// Options are parented to the enclosing function, else to a Module symbol for
+// the script itself (metadata.script, named after the file), which is added
+// when the file has none. metadata.source is "getopts" or "case" and
+// metadata.variable the option variable (`opt`) or the variable the case
+// matches (`1`, `arg`). Option strings built from expansions and glob
+// patterns (`-*`, `--*`) are skipped.
+
+use super::{code_mask, is_word_boundary, parse_shell_word};
+use crate::enrichment::base::{innermost_symbol_at, EnrichmentContext, SymbolOptions};
+use julie_extractors::{ExtractionResults, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+static GETOPTS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bgetopts[ \t]+").unwrap());
+
+/// `case "$1" in`, `case ${arg} in`
+static CASE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bcase[ \t]+"?\$\{?([A-Za-z_][A-Za-z0-9_]*|[0-9])\}?"?[ \t]+in\b"#).unwrap()
+});
+
+/// A pattern alternative naming an option, `--output` or `--output=*`
+static OPTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(--?[A-Za-z0-9][A-Za-z0-9_-]*)(=\??\*)?$").unwrap());
+
+/// A branch body consuming the option's argument
+static VALUE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{?2\b|\bshift[ \t]+2\b|\bshift\b[\s\S]*\$\{?1\b").unwrap());
+
+/// An option parsed from the script
+#[derive(Debug, PartialEq)]
+struct CliOption {
+    name: String,
+    aliases: Vec<String>,
+    takes_value: bool,
+    source: &'static str,
+    variable: String,
+    start: usize,
+    end: usize,
+}
+
+impl CliOption {
+    fn signature(&self) -> String {
+        let names: Vec<&str> = std::iter::once(self.name.as_str())
+            .chain(self.aliases.iter().map(String::as_str))
+            .collect();
+        let mut signature = names.join("|");
+        if self.takes_value {
+            signature.push_str(" <value>");
+        }
+        signature
+    }
+}
+
+pub(super) fn extract_options(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let mask = code_mask(ctx.content);
+    let mut options = getopts_options(ctx.content, &mask);
+    options.extend(case_options(ctx.content, &mask));
+    if options.is_empty() {
+        return;
+    }
+
+    let mut script_id = None;
+    let mut seen = HashSet::new();
+    for option in options {
+        let function = innermost_symbol_at(&results.symbols, option.start, |s| {
+            s.kind == SymbolKind::Function
+        })
+        .map(|s| s.id.clone());
+        let parent_id = match function {
+            Some(id) => id,
+            None => script_id
+                .get_or_insert_with(|| script_symbol(ctx, results))
+                .clone(),
+        };
+        if !seen.insert((parent_id.clone(), option.name.clone())) {
+            continue;
+        }
+
+        let mut metadata = HashMap::from([
+            ("isParameter".to_string(), Value::Bool(true)),
+            ("cliOption".to_string(), Value::Bool(true)),
+            ("takesValue".to_string(), Value::Bool(option.takes_value)),
+            ("source".to_string(), Value::from(option.source)),
+            ("variable".to_string(), Value::from(option.variable.clone())),
+        ]);
+        if !option.aliases.is_empty() {
+            metadata.insert("aliases".to_string(), Value::from(option.aliases.clone()));
+        }
+        results.symbols.push(ctx.create_symbol(
+            &option.name,
+            SymbolKind::Variable,
+            option.start,
+            option.end,
+            SymbolOptions {
+                signature: Some(option.signature()),
+                parent_id: Some(parent_id),
+                metadata: Some(metadata),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+/// The script's own symbol: a top-level Module spanning the file, added if missing
+fn script_symbol(ctx: &EnrichmentContext, results: &mut ExtractionResults) -> String {
+    let existing = results.symbols.iter().find(|s| {
+        s.kind == SymbolKind::Module
+            && s.parent_id.is_none()
+            && s.start_byte == 0
+            && s.end_byte as usize == ctx.content.len()
+    });
+    if let Some(script) = existing {
+        return script.id.clone();
+    }
+
+    let name = ctx.file_path.rsplit('/').next().unwrap_or(ctx.file_path);
+    let script = ctx.create_symbol(
+        name,
+        SymbolKind::Module,
+        0,
+        ctx.content.len(),
+        SymbolOptions {
+            metadata: Some(HashMap::from([("script".to_string(), Value::Bool(true))])),
+            ..Default::default()
+        },
+    );
+    let id = script.id.clone();
+    results.symbols.push(script);
+    id
+}
+
+/// Options declared by `getopts` option strings
+fn getopts_options(content: &str, mask: &[bool]) -> Vec<CliOption> {
+    let mut options = Vec::new();
+    for command in GETOPTS_RE.find_iter(content) {
+        if !mask[command.start()] {
+            continue;
+        }
+        let word_start = command.end();
+        let Some(word) = parse_shell_word(&content[word_start..]) else {
+            continue;
+        };
+        if word.has_expansion {
+            continue;
+        }
+        let rest = &content[word_start + word.len..];
+        let variable = parse_shell_word(rest.trim_start_matches([' ', '\t']))
+            .map(|variable| variable.value)
+            .unwrap_or_default();
+
+        // Offsets of the option string's characters, unless escapes make them ambiguous
+        let raw = &content[word_start..word_start + word.len];
+        let offsets: Vec<usize> = raw
+            .char_indices()
+            .filter(|&(_, c)| !matches!(c, '"' | '\''))
+            .map(|(index, _)| word_start + index)
+            .collect();
+        let letters: Vec<char> = word.value.chars().collect();
+        let exact = offsets.len() == letters.len();
+
+        for (index, &letter) in letters.iter().enumerate() {
+            if !letter.is_ascii_alphanumeric() {
+                continue;
+            }
+            let (start, end) = if exact {
+                (offsets[index], offsets[index] + 1)
+            } else {
+                (word_start, word_start + word.len)
+            };
+            options.push(CliOption {
+                name: format!("-{letter}"),
+                aliases: Vec::new(),
+                takes_value: letters.get(index + 1) == Some(&':'),
+                source: "getopts",
+                variable: variable.clone(),
+                start,
+                end,
+            });
+        }
+    }
+    options
+}
+
+/// Options matched by `case` branches over a variable
+fn case_options(content: &str, mask: &[bool]) -> Vec<CliOption> {
+    let mut options = Vec::new();
+    for captures in CASE_RE.captures_iter(content) {
+        let case = captures.get(0).unwrap();
+        if !mask[case.start()] {
+            continue;
+        }
+        let variable = &captures[1];
+        for (pattern_start, pattern_end, body) in case_items(content, mask, case.end()) {
+            if let Some(option) = pattern_option(
+                content,
+                pattern_start,
+                pattern_end,
+                &content[body.0..body.1],
+            ) {
+                options.push(CliOption {
+                    variable: variable.to_string(),
+                    ..option
+                });
+            }
+        }
+    }
+    options
+}
+
+/// The option a branch pattern names, if any of its alternatives is one
+fn pattern_option(content: &str, start: usize, end: usize, body: &str) -> Option<CliOption> {
+    let mut names: Vec<String> = Vec::new();
+    let mut takes_value = VALUE_RE.is_match(body);
+    for alternative in content[start..end].split('|') {
+        let Some(word) = parse_shell_word(alternative.trim()) else {
+            continue;
+        };
+        let Some(captures) = OPTION_RE.captures(&word.value) else {
+            continue;
+        };
+        takes_value |= captures.get(2).is_some();
+        let name = captures[1].to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    if names.is_empty() {
+        return None;
+    }
+
+    // The long form names the option
+    let primary = names.iter().position(|n| n.starts_with("--")).unwrap_or(0);
+    let name = names.remove(primary);
+    Some(CliOption {
+        name,
+        aliases: names,
+        takes_value,
+        source: "case",
+        variable: String::new(),
+        start,
+        end,
+    })
+}
+
+/// (pattern start, pattern end, body range) of each item of the `case` whose
+/// `in` ends at `start`
+fn case_items(content: &str, mask: &[bool], start: usize) -> Vec<(usize, usize, (usize, usize))> {
+    let bytes = content.as_bytes();
+    let mut items = Vec::new();
+    let mut index = start;
+    loop {
+        // Whitespace and comments before the pattern
+        while index < bytes.len()
+            && (bytes[index].is_ascii_whitespace() || (mask[index] && bytes[index] == b'#'))
+        {
+            if bytes[index] == b'#' {
+                index += content[index..].find('\n').unwrap_or(content.len() - index);
+            } else {
+                index += 1;
+            }
+        }
+        if index >= bytes.len() || is_word(content, mask, index, "esac") {
+            return items;
+        }
+        if bytes[index] == b'(' {
+            index += 1;
+        }
+        let pattern_start = index;
+        let Some(pattern_end) = (index..bytes.len()).find(|&i| mask[i] && bytes[i] == b')') else {
+            return items;
+        };
+
+        // The body runs to `;;`, `;&`, or `;;&`, or to the closing `esac`
+        let body_start = pattern_end + 1;
+        let mut depth = 0usize;
+        let mut cursor = body_start;
+        let mut finished = true;
+        while cursor < bytes.len() {
+            if !mask[cursor] {
+                cursor += 1;
+            } else if depth == 0
+                && (content[cursor..].starts_with(";;") || content[cursor..].starts_with(";&"))
+            {
+                finished = false;
+                break;
+            } else if is_word(content, mask, cursor, "case") {
+                depth += 1;
+                cursor += 4;
+            } else if is_word(content, mask, cursor, "esac") {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+                cursor += 4;
+            } else {
+                cursor += 1;
+            }
+        }
+        items.push((pattern_start, pattern_end, (body_start, cursor)));
+        if finished {
+            return items;
+        }
+        index = cursor + 2;
+        if bytes.get(index) == Some(&b'&') {
+            index += 1;
+        }
+    }
+}
+
+/// Whether the shell word `word` starts at `index`
+fn is_word(content: &str, mask: &[bool], index: usize, word: &str) -> bool {
+    let bytes = content.as_bytes();
+    mask[index]
+        && content[index..].starts_with(word)
+        && (index == 0 || is_word_boundary(bytes[index - 1]))
+        && bytes
+            .get(index + word.len())
+            .is_none_or(|&next| !(next.is_ascii_alphanumeric() || next == b'_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    fn extract(content: &str, symbols: Vec<julie_extractors::Symbol>) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("scripts/deploy.sh", "bash", content, Path::new("."));
+        let mut results = test_results(symbols);
+        extract_options(&ctx, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_getopts_letters_become_script_options() {
+        let content = "#!/bin/bash\n# getopts \"x\" ignored\nwhile getopts \":f:o:v\" opt; do\n  case $opt in\n    f) FILE=$OPTARG ;;\n    v) VERBOSE=1 ;;\n  esac\ndone\n";
+        let results = extract(content, Vec::new());
+
+        let script = &results.symbols[0];
+        assert_eq!(script.name, "deploy.sh");
+        assert_eq!(script.kind, SymbolKind::Module);
+        let options: Vec<(&str, &Value, Option<&str>)> = results.symbols[1..]
+            .iter()
+            .map(|s| {
+                assert_eq!(s.parent_id.as_ref(), Some(&script.id));
+                let metadata = s.metadata.as_ref().unwrap();
+                assert_eq!(metadata["variable"], Value::from("opt"));
+                (
+                    s.name.as_str(),
+                    &metadata["takesValue"],
+                    s.signature.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            options,
+            vec![
+                ("-f", &Value::Bool(true), Some("-f <value>")),
+                ("-o", &Value::Bool(true), Some("-o <value>")),
+                ("-v", &Value::Bool(false), Some("-v")),
+            ]
+        );
+        let f = &results.symbols[1];
+        assert_eq!(&content[f.start_byte as usize..f.end_byte as usize], "f");
+    }
+
+    #[test]
+    fn test_long_options_from_case_over_positional_parameters() {
+        let content = "deploy() {\n  while [ $# -gt 0 ]; do\n    case \"$1\" in\n      --output|-o) OUT=\"$2\"; shift 2 ;;\n      \"--dry-run\") DRY=1 ;;\n      --env=*) ENV=\"${1#*=}\" ;;\n      -h|--help)\n        case $mode in x) ;; esac\n        usage ;;\n      --) shift; break ;;\n      -*) die ;;\n    esac\n    shift\n  done\n}\n";
+        let ctx = EnrichmentContext::new("scripts/deploy.sh", "bash", content, Path::new("."));
+        let function = ctx.create_symbol(
+            "deploy",
+            SymbolKind::Function,
+            0,
+            content.len(),
+            SymbolOptions::default(),
+        );
+        let function_id = function.id.clone();
+        let results = extract(content, vec![function]);
+
+        let options: Vec<(&str, &Value, Option<&Value>)> = results.symbols[1..]
+            .iter()
+            .map(|s| {
+                assert_eq!(s.parent_id.as_ref(), Some(&function_id));
+                let metadata = s.metadata.as_ref().unwrap();
+                assert_eq!(metadata["source"], Value::from("case"));
+                assert_eq!(metadata["variable"], Value::from("1"));
+                (
+                    s.name.as_str(),
+                    &metadata["takesValue"],
+                    metadata.get("aliases"),
+                )
+            })
+            .collect();
+        assert_eq!(
+            options,
+            vec![
+                (
+                    "--output",
+                    &Value::Bool(true),
+                    Some(&Value::from(vec!["-o"]))
+                ),
+                ("--dry-run", &Value::Bool(false), None),
+                ("--env", &Value::Bool(true), None),
+                (
+                    "--help",
+                    &Value::Bool(false),
+                    Some(&Value::from(vec!["-h"]))
+                ),
+            ]
+        );
+        assert_eq!(
+            results.symbols[1].signature.as_deref(),
+            Some("--output|-o <value>")
+        );
+    }
+}
@@ -0,0 +1,357 @@
+// C preprocessor conditional tracking
+//
+// Headers often declare the same function once per platform:
+//
+//     #ifdef _WIN32
+//     int open_file(const wchar_t *path);
+//     #else
+//     int open_file(const char *path);
+//     #endif
+//
+// Julie extracts both declarations as identical-looking symbols. This pass maps
+// every #if/#ifdef/#ifndef group to its branches and tags each symbol inside one
+// with the branch condition (nested groups are joined with "&&") and the branch
+// index, so consumers can tell alternatives from duplicates. Conditions are
+// recorded verbatim, never evaluated. A whole-file include guard is ignored.
+
+use crate::enrichment::base::{code_mask, set_metadata, Lexicon};
+use julie_extractors::Symbol;
+
+/// A preprocessor directive (offsets of the full line, continuations included)
+#[derive(Debug)]
+struct Directive {
+    start: usize,
+    end: usize,
+    keyword: String,
+    argument: String,
+}
+
+/// One branch of a conditional group
+#[derive(Debug)]
+struct ConditionalRegion {
+    start: usize,
+    end: usize,
+    condition: String,
+    branch_index: usize,
+}
+
+/// An open #if group during the scan
+struct Frame {
+    /// Conditions of the branches already closed in this group
+    previous: Vec<String>,
+    /// Condition written on the open branch's directive (empty for #else)
+    own: String,
+    /// Effective condition of the open branch, without enclosing groups
+    current: String,
+    branch_index: usize,
+    start: usize,
+    /// Conjunction of the enclosing groups' open branches
+    enclosing: Vec<String>,
+}
+
+impl Frame {
+    fn full_condition(&self) -> String {
+        let mut parts = self.enclosing.clone();
+        parts.push(self.current.clone());
+        parts.join(" && ")
+    }
+}
+
+/// Tag symbols declared inside preprocessor conditionals
+pub(super) fn annotate_conditionals(content: &str, symbols: &mut [Symbol]) {
+    let regions = conditional_regions(content);
+    if regions.is_empty() {
+        return;
+    }
+
+    for symbol in symbols.iter_mut() {
+        let byte = symbol.start_byte as usize;
+        let innermost = regions
+            .iter()
+            .filter(|r| r.start <= byte && byte < r.end)
+            .min_by_key(|r| r.end - r.start);
+        if let Some(region) = innermost {
+            set_metadata(symbol, "preprocessorCondition", region.condition.clone());
+            set_metadata(symbol, "branchIndex", region.branch_index);
+        }
+    }
+}
+
+fn conditional_regions(content: &str) -> Vec<ConditionalRegion> {
+    let mut directives = find_directives(content);
+    strip_include_guard(content, &mut directives);
+
+    let mut regions = Vec::new();
+    let mut stack: Vec<Frame> = Vec::new();
+
+    for directive in directives {
+        match directive.keyword.as_str() {
+            "if" | "ifdef" | "ifndef" => {
+                let current = if directive.keyword == "ifndef" {
+                    format!("!defined({})", directive.argument)
+                } else {
+                    directive.argument
+                };
+                let enclosing = stack
+                    .last()
+                    .map(|frame| {
+                        let mut parts = frame.enclosing.clone();
+                        parts.push(frame.current.clone());
+                        parts
+                    })
+                    .unwrap_or_default();
+                stack.push(Frame {
+                    previous: Vec::new(),
+                    own: current.clone(),
+                    current,
+                    branch_index: 0,
+                    start: directive.end,
+                    enclosing,
+                });
+            }
+            "elif" | "elifdef" | "elifndef" | "else" => {
+                let Some(frame) = stack.last_mut() else {
+                    continue;
+                };
+                regions.push(ConditionalRegion {
+                    start: frame.start,
+                    end: directive.start,
+                    condition: frame.full_condition(),
+                    branch_index: frame.branch_index,
+                });
+
+                let own = match directive.keyword.as_str() {
+                    "elifdef" | "elif" => directive.argument,
+                    "elifndef" => format!("!defined({})", directive.argument),
+                    _ => String::new(),
+                };
+                let closed = std::mem::replace(&mut frame.own, own);
+                frame.previous.push(closed);
+
+                let mut parts: Vec<String> = frame.previous.iter().map(|c| negate(c)).collect();
+                if !frame.own.is_empty() {
+                    parts.push(frame.own.clone());
+                }
+                frame.current = parts.join(" && ");
+                frame.branch_index += 1;
+                frame.start = directive.end;
+            }
+            "endif" => {
+                if let Some(frame) = stack.pop() {
+                    regions.push(ConditionalRegion {
+                        start: frame.start,
+                        end: directive.start,
+                        condition: frame.full_condition(),
+                        branch_index: frame.branch_index,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    regions
+}
+
+/// Negate a condition, parenthesizing anything but a simple term
+fn negate(condition: &str) -> String {
+    if let Some(inner) = condition.strip_prefix('!') {
+        if is_simple_term(inner) {
+            return inner.to_string();
+        }
+    }
+    if is_simple_term(condition) {
+        format!("!{}", condition)
+    } else {
+        format!("!({})", condition)
+    }
+}
+
+/// An identifier or a single `defined(X)` call
+fn is_simple_term(condition: &str) -> bool {
+    let is_identifier =
+        |text: &str| !text.is_empty() && text.chars().all(|c| c.is_alphanumeric() || c == '_');
+    is_identifier(condition)
+        || condition
+            .strip_prefix("defined(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .is_some_and(|name| is_identifier(name.trim()))
+}
+
+/// Collect conditional directives whose `#` is real code (not in comments/strings)
+fn find_directives(content: &str) -> Vec<Directive> {
+    let mask = code_mask(content, Lexicon::C_LIKE);
+    let mut directives = Vec::new();
+    let mut line_start = 0;
+
+    while line_start < content.len() {
+        // Join backslash-continued lines into one logical line
+        let mut line_end = line_start;
+        loop {
+            line_end = content[line_end..]
+                .find('\n')
+                .map_or(content.len(), |i| line_end + i);
+            if content[line_start..line_end].trim_end().ends_with('\\') && line_end < content.len()
+            {
+                line_end += 1;
+                continue;
+            }
+            break;
+        }
+
+        let line = &content[line_start..line_end];
+        let indent = line.len() - line.trim_start().len();
+        let hash = line_start + indent;
+        if line[indent..].starts_with('#') && mask.get(hash).copied().unwrap_or(false) {
+            let directive = line[indent + 1..].trim_start();
+            let keyword_len = directive
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(directive.len());
+            let keyword = &directive[..keyword_len];
+            if matches!(
+                keyword,
+                "if" | "ifdef"
+                    | "ifndef"
+                    | "elif"
+                    | "elifdef"
+                    | "elifndef"
+                    | "else"
+                    | "endif"
+                    | "define"
+            ) {
+                directives.push(Directive {
+                    start: line_start,
+                    end: (line_end + 1).min(content.len()),
+                    keyword: keyword.to_string(),
+                    argument: clean_argument(&directive[keyword_len..]),
+                });
+            }
+        }
+        line_start = line_end + 1;
+    }
+    directives
+}
+
+/// Drop comments, continuations, and redundant whitespace from a directive argument
+fn clean_argument(argument: &str) -> String {
+    let mut text = argument.replace("\\\n", " ");
+    while let Some(start) = text.find("/*") {
+        let end = text[start..]
+            .find("*/")
+            .map_or(text.len(), |i| start + i + 2);
+        text.replace_range(start..end, " ");
+    }
+    if let Some(start) = text.find("//") {
+        text.truncate(start);
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Remove a classic `#ifndef X / #define X ... #endif` guard spanning the whole file
+fn strip_include_guard(content: &str, directives: &mut Vec<Directive>) {
+    let [first, second, ..] = directives.as_slice() else {
+        return;
+    };
+    let guard_name = first.argument.as_str();
+    let is_guard = first.keyword == "ifndef"
+        && second.keyword == "define"
+        && second.argument.split_whitespace().next() == Some(guard_name)
+        && directives.last().is_some_and(|d| d.keyword == "endif")
+        && only_comments(&content[..first.start])
+        && only_comments(&content[directives.last().unwrap().end..]);
+
+    if is_guard {
+        directives.pop();
+        directives.remove(0);
+    }
+}
+
+fn only_comments(text: &str) -> bool {
+    !code_mask(text, Lexicon::C_LIKE)
+        .iter()
+        .zip(text.bytes())
+        .any(|(&code, byte)| code && !byte.is_ascii_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{EnrichmentContext, SymbolOptions};
+    use julie_extractors::SymbolKind;
+    use serde_json::Value;
+    use std::path::Path;
+
+    /// Create a Function symbol at each occurrence of `name(`, then annotate
+    fn annotate(content: &str, name: &str) -> Vec<Symbol> {
+        let ctx = EnrichmentContext::new("platform.h", "c", content, Path::new("."));
+        let pattern = format!("{}(", name);
+        let mut symbols: Vec<Symbol> = content
+            .match_indices(&pattern)
+            .map(|(start, _)| {
+                ctx.create_symbol(
+                    name,
+                    SymbolKind::Function,
+                    start,
+                    start + name.len(),
+                    SymbolOptions::default(),
+                )
+            })
+            .collect();
+        annotate_conditionals(content, &mut symbols);
+        symbols
+    }
+
+    fn condition(symbol: &Symbol) -> (Option<&str>, Option<u64>) {
+        let metadata = symbol.metadata.as_ref();
+        (
+            metadata
+                .and_then(|m| m.get("preprocessorCondition"))
+                .and_then(Value::as_str),
+            metadata
+                .and_then(|m| m.get("branchIndex"))
+                .and_then(Value::as_u64),
+        )
+    }
+
+    #[test]
+    fn test_ifdef_else_branches() {
+        let content = "#ifdef _WIN32\nint open_file(const wchar_t *p);\n#else\nint open_file(const char *p);\n#endif\nint open_file_any(void);\n";
+        let symbols = annotate(content, "open_file");
+
+        assert_eq!(condition(&symbols[0]), (Some("_WIN32"), Some(0)));
+        assert_eq!(condition(&symbols[1]), (Some("!_WIN32"), Some(1)));
+    }
+
+    #[test]
+    fn test_nested_and_elif_conditions() {
+        let content = "#if !defined(NDEBUG)\n#  if defined(__GNUC__) // gcc\nvoid trace(void);\n#  elif _MSC_VER >= 1900\nvoid trace(void);\n#  else\nvoid trace(void);\n#  endif\n#endif\n";
+        let symbols = annotate(content, "trace");
+
+        assert_eq!(
+            condition(&symbols[0]),
+            (Some("!defined(NDEBUG) && defined(__GNUC__)"), Some(0))
+        );
+        assert_eq!(
+            condition(&symbols[1]),
+            (
+                Some("!defined(NDEBUG) && !defined(__GNUC__) && _MSC_VER >= 1900"),
+                Some(1)
+            )
+        );
+        assert_eq!(
+            condition(&symbols[2]),
+            (
+                Some("!defined(NDEBUG) && !defined(__GNUC__) && !(_MSC_VER >= 1900)"),
+                Some(2)
+            )
+        );
+    }
+
+    #[test]
+    fn test_include_guard_is_ignored() {
+        let content = "/* util.h */\n#ifndef UTIL_H\n#define UTIL_H\nint helper(void);\n#ifdef __linux__\nint helper(void);\n#endif\n#endif /* UTIL_H */\n";
+        let symbols = annotate(content, "helper");
+
+        assert_eq!(condition(&symbols[0]), (None, None));
+        assert_eq!(condition(&symbols[1]), (Some("__linux__"), Some(0)));
+    }
+}
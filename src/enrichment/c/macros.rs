@@ -0,0 +1,359 @@
+// C declaration macros
+//
+// C code often declares through a macro: `MODULE_EXPORT(int, start_server)(int
+// port) { ... }` defines a function and `DEFINE_LIST(IntList, int)` a type, but
+// the parser sees only a call (or an error), so Julie skips the declaration or
+// names the symbol after the macro. A table of DeclarationMacro patterns, the
+// defaults below plus any the caller passes in EnrichmentOptions, says which
+// macros declare something, what kind of symbol, and which argument holds its
+// name. Each top-level invocation of a matching macro becomes a symbol of that
+// kind and name (replacing a symbol Julie named after the macro on the same
+// line), with metadata.generatedByMacro = {"macro", "invocation"} and the
+// MACRO_GENERATED confidence, since the expansion itself is never seen.
+//
+// Function-like `#define`s also get metadata.macroParameters, their parameter
+// names in order; a pattern may name its argument by parameter instead of by
+// position when the file defines the macro.
+
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, set_metadata, split_top_level, EnrichmentContext, Lexicon,
+    SymbolOptions,
+};
+use crate::enrichment::confidence::MACRO_GENERATED;
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// A macro whose invocations declare a symbol
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeclarationMacro {
+    /// Macro name: exact, or with one `*` wildcard at the start or end
+    /// (`*_EXPORT`, `DEFINE_*`)
+    pub pattern: String,
+    /// Kind of the declared symbol
+    pub kind: SymbolKind,
+    /// The argument holding the declared symbol's name
+    pub name_argument: MacroArgument,
+}
+
+/// Which argument of an invocation to read
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MacroArgument {
+    /// Zero-based argument position
+    Position(usize),
+    /// Parameter name in the macro's `#define`; unresolved when the file does
+    /// not define the macro
+    Parameter(String),
+}
+
+impl DeclarationMacro {
+    pub fn new(pattern: &str, kind: SymbolKind, name_argument: MacroArgument) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            kind,
+            name_argument,
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match (
+            self.pattern.strip_prefix('*'),
+            self.pattern.strip_suffix('*'),
+        ) {
+            (Some(suffix), _) => name.len() > suffix.len() && name.ends_with(suffix),
+            (_, Some(prefix)) => name.len() > prefix.len() && name.starts_with(prefix),
+            _ => name == self.pattern,
+        }
+    }
+}
+
+/// Patterns applied to every C file, after the caller's
+pub fn default_declaration_macros() -> Vec<DeclarationMacro> {
+    vec![
+        // MODULE_EXPORT(int, start_server)(int port)
+        DeclarationMacro::new("*_EXPORT", SymbolKind::Function, MacroArgument::Position(1)),
+        DeclarationMacro::new("*_API", SymbolKind::Function, MacroArgument::Position(1)),
+        // DEFINE_LIST(IntList, int)
+        DeclarationMacro::new("DEFINE_*", SymbolKind::Struct, MacroArgument::Position(0)),
+    ]
+}
+
+/// `#define NAME(params)`, the name directly followed by "("
+static FUNCTION_DEFINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*#[ \t]*define[ \t]+([A-Za-z_]\w*)\(([^)\n]*)\)").unwrap()
+});
+
+/// A macro invocation opening a line, after optional storage classes
+static INVOCATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:(?:static|extern|inline)[ \t]+)*([A-Za-z_]\w*)[ \t]*\(").unwrap()
+});
+
+pub(super) fn extract_declaration_macros(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let parameters = record_macro_parameters(ctx, symbols);
+
+    let patterns: Vec<DeclarationMacro> = ctx
+        .options
+        .c_declaration_macros
+        .iter()
+        .cloned()
+        .chain(default_declaration_macros())
+        .collect();
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+    let depths = brace_depths(ctx.content, &code);
+
+    for captures in INVOCATION_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        let macro_name = captures.get(1).unwrap();
+        if !code[macro_name.start()] || depths[macro_name.start()] > 0 {
+            continue;
+        }
+        if continues_directive(ctx.content, whole.start()) {
+            continue;
+        }
+        let Some(pattern) = patterns.iter().find(|p| p.matches(macro_name.as_str())) else {
+            continue;
+        };
+
+        let open = whole.end() - 1;
+        let Some(close) = find_matching_delimiter(ctx.content, open, Lexicon::C_LIKE) else {
+            continue;
+        };
+        let arguments: Vec<&str> =
+            split_top_level(&ctx.content[open + 1..close], b',', Lexicon::C_LIKE)
+                .into_iter()
+                .map(|(_, argument)| argument.trim())
+                .collect();
+        let position = match &pattern.name_argument {
+            MacroArgument::Position(position) => Some(*position),
+            MacroArgument::Parameter(name) => parameters
+                .get(macro_name.as_str())
+                .and_then(|params| params.iter().position(|p| p == name)),
+        };
+        let Some(name) = position.and_then(|p| arguments.get(p)) else {
+            continue;
+        };
+        if !is_identifier(name) {
+            continue;
+        }
+
+        let (signature_end, end) = declaration_end(ctx.content, close);
+        let invocation = &ctx.content[macro_name.start()..signature_end];
+        let signature = invocation.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut symbol = ctx.create_symbol(
+            name,
+            pattern.kind.clone(),
+            macro_name.start(),
+            end,
+            SymbolOptions {
+                signature: Some(signature),
+                confidence: Some(MACRO_GENERATED),
+                ..Default::default()
+            },
+        );
+        set_metadata(
+            &mut symbol,
+            "generatedByMacro",
+            json!({ "macro": macro_name.as_str(), "invocation": invocation }),
+        );
+
+        let line = ctx.line_of(macro_name.start());
+        match symbols
+            .iter()
+            .position(|s| s.name == macro_name.as_str() && s.start_line == line)
+        {
+            Some(index) => {
+                // Keep the ID so children and relationships still point at it
+                symbol.id = symbols[index].id.clone();
+                symbols[index] = symbol;
+            }
+            None => symbols.push(symbol),
+        }
+    }
+}
+
+/// Record each function-like macro's parameter names on its symbol; returns
+/// them by macro name
+fn record_macro_parameters(
+    ctx: &EnrichmentContext,
+    symbols: &mut [Symbol],
+) -> HashMap<String, Vec<String>> {
+    let mut parameters = HashMap::new();
+    for captures in FUNCTION_DEFINE_RE.captures_iter(ctx.content) {
+        let name = captures.get(1).unwrap();
+        let params: Vec<String> = captures[2]
+            .split(',')
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        let line = ctx.line_of(name.start());
+        if let Some(symbol) = symbols
+            .iter_mut()
+            .find(|s| s.name == name.as_str() && s.start_line == line)
+        {
+            set_metadata(symbol, "macroParameters", Value::from(params.clone()));
+        }
+        parameters.insert(name.as_str().to_string(), params);
+    }
+    parameters
+}
+
+/// Brace nesting depth at every byte, counting code braces only
+fn brace_depths(content: &str, code: &[bool]) -> Vec<usize> {
+    let mut depth = 0usize;
+    content
+        .bytes()
+        .enumerate()
+        .map(|(index, byte)| {
+            let at = depth;
+            if code[index] {
+                match byte {
+                    b'{' => depth += 1,
+                    b'}' => depth = depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            at
+        })
+        .collect()
+}
+
+/// Whether the line at `start` continues a `#define` ending in a backslash
+fn continues_directive(content: &str, start: usize) -> bool {
+    content[..start]
+        .trim_end_matches([' ', '\t'])
+        .strip_suffix('\n')
+        .is_some_and(|before| before.trim_end_matches('\r').ends_with('\\'))
+}
+
+/// End of the invocation's signature (through a following parameter list) and
+/// of the whole declaration (through a body or `;`), given its `)`
+fn declaration_end(content: &str, close: usize) -> (usize, usize) {
+    let mut signature_end = close + 1;
+    let after = |at: usize| at + (content[at..].len() - content[at..].trim_start().len());
+    let mut next = after(signature_end);
+    if content[next..].starts_with('(') {
+        if let Some(params_close) = find_matching_delimiter(content, next, Lexicon::C_LIKE) {
+            signature_end = params_close + 1;
+            next = after(signature_end);
+        }
+    }
+    if content[next..].starts_with(';') {
+        return (signature_end, next + 1);
+    }
+    if content[next..].starts_with('{') {
+        if let Some(body_close) = find_matching_delimiter(content, next, Lexicon::C_LIKE) {
+            return (signature_end, body_close + 1);
+        }
+    }
+    (signature_end, signature_end)
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !super::TYPE_KEYWORDS.contains(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::EnrichmentOptions;
+    use std::path::Path;
+
+    const SERVER: &str = "#define MODULE_EXPORT(ret, name) __attribute__((visibility(\"default\"))) ret name\n\nMODULE_EXPORT(int, start_server)(int port)\n{\n    return listen(port);\n}\n\nDEFINE_LIST(IntList, int)\n\nstatic void helper(void) {\n    DEFINE_LIST(Local, int)\n}\n";
+
+    fn summary(symbols: &[Symbol]) -> Vec<(&str, SymbolKind, Option<&str>)> {
+        symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind.clone(), s.signature.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_default_patterns_synthesize_declared_symbols() {
+        let ctx = EnrichmentContext::new("server.c", "c", SERVER, Path::new("."));
+        let define = ctx.create_symbol(
+            "MODULE_EXPORT",
+            SymbolKind::Constant,
+            0,
+            SERVER.find('\n').unwrap(),
+            SymbolOptions::default(),
+        );
+        // Julie names the function after the macro
+        let start = SERVER.find("MODULE_EXPORT(int").unwrap();
+        let misnamed = ctx.create_symbol(
+            "MODULE_EXPORT",
+            SymbolKind::Function,
+            start,
+            SERVER.find("}\n").unwrap() + 1,
+            SymbolOptions::default(),
+        );
+        let misnamed_id = misnamed.id.clone();
+        let mut symbols = vec![define, misnamed];
+        extract_declaration_macros(&ctx, &mut symbols);
+
+        assert_eq!(
+            summary(&symbols),
+            vec![
+                ("MODULE_EXPORT", SymbolKind::Constant, None),
+                (
+                    "start_server",
+                    SymbolKind::Function,
+                    Some("MODULE_EXPORT(int, start_server)(int port)")
+                ),
+                (
+                    "IntList",
+                    SymbolKind::Struct,
+                    Some("DEFINE_LIST(IntList, int)")
+                ),
+            ]
+        );
+        let metadata = symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(metadata["macroParameters"], json!(["ret", "name"]));
+
+        let server = &symbols[1];
+        assert_eq!(server.id, misnamed_id);
+        assert_eq!(server.confidence, Some(MACRO_GENERATED));
+        assert_eq!((server.start_line, server.end_line), (3, 6));
+        assert_eq!(
+            server.metadata.as_ref().unwrap()["generatedByMacro"],
+            json!({
+                "macro": "MODULE_EXPORT",
+                "invocation": "MODULE_EXPORT(int, start_server)(int port)"
+            })
+        );
+    }
+
+    #[test]
+    fn test_caller_patterns_can_name_the_argument_by_parameter() {
+        let content = "#define REGISTER_HANDLER(path, fn) static int fn(void)\n\nREGISTER_HANDLER(\"/health\", health_check);\nSLOT_EXPORT(x);\n";
+        let options = EnrichmentOptions {
+            c_declaration_macros: vec![DeclarationMacro::new(
+                "REGISTER_HANDLER",
+                SymbolKind::Function,
+                MacroArgument::Parameter("fn".to_string()),
+            )],
+            ..Default::default()
+        };
+        let ctx =
+            EnrichmentContext::new("routes.c", "c", content, Path::new(".")).with_options(options);
+        let mut symbols = Vec::new();
+        extract_declaration_macros(&ctx, &mut symbols);
+
+        // SLOT_EXPORT has no second argument to name anything
+        assert_eq!(
+            summary(&symbols),
+            vec![(
+                "health_check",
+                SymbolKind::Function,
+                Some("REGISTER_HANDLER(\"/health\", health_check)")
+            )]
+        );
+        assert_eq!(
+            symbols[0].end_byte as usize,
+            content.find(";\n").unwrap() + 1
+        );
+    }
+}
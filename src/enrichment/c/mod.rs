@@ -0,0 +1,143 @@
+// C enrichment passes
+//
+// Julie's C extractor produces functions, structs, typedefs, and macros; these
+// passes synthesize the symbols declaration macros hide, add per-parameter
+// symbols for function definitions, record the preprocessor conditionals
+// each symbol is declared under, and record the prototype/definition shape
+// of functions that batch pairing of headers with sources needs (shared with
+// the C++ passes).
+
+mod conditionals;
+mod macros;
+mod parameters;
+mod prototypes;
+
+use super::base::EnrichmentContext;
+use julie_extractors::ExtractionResults;
+
+pub use macros::{default_declaration_macros, DeclarationMacro, MacroArgument};
+pub(super) use prototypes::record_prototypes;
+
+pub const EXTRACTOR_VERSION: u32 = 3;
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    macros::extract_declaration_macros(ctx, &mut results.symbols);
+    parameters::enrich_parameters(ctx, &mut results.symbols);
+    conditionals::annotate_conditionals(ctx.content, &mut results.symbols);
+    prototypes::record_prototypes(ctx, &mut results.symbols);
+}
+
+/// Keywords that name (part of) a builtin type rather than a declarator
+const TYPE_KEYWORDS: &[&str] = &[
+    "void", "char", "short", "int", "long", "float", "double", "signed", "unsigned", "_Bool",
+    "bool", "_Complex",
+];
+
+/// Keywords that qualify a declaration without naming anything
+const QUALIFIER_KEYWORDS: &[&str] = &[
+    "const",
+    "volatile",
+    "restrict",
+    "__restrict",
+    "register",
+    "static",
+    "extern",
+    "inline",
+    "struct",
+    "union",
+    "enum",
+];
+
+/// The identifier a C declarator declares, with its byte range in `declaration`
+///
+/// Handles pointers, arrays, and function pointers (`int (*cb)(int)`). Returns
+/// None for abstract declarators such as `const char *` in prototypes.
+fn declarator_name(declaration: &str) -> Option<(usize, usize)> {
+    let bytes = declaration.as_bytes();
+
+    // Function pointer: the name sits inside the first "(*name)" group
+    if let Some(open) = declaration.find('(') {
+        let inner = declaration[open + 1..].trim_start();
+        if inner.starts_with('*') || inner.starts_with('^') {
+            let offset = open + 1 + (declaration[open + 1..].len() - inner.len());
+            let name_start = offset
+                + inner
+                    .find(|c: char| c.is_alphabetic() || c == '_')
+                    .filter(|&i| inner[..i].chars().all(|c| c == '*' || c.is_whitespace()))?;
+            let name_len = identifier_len(&declaration[name_start..]);
+            return Some((name_start, name_start + name_len));
+        }
+    }
+
+    // Otherwise the last identifier outside array brackets
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    let mut index = 0;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match byte {
+            b'[' | b'(' => depth += 1,
+            b']' | b')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && (byte.is_ascii_alphabetic() || byte == b'_') => {
+                let len = identifier_len(&declaration[index..]);
+                words.push((index, index + len));
+                index += len;
+                continue;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    let names: Vec<(usize, usize)> = words
+        .into_iter()
+        .filter(|&(start, end)| !QUALIFIER_KEYWORDS.contains(&&declaration[start..end]))
+        .collect();
+    let &(start, end) = names.last()?;
+    let last = &declaration[start..end];
+    if TYPE_KEYWORDS.contains(&last) {
+        return None;
+    }
+    let has_builtin_type = names[..names.len() - 1]
+        .iter()
+        .any(|&(s, e)| TYPE_KEYWORDS.contains(&&declaration[s..e]));
+    if names.len() < 2 && !has_builtin_type {
+        return None;
+    }
+    Some((start, end))
+}
+
+fn identifier_len(text: &str) -> usize {
+    text.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(declaration: &str) -> Option<&str> {
+        declarator_name(declaration).map(|(start, end)| &declaration[start..end])
+    }
+
+    #[test]
+    fn test_declarator_name() {
+        assert_eq!(name("const char *argv[]"), Some("argv"));
+        assert_eq!(name("unsigned count"), Some("count"));
+        assert_eq!(name("struct node **head"), Some("head"));
+        assert_eq!(
+            name("int (*compare)(const void *, const void *)"),
+            Some("compare")
+        );
+        assert_eq!(name("size_t len"), Some("len"));
+        assert_eq!(name("char buf[MAX_LEN]"), Some("buf"));
+    }
+
+    #[test]
+    fn test_abstract_declarators_have_no_name() {
+        assert_eq!(name("const char *"), None);
+        assert_eq!(name("unsigned long"), None);
+        assert_eq!(name("struct node *"), None);
+        assert_eq!(name("size_t"), None);
+    }
+}
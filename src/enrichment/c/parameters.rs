@@ -0,0 +1,315 @@
+// C parameter enrichment
+//
+// Julie's C extractor records a function's signature but not its parameters, so
+// references to a parameter inside a long function body have nothing to resolve
+// to. This pass parses the parameter list of every function definition and adds
+// one Variable symbol per parameter, parented to the function, with the full
+// declarator ("const char *argv[]") as signature. K&R-style definitions take the
+// declarations between the ")" and the body; a trailing "..." marks the function
+// as variadic instead of producing a symbol.
+
+use super::{declarator_name, identifier_len};
+use crate::enrichment::base::{
+    collapse_whitespace, find_matching_delimiter, set_metadata, split_top_level, trim_with_offset,
+    CodeBytes, EnrichmentContext, Lexicon, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// A parameter parsed from a definition (offsets relative to the function text)
+#[derive(Debug)]
+struct ParsedParameter {
+    name: String,
+    start: usize,
+    end: usize,
+    signature: String,
+    type_name: String,
+}
+
+/// Add parameter symbols to every C function definition in the file
+pub(super) fn enrich_parameters(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let functions: Vec<(String, String, usize, usize)> = symbols
+        .iter()
+        .filter(|s| s.kind == SymbolKind::Function)
+        .map(|s| {
+            (
+                s.id.clone(),
+                s.name.clone(),
+                s.start_byte as usize,
+                s.end_byte as usize,
+            )
+        })
+        .collect();
+
+    for (function_id, name, start, end) in functions {
+        let text = ctx.text(start, end);
+        let Some(definition) = parse_definition(text, &name) else {
+            continue;
+        };
+
+        if definition.variadic {
+            if let Some(function) = symbols.iter_mut().find(|s| s.id == function_id) {
+                set_metadata(function, "variadic", true);
+            }
+        }
+
+        for (position, parameter) in definition.parameters.into_iter().enumerate() {
+            let mut metadata = HashMap::new();
+            metadata.insert("isParameter".to_string(), Value::Bool(true));
+            metadata.insert("position".to_string(), Value::from(position));
+            metadata.insert("type".to_string(), Value::from(parameter.type_name));
+            if definition.knr_style {
+                metadata.insert("knrStyle".to_string(), Value::Bool(true));
+            }
+
+            if let Some(existing) = symbols
+                .iter_mut()
+                .find(|s| s.parent_id.as_deref() == Some(&function_id) && s.name == parameter.name)
+            {
+                existing.signature = Some(parameter.signature);
+                for (key, value) in metadata {
+                    set_metadata(existing, &key, value);
+                }
+                continue;
+            }
+
+            symbols.push(ctx.create_symbol(
+                &parameter.name,
+                SymbolKind::Variable,
+                start + parameter.start,
+                start + parameter.end,
+                SymbolOptions {
+                    signature: Some(parameter.signature),
+                    parent_id: Some(function_id.clone()),
+                    metadata: Some(metadata),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Definition {
+    parameters: Vec<ParsedParameter>,
+    variadic: bool,
+    knr_style: bool,
+}
+
+/// Parse the parameters of a function definition; prototypes yield None
+fn parse_definition(text: &str, name: &str) -> Option<Definition> {
+    let open = find_parameter_list_open(text, name)?;
+    let close = find_matching_delimiter(text, open, Lexicon::C_LIKE)?;
+
+    let body = CodeBytes::new(text, close + 1, Lexicon::C_LIKE)
+        .find(|&(_, byte)| byte == b'{')?
+        .0;
+    let declarations = &text[close + 1..body];
+
+    let list = &text[open + 1..close];
+    let mut definition = Definition::default();
+    if list.trim().is_empty() || list.trim() == "void" {
+        return Some(definition);
+    }
+
+    let mut segments = Vec::new();
+    for (offset, segment) in split_top_level(list, b',', Lexicon::C_LIKE) {
+        let (offset, segment) = trim_with_offset(open + 1 + offset, segment);
+        if segment == "..." {
+            definition.variadic = true;
+        } else if !segment.is_empty() {
+            segments.push((offset, segment));
+        }
+    }
+
+    let bare_names = segments
+        .iter()
+        .all(|(_, segment)| identifier_len(segment) == segment.len());
+    if bare_names && !declarations.trim().is_empty() {
+        definition.knr_style = true;
+        definition.parameters = parse_knr_parameters(&segments, declarations, close + 1);
+        return Some(definition);
+    }
+    if declarations.contains(';') {
+        // A prototype followed by unrelated code
+        return None;
+    }
+
+    for (offset, segment) in segments {
+        let Some((name_start, name_end)) = declarator_name(segment) else {
+            continue;
+        };
+        definition.parameters.push(ParsedParameter {
+            name: segment[name_start..name_end].to_string(),
+            start: offset,
+            end: offset + segment.len(),
+            signature: collapse_whitespace(segment),
+            type_name: collapse_whitespace(&format!(
+                "{}{}",
+                &segment[..name_start],
+                &segment[name_end..]
+            )),
+        });
+    }
+    Some(definition)
+}
+
+/// Find the "(" that follows the function's name
+pub(super) fn find_parameter_list_open(text: &str, name: &str) -> Option<usize> {
+    CodeBytes::new(text, 0, Lexicon::C_LIKE)
+        .filter(|&(_, byte)| byte == b'(')
+        .map(|(index, _)| index)
+        .find(|&index| {
+            let before = text[..index].trim_end();
+            before.ends_with(name)
+                && before[..before.len() - name.len()]
+                    .bytes()
+                    .last()
+                    .is_none_or(|b| !(b.is_ascii_alphanumeric() || b == b'_'))
+        })
+}
+
+/// Match K&R identifier lists with the declarations before the body:
+/// `int f(a, b) int a; char *b; { ... }`. Undeclared names default to int.
+fn parse_knr_parameters(
+    names: &[(usize, &str)],
+    declarations: &str,
+    declarations_offset: usize,
+) -> Vec<ParsedParameter> {
+    let mut declared: HashMap<String, ParsedParameter> = HashMap::new();
+
+    for (offset, declaration) in split_top_level(declarations, b';', Lexicon::C_LIKE) {
+        let mut base_type = None;
+        for (item_offset, item) in split_top_level(declaration, b',', Lexicon::C_LIKE) {
+            let (item_offset, item) = trim_with_offset(offset + item_offset, item);
+            if item.is_empty() {
+                continue;
+            }
+            // Later declarators in "char *a, b" inherit the leading base type
+            let declarator = match &base_type {
+                Some(base) => format!("{} {}", base, item),
+                None => item.to_string(),
+            };
+            let Some((name_start, name_end)) = declarator_name(&declarator) else {
+                continue;
+            };
+            if base_type.is_none() {
+                base_type = Some(
+                    declarator[..name_start]
+                        .trim_end_matches(|c: char| c == '*' || c.is_whitespace())
+                        .to_string(),
+                );
+            }
+            let name = declarator[name_start..name_end].to_string();
+            declared.insert(
+                name.clone(),
+                ParsedParameter {
+                    name,
+                    start: declarations_offset + item_offset,
+                    end: declarations_offset + item_offset + item.len(),
+                    signature: collapse_whitespace(&declarator),
+                    type_name: collapse_whitespace(&format!(
+                        "{}{}",
+                        &declarator[..name_start],
+                        &declarator[name_end..]
+                    )),
+                },
+            );
+        }
+    }
+
+    names
+        .iter()
+        .map(|&(offset, name)| {
+            declared.remove(name).unwrap_or_else(|| ParsedParameter {
+                name: name.to_string(),
+                start: offset,
+                end: offset + name.len(),
+                signature: format!("int {}", name),
+                type_name: "int".to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn enrich(content: &str, name: &str) -> Vec<Symbol> {
+        let ctx = EnrichmentContext::new("main.c", "c", content, Path::new("."));
+        let function = ctx.create_symbol(
+            name,
+            SymbolKind::Function,
+            0,
+            content.len(),
+            SymbolOptions::default(),
+        );
+        let mut symbols = vec![function];
+        enrich_parameters(&ctx, &mut symbols);
+        symbols
+    }
+
+    fn parameter<'s>(symbols: &'s [Symbol], name: &str) -> &'s Symbol {
+        symbols
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap_or_else(|| panic!("parameter {} not extracted", name))
+    }
+
+    #[test]
+    fn test_parameters_keep_full_declarator() {
+        let content = "int main(int argc, const char *argv[])\n{\n    return argc;\n}";
+        let symbols = enrich(content, "main");
+        assert_eq!(symbols.len(), 3);
+
+        let argv = parameter(&symbols, "argv");
+        assert_eq!(argv.signature.as_deref(), Some("const char *argv[]"));
+        assert_eq!(argv.kind, SymbolKind::Variable);
+        assert_eq!(argv.parent_id.as_deref(), Some(symbols[0].id.as_str()));
+        let metadata = argv.metadata.as_ref().unwrap();
+        assert_eq!(metadata["type"], Value::from("const char *[]"));
+        assert_eq!(metadata["position"], Value::from(1));
+        assert_eq!(metadata["isParameter"], Value::Bool(true));
+    }
+
+    #[test]
+    fn test_variadic_and_function_pointer_parameters() {
+        let content = "static void log_with(void (*sink)(const char *), const char *fmt, ...) { }";
+        let symbols = enrich(content, "log_with");
+
+        assert_eq!(
+            parameter(&symbols, "sink").signature.as_deref(),
+            Some("void (*sink)(const char *)")
+        );
+        assert!(parameter(&symbols, "fmt").metadata.is_some());
+        assert_eq!(symbols.len(), 3, "... does not become a symbol");
+        assert_eq!(
+            symbols[0].metadata.as_ref().unwrap()["variadic"],
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn test_knr_definition() {
+        let content = "int copy(dst, src, n)\n    char *dst, *src;\n{\n    return n;\n}";
+        let symbols = enrich(content, "copy");
+
+        let src = parameter(&symbols, "src");
+        assert_eq!(src.signature.as_deref(), Some("char *src"));
+        assert_eq!(src.start_line, 2);
+        assert_eq!(
+            src.metadata.as_ref().unwrap()["knrStyle"],
+            Value::Bool(true)
+        );
+        assert_eq!(parameter(&symbols, "n").signature.as_deref(), Some("int n"));
+    }
+
+    #[test]
+    fn test_prototypes_and_void_lists_are_skipped() {
+        assert_eq!(enrich("int add(int a, int b);", "add").len(), 1);
+        assert_eq!(enrich("int tick(void) { return 0; }", "tick").len(), 1);
+    }
+}
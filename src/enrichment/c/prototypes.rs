@@ -0,0 +1,293 @@
+// C/C++ function prototypes and definitions
+//
+// A function declared in foo.h and defined in foo.c can only be paired once
+// both files are extracted (engine::resolve_cross_file). This pass records
+// what the pairing needs on every function (and, for C++, method,
+// constructor, and destructor) symbol whose parameter list it can read:
+//
+// - metadata.prototype: true for a declaration without a body, false for a
+//   definition (K&R definitions included)
+// - metadata.parameterTypes: the parameter types with names, defaults, and
+//   top-level qualifiers dropped, arrays as pointers, and spacing
+//   normalized, so `const char *s` and `const char*` are both "const char*"
+//   and `const size_t n` is "size_t"; `(void)` is [] and a trailing `...`
+//   is kept as "..."
+// - metadata.fileLocal: true for `static` non-member functions
+// - metadata.externC: true inside an `extern "C" { ... }` block or after
+//   `extern "C"`
+//
+// Pure virtual, defaulted, and deleted members (`= 0`, `= default`) are left
+// alone: there is nothing to pair them with.
+
+use super::parameters::find_parameter_list_open;
+use super::{declarator_name, identifier_len, QUALIFIER_KEYWORDS};
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, set_metadata, split_top_level, CodeBytes,
+    EnrichmentContext, Lexicon,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static EXTERN_C_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\bextern\s*"C(?:\+\+)?"\s*(\{)?"#).unwrap());
+
+/// Qualifiers that don't change which function a parameter type matches
+const TOP_LEVEL_QUALIFIERS: &[&str] = &["const", "volatile", "register"];
+
+pub(in crate::enrichment) fn record_prototypes(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let extern_blocks = extern_c_blocks(ctx.content);
+    for symbol in symbols.iter_mut().filter(|s| {
+        matches!(
+            s.kind,
+            SymbolKind::Function
+                | SymbolKind::Method
+                | SymbolKind::Constructor
+                | SymbolKind::Destructor
+        )
+    }) {
+        let start = symbol.start_byte as usize;
+        let text = ctx.symbol_text(symbol);
+        let Some(shape) = function_shape(text, &symbol.name) else {
+            continue;
+        };
+        set_metadata(symbol, "prototype", shape.prototype);
+        set_metadata(symbol, "parameterTypes", shape.parameter_types);
+
+        let member = symbol.kind != SymbolKind::Function || shape.qualified;
+        if shape.is_static && !member {
+            set_metadata(symbol, "fileLocal", true);
+        }
+        let in_block = extern_blocks.iter().any(|&(s, e)| s < start && start < e);
+        if in_block || shape.extern_c {
+            set_metadata(symbol, "externC", true);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct FunctionShape {
+    prototype: bool,
+    parameter_types: Vec<String>,
+    is_static: bool,
+    extern_c: bool,
+    /// `Widget::render`: a member defined outside its class
+    qualified: bool,
+}
+
+/// Shape of the function named `name` whose declaration or definition is `text`
+fn function_shape(text: &str, name: &str) -> Option<FunctionShape> {
+    let open = find_parameter_list_open(text, name)?;
+    let close = find_matching_delimiter(text, open, Lexicon::C_LIKE)?;
+    let list = &text[open + 1..close];
+    let segments: Vec<&str> = split_top_level(list, b',', Lexicon::C_LIKE)
+        .into_iter()
+        .map(|(_, segment)| segment.trim())
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    // The first `{` or `;` after the list ends the declaration
+    let terminator = CodeBytes::new(text, close + 1, Lexicon::C_LIKE)
+        .find(|&(_, byte)| matches!(byte, b'{' | b';'));
+    let tail = &text[close + 1..terminator.map_or(text.len(), |(index, _)| index)];
+    if tail.contains('=') {
+        return None;
+    }
+    let knr = !segments.is_empty()
+        && segments
+            .iter()
+            .all(|segment| identifier_len(segment) == segment.len() && *segment != "void");
+    let prototype = match terminator {
+        Some((_, b'{')) => false,
+        // `int f(a, b) int a; char *b; { ... }`
+        Some(_) if knr => !text[close + 1..].contains('{'),
+        _ => true,
+    };
+
+    let parameter_types = if segments == ["void"] {
+        Vec::new()
+    } else {
+        segments
+            .iter()
+            .map(|segment| parameter_type(segment))
+            .collect()
+    };
+    let prefix = &text[..open];
+    Some(FunctionShape {
+        prototype,
+        parameter_types,
+        is_static: prefix
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .any(|word| word == "static"),
+        extern_c: EXTERN_C_RE.is_match(prefix),
+        qualified: prefix
+            .trim_end()
+            .strip_suffix(name)
+            .is_some_and(|scope| scope.trim_end().ends_with("::")),
+    })
+}
+
+/// Normalized type of one parameter declaration
+fn parameter_type(segment: &str) -> String {
+    if segment == "..." {
+        return segment.to_string();
+    }
+    // C++ default argument
+    let declaration = split_top_level(segment, b'=', Lexicon::C_LIKE)
+        .first()
+        .map_or(segment, |(_, text)| text.trim());
+    let type_text = match declarator_name(declaration) {
+        // `std::string` is a type, not `std::` and a name
+        Some((start, _)) if declaration[..start].trim_end().ends_with("::") => {
+            declaration.to_string()
+        }
+        Some((start, end)) if has_type_word(&declaration[..start]) => {
+            format!("{} {}", &declaration[..start], &declaration[end..])
+        }
+        _ => declaration.to_string(),
+    };
+    normalize_type(&type_text)
+}
+
+/// Whether `text` names a type beyond qualifiers (`const` alone does not)
+fn has_type_word(text: &str) -> bool {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|word| !word.is_empty() && !QUALIFIER_KEYWORDS.contains(&word))
+}
+
+/// `const char * []` → `const char**`; `const int` → `int`
+fn normalize_type(type_text: &str) -> String {
+    // Array parameters are pointers
+    let mut text = type_text.to_string();
+    if let (Some(open), Some(close)) = (text.find('['), text.find(']')) {
+        if open < close {
+            text.replace_range(open..=close, "*");
+        }
+    }
+
+    let mut tokens: Vec<String> = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() || c == '_' {
+            word.push(c);
+            continue;
+        }
+        if !word.is_empty() {
+            tokens.push(std::mem::take(&mut word));
+        }
+        if !c.is_whitespace() {
+            tokens.push(c.to_string());
+        }
+    }
+    if !word.is_empty() {
+        tokens.push(word);
+    }
+
+    // Qualifiers of the parameter itself: all of them without indirection,
+    // else those after the last `*` or `&`
+    let last_indirection = tokens.iter().rposition(|t| t == "*" || t == "&");
+    let kept = tokens.iter().enumerate().filter(|&(index, token)| {
+        let top_level = last_indirection.is_none_or(|last| index > last);
+        !(top_level && TOP_LEVEL_QUALIFIERS.contains(&token.as_str()))
+    });
+
+    let mut normalized = String::new();
+    for (_, token) in kept {
+        let is_word = token.starts_with(|c: char| c.is_alphanumeric() || c == '_');
+        let after_word = normalized.ends_with(|c: char| c.is_alphanumeric() || c == '_');
+        if is_word && after_word {
+            normalized.push(' ');
+        }
+        normalized.push_str(token);
+    }
+    normalized
+}
+
+/// Byte ranges of `extern "C" { ... }` blocks
+fn extern_c_blocks(content: &str) -> Vec<(usize, usize)> {
+    let code = code_mask(content, Lexicon::C_LIKE);
+    EXTERN_C_RE
+        .captures_iter(content)
+        .filter(|c| code[c.get(0).unwrap().start()])
+        .filter_map(|c| {
+            let open = c.get(1)?.start();
+            let close = find_matching_delimiter(content, open, Lexicon::C_LIKE)?;
+            Some((open, close))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prototypes_and_definitions_normalize_to_the_same_types() {
+        let declaration = function_shape(
+            "extern int copy(const char* src, char dst[], size_t);",
+            "copy",
+        )
+        .unwrap();
+        let definition = function_shape(
+            "int copy(const char *src, char *const dst, const size_t n)\n{\n    return 0;\n}",
+            "copy",
+        )
+        .unwrap();
+        assert!(declaration.prototype);
+        assert!(!definition.prototype);
+        assert_eq!(
+            declaration.parameter_types,
+            vec!["const char*", "char*", "size_t"]
+        );
+        assert_eq!(declaration.parameter_types, definition.parameter_types);
+
+        let knr = function_shape("int add(a, b) int a; int b; { return a + b; }", "add").unwrap();
+        assert!(!knr.prototype);
+        let cpp = function_shape(
+            "void Widget::resize(const std::string &name, int scale = 2) const {}",
+            "resize",
+        )
+        .unwrap();
+        assert_eq!(cpp.parameter_types, vec!["const std::string&", "int"]);
+        assert!(cpp.qualified);
+        assert_eq!(function_shape("virtual void draw() = 0;", "draw"), None);
+        assert_eq!(
+            function_shape("static void helper(void);", "helper")
+                .map(|shape| (shape.is_static, shape.parameter_types)),
+            Some((true, Vec::new()))
+        );
+    }
+
+    #[test]
+    fn test_extern_c_blocks_and_file_local_functions() {
+        let content = "#ifdef __cplusplus\nextern \"C\" {\n#endif\nint open_db(const char *path);\n#ifdef __cplusplus\n}\n#endif\nstatic int cache(void);\nint close_db(int fd);\n";
+        let ctx = EnrichmentContext::new("db.h", "c", content, std::path::Path::new("."));
+        let mut symbols: Vec<Symbol> = ["open_db", "cache", "close_db"]
+            .iter()
+            .map(|name| {
+                let start = content.find(&format!("{name}(")).unwrap();
+                let start = content[..start].rfind('\n').unwrap() + 1;
+                let end = start + content[start..].find(';').unwrap() + 1;
+                ctx.create_symbol(name, SymbolKind::Function, start, end, Default::default())
+            })
+            .collect();
+        record_prototypes(&ctx, &mut symbols);
+
+        let meta = |index: usize, key: &str| {
+            symbols[index]
+                .metadata
+                .as_ref()
+                .and_then(|m| m.get(key))
+                .cloned()
+        };
+        assert_eq!(meta(0, "externC"), Some(true.into()));
+        assert_eq!(
+            meta(0, "parameterTypes"),
+            Some(serde_json::json!(["const char*"]))
+        );
+        assert_eq!(meta(1, "fileLocal"), Some(true.into()));
+        assert_eq!(meta(1, "externC"), None);
+        assert_eq!(meta(2, "externC"), None);
+        assert_eq!(meta(2, "prototype"), Some(true.into()));
+    }
+}
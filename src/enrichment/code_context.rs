@@ -0,0 +1,137 @@
+// Code context - a few source lines around each symbol, for result display
+//
+// Opt-in through EnrichmentOptions::code_context. Each symbol's code_context
+// is the lines from `lines_before` above its first line to `lines_after`
+// below it, sliced from the content already in memory. Lines longer than
+// MAX_LINE_BYTES are cut (on a UTF-8 character boundary) and end with "…",
+// and a snippet stops growing at MAX_CONTEXT_BYTES, so a minified one-line
+// file costs at most a few hundred bytes per symbol.
+
+use julie_extractors::ExtractionResults;
+
+/// Longest line kept in full, in bytes
+pub const MAX_LINE_BYTES: usize = 200;
+/// Largest snippet stored on one symbol, in bytes
+pub const MAX_CONTEXT_BYTES: usize = 2048;
+
+/// How many lines to keep around each symbol's first line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CodeContextOptions {
+    pub lines_before: usize,
+    pub lines_after: usize,
+}
+
+impl Default for CodeContextOptions {
+    fn default() -> Self {
+        CodeContextOptions {
+            lines_before: 2,
+            lines_after: 2,
+        }
+    }
+}
+
+/// Set code_context on every symbol
+pub fn assign_code_context(
+    content: &str,
+    options: CodeContextOptions,
+    results: &mut ExtractionResults,
+) {
+    if results.symbols.is_empty() {
+        return;
+    }
+    let lines: Vec<&str> = content
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
+
+    for symbol in &mut results.symbols {
+        // start_line is 1-based
+        let line = (symbol.start_line as usize)
+            .saturating_sub(1)
+            .min(lines.len() - 1);
+        let first = line.saturating_sub(options.lines_before);
+        let last = (line + options.lines_after).min(lines.len() - 1);
+
+        let mut snippet = String::new();
+        for text in &lines[first..=last] {
+            let text = truncate(text, MAX_LINE_BYTES);
+            if !snippet.is_empty() && snippet.len() + text.len() + 1 > MAX_CONTEXT_BYTES {
+                break;
+            }
+            if !snippet.is_empty() {
+                snippet.push('\n');
+            }
+            snippet.push_str(&text);
+        }
+        symbol.code_context = Some(snippet);
+    }
+}
+
+/// `text` cut to at most `max_bytes` (plus "…") without splitting a character
+fn truncate(text: &str, max_bytes: usize) -> std::borrow::Cow<'_, str> {
+    if text.len() <= max_bytes {
+        return text.into();
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &text[..end]).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, EnrichmentContext, SymbolOptions};
+    use julie_extractors::SymbolKind;
+    use std::path::Path;
+
+    fn results_for(content: &str, names: &[&str]) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("app.py", "python", content, Path::new("."));
+        test_results(
+            names
+                .iter()
+                .map(|name| {
+                    let start = content.find(name).unwrap();
+                    ctx.create_symbol(
+                        name,
+                        SymbolKind::Function,
+                        start,
+                        start + name.len(),
+                        SymbolOptions::default(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_lines_around_the_first_line_clamped_to_the_file() {
+        let content =
+            "import os\r\n\r\ndef first():\r\n    pass\r\n\r\ndef second():\r\n    pass\r\n";
+        let mut results = results_for(content, &["first", "second"]);
+
+        assign_code_context(content, CodeContextOptions::default(), &mut results);
+
+        assert_eq!(
+            results.symbols[0].code_context.as_deref(),
+            Some("import os\n\ndef first():\n    pass\n")
+        );
+        assert_eq!(
+            results.symbols[1].code_context.as_deref(),
+            Some("    pass\n\ndef second():\n    pass\n")
+        );
+    }
+
+    #[test]
+    fn test_minified_lines_are_cut_on_character_boundaries() {
+        let content = format!("var a=function(){{}};{}", "é".repeat(MAX_LINE_BYTES));
+        let mut results = results_for(&content, &["a"]);
+
+        assign_code_context(&content, CodeContextOptions::default(), &mut results);
+
+        let snippet = results.symbols[0].code_context.as_deref().unwrap();
+        assert!(snippet.ends_with('…'));
+        assert!(snippet.len() <= MAX_LINE_BYTES + '…'.len_utf8());
+    }
+}
@@ -0,0 +1,260 @@
+// Symbol confidence - how much to trust each symbol's name and span
+//
+// Julie leaves Symbol::confidence unset. Every symbol leaves enrichment with a
+// score from this policy:
+//
+//   PARSED           1.0  from a well-formed named AST node (Julie's own symbols)
+//   ENRICHED         0.9  created by a Miller pass matching patterns over the
+//                         source (EnrichmentContext::create_symbol's default)
+//   RECOVERED        0.7  recovered from text the parser could not handle
+//                         (metadata.extractedFromError, base::error_recovery)
+//   MACRO_GENERATED  0.6  synthesized from a declaration macro's invocation
+//                         (metadata.generatedByMacro, C declaration macros)
+//   TEXT_FALLBACK    0.5  scraped line by line with text patterns
+//                         (metadata.textFallback, the regex extractor fallback)
+//
+// multiplied by HEURISTIC_NAME_FACTOR when the name itself was guessed rather
+// than written in the source (metadata.heuristicName, e.g. GDScript's implicit
+// class named after the file). Symbols Miller's passes create are scored when
+// they are created: ENRICHED, or a lower score a pass on a recovery path gives
+// through SymbolOptions::confidence. This pass fills in the rest, Julie's
+// symbols, from the markers above and keeps any score already set.
+//
+// Julie's HTML fallback extraction does not mark its symbols, so they score as
+// parsed.
+//
+// Relationships carry a confidence too, and metadata.provenance says how the
+// link was made:
+//
+//   ast             1.0  written in the source and linked through the AST
+//                        (the default: Julie's relationships and
+//                        EnrichmentContext::create_relationship record none)
+//   name-match      0.9  a name looked up among symbols it may not mean
+//   heuristic       0.8  inferred from structure nothing in the source states,
+//                        e.g. Go interface satisfaction
+//   error-recovery  0.7  from a symbol recovered on an error path; this pass
+//                        marks every relationship from a recovered or
+//                        text-fallback symbol that has no provenance yet
+//
+// Passes making the last three kinds call set_provenance with their own score.
+// The cross-language correlation links report the same two fields.
+
+use julie_extractors::{ExtractionResults, Relationship, Symbol};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+pub const PARSED: f32 = 1.0;
+pub const ENRICHED: f32 = 0.9;
+pub const RECOVERED: f32 = 0.7;
+pub const TEXT_FALLBACK: f32 = 0.5;
+pub const MACRO_GENERATED: f32 = 0.6;
+pub const HEURISTIC_NAME_FACTOR: f32 = 0.8;
+pub const NAME_MATCH: f32 = 0.9;
+pub const HEURISTIC: f32 = 0.8;
+
+/// Relationship metadata key holding its Provenance
+pub const PROVENANCE: &str = "provenance";
+
+/// How a relationship (or correlation link) was established
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Provenance {
+    #[default]
+    Ast,
+    NameMatch,
+    Heuristic,
+    ErrorRecovery,
+}
+
+impl Provenance {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Provenance::Ast => "ast",
+            Provenance::NameMatch => "name-match",
+            Provenance::Heuristic => "heuristic",
+            Provenance::ErrorRecovery => "error-recovery",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            Provenance::Ast,
+            Provenance::NameMatch,
+            Provenance::Heuristic,
+            Provenance::ErrorRecovery,
+        ]
+        .into_iter()
+        .find(|p| p.as_str() == value)
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A relationship's provenance, Ast when it records none
+pub fn relationship_provenance(relationship: &Relationship) -> Provenance {
+    relationship
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get(PROVENANCE))
+        .and_then(Value::as_str)
+        .and_then(Provenance::parse)
+        .unwrap_or_default()
+}
+
+/// Record how a relationship was made and how far to trust it
+pub fn set_provenance(relationship: &mut Relationship, provenance: Provenance, confidence: f32) {
+    relationship
+        .metadata
+        .get_or_insert_with(HashMap::new)
+        .insert(PROVENANCE.to_string(), Value::from(provenance.as_str()));
+    relationship.confidence = confidence;
+}
+
+/// Set confidence on every symbol that does not have one yet
+pub fn assign_confidence(results: &mut ExtractionResults) {
+    for symbol in results
+        .symbols
+        .iter_mut()
+        .filter(|s| s.confidence.is_none())
+    {
+        let mut confidence = if flagged(symbol, "extractedFromError") {
+            RECOVERED
+        } else if flagged(symbol, "textFallback") {
+            TEXT_FALLBACK
+        } else {
+            PARSED
+        };
+        if flagged(symbol, "heuristicName") {
+            confidence *= HEURISTIC_NAME_FACTOR;
+        }
+        symbol.confidence = Some(confidence);
+    }
+
+    let recovered: HashMap<&str, f32> = results
+        .symbols
+        .iter()
+        .filter(|s| flagged(s, "extractedFromError") || flagged(s, "textFallback"))
+        .map(|s| (s.id.as_str(), s.confidence.unwrap_or(RECOVERED)))
+        .collect();
+    for relationship in &mut results.relationships {
+        let marked = relationship
+            .metadata
+            .as_ref()
+            .is_some_and(|m| m.contains_key(PROVENANCE));
+        if marked {
+            continue;
+        }
+        if let Some(&score) = recovered.get(relationship.from_symbol_id.as_str()) {
+            let confidence = relationship.confidence.min(score);
+            set_provenance(relationship, Provenance::ErrorRecovery, confidence);
+        }
+    }
+}
+
+pub(crate) fn flagged(symbol: &Symbol, key: &str) -> bool {
+    symbol
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get(key))
+        .is_some_and(|v| v == &Value::Bool(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{set_metadata, test_results, EnrichmentContext, SymbolOptions};
+    use julie_extractors::{RelationshipKind, SymbolKind};
+    use std::path::Path;
+
+    #[test]
+    fn test_scores_follow_recovery_markers() {
+        let ctx = EnrichmentContext::new("player.gd", "gdscript", "", Path::new("."));
+        // Julie's symbols arrive without a score
+        let symbol = |name: &str, confidence: Option<f32>| {
+            let mut symbol =
+                ctx.create_symbol(name, SymbolKind::Class, 0, 0, SymbolOptions::default());
+            symbol.confidence = confidence;
+            symbol
+        };
+        let parsed = symbol("Parsed", None);
+        let mut recovered = symbol("Recovered", None);
+        set_metadata(&mut recovered, "extractedFromError", true);
+        let mut scraped = symbol("Scraped", None);
+        set_metadata(&mut scraped, "textFallback", true);
+        let mut implicit = symbol("Player", None);
+        set_metadata(&mut implicit, "heuristicName", true);
+        let preset = symbol("Preset", Some(0.6));
+        let enriched = ctx.create_symbol(
+            "Enriched",
+            SymbolKind::Class,
+            0,
+            0,
+            SymbolOptions::default(),
+        );
+
+        let mut results =
+            test_results(vec![parsed, recovered, scraped, implicit, preset, enriched]);
+        assign_confidence(&mut results);
+
+        let scores: Vec<Option<f32>> = results.symbols.iter().map(|s| s.confidence).collect();
+        assert_eq!(
+            scores,
+            vec![
+                Some(PARSED),
+                Some(RECOVERED),
+                Some(TEXT_FALLBACK),
+                Some(PARSED * HEURISTIC_NAME_FACTOR),
+                Some(0.6),
+                Some(ENRICHED),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relationships_from_recovered_symbols_are_marked() {
+        let ctx = EnrichmentContext::new("broken.py", "python", "", Path::new("."));
+        let symbol = |name: &str| {
+            let mut symbol =
+                ctx.create_symbol(name, SymbolKind::Function, 0, 0, SymbolOptions::default());
+            symbol.confidence = None;
+            symbol
+        };
+        let parsed = symbol("parsed");
+        let mut recovered = symbol("recovered");
+        set_metadata(&mut recovered, "extractedFromError", true);
+        let call = |from: &Symbol| {
+            ctx.create_relationship(&from.id, "target", RelationshipKind::Calls, 0, None)
+        };
+        let mut guessed = call(&recovered);
+        set_provenance(&mut guessed, Provenance::NameMatch, NAME_MATCH);
+        let relationships = vec![call(&parsed), call(&recovered), guessed];
+
+        let mut results = ExtractionResults {
+            relationships,
+            ..test_results(vec![parsed, recovered])
+        };
+        assign_confidence(&mut results);
+
+        let marks: Vec<(Provenance, f32)> = results
+            .relationships
+            .iter()
+            .map(|r| (relationship_provenance(r), r.confidence))
+            .collect();
+        assert_eq!(
+            marks,
+            vec![
+                (Provenance::Ast, PARSED),
+                (Provenance::ErrorRecovery, RECOVERED),
+                (Provenance::NameMatch, NAME_MATCH),
+            ]
+        );
+        assert_eq!(results.relationships[0].metadata, None);
+        assert_eq!(Provenance::parse("name-match"), Some(Provenance::NameMatch));
+    }
+}
@@ -0,0 +1,207 @@
+// C++ out-of-class member definitions
+//
+// `void Widget::render() { ... }` is extracted as a free function named "render"
+// (or "Widget::render") with no parent. This pass runs after all symbols of the
+// file are collected, reads the qualified declarator of every parentless
+// function, and when a class/struct with the qualifying name exists in the same
+// file, reparents the definition under it as a Method (or Constructor /
+// Destructor). The qualifier is always kept in metadata (className, and
+// qualifiedName including the member) so definitions whose class lives in a
+// header can be linked across files later.
+
+use crate::enrichment::base::{code_mask, set_metadata, EnrichmentContext, Lexicon};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Leading `A::B<T>::` scope of a declarator
+static QUALIFIER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"((?:[A-Za-z_]\w*\s*(?:<[^<>;{}()]*>)?\s*::\s*)+)(~?\s*[A-Za-z_]\w*|operator\s*[^\s(]+)\s*\(")
+        .unwrap()
+});
+
+/// A function definition qualified with its class scope
+#[derive(Debug, PartialEq)]
+struct QualifiedDeclarator {
+    /// Scope components without template arguments, e.g. ["ui", "Widget"]
+    scope: Vec<String>,
+    /// Unqualified member name, e.g. "render" or "~Widget"
+    member: String,
+}
+
+pub(super) fn link_out_of_class_methods(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let candidates: Vec<(usize, QualifiedDeclarator)> = symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.kind == SymbolKind::Function && s.parent_id.is_none())
+        .filter_map(|(index, s)| {
+            qualified_declarator(ctx.symbol_text(s), &s.name).map(|declarator| (index, declarator))
+        })
+        .collect();
+
+    for (index, declarator) in candidates {
+        let class_name = declarator.scope.last().cloned().unwrap_or_default();
+        let class = symbols.iter().find(|s| {
+            matches!(s.kind, SymbolKind::Class | SymbolKind::Struct) && s.name == class_name
+        });
+        let is_namespace = symbols
+            .iter()
+            .any(|s| s.kind == SymbolKind::Namespace && s.name == class_name);
+        if class.is_none() && is_namespace {
+            // `ns::helper()` is a namespaced free function, not a member
+            continue;
+        }
+        let class_id = class.map(|c| c.id.clone());
+
+        let symbol = &mut symbols[index];
+        set_metadata(symbol, "className", class_name.clone());
+        set_metadata(
+            symbol,
+            "qualifiedName",
+            format!("{}::{}", declarator.scope.join("::"), declarator.member),
+        );
+        symbol.name = declarator.member.clone();
+
+        if let Some(class_id) = class_id {
+            symbol.parent_id = Some(class_id);
+            symbol.kind = if declarator.member.starts_with('~') {
+                SymbolKind::Destructor
+            } else if declarator.member == class_name {
+                SymbolKind::Constructor
+            } else {
+                SymbolKind::Method
+            };
+        }
+    }
+}
+
+/// Find the qualified declarator naming `name` in a function definition's text
+fn qualified_declarator(text: &str, name: &str) -> Option<QualifiedDeclarator> {
+    let member_name = name.rsplit("::").next().unwrap_or(name);
+    let mask = code_mask(text, Lexicon::C_LIKE);
+
+    QUALIFIER_RE
+        .captures_iter(text)
+        .filter(|c| {
+            mask.get(c.get(0).unwrap().start())
+                .copied()
+                .unwrap_or(false)
+        })
+        .find_map(|captures| {
+            let member: String = captures[2].split_whitespace().collect();
+            if member != member_name.split_whitespace().collect::<String>() {
+                return None;
+            }
+            let scope = captures[1]
+                .split("::")
+                .map(|part| {
+                    let part = part.trim();
+                    part.split('<').next().unwrap_or(part).trim().to_string()
+                })
+                .filter(|part| !part.is_empty())
+                .collect();
+            Some(QualifiedDeclarator { scope, member })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use serde_json::Value;
+    use std::path::Path;
+
+    fn symbol(ctx: &EnrichmentContext, name: &str, kind: SymbolKind, snippet: &str) -> Symbol {
+        let start = ctx.content.find(snippet).unwrap();
+        ctx.create_symbol(
+            name,
+            kind,
+            start,
+            start + snippet.len(),
+            SymbolOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_definition_is_linked_to_class_in_same_file() {
+        let content = "class Widget {\npublic:\n    Widget();\n    void render();\n};\n\nWidget::Widget() {}\n\nvoid Widget::render() {\n    draw();\n}\n";
+        let ctx = EnrichmentContext::new("widget.cpp", "cpp", content, Path::new("."));
+        let mut symbols = vec![
+            symbol(
+                &ctx,
+                "Widget",
+                SymbolKind::Class,
+                "class Widget {\npublic:\n    Widget();\n    void render();\n};",
+            ),
+            symbol(
+                &ctx,
+                "Widget::Widget",
+                SymbolKind::Function,
+                "Widget::Widget() {}",
+            ),
+            symbol(
+                &ctx,
+                "render",
+                SymbolKind::Function,
+                "void Widget::render() {\n    draw();\n}",
+            ),
+        ];
+        link_out_of_class_methods(&ctx, &mut symbols);
+
+        let class_id = symbols[0].id.clone();
+        assert_eq!(symbols[2].kind, SymbolKind::Method);
+        assert_eq!(symbols[2].parent_id.as_deref(), Some(class_id.as_str()));
+        assert_eq!(symbols[2].name, "render");
+        assert_eq!(symbols[1].kind, SymbolKind::Constructor);
+        assert_eq!(symbols[1].name, "Widget");
+    }
+
+    #[test]
+    fn test_class_in_other_file_keeps_qualifier_in_metadata() {
+        let content = "template <typename T>\nvoid ui::Stack<T>::push(const T& value) { items.push_back(value); }\n";
+        let ctx = EnrichmentContext::new("stack.cpp", "cpp", content, Path::new("."));
+        let mut symbols = vec![symbol(
+            &ctx,
+            "push",
+            SymbolKind::Function,
+            content.trim_end(),
+        )];
+        link_out_of_class_methods(&ctx, &mut symbols);
+
+        assert_eq!(symbols[0].kind, SymbolKind::Function);
+        assert!(symbols[0].parent_id.is_none());
+        let metadata = symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(metadata["className"], Value::from("Stack"));
+        assert_eq!(metadata["qualifiedName"], Value::from("ui::Stack::push"));
+    }
+
+    #[test]
+    fn test_namespaced_free_functions_are_left_alone() {
+        let content = "namespace util { int clamp(int v); }\nint util::clamp(int v) { return v; }\nint plain() { return 0; }\n";
+        let ctx = EnrichmentContext::new("util.cpp", "cpp", content, Path::new("."));
+        let mut symbols = vec![
+            symbol(
+                &ctx,
+                "util",
+                SymbolKind::Namespace,
+                "namespace util { int clamp(int v); }",
+            ),
+            symbol(
+                &ctx,
+                "clamp",
+                SymbolKind::Function,
+                "int util::clamp(int v) { return v; }",
+            ),
+            symbol(
+                &ctx,
+                "plain",
+                SymbolKind::Function,
+                "int plain() { return 0; }",
+            ),
+        ];
+        link_out_of_class_methods(&ctx, &mut symbols);
+
+        assert!(symbols[1].metadata.is_none());
+        assert!(symbols[2].metadata.is_none());
+    }
+}
@@ -0,0 +1,23 @@
+// C++ enrichment passes
+//
+// Julie's C++ extractor handles classes, namespaces, and in-class members; these
+// passes restore class definitions lost to parse errors, connect members
+// defined outside their class body back to the class, name operator
+// overloads, conversion operators, and user-defined literals, and record the
+// prototype/definition shape of functions (c::record_prototypes).
+
+mod methods;
+mod operators;
+mod recovery;
+
+use super::base::EnrichmentContext;
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 2;
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    recovery::recover_declarations(ctx, &mut results.symbols);
+    methods::link_out_of_class_methods(ctx, &mut results.symbols);
+    operators::enrich_operators(ctx, results);
+    super::c::record_prototypes(ctx, &mut results.symbols);
+}
@@ -0,0 +1,466 @@
+// C++ operator overloads
+//
+// Julie's function extraction names a declarator after its identifier, which
+// `operator==`, `operator<<`, conversion operators (`explicit operator bool()
+// const`), and user-defined literals (`operator""_kb`) do not have: they come
+// out under a mangled name or not at all. This pass finds every operator
+// declarator in the code, takes over the symbol Julie made for it (or creates
+// one), and gives it:
+//
+// - the canonical name: "operator==", "operator()", "operator new[]",
+//   "operator bool", "operator const char*", "operator\"\"_kb"
+// - a signature with the full parameter list and trailing qualifiers
+//   (`const`, `noexcept`, `override`, `= default`)
+// - metadata.isOperator and operatorKind ("comparison", "arithmetic",
+//   "assignment", "shift", "bitwise", "logical", "subscript", "call",
+//   "member", "allocation", "conversion", "literal", "comma", "coroutine")
+//
+// Members declared in a class body are Methods of the class. A friend defined
+// (or declared) in a class body is a free Function: it is parented to the
+// enclosing namespace, not the class, marked metadata.friend, and linked to the
+// class with a References relationship carrying metadata.friend (Julie has no
+// Friend relationship kind). `Point::operator==` definitions outside the class
+// keep the parent the out-of-class method pass gave them.
+
+use super::super::base::{
+    code_mask, collapse_whitespace, find_matching_delimiter, innermost_symbol_at, set_metadata,
+    EnrichmentContext, Lexicon, SymbolOptions,
+};
+use julie_extractors::{ExtractionResults, RelationshipKind, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `operator` and the operator it names, up to the parameter list
+static OPERATOR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"\boperator\b\s*(?P<op>""\s*[A-Za-z_]\w*|new\s*\[\s*\]|delete\s*\[\s*\]|new\b|delete\b|co_await\b|\(\s*\)|\[\s*\]|<=>|->\*?|<<=?|>>=?|\+\+|--|&&|\|\||[-+*/%^&|~!=<>,]=?|[A-Za-z_][\w:<>,\s*&]*?)\s*\("#,
+    )
+    .unwrap()
+});
+
+/// `Point::` or `ns::Matrix<T>::` right before `operator`
+static QUALIFIER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:[A-Za-z_]\w*\s*(?:<[^<>;{}]*>)?\s*::\s*)+$").unwrap());
+
+static ACCESS_LABEL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:(?:public|protected|private)\s*:\s*)+").unwrap());
+
+static FRIEND_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bfriend\b").unwrap());
+
+/// One operator declaration or definition
+#[derive(Debug, PartialEq)]
+struct OperatorDecl {
+    name: String,
+    kind: &'static str,
+    /// Declaration start (after any access label) and end (body or `;` included)
+    start: usize,
+    end: usize,
+    keyword: usize,
+    signature: String,
+    /// `Point::operator==` scope components, e.g. ["geo", "Point"]
+    scope: Vec<String>,
+    friend: bool,
+}
+
+pub(super) fn enrich_operators(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    for decl in find_operators(ctx.content) {
+        let is_type = |s: &julie_extractors::Symbol| {
+            matches!(
+                s.kind,
+                SymbolKind::Class | SymbolKind::Struct | SymbolKind::Union
+            )
+        };
+        let class_id = innermost_symbol_at(&results.symbols, decl.keyword, |s| {
+            is_type(s) && (s.start_byte as usize) < decl.start
+        })
+        .map(|s| s.id.clone());
+        let namespace_id = innermost_symbol_at(&results.symbols, decl.keyword, |s| {
+            s.kind == SymbolKind::Namespace
+        })
+        .map(|s| s.id.clone());
+
+        // Julie's symbol for this declarator, if it made one
+        let existing = results.symbols.iter().position(|s| {
+            matches!(
+                s.kind,
+                SymbolKind::Function
+                    | SymbolKind::Method
+                    | SymbolKind::Operator
+                    | SymbolKind::Constructor
+            ) && decl.start <= s.start_byte as usize
+                && (s.start_byte as usize) <= decl.keyword
+                && decl.keyword < s.end_byte as usize
+        });
+        let index = match existing {
+            Some(index) => index,
+            None => {
+                let symbol = ctx.create_symbol(
+                    &decl.name,
+                    SymbolKind::Function,
+                    decl.start,
+                    decl.end,
+                    SymbolOptions::default(),
+                );
+                results.symbols.push(symbol);
+                results.symbols.len() - 1
+            }
+        };
+
+        let symbol = &mut results.symbols[index];
+        symbol.name = decl.name.clone();
+        symbol.signature = Some(decl.signature.clone());
+        set_metadata(symbol, "isOperator", true);
+        set_metadata(symbol, "operatorKind", decl.kind);
+        if let Some(scope_name) = decl.scope.last() {
+            // Mirrors the out-of-class method pass, which may have run on a
+            // mangled name or not seen this symbol at all
+            let class_id = results
+                .symbols
+                .iter()
+                .find(|s| is_type(s) && &s.name == scope_name)
+                .map(|s| s.id.clone());
+            let is_namespace = results
+                .symbols
+                .iter()
+                .any(|s| s.kind == SymbolKind::Namespace && &s.name == scope_name);
+            let symbol = &mut results.symbols[index];
+            set_metadata(
+                symbol,
+                "qualifiedName",
+                format!("{}::{}", decl.scope.join("::"), decl.name),
+            );
+            if class_id.is_some() || !is_namespace {
+                set_metadata(symbol, "className", scope_name.clone());
+            }
+            if let Some(class_id) = class_id {
+                symbol.kind = SymbolKind::Method;
+                symbol.parent_id = Some(class_id);
+            }
+        } else if decl.friend {
+            symbol.kind = SymbolKind::Function;
+            symbol.parent_id = namespace_id;
+            set_metadata(symbol, "friend", true);
+            if let Some(class_id) = class_id {
+                let symbol_id = symbol.id.clone();
+                let mut metadata = HashMap::new();
+                metadata.insert("friend".to_string(), Value::Bool(true));
+                results.relationships.push(ctx.create_relationship(
+                    &symbol_id,
+                    &class_id,
+                    RelationshipKind::References,
+                    decl.keyword,
+                    Some(metadata),
+                ));
+            }
+        } else if let Some(class_id) = class_id {
+            symbol.kind = SymbolKind::Method;
+            symbol.parent_id = Some(class_id);
+        } else {
+            symbol.kind = SymbolKind::Function;
+            symbol.parent_id = namespace_id;
+        }
+    }
+}
+
+fn find_operators(content: &str) -> Vec<OperatorDecl> {
+    let code = code_mask(content, Lexicon::C_LIKE);
+    let bytes = content.as_bytes();
+    let mut decls = Vec::new();
+
+    for captures in OPERATOR_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        let keyword = whole.start();
+        let open = whole.end() - 1;
+        if !code[keyword] || !code[open] {
+            continue;
+        }
+        // `a.operator==(b)` and `p->operator()()` are calls
+        let before = content[..keyword].trim_end();
+        if before.ends_with('.') || before.ends_with("->") {
+            continue;
+        }
+        let Some(close) = find_matching_delimiter(content, open, Lexicon::C_LIKE) else {
+            continue;
+        };
+
+        // Trailing qualifiers run to the body, `;`, or `= default`/`= 0`
+        let mut depth = 0usize;
+        let Some(stop) = (close + 1..content.len()).find(|&i| {
+            if !code[i] {
+                return false;
+            }
+            match bytes[i] {
+                b'(' | b'[' => depth += 1,
+                b')' | b']' => depth = depth.saturating_sub(1),
+                b'{' | b';' | b'=' if depth == 0 => return true,
+                _ => {}
+            }
+            false
+        }) else {
+            continue;
+        };
+        let end = match bytes[stop] {
+            b'{' => match find_matching_delimiter(content, stop, Lexicon::C_LIKE) {
+                Some(brace) => brace + 1,
+                None => continue,
+            },
+            b'=' => content[stop..]
+                .find(';')
+                .map_or(content.len(), |i| stop + i + 1),
+            _ => stop + 1,
+        };
+        let qualifiers = if bytes[stop] == b'=' {
+            content[close + 1..end].trim_end_matches(';')
+        } else {
+            &content[close + 1..stop]
+        };
+
+        let start = declaration_start(content, &code, keyword);
+        let prefix = &content[start..keyword];
+        let (name, kind) = canonical(&captures["op"]);
+        decls.push(OperatorDecl {
+            name,
+            kind,
+            start,
+            end,
+            keyword,
+            signature: collapse_whitespace(&format!(
+                "{}{} {}",
+                prefix,
+                &content[keyword..=close],
+                qualifiers
+            )),
+            scope: QUALIFIER_RE
+                .find(prefix.trim_end())
+                .map(|qualifier| {
+                    qualifier
+                        .as_str()
+                        .split("::")
+                        .map(|part| part.split('<').next().unwrap_or(part).trim().to_string())
+                        .filter(|part| !part.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            friend: FRIEND_RE.is_match(prefix),
+        });
+    }
+    decls
+}
+
+/// First code byte of the declaration holding `keyword`: after the previous
+/// `;`, `{`, or `}`, comments, and access labels
+fn declaration_start(content: &str, code: &[bool], keyword: usize) -> usize {
+    let bytes = content.as_bytes();
+    let boundary = (0..keyword)
+        .rev()
+        .find(|&i| code[i] && matches!(bytes[i], b';' | b'{' | b'}'))
+        .map_or(0, |i| i + 1);
+    let mut start = (boundary..keyword)
+        .find(|&i| code[i] && !bytes[i].is_ascii_whitespace())
+        .unwrap_or(keyword);
+    if let Some(label) = ACCESS_LABEL_RE.find(&content[start..keyword]) {
+        start += label.end();
+    }
+    start
+}
+
+/// Canonical operator name and its operatorKind
+fn canonical(op: &str) -> (String, &'static str) {
+    let compact: String = op.split_whitespace().collect();
+    let kind = match compact.as_str() {
+        "==" | "!=" | "<" | ">" | "<=" | ">=" | "<=>" => "comparison",
+        "+" | "-" | "*" | "/" | "%" | "++" | "--" => "arithmetic",
+        "=" | "+=" | "-=" | "*=" | "/=" | "%=" | "&=" | "|=" | "^=" | "<<=" | ">>=" => "assignment",
+        "<<" | ">>" => "shift",
+        "&" | "|" | "^" | "~" => "bitwise",
+        "&&" | "||" | "!" => "logical",
+        "[]" => "subscript",
+        "()" => "call",
+        "->" | "->*" => "member",
+        "," => "comma",
+        "new" | "delete" | "new[]" | "delete[]" => "allocation",
+        "co_await" => "coroutine",
+        _ if compact.starts_with("\"\"") => "literal",
+        _ => "conversion",
+    };
+    let name = match kind {
+        "allocation" => format!("operator {}", compact),
+        "conversion" | "coroutine" => format!("operator {}", collapse_whitespace(op)),
+        _ => format!("operator{}", compact),
+    };
+    (name, kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"namespace geo {
+class Point {
+public:
+    bool operator==(const Point& other) const noexcept;
+    explicit operator bool() const { return x != 0; }
+    Point& operator+=(const Point&) = default;
+    int operator()(int scale) const;
+    friend std::ostream& operator<<(std::ostream& os, const Point& p) {
+        return os << p.operator bool();
+    }
+private:
+    int x;
+};
+
+bool Point::operator!=(const Point& other) const { return !(*this == other); }
+
+unsigned long long operator"" _kb(unsigned long long v) { return v * 1024; }
+}
+"#;
+
+    fn enriched() -> ExtractionResults {
+        let ctx = EnrichmentContext::new("point.h", "cpp", SOURCE, Path::new("."));
+        let span = |text: &str, end: &str| {
+            let start = SOURCE.find(text).unwrap();
+            (
+                start,
+                SOURCE[start..].find(end).unwrap() + start + end.len(),
+            )
+        };
+        let (ns_start, ns_end) = span("namespace geo", "}\n}");
+        let namespace = ctx.create_symbol(
+            "geo",
+            SymbolKind::Namespace,
+            ns_start,
+            ns_end,
+            SymbolOptions::default(),
+        );
+        let (class_start, class_end) = span("class Point", "};");
+        let class = ctx.create_symbol(
+            "Point",
+            SymbolKind::Class,
+            class_start,
+            class_end,
+            SymbolOptions {
+                parent_id: Some(namespace.id.clone()),
+                ..Default::default()
+            },
+        );
+        // Julie's mangled take on the first declarator
+        let (eq_start, eq_end) = span("bool operator==", ";");
+        let mangled = ctx.create_symbol(
+            "operator",
+            SymbolKind::Function,
+            eq_start,
+            eq_end,
+            SymbolOptions::default(),
+        );
+        let mut results = test_results(vec![namespace, class, mangled]);
+        enrich_operators(&ctx, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_operators_get_canonical_names_and_signatures() {
+        let results = enriched();
+        let summary: Vec<(&str, SymbolKind, &str, &str)> = results.symbols[2..]
+            .iter()
+            .map(|s| {
+                let kind = s.metadata.as_ref().unwrap()["operatorKind"]
+                    .as_str()
+                    .unwrap();
+                (
+                    s.name.as_str(),
+                    s.kind.clone(),
+                    kind,
+                    s.signature.as_deref().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "operator==",
+                    SymbolKind::Method,
+                    "comparison",
+                    "bool operator==(const Point& other) const noexcept"
+                ),
+                (
+                    "operator bool",
+                    SymbolKind::Method,
+                    "conversion",
+                    "explicit operator bool() const"
+                ),
+                (
+                    "operator+=",
+                    SymbolKind::Method,
+                    "assignment",
+                    "Point& operator+=(const Point&) = default"
+                ),
+                (
+                    "operator()",
+                    SymbolKind::Method,
+                    "call",
+                    "int operator()(int scale) const"
+                ),
+                (
+                    "operator<<",
+                    SymbolKind::Function,
+                    "shift",
+                    "friend std::ostream& operator<<(std::ostream& os, const Point& p)"
+                ),
+                (
+                    "operator!=",
+                    SymbolKind::Method,
+                    "comparison",
+                    "bool Point::operator!=(const Point& other) const"
+                ),
+                (
+                    "operator\"\"_kb",
+                    SymbolKind::Function,
+                    "literal",
+                    "unsigned long long operator\"\" _kb(unsigned long long v)"
+                ),
+            ]
+        );
+        // The `p.operator bool()` call is not a declaration
+        assert_eq!(results.symbols.len(), 9);
+    }
+
+    #[test]
+    fn test_friends_belong_to_the_namespace_and_reference_the_class() {
+        let results = enriched();
+        let (namespace, class) = (&results.symbols[0], &results.symbols[1]);
+        let stream = results
+            .symbols
+            .iter()
+            .find(|s| s.name == "operator<<")
+            .unwrap();
+        assert_eq!(stream.parent_id.as_ref(), Some(&namespace.id));
+        assert_eq!(stream.metadata.as_ref().unwrap()["friend"], true);
+        let literal = results.symbols.last().unwrap();
+        assert_eq!(literal.parent_id.as_ref(), Some(&namespace.id));
+        let equals = &results.symbols[2];
+        assert_eq!(equals.parent_id.as_ref(), Some(&class.id));
+        let unequal = results
+            .symbols
+            .iter()
+            .find(|s| s.name == "operator!=")
+            .unwrap();
+        assert_eq!(unequal.parent_id.as_ref(), Some(&class.id));
+        assert_eq!(
+            unequal.metadata.as_ref().unwrap()["qualifiedName"],
+            "Point::operator!="
+        );
+
+        assert_eq!(results.relationships.len(), 1);
+        let friend = &results.relationships[0];
+        assert_eq!(
+            (friend.from_symbol_id.as_str(), friend.to_symbol_id.as_str()),
+            (stream.id.as_str(), class.id.as_str())
+        );
+        assert_eq!(friend.kind, RelationshipKind::References);
+    }
+}
@@ -0,0 +1,139 @@
+// C++ declarations inside parse errors
+//
+// Macro-heavy headers (export macros between `class` and the name, attribute
+// soup, unbalanced preprocessor branches) routinely push tree-sitter-cpp into
+// ERROR nodes, and the class or struct declared there disappears from Julie's
+// output while its methods may still be extracted. This pass runs the shared
+// error-recovery rules over code no symbol covers and restores class, struct,
+// union, and enum definitions that have a body.
+
+use crate::enrichment::base::error_recovery::{recover_symbols, unclaimed_regions, RecoveryRule};
+use crate::enrichment::base::{innermost_symbol_at, EnrichmentContext, Lexicon};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Optional `EXPORT_MACRO` / `[[attr]]` / `alignas(...)` between keyword and name
+const PREFIX: &str = r"(?:\[\[[^\]]*\]\]\s*|alignas\s*\([^)]*\)\s*|[A-Z][A-Z0-9_]*\s+)*";
+
+/// Optional `final` and base clause, then the body
+const SUFFIX: &str = r"\s*(?:final\s*)?(?::[^;{}()]*)?\{";
+
+static ENUM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\benum\s+(?:class\s+|struct\s+)?(?P<name>[A-Za-z_]\w*)\s*(?::\s*[\w:\s]+)?\{")
+        .unwrap()
+});
+static CLASS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"\bclass\s+{PREFIX}(?P<name>[A-Za-z_]\w*){SUFFIX}"
+    ))
+    .unwrap()
+});
+static STRUCT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"\bstruct\s+{PREFIX}(?P<name>[A-Za-z_]\w*){SUFFIX}"
+    ))
+    .unwrap()
+});
+static UNION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"\bunion\s+{PREFIX}(?P<name>[A-Za-z_]\w*){SUFFIX}"
+    ))
+    .unwrap()
+});
+
+/// Enums first, so `enum class Color {` is not also recovered as a class
+static RULES: [RecoveryRule; 4] = [
+    RecoveryRule {
+        pattern: &ENUM_RE,
+        kind: SymbolKind::Enum,
+    },
+    RecoveryRule {
+        pattern: &CLASS_RE,
+        kind: SymbolKind::Class,
+    },
+    RecoveryRule {
+        pattern: &STRUCT_RE,
+        kind: SymbolKind::Struct,
+    },
+    RecoveryRule {
+        pattern: &UNION_RE,
+        kind: SymbolKind::Union,
+    },
+];
+
+pub(super) fn recover_declarations(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let mut recovered = Vec::new();
+    for region in unclaimed_regions(ctx, symbols, Lexicon::C_LIKE) {
+        recovered.extend(recover_symbols(
+            ctx,
+            symbols,
+            region,
+            &RULES,
+            Lexicon::C_LIKE,
+        ));
+    }
+    if recovered.is_empty() {
+        return;
+    }
+
+    // Members Julie did extract were left without a parent; adopt them
+    for symbol in symbols.iter_mut().filter(|s| s.parent_id.is_none()) {
+        let start = symbol.start_byte as usize;
+        if let Some(owner) =
+            innermost_symbol_at(&recovered, start, |s| (s.start_byte as usize) < start)
+        {
+            symbol.parent_id = Some(owner.id.clone());
+        }
+    }
+    symbols.extend(recovered);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    #[test]
+    fn test_recovers_classes_hidden_by_export_macros() {
+        let content = "#include <x>\nclass WIDGET_API Widget : public Base {\n  void render();\n};\nenum class Color { Red };\n";
+        let ctx = EnrichmentContext::new("widget.h", "cpp", content, Path::new("."));
+        let render_at = content.find("void render").unwrap();
+        let mut symbols = vec![ctx.create_symbol(
+            "render",
+            SymbolKind::Method,
+            render_at,
+            render_at + 14,
+            SymbolOptions::default(),
+        )];
+        recover_declarations(&ctx, &mut symbols);
+
+        let widget = symbols.iter().find(|s| s.name == "Widget").unwrap();
+        assert_eq!(widget.kind, SymbolKind::Class);
+        assert_eq!(widget.start_line, 2);
+        assert_eq!(
+            widget.signature.as_deref(),
+            Some("class WIDGET_API Widget : public Base")
+        );
+        assert!(ctx.symbol_text(widget).ends_with("render();\n}"));
+        assert_eq!(symbols[0].parent_id.as_ref(), Some(&widget.id));
+
+        let color = symbols
+            .iter()
+            .filter(|s| s.name == "Color")
+            .collect::<Vec<_>>();
+        assert_eq!(color.len(), 1);
+        assert_eq!(color[0].kind, SymbolKind::Enum);
+    }
+
+    #[test]
+    fn test_parsed_classes_are_not_duplicated() {
+        let content = "class Widget {};\nstruct Fwd;\n";
+        let ctx = EnrichmentContext::new("widget.h", "cpp", content, Path::new("."));
+        let mut symbols =
+            vec![ctx.create_symbol("Widget", SymbolKind::Class, 0, 15, SymbolOptions::default())];
+        recover_declarations(&ctx, &mut symbols);
+        assert_eq!(symbols.len(), 1);
+    }
+}
@@ -0,0 +1,219 @@
+// C# local functions
+//
+// Julie's C# extractor has no arm for local_function_statement, so a helper
+// declared inside a method body (`static int Square(int x) => x * x;`) is
+// invisible and calls inside it are attributed to the outer method. This pass
+// finds declarations at statement position inside method, constructor,
+// accessor, and function bodies (including the synthetic top-level Main and
+// other local functions, so nesting works) and adds a Function symbol parented
+// to the innermost enclosing body. Local functions Julie did extract are
+// reparented instead of duplicated. `static`/`async` modifiers stay in the
+// signature.
+
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, innermost_symbol_at, set_metadata, CodeBytes,
+    EnrichmentContext, Lexicon, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `[static] [async] [unsafe] ReturnType Name<T>(` at the start of a statement
+static LOCAL_FUNCTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:^|[{;}])\s*((?:(?:static|async|unsafe|extern)\s+)*)((?:[\w.]+(?:<[^;{}()=]*>)?|\([^;{}()]*\))(?:\[\])*\??)\s+([A-Za-z_]\w*)\s*(<[^;{}()=]*>)?\s*\(",
+    )
+    .unwrap()
+});
+
+/// Words that can precede `name(` in a statement without declaring a function
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "return", "new", "await", "throw", "else", "yield", "case", "goto", "in", "is", "as", "using",
+    "var", "out", "ref", "when",
+];
+
+/// Symbol kinds whose bodies can declare local functions
+const BODY_KINDS: &[SymbolKind] = &[
+    SymbolKind::Method,
+    SymbolKind::Constructor,
+    SymbolKind::Destructor,
+    SymbolKind::Function,
+    SymbolKind::Property,
+    SymbolKind::Operator,
+];
+
+/// Add local function symbols. Returns the byte range of every symbol added or reparented.
+pub(super) fn extract_local_functions(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+) -> Vec<(usize, usize)> {
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+    let mut ranges = Vec::new();
+
+    for captures in LOCAL_FUNCTION_RE.captures_iter(ctx.content) {
+        let modifiers = captures.get(1).unwrap();
+        let return_type = captures.get(2).unwrap();
+        let name = captures.get(3).unwrap();
+        let start = if modifiers.as_str().is_empty() {
+            return_type.start()
+        } else {
+            modifiers.start()
+        };
+        if !code[name.start()] || STATEMENT_KEYWORDS.contains(&return_type.as_str()) {
+            continue;
+        }
+
+        let open_paren = captures.get(0).unwrap().end() - 1;
+        let Some(close_paren) = find_matching_delimiter(ctx.content, open_paren, Lexicon::C_LIKE)
+        else {
+            continue;
+        };
+        let Some(end) = body_end(ctx.content, close_paren + 1) else {
+            continue;
+        };
+
+        // Must sit inside a body, not be a member declaration Julie already placed
+        let existing = symbols.iter().position(|s| {
+            s.name == name.as_str() && (start..=name.start()).contains(&(s.start_byte as usize))
+        });
+        let existing_id = existing.map(|i| symbols[i].id.clone());
+        let Some(container) = innermost_symbol_at(symbols, start, |s| {
+            (s.start_byte as usize) < start && Some(&s.id) != existing_id.as_ref()
+        }) else {
+            continue;
+        };
+        if !BODY_KINDS.contains(&container.kind) {
+            continue;
+        }
+        let container_id = container.id.clone();
+
+        let header = ctx.text(start, close_paren + 1);
+        let signature = header.split_whitespace().collect::<Vec<_>>().join(" ");
+        let is_static = modifiers.as_str().split_whitespace().any(|m| m == "static");
+
+        let index = match existing {
+            Some(index) => {
+                symbols[index].parent_id = Some(container_id);
+                index
+            }
+            None => {
+                symbols.push(ctx.create_symbol(
+                    name.as_str(),
+                    SymbolKind::Function,
+                    start,
+                    end,
+                    SymbolOptions {
+                        signature: Some(signature),
+                        parent_id: Some(container_id),
+                        ..Default::default()
+                    },
+                ));
+                symbols.len() - 1
+            }
+        };
+        set_metadata(&mut symbols[index], "localFunction", true);
+        if is_static {
+            set_metadata(&mut symbols[index], "static", true);
+        }
+        ranges.push((start, end));
+    }
+    ranges
+}
+
+/// End of a local function body after its parameter list: skips `where`
+/// constraints, then a `{...}` block or an `=> expression;`
+fn body_end(content: &str, from: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut arrow = false;
+    for (index, byte) in CodeBytes::new(content, from, Lexicon::C_LIKE) {
+        match byte {
+            b'{' if !arrow && depth == 0 => {
+                return find_matching_delimiter(content, index, Lexicon::C_LIKE).map(|c| c + 1)
+            }
+            b'=' if !arrow && content[index..].starts_with("=>") => arrow = true,
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => return None,
+            b')' | b']' | b'}' => depth -= 1,
+            b';' if depth == 0 => return arrow.then_some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"class Calc
+{
+    public int Run(int[] values)
+    {
+        var total = Sum(values);
+        return total;
+
+        static int Sum(int[] xs)
+        {
+            int Add(int a, int b) => a + b;
+            return xs.Aggregate(0, Add);
+        }
+    }
+}
+"#;
+
+    fn symbols(ctx: &EnrichmentContext) -> Vec<Symbol> {
+        let class = ctx.create_symbol(
+            "Calc",
+            SymbolKind::Class,
+            0,
+            SOURCE.len() - 1,
+            SymbolOptions::default(),
+        );
+        let start = SOURCE.find("public int Run").unwrap();
+        let method = ctx.create_symbol(
+            "Run",
+            SymbolKind::Method,
+            start,
+            SOURCE.rfind("}\n}").unwrap() + 1,
+            SymbolOptions {
+                parent_id: Some(class.id.clone()),
+                ..Default::default()
+            },
+        );
+        vec![class, method]
+    }
+
+    #[test]
+    fn test_nested_and_static_local_functions() {
+        let ctx = EnrichmentContext::new("Calc.cs", "csharp", SOURCE, Path::new("."));
+        let mut symbols = symbols(&ctx);
+        let ranges = extract_local_functions(&ctx, &mut symbols);
+
+        assert_eq!(ranges.len(), 2);
+        let sum = &symbols[2];
+        assert_eq!(sum.name, "Sum");
+        assert_eq!(sum.parent_id, Some(symbols[1].id.clone()));
+        assert_eq!(sum.signature.as_deref(), Some("static int Sum(int[] xs)"));
+        assert_eq!(
+            sum.metadata.as_ref().unwrap()["static"],
+            serde_json::Value::Bool(true)
+        );
+
+        let add = &symbols[3];
+        assert_eq!(add.name, "Add");
+        assert_eq!(add.parent_id, Some(sum.id.clone()));
+        assert!(ctx.text(ranges[1].0, ranges[1].1).ends_with("a + b;"));
+    }
+
+    #[test]
+    fn test_member_methods_are_not_local_functions() {
+        let ctx = EnrichmentContext::new("Calc.cs", "csharp", SOURCE, Path::new("."));
+        let mut symbols = symbols(&ctx);
+        extract_local_functions(&ctx, &mut symbols);
+        assert!(symbols
+            .iter()
+            .all(|s| s.name != "Run" || s.parent_id.is_some()));
+        assert_eq!(symbols.iter().filter(|s| s.name == "Run").count(), 1);
+    }
+}
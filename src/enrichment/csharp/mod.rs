@@ -0,0 +1,225 @@
+// C# enrichment passes
+//
+// Julie's C# extractor handles namespaces, types, and members; these passes add
+// primary constructor parameters and record positional properties, plus
+// symbols for code Julie leaves unowned (top-level statements and local
+// functions), moving the identifiers and call relationships inside them to
+// their new owners. XML doc comments above attributes are attached to the
+// declaration they document, and partial types are flagged for batch-level
+// grouping.
+
+mod local_functions;
+mod partials;
+mod primary_constructors;
+mod top_level;
+
+use super::base::doc_comments::{fill_doc_comments, DocCommentStyle, DocPlacement};
+use super::base::{reattribute_identifiers, EnrichmentContext, Lexicon};
+use super::confidence::{set_provenance, Provenance, NAME_MATCH};
+use julie_extractors::{ExtractionResults, IdentifierKind, RelationshipKind, Symbol, SymbolKind};
+
+pub const EXTRACTOR_VERSION: u32 = 2;
+
+/// `///` XML docs above the declaration, past any `[Attribute]` sections
+const DOC_COMMENTS: DocCommentStyle = DocCommentStyle {
+    line_prefixes: &["///"],
+    block: Some(("/**", "*/")),
+    placement: DocPlacement::Preceding,
+    attribute_prefixes: &["["],
+    max_blank_lines: 0,
+    lexicon: Lexicon::C_LIKE,
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    fill_doc_comments(ctx, &mut results.symbols, &DOC_COMMENTS, |s| {
+        s.kind != SymbolKind::Import
+    });
+    partials::mark_partial_types(ctx, &mut results.symbols);
+    primary_constructors::extract_primary_constructors(ctx, &mut results.symbols);
+
+    let mut added: Vec<(usize, usize)> = Vec::new();
+    added.extend(top_level::synthesize_main(ctx, &mut results.symbols));
+    added.extend(local_functions::extract_local_functions(
+        ctx,
+        &mut results.symbols,
+    ));
+    if added.is_empty() {
+        return;
+    }
+
+    let previous: Vec<Option<String>> = results
+        .identifiers
+        .iter()
+        .map(|identifier| identifier.containing_symbol_id.clone())
+        .collect();
+    let changed = reattribute_identifiers(
+        &mut results.identifiers,
+        &results.symbols,
+        &added,
+        owns_code,
+    );
+    attribute_calls(ctx, results, &changed, &previous);
+}
+
+fn owns_code(symbol: &Symbol) -> bool {
+    matches!(
+        symbol.kind,
+        SymbolKind::Class
+            | SymbolKind::Struct
+            | SymbolKind::Interface
+            | SymbolKind::Method
+            | SymbolKind::Constructor
+            | SymbolKind::Destructor
+            | SymbolKind::Function
+            | SymbolKind::Property
+            | SymbolKind::Operator
+    )
+}
+
+/// Move Calls relationships of re-owned call sites to the new caller, or add
+/// them when the callee is a function or method declared in this file
+fn attribute_calls(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    changed: &[usize],
+    previous: &[Option<String>],
+) {
+    for &index in changed {
+        let identifier = &results.identifiers[index];
+        if identifier.kind != IdentifierKind::Call {
+            continue;
+        }
+        let Some(caller) = identifier.containing_symbol_id.clone() else {
+            continue;
+        };
+        let line = identifier.start_line;
+        let mut callees = results.symbols.iter().filter(|s| {
+            s.name == identifier.name && matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+        });
+        // Without a resolved target the callee is the only function of that name
+        let (callee, provenance) =
+            match (&identifier.target_symbol_id, callees.next(), callees.next()) {
+                (Some(target), _, _) => (target.clone(), Provenance::Ast),
+                (None, Some(only), None) => (only.id.clone(), Provenance::NameMatch),
+                _ => continue,
+            };
+
+        let existing = results.relationships.iter_mut().find(|r| {
+            r.kind == RelationshipKind::Calls
+                && r.to_symbol_id == callee
+                && r.line_number == line
+                && previous[index].as_deref() == Some(r.from_symbol_id.as_str())
+        });
+        match existing {
+            Some(relationship) => {
+                relationship.from_symbol_id = caller.clone();
+                relationship.id = format!(
+                    "{}_{}_{:?}_{}",
+                    caller, callee, relationship.kind, relationship.line_number
+                );
+            }
+            None => {
+                let byte = identifier.start_byte as usize;
+                let mut relationship =
+                    ctx.create_relationship(&caller, &callee, RelationshipKind::Calls, byte, None);
+                if provenance == Provenance::NameMatch {
+                    set_provenance(&mut relationship, provenance, NAME_MATCH);
+                }
+                if !results
+                    .relationships
+                    .iter()
+                    .any(|r| r.id == relationship.id)
+                {
+                    results.relationships.push(relationship);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    #[test]
+    fn test_top_level_calls_get_an_owner() {
+        let content = "using System;\n\nGreet(\"world\");\n\nvoid Greet(string name) => Console.WriteLine(name);\n";
+        let ctx = EnrichmentContext::new("Program.cs", "csharp", content, Path::new("."));
+        let call = |name: &str| {
+            let at = content.find(&format!("{}(", name)).unwrap();
+            ctx.create_identifier(name, IdentifierKind::Call, at, at + name.len(), None)
+        };
+        let mut results = ExtractionResults {
+            identifiers: vec![call("Greet"), call("WriteLine")],
+            ..test_results(Vec::new())
+        };
+        enrich(&ctx, &mut results);
+
+        let main = results.symbols.iter().find(|s| s.name == "Main").unwrap();
+        let greet = results.symbols.iter().find(|s| s.name == "Greet").unwrap();
+        assert_eq!(greet.parent_id.as_deref(), Some(main.id.as_str()));
+        assert_eq!(
+            results.identifiers[0].containing_symbol_id.as_deref(),
+            Some(main.id.as_str())
+        );
+        assert_eq!(
+            results.identifiers[1].containing_symbol_id.as_deref(),
+            Some(greet.id.as_str())
+        );
+
+        assert_eq!(results.relationships.len(), 1);
+        assert_eq!(results.relationships[0].from_symbol_id, main.id);
+        assert_eq!(results.relationships[0].to_symbol_id, greet.id);
+    }
+
+    #[test]
+    fn test_existing_call_relationship_moves_to_local_function() {
+        let content = "class A {\n  void Run() {\n    int Twice(int x) { return Helper(x) * 2; }\n  }\n  int Helper(int x) => x;\n}\n";
+        let ctx = EnrichmentContext::new("A.cs", "csharp", content, Path::new("."));
+        let class = ctx.create_symbol(
+            "A",
+            SymbolKind::Class,
+            0,
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let run_start = content.find("void Run").unwrap();
+        let run = ctx.create_symbol(
+            "Run",
+            SymbolKind::Method,
+            run_start,
+            content.find("\n  }").unwrap() + 4,
+            SymbolOptions::default(),
+        );
+        let helper_start = content.find("int Helper").unwrap();
+        let helper = ctx.create_symbol(
+            "Helper",
+            SymbolKind::Method,
+            helper_start,
+            content.rfind(';').unwrap() + 1,
+            SymbolOptions::default(),
+        );
+        let at = content.find("Helper(x)").unwrap();
+        let call = ctx.create_identifier(
+            "Helper",
+            IdentifierKind::Call,
+            at,
+            at + 6,
+            Some(run.id.clone()),
+        );
+        let relationship =
+            ctx.create_relationship(&run.id, &helper.id, RelationshipKind::Calls, at, None);
+        let mut results = ExtractionResults {
+            identifiers: vec![call],
+            relationships: vec![relationship],
+            ..test_results(vec![class, run, helper])
+        };
+        enrich(&ctx, &mut results);
+
+        let twice = results.symbols.iter().find(|s| s.name == "Twice").unwrap();
+        assert_eq!(results.relationships.len(), 1);
+        assert_eq!(results.relationships[0].from_symbol_id, twice.id);
+    }
+}
@@ -0,0 +1,122 @@
+// C# partial types
+//
+// `partial class Form1` splits one type across files (designer files, source
+// generators, Razor code-behind). Julie's types::extract_class drops the
+// modifier, so nothing tells a part from a whole class. This pass marks every
+// class, struct, record, and interface declared `partial` with
+// metadata.isPartial; engine::link_partial_types uses the flag to group the
+// parts of a batch.
+
+use crate::enrichment::base::{code_mask, set_metadata, EnrichmentContext, Lexicon};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `partial` among a declaration's modifiers
+static PARTIAL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bpartial\b").unwrap());
+
+pub(super) fn mark_partial_types(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+    for symbol in symbols.iter_mut().filter(|s| {
+        matches!(
+            s.kind,
+            SymbolKind::Class | SymbolKind::Struct | SymbolKind::Interface
+        )
+    }) {
+        if declared_partial(ctx, &code, symbol) {
+            set_metadata(symbol, "isPartial", true);
+        }
+    }
+}
+
+/// Whether `partial` appears before the type keyword and name
+fn declared_partial(ctx: &EnrichmentContext, code: &[bool], symbol: &Symbol) -> bool {
+    let start = symbol.start_byte as usize;
+    let end = (symbol.end_byte as usize).min(ctx.content.len());
+    let Ok(keyword_re) = Regex::new(&format!(
+        r"\b(?:class|struct|interface|record(?:\s+(?:class|struct))?)\s+@?{}\b",
+        regex::escape(&symbol.name)
+    )) else {
+        return false;
+    };
+    // Julie's span may start at the modifiers or at the keyword itself
+    let line_start = ctx.content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let Some(keyword) = keyword_re
+        .find_iter(&ctx.content[line_start..end])
+        .map(|m| line_start + m.start())
+        .find(|&at| code[at])
+    else {
+        return false;
+    };
+    PARTIAL_RE
+        .find_iter(&ctx.content[line_start..keyword])
+        .any(|m| code[line_start + m.start()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    fn marked(content: &str, types: &[(&str, &str, SymbolKind)]) -> Vec<bool> {
+        let ctx = EnrichmentContext::new("Form1.cs", "csharp", content, Path::new("."));
+        let mut symbols: Vec<Symbol> = types
+            .iter()
+            .map(|(text, name, kind)| {
+                let start = content.find(text).unwrap();
+                ctx.create_symbol(
+                    name,
+                    kind.clone(),
+                    start,
+                    start + text.len(),
+                    SymbolOptions::default(),
+                )
+            })
+            .collect();
+        mark_partial_types(&ctx, &mut symbols);
+        symbols
+            .iter()
+            .map(|s| s.metadata.as_ref().is_some_and(|m| m["isPartial"] == true))
+            .collect()
+    }
+
+    #[test]
+    fn test_partial_modifier_marks_classes_structs_and_records() {
+        let content = "public sealed partial class Form1 : Form { }\ninternal partial struct Point { }\npublic partial record Order(int Id);\n";
+        assert_eq!(
+            marked(
+                content,
+                &[
+                    ("class Form1 : Form { }", "Form1", SymbolKind::Class),
+                    (
+                        "internal partial struct Point { }",
+                        "Point",
+                        SymbolKind::Struct
+                    ),
+                    ("record Order(int Id);", "Order", SymbolKind::Class),
+                ],
+            ),
+            [true, true, true]
+        );
+    }
+
+    #[test]
+    fn test_partial_elsewhere_is_ignored() {
+        let content = "// partial class Legacy\npublic class Legacy { void partial() { } }\npublic class Other { }\n";
+        assert_eq!(
+            marked(
+                content,
+                &[
+                    (
+                        "public class Legacy { void partial() { } }",
+                        "Legacy",
+                        SymbolKind::Class
+                    ),
+                    ("public class Other { }", "Other", SymbolKind::Class),
+                ],
+            ),
+            [false, false]
+        );
+    }
+}
@@ -0,0 +1,330 @@
+// C# primary constructors
+//
+// `record Person(string First, int Age);` declares two public properties and
+// `class Widget(string name, int size) { ... }` (C# 12) declares constructor
+// parameters captured for the whole class body. Julie extracts the type but
+// drops the parameter list. This pass parses it and adds:
+//
+// - records: a Property per positional parameter (public, `{ get; init; }`,
+//   or `{ get; set; }` for a mutable record struct), parented to the record
+// - classes/structs: a Variable per parameter (metadata.isParameter), parented
+//   to the type, and the parameter list in the type's signature
+//
+// A member the type declares explicitly with the same name wins, so nothing
+// is reported twice.
+
+use crate::enrichment::base::{
+    code_mask, collapse_whitespace, find_matching_delimiter, split_top_level, trim_with_offset,
+    EnrichmentContext, Lexicon, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind, Visibility};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `record Name<T>(`, `record struct Name(`, `class Name(`, `struct Name(`
+static PRIMARY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:(readonly)\s+)?(record(?:\s+(?:class|struct))?|class|struct)\s+([A-Za-z_]\w*)\s*(?:<[^;{}()]*>)?\s*\(")
+        .unwrap()
+});
+
+/// `[property: Json("x")] in Type name = default`: leading attribute sections
+static ATTRIBUTES_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:\[[^\]]*\]\s*)+").unwrap());
+
+/// One parameter of a primary constructor (offsets absolute in the file)
+#[derive(Debug, PartialEq)]
+struct PrimaryParameter {
+    name: String,
+    type_name: String,
+    default: Option<String>,
+    start: usize,
+    end: usize,
+}
+
+pub(super) fn extract_primary_constructors(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+
+    for captures in PRIMARY_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        if !code[whole.start()] {
+            continue;
+        }
+        let keyword = captures[2].split_whitespace().collect::<Vec<_>>().join(" ");
+        let name = &captures[3];
+        let name_start = captures.get(3).unwrap().start();
+        let open = whole.end() - 1;
+        let Some(close) = find_matching_delimiter(ctx.content, open, Lexicon::C_LIKE) else {
+            continue;
+        };
+        let Some(type_index) = symbols.iter().position(|s| {
+            s.name == name
+                && matches!(s.kind, SymbolKind::Class | SymbolKind::Struct)
+                && (s.start_byte as usize) <= name_start
+                && name_start < s.end_byte as usize
+        }) else {
+            continue;
+        };
+
+        let parameters = parse_parameters(ctx.content, open, close);
+        let type_id = symbols[type_index].id.clone();
+        let is_record = keyword.starts_with("record");
+        let setter = if keyword == "record struct" && captures.get(1).is_none() {
+            "set"
+        } else {
+            "init"
+        };
+
+        let list = collapse_whitespace(ctx.text(open, close + 1));
+        let type_symbol = &mut symbols[type_index];
+        if !type_symbol
+            .signature
+            .as_deref()
+            .is_some_and(|s| s.contains('('))
+        {
+            type_symbol.signature = Some(match type_symbol.signature.take() {
+                Some(signature) => insert_parameter_list(&signature, name, &list),
+                None => format!("{} {}{}", keyword, name, list),
+            });
+        }
+
+        for (position, parameter) in parameters.into_iter().enumerate() {
+            let declared = symbols.iter().any(|s| {
+                s.parent_id.as_deref() == Some(type_id.as_str()) && s.name == parameter.name
+            });
+            if declared {
+                continue;
+            }
+
+            let mut metadata = HashMap::new();
+            metadata.insert("type".to_string(), Value::from(parameter.type_name.clone()));
+            metadata.insert("position".to_string(), Value::from(position));
+            metadata.insert("primaryConstructor".to_string(), Value::Bool(true));
+            if let Some(default) = &parameter.default {
+                metadata.insert("defaultValue".to_string(), Value::from(default.clone()));
+            }
+
+            let (kind, signature, visibility) = if is_record {
+                metadata.insert(
+                    "propertyType".to_string(),
+                    Value::from(parameter.type_name.clone()),
+                );
+                (
+                    SymbolKind::Property,
+                    format!(
+                        "public {} {} {{ get; {}; }}",
+                        parameter.type_name, parameter.name, setter
+                    ),
+                    Some(Visibility::Public),
+                )
+            } else {
+                metadata.insert("isParameter".to_string(), Value::Bool(true));
+                (
+                    SymbolKind::Variable,
+                    collapse_whitespace(ctx.text(parameter.start, parameter.end)),
+                    None,
+                )
+            };
+            symbols.push(ctx.create_symbol(
+                &parameter.name,
+                kind,
+                parameter.start,
+                parameter.end,
+                SymbolOptions {
+                    signature: Some(signature),
+                    visibility,
+                    parent_id: Some(type_id.clone()),
+                    metadata: Some(metadata),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+}
+
+fn parse_parameters(content: &str, open: usize, close: usize) -> Vec<PrimaryParameter> {
+    let list = &content[open + 1..close];
+    let mut parameters = Vec::new();
+
+    for (offset, segment) in split_top_level(list, b',', Lexicon::C_LIKE) {
+        let (start, segment) = trim_with_offset(open + 1 + offset, segment);
+        if segment.is_empty() {
+            continue;
+        }
+        let declaration = ATTRIBUTES_RE.replace(segment, "");
+        let (declaration, default) = match split_top_level(&declaration, b'=', Lexicon::C_LIKE)
+            .as_slice()
+        {
+            [(_, head), (_, tail), ..] => (head.trim().to_string(), Some(tail.trim().to_string())),
+            _ => (declaration.trim().to_string(), None),
+        };
+        let Some((type_name, name)) = declaration.rsplit_once(char::is_whitespace) else {
+            continue;
+        };
+        let type_name = type_name
+            .split_whitespace()
+            .filter(|word| !matches!(*word, "in" | "ref" | "out" | "params" | "scoped"))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        parameters.push(PrimaryParameter {
+            name: name.trim_start_matches('@').to_string(),
+            type_name,
+            default,
+            start,
+            end: start + segment.len(),
+        });
+    }
+    parameters
+}
+
+/// Put the parameter list right after the type name (and its type parameters)
+fn insert_parameter_list(signature: &str, name: &str, list: &str) -> String {
+    let Some(name_start) = signature.match_indices(name).map(|(i, _)| i).find(|&i| {
+        let after = signature[i + name.len()..].chars().next();
+        !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+    }) else {
+        return format!("{}{}", signature, list);
+    };
+    let mut insert_at = name_start + name.len();
+    if signature[insert_at..].starts_with('<') {
+        let mut depth = 0;
+        for (offset, c) in signature[insert_at..].char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        insert_at += offset + 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    format!(
+        "{}{}{}",
+        &signature[..insert_at],
+        list,
+        &signature[insert_at..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn type_symbol(
+        ctx: &EnrichmentContext,
+        content: &str,
+        name: &str,
+        kind: SymbolKind,
+        signature: &str,
+    ) -> Symbol {
+        let start = content.find(name).unwrap();
+        let end = content[start..]
+            .find(['}', ';'])
+            .map_or(content.len(), |i| start + i + 1);
+        ctx.create_symbol(
+            name,
+            kind,
+            start,
+            end,
+            SymbolOptions {
+                signature: Some(signature.to_string()),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_record_positional_parameters_become_properties() {
+        let content = "public record Person([property: JsonPropertyName(\"first\")] string First, int Age = 0);\n";
+        let ctx = EnrichmentContext::new("Person.cs", "csharp", content, Path::new("."));
+        let mut symbols = vec![type_symbol(
+            &ctx,
+            content,
+            "Person",
+            SymbolKind::Class,
+            "public record Person",
+        )];
+        extract_primary_constructors(&ctx, &mut symbols);
+
+        assert_eq!(
+            symbols[0].signature.as_deref(),
+            Some("public record Person([property: JsonPropertyName(\"first\")] string First, int Age = 0)")
+        );
+        let first = &symbols[1];
+        assert_eq!(first.kind, SymbolKind::Property);
+        assert_eq!(
+            first.signature.as_deref(),
+            Some("public string First { get; init; }")
+        );
+        assert_eq!(first.parent_id, Some(symbols[0].id.clone()));
+        let age = symbols[2].metadata.as_ref().unwrap();
+        assert_eq!(age["propertyType"], Value::from("int"));
+        assert_eq!(age["defaultValue"], Value::from("0"));
+    }
+
+    #[test]
+    fn test_class_primary_constructor_parameters() {
+        let content = "public class Widget<T>(string name, in int size) : Base(name)\n{\n    public string Name => name;\n}\n";
+        let ctx = EnrichmentContext::new("Widget.cs", "csharp", content, Path::new("."));
+        let mut symbols = vec![type_symbol(
+            &ctx,
+            content,
+            "Widget",
+            SymbolKind::Class,
+            "public class Widget<T> : Base",
+        )];
+        extract_primary_constructors(&ctx, &mut symbols);
+
+        assert_eq!(
+            symbols[0].signature.as_deref(),
+            Some("public class Widget<T>(string name, in int size) : Base")
+        );
+        assert_eq!(symbols.len(), 3);
+        assert_eq!(symbols[2].kind, SymbolKind::Variable);
+        assert_eq!(symbols[2].signature.as_deref(), Some("in int size"));
+        assert_eq!(
+            symbols[2].metadata.as_ref().unwrap()["type"],
+            Value::from("int")
+        );
+    }
+
+    #[test]
+    fn test_explicit_members_are_not_duplicated() {
+        let content = "record struct Point(int X, int Y)\n{\n    public int X { get; } = X;\n}\n";
+        let ctx = EnrichmentContext::new("Point.cs", "csharp", content, Path::new("."));
+        let point = type_symbol(
+            &ctx,
+            content,
+            "Point",
+            SymbolKind::Struct,
+            "record struct Point",
+        );
+        let x_start = content.find("public int X").unwrap();
+        let explicit = ctx.create_symbol(
+            "X",
+            SymbolKind::Property,
+            x_start,
+            x_start + 24,
+            SymbolOptions {
+                parent_id: Some(point.id.clone()),
+                ..Default::default()
+            },
+        );
+        let mut symbols = vec![point, explicit];
+        extract_primary_constructors(&ctx, &mut symbols);
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Point", "X", "Y"]);
+        assert_eq!(
+            symbols[2].signature.as_deref(),
+            Some("public int Y { get; set; }")
+        );
+    }
+}
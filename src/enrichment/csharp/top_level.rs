@@ -0,0 +1,215 @@
+// C# top-level statements
+//
+// C# 9 programs can drop `static void Main` and write statements directly in
+// the compilation unit. Julie only extracts declarations, so those statements
+// have no containing symbol. This pass classifies the top-level chunks of the
+// file (using directives, namespaces, type declarations, statements) and, when
+// any statements exist, adds a synthetic `Main` Function spanning from the
+// first statement to the last, mirroring the entry point the compiler
+// generates. `await` anywhere in the region makes it `async Task`, as it does
+// for the compiler.
+
+use crate::enrichment::base::{
+    find_matching_delimiter, set_metadata, CodeBytes, EnrichmentContext, Lexicon, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Leading attribute sections and modifiers before a declaration keyword
+static DECLARATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:\[[^\]]*\]\s*)*(?:(?:public|internal|private|protected|static|sealed|abstract|partial|readonly|unsafe|file|ref|new)\s+)*(?:class|struct|interface|enum|record|delegate|namespace)\b",
+    )
+    .unwrap()
+});
+
+/// `using X;`, `using static X;`, `using A = B;`, `global using ...;`, `extern alias X;`
+static DIRECTIVE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:(?:global\s+)?using\s+(?:static\s+)?[\w.]+\s*(?:=\s*[^;]+)?;|extern\s+alias\s+\w+\s*;)")
+        .unwrap()
+});
+
+static AWAIT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bawait\b").unwrap());
+
+/// A top-level chunk of the compilation unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Chunk {
+    Directive,
+    Declaration,
+    Statement,
+}
+
+/// Add a synthetic Main for top-level statements.
+/// Returns the byte range it covers.
+pub(super) fn synthesize_main(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+) -> Option<(usize, usize)> {
+    let statements: Vec<(usize, usize)> = top_level_chunks(ctx.content)
+        .into_iter()
+        .filter(|&(_, _, chunk)| chunk == Chunk::Statement)
+        .map(|(start, end, _)| (start, end))
+        .collect();
+    let start = statements.first()?.0;
+    let end = statements.last()?.1;
+
+    let region = ctx.text(start, end);
+    let returns = if AWAIT_RE.is_match(region) {
+        "async Task"
+    } else {
+        "void"
+    };
+    let mut main = ctx.create_symbol(
+        "Main",
+        SymbolKind::Function,
+        start,
+        end,
+        SymbolOptions {
+            signature: Some(format!("static {} Main(string[] args)", returns)),
+            ..Default::default()
+        },
+    );
+    set_metadata(&mut main, "topLevelStatements", true);
+    set_metadata(&mut main, "synthetic", true);
+    symbols.push(main);
+    Some((start, end))
+}
+
+/// Split the file into (start, end, kind) chunks at nesting depth zero
+fn top_level_chunks(content: &str) -> Vec<(usize, usize, Chunk)> {
+    let bytes = content.as_bytes();
+    let mut chunks = Vec::new();
+    let mut chunk_start: Option<usize> = None;
+    let mut skip_until = 0;
+    let mut depth = 0usize;
+
+    for (index, byte) in CodeBytes::new(content, 0, Lexicon::C_LIKE) {
+        if index < skip_until {
+            continue;
+        }
+        if chunk_start.is_none() {
+            if byte.is_ascii_whitespace() {
+                continue;
+            }
+            // Preprocessor lines (#if, #region, ...) belong to no chunk
+            if byte == b'#' {
+                skip_until = content[index..]
+                    .find('\n')
+                    .map_or(content.len(), |i| index + i);
+                continue;
+            }
+            chunk_start = Some(index);
+        }
+        let start = chunk_start.unwrap();
+
+        match byte {
+            b'(' | b'[' => depth += 1,
+            b')' | b']' => depth = depth.saturating_sub(1),
+            b';' if depth == 0 => {
+                chunks.push((start, index + 1, classify(&content[start..=index])));
+                chunk_start = None;
+            }
+            b'{' if depth == 0 => {
+                let close = find_matching_delimiter(content, index, Lexicon::C_LIKE)
+                    .unwrap_or(content.len() - 1);
+                skip_until = close + 1;
+                let chunk = classify(&content[start..index]);
+                // `var x = new Foo { ... };` and lambdas continue to their `;`
+                let next = content[close + 1..].trim_start().bytes().next();
+                let continues = chunk == Chunk::Statement
+                    && matches!(next, Some(b';' | b'.' | b',' | b')' | b'?' | b':' | b'='));
+                if !continues {
+                    let mut end = close + 1;
+                    if chunk == Chunk::Declaration && bytes.get(end) == Some(&b';') {
+                        end += 1;
+                        skip_until = end;
+                    }
+                    chunks.push((start, end, chunk));
+                    chunk_start = None;
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = chunk_start {
+        let end = content.trim_end().len().max(start);
+        chunks.push((start, end, classify(&content[start..end])));
+    }
+    chunks
+}
+
+fn classify(text: &str) -> Chunk {
+    let text = text.trim_start();
+    if DIRECTIVE_RE.is_match(text) {
+        Chunk::Directive
+    } else if DECLARATION_RE.is_match(text) {
+        Chunk::Declaration
+    } else {
+        Chunk::Statement
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const PROGRAM: &str = r#"using System;
+using static System.Math;
+
+var app = new App { Name = "demo" };
+if (args.Length > 0)
+{
+    await app.RunAsync(args);
+}
+Console.WriteLine(Max(1, 2));
+
+record App
+{
+    public string Name { get; init; }
+}
+"#;
+
+    #[test]
+    fn test_chunk_classification() {
+        let kinds: Vec<Chunk> = top_level_chunks(PROGRAM)
+            .into_iter()
+            .map(|(_, _, chunk)| chunk)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Chunk::Directive,
+                Chunk::Directive,
+                Chunk::Statement,
+                Chunk::Statement,
+                Chunk::Statement,
+                Chunk::Declaration
+            ]
+        );
+    }
+
+    #[test]
+    fn test_main_spans_statement_region() {
+        let ctx = EnrichmentContext::new("Program.cs", "csharp", PROGRAM, Path::new("."));
+        let mut symbols = Vec::new();
+        let (start, end) = synthesize_main(&ctx, &mut symbols).unwrap();
+
+        assert!(ctx.text(start, end).starts_with("var app"));
+        assert!(ctx.text(start, end).ends_with("Max(1, 2));"));
+        assert_eq!(
+            symbols[0].signature.as_deref(),
+            Some("static async Task Main(string[] args)")
+        );
+    }
+
+    #[test]
+    fn test_no_main_without_statements() {
+        let content = "namespace Demo;\n\n[Serializable]\npublic sealed class Widget { }\n";
+        let ctx = EnrichmentContext::new("Widget.cs", "csharp", content, Path::new("."));
+        let mut symbols = Vec::new();
+        assert!(synthesize_main(&ctx, &mut symbols).is_none());
+        assert!(symbols.is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+// CSS enrichment passes
+//
+// Julie's CSS extractor produces a symbol per rule set, named by its selector
+// as written; these passes resolve the selectors of nested rules (CSS Nesting
+// and SCSS-style `&`) against their parent rules, and parse @media and
+// @container conditions into features the rules inside them point back to.
+
+mod nesting;
+mod queries;
+mod query_conditions;
+
+use super::base::EnrichmentContext;
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 2;
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    nesting::resolve_nested_selectors(ctx, &mut results.symbols);
+    queries::enrich_queries(ctx, &mut results.symbols);
+}
@@ -0,0 +1,217 @@
+// Nested CSS selectors
+//
+// With CSS Nesting a rule inside another rule is relative to it:
+//
+//     .card, .panel {
+//       &:hover { }        → .card:hover, .panel:hover
+//       .title { }         → .card .title, .panel .title
+//       @media (x) {
+//         > a { }          → .card > a, .panel > a
+//       }
+//     }
+//
+// Julie names nested rule sets by their literal text ("&:hover"), which is
+// kept as the name. This pass records the flattened selector in
+// metadata.resolvedSelector (every combination, comma-separated, for
+// selector lists) and metadata.resolvedSelectors, and uses it as the
+// signature. `&` is replaced by each parent selector; a selector without `&`
+// is a descendant of its parent. Conditional group rules (@media, @supports,
+// @container, @layer, @scope) are transparent; the contents of other at-rules
+// (@keyframes steps, @font-face) are not selectors and are left alone.
+
+use crate::enrichment::base::{
+    code_mask, collapse_whitespace, set_metadata, split_top_level, CodeBytes,
+};
+use crate::enrichment::base::{EnrichmentContext, Lexicon};
+use julie_extractors::Symbol;
+
+/// At-rules whose blocks hold rules relative to the enclosing rule
+const GROUP_AT_RULES: &[&str] = &["@media", "@supports", "@container", "@layer", "@scope"];
+
+/// What a `{` block holds
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    /// A style rule with its resolved selectors
+    Rule(Vec<String>),
+    /// A conditional group rule, transparent to the rules inside it
+    Group,
+    /// Anything else (@keyframes, @font-face): no style rules inside
+    Opaque,
+}
+
+/// A nested rule and its flattened selectors
+#[derive(Debug, PartialEq)]
+struct NestedRule {
+    /// Byte offset of the selector
+    start: usize,
+    selector: String,
+    resolved: Vec<String>,
+}
+
+pub(super) fn resolve_nested_selectors(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    for rule in nested_rules(ctx.content, Lexicon::CSS) {
+        let line = ctx.line_of(rule.start);
+        let Some(symbol) = symbols.iter_mut().find(|s| {
+            s.start_byte as usize == rule.start
+                || (s.start_line == line && collapse_whitespace(&s.name) == rule.selector)
+        }) else {
+            continue;
+        };
+        let resolved = rule.resolved.join(", ");
+        set_metadata(symbol, "resolvedSelector", resolved.clone());
+        set_metadata(symbol, "resolvedSelectors", rule.resolved);
+        symbol.signature = Some(resolved);
+    }
+}
+
+/// Every rule nested in another rule, with its selectors resolved against the parent chain
+fn nested_rules(css: &str, lexicon: Lexicon) -> Vec<NestedRule> {
+    let code = code_mask(css, lexicon);
+    let mut rules = Vec::new();
+    let mut stack: Vec<Block> = Vec::new();
+    let mut boundary = 0;
+
+    for (index, byte) in CodeBytes::new(css, 0, lexicon) {
+        match byte {
+            b'{' => {
+                // Skip comments and whitespace before the selector
+                let Some(start) = (boundary..index)
+                    .find(|&i| code[i] && !css.as_bytes()[i].is_ascii_whitespace())
+                else {
+                    stack.push(Block::Opaque);
+                    boundary = index + 1;
+                    continue;
+                };
+                let prelude = collapse_whitespace(&css[start..index]);
+                let parent = stack
+                    .iter()
+                    .rev()
+                    .find(|block| **block != Block::Group)
+                    .cloned();
+
+                let block = if prelude.starts_with('@') {
+                    let at_rule = prelude.split([' ', '(']).next().unwrap_or("");
+                    if GROUP_AT_RULES.contains(&at_rule.to_ascii_lowercase().as_str()) {
+                        Block::Group
+                    } else {
+                        Block::Opaque
+                    }
+                } else {
+                    match parent {
+                        Some(Block::Rule(parents)) => {
+                            let resolved = resolve(&parents, &prelude, lexicon);
+                            rules.push(NestedRule {
+                                start,
+                                selector: prelude.clone(),
+                                resolved: resolved.clone(),
+                            });
+                            Block::Rule(resolved)
+                        }
+                        Some(_) => Block::Opaque,
+                        None => Block::Rule(selector_list(&prelude, lexicon)),
+                    }
+                };
+                stack.push(block);
+                boundary = index + 1;
+            }
+            b'}' => {
+                stack.pop();
+                boundary = index + 1;
+            }
+            b';' => boundary = index + 1,
+            _ => {}
+        }
+    }
+    rules
+}
+
+/// Combine each parent selector with each selector of a nested rule
+fn resolve(parents: &[String], selector: &str, lexicon: Lexicon) -> Vec<String> {
+    let children = selector_list(selector, lexicon);
+    parents
+        .iter()
+        .flat_map(|parent| {
+            children.iter().map(move |child| {
+                if child.contains('&') {
+                    child.replace('&', parent)
+                } else {
+                    format!("{} {}", parent, child)
+                }
+            })
+        })
+        .collect()
+}
+
+/// `.a, .b:is(.c, .d)` → [".a", ".b:is(.c, .d)"]
+fn selector_list(selector: &str, lexicon: Lexicon) -> Vec<String> {
+    split_top_level(selector, b',', lexicon)
+        .into_iter()
+        .map(|(_, part)| collapse_whitespace(part))
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use julie_extractors::SymbolKind;
+    use serde_json::json;
+    use std::path::Path;
+
+    fn resolved(css: &str) -> Vec<(String, Vec<String>)> {
+        nested_rules(css, Lexicon::CSS)
+            .into_iter()
+            .map(|rule| (rule.selector, rule.resolved))
+            .collect()
+    }
+
+    #[test]
+    fn test_nested_selectors_are_flattened() {
+        let css = "/* card */\n.card {\n  color: red;\n  &:hover { color: blue; }\n  .title { font-weight: bold;\n    & + & { margin: 0; }\n  }\n  @media (min-width: 600px) {\n    > a { display: block; }\n  }\n}\n@keyframes spin { from { top: 0; } to { top: 1px; } }\n";
+
+        let expected = [
+            ("&:hover", vec![".card:hover"]),
+            (".title", vec![".card .title"]),
+            ("& + &", vec![".card .title + .card .title"]),
+            ("> a", vec![".card > a"]),
+        ];
+        let expected: Vec<(String, Vec<String>)> = expected
+            .into_iter()
+            .map(|(selector, resolved)| {
+                (
+                    selector.to_string(),
+                    resolved.into_iter().map(String::from).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(resolved(css), expected);
+    }
+
+    #[test]
+    fn test_selector_list_parents_record_every_combination() {
+        let css = ".a, .b {\n  &:hover, .c { color: red; }\n}\n";
+        let ctx = EnrichmentContext::new("style.css", "css", css, Path::new("."));
+        let start = css.find("&:hover").unwrap();
+        let mut symbols = vec![ctx.create_symbol(
+            "&:hover, .c",
+            SymbolKind::Class,
+            start,
+            css.rfind('}').unwrap(),
+            SymbolOptions::default(),
+        )];
+
+        resolve_nested_selectors(&ctx, &mut symbols);
+
+        let symbol = &symbols[0];
+        assert_eq!(symbol.name, "&:hover, .c");
+        assert_eq!(
+            symbol.signature.as_deref(),
+            Some(".a:hover, .a .c, .b:hover, .b .c")
+        );
+        assert_eq!(
+            symbol.metadata.as_ref().unwrap()["resolvedSelectors"],
+            json!([".a:hover", ".a .c", ".b:hover", ".b .c"])
+        );
+    }
+}
@@ -0,0 +1,218 @@
+// Media and container queries
+//
+// An @media or @container rule is only useful to search if its condition is:
+// "what applies under 768px" needs the feature, not the text. Each query's
+// symbol (Julie's, or a Namespace symbol created for it when Julie has none)
+// gets:
+//
+//   atRule           "media" or "container"
+//   query            the condition as written, whitespace collapsed
+//   containerName    the named container of `@container sidebar (...)`
+//   queryCondition   the parsed condition: `{type: "feature", name, feature,
+//                    operator, value}` leaves (`max-width: 768px` → name
+//                    maxWidth, operator ":"; `width >= 600px` → operator
+//                    ">="), `{type: "mediaType", name}`, `{type: "function",
+//                    name, argument}` for style() and friends, combined by
+//                    `{type: "and" | "or", conditions}` and `{type: "not",
+//                    condition}`; a query list is an "or"
+//   queryFeatures    every feature leaf, in order
+//
+// A two-sided range (`400px <= width <= 700px`) becomes an "and" of two
+// features. Conditions that don't parse keep only atRule and query. Every rule
+// set and query nested inside a query gets metadata.mediaContext, the ID of the
+// innermost enclosing query's symbol.
+
+use super::query_conditions::{collect_features, parse_query_list};
+use crate::enrichment::base::{
+    code_mask, collapse_whitespace, find_matching_delimiter, set_metadata, CodeBytes,
+    EnrichmentContext, Lexicon, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+
+/// One `{` block of the stylesheet
+struct Block {
+    /// Byte offset of the prelude (selector or at-rule)
+    start: usize,
+    /// Byte offset just past the closing `}`
+    end: usize,
+    prelude: String,
+    /// "media" or "container" for queries
+    at_rule: Option<&'static str>,
+    /// Index of the innermost enclosing query block
+    context: Option<usize>,
+}
+
+pub(super) fn enrich_queries(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let blocks = blocks(ctx.content);
+    if blocks.iter().all(|block| block.at_rule.is_none()) {
+        return;
+    }
+
+    // Symbol index of each block, when it has one
+    let mut indices: Vec<Option<usize>> = Vec::with_capacity(blocks.len());
+    for block in &blocks {
+        let mut index = find_symbol(ctx, symbols, block);
+        if index.is_none() && block.at_rule.is_some() {
+            symbols.push(ctx.create_symbol(
+                &block.prelude,
+                SymbolKind::Namespace,
+                block.start,
+                block.end,
+                SymbolOptions {
+                    signature: Some(block.prelude.clone()),
+                    ..Default::default()
+                },
+            ));
+            index = Some(symbols.len() - 1);
+        }
+        indices.push(index);
+    }
+
+    for (block, index) in blocks.iter().zip(&indices) {
+        let Some(index) = *index else {
+            continue;
+        };
+        if let Some(context) = block.context.and_then(|context| indices[context]) {
+            let id = symbols[context].id.clone();
+            set_metadata(&mut symbols[index], "mediaContext", id);
+        }
+        let Some(at_rule) = block.at_rule else {
+            continue;
+        };
+        let symbol = &mut symbols[index];
+        let condition = block.prelude[at_rule.len() + 1..].trim();
+        set_metadata(symbol, "atRule", at_rule);
+        set_metadata(symbol, "query", condition);
+        let Some(parsed) = parse_query_list(condition, at_rule == "container") else {
+            continue;
+        };
+        if let Some(name) = parsed.container_name {
+            set_metadata(symbol, "containerName", name);
+        }
+        let mut features = Vec::new();
+        collect_features(&parsed.condition, &mut features);
+        set_metadata(symbol, "queryCondition", parsed.condition);
+        set_metadata(symbol, "queryFeatures", features);
+    }
+}
+
+/// Every `{` block, with the query blocks around it
+fn blocks(css: &str) -> Vec<Block> {
+    let code = code_mask(css, Lexicon::CSS);
+    let mut blocks: Vec<Block> = Vec::new();
+    // Indices into `blocks` of the open blocks
+    let mut stack: Vec<usize> = Vec::new();
+    let mut boundary = 0;
+
+    for (index, byte) in CodeBytes::new(css, 0, Lexicon::CSS) {
+        match byte {
+            b'{' => {
+                let start = (boundary..index)
+                    .find(|&i| code[i] && !css.as_bytes()[i].is_ascii_whitespace())
+                    .unwrap_or(index);
+                let prelude = collapse_whitespace(&css[start..index]);
+                let keyword = prelude
+                    .split([' ', '('])
+                    .next()
+                    .unwrap_or("")
+                    .to_ascii_lowercase();
+                let at_rule = match keyword.as_str() {
+                    "@media" => Some("media"),
+                    "@container" => Some("container"),
+                    _ => None,
+                };
+                let context = stack
+                    .iter()
+                    .rev()
+                    .find(|&&open| blocks[open].at_rule.is_some())
+                    .copied();
+                let end = find_matching_delimiter(css, index, Lexicon::CSS)
+                    .map_or(css.len(), |close| close + 1);
+                stack.push(blocks.len());
+                blocks.push(Block {
+                    start,
+                    end,
+                    prelude,
+                    at_rule,
+                    context,
+                });
+                boundary = index + 1;
+            }
+            b'}' => {
+                stack.pop();
+                boundary = index + 1;
+            }
+            b';' => boundary = index + 1,
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Julie's symbol for a block: same start, or same line and prelude
+fn find_symbol(ctx: &EnrichmentContext, symbols: &[Symbol], block: &Block) -> Option<usize> {
+    if block.prelude.is_empty() {
+        return None;
+    }
+    let line = ctx.line_of(block.start);
+    symbols.iter().position(|s| {
+        s.start_byte as usize == block.start
+            || (s.start_line == line && collapse_whitespace(&s.name) == block.prelude)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::Path;
+
+    #[test]
+    fn test_nested_rules_point_at_their_query() {
+        let css = ".nav { color: red; }\n@media (max-width: 768px) {\n  .nav { display: none; }\n  @container card (width > 20em) {\n    .title { font-size: 1em; }\n  }\n}\n@media screen and {\n  .broken { }\n}\n";
+        let ctx = EnrichmentContext::new("site.css", "css", css, Path::new("."));
+        let rule = |selector: &str, from: usize| {
+            let start = css[from..].find(selector).unwrap() + from;
+            let end = css[start..].find('}').unwrap() + start + 1;
+            ctx.create_symbol(
+                selector,
+                SymbolKind::Class,
+                start,
+                end,
+                SymbolOptions::default(),
+            )
+        };
+        let media = css.find("@media").unwrap();
+        let mut symbols = vec![
+            rule(".nav", 0),
+            rule(".nav", media),
+            rule(".title", 0),
+            rule(".broken", 0),
+        ];
+        enrich_queries(&ctx, &mut symbols);
+
+        let names: Vec<&str> = symbols[4..].iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "@media (max-width: 768px)",
+                "@container card (width > 20em)",
+                "@media screen and",
+            ]
+        );
+        let metadata = |index: usize| symbols[index].metadata.clone().unwrap_or_default();
+        assert!(symbols[0].metadata.is_none());
+        assert_eq!(metadata(1)["mediaContext"], json!(symbols[4].id));
+        assert_eq!(metadata(2)["mediaContext"], json!(symbols[5].id));
+        assert_eq!(metadata(5)["mediaContext"], json!(symbols[4].id));
+        assert_eq!(metadata(5)["containerName"], "card");
+        assert_eq!(
+            metadata(4)["queryFeatures"],
+            json!([{ "name": "maxWidth", "feature": "max-width", "operator": ":", "value": "768px" }])
+        );
+        // Malformed: the raw condition only
+        assert_eq!(metadata(6)["query"], "screen and");
+        assert!(!metadata(6).contains_key("queryCondition"));
+        assert_eq!(metadata(3)["mediaContext"], json!(symbols[6].id));
+    }
+}
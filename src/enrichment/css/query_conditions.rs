@@ -0,0 +1,345 @@
+// Media and container query conditions
+//
+// Parses the condition of an @media or @container rule into the queryCondition
+// tree described in queries.rs. Anything outside the grammar (mixed and/or
+// without parentheses, dangling keywords) fails the whole list.
+
+use crate::enrichment::base::{
+    collapse_whitespace, find_matching_delimiter, split_top_level, Lexicon,
+};
+use serde_json::{json, Value};
+
+/// A query list's container name and parsed condition
+pub(super) struct ParsedQuery {
+    pub(super) container_name: Option<String>,
+    pub(super) condition: Value,
+}
+
+/// Parse a comma-separated query list; None when any part is malformed
+pub(super) fn parse_query_list(text: &str, container: bool) -> Option<ParsedQuery> {
+    let queries: Vec<&str> = split_top_level(text, b',', Lexicon::CSS)
+        .into_iter()
+        .map(|(_, query)| query.trim())
+        .collect();
+    if queries.iter().any(|query| query.is_empty()) {
+        return None;
+    }
+
+    let mut container_name = None;
+    let mut conditions = Vec::new();
+    for query in queries {
+        let mut tokens = tokenize(query)?;
+        if container {
+            if let [Token::Word(name), _, ..] = tokens.as_slice() {
+                if !matches!(name.as_str(), "not" | "and" | "or") {
+                    container_name = Some(name.clone());
+                    tokens.remove(0);
+                }
+            }
+            conditions.push(condition(&tokens)?);
+        } else {
+            conditions.push(media_query(&tokens)?);
+        }
+    }
+    let condition = if conditions.len() == 1 {
+        conditions.remove(0)
+    } else {
+        json!({ "type": "or", "conditions": conditions })
+    };
+    Some(ParsedQuery {
+        container_name,
+        condition,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    /// `( ... )`, holding the text inside
+    Parens(String),
+    /// `style( ... )`
+    Function(String, String),
+}
+
+fn tokenize(text: &str) -> Option<Vec<Token>> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if byte.is_ascii_whitespace() {
+            index += 1;
+        } else if byte == b'(' {
+            let close = find_matching_delimiter(text, index, Lexicon::CSS)?;
+            tokens.push(Token::Parens(text[index + 1..close].trim().to_string()));
+            index = close + 1;
+        } else if byte.is_ascii_alphabetic() || byte == b'-' || byte == b'_' {
+            let end = (index..bytes.len())
+                .find(|&i| !(bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'-' | b'_')))
+                .unwrap_or(bytes.len());
+            let word = text[index..end].to_string();
+            if bytes.get(end) == Some(&b'(') {
+                let close = find_matching_delimiter(text, end, Lexicon::CSS)?;
+                tokens.push(Token::Function(
+                    word,
+                    text[end + 1..close].trim().to_string(),
+                ));
+                index = close + 1;
+            } else {
+                tokens.push(Token::Word(word));
+                index = end;
+            }
+        } else {
+            return None;
+        }
+    }
+    Some(tokens)
+}
+
+/// `[not|only] screen [and <condition>]`, or a bare condition
+fn media_query(tokens: &[Token]) -> Option<Value> {
+    let (modifier, rest) = match tokens {
+        [Token::Word(word), Token::Word(_), ..] if word == "not" || word == "only" => {
+            (Some(word.as_str()), &tokens[1..])
+        }
+        _ => (None, tokens),
+    };
+    let [Token::Word(media_type), rest @ ..] = rest else {
+        return condition(tokens);
+    };
+    if matches!(media_type.as_str(), "not" | "and" | "or" | "only") {
+        // `not (color)` negates a condition, not a media type
+        return modifier.map_or_else(|| condition(tokens), |_| None);
+    }
+
+    let media_type = json!({ "type": "mediaType", "name": media_type.to_ascii_lowercase() });
+    let query = match rest {
+        [] => media_type,
+        [Token::Word(and), condition_tokens @ ..] if and == "and" => {
+            let mut conditions = vec![media_type];
+            match condition(condition_tokens)? {
+                Value::Object(map) if map["type"] == "and" => {
+                    conditions.extend(map["conditions"].as_array().cloned().unwrap_or_default())
+                }
+                other => conditions.push(other),
+            }
+            json!({ "type": "and", "conditions": conditions })
+        }
+        _ => return None,
+    };
+    Some(match modifier {
+        Some("not") => json!({ "type": "not", "condition": query }),
+        _ => query,
+    })
+}
+
+/// `not <term>`, or terms joined by a single kind of `and`/`or`
+fn condition(tokens: &[Token]) -> Option<Value> {
+    match tokens {
+        [] => None,
+        [Token::Word(not), term] if not == "not" => {
+            Some(json!({ "type": "not", "condition": term_condition(term)? }))
+        }
+        [first, rest @ ..] => {
+            let mut conditions = vec![term_condition(first)?];
+            let mut operator: Option<&str> = None;
+            for pair in rest.chunks(2) {
+                let [Token::Word(word), term] = pair else {
+                    return None;
+                };
+                if !matches!(word.as_str(), "and" | "or") || operator.is_some_and(|op| op != word) {
+                    return None;
+                }
+                operator = Some(word);
+                conditions.push(term_condition(term)?);
+            }
+            Some(match operator {
+                None => conditions.remove(0),
+                Some(operator) => json!({ "type": operator, "conditions": conditions }),
+            })
+        }
+    }
+}
+
+/// `(feature)`, `((a) or (b))`, or `style(...)`
+fn term_condition(token: &Token) -> Option<Value> {
+    match token {
+        Token::Parens(inner) => {
+            let nested = inner.starts_with('(')
+                || inner
+                    .strip_prefix("not")
+                    .is_some_and(|rest| rest.starts_with([' ', '(']));
+            if nested {
+                condition(&tokenize(inner)?)
+            } else {
+                feature(inner)
+            }
+        }
+        Token::Function(name, argument) => Some(json!({
+            "type": "function",
+            "name": name.to_ascii_lowercase(),
+            "argument": collapse_whitespace(argument),
+        })),
+        Token::Word(_) => None,
+    }
+}
+
+/// `max-width: 768px`, `width >= 600px`, `400px <= width <= 700px`, `hover`
+fn feature(text: &str) -> Option<Value> {
+    if let Some((name, value)) = text.split_once(':') {
+        return Some(feature_value(name.trim(), ":", value.trim()));
+    }
+
+    let mut parts = Vec::new();
+    let mut rest = text;
+    while let Some(at) = rest.find(['<', '>', '=']) {
+        let len = if rest[at + 1..].starts_with('=') {
+            2
+        } else {
+            1
+        };
+        parts.push(rest[..at].trim());
+        parts.push(&rest[at..at + len]);
+        rest = &rest[at + len..];
+    }
+    parts.push(rest.trim());
+
+    match parts[..] {
+        [name] if is_feature_name(name) => Some(json!({
+            "type": "feature",
+            "name": camel_case(name),
+            "feature": name.to_ascii_lowercase(),
+        })),
+        [name, operator, value] if is_feature_name(name) => {
+            Some(feature_value(name, operator, value))
+        }
+        [value, operator, name] if is_feature_name(name) => {
+            Some(feature_value(name, flip(operator), value))
+        }
+        [low, low_operator, name, high_operator, high] if is_feature_name(name) => Some(json!({
+            "type": "and",
+            "conditions": [
+                feature_value(name, flip(low_operator), low),
+                feature_value(name, high_operator, high),
+            ],
+        })),
+        _ => None,
+    }
+}
+
+fn feature_value(name: &str, operator: &str, value: &str) -> Value {
+    json!({
+        "type": "feature",
+        "name": camel_case(name),
+        "feature": name.to_ascii_lowercase(),
+        "operator": operator,
+        "value": collapse_whitespace(value),
+    })
+}
+
+fn is_feature_name(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '-')
+        && text
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// `600px < width` reads as `width > 600px`
+fn flip(operator: &str) -> &str {
+    match operator {
+        "<" => ">",
+        "<=" => ">=",
+        ">" => "<",
+        ">=" => "<=",
+        other => other,
+    }
+}
+
+/// `prefers-color-scheme` → `prefersColorScheme`
+fn camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.to_ascii_lowercase().trim_start_matches('-').chars() {
+        if c == '-' {
+            upper = true;
+        } else if upper {
+            camel.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+    camel
+}
+
+/// Every feature leaf of a condition, in order, without its type tag
+pub(super) fn collect_features(condition: &Value, features: &mut Vec<Value>) {
+    match condition["type"].as_str() {
+        Some("feature") => {
+            let mut feature = condition.clone();
+            if let Some(map) = feature.as_object_mut() {
+                map.remove("type");
+            }
+            features.push(feature);
+        }
+        Some("not") => collect_features(&condition["condition"], features),
+        Some("and" | "or") => {
+            for nested in condition["conditions"].as_array().into_iter().flatten() {
+                collect_features(nested, features);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_conditions_are_parsed() {
+        let parsed = |text: &str, container: bool| {
+            parse_query_list(text, container)
+                .map(|parsed| (parsed.container_name, parsed.condition))
+        };
+        assert_eq!(
+            parsed(
+                "screen and (max-width: 768px) and (prefers-color-scheme: dark)",
+                false
+            ),
+            Some((
+                None,
+                json!({ "type": "and", "conditions": [
+                    { "type": "mediaType", "name": "screen" },
+                    { "type": "feature", "name": "maxWidth", "feature": "max-width", "operator": ":", "value": "768px" },
+                    { "type": "feature", "name": "prefersColorScheme", "feature": "prefers-color-scheme", "operator": ":", "value": "dark" },
+                ]})
+            ))
+        );
+        assert_eq!(
+            parsed("not (hover), print", false).unwrap().1,
+            json!({ "type": "or", "conditions": [
+                { "type": "not", "condition": { "type": "feature", "name": "hover", "feature": "hover" } },
+                { "type": "mediaType", "name": "print" },
+            ]})
+        );
+        assert_eq!(
+            parsed(
+                "sidebar (400px <= width < 700px) or style(--compact: true)",
+                true
+            ),
+            Some((
+                Some("sidebar".to_string()),
+                json!({ "type": "or", "conditions": [
+                    { "type": "and", "conditions": [
+                        { "type": "feature", "name": "width", "feature": "width", "operator": ">=", "value": "400px" },
+                        { "type": "feature", "name": "width", "feature": "width", "operator": "<", "value": "700px" },
+                    ]},
+                    { "type": "function", "name": "style", "argument": "--compact: true" },
+                ]})
+            ))
+        );
+        assert!(parsed("(min-width: 1px) and (hover) or (color)", false).is_none());
+        assert!(parsed("screen and", false).is_none());
+    }
+}
@@ -0,0 +1,293 @@
+// Dart library directives
+//
+// A Dart library can span files: `part 'user.g.dart';` pulls generated code
+// into the library, and that file answers with `part of 'user.dart';` (or the
+// older `part of my.lib;`). Each library, part, part of, import, and export
+// directive becomes an Import symbol (Julie's symbol for the directive is
+// reused when it has one) whose metadata records the directive, its target
+// string, and, for files in the workspace, the resolved path. Relative URIs
+// resolve against the directive's file; `package:` URIs against the
+// package's lib/ directory when that package is part of the workspace.
+//
+// Conditional imports (`import 'stub.dart' if (dart.library.io) 'io.dart'`)
+// keep every branch in metadata.conditions rather than picking one.
+//
+// Parts are tied to their library by name: a `part` directive gets a Contains
+// relationship from the file's `library` symbol, and `part of` adds a pending
+// Imports relationship to the owning library's name (or, for the URI form,
+// its path) for cross-file resolution.
+
+use super::DART;
+use crate::enrichment::base::{code_mask, EnrichmentContext, SymbolOptions};
+use julie_extractors::{ExtractionResults, PendingRelationship, RelationshipKind, SymbolKind};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// `library ...;`, `part ...;`, `part of ...;`, `import ...;`, `export ...;`
+static DIRECTIVE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*(library|part[ \t]+of|part|import|export)\b([^;]*);").unwrap()
+});
+
+/// A single- or double-quoted URI
+static URI_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"'([^'\n]*)'|"([^"\n]*)""#).unwrap());
+
+/// `if (dart.library.io) 'io.dart'`
+static CONDITION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"\bif\s*\(\s*([^)]*?)\s*\)\s*(?:'([^'\n]*)'|"([^"\n]*)")"#).unwrap()
+});
+
+/// `as prefix`
+static PREFIX_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bas\s+([\w$]+)").unwrap());
+
+/// `show A, B` / `hide C`
+static COMBINATOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(show|hide)\s+([\w$]+(?:\s*,\s*[\w$]+)*)").unwrap());
+
+pub(super) fn extract_directives(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let code = code_mask(ctx.content, DART);
+    let mut library_id = None;
+    let mut parts = Vec::new();
+
+    for captures in DIRECTIVE_RE.captures_iter(ctx.content) {
+        let keyword = captures.get(1).unwrap();
+        if !code[keyword.start()] {
+            continue;
+        }
+        let whole = captures.get(0).unwrap();
+        let start = keyword.start();
+        let body = captures[2].trim();
+        let directive = match keyword.as_str() {
+            "library" => "library",
+            "part" => "part",
+            "import" => "import",
+            "export" => "export",
+            _ => "partOf",
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("directive".to_string(), Value::from(directive));
+        let uri = URI_RE
+            .captures(body)
+            .and_then(|c| c.get(1).or(c.get(2)))
+            .map(|m| m.as_str());
+        let target = match (directive, uri) {
+            (_, Some(uri)) => uri.to_string(),
+            // `library;` names the library after its file
+            ("library", None) if body.is_empty() => Path::new(ctx.file_path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            (_, None) => body.split_whitespace().collect(),
+        };
+        metadata.insert("target".to_string(), Value::from(target.clone()));
+
+        let resolved_path = uri.and_then(|uri| resolve_uri(ctx, uri, &mut metadata));
+        if uri.is_some() {
+            let conditions: Vec<Value> = CONDITION_RE
+                .captures_iter(body)
+                .map(|c| {
+                    let branch = c.get(2).or(c.get(3)).unwrap().as_str();
+                    let mut condition = json!({ "condition": &c[1], "uri": branch });
+                    if let Some(path) = resolve_uri(ctx, branch, &mut HashMap::new()) {
+                        condition["resolvedPath"] = Value::from(path);
+                    }
+                    condition
+                })
+                .collect();
+            if !conditions.is_empty() {
+                metadata.insert("conditions".to_string(), Value::Array(conditions));
+            }
+
+            let last_uri_end = URI_RE.find_iter(body).last().map_or(0, |m| m.end());
+            let tail = &body[last_uri_end..];
+            if let Some(prefix) = PREFIX_RE.captures(tail) {
+                metadata.insert("prefix".to_string(), Value::from(&prefix[1]));
+            }
+            if tail.split_whitespace().any(|word| word == "deferred") {
+                metadata.insert("deferred".to_string(), Value::Bool(true));
+            }
+            for combinator in COMBINATOR_RE.captures_iter(tail) {
+                let names: Vec<&str> = combinator[2].split(',').map(str::trim).collect();
+                metadata.insert(combinator[1].to_string(), json!(names));
+            }
+        }
+
+        let line = ctx.line_of(start);
+        let existing = results
+            .symbols
+            .iter_mut()
+            .find(|s| s.kind == SymbolKind::Import && s.start_line == line);
+        let id = match existing {
+            Some(symbol) => {
+                symbol
+                    .metadata
+                    .get_or_insert_with(HashMap::new)
+                    .extend(metadata);
+                symbol.id.clone()
+            }
+            None => {
+                let symbol = ctx.create_symbol(
+                    &target,
+                    SymbolKind::Import,
+                    start,
+                    whole.end(),
+                    SymbolOptions {
+                        signature: Some(ctx.text(start, whole.end()).to_string()),
+                        metadata: Some(metadata),
+                        ..Default::default()
+                    },
+                );
+                let id = symbol.id.clone();
+                results.symbols.push(symbol);
+                id
+            }
+        };
+
+        match directive {
+            "library" => library_id = Some(id),
+            "part" => parts.push((id, start)),
+            "partOf" => results.pending_relationships.push(PendingRelationship {
+                from_symbol_id: id,
+                callee_name: resolved_path.unwrap_or(target),
+                kind: RelationshipKind::Imports,
+                file_path: ctx.file_path.to_string(),
+                line_number: line,
+                confidence: 0.8,
+            }),
+            _ => {}
+        }
+    }
+
+    if let Some(library_id) = library_id {
+        for (part_id, start) in parts {
+            results.relationships.push(ctx.create_relationship(
+                &library_id,
+                &part_id,
+                RelationshipKind::Contains,
+                start,
+                None,
+            ));
+        }
+    }
+}
+
+/// Record whether `uri` is external and, when it names a workspace file, return its path
+fn resolve_uri(
+    ctx: &EnrichmentContext,
+    uri: &str,
+    metadata: &mut HashMap<String, Value>,
+) -> Option<String> {
+    let resolved = if uri.starts_with("dart:") {
+        None
+    } else if let Some(package_path) = uri.strip_prefix("package:") {
+        // package:name/src/x.dart → lib/src/x.dart, or name/lib/src/x.dart in a monorepo
+        let (package, path) = package_path.split_once('/')?;
+        ctx.resolve_workspace_path(&format!("lib/{}", path))
+            .or_else(|| ctx.resolve_workspace_path(&format!("{}/lib/{}", package, path)))
+            .or_else(|| ctx.resolve_workspace_path(&format!("packages/{}/lib/{}", package, path)))
+    } else {
+        ctx.resolve_relative_to_file(uri)
+    };
+
+    let external = uri.starts_with("dart:") || (uri.starts_with("package:") && resolved.is_none());
+    metadata.insert("external".to_string(), Value::Bool(external));
+    metadata.insert("resolved".to_string(), Value::Bool(resolved.is_some()));
+    if let Some(path) = &resolved {
+        metadata.insert("resolvedPath".to_string(), Value::from(path.clone()));
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn extract(file_path: &str, content: &str, root: &Path) -> ExtractionResults {
+        let ctx = EnrichmentContext::new(file_path, "dart", content, root);
+        let mut results = test_results(Vec::new());
+        extract_directives(&ctx, &mut results);
+        results
+    }
+
+    fn metadata<'r>(results: &'r ExtractionResults, name: &str) -> &'r HashMap<String, Value> {
+        results
+            .symbols
+            .iter()
+            .find(|s| s.name == name)
+            .and_then(|s| s.metadata.as_ref())
+            .unwrap_or_else(|| panic!("no directive {}", name))
+    }
+
+    #[test]
+    fn test_library_parts_and_imports() {
+        let root = TempDir::new().unwrap();
+        fs::create_dir_all(root.path().join("lib/src")).unwrap();
+        for file in ["lib/src/user.g.dart", "lib/src/io.dart", "lib/util.dart"] {
+            fs::write(root.path().join(file), "").unwrap();
+        }
+        let content = "library app.models;\n\nimport 'dart:async';\nimport 'package:app/util.dart' as util show format, parse;\nimport 'stub.dart'\n    if (dart.library.io) 'io.dart'\n    if (dart.library.html) 'web.dart';\n// part 'commented.dart';\npart 'user.g.dart';\n";
+        let results = extract("lib/src/user.dart", content, root.path());
+
+        assert_eq!(results.symbols.len(), 5);
+        assert!(results.symbols.iter().all(|s| s.kind == SymbolKind::Import));
+        assert_eq!(
+            metadata(&results, "app.models")["directive"],
+            json!("library")
+        );
+        assert_eq!(metadata(&results, "dart:async")["external"], json!(true));
+
+        let util = metadata(&results, "package:app/util.dart");
+        assert_eq!(util["resolvedPath"], json!("lib/util.dart"));
+        assert_eq!(util["prefix"], json!("util"));
+        assert_eq!(util["show"], json!(["format", "parse"]));
+
+        let stub = metadata(&results, "stub.dart");
+        assert_eq!(stub["resolved"], json!(false));
+        assert_eq!(
+            stub["conditions"],
+            json!([
+                { "condition": "dart.library.io", "uri": "io.dart", "resolvedPath": "lib/src/io.dart" },
+                { "condition": "dart.library.html", "uri": "web.dart" },
+            ])
+        );
+
+        let part = metadata(&results, "user.g.dart");
+        assert_eq!(part["directive"], json!("part"));
+        assert_eq!(part["resolvedPath"], json!("lib/src/user.g.dart"));
+        assert_eq!(results.relationships.len(), 1);
+        assert_eq!(results.relationships[0].kind, RelationshipKind::Contains);
+        assert_eq!(
+            results.relationships[0].from_symbol_id,
+            results.symbols[0].id
+        );
+    }
+
+    #[test]
+    fn test_part_of_points_at_owning_library() {
+        let root = TempDir::new().unwrap();
+        fs::create_dir_all(root.path().join("lib")).unwrap();
+        fs::write(root.path().join("lib/user.dart"), "").unwrap();
+
+        let results = extract("lib/user.g.dart", "part of 'user.dart';\n", root.path());
+        assert_eq!(
+            metadata(&results, "user.dart")["directive"],
+            json!("partOf")
+        );
+        assert_eq!(results.pending_relationships.len(), 1);
+        assert_eq!(
+            results.pending_relationships[0].callee_name,
+            "lib/user.dart"
+        );
+
+        let results = extract("lib/user.g.dart", "part of app.models;\n", root.path());
+        assert_eq!(results.pending_relationships[0].callee_name, "app.models");
+        assert!(!metadata(&results, "app.models").contains_key("resolved"));
+    }
+}
@@ -0,0 +1,22 @@
+// Dart enrichment passes
+//
+// Julie's Dart extractor handles classes, methods, and fields; these passes
+// add library/part/import/export directives (resolved to workspace files), the
+// Flutter widget tree built in build() methods, and the handlers wired to its
+// callback arguments.
+
+mod directives;
+mod widgets;
+
+use super::base::{EnrichmentContext, Lexicon};
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+/// Dart strings, // and /* */ comments (string interpolation is left as string text)
+const DART: Lexicon = Lexicon::C_LIKE;
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    directives::extract_directives(ctx, results);
+    widgets::enrich_build_methods(ctx, results);
+}
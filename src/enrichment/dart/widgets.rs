@@ -0,0 +1,324 @@
+// Flutter widget trees
+//
+// A Flutter build() method is mostly one nested constructor expression:
+//
+//     return Scaffold(
+//       body: Column(children: [Text('Hi'), ElevatedButton(onPressed: _save)]),
+//     );
+//
+// Julie records little of it, so "which screens use ElevatedButton" and "who
+// wires up _save" go unanswered. For each build method this pass adds a Call
+// identifier, owned by the method, for every invocation Julie missed (type
+// invocations are named after the type: `EdgeInsets.all(8)` → EdgeInsets).
+// Julie identifiers carry no metadata, so invocations of uppercase names
+// (widget and other type constructors) are listed on the build method instead:
+// metadata.widgetInstantiations holds `{ name, line, isWidgetInstantiation }`.
+//
+// Callback arguments given a tear-off (`onPressed: _handleTap`,
+// `onChanged: widget.onChanged`) get a MemberAccess identifier for the
+// handler, targeting the method of that name when the file declares one, and
+// are listed in metadata.callbackHandlers as `{ argument, handler, line }`.
+
+use super::DART;
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, EnrichmentContext,
+};
+use julie_extractors::{ExtractionResults, IdentifierKind, SymbolKind};
+use regex::Regex;
+use serde_json::json;
+use std::sync::LazyLock;
+
+/// The `build(` of a build method's header
+static BUILD_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bbuild\s*\(").unwrap());
+
+/// `Name(`, `Name.named(`, `Name<T>(`, `receiver.method(`
+static INVOCATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b([A-Za-z_$][\w$]*)(?:\s*\.\s*([A-Za-z_$][\w$]*))?\s*(?:<[^<>(){};]*(?:<[^<>(){};]*>[^<>(){};]*)*>)?\s*\(",
+    )
+    .unwrap()
+});
+
+/// `onPressed: _handleTap,` or `onChanged: widget.onChanged)`: a tear-off, not a call or closure
+static CALLBACK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(on[A-Z]\w*)\s*:\s*(?:(?:this|widget)\s*\.\s*)?([A-Za-z_$][\w$]*)\s*[,)\]}]")
+        .unwrap()
+});
+
+/// Words followed by `(` that are not invocations
+const KEYWORDS: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "return", "assert", "super", "this", "await",
+];
+
+/// Values of callback arguments that are not handlers
+const LITERALS: &[&str] = &["null", "true", "false"];
+
+/// A build method and the byte range of its body
+struct BuildMethod {
+    id: String,
+    parent_id: Option<String>,
+    body: (usize, usize),
+}
+
+pub(super) fn enrich_build_methods(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let code = code_mask(ctx.content, DART);
+    let builds: Vec<BuildMethod> = results
+        .symbols
+        .iter()
+        .filter(|s| {
+            s.name == "build" && matches!(s.kind, SymbolKind::Method | SymbolKind::Function)
+        })
+        .filter_map(|s| {
+            let (start, end) = (s.start_byte as usize, s.end_byte as usize);
+            let header = BUILD_RE.find(ctx.text(start, end))?;
+            let params_end = find_matching_delimiter(ctx.content, start + header.end() - 1, DART)?;
+            Some(BuildMethod {
+                id: s.id.clone(),
+                parent_id: s.parent_id.clone(),
+                body: (params_end + 1, end),
+            })
+        })
+        .collect();
+
+    for build in builds {
+        let (body_start, body_end) = build.body;
+        let body = ctx.text(body_start, body_end);
+        let mut widgets = Vec::new();
+        let mut handlers = Vec::new();
+
+        for captures in INVOCATION_RE.captures_iter(body) {
+            let receiver = captures.get(1).unwrap();
+            let start = body_start + receiver.start();
+            if !code[start] || is_member_access(ctx.content, start) {
+                continue;
+            }
+            let is_type = receiver
+                .as_str()
+                .starts_with(|c: char| c.is_ascii_uppercase());
+            let (name, name_start) = match captures.get(2) {
+                Some(member) if !is_type => (member.as_str(), body_start + member.start()),
+                _ if KEYWORDS.contains(&receiver.as_str()) => continue,
+                _ => (receiver.as_str(), start),
+            };
+
+            let known = results.identifiers.iter_mut().find(|identifier| {
+                identifier.kind == IdentifierKind::Call
+                    && identifier.start_byte as usize == name_start
+            });
+            match known {
+                Some(identifier) => {
+                    identifier
+                        .containing_symbol_id
+                        .get_or_insert_with(|| build.id.clone());
+                }
+                None => results.identifiers.push(ctx.create_identifier(
+                    name,
+                    IdentifierKind::Call,
+                    name_start,
+                    name_start + name.len(),
+                    Some(build.id.clone()),
+                )),
+            }
+            if is_type {
+                widgets.push(json!({
+                    "name": name,
+                    "line": ctx.line_of(name_start),
+                    "isWidgetInstantiation": true,
+                }));
+            }
+        }
+
+        for captures in CALLBACK_RE.captures_iter(body) {
+            let argument = captures.get(1).unwrap();
+            let handler = captures.get(2).unwrap();
+            if !code[body_start + argument.start()] || LITERALS.contains(&handler.as_str()) {
+                continue;
+            }
+            let handler_start = body_start + handler.start();
+            let target_id = results
+                .symbols
+                .iter()
+                .filter(|s| {
+                    s.name == handler.as_str()
+                        && matches!(s.kind, SymbolKind::Method | SymbolKind::Function)
+                })
+                .max_by_key(|s| s.parent_id == build.parent_id)
+                .map(|s| s.id.clone());
+
+            let known = results
+                .identifiers
+                .iter_mut()
+                .find(|identifier| identifier.start_byte as usize == handler_start);
+            match known {
+                Some(identifier) => {
+                    if identifier.target_symbol_id.is_none() {
+                        identifier.target_symbol_id = target_id;
+                    }
+                }
+                None => {
+                    let mut identifier = ctx.create_identifier(
+                        handler.as_str(),
+                        IdentifierKind::MemberAccess,
+                        handler_start,
+                        handler_start + handler.len(),
+                        Some(build.id.clone()),
+                    );
+                    identifier.target_symbol_id = target_id;
+                    results.identifiers.push(identifier);
+                }
+            }
+            handlers.push(json!({
+                "argument": argument.as_str(),
+                "handler": handler.as_str(),
+                "line": ctx.line_of(handler_start),
+            }));
+        }
+
+        let Some(method) = results.symbols.iter_mut().find(|s| s.id == build.id) else {
+            continue;
+        };
+        for widget in widgets {
+            append_metadata(method, "widgetInstantiations", widget);
+        }
+        for handler in handlers {
+            append_metadata(method, "callbackHandlers", handler);
+        }
+    }
+}
+
+/// Whether the name at `start` follows a `.` (a chained call Julie already sees)
+fn is_member_access(content: &str, start: usize) -> bool {
+    content[..start].trim_end().ends_with('.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use serde_json::Value;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"class CounterPage extends StatefulWidget {}
+
+class _CounterState extends State<CounterPage> {
+  void _handleTap() {}
+
+  @override
+  Widget build(BuildContext context) {
+    // Placeholder(), not code
+    return Scaffold(
+      body: Column(
+        children: [
+          Text('Count: $count'),
+          Padding(padding: EdgeInsets.all(8), child: helper()),
+          ElevatedButton(onPressed: _handleTap, child: const Text("Tap")),
+          Switch(onChanged: null, value: true),
+        ],
+      ),
+    );
+  }
+}
+"#;
+
+    fn widget_names(metadata: &Value) -> Vec<&str> {
+        metadata
+            .as_array()
+            .map(|items| items.iter().filter_map(|w| w["name"].as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    fn run() -> ExtractionResults {
+        let ctx = EnrichmentContext::new("counter.dart", "dart", SOURCE, Path::new("."));
+        let state_start = SOURCE.find("class _CounterState").unwrap();
+        let state = ctx.create_symbol(
+            "_CounterState",
+            SymbolKind::Class,
+            state_start,
+            SOURCE.len() - 1,
+            SymbolOptions::default(),
+        );
+        let tap_start = SOURCE.find("void _handleTap").unwrap();
+        let tap = ctx.create_symbol(
+            "_handleTap",
+            SymbolKind::Method,
+            tap_start,
+            tap_start + "void _handleTap() {}".len(),
+            SymbolOptions {
+                parent_id: Some(state.id.clone()),
+                ..Default::default()
+            },
+        );
+        let build_start = SOURCE.find("@override").unwrap();
+        let build_end = SOURCE.rfind("  }\n}").unwrap() + 3;
+        let build = ctx.create_symbol(
+            "build",
+            SymbolKind::Method,
+            build_start,
+            build_end,
+            SymbolOptions {
+                parent_id: Some(state.id.clone()),
+                ..Default::default()
+            },
+        );
+        let mut results = test_results(vec![state, tap, build]);
+        enrich_build_methods(&ctx, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_widget_invocations_are_attributed_to_build() {
+        let results = run();
+        let build = &results.symbols[2];
+
+        let metadata = build.metadata.as_ref().unwrap();
+        assert_eq!(
+            widget_names(&metadata["widgetInstantiations"]),
+            vec![
+                "Scaffold",
+                "Column",
+                "Text",
+                "Padding",
+                "EdgeInsets",
+                "ElevatedButton",
+                "Text",
+                "Switch"
+            ]
+        );
+
+        let calls: Vec<&str> = results
+            .identifiers
+            .iter()
+            .filter(|i| i.kind == IdentifierKind::Call)
+            .map(|i| i.name.as_str())
+            .collect();
+        assert!(calls.contains(&"helper"));
+        assert!(!calls.contains(&"build") && !calls.contains(&"Placeholder"));
+        assert!(results
+            .identifiers
+            .iter()
+            .all(|i| i.containing_symbol_id.as_deref() == Some(build.id.as_str())));
+    }
+
+    #[test]
+    fn test_callback_tear_offs_target_handlers() {
+        let results = run();
+
+        let handlers: Vec<_> = results
+            .identifiers
+            .iter()
+            .filter(|i| i.kind == IdentifierKind::MemberAccess)
+            .collect();
+        assert_eq!(handlers.len(), 1);
+        assert_eq!(handlers[0].name, "_handleTap");
+        assert_eq!(
+            handlers[0].target_symbol_id.as_deref(),
+            Some(results.symbols[1].id.as_str())
+        );
+
+        let metadata = results.symbols[2].metadata.as_ref().unwrap();
+        assert_eq!(
+            metadata["callbackHandlers"],
+            json!([{ "argument": "onPressed", "handler": "_handleTap", "line": 14 }])
+        );
+    }
+}
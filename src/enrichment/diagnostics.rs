@@ -0,0 +1,186 @@
+// Extraction Diagnostics - How complete a file's extraction results are
+//
+// When a file only partly parses, Julie and the enrichment passes still return
+// best-effort results. Diagnostics tell callers which parts came from
+// something other than a clean parse, so an indexer can flag the file for
+// re-extraction after a grammar upgrade:
+//
+// - recovered_declaration: a declaration recovered from text the parser could
+//   not handle (metadata.extractedFromError, see base::error_recovery)
+// - text_fallback: symbols from the regex extractor's line-by-line text scan
+//   (metadata.textFallback), reported once per file
+// - package_mismatch: a Java file whose package does not match its directory
+//   (metadata.packageMismatch, set by engine::resolve_cross_file), so imports
+//   of its classes were not resolved
+// - ambiguous_pairing: a C/C++ function declaration or definition with more
+//   than one counterpart in the batch (metadata.ambiguousPairing, set by
+//   engine::resolve_cross_file), so none was paired
+//
+// Diagnostics are derived from the results alone, so cached and session
+// results report them the same way as fresh ones. Julie does not expose its
+// parse tree, so ERROR nodes it handled silently are not counted.
+
+use julie_extractors::{ExtractionResults, Symbol};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// What kind of best-effort extraction a diagnostic reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    RecoveredDeclaration,
+    TextFallback,
+    PackageMismatch,
+    AmbiguousPairing,
+}
+
+impl DiagnosticKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticKind::RecoveredDeclaration => "recovered_declaration",
+            DiagnosticKind::TextFallback => "text_fallback",
+            DiagnosticKind::PackageMismatch => "package_mismatch",
+            DiagnosticKind::AmbiguousPairing => "ambiguous_pairing",
+        }
+    }
+}
+
+/// One partial-extraction finding, with the 1-based lines it covers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    pub message: String,
+    pub start_line: u32,
+    pub end_line: u32,
+}
+
+/// Diagnostics for a file's extraction results, in line order
+pub fn diagnose(results: &ExtractionResults) -> Vec<Diagnostic> {
+    let flagged = |symbol: &Symbol, key: &str| {
+        symbol
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get(key))
+            .is_some_and(|v| v == &Value::Bool(true))
+    };
+
+    let mut diagnostics: Vec<Diagnostic> = results
+        .symbols
+        .iter()
+        .filter(|s| flagged(s, "extractedFromError"))
+        .map(|s| Diagnostic {
+            kind: DiagnosticKind::RecoveredDeclaration,
+            message: format!(
+                "{:?} '{}' was recovered from code the parser could not handle",
+                s.kind, s.name
+            ),
+            start_line: s.start_line,
+            end_line: s.end_line,
+        })
+        .collect();
+
+    let fallback: Vec<&Symbol> = results
+        .symbols
+        .iter()
+        .filter(|s| flagged(s, "textFallback"))
+        .collect();
+    if let (Some(first), Some(last)) = (
+        fallback.iter().map(|s| s.start_line).min(),
+        fallback.iter().map(|s| s.end_line).max(),
+    ) {
+        diagnostics.push(Diagnostic {
+            kind: DiagnosticKind::TextFallback,
+            message: format!(
+                "{} symbol(s) came from a text scan instead of the parse tree",
+                fallback.len()
+            ),
+            start_line: first,
+            end_line: last,
+        });
+    }
+
+    diagnostics.extend(results.symbols.iter().filter_map(|s| {
+        let mismatch = s.metadata.as_ref()?.get("packageMismatch")?;
+        Some(Diagnostic {
+            kind: DiagnosticKind::PackageMismatch,
+            message: format!(
+                "package '{}' does not match directory '{}'",
+                mismatch["package"].as_str()?,
+                mismatch["directory"].as_str()?
+            ),
+            start_line: s.start_line,
+            end_line: s.end_line,
+        })
+    }));
+
+    diagnostics.extend(results.symbols.iter().filter_map(|s| {
+        let ambiguity = s.metadata.as_ref()?.get("ambiguousPairing")?;
+        let files = |key: &str| -> Option<String> {
+            let paths: Vec<&str> = ambiguity[key]
+                .as_array()?
+                .iter()
+                .filter_map(Value::as_str)
+                .collect();
+            Some(paths.join(", "))
+        };
+        Some(Diagnostic {
+            kind: DiagnosticKind::AmbiguousPairing,
+            message: format!(
+                "'{}' has declarations in {} and definitions in {}; none was paired",
+                s.name,
+                files("declarations")?,
+                files("definitions")?
+            ),
+            start_line: s.start_line,
+            end_line: s.end_line,
+        })
+    }));
+
+    diagnostics.sort_by_key(|d| (d.start_line, d.end_line));
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{set_metadata, test_results, EnrichmentContext, SymbolOptions};
+    use julie_extractors::SymbolKind;
+    use std::path::Path;
+
+    #[test]
+    fn test_clean_results_have_no_diagnostics() {
+        let ctx = EnrichmentContext::new("a.py", "python", "def f(): pass\n", Path::new("."));
+        let f = ctx.create_symbol("f", SymbolKind::Function, 0, 13, SymbolOptions::default());
+        assert!(diagnose(&test_results(vec![f])).is_empty());
+    }
+
+    #[test]
+    fn test_recovered_and_fallback_symbols() {
+        let content = "a\nb\nc\nCREATE TABLE t (id int);\n";
+        let ctx = EnrichmentContext::new("a.sql", "sql", content, Path::new("."));
+        let mut table = ctx.create_symbol("t", SymbolKind::Class, 6, 30, SymbolOptions::default());
+        set_metadata(&mut table, "extractedFromError", true);
+        let mut patterns = Vec::new();
+        for (name, start) in [("a", 0), ("b", 2)] {
+            let mut symbol = ctx.create_symbol(
+                name,
+                SymbolKind::Variable,
+                start,
+                start + 1,
+                SymbolOptions::default(),
+            );
+            set_metadata(&mut symbol, "textFallback", true);
+            patterns.push(symbol);
+        }
+        patterns.push(table);
+
+        let diagnostics = diagnose(&test_results(patterns));
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::TextFallback);
+        assert_eq!((diagnostics[0].start_line, diagnostics[0].end_line), (1, 2));
+        assert_eq!(diagnostics[1].kind, DiagnosticKind::RecoveredDeclaration);
+        assert_eq!(diagnostics[1].start_line, 4);
+        assert!(diagnostics[1].message.contains("'t'"));
+    }
+}
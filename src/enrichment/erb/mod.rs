@@ -0,0 +1,43 @@
+// ERB template passes
+//
+// Rails views (`show.html.erb`) are HTML with embedded Ruby, and Julie has no
+// ERB extractor. The engine hands the markup, with every `<% %>` region masked
+// out (html::mask_placeholders), to Julie's HTML extractor under the path
+// `show.html.erb.html` (enrichment::parser_path). These passes put the
+// template's own path back on the results and run the HTML passes (so each
+// placeholder also becomes a symbol under its element), then add a Module
+// symbol for the template itself, the identifiers of its embedded Ruby, and
+// an Import for every partial it renders.
+
+mod partials;
+mod ruby;
+
+use super::base::EnrichmentContext;
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    restore_file_path(ctx, results);
+    super::html::enrich(ctx, results);
+
+    let template_id = partials::add_template_symbol(ctx, results);
+    let Some(source) = ruby::ruby_source(ctx.content) else {
+        return;
+    };
+    ruby::extract_embedded_ruby(ctx, results, &source, &template_id);
+    partials::extract_renders(ctx, results, &source, &template_id);
+}
+
+/// Julie saw the template under parser_path's name
+fn restore_file_path(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    for symbol in &mut results.symbols {
+        symbol.file_path = ctx.file_path.to_string();
+    }
+    for identifier in &mut results.identifiers {
+        identifier.file_path = ctx.file_path.to_string();
+    }
+    for relationship in &mut results.relationships {
+        relationship.file_path = ctx.file_path.to_string();
+    }
+}
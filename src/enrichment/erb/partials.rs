@@ -0,0 +1,241 @@
+// ERB templates and partial renders
+//
+// Each template gets a Module symbol spanning the file, named as Rails names
+// it on disk (`show` for show.html.erb, `_header` for a partial), with
+// metadata.erbTemplate, partial, and, under app/views, templatePath (the
+// logical path: "users/show").
+//
+// `render "shared/header"`, `render partial: "form"`, and
+// `render(:partial => "row")` with a literal path become Import symbols under
+// the template, plus an Import identifier on the path, with
+// metadata.importPath as written. When the partial exists in the workspace
+// the import also records resolvedPath (app/views/shared/_header.html.erb)
+// and importedName (the partial's template symbol), so resolve_cross_file
+// links the render to it. A path without a slash is looked up next to the
+// template, one with a slash under app/views (or the template's directory
+// outside a Rails tree); among the partial's formats, the template's own
+// (`.html.erb`) is preferred.
+
+use crate::enrichment::base::{EnrichmentContext, SymbolOptions};
+use julie_extractors::{ExtractionResults, IdentifierKind, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// `render` with a literal partial path, optionally as `partial:`
+static RENDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"\brender\b\s*\(?\s*(?:partial\s*:\s*|:partial\s*=>\s*)?(?:"([^"\n]+)"|'([^'\n]+)')"#,
+    )
+    .unwrap()
+});
+
+const VIEWS_DIR: &str = "app/views";
+
+/// Add the template's own symbol, returning its ID
+pub(super) fn add_template_symbol(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+) -> String {
+    let (name, _) = split_file_name(ctx.file_path);
+    let mut metadata = HashMap::from([
+        ("erbTemplate".to_string(), Value::Bool(true)),
+        ("partial".to_string(), Value::Bool(name.starts_with('_'))),
+    ]);
+    if let Some(root) = views_root(ctx.file_path) {
+        let directory = parent_dir(ctx.file_path);
+        let logical = directory[root.len()..].trim_start_matches('/');
+        let path = if logical.is_empty() {
+            name.to_string()
+        } else {
+            format!("{logical}/{name}")
+        };
+        metadata.insert("templatePath".to_string(), Value::from(path));
+    }
+
+    let template = ctx.create_symbol(
+        name,
+        SymbolKind::Module,
+        0,
+        ctx.content.len(),
+        SymbolOptions {
+            metadata: Some(metadata),
+            ..Default::default()
+        },
+    );
+    let id = template.id.clone();
+    results.symbols.push(template);
+    id
+}
+
+pub(super) fn extract_renders(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    source: &str,
+    template_id: &str,
+) {
+    for captures in RENDER_RE.captures_iter(source) {
+        let render = captures.get(0).unwrap();
+        let partial = captures.get(1).or(captures.get(2)).unwrap();
+        let path = partial.as_str();
+
+        let mut metadata = HashMap::from([
+            ("importPath".to_string(), Value::from(path)),
+            ("partial".to_string(), Value::Bool(true)),
+        ]);
+        if let Some(resolved) = find_partial(ctx, path) {
+            let (imported, _) = split_file_name(&resolved);
+            metadata.insert("importedName".to_string(), Value::from(imported));
+            metadata.insert("resolvedPath".to_string(), Value::from(resolved));
+        }
+
+        results.symbols.push(ctx.create_symbol(
+            path,
+            SymbolKind::Import,
+            render.start(),
+            render.end(),
+            SymbolOptions {
+                signature: Some(ctx.text(render.start(), render.end()).to_string()),
+                parent_id: Some(template_id.to_string()),
+                metadata: Some(metadata),
+                ..Default::default()
+            },
+        ));
+        results.identifiers.push(ctx.create_identifier(
+            path,
+            IdentifierKind::Import,
+            partial.start(),
+            partial.end(),
+            Some(template_id.to_string()),
+        ));
+    }
+}
+
+/// Workspace-relative path of the partial `render` names
+fn find_partial(ctx: &EnrichmentContext, partial: &str) -> Option<String> {
+    let template_dir = parent_dir(ctx.file_path);
+    let (directory, base) = match partial.rsplit_once('/') {
+        Some((directory, base)) => {
+            let root = views_root(ctx.file_path).unwrap_or(template_dir);
+            (Path::new(root).join(directory), base)
+        }
+        None => (Path::new(template_dir).to_path_buf(), partial),
+    };
+    let prefix = format!("_{base}.");
+    let (_, format) = split_file_name(ctx.file_path);
+
+    let mut names: Vec<String> = fs::read_dir(ctx.workspace_root.join(&directory))
+        .ok()?
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with(&prefix) && name.ends_with(".erb"))
+        .collect();
+    names.sort_by(|a, b| {
+        let other_format = |name: &str| name[prefix.len()..] != *format;
+        (other_format(a), a).cmp(&(other_format(b), b))
+    });
+    names
+        .iter()
+        .find_map(|name| ctx.resolve_workspace_path(&directory.join(name).to_string_lossy()))
+}
+
+/// The app/views directory a template is under
+fn views_root(file_path: &str) -> Option<&str> {
+    file_path
+        .match_indices(VIEWS_DIR)
+        .map(|(at, _)| at)
+        .filter(|&at| at == 0 || file_path.as_bytes()[at - 1] == b'/')
+        .find(|&at| file_path.as_bytes().get(at + VIEWS_DIR.len()) == Some(&b'/'))
+        .map(|at| &file_path[..at + VIEWS_DIR.len()])
+}
+
+fn parent_dir(file_path: &str) -> &str {
+    file_path
+        .rsplit_once('/')
+        .map_or("", |(directory, _)| directory)
+}
+
+/// `app/views/users/show.html.erb` → (`show`, `html.erb`)
+fn split_file_name(file_path: &str) -> (&str, &str) {
+    let file_name = file_path.rsplit('/').next().unwrap_or(file_path);
+    file_name.split_once('.').unwrap_or((file_name, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_template_symbol_and_partial_renders() {
+        let workspace = TempDir::new().unwrap();
+        let views = workspace.path().join("app/views");
+        fs::create_dir_all(views.join("shared")).unwrap();
+        fs::create_dir_all(views.join("users")).unwrap();
+        for partial in [
+            "shared/_header.html.erb",
+            "users/_form.text.erb",
+            "users/_form.html.erb",
+        ] {
+            fs::write(views.join(partial), "").unwrap();
+        }
+
+        let content = "<%= render \"shared/header\" %>\n<div>\n  <%= render partial: 'form', locals: { user: @user } %>\n  <%= render @user.posts %>\n  <%= render(\"missing\") %>\n</div>\n";
+        let ctx = EnrichmentContext::new(
+            "app/views/users/show.html.erb",
+            "erb",
+            content,
+            workspace.path(),
+        );
+        let mut results = test_results(Vec::new());
+        let template_id = add_template_symbol(&ctx, &mut results);
+        let source = super::super::ruby::ruby_source(content).unwrap();
+        extract_renders(&ctx, &mut results, &source, &template_id);
+
+        let template = results.symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(results.symbols[0].name, "show");
+        assert_eq!(template["templatePath"], Value::from("users/show"));
+        assert_eq!(template["partial"], Value::Bool(false));
+
+        let imports: Vec<(&str, Option<&Value>, Option<&Value>)> = results.symbols[1..]
+            .iter()
+            .map(|s| {
+                assert_eq!(s.parent_id.as_deref(), Some(template_id.as_str()));
+                let metadata = s.metadata.as_ref().unwrap();
+                (
+                    s.name.as_str(),
+                    metadata.get("resolvedPath"),
+                    metadata.get("importedName"),
+                )
+            })
+            .collect();
+        assert_eq!(
+            imports,
+            vec![
+                (
+                    "shared/header",
+                    Some(&Value::from("app/views/shared/_header.html.erb")),
+                    Some(&Value::from("_header")),
+                ),
+                (
+                    "form",
+                    Some(&Value::from("app/views/users/_form.html.erb")),
+                    Some(&Value::from("_form")),
+                ),
+                ("missing", None, None),
+            ]
+        );
+        assert_eq!(results.identifiers.len(), 3);
+        assert_eq!(
+            ctx.text(
+                results.identifiers[1].start_byte as usize,
+                results.identifiers[1].end_byte as usize
+            ),
+            "form"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+// Embedded Ruby of ERB templates
+//
+// The code of every `<% %>`, `<%= %>`, and `<%- -%>` region is laid into a
+// Ruby source as long as the template: markup and tag delimiters become
+// spaces (newlines are kept), and each closing `%>` becomes `;` so
+// `<% a = 1 %><% b = 2 %>` stays two statements while `<% if admin? %>` ...
+// `<% end %>` still reads as one `if`. `<%# %>` comments and `<%%` escapes are
+// blanked. Julie's Ruby extractor parses that source, so everything it
+// reports is already at the template's offsets and lines. Its identifiers
+// (calls, member accesses, ...) are kept, with language "ruby", contained by
+// the template symbol; its symbols (locals, block parameters) are not.
+
+use crate::enrichment::base::EnrichmentContext;
+use julie_extractors::{ExtractionResults, ExtractorManager};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// A tag and its content, delimiters excluded
+static TAG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)<%(.*?)%>").unwrap());
+
+/// The template's embedded Ruby at its own offsets, None without any
+pub(super) fn ruby_source(content: &str) -> Option<String> {
+    let mut bytes: Vec<u8> = content
+        .bytes()
+        .map(|byte| if byte == b'\n' { b'\n' } else { b' ' })
+        .collect();
+    let mut found = false;
+    for captures in TAG_RE.captures_iter(content) {
+        let inner = captures.get(1).unwrap();
+        let text = inner.as_str();
+        if text.starts_with(['%', '#']) {
+            continue;
+        }
+        // `<%=`, `<%==`, `<%-` open; `-%>` closes
+        let start = inner.start() + text.len() - text.trim_start_matches(['=', '-']).len();
+        let end = inner.start() + text.trim_end_matches('-').len();
+        if start < end {
+            bytes[start..end].copy_from_slice(&content.as_bytes()[start..end]);
+        }
+        bytes[inner.end()] = b';';
+        found = true;
+    }
+    // Only whole UTF-8 sequences were kept or replaced by ASCII
+    found.then(|| String::from_utf8(bytes).expect("blanking keeps UTF-8 valid"))
+}
+
+pub(super) fn extract_embedded_ruby(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    source: &str,
+    template_id: &str,
+) {
+    let path = format!("{}.rb", ctx.file_path);
+    let manager = ExtractorManager::new();
+    let Ok(symbols) = manager.extract_symbols(&path, source, ctx.workspace_root) else {
+        return;
+    };
+    let Ok(identifiers) = manager.extract_identifiers(&path, source, &symbols) else {
+        return;
+    };
+    for mut identifier in identifiers {
+        identifier.file_path = ctx.file_path.to_string();
+        identifier.containing_symbol_id = Some(template_id.to_string());
+        identifier.target_symbol_id = None;
+        results.identifiers.push(identifier);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ruby_source_keeps_template_offsets() {
+        let template =
+            "<p><%= link_to \"Édit\", path -%></p>\n<% if admin? %><%# note %><b>hi</b><% end %>\n<%% raw %>\n";
+        let source = ruby_source(template).unwrap();
+        assert_eq!(source.len(), template.len());
+        let lines: Vec<&str> = source.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            format!("       link_to \"Édit\", path  ;{}", " ".repeat(5))
+        );
+        assert_eq!(
+            lines[1].split_whitespace().collect::<Vec<_>>(),
+            vec!["if", "admin?", ";", "end", ";"]
+        );
+        assert_eq!(
+            lines[1].find("if"),
+            template.lines().nth(1).unwrap().find("if")
+        );
+        assert!(lines[2].trim().is_empty());
+
+        assert_eq!(ruby_source("<p>plain {{ x }}</p>"), None);
+    }
+}
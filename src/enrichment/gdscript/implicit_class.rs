@@ -0,0 +1,68 @@
+// Implicit GDScript classes
+//
+// Every .gd file is a class. Without a `class_name` statement Julie still
+// emits a top-level Class symbol for it, named from the file path rather than
+// from anything written in the source. This pass marks such classes with
+// metadata.heuristicName so their confidence is reduced (see
+// enrichment::confidence); inner `class X:` declarations and named scripts are
+// left alone.
+
+use super::GDSCRIPT;
+use crate::enrichment::base::{code_mask, set_metadata, EnrichmentContext};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `class_name Player` or `class Inner:`
+static CLASS_DECLARATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*(?:class_name|class)[ \t]+([A-Za-z_]\w*)").unwrap());
+
+pub(super) fn mark_implicit_classes(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, GDSCRIPT);
+    let declared: Vec<&str> = CLASS_DECLARATION_RE
+        .captures_iter(ctx.content)
+        .filter(|caps| code[caps.get(1).unwrap().start()])
+        .map(|caps| caps.get(1).unwrap().as_str())
+        .collect();
+
+    for symbol in symbols.iter_mut().filter(|s| {
+        s.kind == SymbolKind::Class && s.parent_id.is_none() && !declared.contains(&s.name.as_str())
+    }) {
+        set_metadata(symbol, "heuristicName", true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    #[test]
+    fn test_only_classes_without_a_declaration_are_marked() {
+        let content = "extends Node\n# class Commented\nclass Inner:\n    var x = 1\n";
+        let ctx = EnrichmentContext::new("player.gd", "gdscript", content, Path::new("."));
+        let class = |name: &str| {
+            ctx.create_symbol(
+                name,
+                SymbolKind::Class,
+                0,
+                content.len(),
+                SymbolOptions::default(),
+            )
+        };
+        let mut symbols = vec![class("Player"), class("Inner"), class("Commented")];
+
+        mark_implicit_classes(&ctx, &mut symbols);
+
+        let marked: Vec<bool> = symbols
+            .iter()
+            .map(|s| {
+                s.metadata
+                    .as_ref()
+                    .is_some_and(|m| m.contains_key("heuristicName"))
+            })
+            .collect();
+        assert_eq!(marked, vec![true, false, true]);
+    }
+}
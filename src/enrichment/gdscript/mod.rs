@@ -0,0 +1,28 @@
+// GDScript enrichment passes
+//
+// Julie's GDScript extractor produces classes, functions, signals, and
+// variables by name; this pass adds what GDScript 2.0 declarations say about
+// those variables: declared or inferred types, @export hints, and the
+// setter/getter functions behind properties. Script classes Julie named after
+// the file are marked as heuristically named.
+
+mod implicit_class;
+mod variables;
+
+use super::base::{EnrichmentContext, Lexicon};
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 3;
+
+/// GDScript strings and # comments (no block comments)
+pub(super) const GDSCRIPT: Lexicon = Lexicon {
+    quotes: b"\"'",
+    escape: Some(b'\\'),
+    line_comment: Some("#"),
+    block_comment: None,
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    variables::enrich_variables(ctx, results);
+    implicit_class::mark_implicit_classes(ctx, &mut results.symbols);
+}
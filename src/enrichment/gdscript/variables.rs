@@ -0,0 +1,362 @@
+// GDScript variable declarations
+//
+// GDScript 2.0 declarations carry more than a name:
+//
+//     @export_range(0, 100) var speed: float = 10.0
+//     var score: int = 0: set = _set_score, get = _get_score
+//     var velocity := Vector2.ZERO
+//
+// For each var/const Julie extracted, this pass records the declaration text
+// as the signature, the declared type (metadata.dataType), or for `:=` the
+// best-effort type of the literal (metadata.typeInferred), @export* and
+// @onready annotations on the same or preceding lines (metadata.exportHint,
+// exportArguments, onready), and the named setter/getter functions, which
+// also get a Uses relationship from the property (metadata.accessor set/get).
+// Properties with indented `set(value):` / `get:` blocks are marked
+// metadata.inlineAccessors.
+
+use super::GDSCRIPT;
+use crate::enrichment::base::{set_metadata, split_top_level, EnrichmentContext};
+use julie_extractors::{ExtractionResults, RelationshipKind, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// `[@annotations] [static] var|const name rest-of-line`
+static DECLARATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*(?P<annotations>(?:@\w+(?:\([^)\n]*\))?[ \t]+)*)(?:static[ \t]+)?(?:var|const)[ \t]+(?P<name>[A-Za-z_]\w*)(?P<rest>[^\n]*)",
+    )
+    .unwrap()
+});
+
+/// `@name` or `@name(args)`
+static ANNOTATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@(\w+)(?:\(([^)\n]*)\))?").unwrap());
+
+/// `set = name` / `get = name`
+static ACCESSOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(set|get)\s*=\s*([A-Za-z_]\w*)").unwrap());
+
+/// Indented `set(value):` or `get:` opening an inline accessor block
+static INLINE_ACCESSOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[ \t]+(?:set\s*\(\s*\w+\s*\)|get)\s*:").unwrap());
+
+/// What a declaration's text says about the variable
+#[derive(Debug, Default, PartialEq)]
+struct Declaration {
+    signature: String,
+    data_type: Option<String>,
+    inferred: bool,
+    annotations: Vec<(String, Vec<String>)>,
+    accessors: Vec<(String, String)>,
+    inline_accessors: bool,
+}
+
+pub(super) fn enrich_variables(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    for captures in DECLARATION_RE.captures_iter(ctx.content) {
+        let name = captures.name("name").unwrap();
+        let line = ctx.line_of(name.start());
+        let Some(index) = results.symbols.iter().position(|s| {
+            s.name == name.as_str()
+                && s.start_line <= line
+                && line <= s.end_line
+                && !matches!(
+                    s.kind,
+                    SymbolKind::Function | SymbolKind::Method | SymbolKind::Class
+                )
+        }) else {
+            continue;
+        };
+
+        let whole = captures.get(0).unwrap();
+        let declaration = parse_declaration(
+            ctx.content,
+            whole.start()..whole.end(),
+            &captures["annotations"],
+            &captures["rest"],
+        );
+        let property_id = results.symbols[index].id.clone();
+
+        for (accessor, function) in &declaration.accessors {
+            let Some(target) = results.symbols.iter().find(|s| {
+                &s.name == function && matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+            }) else {
+                continue;
+            };
+            let mut metadata = HashMap::new();
+            metadata.insert("accessor".to_string(), Value::from(accessor.as_str()));
+            let relationship = ctx.create_relationship(
+                &property_id,
+                &target.id,
+                RelationshipKind::Uses,
+                name.start(),
+                Some(metadata),
+            );
+            results.relationships.push(relationship);
+        }
+
+        let symbol = &mut results.symbols[index];
+        symbol.signature = Some(declaration.signature);
+        if let Some(data_type) = declaration.data_type {
+            set_metadata(symbol, "dataType", data_type);
+        }
+        if declaration.inferred {
+            set_metadata(symbol, "typeInferred", true);
+        }
+        for (annotation, arguments) in declaration.annotations {
+            if annotation == "onready" {
+                set_metadata(symbol, "onready", true);
+            } else if annotation.starts_with("export") {
+                set_metadata(symbol, "exportHint", annotation);
+                set_metadata(symbol, "exportArguments", arguments);
+            }
+        }
+        for (accessor, function) in declaration.accessors {
+            let key = if accessor == "set" {
+                "setter"
+            } else {
+                "getter"
+            };
+            set_metadata(symbol, key, function);
+        }
+        if declaration.inline_accessors {
+            set_metadata(symbol, "inlineAccessors", true);
+        }
+    }
+}
+
+/// Parse the declaration line at `span`, whose text after the name is `rest`
+fn parse_declaration(
+    content: &str,
+    span: Range<usize>,
+    inline_annotations: &str,
+    rest: &str,
+) -> Declaration {
+    let rest = strip_comment(rest);
+    let mut declaration = Declaration {
+        signature: strip_comment(&content[span.clone()]).trim().to_string(),
+        ..Default::default()
+    };
+
+    let annotation_text = annotations_above(content, span.start) + inline_annotations;
+    for captures in ANNOTATION_RE.captures_iter(&annotation_text) {
+        let arguments = captures.get(2).map_or_else(Vec::new, |args| {
+            split_top_level(args.as_str(), b',', GDSCRIPT)
+                .into_iter()
+                .map(|(_, arg)| arg.trim().to_string())
+                .filter(|arg| !arg.is_empty())
+                .collect()
+        });
+        declaration
+            .annotations
+            .push((captures[1].to_string(), arguments));
+    }
+
+    // ": int = 0: set = _s" → ["", " int = 0", " set = _s"]; ":= 5" → ["", "= 5"]
+    let segments = split_top_level(rest, b':', GDSCRIPT);
+    let (type_and_value, accessors) = match segments.as_slice() {
+        [_] | [] => (None, None),
+        [(_, _), (_, inferred)] if inferred.starts_with('=') => {
+            declaration.inferred = true;
+            declaration.data_type = literal_type(inferred[1..].trim()).map(str::to_string);
+            (None, None)
+        }
+        [_, (_, typed), accessors @ ..] => (Some(*typed), accessors.first().map(|(_, a)| *a)),
+    };
+    if let Some(typed) = type_and_value {
+        let data_type = typed.split('=').next().unwrap_or("").trim();
+        if !data_type.is_empty() {
+            declaration.data_type = Some(data_type.to_string());
+        }
+    }
+    match accessors.map(str::trim) {
+        Some("") => {
+            declaration.inline_accessors = content[span.end..]
+                .lines()
+                .nth(1)
+                .is_some_and(|next| INLINE_ACCESSOR_RE.is_match(next));
+        }
+        Some(accessors) => {
+            declaration.accessors = ACCESSOR_RE
+                .captures_iter(accessors)
+                .map(|c| (c[1].to_string(), c[2].to_string()))
+                .collect();
+        }
+        None => {}
+    }
+    declaration
+}
+
+/// Annotation-only lines directly above the line starting at or before `start`
+fn annotations_above(content: &str, start: usize) -> String {
+    let mut annotations = Vec::new();
+    let mut cursor = start;
+    while cursor > 0 {
+        let previous = content[..cursor - 1].rfind('\n').map_or(0, |i| i + 1);
+        let line = strip_comment(&content[previous..cursor - 1]).trim();
+        if !line.starts_with('@') || ANNOTATION_RE.replace_all(line, "").trim() != "" {
+            break;
+        }
+        annotations.push(line);
+        cursor = previous;
+    }
+    annotations.reverse();
+    annotations.join(" ") + " "
+}
+
+/// Text before a trailing `# comment`, ignoring `#` inside strings
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' => return line[..index].trim_end(),
+            None => {}
+        }
+    }
+    line.trim_end()
+}
+
+/// Best-effort type of an initializer for `var x := value`
+fn literal_type(value: &str) -> Option<&str> {
+    let first = value.chars().next()?;
+    if value == "true" || value == "false" {
+        return Some("bool");
+    }
+    if first.is_ascii_digit() || (first == '-' && value.len() > 1) {
+        let number = value.trim_start_matches('-');
+        if number.starts_with("0x") || number.starts_with("0b") {
+            return Some("int");
+        }
+        if number.chars().all(|c| c.is_ascii_digit() || c == '_') {
+            return Some("int");
+        }
+        if number
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | '_' | 'e' | '-'))
+        {
+            return Some("float");
+        }
+        return None;
+    }
+    match first {
+        '"' | '\'' => Some("String"),
+        '&' => Some("StringName"),
+        '^' => Some("NodePath"),
+        '[' => Some("Array"),
+        '{' => Some("Dictionary"),
+        // Vector2(1, 2), Vector2.ZERO, Enemy.new()
+        c if c.is_ascii_uppercase() => {
+            let end = value
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(value.len());
+            Some(&value[..end])
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use julie_extractors::Symbol;
+    use serde_json::json;
+    use std::path::Path;
+
+    fn run(content: &str, symbols: &[(&str, SymbolKind)]) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("player.gd", "gdscript", content, Path::new("."));
+        let symbols: Vec<Symbol> = symbols
+            .iter()
+            .map(|(name, kind)| {
+                let start = content.find(&format!(" {}", name)).unwrap() + 1;
+                ctx.create_symbol(
+                    name,
+                    kind.clone(),
+                    start,
+                    start + name.len(),
+                    SymbolOptions::default(),
+                )
+            })
+            .collect();
+        let mut results = test_results(symbols);
+        enrich_variables(&ctx, &mut results);
+        results
+    }
+
+    fn metadata<'r>(results: &'r ExtractionResults, name: &str) -> &'r HashMap<String, Value> {
+        results
+            .symbols
+            .iter()
+            .find(|s| s.name == name)
+            .and_then(|s| s.metadata.as_ref())
+            .unwrap_or_else(|| panic!("no metadata on {}", name))
+    }
+
+    #[test]
+    fn test_typed_exported_and_inferred_declarations() {
+        let content = "@export_range(0, 100, 0.5) var speed: float = 10.0 # m/s\n@onready\nvar sprite := $Sprite2D\nvar velocity := Vector2.ZERO\nconst MAX := 3\n";
+        let results = run(
+            content,
+            &[
+                ("speed", SymbolKind::Variable),
+                ("sprite", SymbolKind::Variable),
+                ("velocity", SymbolKind::Variable),
+                ("MAX", SymbolKind::Constant),
+            ],
+        );
+
+        let speed = metadata(&results, "speed");
+        assert_eq!(speed["dataType"], Value::from("float"));
+        assert_eq!(speed["exportHint"], Value::from("export_range"));
+        assert_eq!(speed["exportArguments"], json!(["0", "100", "0.5"]));
+        assert_eq!(
+            results.symbols[0].signature.as_deref(),
+            Some("@export_range(0, 100, 0.5) var speed: float = 10.0")
+        );
+
+        let sprite = metadata(&results, "sprite");
+        assert_eq!(sprite["onready"], Value::Bool(true));
+        assert_eq!(sprite["typeInferred"], Value::Bool(true));
+        assert!(!sprite.contains_key("dataType"));
+        assert_eq!(
+            metadata(&results, "velocity")["dataType"],
+            Value::from("Vector2")
+        );
+        assert_eq!(metadata(&results, "MAX")["dataType"], Value::from("int"));
+    }
+
+    #[test]
+    fn test_accessors_link_to_functions() {
+        let content = "var score: int = 0: set = _set_score, get = _get_score\nvar hp: int:\n\tset(value):\n\t\thp = value\n\nfunc _set_score(value):\n\tscore = value\n\nfunc _get_score():\n\treturn score\n";
+        let results = run(
+            content,
+            &[
+                ("score", SymbolKind::Variable),
+                ("hp", SymbolKind::Variable),
+                ("_set_score", SymbolKind::Function),
+                ("_get_score", SymbolKind::Function),
+            ],
+        );
+
+        let score = metadata(&results, "score");
+        assert_eq!(score["dataType"], Value::from("int"));
+        assert_eq!(score["setter"], Value::from("_set_score"));
+        assert_eq!(score["getter"], Value::from("_get_score"));
+        assert_eq!(results.relationships.len(), 2);
+        assert_eq!(results.relationships[0].kind, RelationshipKind::Uses);
+        assert_eq!(results.relationships[0].to_symbol_id, results.symbols[2].id);
+
+        let hp = metadata(&results, "hp");
+        assert_eq!(hp["dataType"], Value::from("int"));
+        assert_eq!(hp["inlineAccessors"], Value::Bool(true));
+    }
+}
@@ -0,0 +1,394 @@
+// Go goroutines and channel operations
+//
+// `go process(job)`, `ch <- msg`, and `<-done` come out of Julie as ordinary
+// calls and names, if at all. Julie identifiers carry no metadata, so this pass
+// records them on the enclosing function or method:
+//
+//   goroutines         `{ target, line }` per `go` statement; target is the
+//                      called name (`process`, `w.run`) or "func" for a literal
+//   spawnsGoroutines   true when there is at least one
+//   channelOperations  `{ channel, direction, line }` per send (`ch <- v`) or
+//                      receive (`<-ch`), with `select: true` for the
+//                      operation of a select case
+//
+// A Call identifier is added for a goroutine's target and a VariableRef (or
+// MemberAccess, for `s.jobs`) identifier for the channel when Julie has none
+// at that position. Operations inside select cases and goroutine literals
+// belong to the enclosing function like any other code in its body. Channel
+// types (`chan<- T`, `<-chan T`) are not operations.
+
+use super::GO;
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, innermost_symbol_at, set_metadata,
+    EnrichmentContext,
+};
+use julie_extractors::{ExtractionResults, IdentifierKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::json;
+use std::sync::LazyLock;
+
+/// `go ` at the start of a go statement
+static GO_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bgo\s+").unwrap());
+
+const KEYWORDS: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+pub(super) fn enrich_concurrency(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    if !ctx.content.contains("go ") && !ctx.content.contains("<-") {
+        return;
+    }
+    let code = code_mask(ctx.content, GO);
+    let content = ctx.content;
+
+    for found in GO_RE.find_iter(content) {
+        if !code[found.start()] || content[..found.start()].ends_with('.') {
+            continue;
+        }
+        let Some(function) = enclosing_function(&results.symbols, found.start()) else {
+            continue;
+        };
+        let start = found.end();
+        let target = if word_at(content, start) == "func" {
+            "func"
+        } else {
+            let end = path_end(content, start);
+            let target = &content[start..end];
+            if target.is_empty() || !content[end..].trim_start().starts_with(['(', '[']) {
+                continue;
+            }
+            let name_start = start + target.rfind('.').map_or(0, |dot| dot + 1);
+            add_identifier(
+                ctx,
+                results,
+                IdentifierKind::Call,
+                name_start,
+                end,
+                &function,
+            );
+            target
+        };
+
+        let symbol = symbol_mut(results, &function);
+        set_metadata(symbol, "spawnsGoroutines", true);
+        append_metadata(
+            symbol,
+            "goroutines",
+            json!({ "target": target, "line": ctx.line_of(found.start()) }),
+        );
+    }
+
+    for (arrow, _) in content.match_indices("<-") {
+        if !code[arrow] {
+            continue;
+        }
+        let Some((direction, start, end, channel)) = channel_operation(content, arrow) else {
+            continue;
+        };
+        let Some(function) = enclosing_function(&results.symbols, arrow) else {
+            continue;
+        };
+        if let Some(path_end) = end {
+            let path = &content[start..path_end];
+            let (kind, name_start) = match path.rfind('.') {
+                Some(dot) => (IdentifierKind::MemberAccess, start + dot + 1),
+                None => (IdentifierKind::VariableRef, start),
+            };
+            add_identifier(ctx, results, kind, name_start, path_end, &function);
+        }
+
+        let mut entry = json!({
+            "channel": channel,
+            "direction": direction,
+            "line": ctx.line_of(arrow),
+        });
+        if in_select_case(content, arrow) {
+            entry["select"] = json!(true);
+        }
+        append_metadata(symbol_mut(results, &function), "channelOperations", entry);
+    }
+}
+
+/// ("send" | "receive", channel start, end of a plain channel name/selector
+/// when the channel is one, channel text) for the `<-` at `arrow`
+fn channel_operation(
+    content: &str,
+    arrow: usize,
+) -> Option<(&'static str, usize, Option<usize>, String)> {
+    let after = arrow + 2;
+    let operand = after + (content[after..].len() - content[after..].trim_start().len());
+    // `<-chan T` and `c <-chan T` are types
+    if word_at(content, operand) == "chan" {
+        return None;
+    }
+
+    let before = content[..arrow].trim_end();
+    let (send_end, indexed) = match before.strip_suffix(']') {
+        Some(_) => (bracket_start(content, before.len() - 1)?, true),
+        None => (before.len(), false),
+    };
+    let send_start = path_start(content, send_end);
+    let sender = &content[send_start..send_end];
+    if sender == "chan" {
+        return None;
+    }
+    if !sender.is_empty() && !KEYWORDS.contains(&sender) {
+        let channel = content[send_start..before.len()].to_string();
+        let end = (!indexed).then_some(send_end);
+        return Some(("send", send_start, end, channel));
+    }
+    // A send to a call's result (`pick() <- v`) names no channel
+    if sender.is_empty() && before.ends_with(')') {
+        return None;
+    }
+
+    let end = path_end(content, operand);
+    if end == operand {
+        return None;
+    }
+    let call = content[end..].starts_with('(');
+    let text_end = if call {
+        find_matching_delimiter(content, end, GO)? + 1
+    } else {
+        end
+    };
+    let name_end = (!call && !content[end..].starts_with('[')).then_some(end);
+    Some((
+        "receive",
+        operand,
+        name_end,
+        content[operand..text_end].to_string(),
+    ))
+}
+
+/// Whether the operation is the communication of a select `case`
+fn in_select_case(content: &str, at: usize) -> bool {
+    let line_start = content[..at].rfind('\n').map_or(0, |i| i + 1);
+    let line = content[line_start..at].trim_start();
+    word_at(line, 0) == "case"
+}
+
+fn enclosing_function(symbols: &[Symbol], at: usize) -> Option<String> {
+    innermost_symbol_at(symbols, at, |s| {
+        matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+    })
+    .map(|s| s.id.clone())
+}
+
+fn symbol_mut<'a>(results: &'a mut ExtractionResults, id: &str) -> &'a mut Symbol {
+    results.symbols.iter_mut().find(|s| s.id == id).unwrap()
+}
+
+/// Add an identifier over `start..end` unless Julie already has one there
+fn add_identifier(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    kind: IdentifierKind,
+    start: usize,
+    end: usize,
+    containing: &str,
+) {
+    if results
+        .identifiers
+        .iter()
+        .any(|identifier| identifier.start_byte as usize == start)
+    {
+        return;
+    }
+    results.identifiers.push(ctx.create_identifier(
+        &ctx.content[start..end],
+        kind,
+        start,
+        end,
+        Some(containing.to_string()),
+    ));
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// The identifier starting at `at`, or ""
+fn word_at(content: &str, at: usize) -> &str {
+    let rest = &content.as_bytes()[at.min(content.len())..];
+    let len = rest.iter().take_while(|&&b| is_word_byte(b)).count();
+    &content[at..at + len]
+}
+
+/// End of the name or selector (`s.jobs`) starting at `start`
+fn path_end(content: &str, start: usize) -> usize {
+    let bytes = content.as_bytes();
+    if !bytes
+        .get(start)
+        .is_some_and(|&b| b.is_ascii_alphabetic() || b == b'_')
+    {
+        return start;
+    }
+    let mut end = start;
+    while end < bytes.len() && (is_word_byte(bytes[end]) || bytes[end] == b'.') {
+        end += 1;
+    }
+    content[start..end].trim_end_matches('.').len() + start
+}
+
+/// Start of the name or selector ending at `end`
+fn path_start(content: &str, end: usize) -> usize {
+    let bytes = content.as_bytes();
+    let mut start = end;
+    while start > 0 && (is_word_byte(bytes[start - 1]) || bytes[start - 1] == b'.') {
+        start -= 1;
+    }
+    while start < end && !(bytes[start].is_ascii_alphabetic() || bytes[start] == b'_') {
+        start += 1;
+    }
+    start
+}
+
+/// The `[` matching the `]` at `close`, on the same line
+fn bracket_start(content: &str, close: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0;
+    for index in (0..=close).rev() {
+        match bytes[index] {
+            b']' => depth += 1,
+            b'[' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            b'\n' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    const SOURCE: &str = r#"package main
+
+func (s *Server) Run(jobs <-chan Job, done chan<- bool) {
+	for job := range jobs {
+		go process(job)
+		go s.audit.record(job)
+	}
+	go func() {
+		s.results <- "started" // not <-ignored
+	}()
+	select {
+	case msg := <-s.inbox:
+		handle(msg)
+	case outs[0] <- 1:
+	case <-ctx.Done():
+		return
+	}
+	done <- true
+}
+
+func quiet() {}
+"#;
+
+    fn results(ctx: &EnrichmentContext) -> ExtractionResults {
+        let function = |name: &str, kind: SymbolKind, anchor: &str, end: &str| {
+            let start = SOURCE.find(anchor).unwrap();
+            let end = SOURCE.find(end).unwrap() + end.len();
+            ctx.create_symbol(name, kind, start, end, SymbolOptions::default())
+        };
+        test_results(vec![
+            function("Run", SymbolKind::Method, "func (s", "done <- true\n}"),
+            function("quiet", SymbolKind::Function, "func quiet", "{}"),
+        ])
+    }
+
+    #[test]
+    fn test_goroutines_and_channel_operations_are_recorded() {
+        let ctx = EnrichmentContext::new("server.go", "go", SOURCE, Path::new("."));
+        let mut results = results(&ctx);
+        enrich_concurrency(&ctx, &mut results);
+
+        let run = results.symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(run["spawnsGoroutines"], json!(true));
+        assert_eq!(
+            run["goroutines"],
+            json!([
+                { "target": "process", "line": 5 },
+                { "target": "s.audit.record", "line": 6 },
+                { "target": "func", "line": 8 },
+            ])
+        );
+        assert_eq!(
+            run["channelOperations"],
+            json!([
+                { "channel": "s.results", "direction": "send", "line": 9 },
+                { "channel": "s.inbox", "direction": "receive", "line": 12, "select": true },
+                { "channel": "outs[0]", "direction": "send", "line": 14, "select": true },
+                { "channel": "ctx.Done()", "direction": "receive", "line": 15, "select": true },
+                { "channel": "done", "direction": "send", "line": 18 },
+            ])
+        );
+        assert!(results.symbols[1].metadata.is_none());
+    }
+
+    #[test]
+    fn test_targets_and_channels_become_identifiers() {
+        let ctx = EnrichmentContext::new("server.go", "go", SOURCE, Path::new("."));
+        let mut results = results(&ctx);
+        let at = SOURCE.find("process(").unwrap();
+        results.identifiers.push(ctx.create_identifier(
+            "process",
+            IdentifierKind::Call,
+            at,
+            at + 7,
+            None,
+        ));
+        enrich_concurrency(&ctx, &mut results);
+
+        let identifiers: Vec<(&str, IdentifierKind)> = results
+            .identifiers
+            .iter()
+            .map(|i| (i.name.as_str(), i.kind.clone()))
+            .collect();
+        assert_eq!(
+            identifiers,
+            vec![
+                ("process", IdentifierKind::Call),
+                ("record", IdentifierKind::Call),
+                ("results", IdentifierKind::MemberAccess),
+                ("inbox", IdentifierKind::MemberAccess),
+                ("done", IdentifierKind::VariableRef),
+            ]
+        );
+        assert!(results.identifiers[1..]
+            .iter()
+            .all(|i| i.containing_symbol_id.as_ref() == Some(&results.symbols[0].id)));
+    }
+}
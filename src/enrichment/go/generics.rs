@@ -0,0 +1,479 @@
+// Go generics
+//
+// Go 1.18 type parameters (`func Map[T any, U any](s []T, f func(T) U) []U`,
+// `type Set[K comparable] struct`) are often missing from Julie's signatures,
+// and nothing links a type parameter to the constraint interface it names.
+// This pass reads the bracketed list after each generic function and type
+// name and:
+//
+// - rebuilds the signature from the declaration text when it lacks the list,
+//   so the list appears verbatim
+// - stores metadata.typeParams, one "Name constraint" entry per parameter
+//   with grouped names expanded (`[K, V any]` → ["K any", "V any"])
+// - adds a Uses relationship (metadata.constraint, metadata.typeParam) from
+//   the generic symbol to every interface declared in the same file that a
+//   constraint names, including union terms (`~int | Number`)
+//
+// Explicit instantiations at call sites (`Map[string, int](xs, f)`) are left
+// to Julie's Call identifiers. Identifiers have no metadata, so the type
+// arguments are appended to metadata.typeArguments ({callee, typeArgs, line})
+// on the enclosing symbol instead.
+
+use super::{type_base_name, GO};
+use crate::enrichment::base::symbol_index::SymbolIndex;
+use crate::enrichment::base::{
+    append_metadata, code_mask, collapse_whitespace, find_matching_delimiter, set_metadata,
+    split_top_level, EnrichmentContext,
+};
+use julie_extractors::{ExtractionResults, RelationshipKind, SymbolKind};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+/// `func Name[`; methods cannot declare their own type parameters
+static GENERIC_FUNC_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bfunc\s+([A-Za-z_]\w*)\s*\[").unwrap());
+
+/// `type Name[`
+static GENERIC_TYPE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\btype\s+([A-Za-z_]\w*)\s*\[").unwrap());
+
+/// `type (` opening a group of type declarations
+static TYPE_GROUP_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\btype\s*\(").unwrap());
+
+/// `Name[` at the start of a line inside a `type ( ... )` group
+static GROUPED_TYPE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*([A-Za-z_]\w*)\[").unwrap());
+
+/// `Name[` or `pkg.Name[` where an instantiation may start
+static INSTANTIATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b((?:[A-Za-z_]\w*\.)?[A-Za-z_]\w*)\[").unwrap());
+
+/// `T any`, `S ~[]E`: a parameter name followed by a constraint
+static TYPE_PARAM_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z_]\w*)\s+([A-Za-z_~*\[(].*)$").unwrap());
+
+/// Predeclared types, which mark `f[int](...)` as an instantiation rather than an index
+const PREDECLARED_TYPES: &[&str] = &[
+    "any",
+    "bool",
+    "byte",
+    "comparable",
+    "complex64",
+    "complex128",
+    "error",
+    "float32",
+    "float64",
+    "int",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "rune",
+    "string",
+    "uint",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "uintptr",
+];
+
+/// A generic declaration: name, type parameter list span, and parameters
+#[derive(Debug, PartialEq)]
+struct GenericDecl {
+    name: String,
+    is_func: bool,
+    /// Start of `func` / `type` (or of the name inside a type group)
+    start: usize,
+    /// The type parameter list, brackets included
+    list: std::ops::Range<usize>,
+    /// (name, constraint) per parameter
+    params: Vec<(String, String)>,
+}
+
+pub(super) fn enrich_generics(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let code = code_mask(ctx.content, GO);
+    let declarations = find_generic_declarations(ctx.content, &code);
+    let generic_funcs: HashSet<&str> = declarations
+        .iter()
+        .filter(|d| d.is_func)
+        .map(|d| d.name.as_str())
+        .collect();
+    let interfaces: HashMap<String, String> = results
+        .symbols
+        .iter()
+        .filter(|s| s.kind == SymbolKind::Interface)
+        .map(|s| (s.name.clone(), s.id.clone()))
+        .collect();
+
+    for declaration in &declarations {
+        let Some(symbol) = results.symbols.iter_mut().find(|s| {
+            s.name == declaration.name
+                && if declaration.is_func {
+                    s.kind == SymbolKind::Function
+                } else {
+                    matches!(
+                        s.kind,
+                        SymbolKind::Struct
+                            | SymbolKind::Interface
+                            | SymbolKind::Type
+                            | SymbolKind::Class
+                    )
+                }
+        }) else {
+            continue;
+        };
+
+        let type_params = &ctx.content[declaration.list.clone()];
+        if !symbol
+            .signature
+            .as_deref()
+            .is_some_and(|s| s.contains(type_params))
+        {
+            symbol.signature = Some(declaration_header(ctx.content, declaration));
+        }
+        set_metadata(
+            symbol,
+            "typeParams",
+            declaration
+                .params
+                .iter()
+                .map(|(name, constraint)| format!("{} {}", name, constraint))
+                .collect::<Vec<_>>(),
+        );
+
+        let from_id = symbol.id.clone();
+        let mut linked = HashSet::new();
+        for (param, constraint) in &declaration.params {
+            for term in constraint.split('|') {
+                let term = term.trim().trim_start_matches('~');
+                let Some(interface_id) = interfaces.get(type_base_name(term)) else {
+                    continue;
+                };
+                if *interface_id == from_id || !linked.insert(interface_id.clone()) {
+                    continue;
+                }
+                results.relationships.push(ctx.create_relationship(
+                    &from_id,
+                    interface_id,
+                    RelationshipKind::Uses,
+                    declaration.start,
+                    Some(HashMap::from([
+                        ("constraint".to_string(), Value::Bool(true)),
+                        ("typeParam".to_string(), Value::from(param.as_str())),
+                    ])),
+                ));
+            }
+        }
+    }
+
+    record_instantiations(ctx, &code, &generic_funcs, results);
+}
+
+/// Generic functions and types declared in the file
+fn find_generic_declarations(content: &str, code: &[bool]) -> Vec<GenericDecl> {
+    let mut declarations = Vec::new();
+    let mut candidates: Vec<(usize, usize, bool)> = Vec::new();
+    for captures in GENERIC_FUNC_RE.captures_iter(content) {
+        candidates.push((
+            captures.get(0).unwrap().start(),
+            captures.get(1).unwrap().start(),
+            true,
+        ));
+    }
+    for captures in GENERIC_TYPE_RE.captures_iter(content) {
+        candidates.push((
+            captures.get(0).unwrap().start(),
+            captures.get(1).unwrap().start(),
+            false,
+        ));
+    }
+    for group in TYPE_GROUP_RE.find_iter(content) {
+        if !code[group.start()] {
+            continue;
+        }
+        let open = group.end() - 1;
+        let Some(close) = find_matching_delimiter(content, open, GO) else {
+            continue;
+        };
+        for captures in GROUPED_TYPE_RE.captures_iter(&content[open + 1..close]) {
+            let name = open + 1 + captures.get(1).unwrap().start();
+            candidates.push((name, name, false));
+        }
+    }
+    candidates.sort();
+
+    for (start, name_start, is_func) in candidates {
+        if !code[start] {
+            continue;
+        }
+        let name_end = name_start
+            + content[name_start..]
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or(0);
+        let Some(open) = content[name_end..].find('[').map(|i| name_end + i) else {
+            continue;
+        };
+        let Some(close) = find_matching_delimiter(content, open, GO) else {
+            continue;
+        };
+        let Some(params) = parse_type_params(&content[open + 1..close]) else {
+            continue;
+        };
+        declarations.push(GenericDecl {
+            name: content[name_start..name_end].to_string(),
+            is_func,
+            start,
+            list: open..close + 1,
+            params,
+        });
+    }
+    declarations
+}
+
+/// (name, constraint) pairs of a type parameter list, or None when the
+/// brackets are an array length (`type Buf [N]byte`) rather than parameters
+fn parse_type_params(list: &str) -> Option<Vec<(String, String)>> {
+    let segments: Vec<&str> = split_top_level(list, b',', GO)
+        .into_iter()
+        .map(|(_, segment)| segment.trim())
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let last = TYPE_PARAM_RE.captures(segments.last()?)?;
+    let mut constraint = collapse_whitespace(&last[2]);
+
+    let mut params = vec![(String::new(), String::new()); segments.len()];
+    for (index, segment) in segments.iter().enumerate().rev() {
+        let name = match TYPE_PARAM_RE.captures(segment) {
+            Some(captures) => {
+                constraint = collapse_whitespace(&captures[2]);
+                captures[1].to_string()
+            }
+            None if segment.chars().all(|c| c.is_alphanumeric() || c == '_') => segment.to_string(),
+            None => return None,
+        };
+        params[index] = (name, constraint.clone());
+    }
+    Some(params)
+}
+
+/// Declaration text up to the body: `func Map[T any](s []T) []U` / `type Set[K comparable] struct`
+fn declaration_header(content: &str, declaration: &GenericDecl) -> String {
+    let rest = &content[declaration.start..];
+    let mut end = rest.len();
+    let mut depth = 0usize;
+    for (index, byte) in rest.bytes().enumerate() {
+        match byte {
+            b'(' | b'[' => depth += 1,
+            b')' | b']' => depth = depth.saturating_sub(1),
+            b'{' if depth == 0 => {
+                end = index;
+                break;
+            }
+            b'\n' if depth == 0 && !declaration.is_func => {
+                end = index;
+                break;
+            }
+            _ => {}
+        }
+    }
+    collapse_whitespace(&rest[..end])
+}
+
+/// Append metadata.typeArguments for explicit `Name[Args](` instantiations
+fn record_instantiations(
+    ctx: &EnrichmentContext,
+    code: &[bool],
+    generic_funcs: &HashSet<&str>,
+    results: &mut ExtractionResults,
+) {
+    let mut found = Vec::new();
+    for captures in INSTANTIATION_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        if !code[whole.start()] || follows_func_keyword(ctx.content, whole.start()) {
+            continue;
+        }
+        let callee = captures.get(1).unwrap().as_str();
+        let open = whole.end() - 1;
+        let Some(close) = find_matching_delimiter(ctx.content, open, GO) else {
+            continue;
+        };
+        if !ctx.content[close + 1..].trim_start().starts_with('(') {
+            continue;
+        }
+        let args: Vec<String> = split_top_level(&ctx.content[open + 1..close], b',', GO)
+            .into_iter()
+            .map(|(_, arg)| collapse_whitespace(arg))
+            .filter(|arg| !arg.is_empty())
+            .collect();
+        let known_generic = generic_funcs.contains(callee.rsplit('.').next().unwrap_or(callee));
+        if args.is_empty() || !(known_generic || args.iter().all(|a| looks_like_type(a))) {
+            continue;
+        }
+        found.push((whole.start(), callee.to_string(), args));
+    }
+    if found.is_empty() {
+        return;
+    }
+
+    let owners: Vec<Option<String>> = {
+        let index = SymbolIndex::new(&results.symbols);
+        found
+            .iter()
+            .map(|(start, ..)| index.innermost_at(*start, |_| true).map(|s| s.id.clone()))
+            .collect()
+    };
+    for ((start, callee, args), owner) in found.into_iter().zip(owners) {
+        let Some(symbol) = owner.and_then(|id| results.symbols.iter_mut().find(|s| s.id == id))
+        else {
+            continue;
+        };
+        append_metadata(
+            symbol,
+            "typeArguments",
+            json!({ "callee": callee, "typeArgs": args, "line": ctx.line_of(start) }),
+        );
+    }
+}
+
+/// Whether the token before `at` is the `func` keyword (a declaration, not a call)
+///
+/// Scans back over whitespace only, so each match costs its own gap.
+fn follows_func_keyword(content: &str, at: usize) -> bool {
+    let bytes = content.as_bytes();
+    let Some(last) = bytes[..at].iter().rposition(|b| !b.is_ascii_whitespace()) else {
+        return false;
+    };
+    let end = last + 1;
+    end >= 4
+        && &bytes[end - 4..end] == b"func"
+        && !(end > 4 && (bytes[end - 5].is_ascii_alphanumeric() || bytes[end - 5] == b'_'))
+}
+
+/// A predeclared type or a composite type literal: `int`, `[]byte`, `*T`, `map[string]int`
+fn looks_like_type(text: &str) -> bool {
+    PREDECLARED_TYPES.contains(&text)
+        || text.starts_with("[]")
+        || text.starts_with('*')
+        || text.starts_with("map[")
+        || text.starts_with("chan ")
+        || text.starts_with("func(")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    fn results_for(
+        ctx: &EnrichmentContext,
+        entries: &[(&str, &str, SymbolKind)],
+    ) -> ExtractionResults {
+        test_results(
+            entries
+                .iter()
+                .map(|(text, name, kind)| {
+                    let start = ctx.content.find(text).unwrap();
+                    ctx.create_symbol(
+                        name,
+                        kind.clone(),
+                        start,
+                        start + text.len(),
+                        SymbolOptions {
+                            signature: Some(format!("func {}", name)),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_type_params_signatures_and_constraint_links() {
+        let content = "package util\n\ntype Number interface {\n\t~int | ~float64\n}\n\nfunc Map[T any, U any](s []T, f func(T) U) []U {\n\treturn nil\n}\n\nfunc Sum[K comparable, V Number](m map[K]V) V {\n\tvar total V\n\treturn total\n}\n\ntype (\n\tPair[K, V any] struct{ Key K; Value V }\n\tBuffer [64]byte\n)\n";
+        let ctx = EnrichmentContext::new("util.go", "go", content, Path::new("."));
+        let mut results = results_for(
+            &ctx,
+            &[
+                ("type Number interface {\n\t~int | ~float64\n}", "Number", SymbolKind::Interface),
+                ("func Map[T any, U any](s []T, f func(T) U) []U {\n\treturn nil\n}", "Map", SymbolKind::Function),
+                ("func Sum[K comparable, V Number](m map[K]V) V {\n\tvar total V\n\treturn total\n}", "Sum", SymbolKind::Function),
+                ("Pair[K, V any] struct{ Key K; Value V }", "Pair", SymbolKind::Struct),
+                ("Buffer [64]byte", "Buffer", SymbolKind::Type),
+            ],
+        );
+
+        enrich_generics(&ctx, &mut results);
+
+        let symbols = &results.symbols;
+        assert_eq!(
+            symbols[1].signature.as_deref(),
+            Some("func Map[T any, U any](s []T, f func(T) U) []U")
+        );
+        assert_eq!(
+            symbols[1].metadata.as_ref().unwrap()["typeParams"],
+            json!(["T any", "U any"])
+        );
+        assert_eq!(
+            symbols[3].metadata.as_ref().unwrap()["typeParams"],
+            json!(["K any", "V any"])
+        );
+        assert!(symbols[4].metadata.is_none());
+
+        assert_eq!(results.relationships.len(), 1);
+        let uses = &results.relationships[0];
+        assert_eq!(
+            (uses.from_symbol_id.as_str(), uses.to_symbol_id.as_str()),
+            (symbols[2].id.as_str(), symbols[0].id.as_str())
+        );
+        assert_eq!(uses.kind, RelationshipKind::Uses);
+        assert_eq!(uses.metadata.as_ref().unwrap()["typeParam"], "V");
+    }
+
+    #[test]
+    fn test_explicit_instantiations_are_recorded_on_the_caller() {
+        let content = "func Map[T, U any](s []T, f func(T) U) []U { return nil }\n\nfunc run(xs []string, fns []func(int)) {\n\tMap[string, int](xs, strconv.Atoi)\n\tslices.Index[[]string](xs, \"a\")\n\tfns[0](1)\n}\n";
+        let ctx = EnrichmentContext::new("run.go", "go", content, Path::new("."));
+        let mut results = results_for(
+            &ctx,
+            &[
+                (
+                    "func Map[T, U any](s []T, f func(T) U) []U { return nil }",
+                    "Map",
+                    SymbolKind::Function,
+                ),
+                (
+                    &content[content.find("func run").unwrap()..content.len() - 1],
+                    "run",
+                    SymbolKind::Function,
+                ),
+            ],
+        );
+
+        enrich_generics(&ctx, &mut results);
+
+        assert_eq!(
+            results.symbols[1].metadata.as_ref().unwrap()["typeArguments"],
+            json!([
+                { "callee": "Map", "typeArgs": ["string", "int"], "line": 4 },
+                { "callee": "slices.Index", "typeArgs": ["[]string"], "line": 5 },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_func_keyword_is_a_whole_preceding_token() {
+        let text = "func Map[T any]() {}
+x := myfunc Map[int](1)
+y := Map[int](2)";
+        let at = |n: usize| text.match_indices("Map[").nth(n).unwrap().0;
+        assert!(follows_func_keyword(text, at(0)));
+        assert!(!follows_func_keyword(text, at(1)));
+        assert!(!follows_func_keyword(text, at(2)));
+        assert!(!follows_func_keyword("Map", 0));
+    }
+}
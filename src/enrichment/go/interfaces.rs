@@ -0,0 +1,455 @@
+// Go interface satisfaction
+//
+// Go types satisfy interfaces implicitly, so nothing in the source links
+// `type FileStore struct` to `type Store interface` even when both sit in the
+// same file. This pass builds each named type's method set from receiver
+// methods (plus methods promoted through embedded fields) and each interface's
+// required methods (expanding embedded interfaces recursively), then emits an
+// Implements relationship whenever the method set covers the interface by
+// name and parameter/result count. Parameter and result types are compared
+// as normalized strings; the relationship metadata records which methods
+// matched and whether every signature matched exactly, so consumers can audit
+// arity-only matches. The relationship's provenance is heuristic, and an
+// arity-only match scores lower than an exact one.
+
+use super::{type_base_name, type_list, GO};
+use crate::enrichment::base::{
+    code_mask, collapse_whitespace, find_matching_delimiter, EnrichmentContext,
+};
+use crate::enrichment::confidence::{set_provenance, Provenance, HEURISTIC};
+use julie_extractors::{ExtractionResults, RelationshipKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+/// `type Name interface {` / `type Name struct {`, also inside `type ( ... )` groups
+/// Confidence factor for a match on parameter and result counts alone
+const ARITY_ONLY_FACTOR: f32 = 0.75;
+
+static TYPE_DECL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)(?:\btype\s+|^[ \t]+)([A-Za-z_]\w*)(?:\[[^\]\n]*\])?\s+(interface|struct)\s*\{",
+    )
+    .unwrap()
+});
+
+/// `func (r *Recv[T]) Name(`
+static METHOD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bfunc\s*\(\s*(?:[A-Za-z_]\w*\s+)?(\*?)\s*([A-Za-z_]\w*)(?:\[[^\]]*\])?\s*\)\s*([A-Za-z_]\w*)\s*\(")
+        .unwrap()
+});
+
+/// A method signature reduced to its type lists
+#[derive(Debug, Clone, PartialEq)]
+struct MethodShape {
+    params: Vec<String>,
+    results: Vec<String>,
+    pointer_receiver: bool,
+}
+
+/// Interfaces and struct types declared in a file
+#[derive(Debug, Default)]
+struct TypeDeclarations {
+    /// Interface name → (own methods, embedded interface names)
+    interfaces: HashMap<String, (HashMap<String, MethodShape>, Vec<String>)>,
+    /// Interfaces with type-set elements (`~int | string`) can't be satisfied by structs
+    constraint_only: HashSet<String>,
+    /// Struct name → embedded field type names
+    structs: HashMap<String, Vec<String>>,
+}
+
+pub(super) fn link_interface_satisfaction(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+) {
+    let code = code_mask(ctx.content, GO);
+    let declarations = parse_type_declarations(ctx.content, &code);
+    if declarations.interfaces.is_empty() {
+        return;
+    }
+    let methods = parse_methods(ctx.content, &code);
+
+    let mut interface_names: Vec<&String> = declarations.interfaces.keys().collect();
+    interface_names.sort();
+    let mut type_names: Vec<&String> = methods.keys().chain(declarations.structs.keys()).collect();
+    type_names.sort();
+    type_names.dedup();
+
+    for interface_name in interface_names {
+        if declarations.constraint_only.contains(interface_name) {
+            continue;
+        }
+        let required = required_methods(&declarations, interface_name, &mut HashSet::new());
+        if required.is_empty() {
+            continue;
+        }
+        let Some(interface_id) = symbol_id(&results.symbols, interface_name, |k| {
+            *k == SymbolKind::Interface
+        }) else {
+            continue;
+        };
+
+        for type_name in &type_names {
+            let method_set =
+                promoted_method_set(&declarations, &methods, type_name, &mut HashSet::new());
+            let Some(matched) = match_methods(&required, &method_set) else {
+                continue;
+            };
+            let Some(type_id) = symbol_id(&results.symbols, type_name, |k| {
+                matches!(k, SymbolKind::Struct | SymbolKind::Class | SymbolKind::Type)
+            }) else {
+                continue;
+            };
+
+            let mut names: Vec<&String> = matched.keys().collect();
+            names.sort();
+            let exact = matched.values().all(|&exact| exact);
+            let pointer_receiver = names
+                .iter()
+                .any(|name| method_set[name.as_str()].pointer_receiver);
+
+            let mut metadata = HashMap::new();
+            metadata.insert(
+                "matchedMethods".to_string(),
+                Value::from(names.iter().map(|n| n.as_str()).collect::<Vec<_>>()),
+            );
+            metadata.insert("exactSignatures".to_string(), Value::Bool(exact));
+            metadata.insert("pointerReceiver".to_string(), Value::Bool(pointer_receiver));
+            metadata.insert("implicit".to_string(), Value::Bool(true));
+
+            let anchor = results
+                .symbols
+                .iter()
+                .find(|s| s.id == type_id)
+                .map_or(0, |s| s.start_byte as usize);
+            let mut relationship = ctx.create_relationship(
+                &type_id,
+                &interface_id,
+                RelationshipKind::Implements,
+                anchor,
+                Some(metadata),
+            );
+            let confidence = if exact {
+                HEURISTIC
+            } else {
+                HEURISTIC * ARITY_ONLY_FACTOR
+            };
+            set_provenance(&mut relationship, Provenance::Heuristic, confidence);
+            results.relationships.push(relationship);
+        }
+    }
+}
+
+fn symbol_id(
+    symbols: &[Symbol],
+    name: &str,
+    accept: impl Fn(&SymbolKind) -> bool,
+) -> Option<String> {
+    symbols
+        .iter()
+        .find(|s| s.name == name && accept(&s.kind))
+        .map(|s| s.id.clone())
+}
+
+/// Check every required method against the method set. Returns method name →
+/// whether the types matched exactly, or None when a method is missing or its
+/// arity differs.
+fn match_methods(
+    required: &HashMap<String, MethodShape>,
+    method_set: &HashMap<String, MethodShape>,
+) -> Option<HashMap<String, bool>> {
+    required
+        .iter()
+        .map(|(name, wanted)| {
+            let have = method_set.get(name)?;
+            if have.params.len() != wanted.params.len()
+                || have.results.len() != wanted.results.len()
+            {
+                return None;
+            }
+            let exact = have.params == wanted.params && have.results == wanted.results;
+            Some((name.clone(), exact))
+        })
+        .collect()
+}
+
+fn required_methods(
+    declarations: &TypeDeclarations,
+    interface: &str,
+    visiting: &mut HashSet<String>,
+) -> HashMap<String, MethodShape> {
+    let mut required = HashMap::new();
+    if !visiting.insert(interface.to_string()) {
+        return required;
+    }
+    if let Some((own, embedded)) = declarations.interfaces.get(interface) {
+        for parent in embedded {
+            required.extend(required_methods(declarations, parent, visiting));
+        }
+        required.extend(own.clone());
+    }
+    required
+}
+
+/// A type's own methods plus those promoted from embedded fields
+fn promoted_method_set(
+    declarations: &TypeDeclarations,
+    methods: &HashMap<String, HashMap<String, MethodShape>>,
+    type_name: &str,
+    visiting: &mut HashSet<String>,
+) -> HashMap<String, MethodShape> {
+    let mut method_set = HashMap::new();
+    if !visiting.insert(type_name.to_string()) {
+        return method_set;
+    }
+    if let Some(embedded) = declarations.structs.get(type_name) {
+        for field_type in embedded {
+            method_set.extend(promoted_method_set(
+                declarations,
+                methods,
+                field_type,
+                visiting,
+            ));
+        }
+    }
+    if let Some(own) = methods.get(type_name) {
+        method_set.extend(own.clone());
+    }
+    method_set
+}
+
+fn parse_type_declarations(content: &str, code: &[bool]) -> TypeDeclarations {
+    let mut declarations = TypeDeclarations::default();
+
+    for captures in TYPE_DECL_RE.captures_iter(content) {
+        let name = captures.get(1).unwrap();
+        if !code.get(name.start()).copied().unwrap_or(false) {
+            continue;
+        }
+        let open = captures.get(0).unwrap().end() - 1;
+        let Some(close) = find_matching_delimiter(content, open, GO) else {
+            continue;
+        };
+        let body = &content[open + 1..close];
+        let name = name.as_str().to_string();
+
+        if &captures[2] == "interface" {
+            let mut own = HashMap::new();
+            let mut embedded = Vec::new();
+            for element in body_elements(body) {
+                if let Some(paren) = element.find('(') {
+                    let method_name = element[..paren].trim();
+                    if let Some(shape) = parse_shape(&element[paren..], false) {
+                        own.insert(method_name.to_string(), shape);
+                    }
+                } else if element.contains('|') || element.starts_with('~') {
+                    declarations.constraint_only.insert(name.clone());
+                } else {
+                    embedded.push(type_base_name(element).to_string());
+                }
+            }
+            declarations.interfaces.insert(name, (own, embedded));
+        } else {
+            let embedded = body_elements(body)
+                .into_iter()
+                .map(|element| element.split('`').next().unwrap_or("").trim())
+                .filter(|element| !element.is_empty() && !element.contains(char::is_whitespace))
+                .map(|element| type_base_name(element).to_string())
+                .collect();
+            declarations.structs.insert(name, embedded);
+        }
+    }
+    declarations
+}
+
+/// Lines / `;`-separated elements of a struct or interface body, comments removed
+fn body_elements(body: &str) -> Vec<&str> {
+    let code = code_mask(body, GO);
+    let mut elements = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    for (index, byte) in body.bytes().enumerate().chain([(body.len(), b'\n')]) {
+        let is_code = code.get(index).copied().unwrap_or(true);
+        match byte {
+            b'(' | b'[' | b'{' if is_code => depth += 1,
+            b')' | b']' | b'}' if is_code => depth = depth.saturating_sub(1),
+            b'\n' | b';' if depth == 0 && (is_code || byte == b'\n') => {
+                let element = strip_comment(&body[start..index.min(body.len())]);
+                if !element.is_empty() {
+                    elements.push(element);
+                }
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    elements
+}
+
+fn strip_comment(element: &str) -> &str {
+    let element = element.split("//").next().unwrap_or(element);
+    element.split("/*").next().unwrap_or(element).trim()
+}
+
+fn parse_methods(content: &str, code: &[bool]) -> HashMap<String, HashMap<String, MethodShape>> {
+    let mut methods: HashMap<String, HashMap<String, MethodShape>> = HashMap::new();
+    for captures in METHOD_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        if !code.get(whole.start()).copied().unwrap_or(false) {
+            continue;
+        }
+        let pointer_receiver = !captures[1].is_empty();
+        let paren = whole.end() - 1;
+        let signature_end = content[paren..]
+            .find(['{', '\n'])
+            .map_or(content.len(), |i| paren + i);
+        if let Some(shape) = parse_shape(&content[paren..signature_end], pointer_receiver) {
+            methods
+                .entry(captures[2].to_string())
+                .or_default()
+                .insert(captures[3].to_string(), shape);
+        }
+    }
+    methods
+}
+
+/// Parse `(params) results` into normalized type lists
+fn parse_shape(signature: &str, pointer_receiver: bool) -> Option<MethodShape> {
+    let close = find_matching_delimiter(signature, 0, GO)?;
+    let params = type_list(&signature[1..close]);
+    let rest = signature[close + 1..].trim();
+    let results = if rest.starts_with('(') {
+        let results_close = find_matching_delimiter(rest, 0, GO)?;
+        type_list(&rest[1..results_close])
+    } else if rest.is_empty() {
+        Vec::new()
+    } else {
+        vec![collapse_whitespace(rest)]
+    };
+    Some(MethodShape {
+        params,
+        results,
+        pointer_receiver,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    fn link(content: &str, declared: &[(&str, SymbolKind)]) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("store.go", "go", content, Path::new("."));
+        let symbols = declared
+            .iter()
+            .map(|(name, kind)| {
+                let start = content.find(&format!("{} ", name)).unwrap();
+                ctx.create_symbol(
+                    name,
+                    kind.clone(),
+                    start,
+                    start + name.len(),
+                    SymbolOptions::default(),
+                )
+            })
+            .collect();
+        let mut results = test_results(symbols);
+        link_interface_satisfaction(&ctx, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_struct_satisfying_embedded_interfaces() {
+        let content = r#"package store
+
+type Reader interface {
+	Get(key string) ([]byte, error)
+}
+
+type Store interface {
+	Reader
+	Put(key string, value []byte) error // write
+}
+
+type FileStore struct {
+	dir string
+}
+
+func (s *FileStore) Get(k string) ([]byte, error) { return nil, nil }
+func (s *FileStore) Put(k string, v []byte) error { return nil }
+
+type ReadOnly struct{}
+
+func (ReadOnly) Get(key string) ([]byte, error) { return nil, nil }
+"#;
+        let results = link(
+            content,
+            &[
+                ("Reader", SymbolKind::Interface),
+                ("Store", SymbolKind::Interface),
+                ("FileStore", SymbolKind::Struct),
+                ("ReadOnly", SymbolKind::Struct),
+            ],
+        );
+        let pairs: Vec<(&str, &str)> = results
+            .relationships
+            .iter()
+            .map(|r| {
+                let name = |id: &str| {
+                    results
+                        .symbols
+                        .iter()
+                        .find(|s| s.id == id)
+                        .unwrap()
+                        .name
+                        .as_str()
+                };
+                (name(&r.from_symbol_id), name(&r.to_symbol_id))
+            })
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("FileStore", "Reader"),
+                ("ReadOnly", "Reader"),
+                ("FileStore", "Store")
+            ]
+        );
+
+        let store = &results.relationships[2];
+        assert_eq!(store.kind, RelationshipKind::Implements);
+        let metadata = store.metadata.as_ref().unwrap();
+        assert_eq!(metadata["matchedMethods"], Value::from(vec!["Get", "Put"]));
+        assert_eq!(metadata["exactSignatures"], Value::Bool(true));
+        assert_eq!(metadata["pointerReceiver"], Value::Bool(true));
+    }
+
+    #[test]
+    fn test_arity_mismatch_and_type_sets_do_not_match() {
+        let content = r#"package num
+
+type Number interface {
+	~int | ~float64
+}
+
+type Sizer interface {
+	Size() int
+}
+
+type Blob struct{}
+
+func (b Blob) Size(unit string) int { return 0 }
+"#;
+        let results = link(
+            content,
+            &[
+                ("Number", SymbolKind::Interface),
+                ("Sizer", SymbolKind::Interface),
+                ("Blob", SymbolKind::Struct),
+            ],
+        );
+        assert!(results.relationships.is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+// Go enrichment passes
+//
+// Julie's Go extractor handles types, functions, and receiver methods; these
+// passes add implicit interface satisfaction between types in the same file,
+// structured struct field tags, type parameters with their constraints, and
+// the goroutines and channel operations of each function.
+
+mod concurrency;
+mod generics;
+mod interfaces;
+mod tags;
+
+use super::base::{collapse_whitespace, split_top_level, EnrichmentContext, Lexicon};
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 4;
+
+/// Go strings: "interpreted", `raw`, and 'r'unes; // and /* */ comments
+pub(super) const GO: Lexicon = Lexicon {
+    quotes: b"\"`'",
+    escape: Some(b'\\'),
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    interfaces::link_interface_satisfaction(ctx, results);
+    tags::enrich_struct_tags(ctx, &mut results.symbols);
+    generics::enrich_generics(ctx, results);
+    concurrency::enrich_concurrency(ctx, results);
+}
+
+/// `*pkg.Name[T]` → `Name`
+fn type_base_name(text: &str) -> &str {
+    let text = text.trim().trim_start_matches('*');
+    let text = text.split('[').next().unwrap_or(text);
+    text.rsplit('.').next().unwrap_or(text).trim()
+}
+
+/// Types of a Go parameter list, expanding grouped names: `a, b int, s string`
+/// → [int, int, string]; unnamed lists are taken as types directly.
+fn type_list(list: &str) -> Vec<String> {
+    let segments: Vec<&str> = split_top_level(list, b',', GO)
+        .into_iter()
+        .map(|(_, segment)| segment.trim())
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    let named = segments.iter().any(|segment| {
+        let first = segment.split_whitespace().next().unwrap_or("");
+        segment.contains(char::is_whitespace)
+            && !first.starts_with(['*', '[', '.'])
+            && !matches!(first, "func" | "chan" | "map" | "struct" | "interface")
+    });
+    if !named {
+        return segments.into_iter().map(collapse_whitespace).collect();
+    }
+
+    let mut types = vec![String::new(); segments.len()];
+    let mut carried = String::new();
+    for (index, segment) in segments.iter().enumerate().rev() {
+        if let Some((_, type_text)) = segment.split_once(char::is_whitespace) {
+            carried = collapse_whitespace(type_text);
+        }
+        types[index] = carried.clone();
+    }
+    types
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_list_expands_grouped_names() {
+        assert_eq!(
+            type_list("a, b int, s string"),
+            vec!["int", "int", "string"]
+        );
+        assert_eq!(type_list("int, *Node"), vec!["int", "*Node"]);
+        assert_eq!(
+            type_list("ctx context.Context, fn func(int) error"),
+            vec!["context.Context", "func(int) error"]
+        );
+    }
+}
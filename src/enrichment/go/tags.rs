@@ -0,0 +1,178 @@
+// Go struct tags
+//
+// Field tags like `json:"user_id,omitempty" db:"user_id"` map Go structs to
+// JSON payloads and SQL columns. This pass reads the raw tag of every field
+// symbol, splits it into key/value pairs following reflect.StructTag rules, and
+// stores them as metadata.tags ({"json": "user_id,omitempty", "db": "user_id"});
+// options after the comma are kept verbatim. The raw tag is appended to the
+// field's signature so it is searchable.
+
+use crate::enrichment::base::{set_metadata, EnrichmentContext};
+use julie_extractors::{Symbol, SymbolKind};
+use serde_json::{Map, Value};
+
+pub(super) fn enrich_struct_tags(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    for symbol in symbols
+        .iter_mut()
+        .filter(|s| matches!(s.kind, SymbolKind::Field | SymbolKind::Property))
+    {
+        let line_start = ctx.content[..symbol.start_byte as usize]
+            .rfind('\n')
+            .map_or(0, |i| i + 1);
+        let line_end = ctx.content[line_start..]
+            .find('\n')
+            .map_or(ctx.content.len(), |i| line_start + i);
+        let line = &ctx.content[line_start..line_end];
+
+        // The tag is the first raw string literal after the field name
+        let Some(open) = find_tag_open(line, symbol.start_byte as usize - line_start) else {
+            continue;
+        };
+        let Some(close) = line[open + 1..].find('`').map(|i| open + 1 + i) else {
+            continue;
+        };
+        let tag = &line[open + 1..close];
+        let pairs = parse_struct_tag(tag);
+        if pairs.is_empty() {
+            continue;
+        }
+
+        let declaration = line[..open].trim();
+        let base = symbol
+            .signature
+            .clone()
+            .filter(|signature| !signature.contains('`'))
+            .unwrap_or_else(|| declaration.to_string());
+        symbol.signature = Some(format!("{} `{}`", base, tag));
+
+        let tags: Map<String, Value> = pairs
+            .into_iter()
+            .map(|(key, value)| (key, Value::from(value)))
+            .collect();
+        set_metadata(symbol, "tags", Value::Object(tags));
+    }
+}
+
+/// Offset of the backtick opening a field tag, stopping at a line comment
+fn find_tag_open(line: &str, from: usize) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut pos = from;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'`' => return Some(pos),
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => return None,
+            b'"' => {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos] != b'"' {
+                    pos += if bytes[pos] == b'\\' { 2 } else { 1 };
+                }
+            }
+            _ => {}
+        }
+        pos += 1;
+    }
+    None
+}
+
+/// Split a struct tag into (key, value) pairs: `json:"id,omitempty" db:"id"`
+fn parse_struct_tag(tag: &str) -> Vec<(String, String)> {
+    let bytes = tag.as_bytes();
+    let mut pairs = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        while pos < bytes.len() && bytes[pos] == b' ' {
+            pos += 1;
+        }
+        let key_start = pos;
+        while pos < bytes.len() && bytes[pos] > b' ' && bytes[pos] != b':' && bytes[pos] != b'"' {
+            pos += 1;
+        }
+        if pos == key_start || bytes.get(pos) != Some(&b':') || bytes.get(pos + 1) != Some(&b'"') {
+            break;
+        }
+        let key = tag[key_start..pos].to_string();
+        pos += 2;
+
+        let mut value = String::new();
+        let mut closed = false;
+        while pos < bytes.len() {
+            match bytes[pos] {
+                b'\\' if pos + 1 < bytes.len() => {
+                    value.push(bytes[pos + 1] as char);
+                    pos += 2;
+                }
+                b'"' => {
+                    closed = true;
+                    pos += 1;
+                    break;
+                }
+                _ => {
+                    let len = tag[pos..].chars().next().map_or(1, char::len_utf8);
+                    value.push_str(&tag[pos..pos + len]);
+                    pos += len;
+                }
+            }
+        }
+        if !closed {
+            break;
+        }
+        pairs.push((key, value));
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    #[test]
+    fn test_parse_struct_tag_keeps_options() {
+        assert_eq!(
+            parse_struct_tag(r#"json:"user_id,omitempty" db:"user_id" validate:"required""#),
+            vec![
+                ("json".to_string(), "user_id,omitempty".to_string()),
+                ("db".to_string(), "user_id".to_string()),
+                ("validate".to_string(), "required".to_string()),
+            ]
+        );
+        assert!(parse_struct_tag("not a tag").is_empty());
+    }
+
+    #[test]
+    fn test_field_symbols_get_tags_and_signature() {
+        let content = "type User struct {\n\tID    int64  `json:\"id\" db:\"user_id\"`\n\tEmail string // `json:\"nope\"`\n}\n";
+        let ctx = EnrichmentContext::new("user.go", "go", content, Path::new("."));
+        let field = |name: &str, signature: &str| {
+            let start = content.find(&format!("\t{}", name)).unwrap() + 1;
+            ctx.create_symbol(
+                name,
+                SymbolKind::Field,
+                start,
+                start + name.len(),
+                SymbolOptions {
+                    signature: Some(signature.to_string()),
+                    ..Default::default()
+                },
+            )
+        };
+        let mut symbols = vec![field("ID", "ID int64"), field("Email", "Email string")];
+        enrich_struct_tags(&ctx, &mut symbols);
+
+        assert_eq!(
+            symbols[0].signature.as_deref(),
+            Some("ID int64 `json:\"id\" db:\"user_id\"`")
+        );
+        let tags = &symbols[0].metadata.as_ref().unwrap()["tags"];
+        assert_eq!(tags["json"], Value::from("id"));
+        assert_eq!(tags["db"], Value::from("user_id"));
+
+        assert!(
+            symbols[1].metadata.is_none(),
+            "tags in comments are ignored"
+        );
+        assert_eq!(symbols[1].signature.as_deref(), Some("Email string"));
+    }
+}
@@ -0,0 +1,374 @@
+// HTML embedded CSS and JavaScript
+//
+// Code lives in an HTML file in three places: `<script>` and `<style>`
+// elements, `style="..."` attributes, and `on*="..."` event handler attributes.
+//
+// Julie extracts script and style elements with the JavaScript and CSS
+// extractors, but what they return keeps positions relative to the element's
+// text: a function on the third line of a script that opens on line 40 of the
+// page reports line 3. A symbol or identifier whose reported range does not
+// contain its name, but does once offset to the start of an element's content,
+// is re-based there (bytes, lines, and columns), and relationships from a
+// moved symbol move their line_number with it.
+//
+// Inline styles go through a CSS declaration parser: each property becomes a
+// MemberAccess identifier (language "css") contained by the element, which
+// records metadata.inlineStyle ({"color": "red"}). Event handlers are
+// JavaScript expressions: each call in one becomes a Call identifier
+// (language "javascript") contained by the element, which records
+// metadata.eventHandlers ({"onclick": "save(event)"}), and a call to a function
+// one of the page's scripts defines also becomes a Calls relationship, a
+// name-match since the handler may mean a global of the same name.
+
+use super::{attribute_spans, non_markup_ranges};
+use crate::enrichment::base::{
+    code_mask, set_metadata, split_top_level, trim_with_offset, EnrichmentContext, Lexicon,
+};
+use crate::enrichment::confidence::{set_provenance, Provenance, NAME_MATCH};
+use crate::enrichment::javascript::JS;
+use julie_extractors::{ExtractionResults, IdentifierKind, RelationshipKind, SymbolKind};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Content of a script or style element
+static BLOCK_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<script\b[^>]*>(.*?)</script\s*>|<style\b[^>]*>(.*?)</style\s*>").unwrap()
+});
+
+/// Any start tag
+static START_TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<([A-Za-z][\w:.-]*)(\s[^>]*)?>").unwrap());
+
+/// `name(` in a handler, possibly the last part of `a.b.name(`
+static CALL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"([A-Za-z_$][\w$]*)\s*\(").unwrap());
+
+/// Keywords followed by a parenthesis that are not calls
+const NOT_CALLS: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "function", "return", "typeof", "void", "delete",
+    "await", "with",
+];
+
+pub(super) fn rebase_embedded(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let blocks: Vec<(usize, usize)> = BLOCK_RE
+        .captures_iter(ctx.content)
+        .filter_map(|c| c.get(1).or(c.get(2)))
+        .filter(|m| !m.as_str().trim().is_empty())
+        .map(|m| (m.start(), m.end()))
+        .collect();
+    if blocks.is_empty() {
+        return;
+    }
+
+    // Moved symbol id → lines its relationships move down
+    let mut moved: HashMap<String, u32> = HashMap::new();
+    for symbol in &mut results.symbols {
+        let (start, end) = (symbol.start_byte as usize, symbol.end_byte as usize);
+        let Some(offset) = relative_block(ctx, &blocks, &symbol.name, start, end) else {
+            continue;
+        };
+        (symbol.start_line, symbol.start_column) = ctx.position(offset + start);
+        (symbol.end_line, symbol.end_column) = ctx.position(offset + end);
+        symbol.start_byte = (offset + start) as u32;
+        symbol.end_byte = (offset + end) as u32;
+        moved.insert(symbol.id.clone(), ctx.line_of(offset) - 1);
+    }
+    for identifier in &mut results.identifiers {
+        let (start, end) = (identifier.start_byte as usize, identifier.end_byte as usize);
+        let Some(offset) = relative_block(ctx, &blocks, &identifier.name, start, end) else {
+            continue;
+        };
+        (identifier.start_line, identifier.start_column) = ctx.position(offset + start);
+        (identifier.end_line, identifier.end_column) = ctx.position(offset + end);
+        identifier.start_byte = (offset + start) as u32;
+        identifier.end_byte = (offset + end) as u32;
+    }
+    for relationship in &mut results.relationships {
+        if let Some(lines) = moved.get(&relationship.from_symbol_id) {
+            relationship.line_number += lines;
+        }
+    }
+}
+
+/// Start of the element content an item named `name` reported `start..end`
+/// relative to, None when the range already holds the name in the file
+fn relative_block(
+    ctx: &EnrichmentContext,
+    blocks: &[(usize, usize)],
+    name: &str,
+    start: usize,
+    end: usize,
+) -> Option<usize> {
+    if name.is_empty() || holds_word(ctx.text(start, end), name) {
+        return None;
+    }
+    blocks
+        .iter()
+        .find(|&&(block_start, block_end)| {
+            block_start + end <= block_end
+                && holds_word(ctx.text(block_start + start, block_start + end), name)
+        })
+        .map(|&(block_start, _)| block_start)
+}
+
+/// Whether `name` occurs in `text` as a whole word (`greet` is not in `greeting`)
+fn holds_word(text: &str, name: &str) -> bool {
+    let word = |c: char| c.is_alphanumeric() || matches!(c, '_' | '$' | '-');
+    text.match_indices(name).any(|(at, _)| {
+        !text[..at].chars().next_back().is_some_and(word)
+            && !text[at + name.len()..].chars().next().is_some_and(word)
+    })
+}
+
+pub(super) fn extract_inline_code(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let excluded = non_markup_ranges(ctx.content);
+    let in_markup = |byte: usize| !excluded.iter().any(|&(s, e)| s <= byte && byte < e);
+
+    for captures in START_TAG_RE.captures_iter(ctx.content) {
+        let tag = captures.get(0).unwrap();
+        let Some(attributes) = captures.get(2) else {
+            continue;
+        };
+        if !in_markup(tag.start()) {
+            continue;
+        }
+        let element = results
+            .symbols
+            .iter()
+            .position(|s| s.start_byte as usize == tag.start());
+        let element_id = element.map(|i| results.symbols[i].id.clone());
+
+        let mut styles = Map::new();
+        let mut handlers = Map::new();
+        for attribute in attribute_spans(attributes.as_str()) {
+            let value_start = attributes.start() + attribute.value_start;
+            if attribute.name == "style" {
+                for (start, property, value) in declarations(attribute.value, value_start) {
+                    let mut identifier = ctx.create_identifier(
+                        property,
+                        IdentifierKind::MemberAccess,
+                        start,
+                        start + property.len(),
+                        element_id.clone(),
+                    );
+                    identifier.language = "css".to_string();
+                    results.identifiers.push(identifier);
+                    styles.insert(property.to_ascii_lowercase(), Value::from(value));
+                }
+            } else if attribute.name.len() > 2
+                && attribute.name.starts_with("on")
+                && !attribute.value.trim().is_empty()
+            {
+                for (start, callee) in calls(attribute.value, value_start) {
+                    let mut identifier = ctx.create_identifier(
+                        callee,
+                        IdentifierKind::Call,
+                        start,
+                        start + callee.len(),
+                        element_id.clone(),
+                    );
+                    identifier.language = "javascript".to_string();
+                    results.identifiers.push(identifier);
+
+                    let function = results
+                        .symbols
+                        .iter()
+                        .find(|s| s.name == callee && matches!(s.kind, SymbolKind::Function));
+                    if let (Some(from), Some(function)) = (&element_id, function) {
+                        let mut relationship = ctx.create_relationship(
+                            from,
+                            &function.id,
+                            RelationshipKind::Calls,
+                            start,
+                            None,
+                        );
+                        set_provenance(&mut relationship, Provenance::NameMatch, NAME_MATCH);
+                        results.relationships.push(relationship);
+                    }
+                }
+                handlers.insert(attribute.name, Value::from(attribute.value.trim()));
+            }
+        }
+
+        if let Some(element) = element.map(|i| &mut results.symbols[i]) {
+            if !styles.is_empty() {
+                set_metadata(element, "inlineStyle", Value::Object(styles));
+            }
+            if !handlers.is_empty() {
+                set_metadata(element, "eventHandlers", Value::Object(handlers));
+            }
+        }
+    }
+}
+
+/// `(offset, property, value)` of each `property: value` declaration in a
+/// style attribute starting at `offset`
+fn declarations(style: &str, offset: usize) -> Vec<(usize, &str, &str)> {
+    split_top_level(style, b';', Lexicon::CSS)
+        .into_iter()
+        .filter_map(|(at, declaration)| {
+            let (start, declaration) = trim_with_offset(offset + at, declaration);
+            let (property, value) = declaration.split_once(':')?;
+            let property = property.trim_end();
+            let valid = !property.is_empty()
+                && property
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+            valid.then(|| (start, property, value.trim()))
+        })
+        .collect()
+}
+
+/// `(offset, callee)` of each call in an event handler starting at `offset`
+fn calls(handler: &str, offset: usize) -> Vec<(usize, &str)> {
+    let code = code_mask(handler, JS);
+    CALL_RE
+        .captures_iter(handler)
+        .map(|c| c.get(1).unwrap())
+        .filter(|callee| code[callee.start()] && !NOT_CALLS.contains(&callee.as_str()))
+        .filter(|callee| {
+            // Not the name of a function expression (`function name(`)
+            !handler[..callee.start()].trim_end().ends_with("function")
+        })
+        .map(|callee| (offset + callee.start(), callee.as_str()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    fn results(
+        symbols: Vec<julie_extractors::Symbol>,
+        identifiers: Vec<julie_extractors::Identifier>,
+    ) -> ExtractionResults {
+        ExtractionResults {
+            identifiers,
+            ..test_results(symbols)
+        }
+    }
+
+    #[test]
+    fn test_script_symbols_are_rebased_onto_the_page() {
+        let script = "\n  const greeting = 'hi';\n  function greet(name) {\n    alert(greeting + name);\n  }\n";
+        let content = format!(
+            "<!DOCTYPE html>\n<html>\n<body>\n  <h1>Title</h1>\n  <script>{}</script>\n</body>\n</html>\n",
+            script
+        );
+        let ctx = EnrichmentContext::new("page.html", "html", &content, Path::new("."));
+        // What the JavaScript extractor reports for the script's own text
+        let script_ctx = EnrichmentContext::new("page.html", "html", script, Path::new("."));
+        let start = script.find("function greet").unwrap();
+        let greet = script_ctx.create_symbol(
+            "greet",
+            SymbolKind::Function,
+            start,
+            script.rfind('}').unwrap() + 1,
+            SymbolOptions::default(),
+        );
+        let at = script.find("alert").unwrap();
+        let alert = script_ctx.create_identifier("alert", IdentifierKind::Call, at, at + 5, None);
+        let h1_start = content.find("<h1>").unwrap();
+        let h1 = ctx.create_symbol(
+            "h1",
+            SymbolKind::Class,
+            h1_start,
+            h1_start + 14,
+            SymbolOptions::default(),
+        );
+        let mut results = results(vec![h1.clone(), greet.clone()], vec![alert]);
+        results.relationships.push(script_ctx.create_relationship(
+            &greet.id,
+            "alert",
+            RelationshipKind::Calls,
+            at,
+            None,
+        ));
+        rebase_embedded(&ctx, &mut results);
+
+        let greet = &results.symbols[1];
+        assert_eq!(
+            greet.start_byte as usize,
+            content.find("function greet").unwrap()
+        );
+        assert_eq!((greet.start_line, greet.start_column), (7, 2));
+        assert_eq!((greet.end_line, greet.end_column), (9, 3));
+        let alert = &results.identifiers[0];
+        assert_eq!((alert.start_line, alert.start_column), (8, 4));
+        assert_eq!(
+            ctx.text(alert.start_byte as usize, alert.end_byte as usize),
+            "alert"
+        );
+        assert_eq!(results.relationships[0].line_number, 8);
+        // Already positioned in the page
+        assert_eq!(results.symbols[0].start_line, h1.start_line);
+    }
+
+    #[test]
+    fn test_inline_styles_and_handlers_are_attributed_to_the_element() {
+        let content = "<script>function save(e) {}</script>\n<button id=\"save\" style=\"color: red; display:flex;\" onclick=\"save(event); return false\">Save</button>\n<div onload='log(\"ready\")'></div>\n";
+        let ctx = EnrichmentContext::new("page.html", "html", content, Path::new("."));
+        let save_start = content.find("function").unwrap();
+        let save = ctx.create_symbol(
+            "save",
+            SymbolKind::Function,
+            save_start,
+            save_start + 19,
+            SymbolOptions::default(),
+        );
+        let button_start = content.find("<button").unwrap();
+        let button = ctx.create_symbol(
+            "button",
+            SymbolKind::Class,
+            button_start,
+            content.find("</button>").unwrap() + 9,
+            SymbolOptions::default(),
+        );
+        let (save_id, button_id) = (save.id.clone(), button.id.clone());
+        let mut results = results(vec![save, button], Vec::new());
+        extract_inline_code(&ctx, &mut results);
+
+        let found: Vec<(&str, IdentifierKind, &str, u32, &str)> = results
+            .identifiers
+            .iter()
+            .map(|i| {
+                (
+                    i.name.as_str(),
+                    i.kind.clone(),
+                    i.language.as_str(),
+                    i.start_line,
+                    ctx.text(i.start_byte as usize, i.end_byte as usize),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("color", IdentifierKind::MemberAccess, "css", 2, "color"),
+                ("display", IdentifierKind::MemberAccess, "css", 2, "display"),
+                ("save", IdentifierKind::Call, "javascript", 2, "save"),
+                ("log", IdentifierKind::Call, "javascript", 3, "log"),
+            ]
+        );
+        assert!(results.identifiers[..3]
+            .iter()
+            .all(|i| i.containing_symbol_id.as_ref() == Some(&button_id)));
+        assert_eq!(results.identifiers[3].containing_symbol_id, None);
+
+        let metadata = results.symbols[1].metadata.as_ref().unwrap();
+        assert_eq!(
+            metadata["inlineStyle"],
+            serde_json::json!({"color": "red", "display": "flex"})
+        );
+        assert_eq!(
+            metadata["eventHandlers"],
+            serde_json::json!({"onclick": "save(event); return false"})
+        );
+        assert_eq!(results.relationships.len(), 1);
+        assert_eq!(results.relationships[0].from_symbol_id, button_id);
+        assert_eq!(results.relationships[0].to_symbol_id, save_id);
+    }
+}
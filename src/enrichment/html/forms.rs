@@ -0,0 +1,259 @@
+// HTML form extraction
+//
+// A form's field names are the parameter names its handler receives, so
+// forms and fields get their own symbols instead of generic elements:
+//
+//     <form action="/api/users" method="post">      Class "form" (or its name/id)
+//       <input name="email" type="email" required>   Field "email", child of the form
+//
+// The form records action, method (uppercased, GET when absent), and enctype
+// in metadata. Each input, select, textarea, and button becomes a Field named
+// by its name attribute, falling back to its id; fields with neither are kept
+// under their tag name and flagged metadata.unnamed. Fields record type
+// (inputs default to text, buttons to submit), required, value, and id.
+// A field's form is the one enclosing it, or the one its `form="id"`
+// attribute names. Julie's element symbol for a tag, when it has one, is
+// specialized in place rather than duplicated.
+
+use super::{non_markup_ranges, parse_attributes};
+use crate::enrichment::base::{EnrichmentContext, SymbolOptions};
+use julie_extractors::{ExtractionResults, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Start tag of a form or form field
+static TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)<(form|input|select|textarea|button)\b([^>]*)>").unwrap());
+
+/// `</form>`
+static FORM_END_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)</form\s*>").unwrap());
+
+/// A form's symbol, element id, and byte range (start tag to `</form>`)
+struct Form {
+    symbol_id: String,
+    element_id: Option<String>,
+    range: (usize, usize),
+}
+
+pub(super) fn extract_forms(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let excluded = non_markup_ranges(ctx.content);
+    let in_markup = |byte: usize| !excluded.iter().any(|&(s, e)| s <= byte && byte < e);
+    let mut forms: Vec<Form> = Vec::new();
+
+    for captures in TAG_RE.captures_iter(ctx.content) {
+        let tag = captures.get(0).unwrap();
+        if !in_markup(tag.start()) {
+            continue;
+        }
+        let element = captures[1].to_ascii_lowercase();
+        let attributes: HashMap<String, String> = parse_attributes(&captures[2])
+            .into_iter()
+            .rev() // the first occurrence of a repeated attribute wins
+            .collect();
+        let attribute = |name: &str| attributes.get(name).filter(|v| !v.is_empty());
+
+        let mut metadata = HashMap::new();
+        metadata.insert("htmlElement".to_string(), Value::from(element.as_str()));
+        if let Some(id) = attribute("id") {
+            metadata.insert("id".to_string(), Value::from(id.as_str()));
+        }
+
+        if element == "form" {
+            if let Some(action) = attribute("action") {
+                metadata.insert("action".to_string(), Value::from(action.as_str()));
+            }
+            let method = attribute("method").map_or("GET".to_string(), |m| m.to_uppercase());
+            metadata.insert("method".to_string(), Value::from(method));
+            if let Some(enctype) = attribute("enctype") {
+                metadata.insert("enctype".to_string(), Value::from(enctype.as_str()));
+            }
+
+            let name = attribute("name")
+                .or(attribute("id"))
+                .map_or("form", |n| n.as_str());
+            let end = FORM_END_RE
+                .find_iter(&ctx.content[tag.end()..])
+                .map(|m| tag.end() + m.end())
+                .find(|&end| in_markup(end - 1))
+                .unwrap_or(ctx.content.len());
+            let symbol_id = upsert(
+                ctx,
+                results,
+                name,
+                SymbolKind::Class,
+                (tag.start(), tag.end()),
+                None,
+                metadata,
+            );
+            forms.push(Form {
+                symbol_id,
+                element_id: attribute("id").cloned(),
+                range: (tag.start(), end),
+            });
+            continue;
+        }
+
+        let form = match attribute("form") {
+            Some(form_id) => forms
+                .iter()
+                .find(|f| f.element_id.as_ref() == Some(form_id)),
+            None => forms
+                .iter()
+                .find(|f| f.range.0 < tag.start() && tag.start() < f.range.1),
+        };
+        let field_type = match element.as_str() {
+            "input" => Some(attribute("type").map_or("text".to_string(), |t| t.to_lowercase())),
+            "button" => Some(attribute("type").map_or("submit".to_string(), |t| t.to_lowercase())),
+            _ => None,
+        };
+        if let Some(field_type) = field_type {
+            metadata.insert("type".to_string(), Value::from(field_type));
+        }
+        metadata.insert(
+            "required".to_string(),
+            Value::Bool(attributes.contains_key("required")),
+        );
+        if let Some(value) = attributes.get("value") {
+            metadata.insert("value".to_string(), Value::from(value.as_str()));
+        }
+        let name = match attribute("name").or(attribute("id")) {
+            Some(name) => name.as_str(),
+            None => {
+                metadata.insert("unnamed".to_string(), Value::Bool(true));
+                element.as_str()
+            }
+        };
+        let parent_id = form.map(|f| f.symbol_id.clone());
+        upsert(
+            ctx,
+            results,
+            name,
+            SymbolKind::Field,
+            (tag.start(), tag.end()),
+            parent_id,
+            metadata,
+        );
+    }
+}
+
+/// Specialize Julie's element symbol starting at the tag, or create one; returns its id
+fn upsert(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    name: &str,
+    kind: SymbolKind,
+    (start, end): (usize, usize),
+    parent_id: Option<String>,
+    metadata: HashMap<String, Value>,
+) -> String {
+    let signature = ctx.text(start, end).to_string();
+    if let Some(symbol) = results
+        .symbols
+        .iter_mut()
+        .find(|s| s.start_byte as usize == start)
+    {
+        symbol.name = name.to_string();
+        symbol.kind = kind;
+        symbol.signature = Some(signature);
+        if parent_id.is_some() {
+            symbol.parent_id = parent_id;
+        }
+        symbol
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .extend(metadata);
+        return symbol.id.clone();
+    }
+
+    let symbol = ctx.create_symbol(
+        name,
+        kind,
+        start,
+        end,
+        SymbolOptions {
+            signature: Some(signature),
+            parent_id,
+            metadata: Some(metadata),
+            ..Default::default()
+        },
+    );
+    let id = symbol.id.clone();
+    results.symbols.push(symbol);
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    fn extract(content: &str) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("signup.html", "html", content, Path::new("."));
+        let mut results = test_results(Vec::new());
+        extract_forms(&ctx, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_form_fields_are_children_named_by_name_or_id() {
+        let results = extract(
+            r#"<form id="signup" action="/api/users" method="post">
+  <input name="email" type="email" required>
+  <input id="nickname">
+  <select name="plan"><option>free</option></select>
+  <textarea name=bio></textarea>
+  <input type="checkbox" value="yes">
+  <button>Sign up</button>
+</form>
+<!-- <input name="old"> -->
+<input name="referrer" form="signup" type="hidden" value="ad">"#,
+        );
+
+        let names: Vec<&str> = results.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["signup", "email", "nickname", "plan", "bio", "input", "button", "referrer"]
+        );
+        let form = &results.symbols[0];
+        assert_eq!(form.kind, SymbolKind::Class);
+        let form_metadata = form.metadata.as_ref().unwrap();
+        assert_eq!(form_metadata["action"], "/api/users");
+        assert_eq!(form_metadata["method"], "POST");
+        assert!(results.symbols[1..]
+            .iter()
+            .all(|s| s.kind == SymbolKind::Field && s.parent_id.as_ref() == Some(&form.id)));
+
+        let email = results.symbols[1].metadata.as_ref().unwrap();
+        assert_eq!(email["type"], "email");
+        assert_eq!(email["required"], true);
+        let checkbox = results.symbols[5].metadata.as_ref().unwrap();
+        assert_eq!(checkbox["unnamed"], true);
+        assert_eq!(checkbox["value"], "yes");
+        assert_eq!(
+            results.symbols[6].metadata.as_ref().unwrap()["type"],
+            "submit"
+        );
+        assert_eq!(results.symbols[7].metadata.as_ref().unwrap()["value"], "ad");
+    }
+
+    #[test]
+    fn test_fields_outside_forms_have_no_parent() {
+        let results =
+            extract("<form action=/search><input name=q></form>\n<input name=\"standalone\">");
+
+        assert_eq!(results.symbols[0].name, "form");
+        assert_eq!(
+            results.symbols[0].metadata.as_ref().unwrap()["method"],
+            "GET"
+        );
+        assert_eq!(
+            results.symbols[1].parent_id.as_ref(),
+            Some(&results.symbols[0].id)
+        );
+        assert_eq!(results.symbols[2].name, "standalone");
+        assert_eq!(results.symbols[2].parent_id, None);
+    }
+}
@@ -0,0 +1,81 @@
+// HTML enrichment passes
+//
+// Julie's HTML extractor produces generic element symbols; these passes move
+// the symbols of script and style elements onto the file's own lines, extract
+// the CSS and JavaScript of style and event handler attributes, give forms and
+// their fields dedicated symbols whose names match the parameter names a
+// server-side handler receives, and turn template engine placeholders (masked
+// out before parsing) into symbols of their own.
+
+mod embedded;
+mod forms;
+mod templates;
+
+use super::base::EnrichmentContext;
+use julie_extractors::ExtractionResults;
+use regex::Regex;
+use std::sync::LazyLock;
+
+pub(super) use templates::mask_placeholders;
+
+pub const EXTRACTOR_VERSION: u32 = 3;
+
+/// Comments, scripts, and styles: text that is not markup
+static NON_MARKUP_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)<!--.*?-->|<script\b.*?</script\s*>|<style\b.*?</style\s*>").unwrap()
+});
+
+/// `name`, `name=value`, `name="value"`, `name='value'` inside a start tag
+static ATTRIBUTE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([^\s"'=<>/`]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+)))?"#).unwrap()
+});
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    embedded::rebase_embedded(ctx, results);
+    embedded::extract_inline_code(ctx, results);
+    forms::extract_forms(ctx, results);
+    templates::extract_placeholders(ctx, results);
+}
+
+/// Byte ranges of comments, scripts, and styles
+fn non_markup_ranges(content: &str) -> Vec<(usize, usize)> {
+    NON_MARKUP_RE
+        .find_iter(content)
+        .map(|m| (m.start(), m.end()))
+        .collect()
+}
+
+/// An attribute of a start tag
+struct Attribute<'t> {
+    /// Lowercased
+    name: String,
+    /// "" for valueless attributes (`required`)
+    value: &'t str,
+    /// Offset of the value in the text parsed
+    value_start: usize,
+}
+
+/// Attributes of a start tag's text after the tag name, names lowercased; valueless
+/// attributes (`required`) map to ""
+fn parse_attributes(text: &str) -> Vec<(String, String)> {
+    attribute_spans(text)
+        .into_iter()
+        .map(|a| (a.name, a.value.to_string()))
+        .collect()
+}
+
+/// `parse_attributes` keeping where each value sits
+fn attribute_spans(text: &str) -> Vec<Attribute<'_>> {
+    ATTRIBUTE_RE
+        .captures_iter(text)
+        .map(|c| {
+            let name = c.get(1).unwrap();
+            let value = c.get(2).or(c.get(3)).or(c.get(4));
+            Attribute {
+                name: name.as_str().to_ascii_lowercase(),
+                value: value.map_or("", |v| v.as_str()),
+                value_start: value.map_or(name.end(), |v| v.start()),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,278 @@
+// HTML template placeholders
+//
+// Server-rendered HTML is rarely plain HTML: Jinja2/Django (`{{ user.name }}`,
+// `{% for x in items %}`, `{# note #}`), Handlebars/Mustache (`{{#each items}}`,
+// `{{{ raw }}}`, `{{! note }}`), and ERB/EJS (`<%= value %>`, `<% if a %>`,
+// `<%# note %>`) regions shred tree-sitter-html's parse into ERROR nodes.
+// Before parsing, the engine asks for the placeholder regions to be masked:
+// every byte becomes a space (newlines are kept), so offsets and lines are
+// unchanged and the surrounding markup extracts cleanly. Enrichment still sees
+// the real text, so code_context shows the template as written, and this pass
+// puts it back into signatures Julie took from the masked text.
+//
+// Each placeholder other than a template comment then becomes a Variable
+// symbol named by its expression (`user.name`, `for x in items`), parented to
+// the innermost element around it, with metadata.templatePlaceholder,
+// templateEngine ("jinja", "handlebars", "erb"), placeholderKind
+// ("expression", "statement"), and templateExpression (the raw region).
+// `{{ }}` is Handlebars when the file uses a Handlebars-only form (`{{#`,
+// `{{/`, `{{>`, `{{!`, `{{else`, `{{{`), Jinja otherwise.
+// EnrichmentOptions::raw_html_templates parses the placeholders as written.
+
+use crate::enrichment::base::{innermost_symbol_at, EnrichmentContext, SymbolOptions};
+use julie_extractors::{ExtractionResults, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static PLACEHOLDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)\{\{\{.*?\}\}\}|\{\{.*?\}\}|\{%.*?%\}|\{#.*?#\}|<%.*?%>").unwrap()
+});
+
+static HANDLEBARS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\{|\{\{~?\s*(?:[#/>!]|else\b)").unwrap());
+
+/// One template region
+#[derive(Debug, PartialEq)]
+struct Placeholder {
+    start: usize,
+    end: usize,
+    engine: &'static str,
+    /// "expression", "statement", or "comment"
+    kind: &'static str,
+    /// The region without delimiters, whitespace-control markers, or tag sigils
+    expression: String,
+}
+
+/// `content` with every template placeholder blanked out, if it has any
+pub fn mask_placeholders(content: &str) -> Cow<'_, str> {
+    let placeholders = find_placeholders(content);
+    if placeholders.is_empty() {
+        return Cow::Borrowed(content);
+    }
+    let mut bytes = content.as_bytes().to_vec();
+    for placeholder in &placeholders {
+        for byte in &mut bytes[placeholder.start..placeholder.end] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    }
+    // Whole UTF-8 sequences were replaced by ASCII
+    Cow::Owned(String::from_utf8(bytes).expect("masking keeps UTF-8 valid"))
+}
+
+pub(super) fn extract_placeholders(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let placeholders = find_placeholders(ctx.content);
+    if placeholders.is_empty() {
+        return;
+    }
+
+    if !ctx.options.raw_html_templates {
+        let masked = mask_placeholders(ctx.content);
+        for symbol in &mut results.symbols {
+            let Some(signature) = &symbol.signature else {
+                continue;
+            };
+            let start = symbol.start_byte as usize;
+            let end = start + signature.len();
+            let overlaps = placeholders.iter().any(|p| p.start < end && start < p.end);
+            if overlaps && masked.get(start..end) == Some(signature.as_str()) {
+                if let Some(original) = ctx.content.get(start..end) {
+                    symbol.signature = Some(original.to_string());
+                }
+            }
+        }
+    }
+
+    let elements = results.symbols.len();
+    for placeholder in placeholders {
+        if placeholder.kind == "comment" || placeholder.expression.is_empty() {
+            continue;
+        }
+        let parent_id =
+            innermost_symbol_at(&results.symbols[..elements], placeholder.start, |_| true)
+                .map(|s| s.id.clone());
+        let raw = &ctx.content[placeholder.start..placeholder.end];
+        let metadata = HashMap::from([
+            ("templatePlaceholder".to_string(), Value::Bool(true)),
+            (
+                "templateEngine".to_string(),
+                Value::from(placeholder.engine),
+            ),
+            ("placeholderKind".to_string(), Value::from(placeholder.kind)),
+            ("templateExpression".to_string(), Value::from(raw)),
+        ]);
+        let symbol = ctx.create_symbol(
+            &placeholder.expression,
+            SymbolKind::Variable,
+            placeholder.start,
+            placeholder.end,
+            SymbolOptions {
+                signature: Some(raw.to_string()),
+                parent_id,
+                metadata: Some(metadata),
+                ..Default::default()
+            },
+        );
+        results.symbols.push(symbol);
+    }
+}
+
+fn find_placeholders(content: &str) -> Vec<Placeholder> {
+    if !["{{", "{%", "{#", "<%"]
+        .iter()
+        .any(|open| content.contains(open))
+    {
+        return Vec::new();
+    }
+    let handlebars = HANDLEBARS_RE.is_match(content);
+
+    PLACEHOLDER_RE
+        .find_iter(content)
+        .map(|m| {
+            let raw = m.as_str();
+            let (engine, open, close) = match &raw[..2] {
+                "<%" => ("erb", 2, 2),
+                "{%" | "{#" => ("jinja", 2, 2),
+                _ if raw.starts_with("{{{") => ("handlebars", 3, 3),
+                _ if handlebars => ("handlebars", 2, 2),
+                _ => ("jinja", 2, 2),
+            };
+            let inner = &raw[open..raw.len() - close];
+            let inner = inner
+                .trim_start_matches(['-', '~', '+'])
+                .trim_end_matches(['-', '~', '+']);
+            // Whether the expression opens with a sigil (`<%=`, `{{#`) to drop
+            let (kind, sigil) = match (&raw[..2], engine) {
+                ("{#", _) => ("comment", false),
+                ("{%", _) => ("statement", false),
+                ("<%", _) => match inner.chars().next() {
+                    Some('=') => ("expression", true),
+                    Some('#') => ("comment", true),
+                    _ => ("statement", false),
+                },
+                (_, "handlebars") => match inner.trim_start().chars().next() {
+                    Some('!') => ("comment", true),
+                    Some('#' | '/' | '>') => ("statement", true),
+                    _ if inner.trim_start().starts_with("else") => ("statement", false),
+                    _ => ("expression", false),
+                },
+                _ => ("expression", false),
+            };
+            let inner = inner.trim_start();
+            let inner = if sigil { &inner[1..] } else { inner };
+            Placeholder {
+                start: m.start(),
+                end: m.end(),
+                engine,
+                kind,
+                expression: inner.split_whitespace().collect::<Vec<_>>().join(" "),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, EnrichmentContext};
+    use std::path::Path;
+
+    const PAGE: &str = "<ul class=\"{{ css }}\">\n  {% for user in users -%}\n  <li>{{ user.name }} {# hidden #}</li>\n  {% endfor %}\n</ul>\n<p><%= link_to \"Édit\", path %></p>\n";
+
+    #[test]
+    fn test_placeholders_are_masked_in_place() {
+        let masked = mask_placeholders(PAGE);
+        assert_eq!(masked.len(), PAGE.len());
+        assert_eq!(masked.lines().count(), PAGE.lines().count());
+        let lines: Vec<&str> = masked.lines().collect();
+        assert_eq!(lines[0], format!("<ul class=\"{}\">", " ".repeat(9)));
+        assert_eq!(lines[1].trim(), "");
+        assert_eq!(lines[2], format!("  <li>{}</li>", " ".repeat(28)));
+        assert_eq!(lines[5], format!("<p>{}</p>", " ".repeat(28)));
+        assert!(matches!(
+            mask_placeholders("<p>plain</p>"),
+            Cow::Borrowed(_)
+        ));
+
+        let kinds: Vec<(&str, &str, String)> =
+            find_placeholders("{{#each items}}{{> row}}{{{ body }}}{{/each}}")
+                .into_iter()
+                .map(|p| (p.engine, p.kind, p.expression))
+                .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("handlebars", "statement", "each items".to_string()),
+                ("handlebars", "statement", "row".to_string()),
+                ("handlebars", "expression", "body".to_string()),
+                ("handlebars", "statement", "each".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_placeholders_become_symbols_under_their_element() {
+        let ctx = EnrichmentContext::new("users.html", "html", PAGE, Path::new("."));
+        let masked = mask_placeholders(PAGE);
+        let li_start = PAGE.find("<li>").unwrap();
+        let li_end = PAGE.find("</li>").unwrap() + 5;
+        let list = ctx.create_symbol(
+            "ul",
+            SymbolKind::Class,
+            0,
+            PAGE.find("</ul>").unwrap() + 5,
+            SymbolOptions {
+                signature: Some(masked[..PAGE.find('>').unwrap() + 1].to_string()),
+                ..Default::default()
+            },
+        );
+        let item = ctx.create_symbol(
+            "li",
+            SymbolKind::Class,
+            li_start,
+            li_end,
+            SymbolOptions::default(),
+        );
+        let mut results = test_results(vec![list, item]);
+        extract_placeholders(&ctx, &mut results);
+
+        assert_eq!(
+            results.symbols[0].signature.as_deref(),
+            Some("<ul class=\"{{ css }}\">")
+        );
+        let placeholders: Vec<(&str, &str, &str, &str)> = results.symbols[2..]
+            .iter()
+            .map(|s| {
+                let metadata = s.metadata.as_ref().unwrap();
+                let parent = results.symbols[..2]
+                    .iter()
+                    .find(|p| s.parent_id.as_ref() == Some(&p.id))
+                    .map_or("-", |p| p.name.as_str());
+                (
+                    s.name.as_str(),
+                    metadata["templateEngine"].as_str().unwrap(),
+                    metadata["placeholderKind"].as_str().unwrap(),
+                    parent,
+                )
+            })
+            .collect();
+        assert_eq!(
+            placeholders,
+            vec![
+                ("css", "jinja", "expression", "ul"),
+                ("for user in users", "jinja", "statement", "ul"),
+                ("user.name", "jinja", "expression", "li"),
+                ("endfor", "jinja", "statement", "ul"),
+                ("link_to \"Édit\", path", "erb", "expression", "-"),
+            ]
+        );
+        assert_eq!(
+            results.symbols[4].metadata.as_ref().unwrap()["templateExpression"],
+            "{{ user.name }}"
+        );
+    }
+}
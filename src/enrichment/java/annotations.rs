@@ -0,0 +1,359 @@
+// Java annotation element values
+//
+// Julie keeps only the annotation name, so `@GetMapping("/api/users/{id}")`
+// surfaces as "GetMapping" and the route template is lost. This pass reads
+// every annotation's full text, parses simple element values (string
+// literals, `{...}` arrays, `name = value` pairs; anything else is kept as raw
+// expression text), and records:
+//
+// - on annotation symbols: the full text as signature, the parsed elements as
+//   metadata.annotationArguments
+// - on the annotated class/method/field: metadata.annotations (raw texts, so
+//   repeatable and nested annotations are preserved)
+// - for Spring's *Mapping family and JAX-RS (@Path, @GET, ...): metadata.routes
+//   and metadata.httpMethod on both, plus metadata.fullRoutes on methods whose
+//   class declares a route prefix
+
+use super::routes::{apply_routes, link_route_prefixes, route_info, RouteInfo};
+use super::{literal_strings, Annotation};
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, paren_depths, set_metadata,
+    split_top_level, CodeBytes, EnrichmentContext, Lexicon,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::sync::LazyLock;
+
+static ANNOTATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@\s*([A-Za-z_$][\w$]*(?:\s*\.\s*[A-Za-z_$][\w$]*)*)").unwrap());
+
+/// `name = value` element of an annotation argument list
+static ELEMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)^([A-Za-z_$][\w$]*)\s*=\s*([^=].*)$").unwrap());
+
+pub(super) fn enrich_annotations(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let annotations = find_annotations(ctx.content);
+    if annotations.is_empty() {
+        return;
+    }
+    let spans: Vec<(usize, usize)> = annotations.iter().map(|a| (a.start, a.end)).collect();
+    let mut route_targets: Vec<usize> = Vec::new();
+
+    for annotation in &annotations {
+        let routes = route_info(&annotation.name, &annotation.elements);
+
+        for symbol in symbols.iter_mut().filter(|s| {
+            let start = s.start_byte as usize;
+            annotation.start <= start && start < annotation.end && s.name == annotation.name
+        }) {
+            symbol.signature = Some(annotation.text.to_string());
+            set_metadata(
+                symbol,
+                "annotationArguments",
+                arguments_metadata(annotation),
+            );
+            apply_routes(symbol, &routes);
+        }
+
+        if annotation.in_parameters {
+            continue;
+        }
+        let Some(target) = annotated_symbol(ctx.content, symbols, annotation, &spans) else {
+            continue;
+        };
+        append_metadata(&mut symbols[target], "annotations", annotation.text);
+        if routes != RouteInfo::default() {
+            apply_routes(&mut symbols[target], &routes);
+            route_targets.push(target);
+        }
+    }
+
+    link_route_prefixes(symbols, &route_targets);
+}
+
+fn find_annotations(content: &str) -> Vec<Annotation<'_>> {
+    let code = code_mask(content, Lexicon::C_LIKE);
+    let paren_depth = paren_depths(content, Lexicon::C_LIKE);
+    let mut annotations = Vec::new();
+    let mut resume = 0;
+
+    for captures in ANNOTATION_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        let start = whole.start();
+        // Nested annotations are recorded as part of their outer annotation's text
+        if start < resume || !code[start] {
+            continue;
+        }
+        let qualified: String = captures[1].split_whitespace().collect();
+        if qualified == "interface" {
+            continue;
+        }
+
+        let mut end = whole.end();
+        let mut elements = Vec::new();
+        let open = end + (content[end..].len() - content[end..].trim_start().len());
+        if content.as_bytes().get(open) == Some(&b'(') {
+            if let Some(close) = find_matching_delimiter(content, open, Lexicon::C_LIKE) {
+                elements = parse_elements(&content[open + 1..close]);
+                end = close + 1;
+            }
+        }
+        resume = end;
+
+        annotations.push(Annotation {
+            start,
+            end,
+            name: qualified
+                .rsplit('.')
+                .next()
+                .unwrap_or(&qualified)
+                .to_string(),
+            text: &content[start..end],
+            elements,
+            in_parameters: paren_depth[start] > 0,
+        });
+    }
+    annotations
+}
+
+fn parse_elements(arguments: &str) -> Vec<(String, &str)> {
+    split_top_level(arguments, b',', Lexicon::C_LIKE)
+        .into_iter()
+        .map(|(_, segment)| segment.trim())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match ELEMENT_RE.captures(segment) {
+            Some(captures) => {
+                let value = captures.get(2).unwrap();
+                (captures[1].to_string(), &segment[value.start()..])
+            }
+            None => ("value".to_string(), segment),
+        })
+        .collect()
+}
+
+fn arguments_metadata(annotation: &Annotation) -> Value {
+    let arguments: Map<String, Value> = annotation
+        .elements
+        .iter()
+        .map(|(name, raw)| {
+            let value = match literal_strings(raw) {
+                Some(strings) if raw.trim_start().starts_with('{') => Value::from(strings),
+                Some(mut strings) => Value::from(strings.remove(0)),
+                None => Value::from(raw.trim()),
+            };
+            (name.clone(), value)
+        })
+        .collect();
+    Value::Object(arguments)
+}
+
+/// Index of the declaration an annotation applies to: the latest-starting
+/// declaration symbol that begins before the declaration header ends and
+/// extends past the annotation.
+fn annotated_symbol(
+    content: &str,
+    symbols: &[Symbol],
+    annotation: &Annotation,
+    spans: &[(usize, usize)],
+) -> Option<usize> {
+    let header_end = declaration_header_end(content, annotation.end)?;
+    symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| {
+            matches!(
+                s.kind,
+                SymbolKind::Class
+                    | SymbolKind::Interface
+                    | SymbolKind::Enum
+                    | SymbolKind::EnumMember
+                    | SymbolKind::Method
+                    | SymbolKind::Constructor
+                    | SymbolKind::Function
+                    | SymbolKind::Field
+                    | SymbolKind::Property
+                    | SymbolKind::Constant
+                    | SymbolKind::Variable
+            )
+        })
+        .filter(|(_, s)| {
+            let start = s.start_byte as usize;
+            start <= header_end
+                && s.end_byte as usize > annotation.end
+                && !spans.iter().any(|&(a, b)| a <= start && start < b)
+        })
+        .max_by_key(|(_, s)| (s.start_byte, std::cmp::Reverse(s.end_byte)))
+        .map(|(index, _)| index)
+}
+
+/// First `{`, `;`, `(`, or `=` of the declaration following an annotation,
+/// skipping any further annotations in between
+fn declaration_header_end(content: &str, from: usize) -> Option<usize> {
+    let mut skip_until = from;
+    for (index, byte) in CodeBytes::new(content, from, Lexicon::C_LIKE) {
+        if index < skip_until {
+            continue;
+        }
+        match byte {
+            b'@' => {
+                let name_end = ANNOTATION_RE
+                    .find_at(content, index)
+                    .filter(|m| m.start() == index)
+                    .map_or(index + 1, |m| m.end());
+                let open =
+                    name_end + (content[name_end..].len() - content[name_end..].trim_start().len());
+                skip_until = if content.as_bytes().get(open) == Some(&b'(') {
+                    find_matching_delimiter(content, open, Lexicon::C_LIKE).map_or(open, |c| c + 1)
+                } else {
+                    name_end
+                };
+            }
+            b'{' | b';' | b'(' | b'=' => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    const CONTROLLER: &str = r#"@RestController
+@RequestMapping("/api/users")
+public class UserController {
+    @GetMapping("/{id}")
+    @ResponseBody
+    public User get(@PathVariable("id") Long id) { return null; }
+
+    @RequestMapping(value = {"/", "/all"}, method = RequestMethod.POST)
+    public void create() {}
+}
+"#;
+
+    fn symbols_for(ctx: &EnrichmentContext, content: &str) -> Vec<Symbol> {
+        let class = ctx.create_symbol(
+            "UserController",
+            SymbolKind::Class,
+            content.find("public class").unwrap(),
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let method = |name: &str, kind| {
+            let start = content
+                .find(&format!("public {}", name))
+                .unwrap_or_else(|| content.find(&format!(" {}(", name)).unwrap() + 1);
+            let end = start + content[start..].find('}').unwrap() + 1;
+            ctx.create_symbol(
+                name,
+                kind,
+                start,
+                end,
+                SymbolOptions {
+                    parent_id: Some(class.id.clone()),
+                    ..Default::default()
+                },
+            )
+        };
+        let get = method("get", SymbolKind::Method);
+        let create = method("create", SymbolKind::Method);
+        let annotation_start = content.find("@GetMapping").unwrap() + 1;
+        let annotation = ctx.create_symbol(
+            "GetMapping",
+            SymbolKind::Interface,
+            annotation_start,
+            annotation_start + "GetMapping".len(),
+            SymbolOptions::default(),
+        );
+        vec![class, get, create, annotation]
+    }
+
+    #[test]
+    fn test_spring_routes_on_methods_and_class() {
+        let ctx = EnrichmentContext::new("UserController.java", "java", CONTROLLER, Path::new("."));
+        let mut symbols = symbols_for(&ctx, CONTROLLER);
+        enrich_annotations(&ctx, &mut symbols);
+
+        let meta = |index: usize| symbols[index].metadata.clone().unwrap();
+        assert_eq!(meta(0)["routes"], serde_json::json!(["/api/users"]));
+        assert_eq!(
+            meta(0)["annotations"],
+            serde_json::json!(["@RestController", "@RequestMapping(\"/api/users\")"])
+        );
+
+        assert_eq!(meta(1)["routes"], serde_json::json!(["/{id}"]));
+        assert_eq!(meta(1)["httpMethod"], Value::from("GET"));
+        assert_eq!(
+            meta(1)["fullRoutes"],
+            serde_json::json!(["/api/users/{id}"])
+        );
+        assert_eq!(
+            meta(1)["annotations"].as_array().unwrap().len(),
+            2,
+            "parameter annotations stay off the method"
+        );
+
+        assert_eq!(meta(2)["httpMethod"], Value::from("POST"));
+        assert_eq!(
+            meta(2)["fullRoutes"],
+            serde_json::json!(["/api/users", "/api/users/all"])
+        );
+    }
+
+    #[test]
+    fn test_annotation_symbol_gets_text_and_arguments() {
+        let ctx = EnrichmentContext::new("UserController.java", "java", CONTROLLER, Path::new("."));
+        let mut symbols = symbols_for(&ctx, CONTROLLER);
+        enrich_annotations(&ctx, &mut symbols);
+
+        let annotation = &symbols[3];
+        assert_eq!(
+            annotation.signature.as_deref(),
+            Some("@GetMapping(\"/{id}\")")
+        );
+        let meta = annotation.metadata.as_ref().unwrap();
+        assert_eq!(meta["annotationArguments"]["value"], Value::from("/{id}"));
+        assert_eq!(meta["routes"], serde_json::json!(["/{id}"]));
+    }
+
+    #[test]
+    fn test_jaxrs_and_raw_expressions() {
+        let content = "@Path(\"/orders\")\nclass Orders {\n  @GET @Path(ORDER_PATH) @Tags({@Tag(\"a\"), @Tag(\"b\")})\n  Order find() { return null; }\n}\n";
+        let ctx = EnrichmentContext::new("Orders.java", "java", content, Path::new("."));
+        let class = ctx.create_symbol(
+            "Orders",
+            SymbolKind::Class,
+            content.find("class").unwrap(),
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let start = content.find("Order find").unwrap();
+        let method = ctx.create_symbol(
+            "find",
+            SymbolKind::Method,
+            start,
+            content.rfind("}\n}").unwrap() + 1,
+            SymbolOptions {
+                parent_id: Some(class.id.clone()),
+                ..Default::default()
+            },
+        );
+        let mut symbols = vec![class, method];
+        enrich_annotations(&ctx, &mut symbols);
+
+        let meta = symbols[1].metadata.as_ref().unwrap();
+        assert_eq!(meta["httpMethod"], Value::from("GET"));
+        assert!(
+            meta.get("routes").is_none(),
+            "constant paths are not routes"
+        );
+        assert_eq!(meta["fullRoutes"], serde_json::json!(["/orders"]));
+        assert_eq!(
+            meta["annotations"][2],
+            Value::from("@Tags({@Tag(\"a\"), @Tag(\"b\")})")
+        );
+    }
+}
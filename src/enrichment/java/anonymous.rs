@@ -0,0 +1,280 @@
+// Java anonymous classes
+//
+// `new Runnable() { public void run() {...} }` declares a class with no name,
+// and Julie produces nothing for it, so code inside its methods is attributed
+// to whatever outer method happens to contain the expression. This pass adds a
+// synthetic Class symbol per anonymous class body, named the way javac names
+// the class file (`Outer$1`, `Outer$1$1` for one nested inside another,
+// numbered per enclosing class), and a Method symbol per method declared in
+// the body. Methods Julie already extracted are reparented instead.
+
+use super::{is_type_symbol, DECLARATION_KINDS};
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, innermost_symbol_at, set_metadata, top_level_mask,
+    EnrichmentContext, Lexicon, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `new Type<Args>(`
+static NEW_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bnew\s+([A-Za-z_$][\w$]*(?:\s*\.\s*[A-Za-z_$][\w$]*)*)\s*(<[^;{}()]*>)?\s*\(")
+        .unwrap()
+});
+
+/// Method declaration at the start of a statement inside a class body
+static METHOD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)(?:^|[{;}])\s*((?:@[\w$.]+(?:\([^)]*\))?\s+)*(?:(?:public|protected|private|static|final|synchronized|abstract|default|native|strictfp)\s+)*(?:<[^>]*>\s+)?([\w$.]+)(?:<[^;{}()=]*>)?(?:\[\])*\s+)([A-Za-z_$][\w$]*)\s*\(",
+    )
+    .unwrap()
+});
+
+/// Words that can precede `name(` without it being a method declaration
+const STATEMENT_KEYWORDS: &[&str] = &["return", "new", "throw", "else", "case", "yield"];
+
+/// Add symbols for anonymous class bodies and their methods.
+/// Returns the byte ranges of every symbol added or reparented.
+pub(super) fn extract_anonymous_classes(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+) -> Vec<(usize, usize)> {
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+    let mut counters: HashMap<String, usize> = HashMap::new();
+    let mut ranges = Vec::new();
+
+    for captures in NEW_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        if !code[whole.start()] {
+            continue;
+        }
+        let open_paren = whole.end() - 1;
+        let Some(close_paren) = find_matching_delimiter(ctx.content, open_paren, Lexicon::C_LIKE)
+        else {
+            continue;
+        };
+        let after = &ctx.content[close_paren + 1..];
+        let brace = close_paren + 1 + (after.len() - after.trim_start().len());
+        if ctx.content.as_bytes().get(brace) != Some(&b'{') {
+            continue;
+        }
+        let Some(close_brace) = find_matching_delimiter(ctx.content, brace, Lexicon::C_LIKE) else {
+            continue;
+        };
+
+        let base_type: String = captures[1].split_whitespace().collect();
+        let simple_type = base_type
+            .rsplit('.')
+            .next()
+            .unwrap_or(&base_type)
+            .to_string();
+        let start = whole.start();
+
+        let enclosing_class = innermost_symbol_at(symbols, start, is_type_symbol);
+        let name = match enclosing_class {
+            Some(class) => {
+                let counter = counters.entry(class.id.clone()).or_insert(0);
+                *counter += 1;
+                format!("{}${}", binary_name(symbols, class), counter)
+            }
+            None => format!("{}(anonymous)", simple_type),
+        };
+        let parent_id =
+            innermost_symbol_at(symbols, start, |s| DECLARATION_KINDS.contains(&s.kind))
+                .map(|s| s.id.clone());
+
+        let mut class = ctx.create_symbol(
+            &name,
+            SymbolKind::Class,
+            start,
+            close_brace + 1,
+            SymbolOptions {
+                signature: Some(ctx.text(start, close_paren + 1).to_string()),
+                parent_id,
+                ..Default::default()
+            },
+        );
+        set_metadata(&mut class, "anonymous", true);
+        set_metadata(&mut class, "baseType", simple_type);
+        let class_id = class.id.clone();
+        symbols.push(class);
+        ranges.push((start, close_brace + 1));
+
+        ranges.extend(extract_body_methods(
+            ctx,
+            symbols,
+            &class_id,
+            brace + 1,
+            close_brace,
+        ));
+    }
+    ranges
+}
+
+/// Method symbols for the declarations directly inside `body_start..body_end`
+fn extract_body_methods(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    class_id: &str,
+    body_start: usize,
+    body_end: usize,
+) -> Vec<(usize, usize)> {
+    let body = ctx.text(body_start, body_end);
+    let top_level = top_level_mask(body, Lexicon::C_LIKE);
+    let mut ranges = Vec::new();
+
+    for captures in METHOD_RE.captures_iter(body) {
+        let declaration = captures.get(1).unwrap();
+        let name = captures.get(3).unwrap();
+        if !top_level[name.start()] || STATEMENT_KEYWORDS.contains(&&captures[2]) {
+            continue;
+        }
+        let open_paren = captures.get(0).unwrap().end() - 1;
+        let Some(close_paren) = find_matching_delimiter(body, open_paren, Lexicon::C_LIKE) else {
+            continue;
+        };
+        // Optional `throws A, B` before the body
+        let Some(brace) = body[close_paren + 1..]
+            .find(['{', ';'])
+            .map(|i| close_paren + 1 + i)
+            .filter(|&i| body.as_bytes()[i] == b'{')
+        else {
+            continue;
+        };
+        let Some(close_brace) = find_matching_delimiter(body, brace, Lexicon::C_LIKE) else {
+            continue;
+        };
+
+        let start = body_start + declaration.start();
+        let end = body_start + close_brace + 1;
+        let name_start = body_start + name.start();
+
+        let existing = symbols.iter_mut().find(|s| {
+            s.name == name.as_str()
+                && matches!(s.kind, SymbolKind::Method | SymbolKind::Function)
+                && (start..end).contains(&(s.start_byte as usize))
+        });
+        match existing {
+            Some(method) => method.parent_id = Some(class_id.to_string()),
+            None => {
+                let signature = ctx.text(name_start, body_start + close_paren + 1);
+                let method = ctx.create_symbol(
+                    name.as_str(),
+                    SymbolKind::Method,
+                    start,
+                    end,
+                    SymbolOptions {
+                        signature: Some(format!("{} {}", captures[2].trim(), signature)),
+                        parent_id: Some(class_id.to_string()),
+                        ..Default::default()
+                    },
+                );
+                symbols.push(method);
+            }
+        }
+        ranges.push((start, end));
+    }
+    ranges
+}
+
+/// javac-style binary name of a type: nested types joined with `$`
+fn binary_name(symbols: &[Symbol], class: &Symbol) -> String {
+    let mut name = class.name.clone();
+    let mut current = class;
+    // Anonymous classes already carry their full binary name
+    while !class_is_anonymous(current) {
+        let Some(parent) = current
+            .parent_id
+            .as_ref()
+            .and_then(|id| symbols.iter().find(|s| &s.id == id))
+        else {
+            break;
+        };
+        if is_type_symbol(parent) {
+            name = format!("{}${}", parent.name, name);
+        }
+        current = parent;
+    }
+    name
+}
+
+pub(super) fn class_is_anonymous(symbol: &Symbol) -> bool {
+    symbol
+        .metadata
+        .as_ref()
+        .is_some_and(|m| m.get("anonymous").is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"public class Outer {
+    void start() {
+        Thread t = new Thread(new Runnable() {
+            @Override
+            public void run() {
+                work(new Comparator<String>() {
+                    public int compare(String a, String b) { return 0; }
+                });
+            }
+        });
+    }
+}
+"#;
+
+    #[test]
+    fn test_anonymous_classes_are_named_like_javac() {
+        let ctx = EnrichmentContext::new("Outer.java", "java", SOURCE, Path::new("."));
+        let outer = ctx.create_symbol(
+            "Outer",
+            SymbolKind::Class,
+            0,
+            SOURCE.len() - 1,
+            SymbolOptions::default(),
+        );
+        let start = SOURCE.find("void start").unwrap();
+        let method = ctx.create_symbol(
+            "start",
+            SymbolKind::Method,
+            start,
+            SOURCE.rfind("}\n}").unwrap() + 1,
+            SymbolOptions {
+                parent_id: Some(outer.id.clone()),
+                ..Default::default()
+            },
+        );
+        let method_id = method.id.clone();
+        let mut symbols = vec![outer, method];
+        let ranges = extract_anonymous_classes(&ctx, &mut symbols);
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Outer", "start", "Outer$1", "run", "Outer$1$1", "compare"]
+        );
+        assert_eq!(ranges.len(), 4);
+
+        assert_eq!(symbols[2].parent_id.as_deref(), Some(method_id.as_str()));
+        assert_eq!(symbols[2].signature.as_deref(), Some("new Runnable()"));
+        assert_eq!(symbols[3].parent_id, Some(symbols[2].id.clone()));
+        assert_eq!(symbols[3].signature.as_deref(), Some("void run()"));
+        assert_eq!(symbols[5].parent_id, Some(symbols[4].id.clone()));
+        assert_eq!(
+            symbols[4].metadata.as_ref().unwrap()["baseType"],
+            serde_json::Value::from("Comparator")
+        );
+    }
+
+    #[test]
+    fn test_plain_constructor_calls_are_ignored() {
+        let content = "class A { void f() { List<String> xs = new ArrayList<>(size); } }";
+        let ctx = EnrichmentContext::new("A.java", "java", content, Path::new("."));
+        let mut symbols = Vec::new();
+        assert!(extract_anonymous_classes(&ctx, &mut symbols).is_empty());
+        assert!(symbols.is_empty());
+    }
+}
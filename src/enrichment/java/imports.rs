@@ -0,0 +1,87 @@
+// Java import paths
+//
+// Julie's import symbols name what the file imports, but the batch pass that
+// links imports to other files (engine::resolve_cross_file) needs the whole
+// dotted name to look the class up by package. Each import symbol gets
+// metadata.importPath (`com.acme.util.Strings`, without a trailing `.*`),
+// with static and wildcard flags for `import static` and on-demand imports.
+
+use crate::enrichment::base::{code_mask, set_metadata, EnrichmentContext, Lexicon};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `import [static] a.b.C[.*];`
+static IMPORT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*import\s+(static\s+)?([\w.]+?)(\.\*)?\s*;").unwrap());
+
+pub(super) fn enrich_import_paths(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    if !ctx.content.contains("import") {
+        return;
+    }
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+    for captures in IMPORT_RE.captures_iter(ctx.content) {
+        let path = captures.get(2).unwrap();
+        if !code[path.start()] {
+            continue;
+        }
+        let line = ctx.line_of(path.start());
+        let Some(import) = symbols
+            .iter_mut()
+            .find(|s| s.kind == SymbolKind::Import && s.start_line == line)
+        else {
+            continue;
+        };
+        set_metadata(import, "importPath", path.as_str());
+        if captures.get(1).is_some() {
+            set_metadata(import, "static", true);
+        }
+        if captures.get(3).is_some() {
+            set_metadata(import, "wildcard", true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use serde_json::json;
+    use std::path::Path;
+
+    #[test]
+    fn test_import_symbols_get_their_dotted_path() {
+        let content = "package com.acme.web;\n\nimport com.acme.util.Strings;\nimport static com.acme.util.Strings.join;\nimport java.util.*;\n// import com.acme.Old;\n";
+        let ctx = EnrichmentContext::new("App.java", "java", content, Path::new("."));
+        let import = |name: &str, anchor: &str| {
+            let start = content.find(anchor).unwrap();
+            let end = content[start..].find(';').unwrap() + start + 1;
+            ctx.create_symbol(
+                name,
+                SymbolKind::Import,
+                start,
+                end,
+                SymbolOptions::default(),
+            )
+        };
+        let mut symbols = vec![
+            import("Strings", "import com.acme.util"),
+            import("join", "import static"),
+            import("java.util", "import java"),
+        ];
+        enrich_import_paths(&ctx, &mut symbols);
+
+        let metadata: Vec<serde_json::Value> = symbols
+            .iter()
+            .map(|s| json!(s.metadata.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            metadata,
+            vec![
+                json!({ "importPath": "com.acme.util.Strings" }),
+                json!({ "importPath": "com.acme.util.Strings.join", "static": true }),
+                json!({ "importPath": "java.util", "wildcard": true }),
+            ]
+        );
+    }
+}
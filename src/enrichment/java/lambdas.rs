@@ -0,0 +1,309 @@
+// Java lambdas assigned to fields and variables
+//
+// `Function<String, Integer> parse = s -> Integer.parseInt(s);` is a named
+// piece of behaviour, but Julie only sees a field or local variable, so calls
+// inside the lambda body belong to nothing (or to the surrounding method).
+// This pass adds a Function symbol named after the variable for each lambda
+// initializer, spanning the lambda itself. Its signature shows the declared
+// type, the name, and the parameter list; metadata.functionalInterface names
+// the target interface when the declared type says which one (not `var`).
+
+use super::DECLARATION_KINDS;
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, innermost_symbol_at, set_metadata, CodeBytes,
+    EnrichmentContext, Lexicon, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// A declared type: `Function<String, Integer>`, `java.util.function.Supplier<T>`, `Runnable[]`
+static TYPE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[A-Za-z_$][\w$]*(?:\s*\.\s*[A-Za-z_$][\w$]*)*(?:\s*<.*>)?(?:\s*\[\s*\])*$")
+        .unwrap()
+});
+
+static ANNOTATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@[\w$.]+(?:\s*\([^)]*\))?").unwrap());
+
+const MODIFIERS: &[&str] = &[
+    "public",
+    "protected",
+    "private",
+    "static",
+    "final",
+    "transient",
+    "volatile",
+];
+
+/// A lambda initializer found in the source
+#[derive(Debug, PartialEq)]
+struct LambdaDeclaration {
+    name: String,
+    declared_type: Option<String>,
+    parameters: String,
+    /// Start of the parameter list through the end of the body
+    start: usize,
+    end: usize,
+}
+
+/// Add Function symbols for lambdas assigned to declarations.
+/// Returns the byte range of every symbol added.
+pub(super) fn extract_lambdas(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    for lambda in find_lambda_declarations(ctx.content) {
+        let parent_id = innermost_symbol_at(symbols, lambda.start, |s| {
+            DECLARATION_KINDS.contains(&s.kind)
+        })
+        .map(|s| s.id.clone());
+
+        let signature = match &lambda.declared_type {
+            Some(declared) => format!("{} {} = {} ->", declared, lambda.name, lambda.parameters),
+            None => format!("var {} = {} ->", lambda.name, lambda.parameters),
+        };
+        let mut function = ctx.create_symbol(
+            &lambda.name,
+            SymbolKind::Function,
+            lambda.start,
+            lambda.end,
+            SymbolOptions {
+                signature: Some(signature),
+                parent_id,
+                ..Default::default()
+            },
+        );
+        set_metadata(&mut function, "lambda", true);
+        if let Some(interface) = lambda.declared_type.as_deref().map(interface_name) {
+            set_metadata(&mut function, "functionalInterface", interface);
+        }
+        symbols.push(function);
+        ranges.push((lambda.start, lambda.end));
+    }
+    ranges
+}
+
+fn find_lambda_declarations(content: &str) -> Vec<LambdaDeclaration> {
+    let code = code_mask(content, Lexicon::C_LIKE);
+    let bytes = content.as_bytes();
+    let mut lambdas = Vec::new();
+
+    for (arrow, _) in content.match_indices("->") {
+        if !code[arrow] {
+            continue;
+        }
+        // Parameters: `(a, b)`, `(String s)`, `()`, or a single bare name
+        let params_end = skip_space_back(bytes, arrow);
+        let params_start = if params_end > 0 && bytes[params_end - 1] == b')' {
+            match matching_open_paren(content, &code, params_end - 1) {
+                Some(open) => open,
+                None => continue,
+            }
+        } else {
+            identifier_start(bytes, params_end)
+        };
+        if params_start == params_end {
+            continue;
+        }
+
+        // `= ` before the parameters, not `==`, `<=`, `+=`, ...
+        let equals = skip_space_back(bytes, params_start);
+        if equals == 0 || bytes[equals - 1] != b'=' {
+            continue;
+        }
+        if equals >= 2 && b"=!<>+-*/%&|^".contains(&bytes[equals - 2]) {
+            continue;
+        }
+        let name_end = skip_space_back(bytes, equals - 1);
+        let name_start = identifier_start(bytes, name_end);
+        if name_start == name_end {
+            continue;
+        }
+
+        // Declared type back to the previous statement boundary; plain
+        // reassignments (`handler = e -> ...`) have none and are skipped
+        let statement_start = statement_start(bytes, &code, name_start);
+        let Some(declared_type) = declared_type(&content[statement_start..name_start]) else {
+            continue;
+        };
+
+        let body_start =
+            arrow + 2 + (content[arrow + 2..].len() - content[arrow + 2..].trim_start().len());
+        let Some(end) = lambda_body_end(content, body_start) else {
+            continue;
+        };
+
+        lambdas.push(LambdaDeclaration {
+            name: content[name_start..name_end].to_string(),
+            declared_type: (declared_type != "var").then_some(declared_type),
+            parameters: content[params_start..params_end].to_string(),
+            start: params_start,
+            end,
+        });
+    }
+    lambdas
+}
+
+/// Type text of a declaration prefix with annotations and modifiers removed
+fn declared_type(prefix: &str) -> Option<String> {
+    let prefix = ANNOTATION_RE.replace_all(prefix, " ");
+    let words: Vec<&str> = prefix
+        .split_whitespace()
+        .filter(|word| !MODIFIERS.contains(word))
+        .collect();
+    let declared = words.join(" ");
+    (!declared.is_empty() && TYPE_RE.is_match(&declared)).then_some(declared)
+}
+
+/// `java.util.function.Function<String, Integer>` → `Function`
+fn interface_name(declared: &str) -> String {
+    let base = declared.split('<').next().unwrap_or(declared).trim();
+    base.rsplit('.').next().unwrap_or(base).trim().to_string()
+}
+
+/// End of a lambda body: a `{...}` block, or an expression up to the `;`, `,`,
+/// or unmatched closing bracket that ends the initializer
+fn lambda_body_end(content: &str, body_start: usize) -> Option<usize> {
+    if content.as_bytes().get(body_start) == Some(&b'{') {
+        return find_matching_delimiter(content, body_start, Lexicon::C_LIKE).map(|c| c + 1);
+    }
+    let mut depth = 0usize;
+    for (index, byte) in CodeBytes::new(content, body_start, Lexicon::C_LIKE) {
+        match byte {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' if depth == 0 => return Some(content[..index].trim_end().len()),
+            b')' | b']' | b'}' => depth -= 1,
+            b';' | b',' if depth == 0 => return Some(content[..index].trim_end().len()),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Start of the declaration ending at `before`: just past the previous `;`,
+/// brace, open paren, or top-level comma (commas inside `<...>` and balanced
+/// annotation arguments don't count)
+fn statement_start(bytes: &[u8], code: &[bool], before: usize) -> usize {
+    let mut angle = 0usize;
+    let mut paren = 0usize;
+    for index in (0..before).rev() {
+        if !code[index] {
+            continue;
+        }
+        match bytes[index] {
+            b'>' => angle += 1,
+            b'<' => angle = angle.saturating_sub(1),
+            b')' => paren += 1,
+            b'(' if paren > 0 => paren -= 1,
+            b';' | b'{' | b'}' | b'(' => return index + 1,
+            b',' if angle == 0 && paren == 0 => return index + 1,
+            _ => {}
+        }
+    }
+    0
+}
+
+fn skip_space_back(bytes: &[u8], mut index: usize) -> usize {
+    while index > 0 && bytes[index - 1].is_ascii_whitespace() {
+        index -= 1;
+    }
+    index
+}
+
+fn identifier_start(bytes: &[u8], mut index: usize) -> usize {
+    while index > 0
+        && (bytes[index - 1].is_ascii_alphanumeric() || matches!(bytes[index - 1], b'_' | b'$'))
+    {
+        index -= 1;
+    }
+    index
+}
+
+/// Offset of the `(` matching the `)` at `close`
+fn matching_open_paren(content: &str, code: &[bool], close: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    for index in (0..=close).rev() {
+        if !code[index] {
+            continue;
+        }
+        match bytes[index] {
+            b')' => depth += 1,
+            b'(' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::path::Path;
+
+    #[test]
+    fn test_field_and_local_lambdas() {
+        let content = r#"class Handlers {
+    private final Function<String, Integer> parse = s -> Integer.parseInt(s);
+    void run() {
+        var log = (String msg, int level) -> { System.out.println(msg); };
+        handler = e -> close(e);
+        boolean same = a == b;
+    }
+}
+"#;
+        let declarations = find_lambda_declarations(content);
+        assert_eq!(declarations.len(), 2);
+
+        assert_eq!(declarations[0].name, "parse");
+        assert_eq!(
+            declarations[0].declared_type.as_deref(),
+            Some("Function<String, Integer>")
+        );
+        assert_eq!(
+            &content[declarations[0].start..declarations[0].end],
+            "s -> Integer.parseInt(s)"
+        );
+
+        assert_eq!(declarations[1].name, "log");
+        assert_eq!(declarations[1].declared_type, None);
+        assert_eq!(declarations[1].parameters, "(String msg, int level)");
+        assert!(content[..declarations[1].end].ends_with("println(msg); }"));
+    }
+
+    #[test]
+    fn test_lambda_symbol_signature_and_interface() {
+        let content = "class A {\n  static final java.util.function.Supplier<List<String>> names = () -> List.of(\"a\", \"b\");\n}\n";
+        let ctx = EnrichmentContext::new("A.java", "java", content, Path::new("."));
+        let class = ctx.create_symbol(
+            "A",
+            SymbolKind::Class,
+            0,
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let class_id = class.id.clone();
+        let mut symbols = vec![class];
+        extract_lambdas(&ctx, &mut symbols);
+
+        let lambda = &symbols[1];
+        assert_eq!(lambda.kind, SymbolKind::Function);
+        assert_eq!(lambda.parent_id.as_deref(), Some(class_id.as_str()));
+        assert_eq!(
+            lambda.signature.as_deref(),
+            Some("java.util.function.Supplier<List<String>> names = () ->")
+        );
+        assert_eq!(
+            lambda.metadata.as_ref().unwrap()["functionalInterface"],
+            Value::from("Supplier")
+        );
+    }
+}
@@ -0,0 +1,181 @@
+// Java enrichment passes
+//
+// Julie's Java extractor handles types, members, and annotation names; these
+// passes add symbols for anonymous classes and lambda-valued declarations,
+// binary names and static/inner flags for nested types, annotation element
+// values, the HTTP routes they declare, Javadoc separated from its
+// declaration by annotations, and the dotted paths of imports.
+
+mod annotations;
+mod anonymous;
+mod imports;
+mod lambdas;
+mod nested_types;
+mod routes;
+
+pub(super) use routes::{apply_routes, link_route_prefixes, route_info, RouteInfo};
+
+use super::base::doc_comments::{fill_doc_comments, DocCommentStyle, DocPlacement};
+use super::base::{reattribute_identifiers, split_top_level, EnrichmentContext, Lexicon};
+use julie_extractors::{ExtractionResults, Symbol, SymbolKind};
+
+pub const EXTRACTOR_VERSION: u32 = 2;
+
+/// Javadoc above the declaration, past any annotations
+const DOC_COMMENTS: DocCommentStyle = DocCommentStyle {
+    line_prefixes: &[],
+    block: Some(("/**", "*/")),
+    placement: DocPlacement::Preceding,
+    attribute_prefixes: &["@"],
+    max_blank_lines: 0,
+    lexicon: Lexicon::C_LIKE,
+};
+
+/// Kinds that can own nested code: the parent candidates for synthetic symbols
+const DECLARATION_KINDS: &[SymbolKind] = &[
+    SymbolKind::Class,
+    SymbolKind::Interface,
+    SymbolKind::Enum,
+    SymbolKind::Method,
+    SymbolKind::Constructor,
+    SymbolKind::Function,
+    SymbolKind::Field,
+    SymbolKind::Property,
+    SymbolKind::Variable,
+    SymbolKind::Constant,
+];
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    fill_doc_comments(ctx, &mut results.symbols, &DOC_COMMENTS, |s| {
+        s.kind != SymbolKind::Import
+    });
+    let mut added = anonymous::extract_anonymous_classes(ctx, &mut results.symbols);
+    added.extend(lambdas::extract_lambdas(ctx, &mut results.symbols));
+    reattribute_identifiers(&mut results.identifiers, &results.symbols, &added, |s| {
+        is_type_symbol(s)
+            || matches!(
+                s.kind,
+                SymbolKind::Method | SymbolKind::Constructor | SymbolKind::Function
+            )
+    });
+    nested_types::enrich_nested_types(ctx, &mut results.symbols);
+
+    annotations::enrich_annotations(ctx, &mut results.symbols);
+    imports::enrich_import_paths(ctx, &mut results.symbols);
+}
+
+fn is_type_symbol(symbol: &Symbol) -> bool {
+    matches!(
+        symbol.kind,
+        SymbolKind::Class | SymbolKind::Interface | SymbolKind::Enum
+    )
+}
+
+/// One annotation occurrence in the source
+#[derive(Debug)]
+struct Annotation<'a> {
+    start: usize,
+    end: usize,
+    /// Simple name: `org.springframework.web.bind.annotation.GetMapping` → `GetMapping`
+    name: String,
+    text: &'a str,
+    /// (element name, raw value text); a lone value is named `value`
+    elements: Vec<(String, &'a str)>,
+    /// Parameter annotations sit inside a parameter list
+    in_parameters: bool,
+}
+
+/// Strings of a literal or `{...}` array of literals (`[...]` in Kotlin);
+/// None for other expressions
+fn literal_strings(value: &str) -> Option<Vec<String>> {
+    let value = value.trim();
+    let array = value
+        .strip_prefix('{')
+        .and_then(|v| v.strip_suffix('}'))
+        .or_else(|| value.strip_prefix('[')?.strip_suffix(']'));
+    if let Some(inner) = array {
+        let mut strings = Vec::new();
+        for (_, item) in split_top_level(inner, b',', Lexicon::C_LIKE) {
+            if item.trim().is_empty() {
+                continue;
+            }
+            strings.extend(literal_strings(item)?);
+        }
+        return Some(strings);
+    }
+    string_literal(value).map(|s| vec![s])
+}
+
+/// Contents of a single `"..."` literal with common escapes decoded
+fn string_literal(value: &str) -> Option<String> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut decoded = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return None,
+            '\\' => match chars.next()? {
+                'n' => decoded.push('\n'),
+                't' => decoded.push('\t'),
+                other => decoded.push(other),
+            },
+            _ => decoded.push(c),
+        }
+    }
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use julie_extractors::IdentifierKind;
+    use std::path::Path;
+
+    #[test]
+    fn test_calls_inside_anonymous_methods_are_reattributed() {
+        let content = "class A {\n  void f() {\n    exec(new Runnable() { public void run() { go(); } });\n  }\n}\n";
+        let ctx = EnrichmentContext::new("A.java", "java", content, Path::new("."));
+        let class = ctx.create_symbol(
+            "A",
+            SymbolKind::Class,
+            0,
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let start = content.find("void f").unwrap();
+        let method = ctx.create_symbol(
+            "f",
+            SymbolKind::Method,
+            start,
+            content.rfind("}\n}").unwrap() + 1,
+            SymbolOptions::default(),
+        );
+        let method_id = method.id.clone();
+        let call = |name: &str| {
+            let at = content.find(&format!("{}(", name)).unwrap();
+            ctx.create_identifier(
+                name,
+                IdentifierKind::Call,
+                at,
+                at + name.len(),
+                Some(method_id.clone()),
+            )
+        };
+        let mut results = ExtractionResults {
+            identifiers: vec![call("exec"), call("go")],
+            ..test_results(vec![class, method])
+        };
+        enrich(&ctx, &mut results);
+
+        let run = results.symbols.iter().find(|s| s.name == "run").unwrap();
+        assert_eq!(
+            results.identifiers[0].containing_symbol_id.as_deref(),
+            Some(method_id.as_str())
+        );
+        assert_eq!(
+            results.identifiers[1].containing_symbol_id.as_deref(),
+            Some(run.id.as_str())
+        );
+    }
+}
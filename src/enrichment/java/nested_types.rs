@@ -0,0 +1,327 @@
+// Java nested and local types
+//
+// Julie sets parent_id on member types but says nothing about how they nest:
+// a `static class Nested` and an inner `class Inner` look the same, and
+// JVM-facing tooling (stack traces, class files, reflection) names them by
+// binary name, `com.acme.Outer$Inner`, which the source-form qualifiedName
+// (`com.acme.Outer.Inner`, from the qualified_names pass) doesn't give. This
+// pass:
+//
+// - records metadata.isStatic on member types: declared `static`, or
+//   implicitly static (interfaces, enums, records, and types nested in an
+//   interface); `static` is put back into signatures that dropped it
+// - sets metadata.binaryName on every type, javac style: `Outer$Inner` for
+//   members, `Outer$1Local` for local classes (numbered per enclosing class
+//   and name, metadata.local), anonymous classes keep their `Outer$1` name
+// - points methods and constructors at the innermost type containing them
+//   when Julie left them unparented or on an outer type
+
+use super::anonymous::class_is_anonymous;
+use super::{is_type_symbol, DECLARATION_KINDS};
+use crate::enrichment::base::{
+    code_mask, innermost_symbol_at, set_metadata, EnrichmentContext, Lexicon,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `package com.acme;`
+static PACKAGE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*package[ \t]+([\w.]+)[ \t]*;").unwrap());
+
+/// `static` among a declaration's modifiers
+static STATIC_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bstatic\b").unwrap());
+
+/// The declaring keyword in a type signature
+static TYPE_KEYWORD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:class|interface|enum|record)\b").unwrap());
+
+/// Naming decided for one type symbol
+struct TypeNaming {
+    index: usize,
+    binary_name: String,
+    /// Some for member types
+    is_static: Option<bool>,
+    /// Declared `static` in the source
+    declared_static: bool,
+    local: bool,
+    parent_id: Option<String>,
+}
+
+pub(super) fn enrich_nested_types(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+    let package = PACKAGE_RE
+        .captures(ctx.content)
+        .map(|caps| format!("{}.", &caps[1]))
+        .unwrap_or_default();
+
+    let mut types: Vec<usize> = (0..symbols.len())
+        .filter(|&i| is_type_symbol(&symbols[i]))
+        .collect();
+    types.sort_by_key(|&i| {
+        (
+            symbols[i].start_byte,
+            std::cmp::Reverse(symbols[i].end_byte),
+        )
+    });
+
+    // Binary names without the package, so children can extend them
+    let mut binary_names: HashMap<String, String> = HashMap::new();
+    let mut local_counters: HashMap<(String, String), usize> = HashMap::new();
+    let mut namings = Vec::new();
+    for index in types {
+        let symbol = &symbols[index];
+        let start = symbol.start_byte as usize;
+        let enclosing = |accept: &dyn Fn(&Symbol) -> bool| {
+            innermost_symbol_at(symbols, start, |s| {
+                s.id != symbol.id && s.end_byte >= symbol.end_byte && accept(s)
+            })
+        };
+        let declaration = enclosing(&|s| DECLARATION_KINDS.contains(&s.kind));
+        let enclosing_type = enclosing(&is_type_symbol);
+
+        let mut naming = TypeNaming {
+            index,
+            binary_name: symbol.name.clone(),
+            is_static: None,
+            declared_static: false,
+            local: false,
+            parent_id: None,
+        };
+        match (declaration, enclosing_type) {
+            _ if class_is_anonymous(symbol) => {}
+            (Some(owner), _) if is_type_symbol(owner) => {
+                let outer = binary_names.get(&owner.id).unwrap_or(&owner.name);
+                naming.binary_name = format!("{}${}", outer, symbol.name);
+                naming.declared_static = declared_static(ctx, &code, symbol);
+                naming.is_static = Some(
+                    naming.declared_static
+                        || matches!(symbol.kind, SymbolKind::Interface | SymbolKind::Enum)
+                        || owner.kind == SymbolKind::Interface
+                        || symbol_keyword(ctx, symbol) == Some("record"),
+                );
+                naming.parent_id = Some(owner.id.clone());
+            }
+            (Some(owner), Some(outer_type)) => {
+                let counter = local_counters
+                    .entry((outer_type.id.clone(), symbol.name.clone()))
+                    .or_insert(0);
+                *counter += 1;
+                let outer = binary_names.get(&outer_type.id).unwrap_or(&outer_type.name);
+                naming.binary_name = format!("{}${}{}", outer, counter, symbol.name);
+                naming.local = true;
+                naming.parent_id = Some(owner.id.clone());
+            }
+            _ => {}
+        }
+        binary_names.insert(symbol.id.clone(), naming.binary_name.clone());
+        namings.push(naming);
+    }
+
+    let reparented: Vec<(usize, String)> = symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| matches!(s.kind, SymbolKind::Method | SymbolKind::Constructor))
+        .filter_map(|(index, method)| {
+            let owner = innermost_symbol_at(symbols, method.start_byte as usize, |s| {
+                s.id != method.id && is_type_symbol(s) && s.end_byte >= method.end_byte
+            })?;
+            let current = method
+                .parent_id
+                .as_ref()
+                .and_then(|id| symbols.iter().find(|s| &s.id == id));
+            let misplaced = match current {
+                None => true,
+                Some(parent) => is_type_symbol(parent) && parent.id != owner.id,
+            };
+            misplaced.then(|| (index, owner.id.clone()))
+        })
+        .collect();
+
+    for naming in namings {
+        let symbol = &mut symbols[naming.index];
+        set_metadata(
+            symbol,
+            "binaryName",
+            format!("{}{}", package, naming.binary_name),
+        );
+        if let Some(is_static) = naming.is_static {
+            set_metadata(symbol, "isStatic", is_static);
+        }
+        if naming.declared_static {
+            add_static_to_signature(symbol);
+        }
+        if naming.local {
+            set_metadata(symbol, "local", true);
+        }
+        if symbol.parent_id.is_none() {
+            symbol.parent_id = naming.parent_id;
+        }
+    }
+    for (index, parent_id) in reparented {
+        symbols[index].parent_id = Some(parent_id);
+    }
+}
+
+/// Whether `static` appears among the type's modifiers
+fn declared_static(ctx: &EnrichmentContext, code: &[bool], symbol: &Symbol) -> bool {
+    let start = symbol.start_byte as usize;
+    let end = (symbol.end_byte as usize).min(ctx.content.len());
+    // Julie's span may start at the modifiers or at the keyword itself
+    let line_start = ctx.content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let Some(keyword) = TYPE_KEYWORD_RE
+        .find_iter(&ctx.content[line_start..end])
+        .map(|m| line_start + m.start())
+        .find(|&at| code[at])
+    else {
+        return false;
+    };
+    STATIC_RE
+        .find_iter(&ctx.content[line_start..keyword])
+        .any(|m| code[line_start + m.start()])
+}
+
+/// `class`, `interface`, `enum`, or `record`, from the declaration
+fn symbol_keyword<'a>(ctx: &'a EnrichmentContext, symbol: &Symbol) -> Option<&'a str> {
+    let text = ctx.symbol_text(symbol);
+    TYPE_KEYWORD_RE.find(text).map(|m| m.as_str())
+}
+
+fn add_static_to_signature(symbol: &mut Symbol) {
+    let Some(signature) = symbol.signature.as_mut() else {
+        return;
+    };
+    if signature.split_whitespace().any(|word| word == "static") {
+        return;
+    }
+    if let Some(keyword) = TYPE_KEYWORD_RE.find(signature) {
+        signature.insert_str(keyword.start(), "static ");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    fn declare(
+        ctx: &EnrichmentContext,
+        name: &str,
+        kind: SymbolKind,
+        header: &str,
+        parent: Option<&Symbol>,
+    ) -> Symbol {
+        let start = ctx.content.find(header).unwrap();
+        let open = ctx.content[start..].find('{').unwrap() + start;
+        let end =
+            crate::enrichment::base::find_matching_delimiter(ctx.content, open, Lexicon::C_LIKE)
+                .unwrap()
+                + 1;
+        ctx.create_symbol(
+            name,
+            kind,
+            start,
+            end,
+            SymbolOptions {
+                signature: Some(header.trim_start_matches("static ").to_string()),
+                parent_id: parent.map(|p| p.id.clone()),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_member_types_get_binary_names_and_static_flags() {
+        let content = "package com.acme;\n\npublic class Outer {\n    static class Nested {\n        void a() {}\n    }\n    class Inner {\n        void b() {}\n    }\n    interface Callback {}\n}\n";
+        let ctx = EnrichmentContext::new("Outer.java", "java", content, Path::new("."));
+        let outer = declare(&ctx, "Outer", SymbolKind::Class, "public class Outer", None);
+        let nested = declare(
+            &ctx,
+            "Nested",
+            SymbolKind::Class,
+            "static class Nested",
+            Some(&outer),
+        );
+        let inner = declare(
+            &ctx,
+            "Inner",
+            SymbolKind::Class,
+            "class Inner",
+            Some(&outer),
+        );
+        let callback = declare(
+            &ctx,
+            "Callback",
+            SymbolKind::Interface,
+            "interface Callback",
+            Some(&outer),
+        );
+        // Julie left `a` on the outer class and `b` unparented
+        let a = declare(&ctx, "a", SymbolKind::Method, "void a()", Some(&outer));
+        let b = declare(&ctx, "b", SymbolKind::Method, "void b()", None);
+        let mut symbols = vec![outer, nested, inner, callback, a, b];
+        enrich_nested_types(&ctx, &mut symbols);
+
+        let meta = |i: usize, key: &str| symbols[i].metadata.as_ref().unwrap().get(key).cloned();
+        assert_eq!(meta(0, "binaryName"), Some("com.acme.Outer".into()));
+        assert_eq!(meta(0, "isStatic"), None);
+        assert_eq!(meta(1, "binaryName"), Some("com.acme.Outer$Nested".into()));
+        assert_eq!(meta(1, "isStatic"), Some(true.into()));
+        assert_eq!(symbols[1].signature.as_deref(), Some("static class Nested"));
+        assert_eq!(meta(2, "binaryName"), Some("com.acme.Outer$Inner".into()));
+        assert_eq!(meta(2, "isStatic"), Some(false.into()));
+        assert_eq!(meta(3, "isStatic"), Some(true.into()));
+
+        assert_eq!(symbols[4].parent_id, Some(symbols[1].id.clone()));
+        assert_eq!(symbols[5].parent_id, Some(symbols[2].id.clone()));
+    }
+
+    #[test]
+    fn test_local_classes_are_numbered_like_javac() {
+        let content = "class Outer {\n    void first() {\n        class Local {}\n        class Helper {}\n    }\n    void second() {\n        class Local {}\n    }\n}\n";
+        let ctx = EnrichmentContext::new("Outer.java", "java", content, Path::new("."));
+        let outer = declare(&ctx, "Outer", SymbolKind::Class, "class Outer", None);
+        let first = declare(
+            &ctx,
+            "first",
+            SymbolKind::Method,
+            "void first()",
+            Some(&outer),
+        );
+        let second = declare(
+            &ctx,
+            "second",
+            SymbolKind::Method,
+            "void second()",
+            Some(&outer),
+        );
+        let local_at = content.rfind("class Local").unwrap();
+        let second_local = ctx.create_symbol(
+            "Local",
+            SymbolKind::Class,
+            local_at,
+            local_at + "class Local {}".len(),
+            SymbolOptions::default(),
+        );
+        let mut symbols = vec![
+            outer,
+            first,
+            second,
+            declare(&ctx, "Local", SymbolKind::Class, "class Local", None),
+            declare(&ctx, "Helper", SymbolKind::Class, "class Helper", None),
+            second_local,
+        ];
+        enrich_nested_types(&ctx, &mut symbols);
+
+        let binary: Vec<&str> = symbols[3..]
+            .iter()
+            .map(|s| s.metadata.as_ref().unwrap()["binaryName"].as_str().unwrap())
+            .collect();
+        assert_eq!(binary, ["Outer$1Local", "Outer$1Helper", "Outer$2Local"]);
+        assert_eq!(symbols[3].parent_id, Some(symbols[1].id.clone()));
+        assert_eq!(symbols[5].parent_id, Some(symbols[2].id.clone()));
+        assert_eq!(symbols[3].metadata.as_ref().unwrap()["local"], true);
+    }
+}
@@ -0,0 +1,184 @@
+// Java HTTP routes
+//
+// Spring's *Mapping family and JAX-RS (@Path, @GET, ...) declare route
+// templates and HTTP methods in annotation elements. These helpers turn a
+// parsed annotation into metadata.routes / metadata.httpMethod and combine a
+// class-level route prefix with its methods' routes into metadata.fullRoutes.
+// Kotlin controllers use the same annotations, so the Kotlin pass shares them.
+
+use super::literal_strings;
+use crate::enrichment::base::{append_metadata, set_metadata};
+use julie_extractors::{Symbol, SymbolKind};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Spring mapping annotations and the HTTP method they imply
+const SPRING_MAPPINGS: &[(&str, Option<&str>)] = &[
+    ("RequestMapping", None),
+    ("GetMapping", Some("GET")),
+    ("PostMapping", Some("POST")),
+    ("PutMapping", Some("PUT")),
+    ("DeleteMapping", Some("DELETE")),
+    ("PatchMapping", Some("PATCH")),
+];
+
+/// JAX-RS method designators
+const JAXRS_METHODS: &[&str] = &["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS"];
+
+/// Route information declared by an annotation
+#[derive(Debug, Default, PartialEq)]
+pub(in crate::enrichment) struct RouteInfo {
+    routes: Vec<String>,
+    http_method: Option<String>,
+}
+
+/// Routes and method declared by annotation `name` with `elements`
+/// ((element name, raw value text), a lone value named `value`)
+pub(in crate::enrichment) fn route_info(name: &str, elements: &[(String, &str)]) -> RouteInfo {
+    let element = |names: &[&str]| {
+        elements
+            .iter()
+            .find(|(name, _)| names.contains(&name.as_str()))
+            .map(|(_, raw)| *raw)
+    };
+
+    if let Some((_, implied)) = SPRING_MAPPINGS.iter().find(|(mapping, _)| *mapping == name) {
+        let routes = element(&["value", "path"])
+            .and_then(literal_strings)
+            .unwrap_or_default();
+        // RequestMapping(method = RequestMethod.POST) or method = {GET, POST}
+        let http_method = implied.map(str::to_string).or_else(|| {
+            let raw = element(&["method"])?;
+            // Kotlin writes arrays as [GET, POST]
+            let methods: Vec<&str> = raw
+                .trim()
+                .trim_start_matches(['{', '['])
+                .trim_end_matches(['}', ']'])
+                .split(',')
+                .map(|m| m.trim().rsplit('.').next().unwrap_or("").trim())
+                .filter(|m| !m.is_empty())
+                .collect();
+            (!methods.is_empty()).then(|| methods.join(","))
+        });
+        return RouteInfo {
+            routes,
+            http_method,
+        };
+    }
+
+    if name == "Path" {
+        return RouteInfo {
+            routes: element(&["value"])
+                .and_then(literal_strings)
+                .unwrap_or_default(),
+            http_method: None,
+        };
+    }
+    if JAXRS_METHODS.contains(&name) && elements.is_empty() {
+        return RouteInfo {
+            routes: Vec::new(),
+            http_method: Some(name.to_string()),
+        };
+    }
+    RouteInfo::default()
+}
+
+pub(in crate::enrichment) fn apply_routes(symbol: &mut Symbol, routes: &RouteInfo) {
+    for route in &routes.routes {
+        append_metadata(symbol, "routes", route.as_str());
+    }
+    if let Some(method) = &routes.http_method {
+        set_metadata(symbol, "httpMethod", method.as_str());
+    }
+}
+
+/// Combine class-level route prefixes with method routes into metadata.fullRoutes
+pub(in crate::enrichment) fn link_route_prefixes(symbols: &mut [Symbol], route_targets: &[usize]) {
+    let prefixes: HashMap<String, Vec<String>> = route_targets
+        .iter()
+        .map(|&index| &symbols[index])
+        .filter(|s| matches!(s.kind, SymbolKind::Class | SymbolKind::Interface))
+        .map(|s| (s.id.clone(), string_array(s, "routes")))
+        .filter(|(_, routes)| !routes.is_empty())
+        .collect();
+
+    for &index in route_targets {
+        let symbol = &symbols[index];
+        if !matches!(symbol.kind, SymbolKind::Method | SymbolKind::Function) {
+            continue;
+        }
+        let Some(prefixes) = symbol.parent_id.as_ref().and_then(|p| prefixes.get(p)) else {
+            continue;
+        };
+        let mut routes = string_array(symbol, "routes");
+        if routes.is_empty() {
+            routes.push(String::new());
+        }
+        let full: Vec<Value> = prefixes
+            .iter()
+            .flat_map(|prefix| routes.iter().map(move |route| join_route(prefix, route)))
+            .map(Value::from)
+            .collect();
+        set_metadata(&mut symbols[index], "fullRoutes", full);
+    }
+}
+
+fn string_array(symbol: &Symbol, key: &str) -> Vec<String> {
+    symbol
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get(key))
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `/api/` + `users/{id}` → `/api/users/{id}`
+fn join_route(prefix: &str, route: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    let route = route.trim_start_matches('/');
+    if route.is_empty() {
+        return if prefix.is_empty() {
+            "/".to_string()
+        } else {
+            prefix.to_string()
+        };
+    }
+    format!("{}/{}", prefix, route)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_mapping_methods() {
+        let info = route_info(
+            "RequestMapping",
+            &[
+                ("path".to_string(), "\"/users\""),
+                (
+                    "method".to_string(),
+                    "{RequestMethod.GET, RequestMethod.HEAD}",
+                ),
+            ],
+        );
+        assert_eq!(info.routes, vec!["/users"]);
+        assert_eq!(info.http_method.as_deref(), Some("GET,HEAD"));
+
+        assert_eq!(route_info("Override", &[]), RouteInfo::default());
+    }
+
+    #[test]
+    fn test_join_route() {
+        assert_eq!(join_route("/api/", "/users/{id}"), "/api/users/{id}");
+        assert_eq!(join_route("/api", ""), "/api");
+        assert_eq!(join_route("", "/"), "/");
+    }
+}
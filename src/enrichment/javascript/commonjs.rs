@@ -0,0 +1,404 @@
+// CommonJS modules
+//
+// Node code imports with `const { readFile } = require('fs')` and exports with
+// `module.exports = {...}` or `exports.foo = bar`. Julie sees only variable
+// declarations and assignments there, so none of it has Import/Export
+// semantics. This pass adds:
+//
+// - an Import symbol per name bound from a require() call (each destructured
+//   name gets its own), with metadata.source, metadata.importedName, and
+//   metadata.resolvedPath / metadata.resolved for relative specifiers
+// - an Export symbol per exported name, with metadata.exportedName and, when
+//   the exported value is a symbol declared in this file, metadata.localName
+//   and metadata.localSymbolId
+//
+// Variable (or Property) symbols Julie created for the same bindings and
+// assignments are converted in place, keeping their ids, rather than
+// duplicated.
+
+use super::{resolve_module, string_literal, JS};
+use crate::enrichment::base::{
+    code_mask, collapse_whitespace, find_matching_delimiter, set_metadata, split_top_level,
+    trim_with_offset, EnrichmentContext, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `const x = require('m')`, `let { a, b: c } = require("m").sub`
+static REQUIRE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"\b(?:const|let|var)\s+([A-Za-z_$][\w$]*|\{[^{}]*\})\s*=\s*require\s*\(\s*('[^'\n]*'|"[^"\n]*")\s*\)(?:\s*\.\s*([A-Za-z_$][\w$]*))?"#,
+    )
+    .unwrap()
+});
+
+/// `exports.name = value` or `module.exports.name = value`
+static NAMED_EXPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:module\s*\.\s*)?exports\s*\.\s*([A-Za-z_$][\w$]*)\s*=").unwrap()
+});
+
+/// `module.exports =`
+static MODULE_EXPORTS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bmodule\s*\.\s*exports\s*=").unwrap());
+
+/// A lone identifier ending its statement
+static VALUE_IDENTIFIER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z_$][\w$]*)[ \t]*(?:;|\r?\n|$)").unwrap());
+
+static IDENTIFIER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_$][\w$]*$").unwrap());
+
+/// A name bound or exported by a CommonJS statement (offsets absolute in the file)
+#[derive(Debug, PartialEq)]
+struct Binding {
+    name: String,
+    /// Imported member, or the local value of an export
+    other: Option<String>,
+    start: usize,
+    end: usize,
+}
+
+pub(super) fn enrich_commonjs(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let code = code_mask(ctx.content, JS);
+
+    for captures in REQUIRE_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        if !code[whole.start()] {
+            continue;
+        }
+        let Some(source) = string_literal(&captures[2]) else {
+            continue;
+        };
+        let resolved = resolve_module(ctx, &source);
+        let member = captures.get(3).map(|m| m.as_str());
+        let pattern = captures.get(1).unwrap();
+        let bindings = if pattern.as_str().starts_with('{') {
+            destructured(pattern.as_str(), pattern.start())
+        } else {
+            vec![Binding {
+                name: pattern.as_str().to_string(),
+                other: None,
+                start: pattern.start(),
+                end: pattern.end(),
+            }]
+        };
+
+        let signature = collapse_whitespace(whole.as_str());
+        for binding in bindings {
+            let imported = match (member, binding.other.as_deref()) {
+                (_, Some(name)) => name,
+                (Some(member), None) => member,
+                (None, None) => "*",
+            };
+            let index = upsert(
+                ctx,
+                symbols,
+                &binding,
+                SymbolKind::Import,
+                (whole.start(), whole.end()),
+                &signature,
+            );
+            let symbol = &mut symbols[index];
+            set_metadata(symbol, "source", source.as_str());
+            set_metadata(symbol, "importedName", imported);
+            set_metadata(symbol, "commonjs", true);
+            set_metadata(symbol, "resolved", resolved.is_some());
+            if let Some(path) = &resolved {
+                set_metadata(symbol, "resolvedPath", path.as_str());
+            }
+        }
+    }
+
+    for (binding, statement, exported) in find_exports(ctx.content, &code) {
+        let local_id = binding.other.as_deref().and_then(|local| {
+            symbols
+                .iter()
+                .find(|s| {
+                    s.name == local
+                        && s.parent_id.is_none()
+                        && !matches!(s.kind, SymbolKind::Import | SymbolKind::Export)
+                })
+                .map(|s| s.id.clone())
+        });
+        let signature = collapse_whitespace(ctx.text(statement.0, statement.1));
+        let index = upsert(
+            ctx,
+            symbols,
+            &binding,
+            SymbolKind::Export,
+            statement,
+            &signature,
+        );
+        let symbol = &mut symbols[index];
+        set_metadata(symbol, "exportedName", exported.as_str());
+        set_metadata(symbol, "commonjs", true);
+        if let Some(local) = &binding.other {
+            set_metadata(symbol, "localName", local.as_str());
+        }
+        if let Some(id) = local_id {
+            set_metadata(symbol, "localSymbolId", id);
+        }
+    }
+}
+
+/// (binding, statement range, exported name) for every exports assignment
+fn find_exports(content: &str, code: &[bool]) -> Vec<(Binding, (usize, usize), String)> {
+    let mut exports = Vec::new();
+    for captures in NAMED_EXPORT_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        if !code[whole.start()] || content[whole.end()..].starts_with('=') {
+            continue;
+        }
+        let name = captures.get(1).unwrap();
+        let value = lone_identifier(content, whole.end());
+        let binding = Binding {
+            name: name.as_str().to_string(),
+            other: value.map(|(value, _)| value.to_string()),
+            start: name.start(),
+            end: name.end(),
+        };
+        let statement = (whole.start(), value.map_or(whole.end(), |(_, end)| end));
+        exports.push((binding, statement, name.as_str().to_string()));
+    }
+
+    for found in MODULE_EXPORTS_RE.find_iter(content) {
+        if !code[found.start()] || content[found.end()..].starts_with('=') {
+            continue;
+        }
+        let rest = &content[found.end()..];
+        let value_start = found.end() + (rest.len() - rest.trim_start().len());
+        if content.as_bytes().get(value_start) == Some(&b'{') {
+            let Some(close) = find_matching_delimiter(content, value_start, JS) else {
+                continue;
+            };
+            for binding in object_keys(&content[value_start + 1..close], value_start + 1) {
+                let exported = binding.name.clone();
+                exports.push((binding, (found.start(), close + 1), exported));
+            }
+            continue;
+        }
+        // `module.exports = router;` exports a local; anything else exports a value
+        let binding = match lone_identifier(content, found.end()) {
+            Some((value, end)) => Binding {
+                name: value.to_string(),
+                other: Some(value.to_string()),
+                start: end - value.len(),
+                end,
+            },
+            None => Binding {
+                name: "module.exports".to_string(),
+                other: None,
+                start: found.start(),
+                end: found.start() + found.as_str().trim_end_matches('=').trim_end().len(),
+            },
+        };
+        let statement = (found.start(), binding.end.max(found.end()));
+        exports.push((binding, statement, "module.exports".to_string()));
+    }
+    exports
+}
+
+/// An assigned value that is just an identifier ending its statement: (name, end)
+fn lone_identifier(content: &str, after_equals: usize) -> Option<(&str, usize)> {
+    let rest = &content[after_equals..];
+    let start = after_equals + (rest.len() - rest.trim_start().len());
+    let value = VALUE_IDENTIFIER_RE.captures(&content[start..])?.get(1)?;
+    let name = value.as_str();
+    (!matches!(name, "function" | "class" | "async" | "new")).then_some((name, start + value.end()))
+}
+
+/// Convert the symbol Julie created for a binding to `kind`, or add one
+fn upsert(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    binding: &Binding,
+    kind: SymbolKind,
+    statement: (usize, usize),
+    signature: &str,
+) -> usize {
+    let existing = symbols.iter().position(|s| {
+        let start = s.start_byte as usize;
+        statement.0 <= start
+            && start < statement.1.max(binding.end)
+            && matches!(
+                s.kind,
+                SymbolKind::Variable | SymbolKind::Constant | SymbolKind::Property
+            )
+            && (s.name == binding.name
+                || s.name.ends_with(&format!("exports.{}", binding.name))
+                || (s.name == "module.exports" && kind == SymbolKind::Export))
+    });
+    match existing {
+        Some(index) => {
+            symbols[index].kind = kind;
+            symbols[index].name = binding.name.clone();
+            index
+        }
+        None => {
+            symbols.push(ctx.create_symbol(
+                &binding.name,
+                kind,
+                binding.start,
+                binding.end,
+                SymbolOptions {
+                    signature: Some(signature.to_string()),
+                    ..Default::default()
+                },
+            ));
+            symbols.len() - 1
+        }
+    }
+}
+
+/// `{ a, b: c, ...rest }` → a, c (imported as b)
+fn destructured(pattern: &str, offset: usize) -> Vec<Binding> {
+    let inner = &pattern[1..pattern.len() - 1];
+    split_top_level(inner, b',', JS)
+        .into_iter()
+        .filter_map(|(at, segment)| {
+            let (start, segment) = trim_with_offset(offset + 1 + at, segment);
+            if segment.is_empty() || segment.starts_with("...") {
+                return None;
+            }
+            let declaration = segment.split('=').next().unwrap_or(segment).trim();
+            let (imported, local) = match declaration.split_once(':') {
+                Some((imported, local)) => (Some(imported.trim()), local.trim()),
+                None => (None, declaration),
+            };
+            let local_start = start + segment.rfind(local).unwrap_or(0);
+            Some(Binding {
+                name: local.to_string(),
+                other: Some(imported.unwrap_or(local).to_string()),
+                start: local_start,
+                end: local_start + local.len(),
+            })
+        })
+        .collect()
+}
+
+/// Keys of an exported object literal with their identifier values
+fn object_keys(body: &str, offset: usize) -> Vec<Binding> {
+    split_top_level(body, b',', JS)
+        .into_iter()
+        .filter_map(|(at, segment)| {
+            let (start, segment) = trim_with_offset(offset + at, segment);
+            if segment.is_empty() || segment.starts_with("...") {
+                return None;
+            }
+            let key_end = segment
+                .find([':', '(', ' ', '\n', '\t'])
+                .unwrap_or(segment.len());
+            let key = &segment[..key_end];
+            let key = string_literal(key).unwrap_or_else(|| key.to_string());
+            let value = match split_top_level(segment, b':', JS).as_slice() {
+                [_, (value_at, _), ..] => segment[*value_at..].trim(),
+                // Shorthand `a` exports the local `a`; methods export themselves
+                _ if key_end == segment.len() => segment,
+                _ => "",
+            };
+            Some(Binding {
+                name: key,
+                other: IDENTIFIER_RE.is_match(value).then(|| value.to_string()),
+                start,
+                end: start + key_end,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const SOURCE: &str = r#"const fs = require('fs');
+const { readFile, join: joinPath } = require("path");
+const helper = require('./lib/helper');
+
+function load() {}
+
+module.exports = { load, other: helper, run() {} };
+exports.extra = load;
+"#;
+
+    fn enriched(root: &TempDir) -> Vec<Symbol> {
+        let ctx = EnrichmentContext::new("src/index.js", "javascript", SOURCE, root.path());
+        let fs_start = SOURCE.find("fs =").unwrap();
+        let variable = ctx.create_symbol(
+            "fs",
+            SymbolKind::Variable,
+            fs_start,
+            fs_start + 2,
+            SymbolOptions::default(),
+        );
+        let load_start = SOURCE.find("function load").unwrap();
+        let load = ctx.create_symbol(
+            "load",
+            SymbolKind::Function,
+            load_start,
+            load_start + 18,
+            SymbolOptions::default(),
+        );
+        let mut symbols = vec![variable, load];
+        enrich_commonjs(&ctx, &mut symbols);
+        symbols
+    }
+
+    fn meta<'a>(symbol: &'a Symbol, key: &str) -> &'a Value {
+        &symbol.metadata.as_ref().unwrap()[key]
+    }
+
+    #[test]
+    fn test_require_bindings_become_imports() {
+        let root = TempDir::new().unwrap();
+        fs::create_dir_all(root.path().join("src/lib")).unwrap();
+        fs::write(root.path().join("src/lib/helper.js"), "").unwrap();
+        let symbols = enriched(&root);
+
+        let imports: Vec<&Symbol> = symbols
+            .iter()
+            .filter(|s| s.kind == SymbolKind::Import)
+            .collect();
+        let names: Vec<&str> = imports.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["fs", "readFile", "joinPath", "helper"]);
+        assert_eq!(
+            symbols.iter().filter(|s| s.name == "fs").count(),
+            1,
+            "the Variable is converted, not duplicated"
+        );
+        assert_eq!(meta(imports[2], "importedName"), &Value::from("join"));
+        assert_eq!(meta(imports[2], "source"), &Value::from("path"));
+        assert_eq!(
+            meta(imports[3], "resolvedPath"),
+            &Value::from("src/lib/helper.js")
+        );
+        assert_eq!(meta(imports[0], "resolved"), &Value::Bool(false));
+    }
+
+    #[test]
+    fn test_exports_reference_local_symbols() {
+        let root = TempDir::new().unwrap();
+        let symbols = enriched(&root);
+
+        let exports: Vec<&Symbol> = symbols
+            .iter()
+            .filter(|s| s.kind == SymbolKind::Export)
+            .collect();
+        let names: Vec<&str> = exports.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["extra", "load", "other", "run"]);
+        assert_eq!(
+            meta(exports[0], "localSymbolId"),
+            &Value::from(symbols[1].id.clone())
+        );
+        assert_eq!(meta(exports[1], "localName"), &Value::from("load"));
+        assert_eq!(meta(exports[2], "localName"), &Value::from("helper"));
+        assert!(!exports[3]
+            .metadata
+            .as_ref()
+            .unwrap()
+            .contains_key("localName"));
+    }
+}
@@ -0,0 +1,392 @@
+// JavaScript/TypeScript decorators
+//
+// Angular and NestJS describe components, injectables, and endpoints with
+// decorators (`@Component({ selector: 'app-user' })`, `@Get(':id')`), which
+// Julie drops from the symbols it extracts. This pass finds every decorator,
+// parses its arguments (string, array, and object literals become JSON; other
+// expressions stay as source text), and records:
+//
+// - on the decorated class/method/property/accessor: the decorators in front
+//   of the signature, and metadata.decorators as `{ name, args, text }`
+// - on the function owning a decorated parameter: metadata.parameterDecorators
+//   with the parameter name added
+// - for NestJS: metadata.route on `@Controller` classes and on methods with an
+//   HTTP method decorator (which also sets metadata.httpMethod), and
+//   metadata.fullRoute combining a method's route with its controller's
+
+use super::{literal_value, JS};
+use crate::enrichment::base::{
+    append_metadata, code_mask, collapse_whitespace, find_matching_delimiter, paren_depths,
+    set_metadata, split_top_level, EnrichmentContext,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::sync::LazyLock;
+
+static DECORATOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@\s*([A-Za-z_$][\w$]*(?:\s*\.\s*[A-Za-z_$][\w$]*)*)").unwrap());
+
+/// Modifiers before a decorated declaration's name
+static DECLARATION_NAME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:export|default|declare|abstract|public|private|protected|static|readonly|async|override|accessor|get|set)\s+)*(?:class\s+)?\*?\s*(#?[A-Za-z_$][\w$]*)",
+    )
+    .unwrap()
+});
+
+/// NestJS HTTP method decorators
+const NEST_HTTP_METHODS: &[(&str, &str)] = &[
+    ("Get", "GET"),
+    ("Post", "POST"),
+    ("Put", "PUT"),
+    ("Delete", "DELETE"),
+    ("Patch", "PATCH"),
+    ("Options", "OPTIONS"),
+    ("Head", "HEAD"),
+    ("All", "ALL"),
+];
+
+/// Kinds a decorator can be attached to
+const TARGET_KINDS: &[SymbolKind] = &[
+    SymbolKind::Class,
+    SymbolKind::Method,
+    SymbolKind::Constructor,
+    SymbolKind::Function,
+    SymbolKind::Property,
+    SymbolKind::Field,
+    SymbolKind::Variable,
+];
+
+/// One decorator occurrence in the source
+#[derive(Debug)]
+struct Decorator<'a> {
+    start: usize,
+    end: usize,
+    /// Last segment of a dotted name: `@ng.Component` → `Component`
+    name: String,
+    text: &'a str,
+    /// Raw argument texts; empty when the decorator is not called
+    args: Vec<&'a str>,
+    in_parameters: bool,
+}
+
+impl Decorator<'_> {
+    fn to_json(&self) -> Value {
+        let args: Vec<Value> = self.args.iter().map(|arg| literal_value(arg)).collect();
+        json!({ "name": self.name, "args": args, "text": collapse_whitespace(self.text) })
+    }
+
+    /// First argument as a route: `'users'`, or `{ path: 'users' }`
+    fn route(&self) -> Option<String> {
+        match literal_value(self.args.first()?) {
+            Value::String(route) => Some(route),
+            Value::Object(options) => options.get("path")?.as_str().map(str::to_string),
+            _ => None,
+        }
+    }
+}
+
+pub(super) fn enrich_decorators(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let decorators = find_decorators(ctx.content);
+    let mut decorated: Vec<(usize, Vec<&Decorator>)> = Vec::new();
+
+    for (index, decorator) in decorators.iter().enumerate() {
+        let after = after_decorators(ctx.content, &decorators[index..]);
+        if decorator.in_parameters {
+            let Some(parameter) = DECLARATION_NAME_RE.captures(&ctx.content[after..]) else {
+                continue;
+            };
+            let owner = symbols
+                .iter_mut()
+                .filter(|s| {
+                    matches!(
+                        s.kind,
+                        SymbolKind::Method | SymbolKind::Constructor | SymbolKind::Function
+                    ) && (s.start_byte as usize) <= decorator.start
+                        && decorator.end < s.end_byte as usize
+                })
+                .max_by_key(|s| s.start_byte);
+            if let Some(owner) = owner {
+                let mut entry = decorator.to_json();
+                entry["parameter"] = Value::from(&parameter[1]);
+                append_metadata(owner, "parameterDecorators", entry);
+            }
+            continue;
+        }
+
+        let Some(target) = decorated_symbol(ctx.content, symbols, after) else {
+            continue;
+        };
+        match decorated.iter_mut().find(|(t, _)| *t == target) {
+            Some((_, list)) => list.push(decorator),
+            None => decorated.push((target, vec![decorator])),
+        }
+    }
+
+    for (target, list) in &decorated {
+        let symbol = &mut symbols[*target];
+        for decorator in list {
+            append_metadata(symbol, "decorators", decorator.to_json());
+            if decorator.name == "Controller" {
+                set_metadata(symbol, "route", decorator.route().unwrap_or_default());
+            } else if let Some((_, method)) = NEST_HTTP_METHODS
+                .iter()
+                .find(|(name, _)| *name == decorator.name)
+            {
+                set_metadata(symbol, "httpMethod", *method);
+                set_metadata(symbol, "route", decorator.route().unwrap_or_default());
+            }
+        }
+        let prefix = list
+            .iter()
+            .map(|decorator| collapse_whitespace(decorator.text))
+            .collect::<Vec<_>>()
+            .join(" ");
+        symbol.signature = Some(match symbol.signature.take() {
+            Some(signature) if signature.starts_with('@') => signature,
+            Some(signature) => format!("{} {}", prefix, signature),
+            None => format!("{} {}", prefix, symbol.name),
+        });
+    }
+
+    link_controller_routes(symbols, &decorated);
+}
+
+/// Set metadata.fullRoute on HTTP handlers inside a `@Controller` class
+fn link_controller_routes(symbols: &mut [Symbol], decorated: &[(usize, Vec<&Decorator>)]) {
+    for (target, list) in decorated {
+        if !list
+            .iter()
+            .any(|d| NEST_HTTP_METHODS.iter().any(|(name, _)| *name == d.name))
+        {
+            continue;
+        }
+        let symbol = &symbols[*target];
+        let Some(controller) = symbols.iter().find(|s| {
+            Some(&s.id) == symbol.parent_id.as_ref()
+                && s.metadata.as_ref().is_some_and(|m| m.contains_key("route"))
+        }) else {
+            continue;
+        };
+        let route = |s: &Symbol| {
+            s.metadata.as_ref().unwrap()["route"]
+                .as_str()
+                .unwrap_or("")
+                .trim_matches('/')
+                .to_string()
+        };
+        let parts: Vec<String> = [route(controller), route(symbol)]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect();
+        let full = format!("/{}", parts.join("/"));
+        set_metadata(&mut symbols[*target], "fullRoute", full);
+    }
+}
+
+fn find_decorators(content: &str) -> Vec<Decorator<'_>> {
+    let code = code_mask(content, JS);
+    let paren_depth = paren_depths(content, JS);
+    let mut decorators = Vec::new();
+    let mut resume = 0;
+
+    for captures in DECORATOR_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        let start = whole.start();
+        // Decorators nested in another's arguments are part of its text
+        if start < resume || !code[start] {
+            continue;
+        }
+        let qualified: String = captures[1].split_whitespace().collect();
+        let mut end = whole.end();
+        let mut args = Vec::new();
+        let open = end + (content[end..].len() - content[end..].trim_start().len());
+        if content.as_bytes().get(open) == Some(&b'(') {
+            if let Some(close) = find_matching_delimiter(content, open, JS) {
+                args = split_top_level(&content[open + 1..close], b',', JS)
+                    .into_iter()
+                    .map(|(_, arg)| arg.trim())
+                    .filter(|arg| !arg.is_empty())
+                    .collect();
+                end = close + 1;
+            }
+        }
+        resume = end;
+
+        decorators.push(Decorator {
+            start,
+            end,
+            name: qualified
+                .rsplit('.')
+                .next()
+                .unwrap_or(&qualified)
+                .to_string(),
+            text: &content[start..end],
+            args,
+            in_parameters: paren_depth[start] > 0,
+        });
+    }
+    decorators
+}
+
+/// Offset just past a run of adjacent decorators starting with `run[0]`
+fn after_decorators(content: &str, run: &[Decorator]) -> usize {
+    let mut end = run[0].end;
+    for next in &run[1..] {
+        if !content[end..next.start].trim().is_empty() {
+            break;
+        }
+        end = next.end;
+    }
+    end
+}
+
+/// Index of the declaration whose name follows the decorators ending at `after`
+fn decorated_symbol(content: &str, symbols: &[Symbol], after: usize) -> Option<usize> {
+    let captures = DECLARATION_NAME_RE.captures(&content[after..])?;
+    let name = captures.get(1).unwrap();
+    let name_start = after + name.start();
+    symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| {
+            TARGET_KINDS.contains(&s.kind)
+                && (s.name == name.as_str() || s.name == name.as_str().trim_start_matches('#'))
+                && (s.start_byte as usize) <= name_start
+                && name_start < s.end_byte as usize
+        })
+        .max_by_key(|(_, s)| s.start_byte)
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    const CONTROLLER: &str = r#"@Controller('users')
+export class UsersController {
+  constructor(@Inject(USERS) private readonly users: UsersService) {}
+
+  @Get(':id')
+  @UseGuards(AuthGuard)
+  async findOne(@Param('id') id: string): Promise<User> {
+    return this.users.find(id);
+  }
+}
+"#;
+
+    fn symbols(ctx: &EnrichmentContext, content: &str) -> Vec<Symbol> {
+        let class_start = content.find("export class").unwrap();
+        let class = ctx.create_symbol(
+            "UsersController",
+            SymbolKind::Class,
+            class_start,
+            content.rfind('}').unwrap() + 1,
+            SymbolOptions {
+                signature: Some("export class UsersController".to_string()),
+                ..Default::default()
+            },
+        );
+        let member = |name: &str, kind: SymbolKind, from: &str, signature: &str| {
+            let start = content.find(from).unwrap();
+            let end = content[start..].find('}').unwrap() + start + 1;
+            ctx.create_symbol(
+                name,
+                kind,
+                start,
+                end,
+                SymbolOptions {
+                    signature: Some(signature.to_string()),
+                    parent_id: Some(class.id.clone()),
+                    ..Default::default()
+                },
+            )
+        };
+        let constructor = member(
+            "constructor",
+            SymbolKind::Constructor,
+            "constructor(",
+            "constructor(users: UsersService)",
+        );
+        let find_one = member(
+            "findOne",
+            SymbolKind::Method,
+            "async findOne",
+            "async findOne(id: string): Promise<User>",
+        );
+        vec![class, constructor, find_one]
+    }
+
+    #[test]
+    fn test_nest_routes() {
+        let ctx = EnrichmentContext::new(
+            "users.controller.ts",
+            "typescript",
+            CONTROLLER,
+            Path::new("."),
+        );
+        let mut symbols = symbols(&ctx, CONTROLLER);
+        enrich_decorators(&ctx, &mut symbols);
+
+        assert_eq!(
+            symbols[0].signature.as_deref(),
+            Some("@Controller('users') export class UsersController")
+        );
+        let find_one = &symbols[2];
+        assert_eq!(
+            find_one.signature.as_deref(),
+            Some("@Get(':id') @UseGuards(AuthGuard) async findOne(id: string): Promise<User>")
+        );
+        let metadata = find_one.metadata.as_ref().unwrap();
+        assert_eq!(metadata["httpMethod"], Value::from("GET"));
+        assert_eq!(metadata["route"], Value::from(":id"));
+        assert_eq!(metadata["fullRoute"], Value::from("/users/:id"));
+        assert_eq!(
+            metadata["parameterDecorators"],
+            json!([{ "name": "Param", "args": ["id"], "text": "@Param('id')", "parameter": "id" }])
+        );
+        assert_eq!(
+            symbols[1].metadata.as_ref().unwrap()["parameterDecorators"][0]["parameter"],
+            Value::from("users")
+        );
+    }
+
+    #[test]
+    fn test_angular_component_arguments() {
+        let content = "@Component({\n  selector: 'app-user',\n  templateUrl: './user.component.html',\n})\nexport class UserComponent {\n  @Input() user!: User;\n}\n";
+        let ctx =
+            EnrichmentContext::new("user.component.ts", "typescript", content, Path::new("."));
+        let class_start = content.find("export class").unwrap();
+        let class = ctx.create_symbol(
+            "UserComponent",
+            SymbolKind::Class,
+            class_start,
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let input_start = content.find("user!").unwrap();
+        let input = ctx.create_symbol(
+            "user",
+            SymbolKind::Property,
+            input_start,
+            input_start + 12,
+            SymbolOptions::default(),
+        );
+        let mut symbols = vec![class, input];
+        enrich_decorators(&ctx, &mut symbols);
+
+        let component = &symbols[0].metadata.as_ref().unwrap()["decorators"][0];
+        assert_eq!(component["args"][0]["selector"], Value::from("app-user"));
+        assert_eq!(
+            symbols[0].signature.as_deref(),
+            Some("@Component({ selector: 'app-user', templateUrl: './user.component.html', }) UserComponent")
+        );
+        assert_eq!(
+            symbols[1].metadata.as_ref().unwrap()["decorators"][0]["name"],
+            Value::from("Input")
+        );
+    }
+}
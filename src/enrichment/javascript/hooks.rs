@@ -0,0 +1,343 @@
+// React hooks
+//
+// Hooks are how React components hold state and run effects, but to Julie a
+// hook is an ordinary function and `useEffect(fn, [user.id])` an ordinary
+// call. This pass:
+//
+// - marks functions named `use` + capital letter with metadata.isHook
+// - records every hook call on the innermost enclosing function as
+//   metadata.hookCalls, `{ hook, line }` plus `dependencies` (the dependency
+//   array's entries, as written) for useEffect, useMemo, useCallback, and the
+//   other hooks that take one; Julie identifiers carry no metadata, so the
+//   call's identifier is made a Call (added if Julie had none)
+// - adds identifiers for the dependency array entries (VariableRef for
+//   `count`, MemberAccess for `user.id`), attributed to the enclosing
+//   function, so "what does this effect depend on" is an identifier query
+// - sets metadata.suspectedRulesOfHooksViolation on functions that call hooks
+//   without being a component (capitalized) or a hook themselves, e.g. a
+//   class method or a plain helper
+
+use super::JS;
+use crate::enrichment::base::symbol_index::SymbolIndex;
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, set_metadata, split_top_level,
+    trim_with_offset, EnrichmentContext,
+};
+use julie_extractors::{ExtractionResults, IdentifierKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// `useState(`, `React.useEffect(`, `useRef<HTMLDivElement>(`
+static HOOK_CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(use[A-Z][\w$]*)\s*(?:<[^<>()]*(?:<[^<>()]*>[^<>()]*)*>)?\s*\(").unwrap()
+});
+
+/// A dependency entry that names something: `count`, `user.id`, `props?.items`
+static DEPENDENCY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_$][\w$]*(?:\s*\??\.\s*[A-Za-z_$][\w$]*)*$").unwrap());
+
+/// Last identifier of a dotted path
+static LAST_SEGMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z_$][\w$]*$").unwrap());
+
+/// Built-in hooks whose last argument is a dependency array
+const DEPENDENCY_HOOKS: &[&str] = &[
+    "useEffect",
+    "useLayoutEffect",
+    "useInsertionEffect",
+    "useMemo",
+    "useCallback",
+    "useImperativeHandle",
+];
+
+/// Symbols a hook call can sit in
+const OWNER_KINDS: &[SymbolKind] = &[
+    SymbolKind::Function,
+    SymbolKind::Method,
+    SymbolKind::Variable,
+    SymbolKind::Constant,
+    SymbolKind::Property,
+];
+
+/// One hook call site
+#[derive(Debug, PartialEq)]
+struct HookCall {
+    hook: String,
+    name_start: usize,
+    /// (byte, text) of each dependency array entry; None without an array
+    dependencies: Option<Vec<(usize, String)>>,
+}
+
+pub(super) fn enrich_hooks(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    for symbol in &mut results.symbols {
+        if is_hook_name(&symbol.name) && is_function_like(ctx, symbol) {
+            set_metadata(symbol, "isHook", true);
+        }
+    }
+
+    let calls = find_hook_calls(ctx.content);
+    if calls.is_empty() {
+        return;
+    }
+
+    let mut records = Vec::new();
+    let mut identifiers = Vec::new();
+    let mut violations = HashSet::new();
+    {
+        let index = SymbolIndex::new(&results.symbols);
+        let existing: HashSet<(usize, &str)> = results
+            .identifiers
+            .iter()
+            .map(|i| (i.start_byte as usize, i.name.as_str()))
+            .collect();
+        for call in &calls {
+            let owner = index.innermost_at(call.name_start, |s| OWNER_KINDS.contains(&s.kind));
+            let owner_id = owner.map(|s| s.id.clone());
+            let Some(owner) = owner else {
+                continue;
+            };
+            if !is_hook_name(&owner.name) && !is_component_name(owner) {
+                violations.insert(owner.id.clone());
+            }
+
+            let mut references = vec![(call.name_start, call.hook.clone(), IdentifierKind::Call)];
+            for (start, text) in call.dependencies.iter().flatten() {
+                if !DEPENDENCY_RE.is_match(text) {
+                    continue;
+                }
+                let last = LAST_SEGMENT_RE.find(text).unwrap();
+                let kind = if text.contains('.') {
+                    IdentifierKind::MemberAccess
+                } else {
+                    IdentifierKind::VariableRef
+                };
+                references.push((start + last.start(), last.as_str().to_string(), kind));
+            }
+            for (start, name, kind) in references {
+                if existing.contains(&(start, name.as_str())) {
+                    continue;
+                }
+                identifiers.push(ctx.create_identifier(
+                    &name,
+                    kind,
+                    start,
+                    start + name.len(),
+                    owner_id.clone(),
+                ));
+            }
+            records.push((owner.id.clone(), call));
+        }
+    }
+
+    let call_starts: HashSet<usize> = calls.iter().map(|c| c.name_start).collect();
+    for identifier in &mut results.identifiers {
+        if call_starts.contains(&(identifier.start_byte as usize)) {
+            identifier.kind = IdentifierKind::Call;
+        }
+    }
+    results.identifiers.extend(identifiers);
+
+    for (owner_id, call) in records {
+        let owner = results
+            .symbols
+            .iter_mut()
+            .find(|s| s.id == owner_id)
+            .unwrap();
+        let mut record = json!({ "hook": call.hook, "line": ctx.line_of(call.name_start) });
+        if let Some(dependencies) = &call.dependencies {
+            let texts: Vec<&str> = dependencies.iter().map(|(_, t)| t.as_str()).collect();
+            record["dependencies"] = Value::from(texts);
+        }
+        append_metadata(owner, "hookCalls", record);
+        if violations.contains(&owner_id) {
+            set_metadata(owner, "suspectedRulesOfHooksViolation", true);
+        }
+    }
+}
+
+/// `useAuth`, `useState`; not `user` or `used`
+fn is_hook_name(name: &str) -> bool {
+    name.strip_prefix("use")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+}
+
+/// Capitalized functions and function-valued variables render components
+fn is_component_name(symbol: &Symbol) -> bool {
+    symbol.kind != SymbolKind::Method && symbol.name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// Functions, and variables holding an arrow function or function expression
+fn is_function_like(ctx: &EnrichmentContext, symbol: &Symbol) -> bool {
+    match symbol.kind {
+        SymbolKind::Function | SymbolKind::Method => true,
+        SymbolKind::Variable | SymbolKind::Constant => {
+            let text = ctx.symbol_text(symbol);
+            text.contains("=>") || text.contains("function")
+        }
+        _ => false,
+    }
+}
+
+fn find_hook_calls(content: &str) -> Vec<HookCall> {
+    let code = code_mask(content, JS);
+    let mut calls = Vec::new();
+    for captures in HOOK_CALL_RE.captures_iter(content) {
+        let name = captures.get(1).unwrap();
+        if !code[name.start()] {
+            continue;
+        }
+        // `function useAuth(` declares the hook rather than calling it
+        let before = content[..name.start()].trim_end();
+        if before.ends_with("function")
+            && !before[..before.len() - 8].ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        {
+            continue;
+        }
+
+        let open = captures.get(0).unwrap().end() - 1;
+        let dependencies = if DEPENDENCY_HOOKS.contains(&name.as_str()) {
+            find_matching_delimiter(content, open, JS)
+                .and_then(|close| dependency_array(content, open + 1, close))
+        } else {
+            None
+        };
+        calls.push(HookCall {
+            hook: name.as_str().to_string(),
+            name_start: name.start(),
+            dependencies,
+        });
+    }
+    calls
+}
+
+/// Entries of the `[...]` literal that is the last argument in `content[start..end]`
+fn dependency_array(content: &str, start: usize, end: usize) -> Option<Vec<(usize, String)>> {
+    let arguments = split_top_level(&content[start..end], b',', JS);
+    let (offset, last) = arguments
+        .iter()
+        .rev()
+        .map(|&(offset, segment)| trim_with_offset(start + offset, segment))
+        .find(|(_, segment)| !segment.is_empty())?;
+    if arguments.len() < 2 || !last.starts_with('[') || !last.ends_with(']') {
+        return None;
+    }
+    let inner = &last[1..last.len() - 1];
+    Some(
+        split_top_level(inner, b',', JS)
+            .into_iter()
+            .map(|(entry_offset, entry)| trim_with_offset(offset + 1 + entry_offset, entry))
+            .filter(|(_, entry)| !entry.is_empty())
+            .map(|(at, entry)| (at, entry.to_string()))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    const SOURCE: &str = r#"export function useAuth(client) {
+  const [user, setUser] = useState<User | null>(null);
+  useEffect(() => {
+    client.load(session.id).then(setUser);
+  }, [client, session.id]);
+  return user;
+}
+
+export const Profile = ({ id }) => {
+  const user = useAuth(api);
+  const label = React.useMemo(() => format(user), [user]);
+  return label;
+};
+
+function helper() {
+  return useContext(Theme);
+}
+"#;
+
+    fn symbol(ctx: &EnrichmentContext, name: &str, kind: SymbolKind, text: &str) -> Symbol {
+        let start = SOURCE.find(text).unwrap();
+        let end = SOURCE[start..].find("\n}").unwrap() + start + 2;
+        ctx.create_symbol(name, kind, start, end, SymbolOptions::default())
+    }
+
+    fn enriched() -> ExtractionResults {
+        let ctx = EnrichmentContext::new("src/auth.tsx", "tsx", SOURCE, Path::new("."));
+        let mut results = test_results(vec![
+            symbol(
+                &ctx,
+                "useAuth",
+                SymbolKind::Function,
+                "export function useAuth",
+            ),
+            symbol(
+                &ctx,
+                "Profile",
+                SymbolKind::Constant,
+                "export const Profile",
+            ),
+            symbol(&ctx, "helper", SymbolKind::Function, "function helper"),
+        ]);
+        enrich_hooks(&ctx, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_hooks_and_dependency_arrays() {
+        let results = enriched();
+        let use_auth = results.symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(use_auth["isHook"], true);
+        assert_eq!(
+            use_auth["hookCalls"],
+            json!([
+                {"hook": "useState", "line": 2},
+                {"hook": "useEffect", "line": 3, "dependencies": ["client", "session.id"]},
+            ])
+        );
+        let profile = results.symbols[1].metadata.as_ref().unwrap();
+        assert!(!profile.contains_key("isHook"));
+        assert_eq!(
+            profile["hookCalls"][1],
+            json!({"hook": "useMemo", "line": 11, "dependencies": ["user"]})
+        );
+
+        let effect_identifiers: Vec<(&str, &IdentifierKind)> = results
+            .identifiers
+            .iter()
+            .filter(|i| i.start_line == 5)
+            .map(|i| (i.name.as_str(), &i.kind))
+            .collect();
+        assert_eq!(
+            effect_identifiers,
+            [
+                ("client", &IdentifierKind::VariableRef),
+                ("id", &IdentifierKind::MemberAccess),
+            ]
+        );
+        // The custom hook call is a Call like the built-in ones
+        assert!(results
+            .identifiers
+            .iter()
+            .any(|i| i.name == "useAuth" && i.kind == IdentifierKind::Call));
+    }
+
+    #[test]
+    fn test_hook_calls_outside_components_and_hooks_are_flagged() {
+        let results = enriched();
+        let flagged: Vec<&str> = results
+            .symbols
+            .iter()
+            .filter(|s| {
+                s.metadata
+                    .as_ref()
+                    .is_some_and(|m| m.contains_key("suspectedRulesOfHooksViolation"))
+            })
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(flagged, ["helper"]);
+    }
+}
@@ -0,0 +1,222 @@
+// Client-side HTTP calls
+//
+// Frontend code reaches the backend through `fetch('/api/users/' + id)`,
+// `axios.get(`/api/users/${id}`)`, or Angular's `this.http.post<User>(...)`.
+// Julie records only a call to `fetch` or `get`, so the URL is lost. This pass
+// finds those calls whose first argument is a URL-like string, template, or
+// concatenation and appends `{ callee, method, url, line }` to
+// metadata.httpCalls on the innermost enclosing symbol. Interpolated parts of
+// the URL become `{expression}` placeholders (`/api/users/{id}`), which
+// utils::cross_language_intelligence::correlate_endpoints matches against
+// server route templates.
+
+use super::{literal_value, string_literal, JS};
+use crate::enrichment::base::symbol_index::SymbolIndex;
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, split_top_level, EnrichmentContext,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::json;
+use std::sync::LazyLock;
+
+/// `fetch(`, `axios.get(`, `this.http.post<User>(`
+static HTTP_CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:\b(fetch)|\.\s*(get|post|put|patch|delete|head))\s*(?:<[^<>()]*(?:<[^<>()]*>[^<>()]*)*>)?\s*\(",
+    )
+    .unwrap()
+});
+
+/// One HTTP request made by client code
+#[derive(Debug, PartialEq)]
+struct HttpCall {
+    callee: String,
+    method: String,
+    url: String,
+    start: usize,
+}
+
+pub(super) fn enrich_http_calls(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let calls = find_http_calls(ctx.content);
+    if calls.is_empty() {
+        return;
+    }
+    let owners: Vec<Option<usize>> = {
+        let index = SymbolIndex::new(symbols);
+        calls
+            .iter()
+            .map(|call| {
+                let owner = index.innermost_at(call.start, |s| {
+                    !matches!(s.kind, SymbolKind::Import | SymbolKind::Export)
+                })?;
+                symbols.iter().position(|s| s.id == owner.id)
+            })
+            .collect()
+    };
+
+    for (call, owner) in calls.into_iter().zip(owners) {
+        let Some(owner) = owner else {
+            continue;
+        };
+        append_metadata(
+            &mut symbols[owner],
+            "httpCalls",
+            json!({
+                "callee": call.callee,
+                "method": call.method,
+                "url": call.url,
+                "line": ctx.line_of(call.start),
+            }),
+        );
+    }
+}
+
+fn find_http_calls(content: &str) -> Vec<HttpCall> {
+    let code = code_mask(content, JS);
+    let mut calls = Vec::new();
+    for captures in HTTP_CALL_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        if !code[whole.start()] {
+            continue;
+        }
+        let open = whole.end() - 1;
+        let Some(close) = find_matching_delimiter(content, open, JS) else {
+            continue;
+        };
+        let arguments = split_top_level(&content[open + 1..close], b',', JS);
+        let Some(url) = arguments.first().and_then(|(_, arg)| url_template(arg)) else {
+            continue;
+        };
+
+        let (callee, method) = match (captures.get(1), captures.get(2)) {
+            (Some(fetch), _) => {
+                // fetch(url, { method: 'POST' })
+                let method = arguments
+                    .get(1)
+                    .map(|(_, options)| literal_value(options))
+                    .and_then(|options| options.get("method")?.as_str().map(str::to_string))
+                    .unwrap_or_else(|| "GET".to_string());
+                (fetch.as_str(), method.to_uppercase())
+            }
+            (None, Some(verb)) => {
+                // `map.get(key)` is not a request; require something path-like
+                if !url.contains('/') {
+                    continue;
+                }
+                (verb.as_str(), verb.as_str().to_uppercase())
+            }
+            (None, None) => continue,
+        };
+        calls.push(HttpCall {
+            callee: callee.to_string(),
+            method,
+            url,
+            start: whole.start(),
+        });
+    }
+    calls
+}
+
+/// URL of a string literal, template literal, or `'...' + expr` concatenation,
+/// with interpolated parts as `{expr}` placeholders
+fn url_template(argument: &str) -> Option<String> {
+    let argument = argument.trim();
+    if let Some(url) = string_literal(argument) {
+        return Some(url);
+    }
+    if let Some(inner) = argument.strip_prefix('`').and_then(|a| a.strip_suffix('`')) {
+        return Some(template_url(inner));
+    }
+
+    let parts = split_top_level(argument, b'+', JS);
+    if parts.len() < 2 {
+        return None;
+    }
+    let mut url = String::new();
+    let mut has_literal = false;
+    for (_, part) in parts {
+        let part = part.trim();
+        if let Some(literal) = string_literal(part) {
+            url.push_str(&literal);
+            has_literal = true;
+        } else if let Some(inner) = part.strip_prefix('`').and_then(|p| p.strip_suffix('`')) {
+            url.push_str(&template_url(inner));
+            has_literal = true;
+        } else {
+            url.push_str(&format!("{{{}}}", part));
+        }
+    }
+    has_literal.then_some(url)
+}
+
+/// `users/${user.id}/posts` → `users/{user.id}/posts`
+fn template_url(inner: &str) -> String {
+    let mut url = String::new();
+    let mut rest = inner;
+    while let Some(start) = rest.find("${") {
+        url.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match find_matching_delimiter(after, 0, JS) {
+            Some(end) => {
+                url.push_str(&format!("{{{}}}", after[1..end].trim()));
+                rest = &after[end + 1..];
+            }
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        }
+    }
+    url.push_str(rest);
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch_axios_and_http_client_calls() {
+        let content = "async function load(id) {\n  await fetch('/api/users/' + id);\n  await fetch(`/api/users/${id}/posts`, { method: 'post' });\n  axios.delete(`${API}/api/users/${user.id}`);\n  this.http.get<User[]>(\"/api/users\");\n  cache.get(id);\n  // fetch('/commented')\n}\n";
+        let calls = find_http_calls(content);
+
+        let summary: Vec<(&str, &str, &str)> = calls
+            .iter()
+            .map(|c| (c.callee.as_str(), c.method.as_str(), c.url.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("fetch", "GET", "/api/users/{id}"),
+                ("fetch", "POST", "/api/users/{id}/posts"),
+                ("delete", "DELETE", "{API}/api/users/{user.id}"),
+                ("get", "GET", "/api/users"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_calls_are_recorded_on_the_enclosing_function() {
+        use crate::enrichment::base::SymbolOptions;
+        use std::path::Path;
+
+        let content = "function load(id) {\n  return fetch(`/api/users/${id}`);\n}\n";
+        let ctx = EnrichmentContext::new("api.ts", "typescript", content, Path::new("."));
+        let mut symbols = vec![ctx.create_symbol(
+            "load",
+            SymbolKind::Function,
+            0,
+            content.len() - 1,
+            SymbolOptions::default(),
+        )];
+
+        enrich_http_calls(&ctx, &mut symbols);
+
+        let metadata = symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(
+            metadata["httpCalls"],
+            json!([{ "callee": "fetch", "method": "GET", "url": "/api/users/{id}", "line": 2 }])
+        );
+    }
+}
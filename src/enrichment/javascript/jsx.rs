@@ -0,0 +1,464 @@
+// JSX elements
+//
+// `<UserCard user={user} onSelect={handleSelect} />` is a call of UserCard
+// with two props, but Julie's identifier pass treats JSX as opaque markup, so
+// component graphs have no edges. This pass parses JSX opening tags in
+// .js/.jsx/.tsx files (plain .ts can't contain JSX) and:
+//
+// - adds a Call identifier for each component element (capitalized or
+//   member-expression tag names), attributed to the innermost enclosing
+//   function; lowercase intrinsic elements (`<div>`) get none
+// - records each component use on that function as metadata.jsxComponents,
+//   `{ component, props, line }`, since Julie identifiers carry no metadata
+// - adds identifiers for `{...}` prop values that name something: VariableRef
+//   for `{handleSelect}`, MemberAccess for `{this.onClick}`, Call for
+//   `{() => save(id)}` or `{format(date)}`
+// - marks the capitalized function, variable, or class that renders JSX
+//   with metadata.isReactComponent
+//
+// A `<` starts a tag only where an expression can start (after `(`, `=`,
+// `return`, `=>`, ...) or in the children of another tag, and only when the
+// whole tag parses as JSX, so comparisons (`a < B && c > d`) and generic
+// calls (`useState<User>(`) are left alone.
+
+use super::JS;
+use crate::enrichment::base::symbol_index::SymbolIndex;
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, set_metadata, EnrichmentContext,
+};
+use julie_extractors::{ExtractionResults, IdentifierKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// Tag name: `div`, `UserCard`, `Foo.Bar`, `svg:path`, `my-element`
+static TAG_NAME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_$][\w$-]*(?:[.:][A-Za-z_$][\w$-]*)*").unwrap());
+
+/// Attribute name: `onClick`, `aria-label`, `xlink:href`
+static ATTRIBUTE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_$][\w$-]*(?::[\w$-]+)?").unwrap());
+
+/// `{handleSelect}` / `{this.props.onClick}`
+static REFERENCE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_$][\w$]*(?:\s*\??\.\s*[A-Za-z_$][\w$]*)*$").unwrap());
+
+/// `{save(id)}` / `{() => save(id)}` / `{(e) => this.save(e)}`: the callee path
+static CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:(?:async\s+)?(?:\([^()]*\)|[A-Za-z_$][\w$]*)\s*=>\s*)?([A-Za-z_$][\w$]*(?:\s*\??\.\s*[A-Za-z_$][\w$]*)*)\s*\(",
+    )
+    .unwrap()
+});
+
+/// Last identifier of a dotted path
+static LAST_SEGMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[A-Za-z_$][\w$]*$").unwrap());
+
+/// Symbols that can own JSX: the function or value that renders it
+const OWNER_KINDS: &[SymbolKind] = &[
+    SymbolKind::Function,
+    SymbolKind::Method,
+    SymbolKind::Variable,
+    SymbolKind::Constant,
+    SymbolKind::Property,
+    SymbolKind::Class,
+];
+
+/// One JSX opening or self-closing tag
+#[derive(Debug, PartialEq)]
+struct JsxElement {
+    name: String,
+    /// Byte of the last segment of the name (`Bar` in `Foo.Bar`)
+    name_start: usize,
+    start: usize,
+    end: usize,
+    props: Vec<String>,
+    /// (byte, text) of each trimmed `{...}` prop value
+    expressions: Vec<(usize, String)>,
+}
+
+impl JsxElement {
+    /// Capitalized and member-expression tags are components; the rest are intrinsic
+    fn is_component(&self) -> bool {
+        self.name.starts_with(|c: char| c.is_ascii_uppercase()) || self.name.contains('.')
+    }
+}
+
+pub(super) fn enrich_jsx(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    if ctx.language == "typescript" {
+        return;
+    }
+    let elements = find_elements(ctx.content);
+    if elements.is_empty() {
+        return;
+    }
+
+    let mut components = Vec::new();
+    let mut identifiers = Vec::new();
+    let mut react_components = HashSet::new();
+    {
+        let index = SymbolIndex::new(&results.symbols);
+        let existing: HashSet<(usize, &str)> = results
+            .identifiers
+            .iter()
+            .map(|i| (i.start_byte as usize, i.name.as_str()))
+            .collect();
+        for element in &elements {
+            let owner = index.innermost_at(element.start, |s| OWNER_KINDS.contains(&s.kind));
+            let owner_id = owner.map(|s| s.id.clone());
+            if let Some(owner) = owner {
+                if let Some(component) = rendering_component(&results.symbols, owner) {
+                    react_components.insert(component.id.clone());
+                }
+            }
+
+            let mut references = expression_references(element);
+            if element.is_component() {
+                let name = &ctx.content[element.name_start..element.start + 1 + element.name.len()];
+                references.push((element.name_start, name.to_string(), IdentifierKind::Call));
+                if let Some(owner_id) = &owner_id {
+                    components.push((owner_id.clone(), element));
+                }
+            }
+            for (start, name, kind) in references {
+                if existing.contains(&(start, name.as_str())) {
+                    continue;
+                }
+                identifiers.push(ctx.create_identifier(
+                    &name,
+                    kind,
+                    start,
+                    start + name.len(),
+                    owner_id.clone(),
+                ));
+            }
+        }
+    }
+
+    // Julie's identifier for a component tag, when it made one, becomes the call
+    let component_starts: HashSet<usize> = elements
+        .iter()
+        .filter(|e| e.is_component())
+        .map(|e| e.name_start)
+        .collect();
+    for identifier in &mut results.identifiers {
+        if component_starts.contains(&(identifier.start_byte as usize)) {
+            identifier.kind = IdentifierKind::Call;
+        }
+    }
+    results.identifiers.extend(identifiers);
+
+    for (owner_id, element) in components {
+        let owner = results
+            .symbols
+            .iter_mut()
+            .find(|s| s.id == owner_id)
+            .unwrap();
+        append_metadata(
+            owner,
+            "jsxComponents",
+            json!({
+                "component": element.name,
+                "props": element.props,
+                "line": ctx.line_of(element.start),
+            }),
+        );
+    }
+    for symbol in &mut results.symbols {
+        if react_components.contains(&symbol.id) {
+            set_metadata(symbol, "isReactComponent", true);
+        }
+    }
+}
+
+/// The capitalized declaration that renders JSX written inside `owner`:
+/// the owner itself, or the class whose `render` method it is
+fn rendering_component<'s>(symbols: &'s [Symbol], owner: &'s Symbol) -> Option<&'s Symbol> {
+    let mut current = owner;
+    // Bounded, so a parent_id cycle can't loop forever
+    for _ in 0..=symbols.len() {
+        let renders = matches!(
+            current.kind,
+            SymbolKind::Function | SymbolKind::Variable | SymbolKind::Constant | SymbolKind::Class
+        );
+        if renders && current.name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            return Some(current);
+        }
+        let parent_id = current.parent_id.as_deref()?;
+        current = symbols.iter().find(|s| s.id == parent_id)?;
+    }
+    None
+}
+
+/// Identifiers for `{...}` prop values that name a value or call a function
+fn expression_references(element: &JsxElement) -> Vec<(usize, String, IdentifierKind)> {
+    let mut references = Vec::new();
+    for (start, text) in &element.expressions {
+        let (path, kind) = if REFERENCE_RE.is_match(text) {
+            let kind = if text.contains('.') {
+                IdentifierKind::MemberAccess
+            } else {
+                IdentifierKind::VariableRef
+            };
+            (text.as_str(), kind)
+        } else if let Some(caps) = CALL_RE.captures(text) {
+            let callee = caps.get(1).unwrap();
+            (&text[..callee.end()], IdentifierKind::Call)
+        } else {
+            continue;
+        };
+        let last = LAST_SEGMENT_RE.find(path).unwrap();
+        references.push((start + last.start(), last.as_str().to_string(), kind));
+    }
+    references
+}
+
+/// Every JSX opening or self-closing tag in the file, in source order
+fn find_elements(content: &str) -> Vec<JsxElement> {
+    let code = code_mask(content, JS);
+    let bytes = content.as_bytes();
+    let mut elements = Vec::new();
+    // End of the last tag; text after it may be JSX children
+    let mut children_from: Option<usize> = None;
+    let mut resume = 0;
+
+    for lt in (0..bytes.len()).filter(|&i| bytes[i] == b'<' && code[i]) {
+        if lt < resume {
+            continue;
+        }
+        let in_children = children_from.is_some_and(|from| is_jsx_text(content, &code, from, lt));
+        match bytes.get(lt + 1) {
+            Some(b'/') if in_children => {
+                if let Some(end) = closing_tag_end(content, lt) {
+                    children_from = Some(end);
+                    resume = end;
+                }
+                continue;
+            }
+            Some(b'>') if in_children || starts_expression(content, &code, lt) => {
+                children_from = Some(lt + 2);
+                continue;
+            }
+            _ => {}
+        }
+        if !in_children && !starts_expression(content, &code, lt) {
+            continue;
+        }
+        if let Some(element) = parse_element(content, lt) {
+            children_from = Some(element.end);
+            resume = element.end;
+            elements.push(element);
+        }
+    }
+    elements
+}
+
+/// Whether `content[from..to]` can be JSX children text: `{...}` containers
+/// and text, but no statement or expression punctuation
+fn is_jsx_text(content: &str, code: &[bool], from: usize, to: usize) -> bool {
+    let bytes = content.as_bytes();
+    let mut index = from;
+    while index < to {
+        if code[index] {
+            match bytes[index] {
+                b'{' => match find_matching_delimiter(content, index, JS) {
+                    Some(close) if close < to => index = close,
+                    _ => return false,
+                },
+                b';' | b'}' | b')' => return false,
+                _ => {}
+            }
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Whether an expression can start at `lt`: after an operator, an opening
+/// bracket, `=>`, or a keyword like `return`
+fn starts_expression(content: &str, code: &[bool], lt: usize) -> bool {
+    let before = content[..lt].trim_end();
+    let Some(previous) = before.bytes().last() else {
+        return true;
+    };
+    if !code[before.len() - 1] {
+        return false;
+    }
+    if matches!(
+        previous,
+        b'(' | b',' | b'=' | b':' | b'?' | b'&' | b'|' | b'{' | b'[' | b'!' | b';' | b'>'
+    ) {
+        return true;
+    }
+    ["return", "yield", "default", "await"].iter().any(|word| {
+        before.ends_with(word)
+            && !before[..before.len() - word.len()]
+                .ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == '$')
+    })
+}
+
+/// Byte after `</Name>` or `</>`
+fn closing_tag_end(content: &str, lt: usize) -> Option<usize> {
+    let rest = &content[lt + 2..];
+    let name_len = TAG_NAME_RE.find(rest).map_or(0, |m| m.end());
+    let after = &rest[name_len..];
+    let trimmed = after.trim_start();
+    trimmed
+        .starts_with('>')
+        .then(|| content.len() - trimmed.len() + 1)
+}
+
+/// Parse the tag at `lt`; None unless it is well-formed JSX
+fn parse_element(content: &str, lt: usize) -> Option<JsxElement> {
+    let bytes = content.as_bytes();
+    let name = TAG_NAME_RE.find(&content[lt + 1..])?;
+    let name_text = name.as_str();
+    let last_segment = name_text.rfind(['.', ':']).map_or(0, |i| i + 1);
+    let mut element = JsxElement {
+        name: name_text.to_string(),
+        name_start: lt + 1 + last_segment,
+        start: lt,
+        end: 0,
+        props: Vec::new(),
+        expressions: Vec::new(),
+    };
+
+    let mut index = lt + 1 + name.end();
+    loop {
+        while bytes.get(index)?.is_ascii_whitespace() {
+            index += 1;
+        }
+        match bytes[index] {
+            b'/' if bytes.get(index + 1) == Some(&b'>') => {
+                element.end = index + 2;
+                return Some(element);
+            }
+            b'>' => {
+                // `<T extends X>(x: T) =>` is a generic arrow function, not a tag
+                if bytes.get(index + 1) == Some(&b'(') {
+                    return None;
+                }
+                element.end = index + 1;
+                return Some(element);
+            }
+            b'{' => {
+                // `{...props}`
+                let close = find_matching_delimiter(content, index, JS)?;
+                if !content[index + 1..close].trim_start().starts_with("...") {
+                    return None;
+                }
+                index = close + 1;
+            }
+            _ => {
+                let attribute = ATTRIBUTE_RE.find(&content[index..])?;
+                element.props.push(attribute.as_str().to_string());
+                index += attribute.end();
+                let after = &content[index..];
+                let trimmed = after.trim_start();
+                if !trimmed.starts_with('=') {
+                    continue;
+                }
+                index += after.len() - trimmed.len() + 1;
+                while bytes.get(index)?.is_ascii_whitespace() {
+                    index += 1;
+                }
+                match bytes[index] {
+                    quote @ (b'"' | b'\'') => {
+                        index += 1 + content[index + 1..].find(quote as char)? + 1;
+                    }
+                    b'{' => {
+                        let close = find_matching_delimiter(content, index, JS)?;
+                        let inner = &content[index + 1..close];
+                        let trimmed = inner.trim_start();
+                        let start = index + 1 + inner.len() - trimmed.len();
+                        element
+                            .expressions
+                            .push((start, trimmed.trim_end().to_string()));
+                        index = close + 1;
+                    }
+                    _ => return None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    #[test]
+    fn test_component_uses_props_and_references() {
+        let content = r#"export function UserList({ users, onPick }) {
+  const handleSelect = (u) => onPick(u.id);
+  return (
+    <ul className="list">
+      {users.map((user) => (
+        <UserCard key={user.id} user={user} onSelect={handleSelect} {...rest} />
+      ))}
+      Total: <Badge count={count(users)} onClick={() => track("badge")} disabled />
+      <button onClick={this.reset}>Reset</button>
+    </ul>
+  );
+}
+"#;
+        let ctx = EnrichmentContext::new("src/UserList.tsx", "tsx", content, Path::new("."));
+        let function = ctx.create_symbol(
+            "UserList",
+            SymbolKind::Function,
+            content.find("export").unwrap(),
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let mut results = test_results(vec![function]);
+        enrich_jsx(&ctx, &mut results);
+
+        let function = &results.symbols[0];
+        let metadata = function.metadata.as_ref().unwrap();
+        assert_eq!(metadata["isReactComponent"], true);
+        assert_eq!(
+            metadata["jsxComponents"],
+            json!([
+                {"component": "UserCard", "props": ["key", "user", "onSelect"], "line": 6},
+                {"component": "Badge", "props": ["count", "onClick", "disabled"], "line": 8},
+            ])
+        );
+
+        let identifiers: Vec<(&str, &IdentifierKind)> = results
+            .identifiers
+            .iter()
+            .map(|i| (i.name.as_str(), &i.kind))
+            .collect();
+        assert_eq!(
+            identifiers,
+            [
+                ("id", &IdentifierKind::MemberAccess),
+                ("user", &IdentifierKind::VariableRef),
+                ("handleSelect", &IdentifierKind::VariableRef),
+                ("UserCard", &IdentifierKind::Call),
+                ("count", &IdentifierKind::Call),
+                ("track", &IdentifierKind::Call),
+                ("Badge", &IdentifierKind::Call),
+                ("reset", &IdentifierKind::MemberAccess),
+            ]
+        );
+        assert!(results
+            .identifiers
+            .iter()
+            .all(|i| i.containing_symbol_id.as_deref() == Some(function.id.as_str())));
+    }
+
+    #[test]
+    fn test_comparisons_and_generics_are_not_jsx() {
+        let content = "const ok = a < Limit && b > c;\nconst [user, setUser] = useState<User>(null);\nconst id = <T,>(x: T) => x;\nconst pick = <T extends Item>(x: T) => x;\nfunction f(items) { return items.length < MAX ? 1 : 2; }\n";
+        assert!(find_elements(content).is_empty());
+
+        let content = "const a = <Layout title=\"Home\">Hi <Nav /> there</Layout>;\n";
+        let names: Vec<String> = find_elements(content).into_iter().map(|e| e.name).collect();
+        assert_eq!(names, ["Layout", "Nav"]);
+    }
+}
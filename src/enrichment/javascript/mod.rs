@@ -0,0 +1,151 @@
+// JavaScript and TypeScript enrichment passes
+//
+// Julie's JavaScript and TypeScript extractors handle functions, classes, and
+// ES module syntax; these passes add decorators (with the routes NestJS
+// declares through them), resolvable re-exports for barrel files, CommonJS
+// require()/exports as imports and exports, the URLs of client HTTP calls,
+// the components and props of JSX elements, and React hook calls with their
+// dependency arrays.
+
+mod commonjs;
+mod decorators;
+mod hooks;
+mod http_calls;
+mod jsx;
+mod reexports;
+
+use super::base::{split_top_level, EnrichmentContext, Lexicon};
+use julie_extractors::ExtractionResults;
+use serde_json::{Map, Value};
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+/// JavaScript strings ('...', "...", and `template` literals); // and /* */ comments
+pub(super) const JS: Lexicon = Lexicon {
+    quotes: b"\"'`",
+    escape: Some(b'\\'),
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    decorators::enrich_decorators(ctx, &mut results.symbols);
+    reexports::enrich_reexports(ctx, &mut results.symbols);
+    commonjs::enrich_commonjs(ctx, &mut results.symbols);
+    http_calls::enrich_http_calls(ctx, &mut results.symbols);
+    jsx::enrich_jsx(ctx, results);
+    hooks::enrich_hooks(ctx, results);
+}
+
+/// Extensions tried, in order, for a module specifier without one
+const MODULE_EXTENSIONS: &[&str] = &[".ts", ".tsx", ".d.ts", ".js", ".jsx", ".mjs", ".cjs"];
+
+/// Resolve a relative module specifier (`./user`, `../lib/foo.js`) to a
+/// workspace-relative file: the path as written, then with each module
+/// extension, then its index file. TypeScript's `./foo.js` spelling for
+/// `./foo.ts` is honored. Bare package specifiers resolve to None.
+fn resolve_module(ctx: &EnrichmentContext, specifier: &str) -> Option<String> {
+    if !specifier.starts_with('.') {
+        return None;
+    }
+    let mut candidates = vec![specifier.to_string()];
+    if let Some(stem) = specifier
+        .strip_suffix(".js")
+        .or_else(|| specifier.strip_suffix(".jsx"))
+    {
+        candidates.extend([format!("{}.ts", stem), format!("{}.tsx", stem)]);
+    }
+    let base = specifier.trim_end_matches('/');
+    candidates.extend(
+        MODULE_EXTENSIONS
+            .iter()
+            .map(|ext| format!("{}{}", base, ext)),
+    );
+    candidates.extend(
+        MODULE_EXTENSIONS
+            .iter()
+            .map(|ext| format!("{}/index{}", base, ext)),
+    );
+
+    candidates
+        .iter()
+        .find_map(|candidate| ctx.resolve_relative_to_file(candidate))
+}
+
+/// Contents of a single '...', "...", or `...` (without substitutions) literal
+fn string_literal(value: &str) -> Option<String> {
+    let value = value.trim();
+    let quote = value
+        .chars()
+        .next()
+        .filter(|c| matches!(c, '\'' | '"' | '`'))?;
+    let inner = value.strip_prefix(quote)?.strip_suffix(quote)?;
+    if quote == '`' && inner.contains("${") {
+        return None;
+    }
+    let mut decoded = String::new();
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c == quote => return None,
+            '\\' => match chars.next()? {
+                'n' => decoded.push('\n'),
+                't' => decoded.push('\t'),
+                other => decoded.push(other),
+            },
+            _ => decoded.push(c),
+        }
+    }
+    Some(decoded)
+}
+
+/// Best-effort JSON for a literal expression: strings are decoded, arrays and
+/// object literals are parsed recursively, anything else is kept as source text
+fn literal_value(value: &str) -> Value {
+    let value = value.trim();
+    if let Some(string) = string_literal(value) {
+        return Value::from(string);
+    }
+    if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return Value::Array(
+            split_top_level(inner, b',', JS)
+                .into_iter()
+                .filter(|(_, item)| !item.trim().is_empty())
+                .map(|(_, item)| literal_value(item))
+                .collect(),
+        );
+    }
+    if let Some(inner) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+        let mut object = Map::new();
+        for (_, property) in split_top_level(inner, b',', JS) {
+            let property = property.trim();
+            if property.is_empty() {
+                continue;
+            }
+            let (key, value) = match split_top_level(property, b':', JS).as_slice() {
+                [(_, key), (offset, _), ..] => (key.trim(), &property[*offset..]),
+                _ => (property, property),
+            };
+            let key = string_literal(key).unwrap_or_else(|| key.to_string());
+            object.insert(key, literal_value(value));
+        }
+        return Value::Object(object);
+    }
+    Value::from(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_literal_values() {
+        assert_eq!(
+            literal_value("{ selector: 'app-user', 'styleUrls': [\"./a.css\"], standalone: true }"),
+            json!({ "selector": "app-user", "styleUrls": ["./a.css"], "standalone": "true" })
+        );
+        assert_eq!(literal_value("`users/${id}`"), Value::from("`users/${id}`"));
+        assert_eq!(literal_value("':id'"), Value::from(":id"));
+    }
+}
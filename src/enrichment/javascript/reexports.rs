@@ -0,0 +1,229 @@
+// JavaScript/TypeScript re-exports
+//
+// Barrel files (`index.ts` full of `export * from './user'` and
+// `export { default as Foo } from './foo'`) forward names from other modules.
+// Julie records the export statement but not where its names come from, so
+// find-definition stops at the barrel. This pass parses every re-export and
+// adds an Import symbol per forwarded name with:
+//
+// - metadata.source: the module specifier as written
+// - metadata.resolvedPath / metadata.resolved: the workspace-relative file it
+//   names, trying TypeScript and JavaScript extensions and index files
+// - metadata.exportedName / metadata.originalName (they differ for `as` renames)
+// - metadata.reexport = true
+//
+// `export * from` becomes a single `*` symbol flagged wildcard=true;
+// `export * as ns from` becomes `ns`, flagged wildcard and namespace. Export
+// symbols Julie created for the statement get the same source metadata.
+
+use super::{resolve_module, string_literal, JS};
+use crate::enrichment::base::{
+    code_mask, set_metadata, split_top_level, trim_with_offset, EnrichmentContext, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `export [type] * [as ns] from 'x'` or `export [type] { ... } from 'x'`
+static REEXPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"\bexport\s+(?:type\s+)?(?:(\*)(?:\s+as\s+([A-Za-z_$][\w$]*))?|\{([^{}]*)\})\s*from\s*('[^'\n]*'|"[^"\n]*")"#,
+    )
+    .unwrap()
+});
+
+/// One name forwarded by a re-export statement
+#[derive(Debug, PartialEq)]
+struct Reexport {
+    exported: String,
+    original: String,
+    wildcard: bool,
+    start: usize,
+    end: usize,
+}
+
+pub(super) fn enrich_reexports(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let code = code_mask(ctx.content, JS);
+
+    for captures in REEXPORT_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        if !code[whole.start()] {
+            continue;
+        }
+        let Some(source) = string_literal(&captures[4]) else {
+            continue;
+        };
+        let resolved = resolve_module(ctx, &source);
+        let signature = whole
+            .as_str()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let reexports = match (captures.get(1), captures.get(3)) {
+            (Some(star), _) => {
+                let namespace = captures.get(2);
+                vec![Reexport {
+                    exported: namespace.map_or("*", |m| m.as_str()).to_string(),
+                    original: "*".to_string(),
+                    wildcard: true,
+                    start: namespace.unwrap_or(star).start(),
+                    end: namespace.unwrap_or(star).end(),
+                }]
+            }
+            (None, Some(list)) => parse_specifiers(list.as_str(), list.start()),
+            _ => continue,
+        };
+
+        // Export symbols Julie made for the statement learn where it points
+        for symbol in symbols.iter_mut().filter(|s| {
+            s.kind == SymbolKind::Export
+                && whole.start() <= s.start_byte as usize
+                && (s.start_byte as usize) < whole.end()
+        }) {
+            set_source_metadata(symbol, &source, resolved.as_deref());
+        }
+
+        for reexport in reexports {
+            let existing = symbols.iter().position(|s| {
+                s.kind == SymbolKind::Import
+                    && s.name == reexport.exported
+                    && whole.start() <= s.start_byte as usize
+                    && (s.start_byte as usize) < whole.end()
+            });
+            let index = match existing {
+                Some(index) => index,
+                None => {
+                    symbols.push(ctx.create_symbol(
+                        &reexport.exported,
+                        SymbolKind::Import,
+                        reexport.start,
+                        reexport.end,
+                        SymbolOptions {
+                            signature: Some(signature.clone()),
+                            ..Default::default()
+                        },
+                    ));
+                    symbols.len() - 1
+                }
+            };
+            let symbol = &mut symbols[index];
+            set_source_metadata(symbol, &source, resolved.as_deref());
+            set_metadata(symbol, "reexport", true);
+            set_metadata(symbol, "exportedName", reexport.exported.as_str());
+            set_metadata(symbol, "originalName", reexport.original.as_str());
+            if reexport.wildcard {
+                set_metadata(symbol, "wildcard", true);
+                if reexport.exported != "*" {
+                    set_metadata(symbol, "namespace", true);
+                }
+            }
+        }
+    }
+}
+
+fn set_source_metadata(symbol: &mut Symbol, source: &str, resolved: Option<&str>) {
+    set_metadata(symbol, "source", source);
+    set_metadata(symbol, "resolved", resolved.is_some());
+    if let Some(path) = resolved {
+        set_metadata(symbol, "resolvedPath", path);
+    }
+}
+
+/// `default as Foo, Bar, type Baz as Qux` (offsets absolute in the file)
+fn parse_specifiers(list: &str, offset: usize) -> Vec<Reexport> {
+    split_top_level(list, b',', JS)
+        .into_iter()
+        .filter_map(|(at, segment)| {
+            let (start, written) = trim_with_offset(offset + at, segment);
+            let segment = written.strip_prefix("type ").unwrap_or(written).trim();
+            let mut words = segment.split_whitespace();
+            let original = words.next()?;
+            let exported = match (words.next(), words.next()) {
+                (Some("as"), Some(alias)) => alias,
+                _ => original,
+            };
+            let unquote = |name: &str| string_literal(name).unwrap_or_else(|| name.to_string());
+            let start = start + written.rfind(exported).unwrap_or(0);
+            Some(Reexport {
+                exported: unquote(exported),
+                original: unquote(original),
+                wildcard: false,
+                start,
+                end: start + exported.len(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn metadata<'a>(symbols: &'a [Symbol], name: &str) -> &'a HashMap<String, Value> {
+        symbols
+            .iter()
+            .find(|s| s.name == name)
+            .and_then(|s| s.metadata.as_ref())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_barrel_reexports() {
+        let root = TempDir::new().unwrap();
+        fs::create_dir_all(root.path().join("src/models/user")).unwrap();
+        fs::write(root.path().join("src/models/user/index.ts"), "").unwrap();
+        fs::write(root.path().join("src/models/foo.tsx"), "").unwrap();
+        let content = "export * from './user';\nexport { default as Foo, Bar } from \"./foo\";\nexport * as util from './util';\n";
+        let ctx = EnrichmentContext::new("src/models/index.ts", "typescript", content, root.path());
+        let mut symbols = Vec::new();
+        enrich_reexports(&ctx, &mut symbols);
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["*", "Foo", "Bar", "util"]);
+
+        let star = metadata(&symbols, "*");
+        assert_eq!(star["wildcard"], Value::Bool(true));
+        assert_eq!(
+            star["resolvedPath"],
+            Value::from("src/models/user/index.ts")
+        );
+
+        let foo = metadata(&symbols, "Foo");
+        assert_eq!(foo["originalName"], Value::from("default"));
+        assert_eq!(foo["exportedName"], Value::from("Foo"));
+        assert_eq!(foo["resolvedPath"], Value::from("src/models/foo.tsx"));
+        assert_eq!(ctx.symbol_text(&symbols[1]), "Foo");
+
+        let util = metadata(&symbols, "util");
+        assert_eq!(util["namespace"], Value::Bool(true));
+        assert_eq!(util["resolved"], Value::Bool(false));
+        assert_eq!(util["source"], Value::from("./util"));
+    }
+
+    #[test]
+    fn test_existing_export_symbols_are_annotated() {
+        let root = TempDir::new().unwrap();
+        let content = "export { Thing as Other } from 'some-package';\nexport const local = 1;\n";
+        let ctx = EnrichmentContext::new("index.js", "javascript", content, root.path());
+        let export =
+            ctx.create_symbol("Other", SymbolKind::Export, 9, 23, SymbolOptions::default());
+        let mut symbols = vec![export];
+        enrich_reexports(&ctx, &mut symbols);
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(
+            symbols[0].metadata.as_ref().unwrap()["source"],
+            Value::from("some-package")
+        );
+        assert_eq!(symbols[1].kind, SymbolKind::Import);
+        assert_eq!(
+            symbols[1].metadata.as_ref().unwrap()["originalName"],
+            Value::from("Thing")
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// Symbol kind filtering - keep only the kinds a caller indexes
+//
+// Opt-in through EnrichmentOptions::symbol_kinds. Runs last, after IDs are
+// stable and identifiers resolved, so everything that survives looks exactly
+// as it would unfiltered. Symbols whose kind is not in the allow-list are
+// dropped; what happens to the symbols nested in them depends on
+// include_children:
+//
+// - true: each descendant is judged by its own kind. A kept symbol keeps its
+//   parent_id, and every dropped ancestor on its parent chain is emitted as a
+//   stub instead (same id, name, kind, position, and parent_id; no signature,
+//   doc comment, code context, or other metadata; metadata.filteredStub), so
+//   the hierarchy still resolves. A method in a skipped class comes out under
+//   a stub of the class.
+// - false: a dropped symbol takes its whole subtree with it, and no stubs are
+//   needed because every kept symbol's ancestors are kept.
+//
+// Identifiers, relationships, and types are left alone: they keep the IDs of
+// the symbols they were attributed to (containing_symbol_id, target_symbol_id,
+// from/to) even when those symbols were filtered out.
+
+use julie_extractors::{ExtractionResults, SymbolKind};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Which symbol kinds to return
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SymbolKindFilter {
+    /// Kinds kept; everything else is dropped or stubbed
+    pub kinds: Vec<SymbolKind>,
+    /// Judge symbols nested in a dropped one by their own kind, stubbing the
+    /// dropped ancestors; when false the subtree is dropped with it
+    pub include_children: bool,
+}
+
+impl SymbolKindFilter {
+    /// Keep `kinds`, including those nested in dropped symbols
+    pub fn new(kinds: impl IntoIterator<Item = SymbolKind>) -> Self {
+        SymbolKindFilter {
+            kinds: kinds.into_iter().collect(),
+            include_children: true,
+        }
+    }
+}
+
+/// Drop or stub every symbol whose kind `filter` does not allow
+pub fn filter_symbol_kinds(filter: &SymbolKindFilter, results: &mut ExtractionResults) {
+    let symbols = &results.symbols;
+    let index: HashMap<&str, usize> = symbols
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.id.as_str(), i))
+        .collect();
+    let parent = |i: usize| {
+        symbols[i]
+            .parent_id
+            .as_deref()
+            .and_then(|id| index.get(id).copied())
+    };
+    // The parent chain of `i`, bounded in case parent_ids form a cycle
+    let ancestors =
+        |i: usize| std::iter::successors(parent(i), move |&p| parent(p)).take(symbols.len());
+
+    let allowed: Vec<bool> = symbols
+        .iter()
+        .map(|s| filter.kinds.contains(&s.kind))
+        .collect();
+    let kept: Vec<bool> = (0..symbols.len())
+        .map(|i| allowed[i] && (filter.include_children || ancestors(i).all(|a| allowed[a])))
+        .collect();
+    let mut stubbed = vec![false; symbols.len()];
+    for i in (0..symbols.len()).filter(|&i| kept[i]) {
+        for ancestor in ancestors(i) {
+            if kept[ancestor] || stubbed[ancestor] {
+                break;
+            }
+            stubbed[ancestor] = true;
+        }
+    }
+
+    let mut position = 0;
+    results.symbols.retain_mut(|symbol| {
+        let i = position;
+        position += 1;
+        if stubbed[i] {
+            symbol.signature = None;
+            symbol.doc_comment = None;
+            symbol.code_context = None;
+            symbol.semantic_group = None;
+            symbol.metadata = Some(HashMap::from([(
+                "filteredStub".to_string(),
+                Value::Bool(true),
+            )]));
+        }
+        kept[i] || stubbed[i]
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, EnrichmentContext, SymbolOptions};
+    use julie_extractors::IdentifierKind;
+    use std::path::Path;
+
+    const SOURCE: &str = "class Outer:\n    class Inner:\n        def run(self):\n            count = 1\n\ndef main():\n    pass\n";
+
+    /// Outer > Inner > run > count, and main
+    fn results(ctx: &EnrichmentContext) -> ExtractionResults {
+        let mut symbols = Vec::new();
+        let mut parent_id = None;
+        for (name, kind) in [
+            ("Outer", SymbolKind::Class),
+            ("Inner", SymbolKind::Class),
+            ("run", SymbolKind::Method),
+            ("count", SymbolKind::Variable),
+        ] {
+            let start = SOURCE.find(name).unwrap();
+            let symbol = ctx.create_symbol(
+                name,
+                kind,
+                start,
+                start + name.len(),
+                SymbolOptions {
+                    signature: Some(name.to_string()),
+                    parent_id: parent_id.take(),
+                    ..Default::default()
+                },
+            );
+            parent_id = Some(symbol.id.clone());
+            symbols.push(symbol);
+        }
+        let start = SOURCE.find("main").unwrap();
+        symbols.push(ctx.create_symbol(
+            "main",
+            SymbolKind::Function,
+            start,
+            start + 4,
+            SymbolOptions::default(),
+        ));
+        let at = SOURCE.find("count").unwrap();
+        let identifiers = vec![ctx.create_identifier(
+            "count",
+            IdentifierKind::VariableRef,
+            at,
+            at + 5,
+            Some(symbols[3].id.clone()),
+        )];
+        ExtractionResults {
+            identifiers,
+            ..test_results(symbols)
+        }
+    }
+
+    #[test]
+    fn test_dropped_ancestors_of_kept_symbols_become_stubs() {
+        let ctx = EnrichmentContext::new("app.py", "python", SOURCE, Path::new("."));
+        let mut results = results(&ctx);
+        let original = results.symbols.clone();
+        filter_symbol_kinds(
+            &SymbolKindFilter::new([SymbolKind::Method, SymbolKind::Function]),
+            &mut results,
+        );
+
+        let names: Vec<&str> = results.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Outer", "Inner", "run", "main"]);
+        for (filtered, original) in results.symbols.iter().zip(original.iter().take(3)) {
+            assert_eq!(filtered.id, original.id);
+            assert_eq!(filtered.parent_id, original.parent_id);
+        }
+        let stubs: Vec<bool> = results
+            .symbols
+            .iter()
+            .map(|s| {
+                s.metadata
+                    .as_ref()
+                    .is_some_and(|m| m.contains_key("filteredStub"))
+            })
+            .collect();
+        assert_eq!(stubs, vec![true, true, false, false]);
+        assert_eq!(results.symbols[0].signature, None);
+        assert_eq!(results.symbols[2].signature.as_deref(), Some("run"));
+        // Still attributed to the dropped variable
+        assert_eq!(
+            results.identifiers[0].containing_symbol_id.as_ref(),
+            Some(&original[3].id)
+        );
+    }
+
+    #[test]
+    fn test_without_children_a_dropped_symbol_takes_its_subtree() {
+        let ctx = EnrichmentContext::new("app.py", "python", SOURCE, Path::new("."));
+        let cases = [
+            (vec![SymbolKind::Method, SymbolKind::Function], vec!["main"]),
+            (
+                vec![SymbolKind::Class, SymbolKind::Method, SymbolKind::Function],
+                vec!["Outer", "Inner", "run", "main"],
+            ),
+        ];
+        for (kinds, expected) in cases {
+            let mut results = results(&ctx);
+            let filter = SymbolKindFilter {
+                include_children: false,
+                ..SymbolKindFilter::new(kinds)
+            };
+            filter_symbol_kinds(&filter, &mut results);
+            let names: Vec<&str> = results.symbols.iter().map(|s| s.name.as_str()).collect();
+            assert_eq!(names, expected);
+            assert!(results.symbols.iter().all(|s| s.metadata.is_none()));
+        }
+    }
+}
@@ -0,0 +1,397 @@
+// Kotlin annotations
+//
+// Julie drops the annotations on Kotlin declarations. This pass reads each
+// annotation (with its use-site target, `@field:Autowired`,
+// `@get:JsonProperty("name")`, kept in the text) and attaches it to the class,
+// object, function, property, or constructor declared after it:
+//
+// - metadata.annotations on the declaration (raw texts, in source order);
+//   annotations on `val`/`var` constructor parameters are recorded by the
+//   classes pass, and those on other parameters, types, and expressions are
+//   ignored
+// - Spring mappings (@GetMapping("/users"), @RequestMapping(path = [...],
+//   method = [RequestMethod.POST])): metadata.routes, httpMethod, and
+//   fullRoutes, the same convention as Java controllers
+// - @Composable functions: metadata.isComposable=true
+// - @Inject / @Autowired: metadata.injected=true on the declaration, also on
+//   constructor properties carrying them; on a primary constructor
+//   (`class Repo @Inject constructor(...)`) the class gets
+//   metadata.constructorAnnotations and injectConstructor=true instead
+
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, paren_depths, set_metadata,
+    split_top_level, CodeBytes, EnrichmentContext, Lexicon,
+};
+use crate::enrichment::java::{apply_routes, link_route_prefixes, route_info, RouteInfo};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+/// `@target:Qualified.Name`; the argument list is matched separately
+static ANNOTATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"@(?:(field|get|set|param|property|setparam|delegate|receiver|file)\s*:\s*)?([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)",
+    )
+    .unwrap()
+});
+
+/// Modifiers and the declaration keyword after an annotation
+static DECLARATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^\s*(?:(?:public|private|protected|internal|override|open|final|abstract|sealed|data|inner|enum|value|annotation|companion|const|lateinit|suspend|inline|operator|infix|tailrec|external|expect|actual)\s+)*\b(fun|class|interface|object|val|var|constructor|typealias)\b",
+    )
+    .unwrap()
+});
+
+/// `name = value` argument of an annotation
+static ELEMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)^([A-Za-z_]\w*)\s*=\s*([^=].*)$").unwrap());
+
+/// Annotations that mark an injection point
+const INJECT_ANNOTATIONS: &[&str] = &["Inject", "Autowired"];
+
+#[derive(Debug)]
+struct KotlinAnnotation<'a> {
+    start: usize,
+    end: usize,
+    /// Simple name, without package or use-site target
+    name: String,
+    text: &'a str,
+    elements: Vec<(String, &'a str)>,
+    /// Declaration keyword following the annotation and its start
+    keyword: Option<(&'a str, usize)>,
+}
+
+pub(super) fn enrich_annotations(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let mut route_targets: Vec<usize> = Vec::new();
+
+    for annotation in find_annotations(ctx.content) {
+        let Some((keyword, keyword_start)) = annotation.keyword else {
+            continue;
+        };
+        let Some(target) = annotated_symbol(ctx.content, symbols, keyword_start) else {
+            continue;
+        };
+        let symbol = &mut symbols[target];
+        let injected = INJECT_ANNOTATIONS.contains(&annotation.name.as_str());
+
+        // The primary constructor has no symbol of its own
+        if keyword == "constructor" && symbol.kind != SymbolKind::Constructor {
+            append_metadata(symbol, "constructorAnnotations", annotation.text);
+            if injected {
+                set_metadata(symbol, "injectConstructor", true);
+            }
+            continue;
+        }
+
+        append_metadata(symbol, "annotations", annotation.text);
+        if injected {
+            set_metadata(symbol, "injected", true);
+        }
+        if annotation.name == "Composable"
+            && matches!(symbol.kind, SymbolKind::Function | SymbolKind::Method)
+        {
+            set_metadata(symbol, "isComposable", true);
+        }
+        let routes = route_info(&annotation.name, &annotation.elements);
+        if routes != RouteInfo::default() {
+            apply_routes(symbol, &routes);
+            route_targets.push(target);
+        }
+    }
+
+    link_route_prefixes(symbols, &route_targets);
+    mark_injected_constructor_properties(symbols);
+}
+
+fn find_annotations(content: &str) -> Vec<KotlinAnnotation<'_>> {
+    let code = code_mask(content, Lexicon::C_LIKE);
+    let paren_depth = paren_depths(content, Lexicon::C_LIKE);
+    let mut annotations = Vec::new();
+    let mut resume = 0;
+
+    for captures in ANNOTATION_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        let start = whole.start();
+        // Nested annotations are part of their outer annotation's text
+        if start < resume || !code[start] {
+            continue;
+        }
+
+        let mut end = whole.end();
+        let mut elements = Vec::new();
+        if content.as_bytes().get(end) == Some(&b'(') {
+            if let Some(close) = find_matching_delimiter(content, end, Lexicon::C_LIKE) {
+                elements = parse_elements(&content[end + 1..close]);
+                end = close + 1;
+            }
+        }
+        resume = end;
+
+        // File annotations and annotations on parameters annotate no symbol
+        if captures.get(1).is_some_and(|t| t.as_str() == "file") || paren_depth[start] > 0 {
+            continue;
+        }
+        annotations.push(KotlinAnnotation {
+            start,
+            end,
+            name: captures[2].rsplit('.').next().unwrap().to_string(),
+            text: &content[start..end],
+            elements,
+            keyword: None,
+        });
+    }
+
+    // Each annotation annotates the declaration after the run it belongs to
+    let mut keyword = None;
+    for index in (0..annotations.len()).rev() {
+        let end = annotations[index].end;
+        let next_start = annotations.get(index + 1).map(|a| a.start);
+        let between = &content[end..next_start.unwrap_or(content.len())];
+        if next_start.is_none() || !between.trim().is_empty() {
+            keyword = DECLARATION_RE.captures(&content[end..]).map(|c| {
+                let found = c.get(1).unwrap();
+                (found.as_str(), end + found.start())
+            });
+        }
+        annotations[index].keyword = keyword;
+    }
+    annotations
+}
+
+fn parse_elements(arguments: &str) -> Vec<(String, &str)> {
+    split_top_level(arguments, b',', Lexicon::C_LIKE)
+        .into_iter()
+        .map(|(_, segment)| segment.trim())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match ELEMENT_RE.captures(segment) {
+            Some(captures) => {
+                let value = captures.get(2).unwrap();
+                (captures[1].to_string(), &segment[value.start()..])
+            }
+            None => ("value".to_string(), segment),
+        })
+        .collect()
+}
+
+/// Index of the declaration whose keyword starts at `keyword_start`: the
+/// latest-starting declaration symbol that begins before its header ends and
+/// extends past the keyword
+fn annotated_symbol(content: &str, symbols: &[Symbol], keyword_start: usize) -> Option<usize> {
+    let header_end = CodeBytes::new(content, keyword_start, Lexicon::C_LIKE)
+        .find(|&(_, byte)| matches!(byte, b'(' | b'{' | b'=' | b':' | b';' | b'\n'))
+        .map_or(content.len(), |(index, _)| index);
+    symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| {
+            matches!(
+                s.kind,
+                SymbolKind::Class
+                    | SymbolKind::Interface
+                    | SymbolKind::Enum
+                    | SymbolKind::Struct
+                    | SymbolKind::Method
+                    | SymbolKind::Constructor
+                    | SymbolKind::Function
+                    | SymbolKind::Field
+                    | SymbolKind::Property
+                    | SymbolKind::Constant
+                    | SymbolKind::Variable
+                    | SymbolKind::Type
+            )
+        })
+        .filter(|(_, s)| s.start_byte as usize <= header_end && s.end_byte as usize > keyword_start)
+        .max_by_key(|(_, s)| (s.start_byte, std::cmp::Reverse(s.end_byte)))
+        .map(|(index, _)| index)
+}
+
+/// `class Service(@field:Inject val repo: Repo)`: the classes pass recorded
+/// the annotation texts on the property
+fn mark_injected_constructor_properties(symbols: &mut [Symbol]) {
+    for symbol in symbols.iter_mut() {
+        let injected = symbol.metadata.as_ref().is_some_and(|m| {
+            m.get("constructorProperty") == Some(&Value::Bool(true))
+                && m.get("annotations")
+                    .and_then(Value::as_array)
+                    .is_some_and(|annotations| {
+                        annotations.iter().filter_map(Value::as_str).any(|text| {
+                            ANNOTATION_RE.captures(text).is_some_and(|c| {
+                                let name = c[2].rsplit('.').next().unwrap();
+                                INJECT_ANNOTATIONS.contains(&name)
+                            })
+                        })
+                    })
+        });
+        if injected {
+            set_metadata(symbol, "injected", true);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use serde_json::json;
+    use std::path::Path;
+
+    /// A symbol from the first occurrence of `from` through the following `to`
+    fn declare(
+        ctx: &EnrichmentContext,
+        name: &str,
+        kind: SymbolKind,
+        from: &str,
+        to: &str,
+        parent: Option<&Symbol>,
+    ) -> Symbol {
+        let start = ctx.content.find(from).unwrap();
+        let end = ctx.content[start..].find(to).unwrap() + start + to.len();
+        ctx.create_symbol(
+            name,
+            kind,
+            start,
+            end,
+            SymbolOptions {
+                parent_id: parent.map(|p| p.id.clone()),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_spring_controller_routes_and_injection() {
+        let content = r#"@RestController
+@RequestMapping("/api/users")
+class UserController @Autowired constructor(private val service: UserService) {
+    @field:Autowired
+    lateinit var audit: AuditLog
+
+    @GetMapping("/{id}")
+    fun get(@PathVariable id: Long): User = service.find(id)
+
+    @RequestMapping(path = ["/", "/all"], method = [RequestMethod.POST])
+    suspend fun create() {}
+}
+"#;
+        let ctx = EnrichmentContext::new("UserController.kt", "kotlin", content, Path::new("."));
+        let class = declare(
+            &ctx,
+            "UserController",
+            SymbolKind::Class,
+            "class UserController",
+            "{}\n}",
+            None,
+        );
+        let mut symbols = vec![
+            declare(
+                &ctx,
+                "audit",
+                SymbolKind::Property,
+                "lateinit",
+                "AuditLog",
+                Some(&class),
+            ),
+            declare(
+                &ctx,
+                "get",
+                SymbolKind::Method,
+                "fun get",
+                "(id)",
+                Some(&class),
+            ),
+            declare(
+                &ctx,
+                "create",
+                SymbolKind::Method,
+                "suspend fun",
+                "{}",
+                Some(&class),
+            ),
+            class,
+        ];
+        enrich_annotations(&ctx, &mut symbols);
+
+        let meta = |index: usize| symbols[index].metadata.clone().unwrap();
+        assert_eq!(meta(0)["annotations"], json!(["@field:Autowired"]));
+        assert_eq!(meta(0)["injected"], json!(true));
+
+        assert_eq!(meta(1)["annotations"], json!(["@GetMapping(\"/{id}\")"]));
+        assert_eq!(meta(1)["httpMethod"], json!("GET"));
+        assert_eq!(meta(1)["fullRoutes"], json!(["/api/users/{id}"]));
+
+        assert_eq!(meta(2)["httpMethod"], json!("POST"));
+        assert_eq!(
+            meta(2)["fullRoutes"],
+            json!(["/api/users", "/api/users/all"])
+        );
+
+        assert_eq!(
+            meta(3)["annotations"],
+            json!(["@RestController", "@RequestMapping(\"/api/users\")"])
+        );
+        assert_eq!(meta(3)["constructorAnnotations"], json!(["@Autowired"]));
+        assert_eq!(meta(3)["injectConstructor"], json!(true));
+    }
+
+    #[test]
+    fn test_composable_and_use_site_targets() {
+        let content = r#"@file:JvmName("Screens")
+package app
+
+data class Dto(@get:JsonProperty("user_name") val name: String) {
+    @get:JsonProperty("display")
+    val display: String get() = name
+}
+
+@Composable
+@Preview(showBackground = true)
+fun Greeting(onClick: @Composable () -> Unit) {
+    Text("hi")
+}
+"#;
+        let ctx = EnrichmentContext::new("Screens.kt", "kotlin", content, Path::new("."));
+        let dto = declare(
+            &ctx,
+            "Dto",
+            SymbolKind::Class,
+            "data class",
+            "name\n}",
+            None,
+        );
+        let mut symbols = vec![
+            declare(
+                &ctx,
+                "display",
+                SymbolKind::Property,
+                "val display",
+                "= name",
+                Some(&dto),
+            ),
+            declare(
+                &ctx,
+                "Greeting",
+                SymbolKind::Function,
+                "fun Greeting",
+                "\n}",
+                None,
+            ),
+            dto,
+        ];
+        enrich_annotations(&ctx, &mut symbols);
+
+        let meta = |index: usize| symbols[index].metadata.clone().unwrap_or_default();
+        assert_eq!(
+            meta(0)["annotations"],
+            json!(["@get:JsonProperty(\"display\")"])
+        );
+        assert_eq!(
+            meta(1)["annotations"],
+            json!(["@Composable", "@Preview(showBackground = true)"])
+        );
+        assert_eq!(meta(1)["isComposable"], json!(true));
+        // The file annotation and the constructor parameter's are not the class's
+        assert_eq!(meta(2).get("annotations"), None);
+    }
+}
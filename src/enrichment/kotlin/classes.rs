@@ -0,0 +1,377 @@
+// Kotlin class modifiers, constructor properties, and sealed hierarchies
+//
+// Julie's Kotlin class symbols don't say whether a class is `data` or
+// `sealed`, and its primary-constructor properties drop their defaults and
+// annotations. For every class, interface, and object declaration this pass:
+//
+// - records the declaration modifiers (data, sealed, inner, abstract, enum,
+//   open, value, ...) as metadata.modifiers
+// - makes every `val`/`var` primary-constructor parameter a Property parented
+//   to the class (reusing Julie's symbol when it has one), with
+//   metadata.constructorProperty, mutable, defaultValue, and annotations; on a
+//   data class each also gets metadata.component (its componentN index) and
+//   the class lists them in order as metadata.components, which are also
+//   copy()'s parameters
+// - emits Extends (sealed class parent) or Implements (sealed interface
+//   parent) from each subclass or object in the file to its sealed parent,
+//   with metadata sealed=true, and lists the subclasses on the parent as
+//   metadata.sealedSubclasses so a `when` can be checked for exhaustiveness
+
+use super::constructor_properties::enrich_constructor_properties;
+use super::{closing_angle, type_base_name};
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, set_metadata, split_top_level,
+    EnrichmentContext, Lexicon,
+};
+use julie_extractors::{ExtractionResults, RelationshipKind, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Modifiers and annotations, the declaration keyword, and the name
+static DECLARATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*((?:(?:@[\w.]+(?:\([^()\n]*\))?|public|private|protected|internal|data|sealed|inner|abstract|enum|open|final|value|annotation|companion|fun|expect|actual)\s+)*)(class|interface|object)\s+([A-Za-z_]\w*)",
+    )
+    .unwrap()
+});
+
+/// `private constructor`, `@Inject constructor` before the parameter list
+static CONSTRUCTOR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:(?:@[\w.]+(?:\([^()\n]*\))?|public|private|protected|internal)\s+)*constructor\s*",
+    )
+    .unwrap()
+});
+
+/// Modifiers recorded in metadata.modifiers; annotations and visibility are not
+const CLASS_MODIFIERS: &[&str] = &[
+    "data",
+    "sealed",
+    "inner",
+    "abstract",
+    "enum",
+    "open",
+    "final",
+    "value",
+    "annotation",
+    "companion",
+    "fun",
+    "expect",
+    "actual",
+];
+
+/// Kinds Julie gives Kotlin classes, interfaces, and objects
+const CLASS_KINDS: &[SymbolKind] = &[
+    SymbolKind::Class,
+    SymbolKind::Interface,
+    SymbolKind::Enum,
+    SymbolKind::Struct,
+];
+
+/// A class-like declaration header
+#[derive(Debug)]
+struct Declaration {
+    keyword_start: usize,
+    keyword: String,
+    name: String,
+    modifiers: Vec<String>,
+    /// Byte range of the primary constructor's parameter list, parentheses excluded
+    parameters: Option<(usize, usize)>,
+    /// Supertypes as written: `State()`, `Comparable<Money>`
+    supertypes: Vec<String>,
+}
+
+pub(super) fn enrich_classes(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let declarations = find_declarations(ctx.content);
+    let mut class_ids = Vec::new();
+    for declaration in &declarations {
+        let Some(index) = results.symbols.iter().position(|s| {
+            CLASS_KINDS.contains(&s.kind)
+                && s.name == declaration.name
+                && (s.start_byte as usize) <= declaration.keyword_start
+                && declaration.keyword_start < s.end_byte as usize
+        }) else {
+            class_ids.push(None);
+            continue;
+        };
+        let class = &mut results.symbols[index];
+        if !declaration.modifiers.is_empty() {
+            set_metadata(class, "modifiers", declaration.modifiers.clone());
+        }
+        let class_id = class.id.clone();
+        class_ids.push(Some(class_id.clone()));
+
+        if let Some(parameters) = declaration.parameters {
+            let is_data = declaration.modifiers.iter().any(|m| m == "data");
+            enrich_constructor_properties(
+                ctx,
+                &mut results.symbols,
+                &class_id,
+                parameters,
+                is_data,
+            );
+        }
+    }
+
+    link_sealed_hierarchy(ctx, results, &declarations, &class_ids);
+}
+
+/// Extends/Implements from subclasses and objects to sealed parents in the file
+fn link_sealed_hierarchy(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    declarations: &[Declaration],
+    class_ids: &[Option<String>],
+) {
+    let sealed: Vec<(&Declaration, &String)> = declarations
+        .iter()
+        .zip(class_ids)
+        .filter(|(d, _)| d.modifiers.iter().any(|m| m == "sealed"))
+        .filter_map(|(d, id)| Some((d, id.as_ref()?)))
+        .collect();
+    if sealed.is_empty() {
+        return;
+    }
+
+    for (declaration, class_id) in declarations.iter().zip(class_ids) {
+        let Some(class_id) = class_id else {
+            continue;
+        };
+        for supertype in &declaration.supertypes {
+            let base = type_base_name(supertype.split('(').next().unwrap_or(supertype));
+            let Some((parent, parent_id)) = sealed.iter().find(|(d, _)| d.name == base) else {
+                continue;
+            };
+            if *parent_id == class_id {
+                continue;
+            }
+            let kind = if parent.keyword == "interface" {
+                RelationshipKind::Implements
+            } else {
+                RelationshipKind::Extends
+            };
+            let mut metadata = HashMap::new();
+            metadata.insert("sealed".to_string(), Value::Bool(true));
+            results.relationships.push(ctx.create_relationship(
+                class_id,
+                parent_id,
+                kind,
+                declaration.keyword_start,
+                Some(metadata),
+            ));
+            if let Some(parent_symbol) = results.symbols.iter_mut().find(|s| &s.id == *parent_id) {
+                append_metadata(parent_symbol, "sealedSubclasses", declaration.name.clone());
+            }
+        }
+    }
+}
+
+fn find_declarations(content: &str) -> Vec<Declaration> {
+    let code = code_mask(content, Lexicon::C_LIKE);
+    let bytes = content.as_bytes();
+    let mut declarations = Vec::new();
+    for captures in DECLARATION_RE.captures_iter(content) {
+        let keyword = captures.get(2).unwrap();
+        let name = captures.get(3).unwrap();
+        if !code[keyword.start()] {
+            continue;
+        }
+        let modifiers: Vec<String> = captures[1]
+            .split_whitespace()
+            .filter(|word| CLASS_MODIFIERS.contains(word))
+            .map(str::to_string)
+            .collect();
+
+        // Type parameters, then an optional `private constructor` / `@Inject constructor`
+        let mut cursor = skip_whitespace(content, name.end());
+        if bytes.get(cursor) == Some(&b'<') {
+            let Some(close) = closing_angle(content, cursor) else {
+                continue;
+            };
+            cursor = skip_whitespace(content, close + 1);
+        }
+        if let Some(constructor) = CONSTRUCTOR_RE.find(&content[cursor..]) {
+            cursor += constructor.end();
+        }
+
+        let mut parameters = None;
+        if bytes.get(cursor) == Some(&b'(') {
+            let Some(close) = find_matching_delimiter(content, cursor, Lexicon::C_LIKE) else {
+                continue;
+            };
+            parameters = Some((cursor + 1, close));
+            cursor = skip_whitespace(content, close + 1);
+        }
+
+        let mut supertypes = Vec::new();
+        if bytes.get(cursor) == Some(&b':') {
+            let end = (cursor + 1..content.len())
+                .find(|&i| code[i] && matches!(bytes[i], b'{' | b'\n' | b';'))
+                .unwrap_or(content.len());
+            let list = &content[cursor + 1..end];
+            let list = list.split(" where ").next().unwrap_or(list);
+            supertypes = split_top_level(list, b',', Lexicon::C_LIKE)
+                .into_iter()
+                .map(|(_, supertype)| supertype.trim().to_string())
+                .filter(|supertype| !supertype.is_empty())
+                .collect();
+        }
+
+        declarations.push(Declaration {
+            keyword_start: keyword.start(),
+            keyword: keyword.as_str().to_string(),
+            name: name.as_str().to_string(),
+            modifiers,
+            parameters,
+            supertypes,
+        });
+    }
+    declarations
+}
+
+fn skip_whitespace(content: &str, from: usize) -> usize {
+    from + (content[from..].len() - content[from..].trim_start().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use julie_extractors::Symbol;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"sealed interface UiState {
+    object Loading : UiState
+    data class Loaded(
+        @SerialName("items") val items: List<Item> = emptyList(),
+        var page: Int = 1,
+        retries: Int,
+    ) : UiState
+}
+
+sealed class Result<out T> {
+    data class Success<T>(val value: T) : Result<T>()
+    class Failure(val error: Throwable) : Result<Nothing>()
+}
+
+abstract class Repository : Closeable
+"#;
+
+    fn declared(ctx: &EnrichmentContext, name: &str, kind: SymbolKind, text: &str) -> Symbol {
+        let start = SOURCE.find(text).unwrap();
+        let line_end = SOURCE[start..].find('\n').unwrap() + start;
+        let end = if SOURCE[start..line_end].ends_with('{') {
+            SOURCE[start..].find("\n}").unwrap() + start + 2
+        } else if SOURCE[start..line_end].ends_with('(') {
+            SOURCE[start..].find(") : UiState").unwrap() + start + 11
+        } else {
+            line_end
+        };
+        ctx.create_symbol(name, kind, start, end, SymbolOptions::default())
+    }
+
+    fn enriched() -> ExtractionResults {
+        let ctx = EnrichmentContext::new("State.kt", "kotlin", SOURCE, Path::new("."));
+        let mut results = test_results(vec![
+            declared(
+                &ctx,
+                "UiState",
+                SymbolKind::Interface,
+                "sealed interface UiState",
+            ),
+            declared(&ctx, "Loading", SymbolKind::Class, "object Loading"),
+            declared(&ctx, "Loaded", SymbolKind::Class, "data class Loaded"),
+            declared(&ctx, "Result", SymbolKind::Class, "sealed class Result"),
+            declared(&ctx, "Success", SymbolKind::Class, "data class Success"),
+            declared(&ctx, "Failure", SymbolKind::Class, "class Failure"),
+            declared(
+                &ctx,
+                "Repository",
+                SymbolKind::Class,
+                "abstract class Repository",
+            ),
+            // Julie's version of a constructor property: no default, no annotation
+            declared(&ctx, "value", SymbolKind::Field, "value: T"),
+        ]);
+        enrich_classes(&ctx, &mut results);
+        results
+    }
+
+    fn named<'a>(results: &'a ExtractionResults, name: &str) -> &'a Symbol {
+        results.symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_sealed_subclasses_link_to_their_parent() {
+        let results = enriched();
+        let links: Vec<(&str, &str, &RelationshipKind)> = results
+            .relationships
+            .iter()
+            .map(|r| {
+                let name = |id: &str| {
+                    results
+                        .symbols
+                        .iter()
+                        .find(|s| s.id == id)
+                        .unwrap()
+                        .name
+                        .as_str()
+                };
+                (name(&r.from_symbol_id), name(&r.to_symbol_id), &r.kind)
+            })
+            .collect();
+        assert_eq!(
+            links,
+            [
+                ("Loading", "UiState", &RelationshipKind::Implements),
+                ("Loaded", "UiState", &RelationshipKind::Implements),
+                ("Success", "Result", &RelationshipKind::Extends),
+                ("Failure", "Result", &RelationshipKind::Extends),
+            ]
+        );
+        let result = named(&results, "Result").metadata.as_ref().unwrap();
+        assert_eq!(result["modifiers"], serde_json::json!(["sealed"]));
+        assert_eq!(
+            result["sealedSubclasses"],
+            serde_json::json!(["Success", "Failure"])
+        );
+        let repository = named(&results, "Repository").metadata.as_ref().unwrap();
+        assert_eq!(repository["modifiers"], serde_json::json!(["abstract"]));
+    }
+
+    #[test]
+    fn test_data_class_constructor_properties() {
+        let results = enriched();
+        let loaded = named(&results, "Loaded");
+        let meta = loaded.metadata.as_ref().unwrap();
+        assert_eq!(meta["modifiers"], serde_json::json!(["data"]));
+        assert_eq!(meta["components"], serde_json::json!(["items", "page"]));
+
+        let items = named(&results, "items");
+        assert_eq!(items.kind, SymbolKind::Property);
+        assert_eq!(items.parent_id.as_ref(), Some(&loaded.id));
+        let items_meta = items.metadata.as_ref().unwrap();
+        assert_eq!(items_meta["defaultValue"], Value::from("emptyList()"));
+        assert_eq!(
+            items_meta["annotations"],
+            serde_json::json!(["@SerialName(\"items\")"])
+        );
+        assert_eq!(items_meta["component"], 1);
+        assert_eq!(
+            named(&results, "page").metadata.as_ref().unwrap()["mutable"],
+            true
+        );
+        // Plain parameters are not properties
+        assert!(results.symbols.iter().all(|s| s.name != "retries"));
+
+        // Julie's field symbol is reused
+        let value = named(&results, "value");
+        assert_eq!(value.kind, SymbolKind::Property);
+        assert_eq!(
+            value.parent_id.as_ref(),
+            Some(&named(&results, "Success").id)
+        );
+    }
+}
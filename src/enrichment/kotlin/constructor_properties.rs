@@ -0,0 +1,154 @@
+// Kotlin primary-constructor properties
+//
+// Every `val`/`var` parameter of a primary constructor becomes a Property
+// parented to its class, reusing Julie's symbol when it has one; Julie drops
+// the default value and annotations, which land in metadata here. On a data
+// class each property is also a componentN, in declaration order.
+
+use crate::enrichment::base::{
+    set_metadata, split_top_level, trim_with_offset, EnrichmentContext, Lexicon, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// A constructor parameter: annotations and modifiers, `val`/`var`, the name
+static PARAMETER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^((?:@[\w.:]+(?:\([^()]*\))?\s*)*)((?:(?:public|private|protected|internal|override|open|final|vararg)\s+)*)(?:(val|var)\s+)?([A-Za-z_]\w*)\s*:",
+    )
+    .unwrap()
+});
+
+static ANNOTATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@[\w.:]+(?:\([^()]*\))?").unwrap());
+
+/// A `val`/`var` primary-constructor parameter
+#[derive(Debug, PartialEq)]
+struct ConstructorProperty {
+    name: String,
+    name_start: usize,
+    mutable: bool,
+    type_text: String,
+    default: Option<String>,
+    annotations: Vec<String>,
+    start: usize,
+    end: usize,
+}
+
+/// Make the `val`/`var` parameters of the constructor list `content[start..end]`
+/// properties of the class
+pub(super) fn enrich_constructor_properties(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    class_id: &str,
+    (start, end): (usize, usize),
+    is_data: bool,
+) {
+    let properties = constructor_properties(ctx.content, start, end);
+    apply_constructor_properties(ctx, symbols, class_id, &properties, is_data);
+}
+
+fn apply_constructor_properties(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    class_id: &str,
+    properties: &[ConstructorProperty],
+    is_data: bool,
+) {
+    for (index, property) in properties.iter().enumerate() {
+        let existing = symbols.iter().position(|s| {
+            s.name == property.name
+                && matches!(
+                    s.kind,
+                    SymbolKind::Property | SymbolKind::Field | SymbolKind::Variable
+                )
+                && (s.start_byte as usize) <= property.name_start
+                && property.name_start < s.end_byte as usize
+        });
+        let keyword = if property.mutable { "var" } else { "val" };
+        let mut signature = format!("{} {}: {}", keyword, property.name, property.type_text);
+        if let Some(default) = &property.default {
+            signature.push_str(&format!(" = {}", default));
+        }
+        let position = match existing {
+            Some(position) => {
+                let symbol = &mut symbols[position];
+                symbol.kind = SymbolKind::Property;
+                symbol.parent_id = Some(class_id.to_string());
+                symbol.signature.get_or_insert(signature);
+                position
+            }
+            None => {
+                symbols.push(ctx.create_symbol(
+                    &property.name,
+                    SymbolKind::Property,
+                    property.start,
+                    property.end,
+                    SymbolOptions {
+                        signature: Some(signature),
+                        parent_id: Some(class_id.to_string()),
+                        ..Default::default()
+                    },
+                ));
+                symbols.len() - 1
+            }
+        };
+
+        let symbol = &mut symbols[position];
+        set_metadata(symbol, "constructorProperty", true);
+        set_metadata(symbol, "mutable", property.mutable);
+        set_metadata(symbol, "propertyType", property.type_text.clone());
+        if let Some(default) = &property.default {
+            set_metadata(symbol, "defaultValue", default.clone());
+        }
+        if !property.annotations.is_empty() {
+            set_metadata(symbol, "annotations", property.annotations.clone());
+        }
+        if is_data {
+            set_metadata(symbol, "component", index + 1);
+        }
+    }
+
+    if is_data {
+        let components: Vec<&str> = properties.iter().map(|p| p.name.as_str()).collect();
+        if let Some(class) = symbols.iter_mut().find(|s| s.id == class_id) {
+            set_metadata(class, "components", components);
+        }
+    }
+}
+
+/// `val`/`var` parameters of the constructor list `content[start..end]`
+fn constructor_properties(content: &str, start: usize, end: usize) -> Vec<ConstructorProperty> {
+    split_top_level(&content[start..end], b',', Lexicon::C_LIKE)
+        .into_iter()
+        .map(|(offset, parameter)| trim_with_offset(start + offset, parameter))
+        .filter_map(|(at, parameter)| {
+            let captures = PARAMETER_RE.captures(parameter)?;
+            let mutable = captures.get(3)?.as_str() == "var";
+            let name = captures.get(4).unwrap();
+            let after_colon = &parameter[captures.get(0).unwrap().end()..];
+            let (type_text, default) =
+                match split_top_level(after_colon, b'=', Lexicon::C_LIKE).as_slice() {
+                    [(_, type_text), (default_at, _), ..] => (
+                        type_text.trim().to_string(),
+                        Some(after_colon[*default_at..].trim().to_string()),
+                    ),
+                    _ => (after_colon.trim().to_string(), None),
+                };
+            Some(ConstructorProperty {
+                name: name.as_str().to_string(),
+                name_start: at + name.start(),
+                mutable,
+                type_text,
+                default,
+                annotations: ANNOTATION_RE
+                    .find_iter(&captures[1])
+                    .map(|m| m.as_str().to_string())
+                    .collect(),
+                start: at,
+                end: at + parameter.len(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,255 @@
+// Kotlin coroutines
+//
+// `suspend` is a modifier Julie drops from the signature, so suspend functions
+// look like ordinary ones. This pass marks them (metadata.isSuspend) and puts
+// the modifier back in the signature.
+//
+// It also finds calls to the coroutine builders (launch, async, withContext,
+// runBlocking), which are where work hops to another coroutine or dispatcher.
+// Julie identifiers carry no metadata, so the call sites are recorded on the
+// enclosing function instead: metadata.coroutineBuilders lists
+// `{ builder, line }` for each one, and a Call identifier is added for any
+// builder call Julie missed. Identifiers inside the builder's lambda are
+// attributed to the enclosing function rather than left unowned.
+
+use super::{parse_fun_header, FUN_RE};
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, innermost_symbol_at,
+    reattribute_identifiers, set_metadata, EnrichmentContext, Lexicon,
+};
+use julie_extractors::{ExtractionResults, IdentifierKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::json;
+use std::sync::LazyLock;
+
+/// `suspend [modifiers] fun`
+static SUSPEND_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bsuspend\s+(?:[a-z]+\s+)*fun\b").unwrap());
+
+/// `launch {`, `async(Dispatchers.IO) {`, `withContext<T>(ctx) {`, `runBlocking {`
+static BUILDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(launch|async|withContext|runBlocking)\s*(?:<[^<>(){};]*>)?\s*[({]").unwrap()
+});
+
+/// `fun `, `fun <T> Receiver.` right before a builder name
+static DECLARATION_PREFIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bfun\s+(?:<[^(){}]*>\s*)?(?:[\w<>?, ]+\.)?$").unwrap());
+
+pub(super) fn enrich_suspend_functions(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+
+    for found in SUSPEND_RE.find_iter(ctx.content) {
+        if !code[found.start()] {
+            continue;
+        }
+        let Some(fun) = FUN_RE.find_at(ctx.content, found.end() - 3) else {
+            continue;
+        };
+        let Some(header) = parse_fun_header(ctx.content, fun.end()) else {
+            continue;
+        };
+        let Some(function) = symbols.iter_mut().find(|s| {
+            matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+                && (s.name == header.name || s.name.ends_with(&format!(".{}", header.name)))
+                && (s.start_byte as usize) <= header.name_start
+                && header.name_start < s.end_byte as usize
+        }) else {
+            continue;
+        };
+
+        set_metadata(function, "isSuspend", true);
+        function.signature = Some(match function.signature.take() {
+            Some(signature) if signature.split_whitespace().any(|w| w == "suspend") => signature,
+            Some(signature) => add_suspend(&signature),
+            None => ctx
+                .text(found.start(), header.name_start + header.name.len())
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        });
+    }
+}
+
+/// `fun load(): Data` → `suspend fun load(): Data`, keeping leading modifiers
+fn add_suspend(signature: &str) -> String {
+    match FUN_RE.find(signature) {
+        Some(fun) => format!(
+            "{}suspend {}",
+            &signature[..fun.start()],
+            &signature[fun.start()..]
+        ),
+        None => format!("suspend {}", signature),
+    }
+}
+
+pub(super) fn enrich_coroutine_builders(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+    let mut bodies = Vec::new();
+
+    for captures in BUILDER_RE.captures_iter(ctx.content) {
+        let builder = captures.get(1).unwrap();
+        if !code[builder.start()] || is_declaration(ctx.content, builder.start()) {
+            continue;
+        }
+        let Some(end) = call_end(ctx.content, captures.get(0).unwrap().end() - 1) else {
+            continue;
+        };
+        let Some(enclosing) = innermost_symbol_at(&results.symbols, builder.start(), owns_code)
+        else {
+            continue;
+        };
+        let enclosing_id = enclosing.id.clone();
+        bodies.push((builder.end(), end));
+
+        let known = results.identifiers.iter().any(|identifier| {
+            identifier.kind == IdentifierKind::Call
+                && identifier.start_byte as usize == builder.start()
+        });
+        if !known {
+            results.identifiers.push(ctx.create_identifier(
+                builder.as_str(),
+                IdentifierKind::Call,
+                builder.start(),
+                builder.end(),
+                Some(enclosing_id.clone()),
+            ));
+        }
+
+        let function = results
+            .symbols
+            .iter_mut()
+            .find(|s| s.id == enclosing_id)
+            .unwrap();
+        append_metadata(
+            function,
+            "coroutineBuilders",
+            json!({ "builder": builder.as_str(), "line": ctx.line_of(builder.start()) }),
+        );
+    }
+
+    reattribute_identifiers(
+        &mut results.identifiers,
+        &results.symbols,
+        &bodies,
+        owns_code,
+    );
+}
+
+fn owns_code(symbol: &Symbol) -> bool {
+    matches!(
+        symbol.kind,
+        SymbolKind::Function | SymbolKind::Method | SymbolKind::Constructor | SymbolKind::Property
+    )
+}
+
+/// `fun launch(` or `fun Scope.launch(`: a declaration named like a builder, not a call
+fn is_declaration(content: &str, at: usize) -> bool {
+    let line_start = content[..at].rfind('\n').map_or(0, |i| i + 1);
+    DECLARATION_PREFIX_RE.is_match(&content[line_start..at])
+}
+
+/// End of the builder call: its argument list and/or trailing lambda
+fn call_end(content: &str, open: usize) -> Option<usize> {
+    let close = find_matching_delimiter(content, open, Lexicon::C_LIKE)?;
+    if content.as_bytes()[open] == b'{' {
+        return Some(close + 1);
+    }
+    let rest = &content[close + 1..];
+    let lambda = close + 1 + (rest.len() - rest.trim_start().len());
+    if content.as_bytes().get(lambda) == Some(&b'{') {
+        return find_matching_delimiter(content, lambda, Lexicon::C_LIKE).map(|c| c + 1);
+    }
+    Some(close + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use serde_json::Value;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"class Repo(private val api: Api) {
+    override suspend fun load(id: String): User = withContext(Dispatchers.IO) {
+        api.fetch(id)
+    }
+
+    fun refresh(scope: CoroutineScope) {
+        scope.launch {
+            val user = load("me")
+            cache.put(user)
+        }
+    }
+}
+"#;
+
+    fn method(ctx: &EnrichmentContext, name: &str, signature: &str) -> Symbol {
+        let start = SOURCE.find(&format!("fun {}", name)).unwrap();
+        let end = SOURCE[start..].find("\n    }").unwrap() + start + 6;
+        ctx.create_symbol(
+            name,
+            SymbolKind::Method,
+            start,
+            end,
+            SymbolOptions {
+                signature: Some(signature.to_string()),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn results(ctx: &EnrichmentContext) -> ExtractionResults {
+        test_results(vec![
+            method(ctx, "load", "override fun load(id: String): User"),
+            method(ctx, "refresh", "fun refresh(scope: CoroutineScope)"),
+        ])
+    }
+
+    #[test]
+    fn test_suspend_functions_are_marked() {
+        let ctx = EnrichmentContext::new("Repo.kt", "kotlin", SOURCE, Path::new("."));
+        let mut results = results(&ctx);
+        enrich_suspend_functions(&ctx, &mut results.symbols);
+
+        let load = &results.symbols[0];
+        assert_eq!(
+            load.signature.as_deref(),
+            Some("override suspend fun load(id: String): User")
+        );
+        assert_eq!(
+            load.metadata.as_ref().unwrap()["isSuspend"],
+            Value::Bool(true)
+        );
+        assert!(results.symbols[1].metadata.is_none());
+    }
+
+    #[test]
+    fn test_builder_calls_are_recorded_and_lambda_identifiers_owned() {
+        let ctx = EnrichmentContext::new("Repo.kt", "kotlin", SOURCE, Path::new("."));
+        let mut results = results(&ctx);
+        let at = SOURCE.find("cache.put").unwrap();
+        results.identifiers.push(ctx.create_identifier(
+            "put",
+            IdentifierKind::Call,
+            at + 6,
+            at + 9,
+            None,
+        ));
+        enrich_coroutine_builders(&ctx, &mut results);
+
+        let refresh = &results.symbols[1];
+        assert_eq!(
+            refresh.metadata.as_ref().unwrap()["coroutineBuilders"],
+            json!([{ "builder": "launch", "line": 7 }])
+        );
+        assert_eq!(
+            results.identifiers[0].containing_symbol_id.as_deref(),
+            Some(refresh.id.as_str())
+        );
+        let builders: Vec<&str> = results.identifiers[1..]
+            .iter()
+            .map(|identifier| identifier.name.as_str())
+            .collect();
+        assert_eq!(builders, vec!["withContext", "launch"]);
+    }
+}
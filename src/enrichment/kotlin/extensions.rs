@@ -0,0 +1,196 @@
+// Kotlin extension functions
+//
+// `fun String.toSlug(): String` is extracted by name only, so there is no way
+// to list every extension on String. This pass parses the receiver type of
+// each extension function (generic `List<T>` and nullable `String?` receivers
+// are kept verbatim), puts it in the signature as written, records it as
+// metadata.receiverType, and emits an Extends relationship (metadata
+// extension=true) from the function to the receiver type when that type is
+// declared in the same file.
+
+use super::{parse_fun_header, type_base_name, FUN_RE};
+use crate::enrichment::base::{code_mask, set_metadata, EnrichmentContext, Lexicon};
+use julie_extractors::{ExtractionResults, RelationshipKind, SymbolKind};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// An extension function header: `fun <T> List<T>.second(`
+#[derive(Debug, PartialEq)]
+struct ExtensionHeader {
+    receiver: String,
+    name: String,
+    name_start: usize,
+    /// `fun` through the name
+    header: String,
+}
+
+pub(super) fn enrich_extensions(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    for extension in find_extensions(ctx.content) {
+        let Some(function) = results.symbols.iter_mut().find(|s| {
+            matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+                && (s.name == extension.name
+                    || s.name == format!("{}.{}", extension.receiver, extension.name))
+                && (s.start_byte as usize) <= extension.name_start
+                && extension.name_start < s.end_byte as usize
+        }) else {
+            continue;
+        };
+
+        set_metadata(function, "receiverType", extension.receiver.as_str());
+        set_metadata(function, "isExtension", true);
+        let qualified = format!("{}.{}", extension.receiver, extension.name);
+        function.signature = Some(match function.signature.take() {
+            Some(signature) if signature.contains(&qualified) => signature,
+            Some(signature) => insert_receiver(&signature, &extension),
+            None => extension.header.clone(),
+        });
+        let function_id = function.id.clone();
+
+        let base = type_base_name(&extension.receiver);
+        let Some(receiver) = results.symbols.iter().find(|s| {
+            s.name == base
+                && matches!(
+                    s.kind,
+                    SymbolKind::Class
+                        | SymbolKind::Interface
+                        | SymbolKind::Struct
+                        | SymbolKind::Enum
+                        | SymbolKind::Type
+                )
+        }) else {
+            continue;
+        };
+        let mut metadata = HashMap::new();
+        metadata.insert("extension".to_string(), Value::Bool(true));
+        metadata.insert(
+            "receiverType".to_string(),
+            Value::from(extension.receiver.clone()),
+        );
+        results.relationships.push(ctx.create_relationship(
+            &function_id,
+            &receiver.id,
+            RelationshipKind::Extends,
+            extension.name_start,
+            Some(metadata),
+        ));
+    }
+}
+
+fn find_extensions(content: &str) -> Vec<ExtensionHeader> {
+    let code = code_mask(content, Lexicon::C_LIKE);
+    let mut extensions = Vec::new();
+
+    for found in FUN_RE.find_iter(content) {
+        if !code[found.start()] {
+            continue;
+        }
+        let Some(header) = parse_fun_header(content, found.end()) else {
+            continue;
+        };
+        let Some(receiver) = header.receiver else {
+            continue;
+        };
+        if receiver.starts_with('(') {
+            continue;
+        }
+        let name_end = header.name_start + header.name.len();
+        extensions.push(ExtensionHeader {
+            receiver: receiver.split_whitespace().collect(),
+            name: header.name.to_string(),
+            name_start: header.name_start,
+            header: content[found.start()..name_end]
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        });
+    }
+    extensions
+}
+
+/// `fun second(): T` → `fun <T> List<T>.second(): T`
+fn insert_receiver(signature: &str, extension: &ExtensionHeader) -> String {
+    let Some(name_at) = signature.find(&format!("{}(", extension.name)) else {
+        return extension.header.clone();
+    };
+    format!(
+        "{}{}",
+        extension.header,
+        &signature[name_at + extension.name.len()..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    #[test]
+    fn test_receivers_are_kept_verbatim() {
+        let content = "fun String.toSlug(): String = lowercase()\nfun <T> List<T>.second(): T = this[1]\nfun String?.orEmpty(): String = this ?: \"\"\nfun plain() {}\n";
+        let receivers: Vec<(String, String)> = find_extensions(content)
+            .into_iter()
+            .map(|e| (e.receiver, e.name))
+            .collect();
+        assert_eq!(
+            receivers,
+            vec![
+                ("String".to_string(), "toSlug".to_string()),
+                ("List<T>".to_string(), "second".to_string()),
+                ("String?".to_string(), "orEmpty".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extension_links_to_receiver_in_file() {
+        let content = "class Money(val cents: Long)\n\nfun <T : Money> T.format(): String = \"$cents\"\nfun Money?.isZero(): Boolean = this == null\n";
+        let ctx = EnrichmentContext::new("Money.kt", "kotlin", content, Path::new("."));
+        let function = |name: &str, signature: &str| {
+            let start = content.find(&format!("{}(", name)).unwrap();
+            let line_start = content[..start].rfind('\n').unwrap() + 1;
+            let end = content[start..].find('\n').unwrap() + start;
+            ctx.create_symbol(
+                name,
+                SymbolKind::Function,
+                line_start,
+                end,
+                SymbolOptions {
+                    signature: Some(signature.to_string()),
+                    ..Default::default()
+                },
+            )
+        };
+        let class = ctx.create_symbol("Money", SymbolKind::Class, 0, 27, SymbolOptions::default());
+        let mut results = test_results(vec![
+            class,
+            function("format", "fun format(): String"),
+            function("isZero", "fun isZero(): Boolean"),
+        ]);
+        enrich_extensions(&ctx, &mut results);
+
+        let format = &results.symbols[1];
+        assert_eq!(
+            format.signature.as_deref(),
+            Some("fun <T : Money> T.format(): String")
+        );
+        assert_eq!(
+            format.metadata.as_ref().unwrap()["receiverType"],
+            Value::from("T")
+        );
+        let is_zero = &results.symbols[2];
+        assert_eq!(
+            is_zero.metadata.as_ref().unwrap()["receiverType"],
+            Value::from("Money?")
+        );
+
+        assert_eq!(
+            results.relationships.len(),
+            1,
+            "type-parameter receivers are not linked"
+        );
+        assert_eq!(results.relationships[0].from_symbol_id, is_zero.id);
+        assert_eq!(results.relationships[0].to_symbol_id, results.symbols[0].id);
+        assert_eq!(results.relationships[0].kind, RelationshipKind::Extends);
+    }
+}
@@ -0,0 +1,108 @@
+// Kotlin enrichment passes
+//
+// Julie's Kotlin extractor handles classes, objects, and functions; these
+// passes add class modifiers, constructor properties, and sealed hierarchy
+// links, extension function receivers (linked to receiver types declared in
+// the same file), suspend modifiers, coroutine builder call sites, and
+// annotations (with Spring routes, injection points, and @Composable).
+
+mod annotations;
+mod classes;
+mod constructor_properties;
+mod coroutines;
+mod extensions;
+
+use super::base::EnrichmentContext;
+use julie_extractors::ExtractionResults;
+use regex::Regex;
+use std::sync::LazyLock;
+
+pub const EXTRACTOR_VERSION: u32 = 2;
+
+/// The `fun` keyword and the whitespace after it
+static FUN_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bfun\b\s*").unwrap());
+
+/// Name and receiver of a function declaration
+#[derive(Debug, PartialEq)]
+struct FunHeader<'a> {
+    /// Receiver type as written, for extension functions
+    receiver: Option<&'a str>,
+    name: &'a str,
+    name_start: usize,
+}
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    classes::enrich_classes(ctx, results);
+    annotations::enrich_annotations(ctx, &mut results.symbols);
+    extensions::enrich_extensions(ctx, results);
+    coroutines::enrich_suspend_functions(ctx, &mut results.symbols);
+    coroutines::enrich_coroutine_builders(ctx, results);
+}
+
+/// `kotlin.collections.List<T>?` → `List`
+fn type_base_name(type_text: &str) -> &str {
+    let text = type_text.trim().trim_end_matches('?');
+    let text = text.split('<').next().unwrap_or(text);
+    text.rsplit('.').next().unwrap_or(text).trim()
+}
+
+/// Parse `<T> Receiver.name(` starting right after the `fun` keyword
+fn parse_fun_header(content: &str, after_fun: usize) -> Option<FunHeader<'_>> {
+    let bytes = content.as_bytes();
+    let mut cursor = after_fun;
+    // Type parameters: `fun <T : Comparable<T>> ...`
+    if bytes.get(cursor) == Some(&b'<') {
+        cursor = closing_angle(content, cursor)? + 1;
+    }
+
+    // Everything up to the parameter list, split at the last top-level dot
+    let mut depth = 0usize;
+    let mut last_dot = None;
+    let mut open_paren = None;
+    for (index, &byte) in bytes.iter().enumerate().skip(cursor) {
+        match byte {
+            b'<' => depth += 1,
+            b'>' => depth = depth.saturating_sub(1),
+            b'.' if depth == 0 => last_dot = Some(index),
+            b'(' if depth == 0 => {
+                open_paren = Some(index);
+                break;
+            }
+            b'{' | b'}' | b'=' | b'\n' | b';' => break,
+            _ => {}
+        }
+    }
+    let open = open_paren?;
+    let name_from = last_dot.map_or(cursor, |dot| dot + 1);
+    let name = content[name_from..open].trim();
+    if name.is_empty() {
+        return None;
+    }
+    let receiver = last_dot
+        .map(|dot| content[cursor..dot].trim())
+        .filter(|receiver| !receiver.is_empty());
+    Some(FunHeader {
+        receiver,
+        name,
+        name_start: name_from
+            + (content[name_from..open].len() - content[name_from..open].trim_start().len()),
+    })
+}
+
+fn closing_angle(content: &str, open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, byte) in content.bytes().enumerate().skip(open) {
+        match byte {
+            b'<' => depth += 1,
+            b'>' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            b'{' | b';' => return None,
+            _ => {}
+        }
+    }
+    None
+}
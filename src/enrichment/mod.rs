@@ -0,0 +1,275 @@
+// Enrichment Module - Miller-specific passes layered on top of Julie's extractors
+//
+// Julie remains the single source of truth for parsing. These passes run after
+// extraction and refine its output with details Miller's tools need (parameter
+// attributes, export surfaces, include paths, ...) using the file content and the symbols
+// Julie already produced.
+
+pub mod base;
+mod bash;
+mod c;
+mod code_context;
+mod confidence;
+mod cpp;
+mod csharp;
+mod css;
+mod dart;
+mod diagnostics;
+mod erb;
+mod gdscript;
+mod go;
+mod html;
+mod java;
+mod javascript;
+mod kind_filter;
+mod kotlin;
+mod php;
+mod powershell;
+mod python;
+mod qualified_names;
+mod regex;
+mod resolution;
+mod ruby;
+mod rust;
+mod sql;
+mod stable_ids;
+mod swift;
+mod vue;
+mod zig;
+
+use base::Lexicon;
+use julie_extractors::{detect_language_from_extension, ExtractionResults};
+use std::borrow::Cow;
+use std::path::Path;
+
+pub use base::EnrichmentContext;
+pub use c::{default_declaration_macros, DeclarationMacro, MacroArgument};
+pub use code_context::CodeContextOptions;
+pub use confidence::{
+    relationship_provenance, set_provenance, Provenance, HEURISTIC, NAME_MATCH, PROVENANCE,
+};
+pub use diagnostics::{diagnose, Diagnostic, DiagnosticKind};
+pub use kind_filter::SymbolKindFilter;
+pub use qualified_names::QUALIFIED_NAME;
+pub use sql::SqlDialect;
+
+/// Caller-supplied settings for the enrichment passes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct EnrichmentOptions {
+    /// SQL dialect; detected from the content when None
+    pub sql_dialect: Option<SqlDialect>,
+    /// Blank lines allowed between a SQL doc comment and its CREATE statement
+    pub sql_doc_blank_lines: usize,
+    /// Parse HTML template placeholders (`{{ }}`, `{% %}`, `<% %>`) as written
+    /// instead of masking them out first
+    pub raw_html_templates: bool,
+    /// Drop the regex extractor's line-by-line text fallback symbols
+    pub regex_ast_only: bool,
+    /// Store surrounding source lines in each symbol's code_context
+    pub code_context: Option<CodeContextOptions>,
+    /// Return only symbols of these kinds (all kinds when None)
+    pub symbol_kinds: Option<SymbolKindFilter>,
+    /// C declaration macros recognized before the defaults
+    /// (default_declaration_macros)
+    pub c_declaration_macros: Vec<DeclarationMacro>,
+}
+
+/// Template languages Miller extracts through other languages' Julie
+/// extractors
+pub const TEMPLATE_LANGUAGES: &[&str] = &["erb"];
+
+/// Language of a file extension: Julie's, or one of TEMPLATE_LANGUAGES
+pub fn language_for_extension(extension: &str) -> Option<&'static str> {
+    match extension {
+        "erb" => Some("erb"),
+        _ => detect_language_from_extension(extension),
+    }
+}
+
+/// How a language writes strings and comments, for scans of raw file text
+///
+/// Languages without a lexicon of their own use C conventions; markup and
+/// plain text skip nothing.
+pub fn lexicon(language: &str) -> Lexicon {
+    match language {
+        "go" => go::GO,
+        "javascript" | "typescript" => javascript::JS,
+        "python" | "bash" | "r" | "yaml" | "toml" => python::PYTHON,
+        "ruby" => ruby::RUBY,
+        "rust" => rust::RUST,
+        "swift" => swift::SWIFT,
+        "zig" => zig::ZIG,
+        "sql" => sql::SQL,
+        "gdscript" => gdscript::GDSCRIPT,
+        "powershell" => powershell::POWERSHELL,
+        "css" => Lexicon::CSS,
+        "html" | "vue" | "erb" | "razor" | "markdown" | "text" => Lexicon::PLAIN,
+        _ => Lexicon::C_LIKE,
+    }
+}
+
+/// The path Julie's extractors get for a file
+///
+/// Julie picks its extractor by extension, so an ERB template goes through the
+/// HTML extractor as `show.html.erb.html`; the ERB passes put the real path
+/// back on the results.
+pub fn parser_path(file_path: &str) -> Cow<'_, str> {
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str());
+    match extension {
+        Some("erb") => Cow::Owned(format!("{file_path}.html")),
+        _ => Cow::Borrowed(file_path),
+    }
+}
+
+/// The text Julie's parser gets for a file
+///
+/// Usually `content` itself; HTML template placeholders are blanked out (same
+/// length, newlines kept) so the markup around them parses. Enrichment still
+/// runs on `content`.
+pub fn parser_input<'a>(
+    file_path: &str,
+    content: &'a str,
+    options: &EnrichmentOptions,
+) -> Cow<'a, str> {
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+    match language_for_extension(extension) {
+        Some("html" | "erb") if !options.raw_html_templates => html::mask_placeholders(content),
+        _ => Cow::Borrowed(content),
+    }
+}
+
+/// The julie-extractors release Miller is built against (the tag in Cargo.toml)
+pub const JULIE_EXTRACTORS_VERSION: &str = "1.20.0";
+
+/// Bumped when a shared pass (confidence, dedup, qualified names, stable IDs,
+/// resolution) changes its output for every language
+pub const PIPELINE_VERSION: u32 = 4;
+
+/// Version tag for everything that produces `language`'s results
+///
+/// "<julie-extractors>+<pipeline>.<language passes>": JULIE_EXTRACTORS_VERSION,
+/// PIPELINE_VERSION, and the language's EXTRACTOR_VERSION. Each language module's EXTRACTOR_VERSION is bumped when its passes change their
+/// output, so callers caching results by content hash can drop exactly that
+/// language's entries. Languages without passes report 0.
+pub fn extractor_version(language: &str) -> String {
+    let passes = match language {
+        "bash" => bash::EXTRACTOR_VERSION,
+        "c" => c::EXTRACTOR_VERSION,
+        "cpp" => cpp::EXTRACTOR_VERSION,
+        "csharp" => csharp::EXTRACTOR_VERSION,
+        "css" => css::EXTRACTOR_VERSION,
+        "dart" => dart::EXTRACTOR_VERSION,
+        "erb" => erb::EXTRACTOR_VERSION,
+        "gdscript" => gdscript::EXTRACTOR_VERSION,
+        "go" => go::EXTRACTOR_VERSION,
+        "html" => html::EXTRACTOR_VERSION,
+        "java" => java::EXTRACTOR_VERSION,
+        "javascript" | "jsx" | "typescript" | "tsx" => javascript::EXTRACTOR_VERSION,
+        "kotlin" => kotlin::EXTRACTOR_VERSION,
+        "php" => php::EXTRACTOR_VERSION,
+        "powershell" => powershell::EXTRACTOR_VERSION,
+        "python" => python::EXTRACTOR_VERSION,
+        "regex" => regex::EXTRACTOR_VERSION,
+        "ruby" => ruby::EXTRACTOR_VERSION,
+        "rust" => rust::EXTRACTOR_VERSION,
+        "sql" => sql::EXTRACTOR_VERSION,
+        "swift" => swift::EXTRACTOR_VERSION,
+        "vue" => vue::EXTRACTOR_VERSION,
+        "zig" => zig::EXTRACTOR_VERSION,
+        _ => 0,
+    };
+    format!("{JULIE_EXTRACTORS_VERSION}+{PIPELINE_VERSION}.{passes}")
+}
+
+/// Run the enrichment passes registered for the file's language
+///
+/// Languages without passes are left untouched.
+pub fn enrich_results(
+    file_path: &str,
+    content: &str,
+    workspace_root: &Path,
+    results: &mut ExtractionResults,
+) {
+    enrich_results_with_options(
+        file_path,
+        content,
+        workspace_root,
+        &EnrichmentOptions::default(),
+        results,
+    );
+}
+
+/// `enrich_results` with explicit options
+pub fn enrich_results_with_options(
+    file_path: &str,
+    content: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+    results: &mut ExtractionResults,
+) {
+    run_language_passes(file_path, content, workspace_root, options, results);
+    confidence::assign_confidence(results);
+    // Needs confidence to pick survivors, and must precede the ID passes
+    base::dedup::dedup_symbols(results);
+    qualified_names::assign_qualified_names(content, results);
+    if let Some(context) = options.code_context {
+        code_context::assign_code_context(content, context, results);
+    }
+    // Last, so IDs created by the passes are made stable too
+    stable_ids::assign_stable_ids(results);
+    resolution::resolve_identifiers(results);
+    if let Some(filter) = &options.symbol_kinds {
+        kind_filter::filter_symbol_kinds(filter, results);
+    }
+}
+
+fn run_language_passes(
+    file_path: &str,
+    content: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+    results: &mut ExtractionResults,
+) {
+    let extension = Path::new(file_path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+    let Some(language) = language_for_extension(extension) else {
+        return;
+    };
+
+    let ctx = EnrichmentContext::new(file_path, language, content, workspace_root)
+        .with_options(options.clone());
+
+    match language {
+        "bash" => bash::enrich(&ctx, results),
+        "c" => c::enrich(&ctx, results),
+        "cpp" => cpp::enrich(&ctx, results),
+        "csharp" => csharp::enrich(&ctx, results),
+        "css" => css::enrich(&ctx, results),
+        "dart" => dart::enrich(&ctx, results),
+        "erb" => erb::enrich(&ctx, results),
+        "gdscript" => gdscript::enrich(&ctx, results),
+        "go" => go::enrich(&ctx, results),
+        "html" => html::enrich(&ctx, results),
+        "java" => java::enrich(&ctx, results),
+        "javascript" | "jsx" | "typescript" | "tsx" => javascript::enrich(&ctx, results),
+        "kotlin" => kotlin::enrich(&ctx, results),
+        "php" => php::enrich(&ctx, results),
+        "powershell" => powershell::enrich(&ctx, results),
+        "python" => python::enrich(&ctx, results),
+        "regex" => regex::enrich(&ctx, results),
+        "ruby" => ruby::enrich(&ctx, results),
+        "rust" => rust::enrich(&ctx, results),
+        "sql" => sql::enrich(&ctx, results),
+        "swift" => swift::enrich(&ctx, results),
+        "vue" => vue::enrich(&ctx, results),
+        "zig" => zig::enrich(&ctx, results),
+        _ => {}
+    }
+}
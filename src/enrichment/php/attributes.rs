@@ -0,0 +1,399 @@
+// PHP attributes
+//
+// PHP 8 attributes (`#[Route('/api/users', methods: ['GET'])]`) are how
+// Symfony, API Platform, and Laravel route packages declare endpoints, but
+// Julie skips attribute lists entirely. This pass reads every attribute group
+// in front of a class, method, function, property, or constant and records on
+// that symbol:
+//
+// - metadata.attributes: `{ name, args, text }` per attribute, where args maps
+//   positional arguments by index ("0", "1", ...) and named arguments by name;
+//   string literals are decoded, anything else is kept as source text
+// - for route attributes (Symfony `Route`, and `Get`/`Post`/... shorthands):
+//   metadata.routes and metadata.httpMethod, plus metadata.fullRoutes on
+//   methods whose class declares a route prefix
+//
+// Attributes on parameters are skipped; they describe arguments, not the
+// declaration.
+
+use crate::enrichment::base::{
+    append_metadata, find_matching_delimiter, set_metadata, split_top_level, CodeBytes,
+    EnrichmentContext, Lexicon,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Attribute name followed by an optional argument list: `\Ns\Route(`
+static NAME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\\?([A-Za-z_]\w*(?:\\[A-Za-z_]\w*)*)\s*").unwrap());
+
+/// `name: value` argument (but not `Foo::class`)
+static NAMED_ARGUMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)^([A-Za-z_]\w*)\s*:([^:].*)$").unwrap());
+
+/// Route attributes and the HTTP method they imply
+const ROUTE_ATTRIBUTES: &[(&str, Option<&str>)] = &[
+    ("Route", None),
+    ("Get", Some("GET")),
+    ("Post", Some("POST")),
+    ("Put", Some("PUT")),
+    ("Patch", Some("PATCH")),
+    ("Delete", Some("DELETE")),
+];
+
+/// Kinds an attribute group can be attached to
+const TARGET_KINDS: &[SymbolKind] = &[
+    SymbolKind::Class,
+    SymbolKind::Interface,
+    SymbolKind::Trait,
+    SymbolKind::Enum,
+    SymbolKind::EnumMember,
+    SymbolKind::Method,
+    SymbolKind::Constructor,
+    SymbolKind::Function,
+    SymbolKind::Property,
+    SymbolKind::Field,
+    SymbolKind::Constant,
+];
+
+/// One attribute inside a `#[...]` group
+#[derive(Debug, PartialEq)]
+struct Attribute<'a> {
+    name: String,
+    /// (key, raw value) with positional arguments keyed by index
+    args: Vec<(String, &'a str)>,
+    text: &'a str,
+}
+
+pub(super) fn enrich_attributes(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let groups = find_attribute_groups(ctx.content);
+    let spans: Vec<(usize, usize)> = groups.iter().map(|&(start, end, _)| (start, end)).collect();
+    let mut route_targets = Vec::new();
+
+    for &(start, end, in_parameters) in &groups {
+        if in_parameters {
+            continue;
+        }
+        let Some(target) = attributed_symbol(ctx.content, symbols, end, &spans) else {
+            continue;
+        };
+        for attribute in parse_group(ctx.text(start + 2, end - 1)) {
+            append_metadata(
+                &mut symbols[target],
+                "attributes",
+                json!({ "name": attribute.name, "args": arguments_metadata(&attribute), "text": attribute.text }),
+            );
+            if apply_route(&mut symbols[target], &attribute) {
+                route_targets.push(target);
+            }
+        }
+    }
+
+    link_route_prefixes(symbols, &route_targets);
+}
+
+/// (start, end, inside a parameter list) of every `#[...]` group
+fn find_attribute_groups(content: &str) -> Vec<(usize, usize, bool)> {
+    let mut groups = Vec::new();
+    let mut depth = 0u32;
+    let mut skip_until = 0;
+    for (index, byte) in CodeBytes::new(content, 0, Lexicon::C_LIKE) {
+        if index < skip_until {
+            continue;
+        }
+        match byte {
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b'#' if content[index..].starts_with("#[") => {
+                let Some(close) = find_matching_delimiter(content, index + 1, Lexicon::C_LIKE)
+                else {
+                    continue;
+                };
+                groups.push((index, close + 1, depth > 0));
+                skip_until = close + 1;
+            }
+            _ => {}
+        }
+    }
+    groups
+}
+
+fn parse_group(group: &str) -> Vec<Attribute<'_>> {
+    split_top_level(group, b',', Lexicon::C_LIKE)
+        .into_iter()
+        .filter_map(|(_, segment)| {
+            let text = segment.trim();
+            let captures = NAME_RE.captures(text)?;
+            let qualified = &captures[1];
+            let rest = &text[captures.get(0).unwrap().end()..];
+            let args = if rest.starts_with('(') {
+                let close = find_matching_delimiter(rest, 0, Lexicon::C_LIKE)?;
+                parse_arguments(&rest[1..close])
+            } else {
+                Vec::new()
+            };
+            Some(Attribute {
+                name: qualified
+                    .rsplit('\\')
+                    .next()
+                    .unwrap_or(qualified)
+                    .to_string(),
+                args,
+                text,
+            })
+        })
+        .collect()
+}
+
+fn parse_arguments(arguments: &str) -> Vec<(String, &str)> {
+    let mut position = 0;
+    split_top_level(arguments, b',', Lexicon::C_LIKE)
+        .into_iter()
+        .map(|(_, segment)| segment.trim())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match NAMED_ARGUMENT_RE.captures(segment) {
+            Some(captures) => {
+                let value = captures.get(2).unwrap();
+                (captures[1].to_string(), segment[value.start()..].trim())
+            }
+            None => {
+                position += 1;
+                ((position - 1).to_string(), segment)
+            }
+        })
+        .collect()
+}
+
+fn arguments_metadata(attribute: &Attribute) -> Value {
+    let args: Map<String, Value> = attribute
+        .args
+        .iter()
+        .map(|(key, raw)| {
+            let value = string_literal(raw).unwrap_or_else(|| raw.to_string());
+            (key.clone(), Value::from(value))
+        })
+        .collect();
+    Value::Object(args)
+}
+
+/// Record routes and HTTP methods declared by a route attribute.
+/// Returns whether the attribute was one.
+fn apply_route(symbol: &mut Symbol, attribute: &Attribute) -> bool {
+    let Some((_, implied)) = ROUTE_ATTRIBUTES
+        .iter()
+        .find(|(name, _)| *name == attribute.name)
+    else {
+        return false;
+    };
+    let argument = |names: &[&str]| {
+        attribute
+            .args
+            .iter()
+            .find(|(key, _)| names.contains(&key.as_str()))
+            .map(|(_, raw)| *raw)
+    };
+
+    if let Some(path) = argument(&["0", "path", "uri"]).and_then(string_literal) {
+        append_metadata(symbol, "routes", path);
+    }
+    let methods: Vec<String> = match implied {
+        Some(method) => vec![method.to_string()],
+        None => argument(&["methods"])
+            .map(|raw| {
+                raw.trim_matches(|c| matches!(c, '[' | ']'))
+                    .split(',')
+                    .filter_map(|method| string_literal(method.trim()))
+                    .map(|method| method.to_uppercase())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    if !methods.is_empty() {
+        set_metadata(symbol, "httpMethod", methods.join(","));
+    }
+    true
+}
+
+/// Combine class-level route prefixes with method routes into metadata.fullRoutes
+fn link_route_prefixes(symbols: &mut [Symbol], route_targets: &[usize]) {
+    let prefixes: HashMap<String, Vec<String>> = route_targets
+        .iter()
+        .map(|&index| &symbols[index])
+        .filter(|s| s.kind == SymbolKind::Class)
+        .map(|s| (s.id.clone(), string_array(s, "routes")))
+        .filter(|(_, routes)| !routes.is_empty())
+        .collect();
+
+    for &index in route_targets {
+        let symbol = &symbols[index];
+        if symbol.kind != SymbolKind::Method {
+            continue;
+        }
+        let Some(prefixes) = symbol.parent_id.as_ref().and_then(|p| prefixes.get(p)) else {
+            continue;
+        };
+        let mut routes = string_array(symbol, "routes");
+        if routes.is_empty() {
+            routes.push(String::new());
+        }
+        let full: Vec<Value> = prefixes
+            .iter()
+            .flat_map(|prefix| {
+                routes.iter().map(move |route| {
+                    let route = route.trim_start_matches('/');
+                    match (prefix.trim_end_matches('/'), route) {
+                        (prefix, "") if !prefix.is_empty() => prefix.to_string(),
+                        (prefix, route) => format!("{}/{}", prefix, route),
+                    }
+                })
+            })
+            .map(Value::from)
+            .collect();
+        set_metadata(&mut symbols[index], "fullRoutes", full);
+    }
+}
+
+fn string_array(symbol: &Symbol, key: &str) -> Vec<String> {
+    symbol
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get(key))
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Contents of a single `'...'` or `"..."` literal
+fn string_literal(value: &str) -> Option<String> {
+    let value = value.trim();
+    let quote = value.chars().next().filter(|c| matches!(c, '\'' | '"'))?;
+    let inner = value.strip_prefix(quote)?.strip_suffix(quote)?;
+    if inner.contains(quote) && !inner.contains('\\') {
+        return None;
+    }
+    Some(
+        inner
+            .replace(&format!("\\{}", quote), &quote.to_string())
+            .replace("\\\\", "\\"),
+    )
+}
+
+/// Index of the declaration an attribute group ending at `end` belongs to:
+/// the latest-starting declaration that begins before the header ends and
+/// extends past the group. Julie's PHP nodes may start at the attributes, so
+/// symbols starting inside another group are allowed.
+fn attributed_symbol(
+    content: &str,
+    symbols: &[Symbol],
+    end: usize,
+    spans: &[(usize, usize)],
+) -> Option<usize> {
+    let header_end = CodeBytes::new(content, end, Lexicon::C_LIKE)
+        .find(|&(index, byte)| {
+            matches!(byte, b'{' | b';' | b'(' | b'=')
+                && !spans.iter().any(|&(a, b)| a <= index && index < b)
+        })
+        .map(|(index, _)| index)?;
+    symbols
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| TARGET_KINDS.contains(&s.kind))
+        .filter(|(_, s)| (s.start_byte as usize) <= header_end && s.end_byte as usize > end)
+        .max_by_key(|(_, s)| (s.start_byte, std::cmp::Reverse(s.end_byte)))
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    const CONTROLLER: &str = r#"<?php
+#[Route('/api/users')]
+class UserController {
+    #[Route('/{id}', name: 'user_show', methods: ['GET'])]
+    #[IsGranted("ROLE_USER"), Cache(maxage: 60)]
+    public function show(#[MapEntity] User $user): Response {}
+
+    #[ORM\Column(type: 'string', length: 180)]
+    private string $email;
+}
+"#;
+
+    fn symbols(ctx: &EnrichmentContext) -> Vec<Symbol> {
+        let class_start = CONTROLLER.find("#[Route").unwrap();
+        let class = ctx.create_symbol(
+            "UserController",
+            SymbolKind::Class,
+            class_start,
+            CONTROLLER.rfind('}').unwrap() + 1,
+            SymbolOptions::default(),
+        );
+        let member = |name: &str, kind: SymbolKind, from: &str, to: &str| {
+            let start = CONTROLLER.find(from).unwrap();
+            let end = CONTROLLER.find(to).unwrap() + to.len();
+            ctx.create_symbol(
+                name,
+                kind,
+                start,
+                end,
+                SymbolOptions {
+                    parent_id: Some(class.id.clone()),
+                    ..Default::default()
+                },
+            )
+        };
+        let show = member("show", SymbolKind::Method, "public function", "Response {}");
+        let email = member("email", SymbolKind::Property, "private string", "$email;");
+        vec![class, show, email]
+    }
+
+    #[test]
+    fn test_attribute_arguments() {
+        let ctx = EnrichmentContext::new("UserController.php", "php", CONTROLLER, Path::new("."));
+        let mut symbols = symbols(&ctx);
+        enrich_attributes(&ctx, &mut symbols);
+
+        let show = symbols[1].metadata.as_ref().unwrap();
+        let attributes = show["attributes"].as_array().unwrap();
+        let names: Vec<&str> = attributes
+            .iter()
+            .map(|a| a["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["Route", "IsGranted", "Cache"]);
+        assert_eq!(
+            attributes[0]["args"],
+            json!({ "0": "/{id}", "name": "user_show", "methods": "['GET']" })
+        );
+        assert_eq!(attributes[2]["text"], Value::from("Cache(maxage: 60)"));
+
+        let email = symbols[2].metadata.as_ref().unwrap();
+        assert_eq!(email["attributes"][0]["name"], Value::from("Column"));
+        assert_eq!(email["attributes"][0]["args"]["length"], Value::from("180"));
+    }
+
+    #[test]
+    fn test_routes_and_prefixes() {
+        let ctx = EnrichmentContext::new("UserController.php", "php", CONTROLLER, Path::new("."));
+        let mut symbols = symbols(&ctx);
+        enrich_attributes(&ctx, &mut symbols);
+
+        let class = symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(class["routes"], json!(["/api/users"]));
+        let show = symbols[1].metadata.as_ref().unwrap();
+        assert_eq!(show["httpMethod"], Value::from("GET"));
+        assert_eq!(show["fullRoutes"], json!(["/api/users/{id}"]));
+        // The parameter attribute stays off the method
+        assert_eq!(show["attributes"].as_array().unwrap().len(), 3);
+    }
+}
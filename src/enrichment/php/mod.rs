@@ -0,0 +1,22 @@
+// PHP enrichment passes
+//
+// Julie's PHP extractor handles classes, traits, functions, and methods; these
+// passes add PHP 8 attributes (with the routes they declare) and trait use
+// declarations (with their insteadof/as adaptations and alias methods) inside
+// class bodies.
+
+mod attributes;
+mod trait_uses;
+
+use super::base::EnrichmentContext;
+use julie_extractors::{ExtractionResults, SymbolKind};
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+/// Symbol kinds whose bodies can use traits
+const PHP_TYPE_KINDS: &[SymbolKind] = &[SymbolKind::Class, SymbolKind::Trait, SymbolKind::Enum];
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    attributes::enrich_attributes(ctx, &mut results.symbols);
+    trait_uses::enrich_trait_uses(ctx, results);
+}
@@ -0,0 +1,479 @@
+// PHP trait uses
+//
+// `use Loggable, Cacheable { Loggable::log insteadof Cacheable; Cacheable::log
+// as protected cacheLog; }` inside a class body is how PHP composes traits, but
+// Julie extracts neither the use nor its adaptations. This pass parses each
+// class-level use declaration and:
+//
+// - emits a Uses relationship from the class to every trait declared in the
+//   file (metadata: the trait name plus its insteadof/as rules), or a pending
+//   relationship for traits declared elsewhere
+// - records every trait name and rule on the class (metadata.traits and
+//   metadata.traitAdaptations), so the rules of out-of-file traits survive
+// - adds a Method symbol for each `as` alias, with the visibility the rule
+//   gives it (falling back to the original method's), so references to the
+//   alias have a definition
+
+use super::PHP_TYPE_KINDS;
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, innermost_symbol_at, EnrichmentContext,
+    Lexicon, SymbolOptions,
+};
+use julie_extractors::{
+    ExtractionResults, PendingRelationship, RelationshipKind, Symbol, SymbolKind, Visibility,
+};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `use A, \Ns\B;` or `use A, B {` at statement position
+static USE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[{;}])\s*(use)\s+([\\\w]+(?:\s*,\s*[\\\w]+)*)\s*([;{])").unwrap()
+});
+
+/// `A::foo insteadof B, C`
+static INSTEADOF_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^([\\\w]+)::(\w+)\s+insteadof\s+([\\\w]+(?:\s*,\s*[\\\w]+)*)$").unwrap()
+});
+
+/// `[A::]foo as [visibility] [alias]`
+static AS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:([\\\w]+)::)?(\w+)\s+as\s+(?:(public|protected|private)\b\s*)?(\w+)?$")
+        .unwrap()
+});
+
+/// One rule inside a trait use block
+#[derive(Debug, Clone, PartialEq)]
+enum Adaptation {
+    /// `Trait::method insteadof Other, ...`
+    Insteadof {
+        trait_name: String,
+        method: String,
+        excluded: Vec<String>,
+    },
+    /// `[Trait::]method as [visibility] [alias]`
+    Alias {
+        trait_name: Option<String>,
+        method: String,
+        visibility: Option<String>,
+        alias: Option<String>,
+        start: usize,
+        end: usize,
+    },
+}
+
+impl Adaptation {
+    fn to_json(&self) -> Value {
+        match self {
+            Adaptation::Insteadof {
+                trait_name,
+                method,
+                excluded,
+            } => {
+                json!({ "kind": "insteadof", "trait": trait_name, "method": method, "insteadof": excluded })
+            }
+            Adaptation::Alias {
+                trait_name,
+                method,
+                visibility,
+                alias,
+                ..
+            } => {
+                let mut rule = json!({ "kind": "as", "method": method });
+                if let Some(trait_name) = trait_name {
+                    rule["trait"] = Value::from(trait_name.clone());
+                }
+                if let Some(visibility) = visibility {
+                    rule["visibility"] = Value::from(visibility.clone());
+                }
+                if let Some(alias) = alias {
+                    rule["alias"] = Value::from(alias.clone());
+                }
+                rule
+            }
+        }
+    }
+
+    /// Whether the rule names `trait_name` (qualified or not)
+    fn mentions(&self, trait_name: &str) -> bool {
+        match self {
+            Adaptation::Insteadof {
+                trait_name: chosen,
+                excluded,
+                ..
+            } => {
+                base_name(chosen) == trait_name
+                    || excluded.iter().any(|other| base_name(other) == trait_name)
+            }
+            Adaptation::Alias { trait_name: t, .. } => {
+                t.as_deref().map(base_name) == Some(trait_name)
+            }
+        }
+    }
+}
+
+pub(super) fn enrich_trait_uses(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let code = code_mask(ctx.content, Lexicon::C_LIKE);
+
+    for captures in USE_RE.captures_iter(ctx.content) {
+        let keyword = captures.get(1).unwrap();
+        if !code[keyword.start()] {
+            continue;
+        }
+        let Some(class) = innermost_symbol_at(&results.symbols, keyword.start(), |s| {
+            PHP_TYPE_KINDS.contains(&s.kind)
+                || matches!(s.kind, SymbolKind::Method | SymbolKind::Function)
+        }) else {
+            continue;
+        };
+        if !PHP_TYPE_KINDS.contains(&class.kind) {
+            continue;
+        }
+        let class_id = class.id.clone();
+        let traits: Vec<String> = captures[2]
+            .split(',')
+            .map(|name| name.trim().trim_start_matches('\\').to_string())
+            .collect();
+        let terminator = captures.get(3).unwrap();
+        let adaptations = if terminator.as_str() == "{" {
+            find_matching_delimiter(ctx.content, terminator.start(), Lexicon::C_LIKE)
+                .map(|close| parse_adaptations(ctx.content, terminator.start() + 1, close))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        link_traits(
+            ctx,
+            results,
+            &class_id,
+            &traits,
+            &adaptations,
+            keyword.start(),
+        );
+        add_aliases(ctx, &mut results.symbols, &class_id, &traits, &adaptations);
+
+        let class = results
+            .symbols
+            .iter_mut()
+            .find(|s| s.id == class_id)
+            .unwrap();
+        for trait_name in &traits {
+            append_metadata(class, "traits", trait_name.as_str());
+        }
+        for adaptation in &adaptations {
+            append_metadata(class, "traitAdaptations", adaptation.to_json());
+        }
+    }
+}
+
+fn link_traits(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    class_id: &str,
+    traits: &[String],
+    adaptations: &[Adaptation],
+    anchor: usize,
+) {
+    for trait_name in traits {
+        let name = base_name(trait_name);
+        let target = results
+            .symbols
+            .iter()
+            .find(|s| s.name == name && s.kind == SymbolKind::Trait);
+        let Some(target) = target else {
+            results.pending_relationships.push(PendingRelationship {
+                from_symbol_id: class_id.to_string(),
+                callee_name: name.to_string(),
+                kind: RelationshipKind::Uses,
+                file_path: ctx.file_path.to_string(),
+                line_number: ctx.line_of(anchor),
+                confidence: 0.8,
+            });
+            continue;
+        };
+
+        let rules: Vec<Value> = adaptations
+            .iter()
+            .filter(|adaptation| adaptation.mentions(name))
+            .map(Adaptation::to_json)
+            .collect();
+        let mut metadata = HashMap::new();
+        metadata.insert("trait".to_string(), Value::from(trait_name.clone()));
+        if !rules.is_empty() {
+            metadata.insert("adaptations".to_string(), Value::Array(rules));
+        }
+        let relationship = ctx.create_relationship(
+            class_id,
+            &target.id,
+            RelationshipKind::Uses,
+            anchor,
+            Some(metadata),
+        );
+        results.relationships.push(relationship);
+    }
+}
+
+/// Add a Method symbol for every `as alias` rule the class does not declare itself
+fn add_aliases(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    class_id: &str,
+    traits: &[String],
+    adaptations: &[Adaptation],
+) {
+    for adaptation in adaptations {
+        let Adaptation::Alias {
+            trait_name,
+            method,
+            visibility,
+            alias: Some(alias),
+            start,
+            end,
+        } = adaptation
+        else {
+            continue;
+        };
+        if symbols
+            .iter()
+            .any(|s| s.parent_id.as_deref() == Some(class_id) && &s.name == alias)
+        {
+            continue;
+        }
+
+        // The original method, when its trait is declared in this file
+        let candidates: Vec<&str> = match trait_name {
+            Some(trait_name) => vec![base_name(trait_name)],
+            None => traits.iter().map(|t| base_name(t)).collect(),
+        };
+        let original = symbols.iter().find(|s| {
+            s.kind == SymbolKind::Method
+                && &s.name == method
+                && symbols.iter().any(|t| {
+                    Some(&t.id) == s.parent_id.as_ref() && candidates.contains(&t.name.as_str())
+                })
+        });
+
+        let visibility = match visibility.as_deref() {
+            Some("private") => Visibility::Private,
+            Some("protected") => Visibility::Protected,
+            Some(_) => Visibility::Public,
+            None => original
+                .and_then(|s| s.visibility.clone())
+                .unwrap_or(Visibility::Public),
+        };
+        let signature = original
+            .and_then(|s| s.signature.as_deref())
+            .and_then(|signature| rename_signature(signature, method, alias, &visibility))
+            .unwrap_or_else(|| {
+                ctx.text(*start, *end)
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            });
+        let aliased = match trait_name {
+            Some(trait_name) => format!("{}::{}", trait_name, method),
+            None => method.clone(),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("traitAlias".to_string(), Value::Bool(true));
+        metadata.insert("aliasOf".to_string(), Value::from(aliased));
+        let doc_comment = original.and_then(|s| s.doc_comment.clone());
+        symbols.push(ctx.create_symbol(
+            alias,
+            SymbolKind::Method,
+            *start,
+            *end,
+            SymbolOptions {
+                signature: Some(signature),
+                visibility: Some(visibility),
+                parent_id: Some(class_id.to_string()),
+                metadata: Some(metadata),
+                doc_comment,
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+fn parse_adaptations(content: &str, from: usize, to: usize) -> Vec<Adaptation> {
+    let mut adaptations = Vec::new();
+    let mut offset = from;
+    for rule in content[from..to].split(';') {
+        let start = offset + (rule.len() - rule.trim_start().len());
+        let end = start + rule.trim().len();
+        offset += rule.len() + 1;
+        let text = rule.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if let Some(captures) = INSTEADOF_RE.captures(&text) {
+            adaptations.push(Adaptation::Insteadof {
+                trait_name: captures[1].trim_start_matches('\\').to_string(),
+                method: captures[2].to_string(),
+                excluded: captures[3]
+                    .split(',')
+                    .map(|name| name.trim().trim_start_matches('\\').to_string())
+                    .collect(),
+            });
+        } else if let Some(captures) = AS_RE.captures(&text) {
+            let visibility = captures.get(3).map(|m| m.as_str().to_string());
+            let alias = captures.get(4).map(|m| m.as_str().to_string());
+            if visibility.is_none() && alias.is_none() {
+                continue;
+            }
+            adaptations.push(Adaptation::Alias {
+                trait_name: captures
+                    .get(1)
+                    .map(|m| m.as_str().trim_start_matches('\\').to_string()),
+                method: captures[2].to_string(),
+                visibility,
+                alias,
+                start,
+                end,
+            });
+        }
+    }
+    adaptations
+}
+
+/// `public function log(string $m): void` → `protected function cacheLog(string $m): void`
+fn rename_signature(
+    signature: &str,
+    method: &str,
+    alias: &str,
+    visibility: &Visibility,
+) -> Option<String> {
+    let at = signature.find(&format!("function {}", method))?;
+    let modifiers: Vec<&str> = signature[..at]
+        .split_whitespace()
+        .filter(|word| !matches!(*word, "public" | "protected" | "private"))
+        .collect();
+    let visibility = match visibility {
+        Visibility::Private => "private",
+        Visibility::Protected => "protected",
+        Visibility::Public => "public",
+    };
+    let mut renamed = vec![visibility];
+    renamed.extend(modifiers);
+    Some(format!(
+        "{} function {}{}",
+        renamed.join(" "),
+        alias,
+        &signature[at + "function ".len() + method.len()..]
+    ))
+}
+
+/// `\App\Concerns\Loggable` → `Loggable`
+fn base_name(name: &str) -> &str {
+    name.rsplit('\\').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"<?php
+trait Loggable {
+    public function log(string $message): void {}
+}
+
+class Service {
+    use Loggable, \App\Cacheable {
+        Loggable::log insteadof Cacheable;
+        Cacheable::log as protected cacheLog;
+        Loggable::log as private;
+    }
+}
+"#;
+
+    fn symbol(ctx: &EnrichmentContext, name: &str, kind: SymbolKind, from: &str) -> Symbol {
+        let start = SOURCE.find(from).unwrap();
+        let end = SOURCE[start..].find("\n}").unwrap() + start + 2;
+        ctx.create_symbol(name, kind, start, end, SymbolOptions::default())
+    }
+
+    fn results(ctx: &EnrichmentContext) -> ExtractionResults {
+        let loggable = symbol(ctx, "Loggable", SymbolKind::Trait, "trait Loggable");
+        let start = SOURCE.find("public function log").unwrap();
+        let log = ctx.create_symbol(
+            "log",
+            SymbolKind::Method,
+            start,
+            start + 45,
+            SymbolOptions {
+                signature: Some("public function log(string $message): void".to_string()),
+                visibility: Some(Visibility::Public),
+                parent_id: Some(loggable.id.clone()),
+                ..Default::default()
+            },
+        );
+        let service = symbol(ctx, "Service", SymbolKind::Class, "class Service");
+        test_results(vec![loggable, log, service])
+    }
+
+    #[test]
+    fn test_uses_relationships_and_adaptations() {
+        let ctx = EnrichmentContext::new("Service.php", "php", SOURCE, Path::new("."));
+        let mut results = results(&ctx);
+        enrich_trait_uses(&ctx, &mut results);
+
+        assert_eq!(results.relationships.len(), 1);
+        let uses = &results.relationships[0];
+        assert_eq!(uses.kind, RelationshipKind::Uses);
+        assert_eq!(uses.to_symbol_id, results.symbols[0].id);
+        let rules = &uses.metadata.as_ref().unwrap()["adaptations"];
+        assert_eq!(rules[0]["insteadof"], json!(["Cacheable"]));
+        assert_eq!(rules.as_array().unwrap().len(), 2);
+
+        assert_eq!(results.pending_relationships.len(), 1);
+        assert_eq!(results.pending_relationships[0].callee_name, "Cacheable");
+        let service = results.symbols[2].metadata.as_ref().unwrap();
+        assert_eq!(service["traits"], json!(["Loggable", "App\\Cacheable"]));
+        assert_eq!(service["traitAdaptations"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_alias_method_symbol() {
+        let ctx = EnrichmentContext::new("Service.php", "php", SOURCE, Path::new("."));
+        let mut results = results(&ctx);
+        enrich_trait_uses(&ctx, &mut results);
+
+        let alias = results
+            .symbols
+            .iter()
+            .find(|s| s.name == "cacheLog")
+            .unwrap();
+        assert_eq!(alias.kind, SymbolKind::Method);
+        assert_eq!(alias.visibility, Some(Visibility::Protected));
+        assert_eq!(alias.parent_id, Some(results.symbols[2].id.clone()));
+        assert_eq!(
+            alias.signature.as_deref(),
+            Some("Cacheable::log as protected cacheLog")
+        );
+        assert_eq!(
+            alias.metadata.as_ref().unwrap()["aliasOf"],
+            Value::from("Cacheable::log")
+        );
+    }
+
+    #[test]
+    fn test_namespace_imports_are_not_trait_uses() {
+        let content = "<?php\nnamespace App;\n\nuse App\\Models\\User;\n\nclass A {}\n";
+        let ctx = EnrichmentContext::new("A.php", "php", content, Path::new("."));
+        let start = content.find("class A").unwrap();
+        let class = ctx.create_symbol(
+            "A",
+            SymbolKind::Class,
+            start,
+            start + 10,
+            SymbolOptions::default(),
+        );
+        let mut results = test_results(vec![class]);
+        enrich_trait_uses(&ctx, &mut results);
+        assert!(results.pending_relationships.is_empty());
+        assert!(results.symbols[0].metadata.is_none());
+    }
+}
@@ -0,0 +1,371 @@
+// PowerShell module export surface
+//
+// A .psm1 that calls Export-ModuleMember only exposes the listed members; every
+// other function stays private to the module. This pass collects the exported
+// names (wildcards included), intersects them with the FunctionsToExport /
+// VariablesToExport lists of a sibling .psd1 manifest when one exists, and sets
+// Visibility on the module's top-level functions and variables accordingly.
+
+use super::POWERSHELL;
+use crate::enrichment::base::{code_mask, set_metadata, EnrichmentContext};
+use julie_extractors::{Symbol, SymbolKind, Visibility};
+use regex::Regex;
+use std::fs;
+use std::sync::LazyLock;
+
+static EXPORT_MODULE_MEMBER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bExport-ModuleMember\b").unwrap());
+
+/// `FunctionsToExport = @('Get-Thing', 'Set-*')` entries in a module manifest
+static MANIFEST_EXPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?is)\b(FunctionsToExport|VariablesToExport)\s*=\s*(@\(.*?\)|'[^']*'|\x22[^\x22]*\x22)",
+    )
+    .unwrap()
+});
+
+/// Names exported per member category; `None` means the category was never listed
+#[derive(Debug, Default)]
+struct ExportLists {
+    functions: Option<Vec<String>>,
+    variables: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Category {
+    Function,
+    Variable,
+    Other,
+}
+
+impl ExportLists {
+    fn push(&mut self, category: Category, name: String) {
+        let list = match category {
+            Category::Function => &mut self.functions,
+            Category::Variable => &mut self.variables,
+            Category::Other => return,
+        };
+        list.get_or_insert_with(Vec::new).push(name);
+    }
+
+    fn declare(&mut self, category: Category) {
+        match category {
+            Category::Function => {
+                self.functions.get_or_insert_with(Vec::new);
+            }
+            Category::Variable => {
+                self.variables.get_or_insert_with(Vec::new);
+            }
+            Category::Other => {}
+        }
+    }
+}
+
+/// Apply Export-ModuleMember (and manifest) visibility to top-level members
+pub(super) fn apply_module_exports(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let exports = collect_module_member_exports(ctx.content);
+    let manifest = load_manifest_exports(ctx);
+
+    apply_category(
+        symbols,
+        SymbolKind::Function,
+        exports.functions.as_deref(),
+        manifest.functions.as_deref(),
+    );
+    apply_category(
+        symbols,
+        SymbolKind::Variable,
+        exports.variables.as_deref(),
+        manifest.variables.as_deref(),
+    );
+}
+
+fn apply_category(
+    symbols: &mut [Symbol],
+    kind: SymbolKind,
+    exported: Option<&[String]>,
+    manifest: Option<&[String]>,
+) {
+    // Without an explicit export list PowerShell's defaults apply; leave Julie's visibility
+    let Some(exported) = exported else {
+        return;
+    };
+
+    for symbol in symbols
+        .iter_mut()
+        .filter(|s| s.kind == kind && s.parent_id.is_none())
+    {
+        let name = symbol.name.trim_start_matches('$');
+        let in_module = exported.iter().any(|pattern| wildcard_match(pattern, name));
+        let in_manifest =
+            manifest.is_none_or(|list| list.iter().any(|pattern| wildcard_match(pattern, name)));
+        let is_exported = in_module && in_manifest;
+
+        symbol.visibility = Some(if is_exported {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        });
+        set_metadata(symbol, "exported", is_exported);
+    }
+}
+
+/// Collect the names listed by every Export-ModuleMember call in the file
+fn collect_module_member_exports(content: &str) -> ExportLists {
+    let mask = code_mask(content, POWERSHELL);
+    let mut exports = ExportLists::default();
+
+    for command in EXPORT_MODULE_MEMBER_RE.find_iter(content) {
+        if !mask.get(command.start()).copied().unwrap_or(false) {
+            continue;
+        }
+        let arguments = statement_arguments(&content[command.end()..]);
+
+        // Positional arguments bind to -Function
+        let mut category = Category::Function;
+        for token in tokenize_arguments(arguments) {
+            if let Some(parameter) = token.strip_prefix('-').filter(|p| !p.is_empty()) {
+                category = parameter_category(parameter);
+                exports.declare(category);
+            } else if !token.starts_with('$') {
+                exports.push(category, token);
+            }
+        }
+    }
+
+    exports
+}
+
+/// Read FunctionsToExport / VariablesToExport from the module's sibling .psd1
+fn load_manifest_exports(ctx: &EnrichmentContext) -> ExportLists {
+    let mut exports = ExportLists::default();
+    if !ctx.file_path.to_ascii_lowercase().ends_with(".psm1") {
+        return exports;
+    }
+
+    let manifest_path = ctx
+        .workspace_root
+        .join(ctx.file_path)
+        .with_extension("psd1");
+    let Ok(manifest) = fs::read_to_string(&manifest_path) else {
+        return exports;
+    };
+
+    for captures in MANIFEST_EXPORT_RE.captures_iter(&manifest) {
+        let category = if captures[1].eq_ignore_ascii_case("FunctionsToExport") {
+            Category::Function
+        } else {
+            Category::Variable
+        };
+        exports.declare(category);
+        for token in tokenize_arguments(&captures[2]) {
+            exports.push(category, token);
+        }
+    }
+
+    exports
+}
+
+/// Map a (possibly abbreviated) parameter name onto a member category
+fn parameter_category(parameter: &str) -> Category {
+    let parameter = parameter.trim_end_matches(':').to_ascii_lowercase();
+    if "function".starts_with(&parameter) {
+        Category::Function
+    } else if "variable".starts_with(&parameter) {
+        Category::Variable
+    } else {
+        Category::Other
+    }
+}
+
+/// Text of the statement following a command name: up to the end of the line
+/// (honoring backtick continuations), a `;`, or a closing brace.
+fn statement_arguments(rest: &str) -> &str {
+    let bytes = rest.as_bytes();
+    let mut quote: Option<u8> = None;
+    let mut depth = 0usize;
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match quote {
+            Some(q) => {
+                if byte == b'`' {
+                    index += 1;
+                } else if byte == q {
+                    quote = None;
+                }
+            }
+            None => match byte {
+                b'\'' | b'"' => quote = Some(byte),
+                b'`' => index += 1,
+                b'(' => depth += 1,
+                b')' => depth = depth.saturating_sub(1),
+                b'#' | b';' | b'}' | b'|' if depth == 0 => break,
+                b'\n' if depth == 0 => break,
+                _ => {}
+            },
+        }
+        index += 1;
+    }
+
+    &rest[..index.min(rest.len())]
+}
+
+/// Split an argument list into bare names and `-Parameter` tokens,
+/// unwrapping quotes, `@( )` arrays, and comma-separated lists.
+fn tokenize_arguments(arguments: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut chars = arguments.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '`' => {
+                    // Line continuation or escaped character
+                    chars.next();
+                }
+                '@' if chars.peek() == Some(&'(') => {}
+                '(' | ')' | ',' | ';' => {
+                    flush_token(&mut current, &mut tokens);
+                }
+                c if c.is_whitespace() => flush_token(&mut current, &mut tokens),
+                _ => current.push(c),
+            },
+        }
+    }
+    flush_token(&mut current, &mut tokens);
+    tokens
+}
+
+fn flush_token(current: &mut String, tokens: &mut Vec<String>) {
+    if !current.is_empty() {
+        tokens.push(std::mem::take(current));
+    }
+}
+
+/// Case-insensitive PowerShell wildcard match supporting `*`, `?`, and `[abc]`
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    wildcard_match_from(&pattern, &name)
+}
+
+fn wildcard_match_from(pattern: &[char], name: &[char]) -> bool {
+    match pattern.first() {
+        None => name.is_empty(),
+        Some('*') => (0..=name.len()).any(|skip| wildcard_match_from(&pattern[1..], &name[skip..])),
+        Some('?') => !name.is_empty() && wildcard_match_from(&pattern[1..], &name[1..]),
+        Some('[') => {
+            let Some(close) = pattern.iter().position(|&c| c == ']') else {
+                return name.first() == Some(&'[')
+                    && wildcard_match_from(&pattern[1..], &name[1..]);
+            };
+            let Some(&c) = name.first() else {
+                return false;
+            };
+            let class = &pattern[1..close];
+            let mut matched = false;
+            let mut i = 0;
+            while i < class.len() {
+                if i + 2 < class.len() && class[i + 1] == '-' {
+                    matched |= class[i] <= c && c <= class[i + 2];
+                    i += 3;
+                } else {
+                    matched |= class[i] == c;
+                    i += 1;
+                }
+            }
+            matched && wildcard_match_from(&pattern[close + 1..], &name[1..])
+        }
+        Some(&p) => name.first() == Some(&p) && wildcard_match_from(&pattern[1..], &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    fn functions(content: &str, names: &[&str]) -> (Vec<Symbol>, Vec<Symbol>) {
+        let ctx = EnrichmentContext::new("Things.psm1", "powershell", content, Path::new("."));
+        let mut symbols: Vec<Symbol> = names
+            .iter()
+            .map(|name| {
+                let start = content.find(&format!("function {}", name)).unwrap();
+                ctx.create_symbol(
+                    name,
+                    SymbolKind::Function,
+                    start,
+                    start + 1,
+                    SymbolOptions::default(),
+                )
+            })
+            .collect();
+        let before = symbols.clone();
+        apply_module_exports(&ctx, &mut symbols);
+        (before, symbols)
+    }
+
+    fn visibility(symbols: &[Symbol], name: &str) -> Option<Visibility> {
+        symbols
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap()
+            .visibility
+            .clone()
+    }
+
+    #[test]
+    fn test_unexported_functions_become_private() {
+        let content = "function Get-Thing {}\nfunction Set-Thing {}\nfunction Helper {}\nExport-ModuleMember -Function Get-Thing,Set-Thing\n";
+        let (_, symbols) = functions(content, &["Get-Thing", "Set-Thing", "Helper"]);
+
+        assert_eq!(visibility(&symbols, "Get-Thing"), Some(Visibility::Public));
+        assert_eq!(visibility(&symbols, "Set-Thing"), Some(Visibility::Public));
+        assert_eq!(visibility(&symbols, "Helper"), Some(Visibility::Private));
+    }
+
+    #[test]
+    fn test_wildcard_exports() {
+        let content = "function Get-Thing {}\nfunction Get-Other {}\nfunction Helper {}\nExport-ModuleMember -Function 'Get-*'\n";
+        let (_, symbols) = functions(content, &["Get-Thing", "Get-Other", "Helper"]);
+
+        assert_eq!(visibility(&symbols, "Get-Thing"), Some(Visibility::Public));
+        assert_eq!(visibility(&symbols, "Get-Other"), Some(Visibility::Public));
+        assert_eq!(visibility(&symbols, "Helper"), Some(Visibility::Private));
+    }
+
+    #[test]
+    fn test_positional_array_and_continuation() {
+        let content = "function A {}\nfunction B {}\nfunction C {}\nExport-ModuleMember @('A', `\n  'B') -Variable Config\n";
+        let (_, symbols) = functions(content, &["A", "B", "C"]);
+
+        assert_eq!(visibility(&symbols, "A"), Some(Visibility::Public));
+        assert_eq!(visibility(&symbols, "B"), Some(Visibility::Public));
+        assert_eq!(visibility(&symbols, "C"), Some(Visibility::Private));
+    }
+
+    #[test]
+    fn test_no_export_call_leaves_visibility_untouched() {
+        let content = "function A {}\n# Export-ModuleMember -Function B\n";
+        let (before, after) = functions(content, &["A"]);
+        assert_eq!(before[0].visibility, after[0].visibility);
+        assert!(after[0].metadata.is_none());
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("Get-*", "get-thing"));
+        assert!(wildcard_match("?et-Thing", "Get-Thing"));
+        assert!(wildcard_match("[GS]et-*", "Set-Thing"));
+        assert!(!wildcard_match("Get-*", "Set-Thing"));
+        assert!(wildcard_match("*", "anything"));
+    }
+}
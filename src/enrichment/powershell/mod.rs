@@ -0,0 +1,31 @@
+// PowerShell enrichment passes
+//
+// Julie's PowerShell extractor produces functions and bare parameter names;
+// these passes restore declarations lost to parse errors, then add the
+// cmdlet-style details (parameter attributes, types, defaults), the module
+// export surface, and the dataflow of command pipelines.
+
+mod exports;
+mod parameters;
+mod pipelines;
+mod recovery;
+
+use super::base::{EnrichmentContext, Lexicon};
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+/// PowerShell strings use backtick escapes; comments are # and <# #>
+pub(super) const POWERSHELL: Lexicon = Lexicon {
+    quotes: b"\"'",
+    escape: Some(b'`'),
+    line_comment: Some("#"),
+    block_comment: Some(("<#", "#>")),
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    recovery::recover_declarations(ctx, &mut results.symbols);
+    parameters::enrich_parameters(ctx, &mut results.symbols);
+    exports::apply_module_exports(ctx, &mut results.symbols);
+    pipelines::link_pipelines(ctx, results);
+}
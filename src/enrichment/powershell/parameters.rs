@@ -0,0 +1,488 @@
+// PowerShell parameter enrichment
+//
+// Parses param() blocks and inline parameter lists of advanced functions so each
+// parameter symbol carries its attribute list, type accelerator, and default value,
+// e.g. "[Parameter(Mandatory)][string]$Name = 'default'". Parameters declared in
+// DynamicParam blocks are surfaced as named symbols.
+
+use super::POWERSHELL;
+use crate::enrichment::base::{
+    find_matching_delimiter, set_metadata, split_top_level, top_level_mask, CodeBytes,
+    EnrichmentContext, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static PARAM_BLOCK_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bparam\s*\(").unwrap());
+
+static DYNAMIC_PARAM_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bdynamicparam\s*\{").unwrap());
+
+/// `RuntimeDefinedParameter('Name', ...)` or `[RuntimeDefinedParameter]::new("Name", ...)`
+static RUNTIME_PARAMETER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)RuntimeDefinedParameter\]?(?:::new)?\s*\(\s*['"]([A-Za-z_][\w-]*)['"]"#)
+        .unwrap()
+});
+
+/// Attribute names that are valid without an argument list
+const BARE_ATTRIBUTES: &[&str] = &[
+    "Parameter",
+    "Alias",
+    "AllowNull",
+    "AllowEmptyString",
+    "AllowEmptyCollection",
+    "SupportsWildcards",
+    "ValidateNotNull",
+    "ValidateNotNullOrEmpty",
+];
+
+/// A parameter parsed from a parameter list (offsets relative to the list)
+#[derive(Debug)]
+struct ParsedParameter {
+    name: String,
+    start: usize,
+    end: usize,
+    attributes: Vec<String>,
+    type_name: Option<String>,
+    default_value: Option<String>,
+}
+
+impl ParsedParameter {
+    fn signature(&self) -> String {
+        let mut signature = self.attributes.concat();
+        if let Some(type_name) = &self.type_name {
+            signature.push_str(&format!("[{}]", type_name));
+        }
+        signature.push_str(&format!("${}", self.name));
+        if let Some(default_value) = &self.default_value {
+            signature.push_str(&format!(" = {}", default_value));
+        }
+        signature
+    }
+
+    fn metadata(&self) -> HashMap<String, Value> {
+        let mut metadata = HashMap::new();
+        let mut mandatory = false;
+        let mut position = None;
+
+        for attribute in &self.attributes {
+            let Some((name, args)) = attribute_parts(attribute) else {
+                continue;
+            };
+            if !name.eq_ignore_ascii_case("Parameter") {
+                continue;
+            }
+            for (_, arg) in split_top_level(args, b',', POWERSHELL) {
+                let (key, value) = match arg.split_once('=') {
+                    Some((key, value)) => (key.trim(), Some(value.trim())),
+                    None => (arg.trim(), None),
+                };
+                if key.eq_ignore_ascii_case("Mandatory") {
+                    mandatory = value.is_none_or(|v| !v.eq_ignore_ascii_case("$false"));
+                } else if key.eq_ignore_ascii_case("Position") {
+                    position = value.and_then(|v| v.parse::<i64>().ok());
+                }
+            }
+        }
+
+        metadata.insert("isParameter".to_string(), Value::Bool(true));
+        metadata.insert("mandatory".to_string(), Value::Bool(mandatory));
+        if let Some(position) = position {
+            metadata.insert("position".to_string(), Value::from(position));
+        }
+        if let Some(type_name) = &self.type_name {
+            metadata.insert("type".to_string(), Value::from(type_name.clone()));
+        }
+        if let Some(default_value) = &self.default_value {
+            metadata.insert(
+                "defaultValue".to_string(),
+                Value::from(default_value.clone()),
+            );
+        }
+        if !self.attributes.is_empty() {
+            metadata.insert(
+                "attributes".to_string(),
+                Value::from(self.attributes.clone()),
+            );
+        }
+        metadata
+    }
+}
+
+/// Enrich parameter symbols of every function/method in the file
+pub(super) fn enrich_parameters(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let functions: Vec<(String, usize, usize)> = symbols
+        .iter()
+        .filter(|s| matches!(s.kind, SymbolKind::Function | SymbolKind::Method))
+        .map(|s| (s.id.clone(), s.start_byte as usize, s.end_byte as usize))
+        .collect();
+
+    for (function_id, start, end) in functions {
+        let text = ctx.text(start, end);
+
+        if let Some((list_offset, list)) = find_parameter_list(text) {
+            for parameter in parse_parameter_list(list) {
+                apply_parameter(ctx, symbols, &function_id, start + list_offset, parameter);
+            }
+        }
+
+        for (name, dyn_start, dyn_end) in find_dynamic_parameters(text) {
+            if find_parameter_symbol(symbols, &function_id, &name).is_some() {
+                continue;
+            }
+            let mut metadata = HashMap::new();
+            metadata.insert("isParameter".to_string(), Value::Bool(true));
+            metadata.insert("dynamic".to_string(), Value::Bool(true));
+
+            symbols.push(ctx.create_symbol(
+                &name,
+                SymbolKind::Variable,
+                start + dyn_start,
+                start + dyn_end,
+                SymbolOptions {
+                    signature: Some(format!("DynamicParam ${}", name)),
+                    parent_id: Some(function_id.clone()),
+                    metadata: Some(metadata),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+}
+
+/// Locate the parameter list of a function: an inline `function Name(...)` list,
+/// or the top-level `param(...)` block of the body. Returns (offset, list text).
+fn find_parameter_list(text: &str) -> Option<(usize, &str)> {
+    let mut first_paren = None;
+    let mut first_brace = None;
+    for (index, byte) in CodeBytes::new(text, 0, POWERSHELL) {
+        match byte {
+            b'(' if first_paren.is_none() => first_paren = Some(index),
+            b'{' => {
+                first_brace = Some(index);
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    let brace = first_brace?;
+    if let Some(paren) = first_paren.filter(|&paren| paren < brace) {
+        let close = find_matching_delimiter(text, paren, POWERSHELL)?;
+        return Some((paren + 1, &text[paren + 1..close]));
+    }
+
+    let body_end = find_matching_delimiter(text, brace, POWERSHELL).unwrap_or(text.len());
+    let body = &text[brace + 1..body_end];
+    let mask = top_level_mask(body, POWERSHELL);
+    let param = PARAM_BLOCK_RE
+        .find_iter(body)
+        .find(|m| mask.get(m.start()).copied().unwrap_or(false))?;
+
+    let paren = param.end() - 1;
+    let close = find_matching_delimiter(body, paren, POWERSHELL)?;
+    let offset = brace + 1 + paren + 1;
+    Some((offset, &body[paren + 1..close]))
+}
+
+fn parse_parameter_list(list: &str) -> Vec<ParsedParameter> {
+    split_top_level(list, b',', POWERSHELL)
+        .into_iter()
+        .filter_map(|(offset, segment)| {
+            let mut parameter = parse_parameter(segment)?;
+            parameter.start += offset;
+            parameter.end += offset;
+            Some(parameter)
+        })
+        .collect()
+}
+
+/// Parse `[Attr(...)][type]$Name = default`, skipping leading help comments
+fn parse_parameter(segment: &str) -> Option<ParsedParameter> {
+    let bytes = segment.as_bytes();
+    let mut pos = 0;
+    let mut start = None;
+    let mut attributes = Vec::new();
+    let mut type_name = None;
+
+    loop {
+        while pos < bytes.len() && bytes[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        let rest = &segment[pos..];
+        if rest.starts_with("<#") {
+            pos = rest.find("#>").map_or(bytes.len(), |i| pos + i + 2);
+        } else if rest.starts_with('#') {
+            pos = rest.find('\n').map_or(bytes.len(), |i| pos + i);
+        } else if rest.starts_with('[') {
+            let close = find_matching_delimiter(segment, pos, POWERSHELL)?;
+            start.get_or_insert(pos);
+            let group = &segment[pos..=close];
+            let inner = group[1..group.len() - 1].trim();
+            if is_attribute(inner) {
+                attributes.push(group.to_string());
+            } else {
+                type_name = Some(inner.to_string());
+            }
+            pos = close + 1;
+        } else {
+            break;
+        }
+    }
+
+    if bytes.get(pos) != Some(&b'$') {
+        return None;
+    }
+    let start = *start.get_or_insert(pos);
+    let name_start = pos + 1;
+    let name_len = segment[name_start..]
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(segment.len() - name_start);
+    if name_len == 0 {
+        return None;
+    }
+    let name = segment[name_start..name_start + name_len].to_string();
+
+    let after_name = &segment[name_start + name_len..];
+    let default_value = after_name
+        .trim_start()
+        .strip_prefix('=')
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    let end = segment.trim_end().len();
+
+    Some(ParsedParameter {
+        name,
+        start,
+        end,
+        attributes,
+        type_name,
+        default_value,
+    })
+}
+
+/// Split `[Name(args)]` into its name and argument text
+fn attribute_parts(attribute: &str) -> Option<(&str, &str)> {
+    let inner = attribute.strip_prefix('[')?.strip_suffix(']')?.trim();
+    let open = inner.find('(')?;
+    let close = find_matching_delimiter(inner, open, POWERSHELL)?;
+    Some((inner[..open].trim(), &inner[open + 1..close]))
+}
+
+/// Attributes carry an argument list or are well-known marker attributes;
+/// anything else in brackets is a type accelerator.
+fn is_attribute(inner: &str) -> bool {
+    let name_len = inner
+        .find(|c: char| !(c.is_alphanumeric() || c == '.' || c == '_'))
+        .unwrap_or(inner.len());
+    let name = &inner[..name_len];
+    inner[name_len..].trim_start().starts_with('(')
+        || BARE_ATTRIBUTES
+            .iter()
+            .any(|bare| bare.eq_ignore_ascii_case(name))
+}
+
+fn find_dynamic_parameters(text: &str) -> Vec<(String, usize, usize)> {
+    let mut parameters = Vec::new();
+    for block in DYNAMIC_PARAM_RE.find_iter(text) {
+        let brace = block.end() - 1;
+        let Some(close) = find_matching_delimiter(text, brace, POWERSHELL) else {
+            continue;
+        };
+        for captures in RUNTIME_PARAMETER_RE.captures_iter(&text[brace..close]) {
+            let whole = captures.get(0).unwrap();
+            parameters.push((
+                captures[1].to_string(),
+                brace + whole.start(),
+                brace + whole.end(),
+            ));
+        }
+    }
+    parameters
+}
+
+fn find_parameter_symbol<'s>(
+    symbols: &'s mut [Symbol],
+    function_id: &str,
+    name: &str,
+) -> Option<&'s mut Symbol> {
+    symbols.iter_mut().find(|s| {
+        s.parent_id.as_deref() == Some(function_id)
+            && s.name.trim_start_matches('$').eq_ignore_ascii_case(name)
+    })
+}
+
+fn apply_parameter(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    function_id: &str,
+    list_start: usize,
+    parameter: ParsedParameter,
+) {
+    let signature = parameter.signature();
+    let metadata = parameter.metadata();
+
+    if let Some(existing) = find_parameter_symbol(symbols, function_id, &parameter.name) {
+        existing.signature = Some(signature);
+        for (key, value) in metadata {
+            set_metadata(existing, &key, value);
+        }
+        return;
+    }
+
+    symbols.push(ctx.create_symbol(
+        &parameter.name,
+        SymbolKind::Variable,
+        list_start + parameter.start,
+        list_start + parameter.end,
+        SymbolOptions {
+            signature: Some(signature),
+            parent_id: Some(function_id.to_string()),
+            metadata: Some(metadata),
+            ..Default::default()
+        },
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn enrich(content: &str) -> Vec<Symbol> {
+        let ctx = EnrichmentContext::new("module.ps1", "powershell", content, Path::new("."));
+        let name_start = content.find("function ").unwrap() + "function ".len();
+        let name_end = name_start + content[name_start..].find([' ', '(', '{']).unwrap();
+        let function = ctx.create_symbol(
+            &content[name_start..name_end],
+            SymbolKind::Function,
+            0,
+            content.len(),
+            SymbolOptions::default(),
+        );
+        let mut symbols = vec![function];
+        enrich_parameters(&ctx, &mut symbols);
+        symbols
+    }
+
+    fn parameter<'s>(symbols: &'s [Symbol], name: &str) -> &'s Symbol {
+        symbols
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap_or_else(|| panic!("parameter {} not extracted", name))
+    }
+
+    #[test]
+    fn test_param_block_attributes_type_and_default() {
+        let content = r#"function Get-Thing {
+    [CmdletBinding()]
+    param(
+        # The thing to get
+        [Parameter(Mandatory=$true, Position=0)]
+        [ValidateSet('a', 'b')]
+        [string]$Name = 'a',
+
+        [int]$Count
+    )
+    $Name
+}"#;
+        let symbols = enrich(content);
+        let name = parameter(&symbols, "Name");
+
+        assert_eq!(
+            name.signature.as_deref(),
+            Some("[Parameter(Mandatory=$true, Position=0)][ValidateSet('a', 'b')][string]$Name = 'a'")
+        );
+        let metadata = name.metadata.as_ref().unwrap();
+        assert_eq!(metadata["mandatory"], Value::Bool(true));
+        assert_eq!(metadata["position"], Value::from(0));
+        assert_eq!(metadata["type"], Value::from("string"));
+        assert_eq!(metadata["defaultValue"], Value::from("'a'"));
+        assert_eq!(name.parent_id.as_deref(), Some(symbols[0].id.as_str()));
+        assert_eq!(name.start_line, 5, "range starts at the first attribute");
+
+        let count = parameter(&symbols, "Count");
+        assert_eq!(count.signature.as_deref(), Some("[int]$Count"));
+        assert_eq!(
+            count.metadata.as_ref().unwrap()["mandatory"],
+            Value::Bool(false)
+        );
+    }
+
+    #[test]
+    fn test_inline_parameter_list() {
+        let symbols = enrich("function Add($a, [int]$b = 2) { $a + $b }");
+        assert_eq!(parameter(&symbols, "a").signature.as_deref(), Some("$a"));
+        assert_eq!(
+            parameter(&symbols, "b").signature.as_deref(),
+            Some("[int]$b = 2")
+        );
+    }
+
+    #[test]
+    fn test_existing_parameter_symbol_is_updated_not_duplicated() {
+        let content = "function Get-Thing { param([Parameter(Mandatory)][string]$Name) }";
+        let ctx = EnrichmentContext::new("module.ps1", "powershell", content, Path::new("."));
+        let function = ctx.create_symbol(
+            "Get-Thing",
+            SymbolKind::Function,
+            0,
+            content.len(),
+            SymbolOptions::default(),
+        );
+        let existing_start = content.find("$Name").unwrap();
+        let existing = ctx.create_symbol(
+            "Name",
+            SymbolKind::Variable,
+            existing_start,
+            existing_start + 5,
+            SymbolOptions {
+                parent_id: Some(function.id.clone()),
+                ..Default::default()
+            },
+        );
+        let mut symbols = vec![function, existing];
+        enrich_parameters(&ctx, &mut symbols);
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(
+            symbols[1].signature.as_deref(),
+            Some("[Parameter(Mandatory)][string]$Name")
+        );
+        assert_eq!(
+            symbols[1].metadata.as_ref().unwrap()["mandatory"],
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn test_nested_function_param_block_is_not_used() {
+        let content = "function Outer { function Inner { param($x) } }";
+        let symbols = enrich(content);
+        assert!(symbols.iter().all(|s| s.name != "x"));
+    }
+
+    #[test]
+    fn test_dynamic_parameters_become_named_symbols() {
+        let content = r#"function Get-Dyn {
+    [CmdletBinding()]
+    param([string]$Path)
+    DynamicParam {
+        $param = New-Object System.Management.Automation.RuntimeDefinedParameter('Environment', [string], $attrs)
+        $dict.Add('Environment', $param)
+        return $dict
+    }
+}"#;
+        let symbols = enrich(content);
+        let dynamic = parameter(&symbols, "Environment");
+        assert_eq!(
+            dynamic.metadata.as_ref().unwrap()["dynamic"],
+            Value::Bool(true)
+        );
+        assert_eq!(dynamic.start_line, 5);
+    }
+}
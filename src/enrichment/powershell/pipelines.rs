@@ -0,0 +1,399 @@
+// PowerShell pipeline dataflow
+//
+// `Get-ADUser -Filter * | Where-Object { $_.Enabled } | Export-Csv users.csv`
+// passes objects from each command to the next. Julie records the three
+// command invocations, but not that they form one pipeline. For each pipeline
+// this pass links consecutive command stages with Uses relationships from the
+// producing command to the consuming one (metadata.dataflow = "pipeline"),
+// reusing Julie's invocation symbols and adding one (metadata.isCommandInvocation)
+// where it has none. Stages that are expressions (`$users | ...`) have no
+// symbol and break the chain.
+//
+// The first command of a pipeline carries its shape: metadata.pipeline lists
+// the stages, and metadata.pipelineOutput the file the pipeline writes when
+// it ends in Export-*, Out-File, Set-Content, Add-Content, or Tee-Object (the
+// -Path/-FilePath/-LiteralPath argument or first positional one), or in a
+// `> file` redirection. Identifiers inside script-block arguments
+// (`{ $_.Enabled }`) are attributed to the enclosing function, or to the
+// pipeline's first command at script level.
+
+use super::POWERSHELL;
+use crate::enrichment::base::{innermost_symbol_at, set_metadata, CodeBytes};
+use crate::enrichment::base::{EnrichmentContext, SymbolOptions};
+use julie_extractors::{ExtractionResults, RelationshipKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Whitespace, `$var =` assignments, and `return` before a stage's command
+static PREFIX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^\s*(?:\$[\w:]+\s*[-+]?=\s*|return\s+)?").unwrap());
+
+/// Command name at the start of a pipeline stage (`& Get-Thing`, `. ./x.ps1`, `%`, `?`)
+static COMMAND_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:[&.][ \t]*)?([A-Za-z_][\w.-]*|[%?])").unwrap());
+
+/// Arguments: quoted strings, or runs of non-whitespace
+static ARGUMENT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""[^"]*"|'[^']*'|\S+"#).unwrap());
+
+/// `> file` / `>> file` / `2> file`, the redirection that ends a stage
+static REDIRECT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?:^|\s)[1-6*]?>>?\s*("[^"]*"|'[^']*'|[^\s|;]+)"#).unwrap());
+
+/// Cmdlets that write their pipeline input to a file
+const OUTPUT_CMDLETS: &[&str] = &["out-file", "set-content", "add-content", "tee-object"];
+
+/// Parameters naming the file an output cmdlet writes
+const PATH_PARAMETERS: &[&str] = &["-path", "-filepath", "-literalpath"];
+
+/// Parameters of output cmdlets that take a value (anything else is a switch)
+const VALUE_PARAMETERS: &[&str] = &["-encoding", "-delimiter", "-value", "-width", "-variable"];
+
+/// One pipeline: the byte ranges of its stages
+#[derive(Debug, PartialEq)]
+struct Pipeline {
+    stages: Vec<(usize, usize)>,
+}
+
+pub(super) fn link_pipelines(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    for pipeline in find_pipelines(ctx.content) {
+        let (pipeline_start, pipeline_end) =
+            (pipeline.stages[0].0, pipeline.stages.last().unwrap().1);
+        let function_id = innermost_symbol_at(&results.symbols, pipeline_start, |s| {
+            s.kind == SymbolKind::Function && !is_invocation(s)
+        })
+        .map(|s| s.id.clone());
+
+        let mut stages = Vec::new();
+        let mut stage_ids = Vec::new();
+        for &(start, end) in &pipeline.stages {
+            let text = ctx.text(start, end);
+            // `$users = Get-ADUser | ...` pipes from the command, not the variable
+            let prefix = PREFIX_RE.find(text).map_or(0, |m| m.end());
+            let command = COMMAND_RE
+                .captures(&text[prefix..])
+                .map(|c| c.get(1).unwrap());
+            let Some(command) = command else {
+                stages.push(Value::from(text.trim()));
+                stage_ids.push(None);
+                continue;
+            };
+            stages.push(Value::from(command.as_str()));
+            let command_start = start + prefix + command.start();
+            let id = command_symbol(
+                ctx,
+                results,
+                command.as_str(),
+                command_start,
+                function_id.clone(),
+            );
+            stage_ids.push(Some(id));
+        }
+
+        for (index, pair) in stage_ids.windows(2).enumerate() {
+            let (Some(from), Some(to)) = (&pair[0], &pair[1]) else {
+                continue;
+            };
+            let mut metadata = HashMap::new();
+            metadata.insert("dataflow".to_string(), Value::from("pipeline"));
+            metadata.insert("stage".to_string(), Value::from(index + 1));
+            results.relationships.push(ctx.create_relationship(
+                from,
+                to,
+                RelationshipKind::Uses,
+                pipeline.stages[index + 1].0,
+                Some(metadata),
+            ));
+        }
+
+        let &(last_start, last_end) = pipeline.stages.last().unwrap();
+        let output = output_target(ctx.text(last_start, last_end));
+        let Some(head_id) = stage_ids.iter().flatten().next().cloned() else {
+            continue;
+        };
+        let owner = function_id.unwrap_or_else(|| head_id.clone());
+        for identifier in &mut results.identifiers {
+            let byte = identifier.start_byte as usize;
+            if pipeline_start <= byte
+                && byte < pipeline_end
+                && in_script_block(ctx.content, pipeline_start, byte)
+            {
+                identifier.containing_symbol_id = Some(owner.clone());
+            }
+        }
+        let head = results
+            .symbols
+            .iter_mut()
+            .find(|s| s.id == head_id)
+            .unwrap();
+        set_metadata(head, "pipeline", stages);
+        if let Some(output) = output {
+            set_metadata(head, "pipelineOutput", output);
+        }
+    }
+}
+
+/// Julie's invocation symbol for the command at `start`, or a new one; returns its id
+fn command_symbol(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    name: &str,
+    start: usize,
+    parent_id: Option<String>,
+) -> String {
+    if let Some(symbol) = results
+        .symbols
+        .iter()
+        .find(|s| s.start_byte as usize == start && s.name.eq_ignore_ascii_case(name))
+    {
+        return symbol.id.clone();
+    }
+    let mut metadata = HashMap::new();
+    metadata.insert("isCommandInvocation".to_string(), Value::Bool(true));
+    let symbol = ctx.create_symbol(
+        name,
+        SymbolKind::Function,
+        start,
+        start + name.len(),
+        SymbolOptions {
+            parent_id,
+            metadata: Some(metadata),
+            ..Default::default()
+        },
+    );
+    let id = symbol.id.clone();
+    results.symbols.push(symbol);
+    id
+}
+
+/// Whether a symbol is a command invocation added by this pass
+fn is_invocation(symbol: &Symbol) -> bool {
+    symbol
+        .metadata
+        .as_ref()
+        .is_some_and(|m| m.contains_key("isCommandInvocation"))
+}
+
+/// Whether `byte` sits inside a `{ }` opened after `start`
+fn in_script_block(content: &str, start: usize, byte: usize) -> bool {
+    let mut depth = 0usize;
+    for (_, b) in CodeBytes::new(&content[..byte], start, POWERSHELL) {
+        match b {
+            b'{' => depth += 1,
+            b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    depth > 0
+}
+
+/// Statements with at least one top-level `|`, at every nesting level
+fn find_pipelines(content: &str) -> Vec<Pipeline> {
+    /// A bracket level: where its current statement starts and the pipes seen in it
+    struct Level {
+        statement_start: usize,
+        pipes: Vec<usize>,
+    }
+    fn finish(level: &mut Level, end: usize, pipelines: &mut Vec<Pipeline>) {
+        if !level.pipes.is_empty() {
+            let mut bounds = vec![level.statement_start];
+            for &pipe in &level.pipes {
+                bounds.push(pipe);
+                bounds.push(pipe + 1);
+            }
+            bounds.push(end);
+            let stages = bounds.chunks(2).map(|b| (b[0], b[1])).collect();
+            pipelines.push(Pipeline { stages });
+        }
+        level.pipes.clear();
+    }
+
+    let bytes = content.as_bytes();
+    let mut pipelines = Vec::new();
+    let mut levels = vec![Level {
+        statement_start: 0,
+        pipes: Vec::new(),
+    }];
+    let mut last_code = b'\n';
+
+    for (index, byte) in CodeBytes::new(content, 0, POWERSHELL) {
+        match byte {
+            b'{' | b'(' | b'[' => levels.push(Level {
+                statement_start: index + 1,
+                pipes: Vec::new(),
+            }),
+            b'}' | b')' | b']' if levels.len() > 1 => {
+                let mut level = levels.pop().unwrap();
+                finish(&mut level, index, &mut pipelines);
+            }
+            b'|' if bytes.get(index + 1) != Some(&b'|') && last_code != b'|' => {
+                levels.last_mut().unwrap().pipes.push(index);
+            }
+            b';' | b'\n' => {
+                // A trailing `|` or backtick continues the statement on the next line
+                if byte == b'\n' && matches!(last_code, b'|' | b'`') {
+                    continue;
+                }
+                let level = levels.last_mut().unwrap();
+                finish(level, index, &mut pipelines);
+                level.statement_start = index + 1;
+            }
+            _ => {}
+        }
+        if !byte.is_ascii_whitespace() {
+            last_code = byte;
+        }
+    }
+    for level in levels.iter_mut().rev() {
+        finish(level, content.len(), &mut pipelines);
+    }
+    pipelines.sort_by_key(|p| p.stages[0].0);
+    pipelines
+}
+
+/// The file an output stage writes, if it is one
+fn output_target(stage: &str) -> Option<String> {
+    // `2>&1` merges streams rather than writing a file
+    if let Some(redirect) = REDIRECT_RE
+        .captures_iter(stage)
+        .find(|c| !c[1].starts_with('&'))
+    {
+        return Some(unquote(&redirect[1]).to_string());
+    }
+    let arguments: Vec<&str> = ARGUMENT_RE
+        .find_iter(stage.trim())
+        .map(|m| m.as_str())
+        .collect();
+    let (command, arguments) = arguments.split_first()?;
+    let command = command.to_ascii_lowercase();
+    if !command.starts_with("export-") && !OUTPUT_CMDLETS.contains(&command.as_str()) {
+        return None;
+    }
+
+    let mut positional = None;
+    let mut index = 0;
+    while index < arguments.len() {
+        let argument = arguments[index];
+        let lowered = argument.to_ascii_lowercase();
+        if PATH_PARAMETERS.contains(&lowered.trim_end_matches(':')) {
+            return arguments
+                .get(index + 1)
+                .map(|path| unquote(path).to_string());
+        }
+        if lowered.starts_with('-') {
+            index += if VALUE_PARAMETERS.contains(&lowered.as_str()) {
+                2
+            } else {
+                1
+            };
+            continue;
+        }
+        positional.get_or_insert(unquote(argument));
+        index += 1;
+    }
+    positional.map(str::to_string)
+}
+
+fn unquote(text: &str) -> &str {
+    text.trim_matches(|c| c == '"' || c == '\'')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use julie_extractors::IdentifierKind;
+    use std::path::Path;
+
+    fn link(content: &str) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("report.ps1", "powershell", content, Path::new("."));
+        let enabled = content.find("Enabled").unwrap();
+        let mut results = ExtractionResults {
+            identifiers: vec![ctx.create_identifier(
+                "Enabled",
+                IdentifierKind::MemberAccess,
+                enabled,
+                enabled + "Enabled".len(),
+                None,
+            )],
+            ..test_results(Vec::new())
+        };
+        link_pipelines(&ctx, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_pipeline_stages_are_linked_with_output_target() {
+        let content = "Import-Module AD\nGet-ADUser -Filter * |\n    Where-Object { $_.Enabled } | Export-Csv -NoTypeInformation 'users.csv'\n";
+        let results = link(content);
+
+        let names: Vec<&str> = results.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Get-ADUser", "Where-Object", "Export-Csv"]);
+        assert_eq!(results.relationships.len(), 2);
+        assert_eq!(
+            results.relationships[0].from_symbol_id,
+            results.symbols[0].id
+        );
+        assert_eq!(results.relationships[0].to_symbol_id, results.symbols[1].id);
+        assert_eq!(results.relationships[1].to_symbol_id, results.symbols[2].id);
+
+        let head = results.symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(head["pipelineOutput"], "users.csv");
+        assert_eq!(
+            head["pipeline"],
+            serde_json::json!(["Get-ADUser", "Where-Object", "Export-Csv"])
+        );
+        assert_eq!(
+            results.identifiers[0].containing_symbol_id.as_deref(),
+            Some(results.symbols[0].id.as_str())
+        );
+    }
+
+    #[test]
+    fn test_pipelines_inside_functions_and_redirections() {
+        let content = "function Save-Report {\n    $items | ForEach-Object { $_.Enabled } | Sort-Object > report.txt\n    if ($a -or $b) { Write-Output 'x' }\n}\n";
+        let ctx = EnrichmentContext::new("report.ps1", "powershell", content, Path::new("."));
+        let function = ctx.create_symbol(
+            "Save-Report",
+            SymbolKind::Function,
+            0,
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let enabled = content.find("Enabled").unwrap();
+        let mut results = ExtractionResults {
+            identifiers: vec![ctx.create_identifier(
+                "Enabled",
+                IdentifierKind::MemberAccess,
+                enabled,
+                enabled + 7,
+                None,
+            )],
+            ..test_results(vec![function])
+        };
+        link_pipelines(&ctx, &mut results);
+
+        // $items is an expression stage: only ForEach-Object → Sort-Object is linked
+        assert_eq!(results.relationships.len(), 1);
+        let head = results
+            .symbols
+            .iter()
+            .find(|s| s.name == "ForEach-Object")
+            .unwrap();
+        assert_eq!(
+            head.parent_id.as_deref(),
+            Some(results.symbols[0].id.as_str())
+        );
+        assert_eq!(
+            head.metadata.as_ref().unwrap()["pipelineOutput"],
+            "report.txt"
+        );
+        assert_eq!(
+            results.identifiers[0].containing_symbol_id.as_deref(),
+            Some(results.symbols[0].id.as_str())
+        );
+        assert!(!results.symbols.iter().any(|s| s.name == "Write-Output"));
+    }
+}
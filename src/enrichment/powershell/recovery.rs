@@ -0,0 +1,107 @@
+// PowerShell declarations inside parse errors
+//
+// DSC `Configuration` blocks, `workflow` definitions, and functions following a
+// malformed here-string all land in tree-sitter ERROR nodes, so Julie never
+// reports them. This pass scans code no symbol covers for the declaring
+// keywords with the shared error-recovery primitives and restores each as a
+// Function spanning its parameter list and body. A `global:`/`script:` scope
+// prefix is dropped from the name and kept in metadata.scope.
+
+use super::POWERSHELL;
+use crate::enrichment::base::error_recovery::{
+    keyword_names, recovered_symbol, unclaimed_regions, RecoveredDeclaration,
+};
+use crate::enrichment::base::{set_metadata, EnrichmentContext};
+use julie_extractors::{Symbol, SymbolKind};
+
+const DECLARATION_KEYWORDS: &[&str] = &["function", "filter", "workflow", "configuration"];
+
+pub(super) fn recover_declarations(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let mut recovered: Vec<Symbol> = Vec::new();
+    for region in unclaimed_regions(ctx, symbols, POWERSHELL) {
+        for pair in keyword_names(ctx.content, region, DECLARATION_KEYWORDS, POWERSHELL) {
+            let (scope, name, name_start) = match pair.name.split_once(':') {
+                Some((scope, name)) => (Some(scope), name, pair.name_start + scope.len() + 1),
+                None => (None, pair.name, pair.name_start),
+            };
+            if name.is_empty() {
+                continue;
+            }
+            let Some(mut symbol) = recovered_symbol(
+                ctx,
+                symbols.iter().chain(&recovered),
+                symbols,
+                RecoveredDeclaration {
+                    name,
+                    kind: SymbolKind::Function,
+                    start: pair.keyword_start,
+                    header_end: name_start + name.len(),
+                    name_range: name_start..name_start + name.len(),
+                },
+                POWERSHELL,
+            ) else {
+                continue;
+            };
+            set_metadata(&mut symbol, "keyword", pair.keyword.to_ascii_lowercase());
+            if let Some(scope) = scope {
+                set_metadata(&mut symbol, "scope", scope.to_ascii_lowercase());
+            }
+            recovered.push(symbol);
+        }
+    }
+    symbols.extend(recovered);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use serde_json::Value;
+    use std::path::Path;
+
+    #[test]
+    fn test_recovers_configuration_and_scoped_functions() {
+        let content = "Configuration WebServer {\n  Node localhost { }\n}\n\nfunction global:Get-Thing($Name) {\n  'function Nope'\n}\n";
+        let ctx = EnrichmentContext::new("deploy.ps1", "powershell", content, Path::new("."));
+        let mut symbols = Vec::new();
+        recover_declarations(&ctx, &mut symbols);
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["WebServer", "Get-Thing"]);
+
+        let web = &symbols[0];
+        assert_eq!(
+            ctx.symbol_text(web),
+            "Configuration WebServer {\n  Node localhost { }\n}"
+        );
+        let metadata = web.metadata.as_ref().unwrap();
+        assert_eq!(metadata["keyword"], Value::from("configuration"));
+        assert_eq!(metadata["extractedFromError"], Value::Bool(true));
+
+        let get_thing = &symbols[1];
+        assert_eq!(get_thing.start_line, 5);
+        assert_eq!(
+            get_thing.metadata.as_ref().unwrap()["scope"],
+            Value::from("global")
+        );
+        assert_eq!(
+            get_thing.signature.as_deref(),
+            Some("function global:Get-Thing")
+        );
+    }
+
+    #[test]
+    fn test_extracted_functions_are_not_duplicated() {
+        let content = "function Get-Thing { }\n";
+        let ctx = EnrichmentContext::new("a.ps1", "powershell", content, Path::new("."));
+        let mut symbols = vec![ctx.create_symbol(
+            "Get-Thing",
+            SymbolKind::Function,
+            0,
+            22,
+            SymbolOptions::default(),
+        )];
+        recover_declarations(&ctx, &mut symbols);
+        assert_eq!(symbols.len(), 1);
+    }
+}
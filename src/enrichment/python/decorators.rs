@@ -0,0 +1,439 @@
+// Python decorators
+//
+// Julie extracts a decorated def or class without its decorators, so
+// `@property`, `@app.route("/users")`, and `@pytest.fixture` leave no trace on
+// the symbol. This pass collects each definition's decorators (multi-line
+// ones included) and records:
+//
+// - metadata.decorators as `{ name, args, text }`, in source order, with the
+//   dotted name as written and each argument as source text; the decorators
+//   are also prepended to the signature, so `@functools.lru_cache` shows there
+// - `@property` / `@cached_property` and their `.setter` / `.deleter`: kind
+//   Property, with metadata.propertyAccessor for setters and deleters
+// - `@staticmethod` and `@classmethod`: metadata.isStatic / isClassMethod
+// - Flask, FastAPI, and Django REST framework routes: metadata.route and
+//   metadata.httpMethod (comma-separated when several are allowed); Django's
+//   `require_http_methods` family sets httpMethod only, since its URLs live in
+//   urls.py
+// - `@pytest.fixture`: metadata.isFixture, plus fixtureScope when given
+
+use super::{logical_line_end, string_list, string_literal, PYTHON};
+use crate::enrichment::base::{
+    append_metadata, code_mask, collapse_whitespace, find_matching_delimiter, set_metadata,
+    split_top_level, trim_with_offset, EnrichmentContext,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::json;
+use std::sync::LazyLock;
+
+/// `@name` or `@a.b.c` at the start of a line
+static DECORATOR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*@[ \t]*([A-Za-z_]\w*(?:[ \t]*\.[ \t]*[A-Za-z_]\w*)*)").unwrap()
+});
+
+/// The def or class a decorator run applies to
+static DEFINITION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[ \t]*(?:async[ \t]+)?(?:def|class)[ \t]+([A-Za-z_]\w*)").unwrap()
+});
+
+/// Route decorators named after the HTTP method: `@router.get`, `@bp.post`
+const METHOD_DECORATORS: &[&str] = &["get", "post", "put", "delete", "patch", "options", "head"];
+
+/// Django view decorators that restrict the HTTP method
+const DJANGO_METHOD_DECORATORS: &[(&str, &str)] = &[
+    ("require_GET", "GET"),
+    ("require_POST", "POST"),
+    ("require_safe", "GET,HEAD"),
+];
+
+/// Kinds a decorator can be attached to
+const TARGET_KINDS: &[SymbolKind] = &[
+    SymbolKind::Class,
+    SymbolKind::Function,
+    SymbolKind::Method,
+    SymbolKind::Property,
+];
+
+/// One decorator occurrence in the source
+#[derive(Debug)]
+struct Decorator<'a> {
+    start: usize,
+    end: usize,
+    /// Dotted name with whitespace removed: `app.route`
+    name: String,
+    text: &'a str,
+    /// Raw argument texts; empty when the decorator is not called
+    args: Vec<&'a str>,
+}
+
+impl Decorator<'_> {
+    /// Last segment of the dotted name
+    fn short_name(&self) -> &str {
+        self.name.rsplit('.').next().unwrap_or(&self.name)
+    }
+
+    fn positional(&self, index: usize) -> Option<&str> {
+        self.args
+            .iter()
+            .filter(|a| keyword_arg(a).is_none())
+            .nth(index)
+            .copied()
+    }
+
+    fn keyword(&self, key: &str) -> Option<&str> {
+        self.args
+            .iter()
+            .filter_map(|a| keyword_arg(a))
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value)
+    }
+}
+
+pub(super) fn enrich_decorators(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, PYTHON);
+    let decorators = find_decorators(ctx.content, &code);
+
+    let mut index = 0;
+    while index < decorators.len() {
+        // A run of decorators separated only by blank and comment lines
+        let mut last = index;
+        while last + 1 < decorators.len()
+            && only_blank_or_comments(
+                &ctx.content[decorators[last].end..decorators[last + 1].start],
+            )
+        {
+            last += 1;
+        }
+        let run = &decorators[index..=last];
+        index = last + 1;
+
+        let after = run.last().unwrap().end;
+        let Some(definition) = next_definition(ctx.content, after) else {
+            continue;
+        };
+        let name = definition.1;
+        let Some(symbol) = symbols
+            .iter_mut()
+            .filter(|s| TARGET_KINDS.contains(&s.kind) && s.name == name)
+            .find(|s| {
+                run[0].start <= s.start_byte as usize && s.start_byte as usize <= definition.0
+            })
+        else {
+            continue;
+        };
+        apply_decorators(symbol, run);
+    }
+}
+
+fn apply_decorators(symbol: &mut Symbol, run: &[Decorator]) {
+    for decorator in run {
+        append_metadata(
+            symbol,
+            "decorators",
+            json!({ "name": decorator.name, "args": decorator.args, "text": collapse_whitespace(decorator.text) }),
+        );
+
+        match decorator.short_name() {
+            "property" | "cached_property" => symbol.kind = SymbolKind::Property,
+            accessor @ ("setter" | "deleter") if decorator.name.contains('.') => {
+                symbol.kind = SymbolKind::Property;
+                set_metadata(symbol, "propertyAccessor", accessor);
+            }
+            "staticmethod" => set_metadata(symbol, "isStatic", true),
+            "classmethod" => set_metadata(symbol, "isClassMethod", true),
+            "fixture" => {
+                set_metadata(symbol, "isFixture", true);
+                if let Some(scope) = decorator.keyword("scope").and_then(string_literal) {
+                    set_metadata(symbol, "fixtureScope", scope);
+                }
+            }
+            _ => apply_route(symbol, decorator),
+        }
+    }
+
+    let prefix = run
+        .iter()
+        .map(|decorator| collapse_whitespace(decorator.text))
+        .collect::<Vec<_>>()
+        .join(" ");
+    symbol.signature = Some(match symbol.signature.take() {
+        Some(signature) if signature.starts_with('@') => signature,
+        Some(signature) => format!("{} {}", prefix, signature),
+        None => format!("{} {}", prefix, symbol.name),
+    });
+}
+
+/// Flask / FastAPI / Django REST framework / Django method restrictions
+fn apply_route(symbol: &mut Symbol, decorator: &Decorator) {
+    let short = decorator.short_name();
+    let dotted = decorator.name.contains('.');
+    let path = || {
+        decorator
+            .positional(0)
+            .or_else(|| decorator.keyword("path"))
+            .or_else(|| decorator.keyword("rule"))
+            .and_then(string_literal)
+    };
+
+    let (route, methods) = match short {
+        // Flask `@app.route("/users", methods=["GET", "POST"])`, FastAPI `api_route`
+        "route" | "api_route" if dotted => {
+            let methods = decorator
+                .keyword("methods")
+                .map(string_list)
+                .filter(|m| !m.is_empty())
+                .unwrap_or_else(|| vec!["GET".to_string()]);
+            (path(), methods)
+        }
+        // FastAPI `@router.get("/users")`, Flask 2 `@bp.post("/users")`
+        method if dotted && METHOD_DECORATORS.contains(&method) => {
+            (path(), vec![method.to_ascii_uppercase()])
+        }
+        // Django REST framework `@api_view(["GET", "POST"])`
+        "api_view" => {
+            let methods = decorator
+                .positional(0)
+                .or_else(|| decorator.keyword("http_method_names"))
+                .map(string_list)
+                .filter(|m| !m.is_empty())
+                .unwrap_or_else(|| vec!["GET".to_string()]);
+            (None, methods)
+        }
+        // Django REST framework `@action(detail=True, methods=["post"], url_path="reset")`
+        "action" if !decorator.args.is_empty() => {
+            let methods = decorator
+                .keyword("methods")
+                .map(string_list)
+                .filter(|m| !m.is_empty())
+                .unwrap_or_else(|| vec!["GET".to_string()]);
+            (
+                decorator.keyword("url_path").and_then(string_literal),
+                methods,
+            )
+        }
+        "require_http_methods" => {
+            let methods = decorator.positional(0).map(string_list).unwrap_or_default();
+            (None, methods)
+        }
+        other => match DJANGO_METHOD_DECORATORS
+            .iter()
+            .find(|(name, _)| *name == other)
+        {
+            Some((_, methods)) => (None, vec![methods.to_string()]),
+            None => return,
+        },
+    };
+
+    if methods.is_empty() {
+        return;
+    }
+    let methods: Vec<String> = methods.iter().map(|m| m.to_ascii_uppercase()).collect();
+    set_metadata(symbol, "httpMethod", methods.join(","));
+    if let Some(route) = route {
+        set_metadata(symbol, "route", route);
+    }
+}
+
+fn find_decorators<'a>(content: &'a str, code: &[bool]) -> Vec<Decorator<'a>> {
+    let mut decorators = Vec::new();
+    let mut resume = 0;
+    for captures in DECORATOR_RE.captures_iter(content) {
+        let name = captures.get(1).unwrap();
+        let at = content[..name.start()].rfind('@').unwrap();
+        if at < resume || !code[at] {
+            continue;
+        }
+        let end = logical_line_end(content, code, at);
+        resume = end;
+
+        let after_name = name.end()
+            + (content[name.end()..end].len() - content[name.end()..end].trim_start().len());
+        let args = if content.as_bytes().get(after_name) == Some(&b'(') {
+            find_matching_delimiter(content, after_name, PYTHON)
+                .map(|close| {
+                    split_top_level(&content[after_name + 1..close], b',', PYTHON)
+                        .into_iter()
+                        .map(|(offset, arg)| trim_with_offset(offset, arg).1)
+                        .filter(|arg| !arg.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        decorators.push(Decorator {
+            start: at,
+            end,
+            name: name.as_str().split_whitespace().collect(),
+            text: content[at..end].trim_end(),
+            args,
+        });
+    }
+    decorators
+}
+
+/// Byte and text of the name in the first def/class line after `from`, when
+/// only blank and comment lines come between
+fn next_definition(content: &str, from: usize) -> Option<(usize, &str)> {
+    let mut line_start = content[from..].find('\n').map(|i| from + i + 1)?;
+    while line_start < content.len() {
+        let line_end = content[line_start..]
+            .find('\n')
+            .map_or(content.len(), |i| line_start + i);
+        let line = &content[line_start..line_end];
+        if let Some(captures) = DEFINITION_RE.captures(line) {
+            let name = captures.get(1).unwrap();
+            return Some((line_start + name.start(), name.as_str()));
+        }
+        if !only_blank_or_comments(line) {
+            return None;
+        }
+        line_start = line_end + 1;
+    }
+    None
+}
+
+fn only_blank_or_comments(text: &str) -> bool {
+    text.lines().all(|line| {
+        let line = line.trim();
+        line.is_empty() || line.starts_with('#')
+    })
+}
+
+/// `key=value` → (key, value); None for positional arguments
+fn keyword_arg(arg: &str) -> Option<(&str, &str)> {
+    let (key, value) = arg.split_once('=')?;
+    let key = key.trim();
+    let is_name = !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+    (is_name && !value.starts_with('=')).then(|| (key, value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"@app.route("/users", methods=["GET", "POST"])
+@login_required
+def users():
+    pass
+
+@router.get(
+    "/items/{item_id}",
+    response_model=Item,
+)
+async def read_item(item_id: int):
+    pass
+
+@pytest.fixture(scope="module")
+def client():
+    pass
+
+class Account:
+    @property
+    def balance(self):
+        return self._balance
+
+    @balance.setter
+    def balance(self, value):
+        self._balance = value
+
+    @staticmethod
+    @functools.lru_cache(maxsize=None)
+    def rates():
+        pass
+
+    @classmethod
+    def create(cls):
+        pass
+"#;
+
+    fn enriched() -> Vec<Symbol> {
+        let ctx = EnrichmentContext::new("app/views.py", "python", SOURCE, Path::new("."));
+        let definitions = [
+            ("def users", "users", SymbolKind::Function),
+            ("async def read_item", "read_item", SymbolKind::Function),
+            ("def client", "client", SymbolKind::Function),
+            ("def balance(self):", "balance", SymbolKind::Method),
+            ("def balance(self, value)", "balance", SymbolKind::Method),
+            ("def rates", "rates", SymbolKind::Method),
+            ("def create", "create", SymbolKind::Method),
+        ];
+        let mut symbols: Vec<Symbol> = definitions
+            .iter()
+            .map(|(text, name, kind)| {
+                let start = SOURCE.find(text).unwrap();
+                let end = SOURCE[start..]
+                    .find("pass\n")
+                    .map_or(start + text.len(), |i| start + i + 4);
+                ctx.create_symbol(
+                    name,
+                    kind.clone(),
+                    start,
+                    end,
+                    SymbolOptions {
+                        signature: Some(text.to_string()),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        enrich_decorators(&ctx, &mut symbols);
+        symbols
+    }
+
+    fn meta(symbol: &Symbol) -> &HashMap<String, Value> {
+        symbol.metadata.as_ref().unwrap()
+    }
+
+    #[test]
+    fn test_route_decorators_and_ordered_decorator_list() {
+        let symbols = enriched();
+        let users = meta(&symbols[0]);
+        assert_eq!(users["route"], Value::from("/users"));
+        assert_eq!(users["httpMethod"], Value::from("GET,POST"));
+        assert_eq!(
+            users["decorators"],
+            json!([
+                {
+                    "name": "app.route",
+                    "args": ["\"/users\"", "methods=[\"GET\", \"POST\"]"],
+                    "text": "@app.route(\"/users\", methods=[\"GET\", \"POST\"])",
+                },
+                { "name": "login_required", "args": [], "text": "@login_required" },
+            ])
+        );
+
+        // Multi-line decorator arguments
+        let read_item = meta(&symbols[1]);
+        assert_eq!(read_item["route"], Value::from("/items/{item_id}"));
+        assert_eq!(read_item["httpMethod"], Value::from("GET"));
+
+        let client = meta(&symbols[2]);
+        assert_eq!(client["isFixture"], true);
+        assert_eq!(client["fixtureScope"], Value::from("module"));
+    }
+
+    #[test]
+    fn test_property_static_and_class_methods() {
+        let symbols = enriched();
+        assert_eq!(symbols[3].kind, SymbolKind::Property);
+        assert!(!meta(&symbols[3]).contains_key("propertyAccessor"));
+        assert_eq!(symbols[4].kind, SymbolKind::Property);
+        assert_eq!(meta(&symbols[4])["propertyAccessor"], Value::from("setter"));
+
+        assert_eq!(symbols[5].kind, SymbolKind::Method);
+        assert_eq!(meta(&symbols[5])["isStatic"], true);
+        assert_eq!(
+            symbols[5].signature.as_deref(),
+            Some("@staticmethod @functools.lru_cache(maxsize=None) def rates")
+        );
+        assert_eq!(meta(&symbols[6])["isClassMethod"], true);
+        assert!(!meta(&symbols[6]).contains_key("isStatic"));
+    }
+}
@@ -0,0 +1,289 @@
+// Python module exports
+//
+// Python visibility is convention: a module's public API is its `__all__`
+// when it has one, and otherwise every name without a leading underscore.
+// Julie leaves module members without a visibility, so this pass sets it on
+// top-level symbols:
+//
+// - with a literal `__all__` (list or tuple of strings, extended by `+=`,
+//   `.extend()`, and `.append()`): listed names Public, everything else Private
+// - without one: `_name` Private (dunders like `__version__` excepted)
+// - when any part of `__all__` is computed (`[n for n in dir()]`,
+//   `other.__all__ + [...]`), the `__all__` symbol gets metadata.dynamicAll;
+//   the literal names found are still Public, the rest fall back to the
+//   underscore convention rather than a guess
+//
+// The literal names are recorded on the `__all__` symbol as
+// metadata.exportedNames. In `__init__.py`, relative from-imports
+// (`from .models import User`) and from-imports named in `__all__` are the
+// package's re-exports: metadata.reExport plus reExportFrom, the module as
+// written.
+
+use super::imports::{imported_names, FROM_IMPORT_RE};
+use super::{logical_line_end, string_literal, PYTHON};
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, set_metadata, split_top_level, EnrichmentContext,
+};
+use julie_extractors::{Symbol, SymbolKind, Visibility};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `__all__ = [...]`, `__all__: list[str] = (...)`, `__all__ += [...]`,
+/// `__all__.extend([...])`, `__all__.append("x")` at module level
+static ALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^__all__[ \t]*(?:(\+?=)|:[^=\n]*=|\.(extend|append)[ \t]*\()[ \t]*").unwrap()
+});
+
+/// Names collected from every `__all__` statement in a module
+#[derive(Debug, Default, PartialEq)]
+struct ModuleAll {
+    names: Vec<String>,
+    dynamic: bool,
+}
+
+pub(super) fn apply_module_exports(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, PYTHON);
+    let all = module_all(ctx.content, &code);
+
+    for symbol in symbols.iter_mut().filter(|s| is_module_member(s)) {
+        if symbol.name == "__all__" {
+            if let Some(all) = &all {
+                set_metadata(symbol, "exportedNames", all.names.clone());
+                if all.dynamic {
+                    set_metadata(symbol, "dynamicAll", true);
+                }
+            }
+            continue;
+        }
+        let listed = all.as_ref().map(|all| all.names.contains(&symbol.name));
+        symbol.visibility = match (listed, &all) {
+            (Some(true), _) => Some(Visibility::Public),
+            (Some(false), Some(all)) if !all.dynamic => Some(Visibility::Private),
+            _ if is_private_name(&symbol.name) => Some(Visibility::Private),
+            _ => symbol.visibility.take(),
+        };
+    }
+
+    if ctx.file_path.ends_with("__init__.py") {
+        mark_re_exports(ctx, &code, symbols, all.as_ref());
+    }
+}
+
+/// Top-level symbols: no parent and starting in column 0
+fn is_module_member(symbol: &Symbol) -> bool {
+    symbol.parent_id.is_none() && symbol.start_column == 0
+}
+
+/// `_helper`, `__cache`; not `__version__`
+fn is_private_name(name: &str) -> bool {
+    name.starts_with('_') && !(name.len() > 4 && name.starts_with("__") && name.ends_with("__"))
+}
+
+/// Every module-level `__all__` statement, in order; None without any
+fn module_all(content: &str, code: &[bool]) -> Option<ModuleAll> {
+    let mut all: Option<ModuleAll> = None;
+    for captures in ALL_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        if !code[whole.start()] {
+            continue;
+        }
+        let entry = all.get_or_insert_with(ModuleAll::default);
+        let value_start = whole.end();
+        let method = captures.get(2).map(|m| m.as_str());
+        let value = match method {
+            // `.extend(...)` / `.append(...)`: the call's argument
+            Some(_) => {
+                let open = content[..value_start].rfind('(').unwrap();
+                match find_matching_delimiter(content, open, PYTHON) {
+                    Some(close) => &content[open + 1..close],
+                    None => {
+                        entry.dynamic = true;
+                        continue;
+                    }
+                }
+            }
+            None => {
+                // Plain and annotated assignments replace what came before
+                if captures.get(1).is_none_or(|op| op.as_str() == "=") {
+                    entry.names.clear();
+                    entry.dynamic = false;
+                }
+                let end = logical_line_end(content, code, value_start);
+                content[value_start..end].split('#').next().unwrap_or("")
+            }
+        };
+        let names = if method == Some("append") {
+            string_literal(value).map(|name| vec![name])
+        } else {
+            literal_names(value)
+        };
+        match names {
+            Some(names) => {
+                for name in names {
+                    if !entry.names.contains(&name) {
+                        entry.names.push(name);
+                    }
+                }
+            }
+            None => entry.dynamic = true,
+        }
+    }
+    all
+}
+
+/// Entries of a list or tuple made only of string literals; None otherwise
+fn literal_names(value: &str) -> Option<Vec<String>> {
+    let value = value.trim();
+    let inner = match (value.chars().next(), value.chars().last()) {
+        (Some('['), Some(']')) | (Some('('), Some(')')) => &value[1..value.len() - 1],
+        _ => return None,
+    };
+    split_top_level(inner, b',', PYTHON)
+        .into_iter()
+        .map(|(_, entry)| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(string_literal)
+        .collect()
+}
+
+/// Set metadata.reExport / reExportFrom on an `__init__.py`'s re-exported imports
+fn mark_re_exports(
+    ctx: &EnrichmentContext,
+    code: &[bool],
+    symbols: &mut [Symbol],
+    all: Option<&ModuleAll>,
+) {
+    for captures in FROM_IMPORT_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        let keyword = whole.start() + (whole.as_str().len() - whole.as_str().trim_start().len());
+        if !code[keyword] || keyword != whole.start() {
+            continue;
+        }
+        let end = logical_line_end(ctx.content, code, whole.end());
+        let module = format!("{}{}", &captures[1], &captures[2]);
+        let relative = !captures[1].is_empty();
+        let names = imported_names(&ctx.content[whole.end()..end]);
+
+        for (name, alias) in &names {
+            let bound = alias.as_ref().unwrap_or(name);
+            let listed = all.is_some_and(|all| all.names.contains(bound));
+            // A literal __all__ decides; without one, relative imports are re-exports
+            let exported = match all {
+                Some(all) if !all.dynamic => listed,
+                _ => relative || listed,
+            };
+            if !exported {
+                continue;
+            }
+            let Some(symbol) = symbols
+                .iter_mut()
+                .filter(|s| s.kind == SymbolKind::Import)
+                .filter(|s| keyword <= s.start_byte as usize && (s.start_byte as usize) < end)
+                .find(|s| &s.name == bound || (names.len() == 1 && &s.name == name))
+            else {
+                continue;
+            };
+            set_metadata(symbol, "reExport", true);
+            set_metadata(symbol, "reExportFrom", module.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use serde_json::Value;
+    use std::path::Path;
+
+    /// A top-level symbol for each `(anchor, name, kind)`, starting at `anchor`
+    fn apply(path: &str, content: &str, names: &[(&str, &str, SymbolKind)]) -> Vec<Symbol> {
+        let ctx = EnrichmentContext::new(path, "python", content, Path::new("."));
+        let mut symbols: Vec<Symbol> = names
+            .iter()
+            .map(|(anchor, name, kind)| {
+                let start = content.find(anchor).unwrap();
+                let mut symbol = ctx.create_symbol(
+                    name,
+                    kind.clone(),
+                    start,
+                    start + anchor.len(),
+                    SymbolOptions::default(),
+                );
+                symbol.start_column = 0;
+                symbol
+            })
+            .collect();
+        apply_module_exports(&ctx, &mut symbols);
+        symbols
+    }
+
+    fn visibility(symbols: &[Symbol], name: &str) -> Option<Visibility> {
+        symbols
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap()
+            .visibility
+            .clone()
+    }
+
+    #[test]
+    fn test_all_sets_visibility_and_init_re_exports() {
+        let content = "from .models import User, Group as Team\nfrom .internal import Engine\nfrom typing import List\n\n__all__ = [\"User\", \"Team\"]\n__all__ += (\"create\",)\n__all__.append('VERSION')\n\ndef create(): pass\ndef helper(): pass\nVERSION = '1.0'\n";
+        let symbols = apply(
+            "pkg/__init__.py",
+            content,
+            &[
+                ("User", "User", SymbolKind::Import),
+                ("Group as Team", "Team", SymbolKind::Import),
+                ("Engine", "Engine", SymbolKind::Import),
+                ("List", "List", SymbolKind::Import),
+                ("__all__ =", "__all__", SymbolKind::Variable),
+                ("def create", "create", SymbolKind::Function),
+                ("def helper", "helper", SymbolKind::Function),
+                ("VERSION =", "VERSION", SymbolKind::Constant),
+            ],
+        );
+
+        let all = symbols[4].metadata.as_ref().unwrap();
+        assert_eq!(
+            all["exportedNames"],
+            serde_json::json!(["User", "Team", "create", "VERSION"])
+        );
+        assert!(!all.contains_key("dynamicAll"));
+        assert_eq!(visibility(&symbols, "create"), Some(Visibility::Public));
+        assert_eq!(visibility(&symbols, "helper"), Some(Visibility::Private));
+        assert_eq!(visibility(&symbols, "Engine"), Some(Visibility::Private));
+
+        let team = symbols[1].metadata.as_ref().unwrap();
+        assert_eq!(team["reExport"], true);
+        assert_eq!(team["reExportFrom"], Value::from(".models"));
+        // Relative but left out of a literal __all__
+        assert!(symbols[2].metadata.is_none());
+    }
+
+    #[test]
+    fn test_underscore_convention_and_dynamic_all() {
+        let content = "__all__ = [name for name in dir() if not name.startswith('_')]\n__all__ += ['run']\n\ndef run(): pass\ndef _cache(): pass\ndef other(): pass\n__version__ = '2'\n";
+        let symbols = apply(
+            "pkg/cli.py",
+            content,
+            &[
+                ("__all__ =", "__all__", SymbolKind::Variable),
+                ("def run", "run", SymbolKind::Function),
+                ("def _cache", "_cache", SymbolKind::Function),
+                ("def other", "other", SymbolKind::Function),
+                ("__version__ =", "__version__", SymbolKind::Variable),
+            ],
+        );
+
+        let all = symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(all["dynamicAll"], true);
+        assert_eq!(all["exportedNames"], serde_json::json!(["run"]));
+        assert_eq!(visibility(&symbols, "run"), Some(Visibility::Public));
+        assert_eq!(visibility(&symbols, "_cache"), Some(Visibility::Private));
+        // Not guessed from a computed __all__
+        assert_eq!(visibility(&symbols, "other"), None);
+        assert_eq!(visibility(&symbols, "__version__"), None);
+    }
+}
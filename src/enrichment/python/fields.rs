@@ -0,0 +1,393 @@
+// Python model fields
+//
+// In @dataclass, attrs, and pydantic classes the class-level annotated
+// assignments are the model's fields (`email: str = Field(default=None)`), but
+// Julie extracts them like any other assignment. This pass recognizes such
+// classes by decorator or base class and turns each annotated class-level
+// assignment into a Property symbol parented to the class, with the
+// annotation as propertyType and the default summarized in the signature.
+// ClassVar annotations are marked static since they are not instance fields.
+
+use super::{decorator_name, decorators_above, logical_line_end, PYTHON};
+use crate::enrichment::base::{
+    code_mask, collapse_whitespace, set_metadata, EnrichmentContext, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static CLASS_HEADER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^([ \t]*)class\s+([A-Za-z_]\w*)\s*(?:\(([^)]*)\))?\s*:").unwrap()
+});
+
+/// `name: annotation` at the start of a logical line
+static ANNOTATED_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([A-Za-z_]\w*)\s*:\s*(\S.*)$").unwrap());
+
+/// attrs decorators, with and without their module prefix
+const ATTRS_DECORATORS: &[&str] = &[
+    "attr.s",
+    "attr.attrs",
+    "attr.define",
+    "attr.frozen",
+    "attr.mutable",
+    "attrs.define",
+    "attrs.frozen",
+    "attrs.mutable",
+    "define",
+    "frozen",
+    "mutable",
+];
+
+/// Base classes whose subclasses declare fields by annotation
+const MODEL_BASES: &[&str] = &["BaseModel", "BaseSettings", "SQLModel", "RootModel"];
+
+/// Defaults longer than this are shortened in signatures
+const MAX_DEFAULT_LEN: usize = 60;
+
+pub(super) fn enrich_model_fields(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let code = code_mask(ctx.content, PYTHON);
+
+    for captures in CLASS_HEADER_RE.captures_iter(ctx.content) {
+        let header = captures.get(0).unwrap();
+        let class_start = header.start() + captures[1].len();
+        if !code.get(class_start).copied().unwrap_or(false) {
+            continue;
+        }
+        let Some(style) = field_style(
+            ctx.content,
+            header.start(),
+            captures.get(3).map(|m| m.as_str()),
+        ) else {
+            continue;
+        };
+        let class_name = &captures[2];
+        let Some(class_id) = symbols
+            .iter()
+            .filter(|s| s.kind == SymbolKind::Class && s.name == class_name)
+            .find(|s| (s.start_byte as usize) <= class_start && class_start < (s.end_byte as usize))
+            .map(|s| s.id.clone())
+        else {
+            continue;
+        };
+
+        for field in class_fields(ctx.content, &code, captures[1].len(), header.end()) {
+            apply_field(ctx, symbols, &class_id, style, field);
+        }
+    }
+}
+
+/// dataclass / attrs / pydantic, from the class's decorators and bases
+fn field_style(content: &str, header_line: usize, bases: Option<&str>) -> Option<&'static str> {
+    for decorator in decorators_above(content, header_line) {
+        let full = decorator.trim_start_matches('@');
+        let full = full.split('(').next().unwrap_or(full).trim();
+        if decorator_name(decorator) == "dataclass" {
+            return Some("dataclass");
+        }
+        if ATTRS_DECORATORS.contains(&full) {
+            return Some("attrs");
+        }
+    }
+    let is_model = bases.is_some_and(|bases| {
+        bases.split(',').any(|base| {
+            let base = base.trim();
+            let base = base.split('[').next().unwrap_or(base);
+            MODEL_BASES.contains(&base.rsplit('.').next().unwrap_or(base))
+        })
+    });
+    is_model.then_some("pydantic")
+}
+
+/// An annotated class-level assignment
+#[derive(Debug)]
+struct ModelField {
+    name: String,
+    annotation: String,
+    default: Option<String>,
+    start: usize,
+    end: usize,
+}
+
+/// Annotated assignments directly in a class body (nested defs/classes skipped)
+fn class_fields(
+    content: &str,
+    code: &[bool],
+    class_indent: usize,
+    body_start: usize,
+) -> Vec<ModelField> {
+    let mut fields = Vec::new();
+    let mut body_indent = None;
+    let mut pos = body_start;
+
+    // Skip the rest of the header line
+    if let Some(newline) = content[pos..].find('\n') {
+        if !content[pos..pos + newline].trim().is_empty()
+            && !content[pos..pos + newline].trim_start().starts_with('#')
+        {
+            return fields;
+        }
+        pos += newline + 1;
+    } else {
+        return fields;
+    }
+
+    while pos < content.len() {
+        let end = logical_line_end(content, code, pos);
+        let line = &content[pos..end];
+        let indent = line.len() - line.trim_start().len();
+        let text_start = pos + indent;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() || !code.get(text_start).copied().unwrap_or(false) {
+            // Blank lines, comments, and docstrings don't end the body
+            if !trimmed.is_empty()
+                && indent <= class_indent
+                && code.get(text_start).copied().unwrap_or(false)
+            {
+                break;
+            }
+            pos = end + 1;
+            continue;
+        }
+        if indent <= class_indent {
+            break;
+        }
+        let body_indent = *body_indent.get_or_insert(indent);
+
+        if indent == body_indent {
+            if let Some(field) = parse_field(content, code, text_start, end) {
+                fields.push(field);
+            }
+        }
+        pos = end + 1;
+    }
+    fields
+}
+
+fn parse_field(content: &str, code: &[bool], start: usize, end: usize) -> Option<ModelField> {
+    // Drop a trailing comment, then split on the first top-level `=`
+    let mut statement_end = end;
+    let mut equals = None;
+    let mut depth = 0usize;
+    for index in start..end {
+        let byte = content.as_bytes()[index];
+        let is_code = code.get(index).copied().unwrap_or(false);
+        // Comment bytes are masked out; a comment's `#` follows code, a string's doesn't
+        if byte == b'#' && !is_code && code.get(index - 1).copied().unwrap_or(false) {
+            statement_end = index;
+            break;
+        }
+        if !is_code {
+            continue;
+        }
+        match byte {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth = depth.saturating_sub(1),
+            b'=' if depth == 0 && equals.is_none() => {
+                let bytes = content.as_bytes();
+                let previous = bytes[index - 1];
+                let next = bytes.get(index + 1).copied();
+                if next != Some(b'=') && !matches!(previous, b'=' | b'!' | b'<' | b'>' | b':') {
+                    equals = Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let declaration = &content[start..equals.unwrap_or(statement_end)];
+    let captures = ANNOTATED_RE.captures(declaration.trim_end())?;
+    let name = captures[1].to_string();
+    if matches!(name.as_str(), "else" | "try" | "finally" | "lambda") {
+        return None;
+    }
+    let default = equals
+        .map(|equals| collapse_whitespace(&content[equals + 1..statement_end]))
+        .filter(|default| !default.is_empty());
+
+    Some(ModelField {
+        name,
+        annotation: collapse_whitespace(&captures[2]),
+        default,
+        start,
+        end: start + content[start..statement_end].trim_end().len(),
+    })
+}
+
+fn apply_field(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    class_id: &str,
+    style: &str,
+    field: ModelField,
+) {
+    let is_class_var =
+        field.annotation.starts_with("ClassVar") || field.annotation.starts_with("typing.ClassVar");
+    let mut signature = format!("{}: {}", field.name, field.annotation);
+    if let Some(default) = &field.default {
+        signature.push_str(" = ");
+        signature.push_str(&summarize(default));
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert(
+        "propertyType".to_string(),
+        Value::from(field.annotation.clone()),
+    );
+    metadata.insert("fieldStyle".to_string(), Value::from(style));
+    if let Some(default) = &field.default {
+        metadata.insert("defaultValue".to_string(), Value::from(default.clone()));
+    }
+    if is_class_var {
+        metadata.insert("static".to_string(), Value::Bool(true));
+    }
+
+    let existing = symbols.iter_mut().find(|s| {
+        s.name == field.name
+            && field.start <= s.start_byte as usize
+            && (s.start_byte as usize) < field.end
+    });
+    if let Some(symbol) = existing {
+        symbol.kind = SymbolKind::Property;
+        symbol.parent_id = Some(class_id.to_string());
+        symbol.signature = Some(signature);
+        for (key, value) in metadata {
+            set_metadata(symbol, &key, value);
+        }
+        return;
+    }
+
+    symbols.push(ctx.create_symbol(
+        &field.name,
+        SymbolKind::Property,
+        field.start,
+        field.end,
+        SymbolOptions {
+            signature: Some(signature),
+            parent_id: Some(class_id.to_string()),
+            metadata: Some(metadata),
+            ..Default::default()
+        },
+    ));
+}
+
+/// Shorten long defaults such as `Field(default_factory=list, description="...")`
+fn summarize(default: &str) -> String {
+    if default.chars().count() <= MAX_DEFAULT_LEN {
+        return default.to_string();
+    }
+    let head: String = default.chars().take(MAX_DEFAULT_LEN).collect();
+    let head = head.trim_end().trim_end_matches(',');
+    if head.contains('(') && default.ends_with(')') {
+        format!("{}...)", head)
+    } else {
+        format!("{}...", head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn enrich(content: &str, class_name: &str) -> Vec<Symbol> {
+        let ctx = EnrichmentContext::new("models.py", "python", content, Path::new("."));
+        let start = content.find(&format!("class {}", class_name)).unwrap();
+        let class = ctx.create_symbol(
+            class_name,
+            SymbolKind::Class,
+            start,
+            content.len(),
+            SymbolOptions::default(),
+        );
+        let mut symbols = vec![class];
+        enrich_model_fields(&ctx, &mut symbols);
+        symbols
+    }
+
+    fn field<'s>(symbols: &'s [Symbol], name: &str) -> &'s Symbol {
+        symbols
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap_or_else(|| panic!("field {} not extracted", name))
+    }
+
+    #[test]
+    fn test_dataclass_fields_and_class_vars() {
+        let content = r#"from dataclasses import dataclass, field
+
+@dataclass(frozen=True)
+class User:
+    """A user.
+
+    name: not a field
+    """
+    name: str
+    tags: list[str] = field(default_factory=list)  # labels
+    registry: ClassVar[dict] = {}
+
+    def greet(self) -> str:
+        local: str = "hi"
+        return local
+"#;
+        let symbols = enrich(content, "User");
+        assert_eq!(
+            symbols.len(),
+            4,
+            "docstring and method locals are not fields"
+        );
+
+        let tags = field(&symbols, "tags");
+        assert_eq!(tags.kind, SymbolKind::Property);
+        assert_eq!(
+            tags.signature.as_deref(),
+            Some("tags: list[str] = field(default_factory=list)")
+        );
+        assert_eq!(tags.parent_id.as_deref(), Some(symbols[0].id.as_str()));
+        let metadata = tags.metadata.as_ref().unwrap();
+        assert_eq!(metadata["propertyType"], Value::from("list[str]"));
+        assert_eq!(metadata["fieldStyle"], Value::from("dataclass"));
+
+        let registry = field(&symbols, "registry");
+        assert_eq!(
+            registry.metadata.as_ref().unwrap()["static"],
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn test_pydantic_model_with_multiline_field() {
+        let content = "class Account(pydantic.BaseModel):\n    email: Annotated[str, Field(min_length=3)] = Field(\n        default=None,\n        description=\"Primary contact address used for all notifications\",\n    )\n";
+        let symbols = enrich(content, "Account");
+
+        let email = field(&symbols, "email");
+        assert_eq!(
+            email.metadata.as_ref().unwrap()["propertyType"],
+            Value::from("Annotated[str, Field(min_length=3)]")
+        );
+        assert_eq!(
+            email.metadata.as_ref().unwrap()["fieldStyle"],
+            Value::from("pydantic")
+        );
+        assert!(email.signature.as_deref().unwrap().ends_with("...)"));
+        assert_eq!(email.end_line, 5);
+    }
+
+    #[test]
+    fn test_plain_classes_are_ignored() {
+        let symbols = enrich("class Plain:\n    x: int = 1\n", "Plain");
+        assert_eq!(symbols.len(), 1);
+
+        let symbols = enrich(
+            "@attr.s(auto_attribs=True)\nclass Point:\n    x: int = 0\n",
+            "Point",
+        );
+        assert_eq!(
+            field(&symbols, "x").metadata.as_ref().unwrap()["fieldStyle"],
+            Value::from("attrs")
+        );
+    }
+}
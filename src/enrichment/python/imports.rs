@@ -0,0 +1,331 @@
+// Python import resolution
+//
+// Julie records `from ..models.user import User` as an Import symbol carrying
+// the raw dotted path. This pass resolves each import statement to the file it
+// names inside the workspace: relative imports climb one package per leading
+// dot from the current file's directory, absolute imports are looked up from
+// the workspace root. A module resolves to `name.py`, a regular package to
+// `name/__init__.py`, and a namespace package (directory without __init__.py)
+// to the directory itself. Imported names that are submodules
+// (`from . import views`) resolve to the submodule's own file. Results land in
+// the Import symbols' metadata; unresolvable imports are left untouched.
+
+use super::{logical_line_end, PYTHON};
+use crate::enrichment::base::{code_mask, set_metadata, EnrichmentContext};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::path::Path;
+use std::sync::LazyLock;
+
+pub(super) static FROM_IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*from[ \t]+(\.*)[ \t]*([\w.]*)[ \t]+import\b").unwrap()
+});
+
+static IMPORT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*import[ \t]+").unwrap());
+
+/// One imported module or name and what it resolved to
+#[derive(Debug, Clone, PartialEq)]
+struct ResolvedImport {
+    /// Dotted path as written, leading dots included: "..models.user"
+    import_path: String,
+    /// Name the import binds locally (alias, or first/last path component)
+    bound_names: Vec<String>,
+    resolved_path: String,
+    namespace_package: bool,
+}
+
+pub(super) fn resolve_imports(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, PYTHON);
+
+    let mut statements = Vec::new();
+    for captures in FROM_IMPORT_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        let keyword = whole.start() + (whole.as_str().len() - whole.as_str().trim_start().len());
+        if !code.get(keyword).copied().unwrap_or(false) {
+            continue;
+        }
+        let end = logical_line_end(ctx.content, &code, whole.end());
+        let level = captures[1].len();
+        let module = &captures[2];
+        let names = imported_names(&ctx.content[whole.end()..end]);
+        statements.push((
+            keyword,
+            end,
+            resolve_from_import(ctx, level, module, &names),
+        ));
+    }
+    for found in IMPORT_RE.find_iter(ctx.content) {
+        let keyword = found.start() + (found.as_str().len() - found.as_str().trim_start().len());
+        if !code.get(keyword).copied().unwrap_or(false) {
+            continue;
+        }
+        let end = logical_line_end(ctx.content, &code, found.end());
+        let resolved = imported_names(&ctx.content[found.end()..end])
+            .into_iter()
+            .filter_map(|(module, alias)| {
+                let (resolved_path, namespace_package) = resolve_module(ctx, None, &module)?;
+                let bound =
+                    alias.unwrap_or_else(|| module.split('.').next().unwrap_or("").to_string());
+                Some(ResolvedImport {
+                    bound_names: vec![
+                        bound,
+                        module.clone(),
+                        module.rsplit('.').next().unwrap_or("").to_string(),
+                    ],
+                    import_path: module,
+                    resolved_path,
+                    namespace_package,
+                })
+            })
+            .collect();
+        statements.push((keyword, end, resolved));
+    }
+
+    for (start, end, resolved) in statements {
+        if resolved.is_empty() {
+            continue;
+        }
+        let imports: Vec<&mut Symbol> = symbols
+            .iter_mut()
+            .filter(|s| s.kind == SymbolKind::Import)
+            .filter(|s| start <= s.start_byte as usize && (s.start_byte as usize) < end)
+            .collect();
+        let single = resolved.len() == 1;
+
+        for symbol in imports {
+            let matched = resolved
+                .iter()
+                .find(|r| r.bound_names.iter().any(|name| name == &symbol.name))
+                .or_else(|| single.then(|| &resolved[0]));
+            let Some(import) = matched else {
+                continue;
+            };
+            set_metadata(symbol, "importPath", import.import_path.clone());
+            set_metadata(symbol, "resolved", true);
+            set_metadata(symbol, "resolvedPath", import.resolved_path.clone());
+            if import.namespace_package {
+                set_metadata(symbol, "namespacePackage", true);
+            }
+        }
+    }
+}
+
+/// `a, b as c` or `(a,\n b)` → [(a, None), (b, Some(c))]; `*` is skipped
+pub(super) fn imported_names(list: &str) -> Vec<(String, Option<String>)> {
+    let list = list.split('#').next().unwrap_or(list);
+    list.trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .replace("\\\n", " ")
+        .split(',')
+        .filter_map(|item| {
+            let mut words = item.split_whitespace();
+            let name = words.next()?;
+            if name == "*" {
+                return None;
+            }
+            let alias = match (words.next(), words.next()) {
+                (Some("as"), Some(alias)) => Some(alias.to_string()),
+                _ => None,
+            };
+            Some((name.to_string(), alias))
+        })
+        .collect()
+}
+
+fn resolve_from_import(
+    ctx: &EnrichmentContext,
+    level: usize,
+    module: &str,
+    names: &[(String, Option<String>)],
+) -> Vec<ResolvedImport> {
+    let import_path = format!("{}{}", ".".repeat(level), module);
+    let level = (level > 0).then_some(level);
+    let module_resolution = if module.is_empty() {
+        None
+    } else {
+        resolve_module(ctx, level, module)
+    };
+
+    names
+        .iter()
+        .filter_map(|(name, alias)| {
+            // `from pkg import submodule` names a file of its own
+            let submodule = if module.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", module, name)
+            };
+            let (resolved_path, namespace_package) = resolve_module(ctx, level, &submodule)
+                .or_else(|| module_resolution.clone())
+                .or_else(|| {
+                    // `from . import name` where name lives in the package's __init__.py
+                    module
+                        .is_empty()
+                        .then(|| resolve_module(ctx, level, ""))
+                        .flatten()
+                })?;
+            let mut bound_names = vec![name.clone()];
+            bound_names.extend(alias.clone());
+            Some(ResolvedImport {
+                import_path: import_path.clone(),
+                bound_names,
+                resolved_path,
+                namespace_package,
+            })
+        })
+        .collect()
+}
+
+/// Resolve a dotted module path to (workspace-relative path, is namespace package).
+/// `level` is the number of leading dots for relative imports.
+fn resolve_module(
+    ctx: &EnrichmentContext,
+    level: Option<usize>,
+    module: &str,
+) -> Option<(String, bool)> {
+    let mut base = match level {
+        Some(level) => {
+            let mut dir = Path::new(ctx.file_path).parent()?.to_path_buf();
+            for _ in 1..level {
+                if !dir.pop() {
+                    return None;
+                }
+            }
+            dir
+        }
+        None => Path::new("").to_path_buf(),
+    };
+    for part in module.split('.').filter(|part| !part.is_empty()) {
+        base.push(part);
+    }
+    let candidate = base.to_string_lossy().replace('\\', "/");
+    let candidate = candidate.trim_end_matches('/');
+
+    if !candidate.is_empty() {
+        if let Some(path) = ctx.resolve_workspace_path(&format!("{}.py", candidate)) {
+            return Some((path, false));
+        }
+    }
+    let package = if candidate.is_empty() {
+        "__init__.py".to_string()
+    } else {
+        format!("{}/__init__.py", candidate)
+    };
+    if let Some(path) = ctx.resolve_workspace_path(&package) {
+        return Some((path, false));
+    }
+    if candidate.is_empty() {
+        return None;
+    }
+    ctx.resolve_workspace_directory(candidate)
+        .map(|path| (path, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use serde_json::Value;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn workspace(files: &[&str]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        dir
+    }
+
+    /// Create an Import symbol per bound name, then resolve
+    fn resolve(root: &TempDir, file_path: &str, content: &str, names: &[&str]) -> Vec<Symbol> {
+        let ctx = EnrichmentContext::new(file_path, "python", content, root.path());
+        let mut symbols: Vec<Symbol> = names
+            .iter()
+            .map(|name| {
+                let start = content.rfind(name).unwrap();
+                ctx.create_symbol(
+                    name,
+                    SymbolKind::Import,
+                    start,
+                    start + name.len(),
+                    SymbolOptions::default(),
+                )
+            })
+            .collect();
+        resolve_imports(&ctx, &mut symbols);
+        symbols
+    }
+
+    fn resolved_path(symbol: &Symbol) -> Option<&str> {
+        symbol.metadata.as_ref()?.get("resolvedPath")?.as_str()
+    }
+
+    #[test]
+    fn test_from_dot_import_submodule() {
+        let root = workspace(&["app/__init__.py", "app/views.py", "app/main.py"]);
+        let symbols = resolve(
+            &root,
+            "app/main.py",
+            "from . import views, missing\n",
+            &["views", "missing"],
+        );
+
+        assert_eq!(resolved_path(&symbols[0]), Some("app/views.py"));
+        assert_eq!(
+            resolved_path(&symbols[1]),
+            Some("app/__init__.py"),
+            "falls back to the package"
+        );
+        assert_eq!(
+            symbols[0].metadata.as_ref().unwrap()["importPath"],
+            Value::from(".")
+        );
+    }
+
+    #[test]
+    fn test_parent_package_import() {
+        let root = workspace(&["app/pkg/__init__.py", "app/api/routes.py"]);
+        let symbols = resolve(
+            &root,
+            "app/api/routes.py",
+            "from ..pkg import (\n    y as why,\n)\nfrom ...outside import z\n",
+            &["why", "z"],
+        );
+
+        assert_eq!(resolved_path(&symbols[0]), Some("app/pkg/__init__.py"));
+        assert_eq!(
+            symbols[0].metadata.as_ref().unwrap()["importPath"],
+            Value::from("..pkg")
+        );
+        assert!(
+            symbols[1].metadata.is_none(),
+            "unresolvable imports are untouched"
+        );
+    }
+
+    #[test]
+    fn test_namespace_package_and_absolute_imports() {
+        let root = workspace(&["services/billing/invoice.py", "services/main.py"]);
+        let symbols = resolve(
+            &root,
+            "services/main.py",
+            "import services.billing.invoice as inv\nfrom services import billing\n",
+            &["inv", "billing"],
+        );
+
+        assert_eq!(
+            resolved_path(&symbols[0]),
+            Some("services/billing/invoice.py")
+        );
+        assert_eq!(resolved_path(&symbols[1]), Some("services/billing"));
+        assert_eq!(
+            symbols[1].metadata.as_ref().unwrap()["namespacePackage"],
+            Value::Bool(true)
+        );
+    }
+}
@@ -0,0 +1,116 @@
+// Python enrichment passes
+//
+// Julie's Python extractor handles classes, functions, imports, and
+// assignments; these passes fill in missing docstrings, apply decorator
+// semantics (properties, routes, fixtures), add declarative model fields,
+// resolve imports to workspace files, and derive module visibility from
+// `__all__` and underscore naming.
+
+mod decorators;
+mod exports;
+mod fields;
+mod imports;
+
+use super::base::doc_comments::{fill_doc_comments, DocCommentStyle, DocPlacement};
+use super::base::{split_top_level, EnrichmentContext, Lexicon};
+use julie_extractors::{ExtractionResults, SymbolKind};
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+/// Python strings ('...', "...", and triple-quoted runs) and # comments
+pub(super) const PYTHON: Lexicon = Lexicon {
+    quotes: b"\"'",
+    escape: Some(b'\\'),
+    line_comment: Some("#"),
+    block_comment: None,
+};
+
+/// Docstrings: the string literal opening a def or class body
+const DOC_COMMENTS: DocCommentStyle = DocCommentStyle {
+    line_prefixes: &[],
+    block: None,
+    placement: DocPlacement::Following,
+    attribute_prefixes: &[],
+    max_blank_lines: 0,
+    lexicon: PYTHON,
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    fill_doc_comments(ctx, &mut results.symbols, &DOC_COMMENTS, |s| {
+        matches!(
+            s.kind,
+            SymbolKind::Class | SymbolKind::Function | SymbolKind::Method
+        )
+    });
+    decorators::enrich_decorators(ctx, &mut results.symbols);
+    fields::enrich_model_fields(ctx, &mut results.symbols);
+    imports::resolve_imports(ctx, &mut results.symbols);
+    exports::apply_module_exports(ctx, &mut results.symbols);
+}
+
+/// Decorator lines (`@name(...)`) directly above the line starting at `line_start`
+fn decorators_above(content: &str, line_start: usize) -> Vec<&str> {
+    let mut decorators = Vec::new();
+    let mut cursor = line_start;
+    while cursor > 0 {
+        let previous_start = content[..cursor - 1].rfind('\n').map_or(0, |i| i + 1);
+        let line = content[previous_start..cursor - 1].trim();
+        if line.starts_with('@') {
+            decorators.push(line);
+        } else if !line.is_empty() && !line.starts_with('#') {
+            break;
+        }
+        cursor = previous_start;
+    }
+    decorators
+}
+
+/// Decorator name without `@`, arguments, or module path: `@dataclasses.dataclass(frozen=True)` → `dataclass`
+fn decorator_name(decorator: &str) -> &str {
+    let name = decorator.trim_start_matches('@');
+    let name = name.split('(').next().unwrap_or(name).trim();
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// End of the logical line starting at `start`, following open brackets and `\` continuations
+fn logical_line_end(content: &str, code: &[bool], start: usize) -> usize {
+    let bytes = content.as_bytes();
+    let mut depth = 0isize;
+    let mut index = start;
+    while index < bytes.len() {
+        let is_code = code.get(index).copied().unwrap_or(false);
+        match bytes[index] {
+            b'(' | b'[' | b'{' if is_code => depth += 1,
+            b')' | b']' | b'}' if is_code => depth -= 1,
+            b'\n' if depth <= 0 && !(index > 0 && bytes[index - 1] == b'\\') => return index,
+            _ => {}
+        }
+        index += 1;
+    }
+    bytes.len()
+}
+
+/// `"users"`, `r'/a'`, `f"/x"` → contents; None for other expressions
+fn string_literal(text: &str) -> Option<String> {
+    let text = text.trim();
+    let body = text.trim_start_matches(['r', 'R', 'b', 'B', 'u', 'U', 'f', 'F']);
+    let quote = body.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let body = body
+        .strip_prefix(&quote.to_string().repeat(3))
+        .and_then(|b| b.strip_suffix(&quote.to_string().repeat(3)))
+        .or_else(|| body.strip_prefix(quote).and_then(|b| b.strip_suffix(quote)))?;
+    Some(body.to_string())
+}
+
+/// String entries of a list, tuple, or set literal, or a single string
+fn string_list(text: &str) -> Vec<String> {
+    let text = text.trim();
+    let inner = match text.as_bytes().first() {
+        Some(b'[' | b'(' | b'{') if text.len() >= 2 => &text[1..text.len() - 1],
+        _ => text,
+    };
+    split_top_level(inner, b',', PYTHON)
+        .into_iter()
+        .filter_map(|(_, entry)| string_literal(entry))
+        .collect()
+}
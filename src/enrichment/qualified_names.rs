@@ -0,0 +1,332 @@
+// Qualified names - namespace/class paths for every symbol
+//
+// Julie's Symbol carries only a bare name, so `run` in `A` and `run` in `B`
+// look the same to anything that does not walk parent_id. This pass joins each
+// symbol's enclosing declarations into `metadata.qualifiedName` using the
+// language's separator: `com.acme.Service.run` (Java, C#, Python, and most
+// others), `net::http::Client::send` (Rust, C++, Ruby), `App\Models\User::save`
+// (PHP), and `server.tls.cert` for nested YAML/JSON/TOML keys.
+//
+// The enclosing declaration is the parent when Julie set one; otherwise, for
+// languages with block namespaces/modules, the innermost Namespace (Module for
+// Rust) whose span contains the symbol. A file-level `package` (Java, Kotlin,
+// Go, Scala) or file-scoped `namespace X;` (C#, PHP) prefixes the outermost
+// name unless a namespace symbol already supplies it. A qualifiedName set by a
+// language pass (C++ out-of-class definitions) is kept and prefixes its
+// children. Imports, exports, and synthetic file-scope symbols get none.
+
+use crate::enrichment::base::set_metadata;
+use crate::enrichment::base::symbol_index::SymbolIndex;
+use julie_extractors::{ExtractionResults, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+pub const QUALIFIED_NAME: &str = "qualifiedName";
+
+/// `package com.acme.util` (semicolon optional for Kotlin, Go, Scala)
+static PACKAGE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*package[ \t]+([\w.]+)").unwrap());
+
+/// File-scoped `namespace Acme.Billing;` (C#) or `namespace App\Models;` (PHP)
+static FILE_NAMESPACE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*namespace[ \t]+([\w.\\]+)[ \t]*;").unwrap());
+
+/// How a language spells qualified names
+struct Scheme {
+    /// Between a type (or function, or key) and its members
+    separator: &'static str,
+    /// After a namespace, module, or package
+    namespace_separator: &'static str,
+    /// File-level package/namespace declaration
+    package: Option<&'static LazyLock<Regex>>,
+    /// Kinds that enclose later symbols by span when Julie set no parent
+    scope_kinds: &'static [SymbolKind],
+}
+
+impl Scheme {
+    fn for_language(language: &str) -> Self {
+        let (separator, namespace_separator, package, scope_kinds): (_, _, _, &[SymbolKind]) =
+            match language {
+                "rust" => ("::", "::", None, &[SymbolKind::Module]),
+                "cpp" => ("::", "::", None, &[SymbolKind::Namespace]),
+                "ruby" => ("::", "::", None, &[]),
+                "php" => (
+                    "::",
+                    "\\",
+                    Some(&FILE_NAMESPACE_RE),
+                    &[SymbolKind::Namespace],
+                ),
+                "csharp" => (".", ".", Some(&FILE_NAMESPACE_RE), &[SymbolKind::Namespace]),
+                "java" | "kotlin" | "go" | "scala" => (".", ".", Some(&PACKAGE_RE), &[]),
+                _ => (".", ".", None, &[]),
+            };
+        Scheme {
+            separator,
+            namespace_separator,
+            package,
+            scope_kinds,
+        }
+    }
+}
+
+/// Set `metadata.qualifiedName` on every named declaration in the results
+pub fn assign_qualified_names(content: &str, results: &mut ExtractionResults) {
+    let Some(language) = results.symbols.first().map(|s| s.language.clone()) else {
+        return;
+    };
+    let scheme = Scheme::for_language(&language);
+    let package = scheme
+        .package
+        .and_then(|re| re.captures(content))
+        .map(|caps| caps[1].to_string());
+
+    let names = qualified_names(&results.symbols, &scheme, package.as_deref());
+    for (symbol, name) in results.symbols.iter_mut().zip(names) {
+        if has_qualified_name(symbol) || !is_named_declaration(symbol) {
+            continue;
+        }
+        if let Some((name, _)) = name {
+            set_metadata(symbol, QUALIFIED_NAME, name);
+        }
+    }
+}
+
+/// (qualified name, ends in a namespace) for every symbol
+fn qualified_names(
+    symbols: &[Symbol],
+    scheme: &Scheme,
+    package: Option<&str>,
+) -> Vec<Option<(String, bool)>> {
+    let mut by_id: HashMap<&str, usize> = HashMap::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        by_id.entry(symbol.id.as_str()).or_insert(index);
+    }
+    let index = SymbolIndex::new(symbols);
+    let enclosing: Vec<Option<usize>> = symbols
+        .iter()
+        .enumerate()
+        .map(|(position, symbol)| {
+            let found = match symbol.parent_id.as_deref() {
+                Some(parent_id) => by_id.get(parent_id).copied(),
+                None => index
+                    .innermost_at(symbol.start_byte as usize, |scope| {
+                        scope.id != symbol.id
+                            && scheme.scope_kinds.contains(&scope.kind)
+                            && scope.end_byte >= symbol.end_byte
+                    })
+                    .and_then(|scope| by_id.get(scope.id.as_str()).copied()),
+            };
+            found.filter(|&p| p != position && !is_file_scope(&symbols[p]))
+        })
+        .collect();
+
+    let mut names: Vec<Option<(String, bool)>> = symbols
+        .iter()
+        .map(|s| {
+            let name = s.metadata.as_ref()?.get(QUALIFIED_NAME)?.as_str()?;
+            Some((name.to_string(), is_namespace(s)))
+        })
+        .collect();
+
+    for start in 0..symbols.len() {
+        // Walk up to the first ancestor already named, stopping at cycles
+        let mut chain = Vec::new();
+        let mut current = Some(start);
+        let mut base = None;
+        while let Some(position) = current {
+            if let Some(name) = &names[position] {
+                base = Some(name.clone());
+                break;
+            }
+            if chain.contains(&position) {
+                break;
+            }
+            chain.push(position);
+            current = enclosing[position];
+        }
+        if base.is_none() {
+            let outermost = chain.last().map(|&p| &symbols[p]);
+            if let (Some(package), Some(outermost)) = (package, outermost) {
+                if !is_namespace(outermost) {
+                    base = Some((package.to_string(), true));
+                }
+            }
+        }
+
+        for &position in chain.iter().rev() {
+            let symbol = &symbols[position];
+            let name = match &base {
+                Some((prefix, true)) => {
+                    format!("{}{}{}", prefix, scheme.namespace_separator, symbol.name)
+                }
+                Some((prefix, false)) => format!("{}{}{}", prefix, scheme.separator, symbol.name),
+                None => symbol.name.clone(),
+            };
+            let entry = (name, is_namespace(symbol));
+            base = Some(entry.clone());
+            names[position] = Some(entry);
+        }
+    }
+    names
+}
+
+fn is_namespace(symbol: &Symbol) -> bool {
+    matches!(symbol.kind, SymbolKind::Namespace | SymbolKind::Module)
+}
+
+fn is_named_declaration(symbol: &Symbol) -> bool {
+    !matches!(symbol.kind, SymbolKind::Import | SymbolKind::Export) && !is_file_scope(symbol)
+}
+
+fn has_qualified_name(symbol: &Symbol) -> bool {
+    symbol
+        .metadata
+        .as_ref()
+        .is_some_and(|m| m.contains_key(QUALIFIED_NAME))
+}
+
+/// Synthetic symbols spanning the whole file (e.g. SQL's statement owner)
+fn is_file_scope(symbol: &Symbol) -> bool {
+    symbol
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("fileScope"))
+        .is_some_and(|v| *v == Value::Bool(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, EnrichmentContext, SymbolOptions};
+    use std::path::Path;
+
+    /// Symbols positioned at the first occurrence of each `(text, name, kind, parent)` entry
+    fn extract(
+        file_path: &str,
+        language: &str,
+        content: &str,
+        entries: &[(&str, &str, SymbolKind, Option<usize>)],
+    ) -> ExtractionResults {
+        let ctx = EnrichmentContext::new(file_path, language, content, Path::new("."));
+        let mut symbols: Vec<Symbol> = Vec::new();
+        for &(text, name, ref kind, parent) in entries {
+            let start = content.find(text).unwrap();
+            let parent_id = parent.map(|p| symbols[p].id.clone());
+            symbols.push(ctx.create_symbol(
+                name,
+                kind.clone(),
+                start,
+                start + text.len(),
+                SymbolOptions {
+                    parent_id,
+                    ..Default::default()
+                },
+            ));
+        }
+        test_results(symbols)
+    }
+
+    fn qualified(results: &ExtractionResults) -> Vec<Option<&str>> {
+        results
+            .symbols
+            .iter()
+            .map(|s| s.metadata.as_ref()?.get(QUALIFIED_NAME)?.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_java_package_prefixes_the_parent_chain() {
+        let content = "package com.acme.billing;\n\nimport java.util.List;\n\npublic class Invoice {\n    class Line { int total() { return 0; } }\n}\n";
+        let mut results = extract(
+            "Invoice.java",
+            "java",
+            content,
+            &[
+                (
+                    "package com.acme.billing;",
+                    "com.acme.billing",
+                    SymbolKind::Namespace,
+                    None,
+                ),
+                ("import java.util.List;", "List", SymbolKind::Import, None),
+                ("public class Invoice {", "Invoice", SymbolKind::Class, None),
+                ("class Line", "Line", SymbolKind::Class, Some(2)),
+                ("int total()", "total", SymbolKind::Method, Some(3)),
+            ],
+        );
+
+        assign_qualified_names(content, &mut results);
+
+        assert_eq!(
+            qualified(&results),
+            vec![
+                Some("com.acme.billing"),
+                None,
+                Some("com.acme.billing.Invoice"),
+                Some("com.acme.billing.Invoice.Line"),
+                Some("com.acme.billing.Invoice.Line.total"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_block_namespaces_enclose_parentless_symbols_by_span() {
+        let content =
+            "namespace App\\Models {\n    class User {\n        function save() {}\n    }\n}\n";
+        let mut results = extract(
+            "User.php",
+            "php",
+            content,
+            &[
+                (
+                    content.trim_end(),
+                    "App\\Models",
+                    SymbolKind::Namespace,
+                    None,
+                ),
+                (
+                    "class User {\n        function save() {}\n    }",
+                    "User",
+                    SymbolKind::Class,
+                    None,
+                ),
+                ("function save() {}", "save", SymbolKind::Method, Some(1)),
+            ],
+        );
+
+        assign_qualified_names(content, &mut results);
+
+        assert_eq!(
+            qualified(&results),
+            vec![
+                Some("App\\Models"),
+                Some("App\\Models\\User"),
+                Some("App\\Models\\User::save"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_names_set_by_language_passes_are_kept_and_prefix_children() {
+        let content = "void ui::Stack::push() { struct Guard {}; }\n";
+        let mut results = extract(
+            "stack.cpp",
+            "cpp",
+            content,
+            &[
+                (content.trim_end(), "push", SymbolKind::Function, None),
+                ("struct Guard {}", "Guard", SymbolKind::Struct, Some(0)),
+            ],
+        );
+        set_metadata(&mut results.symbols[0], QUALIFIED_NAME, "ui::Stack::push");
+
+        assign_qualified_names(content, &mut results);
+
+        assert_eq!(
+            qualified(&results),
+            vec![Some("ui::Stack::push"), Some("ui::Stack::push::Guard")]
+        );
+    }
+}
@@ -0,0 +1,17 @@
+// Regex enrichment passes
+//
+// Julie's regex extractor pairs a tree-sitter pass with a line-by-line text
+// fallback that accepts anything resembling a pattern and records it at byte 0.
+// The text-fallback pass keeps those symbols only when they are needed and
+// look like patterns, and gives them real positions.
+
+mod text_fallback;
+
+use super::base::EnrichmentContext;
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    text_fallback::filter_text_patterns(ctx, results);
+}
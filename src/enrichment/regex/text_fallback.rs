@@ -0,0 +1,187 @@
+// Text-fallback regex patterns
+//
+// The fallback runs over every line of the file and turns any line that
+// compiles as a regex (most code and a lot of prose) into a symbol with
+// start_byte = end_byte = 0. This pass treats those zero-width symbols as
+// candidates and:
+//
+// - drops them all when the tree-sitter pass produced symbols (the fallback is
+//   only meant for files it could not parse) or when the caller asked for
+//   AST-backed symbols only (EnrichmentOptions::regex_ast_only)
+// - keeps a candidate only with structural evidence on its line: a whole-line
+//   /pattern/flags literal, a raw string (r"...", r'...', r#"..."#), or a
+//   regex constructor call (re.compile, new RegExp, Regex::new, ...)
+// - positions kept candidates on their line, marks them metadata.textFallback,
+//   and scores them confidence::TEXT_FALLBACK
+// - keeps at most MAX_TEXT_PATTERNS per file
+//
+// Relationships and identifier ownership pointing at dropped symbols are removed.
+
+use crate::enrichment::base::{set_metadata, EnrichmentContext};
+use crate::enrichment::confidence;
+use julie_extractors::{ExtractionResults, Symbol};
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+const MAX_TEXT_PATTERNS: usize = 50;
+
+/// `/pattern/flags` filling the whole line
+static SLASH_LITERAL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^/(?:[^/\\\n]|\\.)+/[dgimsuvxy]*;?$").unwrap());
+
+/// r"...", r'...', r#"..."#
+static RAW_STRING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r##"(?:^|[^\w])r(?:#*"[^"\n]+"#*|'[^'\n]+')"##).unwrap());
+
+/// A regex constructor or matcher call taking the pattern
+static REGEX_CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:\bre\.(?:compile|match|search|fullmatch|sub|subn|split|findall|finditer)|\bnew\s+RegExp|\bRegExp|\bRegex(?:Builder)?::new|\bPattern\.compile|\bpreg_\w+|\bregexp\.(?:MustCompile|Compile)|\bRegex)\s*\(",
+    )
+    .unwrap()
+});
+
+pub(super) fn filter_text_patterns(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let is_candidate = |s: &Symbol| s.start_byte == 0 && s.end_byte == 0 && !s.name.is_empty();
+    let has_ast_symbols = results.symbols.iter().any(|s| !is_candidate(s));
+    let keep_any = !ctx.options.regex_ast_only && !has_ast_symbols;
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(ctx.content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let mut dropped = HashSet::new();
+    let mut kept = 0;
+    let mut search_from = 0;
+
+    for symbol in results.symbols.iter_mut().filter(|s| is_candidate(s)) {
+        let located = keep_any
+            .then(|| locate(ctx.content, &line_starts, symbol, search_from))
+            .flatten();
+        let Some((start, _)) = located
+            .filter(|&(_, line)| kept < MAX_TEXT_PATTERNS && has_structural_evidence(line.trim()))
+        else {
+            dropped.insert(symbol.id.clone());
+            continue;
+        };
+
+        let end = start + symbol.name.len();
+        let (start_line, start_column) = ctx.position(start);
+        let (end_line, end_column) = ctx.position(end);
+        symbol.start_byte = start as u32;
+        symbol.end_byte = end as u32;
+        symbol.start_line = start_line;
+        symbol.start_column = start_column;
+        symbol.end_line = end_line;
+        symbol.end_column = end_column;
+        set_metadata(symbol, "textFallback", true);
+        symbol.confidence = Some(confidence::TEXT_FALLBACK);
+        search_from = end;
+        kept += 1;
+    }
+
+    if dropped.is_empty() {
+        return;
+    }
+    results.symbols.retain(|s| !dropped.contains(&s.id));
+    results
+        .relationships
+        .retain(|r| !dropped.contains(&r.from_symbol_id) && !dropped.contains(&r.to_symbol_id));
+    for identifier in results.identifiers.iter_mut() {
+        if identifier
+            .containing_symbol_id
+            .as_ref()
+            .is_some_and(|id| dropped.contains(id))
+        {
+            identifier.containing_symbol_id = None;
+        }
+    }
+}
+
+/// Byte offset of the symbol's text and the line holding it: on the reported
+/// line when that matches, otherwise the next occurrence after `search_from`
+fn locate<'c>(
+    content: &'c str,
+    line_starts: &[usize],
+    symbol: &Symbol,
+    search_from: usize,
+) -> Option<(usize, &'c str)> {
+    let line_at = |index: usize| {
+        let start = line_starts[index];
+        let end = line_starts
+            .get(index + 1)
+            .map_or(content.len(), |next| next - 1);
+        (start, &content[start..end])
+    };
+
+    let reported = (symbol.start_line as usize).checked_sub(1);
+    if let Some((line_start, line)) = reported.filter(|&i| i < line_starts.len()).map(line_at) {
+        if let Some(offset) = line.find(symbol.name.as_str()) {
+            return Some((line_start + offset, line));
+        }
+    }
+
+    let start = search_from + content.get(search_from..)?.find(symbol.name.as_str())?;
+    let index = line_starts.partition_point(|&line_start| line_start <= start) - 1;
+    Some((start, line_at(index).1))
+}
+
+fn has_structural_evidence(line: &str) -> bool {
+    SLASH_LITERAL_RE.is_match(line) || RAW_STRING_RE.is_match(line) || REGEX_CALL_RE.is_match(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use crate::enrichment::EnrichmentOptions;
+    use julie_extractors::SymbolKind;
+    use std::path::Path;
+
+    fn fallback_symbol(ctx: &EnrichmentContext, name: &str, line: u32) -> Symbol {
+        let mut symbol =
+            ctx.create_symbol(name, SymbolKind::Variable, 0, 0, SymbolOptions::default());
+        symbol.id = format!("{}:{}", name, line);
+        symbol.start_line = line;
+        symbol
+    }
+
+    #[test]
+    fn test_keeps_only_structural_patterns_with_real_offsets() {
+        let content = "See the [docs] for *details*\n/^\\d{3}-\\d{4}$/i\nEMAIL = re.compile(r\"[\\w.]+@\\w+\")\n";
+        let ctx = EnrichmentContext::new("patterns.regex", "regex", content, Path::new("."));
+        let mut results = test_results(vec![
+            fallback_symbol(&ctx, "See the [docs] for *details*", 1),
+            fallback_symbol(&ctx, "/^\\d{3}-\\d{4}$/i", 2),
+            fallback_symbol(&ctx, "EMAIL = re.compile(r\"[\\w.]+@\\w+\")", 0),
+        ]);
+        filter_text_patterns(&ctx, &mut results);
+
+        assert_eq!(results.symbols.len(), 2);
+        let phone = &results.symbols[0];
+        assert_eq!(phone.start_line, 2);
+        assert_eq!(ctx.symbol_text(phone), "/^\\d{3}-\\d{4}$/i");
+        let email = &results.symbols[1];
+        assert_eq!(email.start_line, 3);
+        assert_eq!(email.start_byte as usize, content.find("EMAIL").unwrap());
+    }
+
+    #[test]
+    fn test_fallback_dropped_when_ast_symbols_exist_or_disabled() {
+        let content = "/abc/\n";
+        let ctx = EnrichmentContext::new("p.regex", "regex", content, Path::new("."));
+        let ast = ctx.create_symbol("abc", SymbolKind::Variable, 1, 4, SymbolOptions::default());
+        let mut with_ast = test_results(vec![ast, fallback_symbol(&ctx, "/abc/", 1)]);
+        filter_text_patterns(&ctx, &mut with_ast);
+        assert_eq!(with_ast.symbols.len(), 1);
+        assert_eq!(with_ast.symbols[0].name, "abc");
+
+        let ctx = ctx.with_options(EnrichmentOptions {
+            regex_ast_only: true,
+            ..Default::default()
+        });
+        let mut disabled = test_results(vec![fallback_symbol(&ctx, "/abc/", 1)]);
+        filter_text_patterns(&ctx, &mut disabled);
+        assert!(disabled.symbols.is_empty());
+    }
+}
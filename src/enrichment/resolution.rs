@@ -0,0 +1,317 @@
+// Identifier resolution - link usages to definitions in the same file
+//
+// Julie records a usage's name and containing symbol, and only rarely its
+// target. For every identifier still without target_symbol_id this pass looks
+// for file-local symbols of that name and ranks them by:
+//
+//   scope  the candidate's parent is the usage's containing symbol, then each
+//          enclosing symbol outwards, then the file; symbols nested in an
+//          unrelated scope are not visible (member accesses excepted, which
+//          may name a member of any type). In Python and Ruby a method body
+//          does not see its class body: a bare name there needs `self.` to
+//          reach a method, so class and other type bodies are skipped as
+//          enclosing scopes of the symbols nested in them
+//   kind   a Call prefers Function/Method/Constructor, then a type (constructor
+//          call), then anything callable; a TypeUsage wants a type; a
+//          MemberAccess a member; a VariableRef a variable-like symbol
+//   order  a candidate that starts before the usage (or encloses it) over one
+//          declared after it
+//
+// A single best candidate becomes target_symbol_id (exposed to Python as
+// Identifier.resolved_symbol_id too). When several tie, nothing is guessed: the
+// containing symbol gets an entry in metadata.ambiguousReferences
+// ({name, line, candidates}); usages outside any symbol are left unresolved.
+// Import identifiers name other files and are never resolved here. Runs after
+// stable IDs are assigned, so the IDs it records are final.
+
+use super::base::append_metadata;
+use julie_extractors::{ExtractionResults, IdentifierKind, Symbol, SymbolKind};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Set target_symbol_id on identifiers with a single best same-file definition
+pub fn resolve_identifiers(results: &mut ExtractionResults) {
+    let symbols = &results.symbols;
+    let by_id: HashMap<&str, usize> = symbols
+        .iter()
+        .enumerate()
+        .map(|(index, s)| (s.id.as_str(), index))
+        .collect();
+    let mut by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        by_name.entry(symbol.name.as_str()).or_default().push(index);
+    }
+
+    let mut ambiguous: Vec<(usize, serde_json::Value)> = Vec::new();
+    for identifier in results
+        .identifiers
+        .iter_mut()
+        .filter(|i| i.target_symbol_id.is_none() && i.kind != IdentifierKind::Import)
+    {
+        let Some(named) = by_name.get(identifier.name.as_str()) else {
+            continue;
+        };
+        // The containing symbol and its ancestors, innermost first
+        let mut scopes = scope_chain(symbols, &by_id, identifier.containing_symbol_id.as_deref());
+        if type_bodies_hidden(&identifier.language) {
+            // The innermost scope stays visible: a usage directly in a class body
+            let mut depth = 0;
+            scopes.retain(|id| {
+                depth += 1;
+                depth == 1 || !by_id.get(id).is_some_and(|&i| is_type(&symbols[i].kind))
+            });
+        }
+        let member = identifier.kind == IdentifierKind::MemberAccess;
+
+        let ranked: Vec<((usize, u8, bool), usize)> = named
+            .iter()
+            .filter_map(|&index| {
+                let symbol = &symbols[index];
+                let kind = kind_rank(&identifier.kind, &symbol.kind)?;
+                let scope = match symbol.parent_id.as_deref() {
+                    Some(parent) => match scopes.iter().position(|s| *s == parent) {
+                        Some(depth) => depth,
+                        None if member => scopes.len() + 1,
+                        None => return None,
+                    },
+                    None => scopes.len(),
+                };
+                let after = symbol.start_byte > identifier.start_byte;
+                Some(((scope, kind, after), index))
+            })
+            .collect();
+        let Some(best) = ranked.iter().map(|(key, _)| *key).min() else {
+            continue;
+        };
+        let winners: Vec<usize> = ranked
+            .iter()
+            .filter(|(key, _)| *key == best)
+            .map(|(_, index)| *index)
+            .collect();
+
+        if let [winner] = winners[..] {
+            identifier.target_symbol_id = Some(symbols[winner].id.clone());
+        } else if let Some(&container) = identifier
+            .containing_symbol_id
+            .as_deref()
+            .and_then(|id| by_id.get(id))
+        {
+            let candidates: Vec<&str> = winners.iter().map(|&i| symbols[i].id.as_str()).collect();
+            ambiguous.push((
+                container,
+                json!({
+                    "name": identifier.name,
+                    "line": identifier.start_line,
+                    "candidates": candidates,
+                }),
+            ));
+        }
+    }
+
+    for (container, entry) in ambiguous {
+        append_metadata(
+            &mut results.symbols[container],
+            "ambiguousReferences",
+            entry,
+        );
+    }
+}
+
+/// IDs of `containing` and its ancestors, innermost first (stopping at cycles)
+fn scope_chain<'a>(
+    symbols: &'a [Symbol],
+    by_id: &HashMap<&str, usize>,
+    containing: Option<&'a str>,
+) -> Vec<&'a str> {
+    let mut chain = Vec::new();
+    let mut current = containing;
+    while let Some(id) = current {
+        if chain.contains(&id) || chain.len() > symbols.len() {
+            break;
+        }
+        chain.push(id);
+        current = by_id
+            .get(id)
+            .and_then(|&index| symbols[index].parent_id.as_deref());
+    }
+    chain
+}
+
+/// Languages whose methods don't see names declared in their class body
+fn type_bodies_hidden(language: &str) -> bool {
+    matches!(language, "python" | "ruby")
+}
+
+fn is_type(kind: &SymbolKind) -> bool {
+    use SymbolKind::*;
+    matches!(
+        kind,
+        Class | Interface | Struct | Enum | Type | Trait | Union | Delegate
+    )
+}
+
+/// How well a symbol kind fits a usage kind (lower is better); None when it can't
+fn kind_rank(usage: &IdentifierKind, kind: &SymbolKind) -> Option<u8> {
+    use SymbolKind::*;
+    let is_type = is_type(kind);
+    match usage {
+        IdentifierKind::Call => match kind {
+            Function | Method | Constructor => Some(0),
+            _ if is_type => Some(1),
+            Variable | Constant | Property | Field | Import => Some(2),
+            _ => None,
+        },
+        IdentifierKind::TypeUsage => match kind {
+            _ if is_type => Some(0),
+            Import => Some(1),
+            _ => None,
+        },
+        IdentifierKind::MemberAccess => match kind {
+            Property | Field | Method | EnumMember | Constant | Event => Some(0),
+            Variable | Function => Some(1),
+            _ => None,
+        },
+        IdentifierKind::VariableRef => match kind {
+            Variable | Constant | Field | Property | EnumMember => Some(0),
+            Function | Method | Import => Some(1),
+            _ if is_type => Some(1),
+            _ => None,
+        },
+        IdentifierKind::Import => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, EnrichmentContext, SymbolOptions};
+    use std::path::Path;
+
+    const SOURCE: &str = "def helper(): pass\n\nclass Service:\n    def helper(self): pass\n    def run(self):\n        helper()\n        self.helper()\n        return Config()\n\nclass Config: pass\nhelper()\nrun()\n";
+
+    /// Symbols for SOURCE, each spanning `anchor` onwards to the end of its line
+    fn symbols(ctx: &EnrichmentContext) -> Vec<Symbol> {
+        let mut symbols: Vec<Symbol> = Vec::new();
+        for (anchor, name, kind, parent) in [
+            ("def helper():", "helper", SymbolKind::Function, None),
+            ("class Service", "Service", SymbolKind::Class, None),
+            ("def helper(self)", "helper", SymbolKind::Method, Some(1)),
+            ("def run", "run", SymbolKind::Method, Some(1)),
+            ("class Config", "Config", SymbolKind::Class, None),
+        ] {
+            let start = SOURCE.find(anchor).unwrap();
+            let end = SOURCE[start..].find('\n').unwrap() + start;
+            symbols.push(ctx.create_symbol(
+                name,
+                kind,
+                start,
+                end,
+                SymbolOptions {
+                    parent_id: parent.map(|p: usize| symbols[p].id.clone()),
+                    ..Default::default()
+                },
+            ));
+        }
+        symbols
+    }
+
+    fn resolved(results: &ExtractionResults, nth: usize) -> Option<&str> {
+        results.identifiers[nth].target_symbol_id.as_deref()
+    }
+
+    #[test]
+    fn test_usages_resolve_to_the_nearest_visible_definition() {
+        let ctx = EnrichmentContext::new("service.py", "python", SOURCE, Path::new("."));
+        let symbols = symbols(&ctx);
+        let run = symbols[3].id.clone();
+        let usage = |name: &str, kind: IdentifierKind, anchor: &str, containing: Option<&str>| {
+            let start = SOURCE.find(anchor).unwrap();
+            ctx.create_identifier(
+                name,
+                kind,
+                start,
+                start + name.len(),
+                containing.map(str::to_string),
+            )
+        };
+        let identifiers = vec![
+            usage(
+                "helper",
+                IdentifierKind::Call,
+                "helper()\n        self",
+                Some(&run),
+            ),
+            usage("Config", IdentifierKind::Call, "Config()", Some(&run)),
+            usage("helper", IdentifierKind::Call, "helper()\nrun", None),
+            // `run` is a method of Service, not visible at module level
+            usage("run", IdentifierKind::Call, "run()\n", None),
+        ];
+        let mut results = ExtractionResults {
+            identifiers,
+            ..test_results(symbols)
+        };
+        resolve_identifiers(&mut results);
+
+        // A bare name in a Python method doesn't see the class body
+        assert_eq!(resolved(&results, 0), Some(results.symbols[0].id.as_str()));
+        // Declared after the usage, but the only class named Config
+        assert_eq!(resolved(&results, 1), Some(results.symbols[4].id.as_str()));
+        assert_eq!(resolved(&results, 2), Some(results.symbols[0].id.as_str()));
+        assert_eq!(resolved(&results, 3), None);
+    }
+
+    #[test]
+    fn test_ties_are_recorded_as_candidates() {
+        let content =
+            "class A:\n    name = 1\nclass B:\n    name = 2\ndef show(x):\n    return x.name\n";
+        let ctx = EnrichmentContext::new("models.py", "python", content, Path::new("."));
+        let at = |anchor: &str| content.find(anchor).unwrap();
+        let a = ctx.create_symbol("A", SymbolKind::Class, 0, 20, SymbolOptions::default());
+        let b = ctx.create_symbol(
+            "B",
+            SymbolKind::Class,
+            at("class B"),
+            40,
+            SymbolOptions::default(),
+        );
+        let field = |parent: &Symbol, anchor: &str| {
+            ctx.create_symbol(
+                "name",
+                SymbolKind::Property,
+                at(anchor),
+                at(anchor) + 8,
+                SymbolOptions {
+                    parent_id: Some(parent.id.clone()),
+                    ..Default::default()
+                },
+            )
+        };
+        let (a_name, b_name) = (field(&a, "name = 1"), field(&b, "name = 2"));
+        let show = ctx.create_symbol(
+            "show",
+            SymbolKind::Function,
+            at("def show"),
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let access = at("x.name") + 2;
+        let mut results = ExtractionResults {
+            identifiers: vec![ctx.create_identifier(
+                "name",
+                IdentifierKind::MemberAccess,
+                access,
+                access + 4,
+                Some(show.id.clone()),
+            )],
+            ..test_results(vec![a, b, a_name.clone(), b_name.clone(), show])
+        };
+        resolve_identifiers(&mut results);
+
+        assert_eq!(resolved(&results, 0), None);
+        let show = results.symbols[4].metadata.as_ref().unwrap();
+        assert_eq!(
+            show["ambiguousReferences"],
+            json!([{ "name": "name", "line": 6, "candidates": [a_name.id, b_name.id] }])
+        );
+    }
+}
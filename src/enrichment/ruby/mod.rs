@@ -0,0 +1,29 @@
+// Ruby enrichment passes
+//
+// Julie's Ruby extractor handles classes, modules, methods, and the calls it
+// knows about (require, attr_accessor, define_method); its call handling lives
+// in Julie's calls::extract_call and can't be extended from Miller. These
+// passes correct method visibility for the call forms of private/protected
+// (`private def x`, `private :x`) and add what a Rails codebase is built
+// from: the model DSL calls (associations, scopes, validations).
+
+mod rails_dsl;
+mod visibility;
+
+use super::base::{EnrichmentContext, Lexicon};
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+/// "..." / '...' strings, `#` line comments, `=begin` / `=end` blocks
+pub(super) const RUBY: Lexicon = Lexicon {
+    quotes: b"\"'",
+    escape: Some(b'\\'),
+    line_comment: Some("#"),
+    block_comment: Some(("=begin", "=end")),
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    visibility::fix_method_visibility(ctx, &mut results.symbols);
+    rails_dsl::enrich_rails_dsl(ctx, results);
+}
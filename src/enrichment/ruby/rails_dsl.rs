@@ -0,0 +1,327 @@
+// Rails model DSL
+//
+// `has_many :orders`, `belongs_to :user`, `scope :active, -> { ... }`, and
+// `validates :email, presence: true` are the structure of a Rails model, but
+// to Julie they are just method calls in a class body. This pass turns them
+// into symbols on the enclosing class:
+//
+// - association macros (has_many, has_one, belongs_to,
+//   has_and_belongs_to_many) become a Property named after the association,
+//   with metadata.associationType, className (the class_name: override, else
+//   the camelized name, singularized for collection associations), and
+//   foreignKey/through/polymorphic when given; the association references its
+//   class, directly when it is declared in the same file and through a pending
+//   relationship otherwise
+// - `scope :name, ->(...) { ... }` becomes a Method (metadata.isScope) whose
+//   span covers the lambda, and identifiers inside the lambda are attributed
+//   to it
+// - validates / validates_*_of append the validated attributes to the class's
+//   metadata.validatedAttributes
+
+mod associations;
+mod scopes;
+
+use super::RUBY;
+use crate::enrichment::base::{
+    append_metadata, code_mask, reattribute_identifiers, split_top_level,
+    symbol_index::SymbolIndex, trim_with_offset, EnrichmentContext,
+};
+use associations::{link_association, parse_association};
+use julie_extractors::{ExtractionResults, Symbol, SymbolKind};
+use regex::Regex;
+use scopes::parse_scope;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// A DSL call at statement start, up to its first argument
+static DSL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*(has_many|has_one|belongs_to|has_and_belongs_to_many|scope|validates|validates_\w+_of)\b[ \t]*\(?[ \t]*",
+    )
+    .unwrap()
+});
+
+/// `:name` argument
+static SYMBOL_ARG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^:(\w+[?!]?)$").unwrap());
+
+/// `key: value` or `:key => value` option
+static OPTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?::(\w+)\s*=>|(\w+):)\s*(.+)$").unwrap());
+
+/// One recognized DSL call, resolved before any symbol is added
+enum DslCall {
+    Association {
+        class_id: String,
+        symbol: Symbol,
+        target: Option<String>,
+    },
+    Scope {
+        symbol: Symbol,
+        body: (usize, usize),
+    },
+    Validation {
+        class_id: String,
+        attributes: Vec<String>,
+    },
+}
+
+pub(super) fn enrich_rails_dsl(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let code = code_mask(ctx.content, RUBY);
+    let calls: Vec<DslCall> = {
+        let classes = SymbolIndex::new(&results.symbols);
+        DSL_RE
+            .captures_iter(ctx.content)
+            .filter_map(|caps| {
+                let keyword = caps.get(1).unwrap();
+                if !code[keyword.start()] {
+                    return None;
+                }
+                let class = classes.innermost_at(keyword.start(), |s| {
+                    matches!(s.kind, SymbolKind::Class | SymbolKind::Module)
+                })?;
+                let args_start = caps.get(0).unwrap().end();
+                match keyword.as_str() {
+                    "scope" => parse_scope(ctx, &code, class, keyword.start(), args_start),
+                    macro_name if macro_name.starts_with("validates") => {
+                        let end = statement_end(ctx.content, &code, args_start);
+                        let (attributes, _) = parse_args(&ctx.content[args_start..end]);
+                        (!attributes.is_empty()).then(|| DslCall::Validation {
+                            class_id: class.id.clone(),
+                            attributes,
+                        })
+                    }
+                    macro_name => parse_association(
+                        ctx,
+                        &code,
+                        class,
+                        macro_name,
+                        keyword.start(),
+                        args_start,
+                    ),
+                }
+            })
+            .collect()
+    };
+
+    let mut scope_bodies = Vec::new();
+    for call in calls {
+        match call {
+            DslCall::Association {
+                class_id,
+                symbol,
+                target,
+            } => {
+                if let Some(target) = target {
+                    link_association(ctx, results, &symbol, &class_id, &target);
+                }
+                results.symbols.push(symbol);
+            }
+            DslCall::Scope { symbol, body } => {
+                scope_bodies.push(body);
+                results.symbols.push(symbol);
+            }
+            DslCall::Validation {
+                class_id,
+                attributes,
+            } => {
+                let class = results
+                    .symbols
+                    .iter_mut()
+                    .find(|s| s.id == class_id)
+                    .unwrap();
+                for attribute in attributes {
+                    let known = class
+                        .metadata
+                        .as_ref()
+                        .and_then(|m| m.get("validatedAttributes"))
+                        .and_then(Value::as_array)
+                        .is_some_and(|items| items.iter().any(|v| v == attribute.as_str()));
+                    if !known {
+                        append_metadata(class, "validatedAttributes", attribute);
+                    }
+                }
+            }
+        }
+    }
+
+    reattribute_identifiers(
+        &mut results.identifiers,
+        &results.symbols,
+        &scope_bodies,
+        |s| {
+            matches!(
+                s.kind,
+                SymbolKind::Method | SymbolKind::Function | SymbolKind::Class | SymbolKind::Module
+            )
+        },
+    );
+}
+
+/// Leading `:symbol` arguments and the `key: value` options after them
+fn parse_args(args: &str) -> (Vec<String>, HashMap<String, Value>) {
+    let args = args.trim_end();
+    let args = args.strip_suffix(')').unwrap_or(args);
+    let mut names = Vec::new();
+    let mut options = HashMap::new();
+    for (offset, segment) in split_top_level(args, b',', RUBY) {
+        let (_, segment) = trim_with_offset(offset, segment);
+        if let Some(caps) = SYMBOL_ARG_RE.captures(segment) {
+            names.push(caps[1].to_string());
+        } else if let Some(caps) = OPTION_RE.captures(segment) {
+            let key = caps.get(1).or(caps.get(2)).unwrap().as_str();
+            options.insert(key.to_string(), option_value(caps[3].trim()));
+        }
+    }
+    (names, options)
+}
+
+/// `"User"` / `:user_id` → string, `true` / `false` → bool, anything else verbatim
+fn option_value(text: &str) -> Value {
+    match text {
+        "true" => Value::from(true),
+        "false" => Value::from(false),
+        _ => Value::from(
+            text.trim_start_matches(':')
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string(),
+        ),
+    }
+}
+
+/// End of the statement starting at `from`, following lines that end in `,` or `\`
+fn statement_end(content: &str, code: &[bool], from: usize) -> usize {
+    let mut line_start = from;
+    loop {
+        let line_end = content[line_start..]
+            .find('\n')
+            .map_or(content.len(), |i| line_start + i);
+        let last = (line_start..line_end)
+            .rev()
+            .find(|&i| code[i] && !content.as_bytes()[i].is_ascii_whitespace())
+            .map(|i| content.as_bytes()[i]);
+        if !matches!(last, Some(b',' | b'\\')) || line_end == content.len() {
+            return content[..line_end].trim_end().len().max(from);
+        }
+        line_start = line_end + 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use julie_extractors::IdentifierKind;
+    use std::path::Path;
+
+    fn class(ctx: &EnrichmentContext, name: &str) -> Symbol {
+        let start = ctx.content.find(&format!("class {}", name)).unwrap();
+        let end = ctx.content[start..].find("\nend").unwrap() + start + 4;
+        ctx.create_symbol(
+            name,
+            SymbolKind::Class,
+            start,
+            end,
+            SymbolOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_associations_become_properties_linked_to_their_class() {
+        let content = "class User < ApplicationRecord\nend\n\nclass Post < ApplicationRecord\n  has_many :line_items, dependent: :destroy\n  has_and_belongs_to_many :categories\n  belongs_to :author, class_name: \"User\",\n             foreign_key: :writer_id\n  belongs_to :commentable, polymorphic: true\n  # has_one :ignored\nend\n";
+        let ctx = EnrichmentContext::new("app/models/post.rb", "ruby", content, Path::new("."));
+        let mut results = test_results(vec![class(&ctx, "User"), class(&ctx, "Post")]);
+        enrich_rails_dsl(&ctx, &mut results);
+
+        let post_id = results.symbols[1].id.clone();
+        let associations: Vec<&Symbol> = results
+            .symbols
+            .iter()
+            .filter(|s| s.kind == SymbolKind::Property)
+            .collect();
+        let names: Vec<&str> = associations.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["line_items", "categories", "author", "commentable"]);
+        assert!(associations
+            .iter()
+            .all(|s| s.parent_id.as_deref() == Some(post_id.as_str())));
+
+        let author = associations[2].metadata.as_ref().unwrap();
+        assert_eq!(author["associationType"], "belongs_to");
+        assert_eq!(author["className"], "User");
+        assert_eq!(author["foreignKey"], "writer_id");
+        assert_eq!(
+            associations[2].signature.as_deref(),
+            Some("belongs_to :author, class_name: \"User\", foreign_key: :writer_id")
+        );
+        assert_eq!(
+            associations[1].metadata.as_ref().unwrap()["className"],
+            "Category"
+        );
+        let commentable = associations[3].metadata.as_ref().unwrap();
+        assert_eq!(commentable["polymorphic"], true);
+        assert!(!commentable.contains_key("className"));
+
+        // User is declared here; LineItem and Category are resolved across files
+        assert_eq!(results.relationships.len(), 1);
+        assert_eq!(results.relationships[0].from_symbol_id, associations[2].id);
+        assert_eq!(results.relationships[0].to_symbol_id, results.symbols[0].id);
+        let pending: Vec<&str> = results
+            .pending_relationships
+            .iter()
+            .map(|p| p.callee_name.as_str())
+            .collect();
+        assert_eq!(pending, ["LineItem", "Category"]);
+    }
+
+    #[test]
+    fn test_scopes_own_their_lambda_and_validations_land_on_the_class() {
+        let content = "class Order < ApplicationRecord\n  validates :email, :total, presence: true\n  validates_uniqueness_of :email\n  scope :recent, ->(since) { where(\"created_at > ?\", since) }\n  scope :open, -> do\n    where(state: :open) if active?\n  end\nend\n";
+        let ctx = EnrichmentContext::new("app/models/order.rb", "ruby", content, Path::new("."));
+        let order = class(&ctx, "Order");
+        let where_at: Vec<usize> = content.match_indices("where").map(|(i, _)| i).collect();
+        let mut results = ExtractionResults {
+            identifiers: where_at
+                .iter()
+                .map(|&at| {
+                    ctx.create_identifier(
+                        "where",
+                        IdentifierKind::Call,
+                        at,
+                        at + 5,
+                        Some(order.id.clone()),
+                    )
+                })
+                .collect(),
+            ..test_results(vec![order])
+        };
+        enrich_rails_dsl(&ctx, &mut results);
+
+        let order = &results.symbols[0];
+        assert_eq!(
+            order.metadata.as_ref().unwrap()["validatedAttributes"],
+            serde_json::json!(["email", "total"])
+        );
+
+        let scopes: Vec<&Symbol> = results.symbols[1..].iter().collect();
+        assert_eq!(scopes.len(), 2);
+        assert_eq!(scopes[0].name, "recent");
+        assert_eq!(scopes[0].kind, SymbolKind::Method);
+        assert_eq!(
+            scopes[0].signature.as_deref(),
+            Some("scope :recent, ->(since)")
+        );
+        assert_eq!(scopes[1].name, "open");
+        assert!(ctx.symbol_text(scopes[1]).ends_with("active?\n  end"));
+        assert_eq!(scopes[1].metadata.as_ref().unwrap()["isScope"], true);
+
+        let owners: Vec<Option<&str>> = results
+            .identifiers
+            .iter()
+            .map(|i| i.containing_symbol_id.as_deref())
+            .collect();
+        assert_eq!(
+            owners,
+            [Some(scopes[0].id.as_str()), Some(scopes[1].id.as_str())]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+// Rails model DSL - association macros
+//
+// has_many / has_one / belongs_to / has_and_belongs_to_many become Property
+// symbols whose className is inferred the way Rails' inflector does it.
+
+use super::{parse_args, statement_end, DslCall};
+use crate::enrichment::base::{collapse_whitespace, EnrichmentContext, SymbolOptions};
+use julie_extractors::{
+    ExtractionResults, PendingRelationship, RelationshipKind, Symbol, SymbolKind, Visibility,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Irregular plurals Rails' inflector knows that suffix rules get wrong
+const IRREGULAR_PLURALS: &[(&str, &str)] = &[
+    ("people", "person"),
+    ("children", "child"),
+    ("men", "man"),
+    ("women", "woman"),
+    ("data", "datum"),
+];
+
+pub(super) fn parse_association(
+    ctx: &EnrichmentContext,
+    code: &[bool],
+    class: &Symbol,
+    macro_name: &str,
+    start: usize,
+    args_start: usize,
+) -> Option<DslCall> {
+    let end = statement_end(ctx.content, code, args_start);
+    let (names, options) = parse_args(&ctx.content[args_start..end]);
+    let name = names.into_iter().next()?;
+
+    let polymorphic = options.get("polymorphic").and_then(Value::as_bool) == Some(true);
+    let class_name = match options.get("class_name").and_then(Value::as_str) {
+        Some(explicit) => Some(explicit.trim_start_matches("::").to_string()),
+        None if polymorphic => None,
+        None if matches!(macro_name, "has_many" | "has_and_belongs_to_many") => {
+            Some(camelize(&singularize(&name)))
+        }
+        None => Some(camelize(&name)),
+    };
+
+    let mut metadata = HashMap::new();
+    metadata.insert("associationType".to_string(), Value::from(macro_name));
+    if let Some(class_name) = &class_name {
+        metadata.insert("className".to_string(), Value::from(class_name.clone()));
+    }
+    for (option, key) in [("foreign_key", "foreignKey"), ("through", "through")] {
+        if let Some(value) = options.get(option) {
+            metadata.insert(key.to_string(), value.clone());
+        }
+    }
+    if polymorphic {
+        metadata.insert("polymorphic".to_string(), Value::from(true));
+    }
+
+    let symbol = ctx.create_symbol(
+        &name,
+        SymbolKind::Property,
+        start,
+        end,
+        SymbolOptions {
+            signature: Some(collapse_whitespace(ctx.text(start, end))),
+            visibility: Some(Visibility::Public),
+            parent_id: Some(class.id.clone()),
+            metadata: Some(metadata),
+            ..Default::default()
+        },
+    );
+    Some(DslCall::Association {
+        class_id: class.id.clone(),
+        symbol,
+        target: class_name.map(|c| c.rsplit("::").next().unwrap_or(&c).to_string()),
+    })
+}
+
+/// Reference the association's class: directly when declared in this file,
+/// otherwise by name for cross-file resolution
+pub(super) fn link_association(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    association: &Symbol,
+    class_id: &str,
+    target: &str,
+) {
+    let anchor = association.start_byte as usize;
+    let local = results
+        .symbols
+        .iter()
+        .find(|s| s.name == target && s.kind == SymbolKind::Class && s.id != class_id);
+    match local {
+        Some(target) => {
+            let relationship = ctx.create_relationship(
+                &association.id,
+                &target.id,
+                RelationshipKind::References,
+                anchor,
+                None,
+            );
+            results.relationships.push(relationship);
+        }
+        None => results.pending_relationships.push(PendingRelationship {
+            from_symbol_id: association.id.clone(),
+            callee_name: target.to_string(),
+            kind: RelationshipKind::References,
+            file_path: ctx.file_path.to_string(),
+            line_number: ctx.line_of(anchor),
+            confidence: 0.8,
+        }),
+    }
+}
+
+/// `line_items` → `LineItems`
+fn camelize(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// `line_items` → `line_item`, `categories` → `category`, `people` → `person`
+fn singularize(name: &str) -> String {
+    let (prefix, word) = match name.rfind('_') {
+        Some(at) => name.split_at(at + 1),
+        None => ("", name),
+    };
+    let singular = if let Some((_, singular)) = IRREGULAR_PLURALS.iter().find(|(p, _)| *p == word) {
+        singular.to_string()
+    } else if let Some(stem) = word.strip_suffix("ies").filter(|s| !s.is_empty()) {
+        format!("{}y", stem)
+    } else if ["sses", "shes", "ches", "xes", "zzes"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        word[..word.len() - 2].to_string()
+    } else if word.ends_with('s') && !word.ends_with("ss") {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    };
+    format!("{}{}", prefix, singular)
+}
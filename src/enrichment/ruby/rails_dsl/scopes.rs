@@ -0,0 +1,103 @@
+// Rails model DSL - `scope :name, ->(...) { ... }`
+//
+// The scope's span runs to the end of its lambda, brace or do/end form, so
+// identifiers in the body can be attributed to it.
+
+use super::{DslCall, SYMBOL_ARG_RE};
+use crate::enrichment::base::{
+    collapse_whitespace, find_matching_delimiter, EnrichmentContext, SymbolOptions,
+};
+use crate::enrichment::ruby::RUBY;
+use julie_extractors::{Symbol, SymbolKind, Visibility};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `->`, `->(args)`, `lambda`, `proc` opening the scope body
+static LAMBDA_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(?:->|lambda\b|proc\b)\s*").unwrap());
+
+/// Keywords that open or close a `do ... end` nesting level
+static BLOCK_KEYWORD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(do|def|class|module|begin|case|if|unless|while|until|for|end)\b").unwrap()
+});
+
+pub(super) fn parse_scope(
+    ctx: &EnrichmentContext,
+    code: &[bool],
+    class: &Symbol,
+    start: usize,
+    args_start: usize,
+) -> Option<DslCall> {
+    let rest = &ctx.content[args_start..];
+    let name_end = rest.find(',')?;
+    let name = SYMBOL_ARG_RE
+        .captures(rest[..name_end].trim())?
+        .get(1)?
+        .as_str();
+
+    let lambda_at = args_start + name_end + 1;
+    let lambda_at =
+        lambda_at + (ctx.content[lambda_at..].len() - ctx.content[lambda_at..].trim_start().len());
+    let lambda = LAMBDA_RE.find(&ctx.content[lambda_at..])?;
+    let mut open = lambda_at + lambda.end();
+    if ctx.content.as_bytes().get(open) == Some(&b'(') {
+        open = find_matching_delimiter(ctx.content, open, RUBY)? + 1;
+        open += ctx.content[open..].len() - ctx.content[open..].trim_start().len();
+    }
+    let close = if ctx.content[open..].starts_with('{') {
+        find_matching_delimiter(ctx.content, open, RUBY)? + 1
+    } else if ctx.content[open..].starts_with("do") {
+        block_end(ctx.content, code, open + 2)?
+    } else {
+        return None;
+    };
+    let end = match ctx.content[close..].trim_start().strip_prefix(')') {
+        Some(after) => ctx.content.len() - after.len(),
+        None => close,
+    };
+
+    let symbol = ctx.create_symbol(
+        name,
+        SymbolKind::Method,
+        start,
+        end,
+        SymbolOptions {
+            signature: Some(collapse_whitespace(ctx.text(start, open))),
+            visibility: Some(Visibility::Public),
+            parent_id: Some(class.id.clone()),
+            metadata: Some(HashMap::from([("isScope".to_string(), Value::from(true))])),
+            ..Default::default()
+        },
+    );
+    Some(DslCall::Scope {
+        symbol,
+        body: (open, close),
+    })
+}
+
+/// Byte after the `end` closing a `do` block whose body starts at `from`
+fn block_end(content: &str, code: &[bool], from: usize) -> Option<usize> {
+    let mut depth = 1usize;
+    for keyword in BLOCK_KEYWORD_RE.find_iter(&content[from..]) {
+        let at = from + keyword.start();
+        let before = content[..at].trim_end_matches([' ', '\t']);
+        if !code[at] || before.ends_with(['.', ':']) {
+            continue;
+        }
+        match keyword.as_str() {
+            "end" => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(from + keyword.end());
+                }
+            }
+            // Modifier forms (`x if y`) open nothing
+            "if" | "unless" | "while" | "until"
+                if !(before.is_empty() || before.ends_with(['\n', '=', '(', ';'])) => {}
+            _ => depth += 1,
+        }
+    }
+    None
+}
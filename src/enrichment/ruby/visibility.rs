@@ -0,0 +1,263 @@
+// Ruby method visibility
+//
+// Julie tracks visibility by flipping the ambient visibility whenever it sees
+// a `private` identifier, which only matches the bare section form. Ruby's
+// call forms are scoped more narrowly:
+//
+//   private                        every later def in the class (section form)
+//   private def helper; end        that one method only
+//   private :foo, :bar             the named methods, wherever they are defined
+//   module_function [:name]        module functions: callable on the module
+//
+// so `private def` made every following method private and `private :foo`
+// did nothing. This pass recomputes the visibility of the methods of every
+// class or module that uses any of these forms: the ambient section applies
+// to instance methods (`def self.x` stays public), then inline modifiers,
+// then the named forms in source order. Module functions are marked with
+// metadata.moduleFunction and stay public, since their singleton copy is the
+// one callers use.
+
+use super::RUBY;
+use crate::enrichment::base::{
+    code_mask, set_metadata, split_top_level, symbol_index::SymbolIndex, EnrichmentContext,
+};
+use julie_extractors::{Symbol, SymbolKind, Visibility};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Section form: the modifier alone on its line
+static SECTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*(private|protected|public|module_function)[ \t]*(?:;[ \t]*)?(?:#.*)?$")
+        .unwrap()
+});
+
+/// Inline form: `private def name`
+static INLINE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*(private|protected|public|module_function|private_class_method|public_class_method)[ \t]*\(?[ \t]*(def)\s")
+        .unwrap()
+});
+
+/// Named form: `private :foo, "bar"`
+static NAMED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?m)^[ \t]*(private|protected|public|module_function|private_class_method|public_class_method)[ \t]*\(?[ \t]*([:"'].*)$"#)
+        .unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Public,
+    Protected,
+    Private,
+    ModuleFunction,
+}
+
+impl Mode {
+    fn from_keyword(keyword: &str) -> Self {
+        match keyword {
+            "private" | "private_class_method" => Mode::Private,
+            "protected" => Mode::Protected,
+            "module_function" => Mode::ModuleFunction,
+            _ => Mode::Public,
+        }
+    }
+}
+
+/// One visibility call, owned by the class or module it appears in
+enum Directive {
+    Section(Mode),
+    /// Byte of the `def` the modifier applies to
+    Inline(Mode, usize),
+    Named(Mode, Vec<String>),
+}
+
+pub(super) fn fix_method_visibility(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, RUBY);
+    let (directives, updates) = {
+        let owners = SymbolIndex::new(symbols);
+        let owner_of = |byte: usize| {
+            owners
+                .innermost_at(byte, |s| {
+                    matches!(s.kind, SymbolKind::Class | SymbolKind::Module)
+                })
+                .map(|s| s.id.clone())
+        };
+
+        // (owner id, byte, directive) in source order
+        let mut directives: Vec<(String, usize, Directive)> = Vec::new();
+        for caps in SECTION_RE.captures_iter(ctx.content) {
+            let keyword = caps.get(1).unwrap();
+            if let (true, Some(owner)) = (code[keyword.start()], owner_of(keyword.start())) {
+                let mode = Mode::from_keyword(keyword.as_str());
+                directives.push((owner, keyword.start(), Directive::Section(mode)));
+            }
+        }
+        for caps in INLINE_RE.captures_iter(ctx.content) {
+            let keyword = caps.get(1).unwrap();
+            if let (true, Some(owner)) = (code[keyword.start()], owner_of(keyword.start())) {
+                let mode = Mode::from_keyword(keyword.as_str());
+                let def_at = caps.get(2).unwrap().start();
+                directives.push((owner, keyword.start(), Directive::Inline(mode, def_at)));
+            }
+        }
+        for caps in NAMED_RE.captures_iter(ctx.content) {
+            let keyword = caps.get(1).unwrap();
+            if let (true, Some(owner)) = (code[keyword.start()], owner_of(keyword.start())) {
+                let mode = Mode::from_keyword(keyword.as_str());
+                let names = method_names(caps.get(2).unwrap().as_str());
+                directives.push((owner, keyword.start(), Directive::Named(mode, names)));
+            }
+        }
+        directives.sort_by_key(|(_, byte, _)| *byte);
+
+        let updates: Vec<(usize, String)> = symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.kind == SymbolKind::Method)
+            .filter_map(|(index, s)| Some((index, owner_of(s.start_byte as usize)?)))
+            .filter(|(_, owner)| directives.iter().any(|(o, ..)| o == owner))
+            .collect();
+        (directives, updates)
+    };
+
+    for (index, owner) in updates {
+        let method = &symbols[index];
+        let start = method.start_byte as usize;
+        let end = method.end_byte as usize;
+        let name = method.name.trim_start_matches("self.");
+        let singleton =
+            method.name.starts_with("self.") || ctx.symbol_text(method).contains("def self.");
+
+        let mut mode = Mode::Public;
+        for (_, byte, directive) in directives.iter().filter(|(o, ..)| *o == owner) {
+            match directive {
+                Directive::Section(section) if *byte < start && !singleton => mode = *section,
+                Directive::Inline(inline, def_at) if start <= *def_at && *def_at < end => {
+                    mode = *inline
+                }
+                Directive::Named(named, names) if names.iter().any(|n| n == name) => mode = *named,
+                _ => {}
+            }
+        }
+
+        let method = &mut symbols[index];
+        method.visibility = Some(match mode {
+            Mode::Public | Mode::ModuleFunction => Visibility::Public,
+            Mode::Protected => Visibility::Protected,
+            Mode::Private => Visibility::Private,
+        });
+        if mode == Mode::ModuleFunction {
+            set_metadata(method, "moduleFunction", true);
+        }
+    }
+}
+
+/// `:foo, "bar", :baz?)` → foo, bar, baz?
+fn method_names(args: &str) -> Vec<String> {
+    let code = code_mask(args, RUBY);
+    let end = (0..args.len())
+        .find(|&i| !code[i] && args.as_bytes()[i] == b'#')
+        .unwrap_or(args.len());
+    split_top_level(&args[..end], b',', RUBY)
+        .into_iter()
+        .map(|(_, arg)| {
+            arg.trim()
+                .trim_end_matches(')')
+                .trim_start_matches(':')
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string()
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    /// A container symbol plus a Method for every `def` in `content`
+    fn symbols(ctx: &EnrichmentContext, kind: SymbolKind) -> Vec<Symbol> {
+        let container_end = ctx.content.rfind("end").unwrap() + 3;
+        let mut symbols = vec![ctx.create_symbol(
+            "Container",
+            kind,
+            0,
+            container_end,
+            SymbolOptions::default(),
+        )];
+        for (at, _) in ctx.content.match_indices("def ") {
+            let name_start = at + 4;
+            let name_end = ctx.content[name_start..].find([';', '\n', '(']).unwrap() + name_start;
+            let end = ctx.content[at..].find("end").unwrap() + at + 3;
+            symbols.push(ctx.create_symbol(
+                &ctx.content[name_start..name_end],
+                SymbolKind::Method,
+                at,
+                end,
+                SymbolOptions {
+                    // What Julie reports after `private def`: private from then on
+                    visibility: Some(Visibility::Private),
+                    ..Default::default()
+                },
+            ));
+        }
+        symbols
+    }
+
+    fn visibilities(content: &str, kind: SymbolKind) -> Vec<(String, Visibility, bool)> {
+        let ctx = EnrichmentContext::new("lib/service.rb", "ruby", content, Path::new("."));
+        let mut symbols = symbols(&ctx, kind);
+        fix_method_visibility(&ctx, &mut symbols);
+        symbols[1..]
+            .iter()
+            .map(|s| {
+                let module_function = s
+                    .metadata
+                    .as_ref()
+                    .is_some_and(|m| m.contains_key("moduleFunction"));
+                (
+                    s.name.clone(),
+                    s.visibility.clone().unwrap(),
+                    module_function,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_inline_and_named_modifiers_only_touch_their_methods() {
+        let content = "class Service\n  private def helper; end\n  def call; end\n  def foo; end\n  def bar; end\n  def self.build; end\n  private :foo, :bar # after the fact\n  protected\n  def compare; end\n  public def open; end\nend\n";
+        let result = visibilities(content, SymbolKind::Class);
+
+        let expected = [
+            ("helper", Visibility::Private),
+            ("call", Visibility::Public),
+            ("foo", Visibility::Private),
+            ("bar", Visibility::Private),
+            ("self.build", Visibility::Public),
+            ("compare", Visibility::Protected),
+            ("open", Visibility::Public),
+        ];
+        let actual: Vec<(&str, Visibility)> = result
+            .iter()
+            .map(|(name, visibility, _)| (name.as_str(), visibility.clone()))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_module_function_section_and_named_forms() {
+        let content = "module Util\n  def shared; end\n  module_function :shared\n  module_function\n  def slugify(text); end\n  private\n  def internal; end\nend\n";
+        let result = visibilities(content, SymbolKind::Module);
+
+        assert_eq!(
+            result,
+            [
+                ("shared".to_string(), Visibility::Public, true),
+                ("slugify".to_string(), Visibility::Public, true),
+                ("internal".to_string(), Visibility::Private, false),
+            ]
+        );
+    }
+}
@@ -0,0 +1,312 @@
+// Rust declaration macros
+//
+// Macros like lazy_static!, thread_local!, bitflags!, and cfg_if! wrap ordinary
+// item declarations in a token tree, so tree-sitter never sees them as items and
+// `CONFIG` in `lazy_static! { static ref CONFIG: Config = ...; }` has no symbol.
+// This pass scans the bodies of a configurable set of such macros for item
+// declarations and emits real symbols for them, tagged with the macro name
+// (and the cfg predicate for cfg_if! branches).
+
+use super::RUST;
+use crate::enrichment::base::{
+    collapse_whitespace, find_matching_delimiter, innermost_symbol_at, top_level_mask,
+    EnrichmentContext, SymbolOptions,
+};
+use julie_extractors::{ExtractionResults, SymbolKind, Visibility};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// An item declaration keyword with optional visibility
+static ITEM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:(pub(?:\s*\([^)]*\))?)\s+)?(fn|struct|enum|union|static\s+ref|static|const|type|mod|trait)\s+([A-Za-z_]\w*)",
+    )
+    .unwrap()
+});
+
+/// A `#[cfg(...)]` predicate opening a cfg_if! branch
+static CFG_BRANCH_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"#\s*\[\s*cfg\s*\(").unwrap());
+
+/// Which macro invocations are scanned for item declarations
+#[derive(Debug, Clone)]
+pub struct MacroConfig {
+    /// Macro names whose bodies contain item declarations
+    pub declaration_macros: Vec<String>,
+}
+
+impl Default for MacroConfig {
+    fn default() -> Self {
+        Self {
+            declaration_macros: ["lazy_static", "thread_local", "bitflags", "cfg_if"]
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl MacroConfig {
+    pub fn is_declaration_macro(&self, name: &str) -> bool {
+        self.declaration_macros.iter().any(|m| m == name)
+    }
+}
+
+/// Emit symbols for the items declared in a declaration macro's body
+/// (`body_start..body_end` excludes the delimiters)
+pub(super) fn expand(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    macro_name: &str,
+    body_start: usize,
+    body_end: usize,
+) {
+    if macro_name == "cfg_if" {
+        for (cfg, start, end) in cfg_if_branches(ctx.content, body_start, body_end) {
+            scan_items(ctx, results, macro_name, Some(cfg), start, end);
+        }
+    } else {
+        scan_items(ctx, results, macro_name, None, body_start, body_end);
+    }
+}
+
+/// Branches of `if #[cfg(a)] { .. } else if #[cfg(b)] { .. } else { .. }` as
+/// (predicate, body start, body end). The else branch gets `not(any(a, b))`.
+fn cfg_if_branches(content: &str, start: usize, end: usize) -> Vec<(String, usize, usize)> {
+    let mut branches = Vec::new();
+    let mut previous: Vec<String> = Vec::new();
+    let mut pos = start;
+
+    while pos < end {
+        let rest = &content[pos..end];
+        let Some(brace) = rest.find('{').map(|i| pos + i) else {
+            break;
+        };
+        let header = &content[pos..brace];
+        let predicate = match CFG_BRANCH_RE.find(header) {
+            Some(cfg) => {
+                let open = pos + cfg.end() - 1;
+                let Some(close) = find_matching_delimiter(content, open, RUST) else {
+                    break;
+                };
+                content[open + 1..close].trim().to_string()
+            }
+            None if header.trim() == "else" || header.trim_end().ends_with("} else") => {
+                format!("not(any({}))", previous.join(", "))
+            }
+            None => break,
+        };
+        let Some(close) = find_matching_delimiter(content, brace, RUST) else {
+            break;
+        };
+        branches.push((predicate.clone(), brace + 1, close));
+        previous.push(predicate);
+        pos = close + 1;
+    }
+    branches
+}
+
+fn scan_items(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    macro_name: &str,
+    cfg: Option<String>,
+    start: usize,
+    end: usize,
+) {
+    let body = &ctx.content[start..end];
+    let mask = top_level_mask(body, RUST);
+    let parent_id = innermost_symbol_at(&results.symbols, start, |s| {
+        matches!(
+            s.kind,
+            SymbolKind::Function | SymbolKind::Method | SymbolKind::Module
+        )
+    })
+    .map(|s| s.id.clone());
+
+    let mut skip_until = 0;
+    for captures in ITEM_RE.captures_iter(body) {
+        let item = captures.get(0).unwrap();
+        if item.start() < skip_until || !mask.get(item.start()).copied().unwrap_or(false) {
+            continue;
+        }
+        let keyword: String = captures[2].split_whitespace().collect::<Vec<_>>().join(" ");
+        let name = &captures[3];
+        let (signature_end, item_end, block) = item_extent(body, item.end());
+        skip_until = item_end;
+
+        let mut metadata = HashMap::new();
+        metadata.insert("macro".to_string(), Value::from(macro_name));
+        if let Some(cfg) = &cfg {
+            metadata.insert("cfg".to_string(), Value::from(cfg.clone()));
+        }
+        let visibility = if captures.get(1).is_some() {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        };
+
+        let symbol = ctx.create_symbol(
+            name,
+            item_kind(&keyword),
+            start + item.start(),
+            start + item_end,
+            SymbolOptions {
+                signature: Some(collapse_whitespace(&body[item.start()..signature_end])),
+                visibility: Some(visibility),
+                parent_id: parent_id.clone(),
+                metadata: Some(metadata.clone()),
+                ..Default::default()
+            },
+        );
+
+        // bitflags-style `struct Flags: u32 { const A = 1; }` declares its members inline
+        if keyword == "struct" {
+            if let Some((block_start, block_end)) = block {
+                let block_body = &body[block_start + 1..block_end];
+                let block_mask = top_level_mask(block_body, RUST);
+                for member in ITEM_RE.captures_iter(block_body) {
+                    let whole = member.get(0).unwrap();
+                    if &member[2] != "const"
+                        || !block_mask.get(whole.start()).copied().unwrap_or(false)
+                    {
+                        continue;
+                    }
+                    let (member_signature_end, member_end, _) =
+                        item_extent(block_body, whole.end());
+                    let offset = start + block_start + 1;
+                    results.symbols.push(ctx.create_symbol(
+                        &member[3],
+                        SymbolKind::Constant,
+                        offset + whole.start(),
+                        offset + member_end,
+                        SymbolOptions {
+                            signature: Some(collapse_whitespace(
+                                &block_body[whole.start()..member_signature_end],
+                            )),
+                            visibility: Some(Visibility::Public),
+                            parent_id: Some(symbol.id.clone()),
+                            metadata: Some(metadata.clone()),
+                            ..Default::default()
+                        },
+                    ));
+                }
+            }
+        }
+
+        results.symbols.push(symbol);
+    }
+}
+
+/// From the end of an item's name, find (signature end, item end, block range):
+/// the signature stops at the first top-level `=`, `;`, or `{`.
+fn item_extent(body: &str, from: usize) -> (usize, usize, Option<(usize, usize)>) {
+    let mask = top_level_mask(&body[from..], RUST);
+    let bytes = body.as_bytes();
+    let mut signature_end = None;
+
+    for (offset, &is_code) in mask.iter().enumerate() {
+        let index = from + offset;
+        if !is_code {
+            continue;
+        }
+        match bytes[index] {
+            b'=' if signature_end.is_none() && bytes.get(index + 1) != Some(&b'>') => {
+                signature_end = Some(index)
+            }
+            b';' => return (signature_end.unwrap_or(index), index + 1, None),
+            b'{' => {
+                let close = find_matching_delimiter(body, index, RUST).unwrap_or(body.len() - 1);
+                if signature_end.is_none() {
+                    return (index, close + 1, Some((index, close)));
+                }
+            }
+            _ => {}
+        }
+    }
+    (signature_end.unwrap_or(body.len()), body.len(), None)
+}
+
+fn item_kind(keyword: &str) -> SymbolKind {
+    match keyword {
+        "fn" => SymbolKind::Function,
+        "struct" => SymbolKind::Struct,
+        "enum" => SymbolKind::Enum,
+        "union" => SymbolKind::Union,
+        "const" => SymbolKind::Constant,
+        "type" => SymbolKind::Type,
+        "mod" => SymbolKind::Module,
+        "trait" => SymbolKind::Trait,
+        _ => SymbolKind::Variable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    fn expand_body(content: &str, macro_name: &str) -> Vec<julie_extractors::Symbol> {
+        let ctx = EnrichmentContext::new("src/lib.rs", "rust", content, Path::new("."));
+        let open = content.find('{').unwrap();
+        let close = find_matching_delimiter(content, open, RUST).unwrap();
+        let mut results = test_results(Vec::new());
+        expand(&ctx, &mut results, macro_name, open + 1, close);
+        results.symbols
+    }
+
+    #[test]
+    fn test_lazy_static_declares_statics() {
+        let content = "lazy_static! {\n    pub static ref CONFIG: Config = Config::load();\n    static ref CACHE: Mutex<HashMap<String, u32>> = Mutex::new(HashMap::new());\n}";
+        let symbols = expand_body(content, "lazy_static");
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[0].name, "CONFIG");
+        assert_eq!(symbols[0].kind, SymbolKind::Variable);
+        assert_eq!(
+            symbols[0].signature.as_deref(),
+            Some("pub static ref CONFIG: Config")
+        );
+        assert_eq!(symbols[0].start_line, 2);
+        assert_eq!(
+            symbols[1].metadata.as_ref().unwrap()["macro"],
+            Value::from("lazy_static")
+        );
+    }
+
+    #[test]
+    fn test_bitflags_struct_with_members() {
+        let content = "bitflags! {\n    #[derive(Debug)]\n    pub struct Perms: u32 {\n        const READ = 0b001;\n        const WRITE = 0b010;\n    }\n}";
+        let symbols = expand_body(content, "bitflags");
+
+        let perms = symbols.iter().find(|s| s.name == "Perms").unwrap();
+        assert_eq!(perms.kind, SymbolKind::Struct);
+        assert_eq!(perms.signature.as_deref(), Some("pub struct Perms: u32"));
+        let write = symbols.iter().find(|s| s.name == "WRITE").unwrap();
+        assert_eq!(write.kind, SymbolKind::Constant);
+        assert_eq!(write.parent_id.as_deref(), Some(perms.id.as_str()));
+        assert_eq!(symbols.len(), 3);
+    }
+
+    #[test]
+    fn test_cfg_if_branches_carry_predicates() {
+        let content = "cfg_if::cfg_if! {\n    if #[cfg(unix)] {\n        fn imp() -> u32 { 1 }\n    } else if #[cfg(windows)] {\n        fn imp() -> u32 { 2 }\n    } else {\n        fn imp() -> u32 { 0 }\n    }\n}";
+        let symbols = expand_body(content, "cfg_if");
+
+        let cfgs: Vec<&Value> = symbols
+            .iter()
+            .map(|s| &s.metadata.as_ref().unwrap()["cfg"])
+            .collect();
+        assert_eq!(
+            cfgs,
+            vec![
+                &Value::from("unix"),
+                &Value::from("windows"),
+                &Value::from("not(any(unix, windows))")
+            ]
+        );
+        assert_eq!(symbols[0].signature.as_deref(), Some("fn imp() -> u32"));
+    }
+}
@@ -0,0 +1,308 @@
+// Rust trait implementations
+//
+// For every `impl Trait for Type` block this pass emits an Implements
+// relationship from the type symbol to the trait symbol when both are declared
+// in the file. Impls that can't be resolved locally still leave a record:
+// the type gets the trait path in "implementedTraits", and generic or blanket
+// impls (`impl<T: Display> Summary for T`) are listed on the trait under
+// "implementors". Methods inside the block are tagged with "traitImpl".
+
+use super::{base_name, find_closing_angle, RUST};
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, set_metadata, CodeBytes, EnrichmentContext,
+};
+use julie_extractors::{ExtractionResults, RelationshipKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static IMPL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bimpl\b").unwrap());
+
+static FOR_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\sfor\s").unwrap());
+
+static WHERE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\swhere\b").unwrap());
+
+/// A parsed `impl<...> Trait for Type { ... }` header
+#[derive(Debug, PartialEq)]
+struct TraitImpl {
+    /// Names of the impl's generic type parameters
+    generics: Vec<String>,
+    trait_path: String,
+    self_type: String,
+    negative: bool,
+    /// Byte range of the impl body, braces included
+    body: (usize, usize),
+}
+
+impl TraitImpl {
+    /// Whether the self type is (a reference to) one of the impl's own type parameters
+    fn is_blanket(&self) -> bool {
+        self.generics
+            .iter()
+            .any(|g| g == base_name(&self.self_type))
+    }
+
+    fn is_generic(&self) -> bool {
+        !self.generics.is_empty()
+    }
+}
+
+pub(super) fn link_trait_impls(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    for trait_impl in find_trait_impls(ctx.content) {
+        let trait_name = base_name(&trait_impl.trait_path).to_string();
+        let type_name = base_name(&trait_impl.self_type).to_string();
+
+        let trait_id = find_symbol(&results.symbols, &trait_name, |k| {
+            *k == SymbolKind::Trait || *k == SymbolKind::Interface
+        });
+        let type_id = if trait_impl.is_blanket() {
+            None
+        } else {
+            find_symbol(&results.symbols, &type_name, |k| {
+                matches!(
+                    k,
+                    SymbolKind::Struct
+                        | SymbolKind::Enum
+                        | SymbolKind::Union
+                        | SymbolKind::Type
+                        | SymbolKind::Class
+                )
+            })
+        };
+
+        let (body_start, body_end) = trait_impl.body;
+        for symbol in results.symbols.iter_mut().filter(|s| {
+            matches!(s.kind, SymbolKind::Method | SymbolKind::Function)
+                && body_start < s.start_byte as usize
+                && (s.end_byte as usize) <= body_end
+        }) {
+            set_metadata(symbol, "traitImpl", trait_impl.trait_path.clone());
+        }
+
+        if let Some(type_symbol) = type_id
+            .as_ref()
+            .and_then(|id| results.symbols.iter_mut().find(|s| &s.id == id))
+        {
+            append_metadata(
+                type_symbol,
+                "implementedTraits",
+                trait_impl.trait_path.clone(),
+            );
+        }
+
+        match (&type_id, &trait_id) {
+            (Some(type_id), Some(trait_id)) if !trait_impl.negative => {
+                let mut metadata = HashMap::new();
+                metadata.insert(
+                    "traitPath".to_string(),
+                    Value::from(trait_impl.trait_path.clone()),
+                );
+                metadata.insert(
+                    "implType".to_string(),
+                    Value::from(trait_impl.self_type.clone()),
+                );
+                metadata.insert("generic".to_string(), Value::Bool(trait_impl.is_generic()));
+                results.relationships.push(ctx.create_relationship(
+                    type_id,
+                    trait_id,
+                    RelationshipKind::Implements,
+                    body_start,
+                    Some(metadata),
+                ));
+            }
+            (None, Some(trait_id)) => {
+                if let Some(trait_symbol) = results.symbols.iter_mut().find(|s| &s.id == trait_id) {
+                    append_metadata(trait_symbol, "implementors", trait_impl.self_type.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn find_symbol(
+    symbols: &[Symbol],
+    name: &str,
+    accept: impl Fn(&SymbolKind) -> bool,
+) -> Option<String> {
+    symbols
+        .iter()
+        .find(|s| s.name == name && accept(&s.kind))
+        .map(|s| s.id.clone())
+}
+
+fn find_trait_impls(content: &str) -> Vec<TraitImpl> {
+    let code = code_mask(content, RUST);
+    IMPL_RE
+        .find_iter(content)
+        .filter(|m| code.get(m.start()).copied().unwrap_or(false))
+        .filter_map(|m| parse_impl(content, m.end()))
+        .collect()
+}
+
+/// Parse an impl header starting right after the `impl` keyword
+fn parse_impl(content: &str, after_keyword: usize) -> Option<TraitImpl> {
+    let mut pos = after_keyword
+        + (content[after_keyword..].len() - content[after_keyword..].trim_start().len());
+
+    let mut generics = Vec::new();
+    if content[pos..].starts_with('<') {
+        let close = find_closing_angle(content, pos)?;
+        generics = generic_type_params(&content[pos + 1..close]);
+        pos = close + 1;
+    }
+
+    // The body brace, unless a top-level `;` ends the item first
+    let mut depth = 0usize;
+    let brace = CodeBytes::new(content, pos, RUST)
+        .find(|&(_, byte)| {
+            match byte {
+                b'(' | b'[' => depth += 1,
+                b')' | b']' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            depth == 0 && (byte == b'{' || byte == b';')
+        })
+        .filter(|&(_, byte)| byte == b'{')?
+        .0;
+    let header = &content[pos..brace];
+    let header = WHERE_RE
+        .find(header)
+        .map_or(header, |m| &header[..m.start()]);
+
+    let for_keyword = FOR_RE
+        .find_iter(header)
+        .find(|m| angle_depth(&header[..m.start()]) == 0)?;
+    let trait_part = header[..for_keyword.start()].trim();
+    let (negative, trait_path) = match trait_part.strip_prefix('!') {
+        Some(rest) => (true, rest.trim()),
+        None => (false, trait_part),
+    };
+    let self_type = header[for_keyword.end()..].trim();
+    if trait_path.is_empty() || self_type.is_empty() {
+        return None;
+    }
+
+    let body_end = find_matching_delimiter(content, brace, RUST).unwrap_or(content.len());
+    Some(TraitImpl {
+        generics,
+        trait_path: trait_path.to_string(),
+        self_type: self_type.to_string(),
+        negative,
+        body: (brace, body_end),
+    })
+}
+
+/// Type parameter names from `T: Display, 'a, const N: usize`
+fn generic_type_params(params: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, byte) in params.bytes().enumerate().chain([(params.len(), b',')]) {
+        match byte {
+            b'<' | b'(' | b'[' => depth += 1,
+            b'>' | b')' | b']' => depth = depth.saturating_sub(1),
+            b',' if depth == 0 => {
+                let param = params[start..index.min(params.len())].trim();
+                start = index + 1;
+                if param.starts_with('\'') || param.starts_with("const ") {
+                    continue;
+                }
+                let name = param.split([':', '=']).next().unwrap_or("").trim();
+                if !name.is_empty() {
+                    names.push(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+fn angle_depth(text: &str) -> isize {
+    text.bytes().fold(0, |depth, byte| match byte {
+        b'<' => depth + 1,
+        b'>' => depth - 1,
+        _ => depth,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    fn symbol(ctx: &EnrichmentContext, name: &str, kind: SymbolKind, snippet: &str) -> Symbol {
+        let start = ctx.content.find(snippet).unwrap();
+        ctx.create_symbol(
+            name,
+            kind,
+            start,
+            start + snippet.len(),
+            SymbolOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_parse_impl_headers() {
+        let content =
+            "impl<'a, T: Clone, const N: usize> From<[T; N]> for Buffer<'a, T> where T: Send { }";
+        let parsed = parse_impl(content, 4).unwrap();
+        assert_eq!(parsed.generics, vec!["T".to_string()]);
+        assert_eq!(parsed.trait_path, "From<[T; N]>");
+        assert_eq!(parsed.self_type, "Buffer<'a, T>");
+
+        assert!(parse_impl("impl User { fn new() {} }", 4).is_none());
+        assert!(parse_impl("impl !Send for Handle {}", 4).unwrap().negative);
+    }
+
+    #[test]
+    fn test_trait_impl_in_same_file_emits_implements() {
+        let content = "struct User;\ntrait Greet { fn hi(&self); }\nimpl Greet for User {\n    fn hi(&self) {}\n}\nimpl std::fmt::Display for User {}\n";
+        let ctx = EnrichmentContext::new("src/user.rs", "rust", content, Path::new("."));
+        let user = symbol(&ctx, "User", SymbolKind::Struct, "struct User;");
+        let greet = symbol(
+            &ctx,
+            "Greet",
+            SymbolKind::Trait,
+            "trait Greet { fn hi(&self); }",
+        );
+        let hi = symbol(&ctx, "hi", SymbolKind::Method, "fn hi(&self) {}");
+        let mut results = test_results(vec![user, greet, hi]);
+        link_trait_impls(&ctx, &mut results);
+
+        assert_eq!(results.relationships.len(), 1);
+        let relationship = &results.relationships[0];
+        assert_eq!(relationship.kind, RelationshipKind::Implements);
+        assert_eq!(relationship.from_symbol_id, results.symbols[0].id);
+        assert_eq!(relationship.to_symbol_id, results.symbols[1].id);
+        assert_eq!(relationship.line_number, 3);
+
+        let user_metadata = results.symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(
+            user_metadata["implementedTraits"],
+            Value::from(vec!["Greet", "std::fmt::Display"])
+        );
+        assert_eq!(
+            results.symbols[2].metadata.as_ref().unwrap()["traitImpl"],
+            Value::from("Greet")
+        );
+    }
+
+    #[test]
+    fn test_blanket_and_generic_impls_are_metadata_only() {
+        let content = "pub trait Summary {}\nimpl<T: std::fmt::Display> Summary for T {}\nimpl<T> Summary for Vec<T> {}\n";
+        let ctx = EnrichmentContext::new("src/summary.rs", "rust", content, Path::new("."));
+        let summary = symbol(&ctx, "Summary", SymbolKind::Trait, "pub trait Summary {}");
+        let mut results = test_results(vec![summary]);
+        link_trait_impls(&ctx, &mut results);
+
+        assert!(results.relationships.is_empty());
+        assert_eq!(
+            results.symbols[0].metadata.as_ref().unwrap()["implementors"],
+            Value::from(vec!["T", "Vec<T>"])
+        );
+    }
+}
@@ -0,0 +1,255 @@
+// Rust macro definitions and invocations
+//
+// macro_rules! symbols get a signature that lists their arms' matchers, e.g.
+// "macro_rules! hashmap { () | ($($k:expr => $v:expr),* $(,)?) }", with the
+// full matcher list in metadata. Invocations of configured declaration macros
+// are expanded into item symbols (see declaration_macros); every other
+// invocation is scanned for calls in its arguments, which become Call
+// identifiers so `assert_eq!(parse(input), ..)` still references `parse`.
+
+use super::declaration_macros::{self, MacroConfig};
+use super::RUST;
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, innermost_symbol_at, set_metadata, EnrichmentContext,
+    SymbolOptions,
+};
+use julie_extractors::{ExtractionResults, IdentifierKind, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static MACRO_RULES_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bmacro_rules!\s*([A-Za-z_]\w*)\s*([({\[])").unwrap());
+
+/// `path::name!(` / `name![` / `name! {`
+static INVOCATION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b((?:[A-Za-z_]\w*::)*([A-Za-z_]\w*))\s*!\s*([({\[])").unwrap());
+
+/// A call inside macro arguments: `name(` or `path::name(`, optionally turbofished
+static CALL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:[A-Za-z_]\w*::)*([A-Za-z_]\w*)\s*(?:::\s*<[^<>()]*>\s*)?\(").unwrap()
+});
+
+/// Keywords that look like calls when followed by a parenthesis
+const NON_CALL_KEYWORDS: &[&str] = &[
+    "if", "while", "match", "for", "return", "in", "as", "let", "fn", "move", "loop",
+];
+
+pub(super) fn enrich_macros(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    config: &MacroConfig,
+) {
+    let code = code_mask(ctx.content, RUST);
+    let is_code = |byte: usize| code.get(byte).copied().unwrap_or(false);
+
+    let mut definitions = Vec::new();
+    for captures in MACRO_RULES_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        if !is_code(whole.start()) {
+            continue;
+        }
+        let open = whole.end() - 1;
+        let Some(close) = find_matching_delimiter(ctx.content, open, RUST) else {
+            continue;
+        };
+        definitions.push((whole.start(), close + 1));
+        describe_macro_rules(ctx, results, &captures[1], whole.start(), open, close);
+    }
+
+    let mut processed_until = 0;
+    for captures in INVOCATION_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        let name = &captures[2];
+        if whole.start() < processed_until
+            || name == "macro_rules"
+            || !is_code(whole.start())
+            || definitions
+                .iter()
+                .any(|&(start, end)| start <= whole.start() && whole.start() < end)
+        {
+            continue;
+        }
+        let open = whole.end() - 1;
+        let Some(close) = find_matching_delimiter(ctx.content, open, RUST) else {
+            continue;
+        };
+        processed_until = close;
+
+        if config.is_declaration_macro(name) {
+            declaration_macros::expand(ctx, results, name, open + 1, close);
+        } else {
+            emit_argument_calls(ctx, results, &code, open + 1, close);
+        }
+    }
+}
+
+/// Summarize a macro_rules! definition's arms into its symbol
+fn describe_macro_rules(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    name: &str,
+    start: usize,
+    open: usize,
+    close: usize,
+) {
+    let arms = macro_arms(ctx.content, open + 1, close);
+    let signature = format!("macro_rules! {} {{ {} }}", name, arms.join(" | "));
+
+    let existing = results.symbols.iter_mut().find(|s| {
+        s.name == name && start <= s.start_byte as usize && (s.start_byte as usize) < close
+    });
+    if let Some(symbol) = existing {
+        symbol.signature = Some(signature);
+        set_metadata(symbol, "arms", arms);
+        return;
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert("macroRules".to_string(), Value::Bool(true));
+    metadata.insert("arms".to_string(), Value::from(arms));
+    let parent_id = innermost_symbol_at(&results.symbols, start, |s| {
+        matches!(s.kind, SymbolKind::Function | SymbolKind::Module)
+    })
+    .map(|s| s.id.clone());
+    results.symbols.push(ctx.create_symbol(
+        name,
+        SymbolKind::Function,
+        start,
+        close + 1,
+        SymbolOptions {
+            signature: Some(signature),
+            parent_id,
+            metadata: Some(metadata),
+            ..Default::default()
+        },
+    ));
+}
+
+/// Matchers of `(matcher) => { transcriber };` arms, whitespace-normalized
+fn macro_arms(content: &str, start: usize, end: usize) -> Vec<String> {
+    let bytes = content.as_bytes();
+    let mut arms = Vec::new();
+    let mut pos = start;
+
+    while pos < end {
+        while pos < end && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b';') {
+            pos += 1;
+        }
+        if pos >= end || !matches!(bytes[pos], b'(' | b'[' | b'{') {
+            break;
+        }
+        let Some(matcher_close) = find_matching_delimiter(content, pos, RUST) else {
+            break;
+        };
+        arms.push(
+            content[pos..=matcher_close]
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+
+        let after = &content[matcher_close + 1..end];
+        let Some(arrow) = after.find("=>") else {
+            break;
+        };
+        let transcriber = matcher_close + 1 + arrow + 2;
+        let transcriber = transcriber
+            + (content[transcriber..end].len() - content[transcriber..end].trim_start().len());
+        let Some(transcriber_close) = find_matching_delimiter(content, transcriber, RUST) else {
+            break;
+        };
+        pos = transcriber_close + 1;
+    }
+    arms
+}
+
+/// Emit a Call identifier for each call expression inside macro arguments
+fn emit_argument_calls(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    code: &[bool],
+    start: usize,
+    end: usize,
+) {
+    let containing_id = innermost_symbol_at(&results.symbols, start, |s| {
+        matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+    })
+    .map(|s| s.id.clone());
+
+    for captures in CALL_RE.captures_iter(&ctx.content[start..end]) {
+        let name = captures.get(1).unwrap();
+        let name_start = start + name.start();
+        if !code.get(name_start).copied().unwrap_or(false)
+            || NON_CALL_KEYWORDS.contains(&name.as_str())
+        {
+            continue;
+        }
+        results.identifiers.push(ctx.create_identifier(
+            name.as_str(),
+            IdentifierKind::Call,
+            name_start,
+            start + name.end(),
+            containing_id.clone(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    fn enrich(content: &str) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("src/lib.rs", "rust", content, Path::new("."));
+        let mut results = test_results(Vec::new());
+        enrich_macros(&ctx, &mut results, &MacroConfig::default());
+        results
+    }
+
+    #[test]
+    fn test_macro_rules_signature_lists_arms() {
+        let results = enrich(
+            "macro_rules! hashmap {\n    () => { HashMap::new() };\n    ($($k:expr => $v:expr),* $(,)?) => {{\n        let mut m = HashMap::new();\n        $(m.insert($k, $v);)*\n        m\n    }};\n}\n",
+        );
+
+        assert_eq!(results.symbols.len(), 1);
+        assert_eq!(
+            results.symbols[0].signature.as_deref(),
+            Some("macro_rules! hashmap { () | ($($k:expr => $v:expr),* $(,)?) }")
+        );
+        assert!(
+            results.identifiers.is_empty(),
+            "transcribers are not invocations"
+        );
+    }
+
+    #[test]
+    fn test_invocation_arguments_emit_call_identifiers() {
+        let results = enrich(
+            "fn check() {\n    assert_eq!(parse(\"x(1)\"), config::load::<Settings>(path));\n    println!(\"{}\", if ok { format!(\"{}\", render(1)) } else { String::new() });\n}\n",
+        );
+        let names: Vec<&str> = results
+            .identifiers
+            .iter()
+            .map(|i| i.name.as_str())
+            .collect();
+
+        assert_eq!(names, vec!["parse", "load", "render", "new"]);
+        assert!(results
+            .identifiers
+            .iter()
+            .all(|i| i.kind == IdentifierKind::Call));
+    }
+
+    #[test]
+    fn test_declaration_macros_are_expanded() {
+        let results = enrich("thread_local! {\n    static DEPTH: Cell<u32> = Cell::new(0);\n}\n");
+
+        assert_eq!(results.symbols.len(), 1);
+        assert_eq!(results.symbols[0].name, "DEPTH");
+        assert!(results.identifiers.is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+// Rust enrichment passes
+//
+// Julie's Rust extractor already links impl-block methods to their type; these
+// passes add the trait side of `impl Trait for Type`, look inside macros, and
+// find doc comments separated from their item by attributes, and rebuild fn
+// signatures with their qualifiers, generics, and where clauses.
+
+mod declaration_macros;
+mod impls;
+mod macros;
+mod signatures;
+
+use declaration_macros::MacroConfig;
+
+use super::base::doc_comments::{fill_doc_comments, DocCommentStyle, DocPlacement};
+use super::base::{EnrichmentContext, Lexicon};
+use julie_extractors::{ExtractionResults, SymbolKind};
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+/// Rust strings and comments. Single quotes are left out because they start
+/// lifetimes far more often than char literals.
+pub(super) const RUST: Lexicon = Lexicon {
+    quotes: b"\"",
+    escape: Some(b'\\'),
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+};
+
+/// `///` and `/** */` above the item, past any `#[...]` attributes
+const DOC_COMMENTS: DocCommentStyle = DocCommentStyle {
+    line_prefixes: &["///"],
+    block: Some(("/**", "*/")),
+    placement: DocPlacement::Preceding,
+    attribute_prefixes: &["#["],
+    max_blank_lines: 0,
+    lexicon: RUST,
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    fill_doc_comments(ctx, &mut results.symbols, &DOC_COMMENTS, |s| {
+        s.kind != SymbolKind::Import
+    });
+    signatures::rebuild_fn_signatures(ctx, &mut results.symbols);
+    impls::link_trait_impls(ctx, results);
+    macros::enrich_macros(ctx, results, &MacroConfig::default());
+}
+
+/// Offset of the `>` closing the `<` at `open`, skipping `->` arrows
+fn find_closing_angle(text: &str, open: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut depth = 0usize;
+    for (index, &byte) in bytes.iter().enumerate().skip(open) {
+        match byte {
+            b'<' => depth += 1,
+            b'>' if index > 0 && bytes[index - 1] == b'-' => {}
+            b'>' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Last path segment of a type or trait, without generic arguments:
+/// `&'a mut std::fmt::Display<T>` → `Display`
+fn base_name(path: &str) -> &str {
+    let path = path.split('<').next().unwrap_or(path).trim();
+    let path = path.rsplit("::").next().unwrap_or(path);
+    path.trim_start_matches(['&', '*'])
+        .split_whitespace()
+        .last()
+        .unwrap_or("")
+        .trim_start_matches(['&', '*'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+
+    #[test]
+    fn test_base_name_strips_paths_generics_and_references() {
+        assert_eq!(base_name("std::fmt::Display"), "Display");
+        assert_eq!(base_name("Vec<T>"), "Vec");
+        assert_eq!(base_name("&'a mut Config"), "Config");
+        assert_eq!(base_name("From<HashMap<String, u32>>"), "From");
+    }
+
+    #[test]
+    fn test_doc_comment_above_attributes() {
+        use std::path::Path;
+
+        let content = "/// A point.\n#[derive(Debug, Clone)]\n#[serde(rename_all = \"camelCase\")]\npub struct Point {\n    x: i32,\n}\n";
+        let ctx = EnrichmentContext::new("src/point.rs", "rust", content, Path::new("."));
+        let start = content.find("pub struct").unwrap();
+        let point = ctx.create_symbol(
+            "Point",
+            SymbolKind::Struct,
+            start,
+            content.len() - 1,
+            Default::default(),
+        );
+        let mut results = ExtractionResults {
+            types: Default::default(),
+            ..test_results(vec![point])
+        };
+        enrich(&ctx, &mut results);
+        assert_eq!(
+            results.symbols[0].doc_comment.as_deref(),
+            Some("/// A point.")
+        );
+    }
+
+    #[test]
+    fn test_find_closing_angle_skips_arrows() {
+        let text = "<F: Fn() -> u32, T>";
+        assert_eq!(find_closing_angle(text, 0), Some(text.len() - 1));
+    }
+}
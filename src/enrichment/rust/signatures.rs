@@ -0,0 +1,263 @@
+// Rust function signatures
+//
+// Julie's Rust signatures are cut from a text slice and lose the parts that
+// matter when comparing APIs: `async`, the generic parameter list, and the
+// where clause. This pass rebuilds the signature of every fn from its
+// declaration header, from the visibility and qualifiers (`pub(crate) const
+// async unsafe extern "C"`) through the generics, arguments, return type, and
+// where clause, stopping at the body or the `;` of a trait method, with
+// attributes and comments dropped and whitespace collapsed:
+//
+//   pub async fn fetch<T: DeserializeOwned>(url: &str) -> Result<T> where T: Send
+//
+// Each fn also gets metadata.isAsync, isUnsafe, and isConst, and
+// metadata.returnType (absent for `()`) so consumers read the return type
+// from a field instead of re-parsing the signature.
+
+use super::RUST;
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, set_metadata, EnrichmentContext,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Qualifiers that may precede `fn`, in source order
+static QUALIFIERS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?:\b(?:pub(?:\s*\([^)]*\))?|default|const|async|unsafe|extern(?:\s+"[^"]*")?)\s+)*$"#,
+    )
+    .unwrap()
+});
+
+/// A parsed fn declaration header
+#[derive(Debug, PartialEq)]
+struct FnHeader {
+    signature: String,
+    return_type: Option<String>,
+    is_async: bool,
+    is_unsafe: bool,
+    is_const: bool,
+}
+
+pub(super) fn rebuild_fn_signatures(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, RUST);
+    for symbol in symbols
+        .iter_mut()
+        .filter(|s| matches!(s.kind, SymbolKind::Function | SymbolKind::Method))
+    {
+        let Some(header) = parse_fn_header(ctx.content, &code, symbol) else {
+            continue;
+        };
+        symbol.signature = Some(header.signature);
+        set_metadata(symbol, "isAsync", header.is_async);
+        set_metadata(symbol, "isUnsafe", header.is_unsafe);
+        set_metadata(symbol, "isConst", header.is_const);
+        if let Some(return_type) = header.return_type {
+            set_metadata(symbol, "returnType", return_type);
+        }
+    }
+}
+
+fn parse_fn_header(content: &str, code: &[bool], symbol: &Symbol) -> Option<FnHeader> {
+    let start = symbol.start_byte as usize;
+    let end = (symbol.end_byte as usize).min(content.len());
+    let fn_re = Regex::new(&format!(r"\bfn\s+{}\b", regex::escape(&symbol.name))).ok()?;
+    let fn_at = fn_re
+        .find_iter(&content[start..end])
+        .map(|m| start + m.start())
+        .find(|&at| code[at])?;
+
+    // Qualifiers sit between the last attribute/comment and `fn`; Julie's span
+    // may start at them or at `fn` itself
+    let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+    let prefix = code_only(content, code, line_start, fn_at);
+    let qualifiers = QUALIFIERS_RE
+        .find(&prefix)
+        .map_or("", |m| m.as_str())
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let body = header_end(content, code, fn_at)?;
+    let declaration = code_only(content, code, fn_at, body);
+    let declaration = declaration.split_whitespace().collect::<Vec<_>>().join(" ");
+    let signature = if qualifiers.is_empty() {
+        declaration
+    } else {
+        format!("{} {}", qualifiers, declaration)
+    };
+
+    let words: Vec<&str> = qualifiers.split([' ', '(']).collect();
+    Some(FnHeader {
+        return_type: return_type(&signature),
+        is_async: words.contains(&"async"),
+        is_unsafe: words.contains(&"unsafe"),
+        is_const: words.contains(&"const"),
+        signature,
+    })
+}
+
+/// Byte of the `{` or `;` ending the header that starts at `fn_at`
+fn header_end(content: &str, code: &[bool], fn_at: usize) -> Option<usize> {
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut index = fn_at;
+    while index < bytes.len() {
+        if code[index] {
+            match bytes[index] {
+                b'(' | b'[' => {
+                    index = find_matching_delimiter(content, index, RUST)?;
+                }
+                b'<' => depth += 1,
+                b'>' if index > 0 && bytes[index - 1] == b'-' => {}
+                b'>' => depth = depth.saturating_sub(1),
+                b'{' | b';' if depth == 0 => return Some(index),
+                _ => {}
+            }
+        }
+        index += 1;
+    }
+    None
+}
+
+/// `content[from..to]` with attributes removed and comments blanked; strings are kept
+fn code_only(content: &str, code: &[bool], from: usize, to: usize) -> String {
+    let mut text = String::new();
+    let mut index = from;
+    while index < to {
+        if !code[index] {
+            let run_end = (index..to).find(|&i| code[i]).unwrap_or(to);
+            if content.as_bytes()[index] == b'"' {
+                text.push_str(&content[index..run_end]);
+            } else {
+                text.push(' ');
+            }
+            index = run_end;
+            continue;
+        }
+        let rest = &content[index..to];
+        if rest.starts_with("#[") || rest.starts_with("#![") {
+            let open = index + rest.find('[').unwrap();
+            if let Some(close) = find_matching_delimiter(content, open, RUST).filter(|&c| c < to) {
+                index = close + 1;
+                continue;
+            }
+        }
+        let ch = rest.chars().next().unwrap();
+        text.push(ch);
+        index += ch.len_utf8();
+    }
+    text
+}
+
+/// The type after the top-level `->`, up to a where clause
+fn return_type(signature: &str) -> Option<String> {
+    let bytes = signature.as_bytes();
+    let mut depth = 0usize;
+    let mut arrow = None;
+    for (index, &byte) in bytes.iter().enumerate() {
+        if depth == 0 && signature[index..].starts_with(" where ") {
+            break;
+        }
+        match byte {
+            b'(' | b'[' | b'<' => depth += 1,
+            b'>' if index > 0 && bytes[index - 1] == b'-' && depth == 0 => {
+                arrow = Some(index + 1);
+                break;
+            }
+            b'>' if index > 0 && bytes[index - 1] == b'-' => {}
+            b')' | b']' | b'>' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    let rest = signature[arrow?..].trim();
+    let return_type = match rest.find(" where ") {
+        Some(at) => &rest[..at],
+        None => rest,
+    };
+    let return_type = return_type.trim();
+    (!return_type.is_empty() && return_type != "()").then(|| return_type.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    fn rebuild(content: &str, functions: &[(&str, &str)]) -> Vec<Symbol> {
+        let ctx = EnrichmentContext::new("lib.rs", "rust", content, Path::new("."));
+        let mut symbols: Vec<Symbol> = functions
+            .iter()
+            .map(|(text, name)| {
+                let start = content.find(text).unwrap();
+                ctx.create_symbol(
+                    name,
+                    SymbolKind::Function,
+                    start,
+                    start + text.len(),
+                    SymbolOptions {
+                        signature: Some(format!("fn {}", name)),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        rebuild_fn_signatures(&ctx, &mut symbols);
+        symbols
+    }
+
+    #[test]
+    fn test_async_generics_and_where_clause_survive() {
+        let content = "/// Fetch and decode\n#[tracing::instrument]\npub async fn fetch<T: DeserializeOwned>(\n    url: &str, // absolute\n) -> Result<T>\nwhere\n    T: Send,\n{\n    todo!()\n}\n";
+        let symbols = rebuild(
+            content,
+            &[(&content[content.find("pub async").unwrap()..], "fetch")],
+        );
+
+        let symbol = &symbols[0];
+        assert_eq!(
+            symbol.signature.as_deref(),
+            Some(
+                "pub async fn fetch<T: DeserializeOwned>( url: &str, ) -> Result<T> where T: Send,"
+            )
+        );
+        let metadata = symbol.metadata.as_ref().unwrap();
+        assert_eq!(metadata["isAsync"], true);
+        assert_eq!(metadata["isUnsafe"], false);
+        assert_eq!(metadata["returnType"], "Result<T>");
+    }
+
+    #[test]
+    fn test_qualifiers_before_the_span_and_trait_methods() {
+        let content = "pub(crate) const unsafe extern \"C\" fn raw(p: *const u8) -> u8 { 0 }\ntrait Store {\n    fn get<F>(&self, f: F) where F: Fn(u32) -> bool;\n}\n";
+        let symbols = rebuild(
+            content,
+            &[
+                ("fn raw(p: *const u8) -> u8 { 0 }", "raw"),
+                ("fn get<F>(&self, f: F) where F: Fn(u32) -> bool;", "get"),
+            ],
+        );
+
+        assert_eq!(
+            symbols[0].signature.as_deref(),
+            Some("pub(crate) const unsafe extern \"C\" fn raw(p: *const u8) -> u8")
+        );
+        let metadata = symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(
+            (&metadata["isConst"], &metadata["isUnsafe"]),
+            (&true.into(), &true.into())
+        );
+
+        assert_eq!(
+            symbols[1].signature.as_deref(),
+            Some("fn get<F>(&self, f: F) where F: Fn(u32) -> bool")
+        );
+        assert!(!symbols[1]
+            .metadata
+            .as_ref()
+            .unwrap()
+            .contains_key("returnType"));
+    }
+}
@@ -0,0 +1,153 @@
+// SQL doc comments
+//
+// Julie's SQL extractor never sets doc_comment, though schemas are usually
+// documented with plain comments: a run of `--` lines or a `/* */` block right
+// above CREATE TABLE/VIEW/FUNCTION/INDEX, and a trailing `-- ...` after a
+// column definition. Statement docs come from the shared preceding-comment
+// scan (EnrichmentOptions::sql_doc_blank_lines blank lines may separate the
+// comment and the statement; none by default); a column's doc is the comment
+// following it on the same line, after its comma. Markers are stripped either
+// way, since SQL has no doc syntax to keep.
+
+use super::super::base::doc_comments::{
+    preceding_doc, strip_comment_markers, DocCommentStyle, DocPlacement,
+};
+use super::super::base::EnrichmentContext;
+use super::SQL;
+use julie_extractors::{Symbol, SymbolKind};
+
+const DOC_COMMENTS: DocCommentStyle = DocCommentStyle {
+    line_prefixes: &["--"],
+    block: Some(("/*", "*/")),
+    placement: DocPlacement::Preceding,
+    attribute_prefixes: &[],
+    max_blank_lines: 0,
+    lexicon: SQL,
+};
+
+pub(super) fn fill_doc_comments(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let style = DocCommentStyle {
+        max_blank_lines: ctx.options.sql_doc_blank_lines,
+        ..DOC_COMMENTS
+    };
+    for symbol in symbols.iter_mut().filter(|s| s.doc_comment.is_none()) {
+        let start = symbol.start_byte as usize;
+        let comment = if symbol.kind == SymbolKind::Field {
+            trailing_comment(ctx.content, symbol.end_byte as usize)
+        } else if starts_create_statement(ctx.content, start) {
+            preceding_doc(ctx.content, start, &style)
+        } else {
+            None
+        };
+        symbol.doc_comment = comment
+            .map(|comment| strip_comment_markers(&comment, &style))
+            .filter(|doc| !doc.is_empty());
+    }
+}
+
+/// Whether the line holding `start` opens a CREATE statement
+fn starts_create_statement(content: &str, start: usize) -> bool {
+    let line_start = content[..start.min(content.len())]
+        .rfind('\n')
+        .map_or(0, |i| i + 1);
+    content[line_start..]
+        .trim_start()
+        .get(..6)
+        .is_some_and(|word| word.eq_ignore_ascii_case("create"))
+}
+
+/// A comment after `end` on the same line, past an optional comma
+fn trailing_comment(content: &str, end: usize) -> Option<String> {
+    let rest = content.get(end..)?;
+    let line = &rest[..rest.find('\n').unwrap_or(rest.len())];
+    let line = line.trim_start();
+    let line = line.strip_prefix(',').unwrap_or(line).trim_start();
+    if line.starts_with("--") {
+        return Some(line.to_string());
+    }
+    let close = line.strip_prefix("/*")?.find("*/")?;
+    Some(line[..close + 4].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use crate::enrichment::EnrichmentOptions;
+    use std::path::Path;
+
+    const SCHEMA: &str = "-- Stores one row per registered user.\n-- Soft-deleted rows keep their email.\nCREATE TABLE users (\n  id INT PRIMARY KEY,\n  email VARCHAR(255), -- unique, lowercased\n  name TEXT /* display name */\n);\n\n/*\n * Users who signed in this month.\n */\n\nCREATE VIEW active_users AS SELECT * FROM users;\n\n-- not a doc: separated by a statement\nSELECT 1;\nCREATE INDEX users_email ON users (email);\n";
+
+    fn symbols(ctx: &EnrichmentContext) -> Vec<Symbol> {
+        let symbol = |name: &str, kind: SymbolKind, text: &str| {
+            let start = SCHEMA.find(text).unwrap();
+            ctx.create_symbol(
+                name,
+                kind,
+                start,
+                start + text.len(),
+                SymbolOptions::default(),
+            )
+        };
+        vec![
+            symbol(
+                "users",
+                SymbolKind::Class,
+                &SCHEMA[SCHEMA.find("CREATE TABLE").unwrap()..SCHEMA.find(");").unwrap() + 2],
+            ),
+            symbol("id", SymbolKind::Field, "id INT PRIMARY KEY"),
+            symbol("email", SymbolKind::Field, "email VARCHAR(255)"),
+            symbol("name", SymbolKind::Field, "name TEXT"),
+            symbol(
+                "active_users",
+                SymbolKind::Interface,
+                "CREATE VIEW active_users AS SELECT * FROM users;",
+            ),
+            symbol(
+                "users_email",
+                SymbolKind::Property,
+                "CREATE INDEX users_email ON users (email);",
+            ),
+        ]
+    }
+
+    fn docs(symbols: &[Symbol]) -> Vec<Option<&str>> {
+        symbols.iter().map(|s| s.doc_comment.as_deref()).collect()
+    }
+
+    #[test]
+    fn test_statement_and_column_comments_become_docs() {
+        let ctx = EnrichmentContext::new("schema.sql", "sql", SCHEMA, Path::new("."));
+        let mut symbols = symbols(&ctx);
+        fill_doc_comments(&ctx, &mut symbols);
+        assert_eq!(
+            docs(&symbols),
+            vec![
+                Some("Stores one row per registered user.\nSoft-deleted rows keep their email."),
+                None,
+                Some("unique, lowercased"),
+                Some("display name"),
+                // Separated by a blank line
+                None,
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blank_lines_before_statement_are_configurable() {
+        let ctx = EnrichmentContext::new("schema.sql", "sql", SCHEMA, Path::new(".")).with_options(
+            EnrichmentOptions {
+                sql_doc_blank_lines: 1,
+                ..Default::default()
+            },
+        );
+        let mut symbols = symbols(&ctx);
+        fill_doc_comments(&ctx, &mut symbols);
+        assert_eq!(
+            symbols[4].doc_comment.as_deref(),
+            Some("Users who signed in this month.")
+        );
+        assert_eq!(symbols[5].doc_comment, None);
+    }
+}
@@ -0,0 +1,354 @@
+// SQL dialects
+//
+// T-SQL, PostgreSQL, and MySQL quote identifiers differently ([dbo].[Users],
+// `users`, "users"), separate batches differently (GO), and have their own
+// column types (NVARCHAR, JSONB, MEDIUMTEXT). The dialect is taken from the
+// caller when given and otherwise guessed from the content, then recorded on
+// every symbol as metadata.dialect. This pass also unquotes symbol names
+// (keeping the written form in metadata.quotedName and any qualifier in
+// metadata.schema) and infers metadata.dataType for columns and variables.
+
+use super::SQL;
+use crate::enrichment::base::{code_mask, set_metadata, EnrichmentContext};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// SQL dialect used for quoting, batch separators, and type names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SqlDialect {
+    #[default]
+    Generic,
+    Postgres,
+    Mysql,
+    Tsql,
+}
+
+impl SqlDialect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SqlDialect::Generic => "generic",
+            SqlDialect::Postgres => "postgres",
+            SqlDialect::Mysql => "mysql",
+            SqlDialect::Tsql => "tsql",
+        }
+    }
+
+    /// Guess the dialect from dialect-only syntax: GO batches and [brackets]
+    /// for T-SQL, `backticks` for MySQL, ::casts and $$ bodies for PostgreSQL
+    pub fn detect(content: &str) -> SqlDialect {
+        let code = code_mask(content, SQL);
+        let in_code = |m: &regex::Match| code.get(m.start()).copied().unwrap_or(false);
+        let count = |re: &Regex| re.find_iter(content).filter(in_code).count();
+
+        let scores = [
+            (SqlDialect::Tsql, 3 * count(&GO_RE) + count(&BRACKET_RE)),
+            (SqlDialect::Mysql, content.matches('`').count() / 2),
+            (
+                SqlDialect::Postgres,
+                count(&CAST_RE) + 2 * count(&DOLLAR_QUOTE_RE),
+            ),
+        ];
+        scores
+            .into_iter()
+            .filter(|(_, score)| *score > 0)
+            .max_by_key(|(_, score)| *score)
+            .map_or(SqlDialect::Generic, |(dialect, _)| dialect)
+    }
+
+    /// Identifier quote pairs this dialect honors
+    fn quotes(&self) -> &'static [(char, char)] {
+        match self {
+            SqlDialect::Tsql => &[('[', ']'), ('"', '"')],
+            SqlDialect::Mysql => &[('`', '`'), ('"', '"')],
+            SqlDialect::Generic | SqlDialect::Postgres => &[('"', '"')],
+        }
+    }
+
+    fn types(&self) -> &'static str {
+        match self {
+            SqlDialect::Generic => "",
+            SqlDialect::Postgres => POSTGRES_TYPES,
+            SqlDialect::Mysql => MYSQL_TYPES,
+            SqlDialect::Tsql => TSQL_TYPES,
+        }
+    }
+}
+
+impl fmt::Display for SqlDialect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SqlDialect {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "generic" | "ansi" | "sql" => Ok(SqlDialect::Generic),
+            "postgres" | "postgresql" | "pg" => Ok(SqlDialect::Postgres),
+            "mysql" | "mariadb" => Ok(SqlDialect::Mysql),
+            "tsql" | "t-sql" | "mssql" | "sqlserver" => Ok(SqlDialect::Tsql),
+            other => Err(format!(
+                "Unknown SQL dialect '{}' (expected generic, postgres, mysql, or tsql)",
+                other
+            )),
+        }
+    }
+}
+
+/// A T-SQL batch separator line: `GO` or `GO 5`
+pub(super) static GO_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?im)^[ \t]*GO(?:[ \t]+\d+)?[ \t]*;?[ \t]*$").unwrap());
+static BRACKET_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[[A-Za-z_][\w ]*\]\.|\.\[[A-Za-z_][\w ]*\]|\bAS\s+\[").unwrap());
+static CAST_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\w::[A-Za-z_]").unwrap());
+static DOLLAR_QUOTE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$\w*\$").unwrap());
+
+/// Types every dialect understands
+const STANDARD_TYPES: &str = "INT INTEGER SMALLINT BIGINT DECIMAL NUMERIC REAL FLOAT DOUBLE \
+    CHAR CHARACTER VARCHAR TEXT DATE TIME TIMESTAMP BOOLEAN BOOL BLOB CLOB BINARY VARBINARY INTERVAL";
+const POSTGRES_TYPES: &str = "SERIAL BIGSERIAL SMALLSERIAL JSON JSONB UUID BYTEA TIMESTAMPTZ \
+    TIMETZ INET CIDR MACADDR TSVECTOR TSQUERY MONEY CITEXT HSTORE INT2 INT4 INT8 FLOAT4 FLOAT8 \
+    POINT GEOMETRY";
+const MYSQL_TYPES: &str = "TINYINT MEDIUMINT DATETIME TINYTEXT MEDIUMTEXT LONGTEXT TINYBLOB \
+    MEDIUMBLOB LONGBLOB ENUM SET JSON YEAR BIT";
+const TSQL_TYPES: &str = "NVARCHAR NCHAR NTEXT DATETIME DATETIME2 DATETIMEOFFSET SMALLDATETIME \
+    UNIQUEIDENTIFIER BIT MONEY SMALLMONEY TINYINT IMAGE XML SQL_VARIANT ROWVERSION HIERARCHYID \
+    GEOGRAPHY GEOMETRY";
+
+fn lists(types: &str, word: &str) -> bool {
+    types.split_whitespace().any(|t| t == word)
+}
+
+/// A type name with optional multi-word form, (size), UNSIGNED, and [] suffix
+static TYPE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)^(DOUBLE\s+PRECISION|CHARACTER\s+VARYING|TIMESTAMP\s+WITH(?:OUT)?\s+TIME\s+ZONE|[A-Za-z_][A-Za-z0-9_]*)(\s*\([^()]*\))?(\s+UNSIGNED)?(\s*\[\])?",
+    )
+    .unwrap()
+});
+
+/// Byte ranges of the GO-separated batches of a T-SQL script
+pub(super) fn go_batches(content: &str) -> Vec<Range<usize>> {
+    let code = code_mask(content, SQL);
+    let mut batches = Vec::new();
+    let mut start = 0;
+    for separator in GO_RE.find_iter(content).filter(|m| code[m.start()]) {
+        batches.push(start..separator.start());
+        start = separator.end();
+    }
+    batches.push(start..content.len());
+    batches
+}
+
+/// Record the dialect on every symbol, unquote names, and infer column types
+pub(super) fn apply_dialect(ctx: &EnrichmentContext, dialect: SqlDialect, symbols: &mut [Symbol]) {
+    for symbol in symbols.iter_mut() {
+        set_metadata(symbol, "dialect", dialect.as_str());
+
+        if let Some(parts) = unquote_qualified(&symbol.name, dialect) {
+            let quoted = std::mem::take(&mut symbol.name);
+            let mut parts = parts.into_iter().rev();
+            symbol.name = parts.next().unwrap_or_default();
+            if let Some(schema) = parts.next() {
+                set_metadata(symbol, "schema", schema);
+            }
+            set_metadata(symbol, "quotedName", quoted);
+        }
+
+        if matches!(symbol.kind, SymbolKind::Field | SymbolKind::Variable) {
+            if let Some(data_type) = infer_data_type(ctx, symbol, dialect) {
+                set_metadata(symbol, "dataType", data_type);
+            }
+        }
+    }
+}
+
+/// Split `[dbo].[Users]` / `"public"."users"` into unquoted parts, honoring
+/// only the dialect's quotes. None when the name is a plain identifier.
+fn unquote_qualified(name: &str, dialect: SqlDialect) -> Option<Vec<String>> {
+    let quotes = dialect.quotes();
+    if !name.contains('.') && !name.starts_with(|c| quotes.iter().any(|(open, _)| *open == c)) {
+        return None;
+    }
+
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = name.chars().peekable();
+    while let Some(c) = chars.next() {
+        if let Some(&(_, close)) = quotes.iter().find(|(open, _)| *open == c) {
+            while let Some(inner) = chars.next() {
+                if inner == close {
+                    // A doubled closing quote is an escaped quote character
+                    if chars.peek() == Some(&close) {
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                current.push(inner);
+            }
+        } else if c == '.' {
+            parts.push(std::mem::take(&mut current));
+        } else {
+            current.push(c);
+        }
+    }
+    parts.push(current);
+
+    if parts.iter().any(|part| part.trim().is_empty()) {
+        return None;
+    }
+    Some(parts)
+}
+
+/// Column or variable type written after the name, if the dialect knows it
+fn infer_data_type(
+    ctx: &EnrichmentContext,
+    symbol: &Symbol,
+    dialect: SqlDialect,
+) -> Option<String> {
+    let text = symbol
+        .signature
+        .as_deref()
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| ctx.symbol_text(symbol));
+    let quoted = symbol
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("quotedName"))
+        .and_then(|v| v.as_str())
+        .unwrap_or(&symbol.name);
+    let after_name = text.find(quoted).map(|at| at + quoted.len())?;
+    let rest = text[after_name..]
+        .trim_start_matches([']', '"', '`'])
+        .trim_start();
+    let rest = rest
+        .strip_prefix("AS ")
+        .or_else(|| rest.strip_prefix("as "))
+        .unwrap_or(rest);
+
+    let captures = TYPE_RE.captures(rest)?;
+    let base = captures[1]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_uppercase();
+    let first_word = base.split(' ').next().unwrap_or_default();
+    let known = lists(STANDARD_TYPES, first_word)
+        || lists(dialect.types(), first_word)
+        || (dialect == SqlDialect::Generic
+            && [POSTGRES_TYPES, MYSQL_TYPES, TSQL_TYPES]
+                .iter()
+                .any(|types| lists(types, first_word)));
+    if !known {
+        return None;
+    }
+
+    let size = captures
+        .get(2)
+        .map(|m| {
+            m.as_str()
+                .split_whitespace()
+                .collect::<String>()
+                .to_ascii_uppercase()
+        })
+        .unwrap_or_default();
+    let unsigned = if captures.get(3).is_some() {
+        " UNSIGNED"
+    } else {
+        ""
+    };
+    let array = if captures.get(4).is_some() { "[]" } else { "" };
+    let data_type = format!("{}{}{}{}", base, size, unsigned, array);
+    Some(data_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    #[test]
+    fn test_detects_dialect_from_syntax() {
+        assert_eq!(
+            SqlDialect::detect("SELECT * FROM [dbo].[Users]\nGO\n"),
+            SqlDialect::Tsql
+        );
+        assert_eq!(
+            SqlDialect::detect("CREATE TABLE `users` (`id` INT);"),
+            SqlDialect::Mysql
+        );
+        assert_eq!(
+            SqlDialect::detect("SELECT id::text FROM users;"),
+            SqlDialect::Postgres
+        );
+        assert_eq!(SqlDialect::detect("SELECT 1; -- GO\n"), SqlDialect::Generic);
+        assert_eq!("MSSQL".parse::<SqlDialect>(), Ok(SqlDialect::Tsql));
+        assert!("oracle".parse::<SqlDialect>().is_err());
+    }
+
+    #[test]
+    fn test_unquotes_names_and_infers_types() {
+        let content = "CREATE TABLE [dbo].[Order Details] (\n  [Name] NVARCHAR(50) NOT NULL,\n  Total decimal(10, 2)\n);\n";
+        let ctx = EnrichmentContext::new("a.sql", "sql", content, Path::new("."));
+        let name_at = content.find("[Name]").unwrap();
+        let total_at = content.find("Total").unwrap();
+        let mut symbols = vec![
+            ctx.create_symbol(
+                "[dbo].[Order Details]",
+                SymbolKind::Class,
+                0,
+                content.len() - 2,
+                SymbolOptions::default(),
+            ),
+            ctx.create_symbol(
+                "[Name]",
+                SymbolKind::Field,
+                name_at,
+                name_at + 29,
+                SymbolOptions::default(),
+            ),
+            ctx.create_symbol(
+                "Total",
+                SymbolKind::Field,
+                total_at,
+                total_at + 20,
+                SymbolOptions::default(),
+            ),
+        ];
+        apply_dialect(&ctx, SqlDialect::Tsql, &mut symbols);
+
+        let table = &symbols[0];
+        assert_eq!(table.name, "Order Details");
+        let metadata = table.metadata.as_ref().unwrap();
+        assert_eq!(metadata["schema"], "dbo");
+        assert_eq!(metadata["quotedName"], "[dbo].[Order Details]");
+        assert_eq!(metadata["dialect"], "tsql");
+
+        assert_eq!(symbols[1].name, "Name");
+        assert_eq!(
+            symbols[1].metadata.as_ref().unwrap()["dataType"],
+            "NVARCHAR(50)"
+        );
+        assert_eq!(
+            symbols[2].metadata.as_ref().unwrap()["dataType"],
+            "DECIMAL(10,2)"
+        );
+    }
+
+    #[test]
+    fn test_go_batches_split_scripts() {
+        let content = "CREATE PROC a AS SELECT 1\nGO\n-- GO\nCREATE PROC b AS SELECT 2\ngo 2\n";
+        let batches = go_batches(content);
+        assert_eq!(batches.len(), 3);
+        assert!(content[batches[1].clone()].contains("PROC b"));
+        assert!(content[batches[2].clone()].trim().is_empty());
+    }
+}
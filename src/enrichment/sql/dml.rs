@@ -0,0 +1,487 @@
+// SQL data modification statements
+//
+// Migration and seed scripts are mostly INSERT/UPDATE/DELETE/MERGE, which
+// Julie's SQL extractor does not look at, so nothing records which code writes
+// which table. This pass finds those statements and adds:
+//
+// - a Call identifier for the target table (target_symbol_id set when the
+//   table is created in the same file, temp tables and table variables
+//   included)
+// - a MemberAccess identifier for each column in the INSERT column list or
+//   UPDATE SET clause, and in a MERGE's UPDATE and INSERT actions
+// - Call identifiers for the source tables of INSERT ... SELECT and of a
+//   MERGE's USING clause
+// - metadata.tableWrites ([{table, operation, columns, line}]) and
+//   metadata.tableReads on the containing procedure, function, or trigger.
+//   A MERGE's write also lists its WHEN clauses in order as actions:
+//   [{when: "matched" | "not matched" | "not matched by source", condition,
+//   action: "update" | "delete" | "insert" | "nothing"}], condition being the
+//   `AND ...` predicate when there is one
+//
+// Statements outside any routine are attributed to a synthetic Module symbol
+// named after the file (metadata.synthetic, metadata.fileScope). MERGE
+// statements are read in merge.rs.
+
+use super::dialect::GO_RE;
+use super::merge::{find_merges, MergeAction};
+use super::{SqlDialect, SQL};
+use crate::enrichment::base::symbol_index::SymbolIndex;
+use crate::enrichment::base::{
+    append_metadata, code_mask, set_metadata, split_top_level, trim_with_offset, EnrichmentContext,
+    SymbolOptions,
+};
+use julie_extractors::{ExtractionResults, IdentifierKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// One identifier part: [bracketed], "quoted", `backticked`, or bare (#temp, @table)
+pub(super) const PART: &str = r#"(?:\[[^\]\n]+\]|"[^"\n]+"|`[^`\n]+`|[A-Za-z_#@][\w$#@]*)"#;
+
+pub(super) static PART_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(PART).unwrap());
+
+static INSERT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\bINSERT\s+(?:(?:IGNORE|OR\s+\w+)\s+)?(?:INTO\s+)?(?P<table>{PART}(?:\s*\.\s*{PART})*)\s*(?P<columns>\([^()]*\))?"
+    ))
+    .unwrap()
+});
+static UPDATE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\bUPDATE\s+(?:ONLY\s+)?(?P<table>{PART}(?:\s*\.\s*{PART})*)(?:\s+(?:AS\s+)?\w+)?\s+SET\b"
+    ))
+    .unwrap()
+});
+static DELETE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\bDELETE\s+(?:FROM\s+)?(?P<table>{PART}(?:\s*\.\s*{PART})*)"
+    ))
+    .unwrap()
+});
+static SOURCE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\b(?:FROM|JOIN)\s+(?P<table>{PART}(?:\s*\.\s*{PART})*)"
+    ))
+    .unwrap()
+});
+
+/// End of an UPDATE's SET clause
+static SET_END_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(?:WHERE|FROM|RETURNING|OUTPUT)\b").unwrap());
+static SELECT_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^\s*SELECT\b").unwrap());
+
+/// Words that follow INSERT/DELETE in DDL (`ON DELETE CASCADE`, `AFTER INSERT ON`)
+const NOT_TABLES: &[&str] = &[
+    "ON", "OR", "OF", "AS", "SET", "NO", "CASCADE", "RESTRICT", "FROM", "INTO", "WHERE", "TOP",
+    "VALUES", "SELECT", "DEFAULT", "WHEN", "OUTPUT",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Operation {
+    Insert,
+    Update,
+    Delete,
+    Merge,
+}
+
+impl Operation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Insert => "insert",
+            Operation::Update => "update",
+            Operation::Delete => "delete",
+            Operation::Merge => "merge",
+        }
+    }
+}
+
+/// A name written in the statement, unquoted, with the byte span of its last part
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct NameRef {
+    pub(super) name: String,
+    pub(super) start: usize,
+    pub(super) end: usize,
+}
+
+/// One data modification statement
+#[derive(Debug)]
+pub(super) struct Statement {
+    pub(super) operation: Operation,
+    pub(super) start: usize,
+    pub(super) table: NameRef,
+    pub(super) columns: Vec<NameRef>,
+    pub(super) sources: Vec<NameRef>,
+    /// A MERGE's WHEN clauses
+    pub(super) actions: Vec<MergeAction>,
+    /// Byte past the statement's end
+    pub(super) end: usize,
+}
+
+pub(super) fn enrich_dml(
+    ctx: &EnrichmentContext,
+    dialect: SqlDialect,
+    results: &mut ExtractionResults,
+) {
+    let statements = find_statements(ctx.content);
+    if statements.is_empty() {
+        return;
+    }
+
+    let is_routine = |s: &Symbol| matches!(s.kind, SymbolKind::Function | SymbolKind::Method);
+    let index = SymbolIndex::new(&results.symbols);
+    let mut owners: Vec<Option<String>> = statements
+        .iter()
+        .map(|st| {
+            index
+                .innermost_at(st.start, is_routine)
+                .map(|s| s.id.clone())
+        })
+        .collect();
+    if owners.iter().any(Option::is_none) {
+        let file_scope = file_scope_symbol(ctx, dialect);
+        for owner in owners.iter_mut().filter(|o| o.is_none()) {
+            *owner = Some(file_scope.id.clone());
+        }
+        results.symbols.push(file_scope);
+    }
+
+    let tables: HashMap<String, String> = results
+        .symbols
+        .iter()
+        .rev()
+        .filter(|s| s.kind == SymbolKind::Class)
+        .map(|s| (s.name.to_ascii_lowercase(), s.id.clone()))
+        .collect();
+    let positions: HashMap<String, usize> = results
+        .symbols
+        .iter()
+        .enumerate()
+        .map(|(position, s)| (s.id.clone(), position))
+        .collect();
+    let mut known: HashSet<(u32, String)> = results
+        .identifiers
+        .iter()
+        .map(|i| (i.start_byte, i.name.clone()))
+        .collect();
+
+    for (statement, owner) in statements.into_iter().zip(owners) {
+        let line = ctx.line_of(statement.start);
+        for (name, kind) in std::iter::once((&statement.table, IdentifierKind::Call))
+            .chain(
+                statement
+                    .columns
+                    .iter()
+                    .map(|c| (c, IdentifierKind::MemberAccess)),
+            )
+            .chain(statement.sources.iter().map(|s| (s, IdentifierKind::Call)))
+        {
+            let mut identifier = ctx.create_identifier(
+                &name.name,
+                kind.clone(),
+                name.start,
+                name.end,
+                owner.clone(),
+            );
+            if kind == IdentifierKind::Call {
+                identifier.target_symbol_id = tables.get(&name.name.to_ascii_lowercase()).cloned();
+            }
+            identifier.code_context = Some(line_text(ctx, name.start).to_string());
+            if known.insert((identifier.start_byte, identifier.name.clone())) {
+                results.identifiers.push(identifier);
+            }
+        }
+
+        let Some(&position) = owner.as_ref().and_then(|id| positions.get(id)) else {
+            continue;
+        };
+        let symbol = &mut results.symbols[position];
+        let mut columns: Vec<&str> = Vec::new();
+        for column in &statement.columns {
+            if !columns.contains(&column.name.as_str()) {
+                columns.push(&column.name);
+            }
+        }
+        let mut write = json!({
+            "table": statement.table.name,
+            "operation": statement.operation.as_str(),
+            "columns": columns,
+            "line": line,
+        });
+        if statement.operation == Operation::Merge {
+            write["actions"] = statement
+                .actions
+                .iter()
+                .map(|a| {
+                    let mut action = json!({ "when": a.when, "action": a.action });
+                    if let Some(condition) = &a.condition {
+                        action["condition"] = json!(condition);
+                    }
+                    action
+                })
+                .collect();
+        }
+        append_metadata(symbol, "tableWrites", write);
+        for source in &statement.sources {
+            append_metadata(
+                symbol,
+                "tableReads",
+                json!({ "table": source.name, "line": ctx.line_of(source.start) }),
+            );
+        }
+    }
+}
+
+/// Synthetic Module spanning the file, owning statements outside any routine
+fn file_scope_symbol(ctx: &EnrichmentContext, dialect: SqlDialect) -> Symbol {
+    let name = Path::new(ctx.file_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(ctx.file_path);
+    let mut symbol = ctx.create_symbol(
+        name,
+        SymbolKind::Module,
+        0,
+        ctx.content.len(),
+        SymbolOptions::default(),
+    );
+    set_metadata(&mut symbol, "synthetic", true);
+    set_metadata(&mut symbol, "fileScope", true);
+    set_metadata(&mut symbol, "dialect", dialect.as_str());
+    symbol
+}
+
+fn line_text<'a>(ctx: &EnrichmentContext<'a>, byte: usize) -> &'a str {
+    let start = ctx.content[..byte].rfind('\n').map_or(0, |i| i + 1);
+    let end = ctx.content[byte..]
+        .find('\n')
+        .map_or(ctx.content.len(), |i| byte + i);
+    ctx.content[start..end].trim()
+}
+
+fn find_statements(content: &str) -> Vec<Statement> {
+    let code = code_mask(content, SQL);
+    let mut statements = Vec::new();
+
+    for (re, operation) in [
+        (&*INSERT_RE, Operation::Insert),
+        (&*UPDATE_RE, Operation::Update),
+        (&*DELETE_RE, Operation::Delete),
+    ] {
+        for captures in re.captures_iter(content) {
+            let whole = captures.get(0).unwrap();
+            let table = captures.name("table").unwrap();
+            if !code[whole.start()] {
+                continue;
+            }
+            let Some(table_ref) = last_part(table.as_str(), table.start()) else {
+                continue;
+            };
+            let end = statement_end(content, &code, whole.end());
+
+            let (columns, sources) = match operation {
+                Operation::Insert => {
+                    let columns = captures
+                        .name("columns")
+                        .map(|list| column_list(list.as_str(), list.start()))
+                        .unwrap_or_default();
+                    let rest = &content[whole.end()..end];
+                    let sources = if SELECT_RE.is_match(rest) {
+                        source_tables(rest, whole.end(), &code)
+                    } else {
+                        Vec::new()
+                    };
+                    (columns, sources)
+                }
+                Operation::Update => (set_columns(content, whole.end(), end), Vec::new()),
+                Operation::Delete | Operation::Merge => (Vec::new(), Vec::new()),
+            };
+
+            statements.push(Statement {
+                operation,
+                start: whole.start(),
+                table: table_ref,
+                columns,
+                sources,
+                actions: Vec::new(),
+                end,
+            });
+        }
+    }
+
+    statements.extend(find_merges(content, &code));
+    // The UPDATE/DELETE/INSERT actions of a MERGE are part of it
+    let merges: Vec<(usize, usize)> = statements
+        .iter()
+        .filter(|s| s.operation == Operation::Merge)
+        .map(|s| (s.start, s.end))
+        .collect();
+    statements.retain(|s| {
+        s.operation == Operation::Merge
+            || !merges
+                .iter()
+                .any(|&(start, end)| start < s.start && s.start < end)
+    });
+    statements.sort_by_key(|s| s.start);
+    statements
+}
+
+/// Unquoted last part of a possibly qualified name, None for keywords
+pub(super) fn last_part(text: &str, offset: usize) -> Option<NameRef> {
+    let part = PART_RE.find_iter(text).last()?;
+    let raw = part.as_str();
+    if NOT_TABLES.iter().any(|k| k.eq_ignore_ascii_case(raw)) {
+        return None;
+    }
+    let quoted = raw.starts_with(['[', '"', '`']);
+    let (start, name) = if quoted {
+        (offset + part.start() + 1, &raw[1..raw.len() - 1])
+    } else {
+        (offset + part.start(), raw)
+    };
+    Some(NameRef {
+        name: name.to_string(),
+        start,
+        end: start + name.len(),
+    })
+}
+
+/// First `;` or GO line after `from` in code, or the end of the content
+pub(super) fn statement_end(content: &str, code: &[bool], from: usize) -> usize {
+    let semicolon = content[from..]
+        .match_indices(';')
+        .map(|(i, _)| from + i)
+        .find(|&i| code[i]);
+    let go = std::iter::successors(GO_RE.find_at(content, from), |m| {
+        GO_RE.find_at(content, m.end())
+    })
+    .map(|m| m.start())
+    .find(|&i| code[i]);
+    [semicolon, go]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(content.len())
+}
+
+/// `(a, [b], "c")` (offset absolute)
+pub(super) fn column_list(list: &str, offset: usize) -> Vec<NameRef> {
+    let inner = &list[1..list.len() - 1];
+    split_top_level(inner, b',', SQL)
+        .into_iter()
+        .filter_map(|(at, segment)| {
+            let (start, segment) = trim_with_offset(offset + 1 + at, segment);
+            last_part(segment, start)
+        })
+        .collect()
+}
+
+/// Assigned columns of the SET clause starting at `set_end`
+pub(super) fn set_columns(content: &str, set_end: usize, end: usize) -> Vec<NameRef> {
+    let clause = &content[set_end..end];
+    let clause = SET_END_RE
+        .find(clause)
+        .map_or(clause, |m| &clause[..m.start()]);
+    split_top_level(clause, b',', SQL)
+        .into_iter()
+        .filter_map(|(at, segment)| {
+            let target = segment.split('=').next()?;
+            let (start, target) = trim_with_offset(set_end + at, target);
+            last_part(target, start)
+        })
+        .collect()
+}
+
+/// Tables after FROM/JOIN in the SELECT of an INSERT ... SELECT
+pub(super) fn source_tables(select: &str, offset: usize, code: &[bool]) -> Vec<NameRef> {
+    SOURCE_RE
+        .captures_iter(select)
+        .filter(|c| code[offset + c.get(0).unwrap().start()])
+        .filter_map(|c| {
+            let table = c.name("table")?;
+            last_part(table.as_str(), offset + table.start())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use serde_json::Value;
+
+    fn run(content: &str, symbols: Vec<Symbol>) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("db/seed.sql", "sql", content, Path::new("."));
+        let mut results = test_results(symbols);
+        enrich_dml(&ctx, SqlDialect::Tsql, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_top_level_dml_is_attributed_to_file_scope() {
+        let content = "INSERT INTO [dbo].[orders] (id, [total]) VALUES (1, 2);\nUPDATE orders o SET o.total = 3, status = 'x' WHERE id = 1;\nDELETE FROM orders;\n";
+        let results = run(content, Vec::new());
+
+        let names: Vec<(&str, IdentifierKind)> = results
+            .identifiers
+            .iter()
+            .map(|i| (i.name.as_str(), i.kind.clone()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("orders", IdentifierKind::Call),
+                ("id", IdentifierKind::MemberAccess),
+                ("total", IdentifierKind::MemberAccess),
+                ("orders", IdentifierKind::Call),
+                ("total", IdentifierKind::MemberAccess),
+                ("status", IdentifierKind::MemberAccess),
+                ("orders", IdentifierKind::Call),
+            ]
+        );
+        let first = &results.identifiers[0];
+        assert_eq!(
+            &content[first.start_byte as usize..first.end_byte as usize],
+            "orders"
+        );
+
+        let scope = &results.symbols[0];
+        assert_eq!(scope.name, "seed");
+        assert_eq!(
+            results.identifiers[6].containing_symbol_id.as_ref(),
+            Some(&scope.id)
+        );
+        let writes = &scope.metadata.as_ref().unwrap()["tableWrites"];
+        assert_eq!(writes[1]["operation"], Value::from("update"));
+        assert_eq!(writes[1]["line"], Value::from(2));
+    }
+
+    #[test]
+    fn test_insert_select_inside_procedure() {
+        let content = "CREATE PROCEDURE archive AS\nBEGIN\n  INSERT INTO archive_orders SELECT * FROM orders o JOIN customers c ON 1 = 1;\nEND;\nCREATE TABLE t (id INT REFERENCES x ON DELETE CASCADE);\n";
+        let ctx = EnrichmentContext::new("db/seed.sql", "sql", content, Path::new("."));
+        let procedure = ctx.create_symbol(
+            "archive",
+            SymbolKind::Function,
+            0,
+            content.find("END;").unwrap() + 3,
+            SymbolOptions::default(),
+        );
+        let results = run(content, vec![procedure]);
+
+        assert_eq!(results.symbols.len(), 1);
+        let names: Vec<&str> = results
+            .identifiers
+            .iter()
+            .map(|i| i.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["archive_orders", "orders", "customers"]);
+        let metadata = results.symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(
+            metadata["tableWrites"][0]["table"],
+            Value::from("archive_orders")
+        );
+        assert_eq!(metadata["tableReads"][1]["table"], Value::from("customers"));
+    }
+}
@@ -0,0 +1,221 @@
+// SQL MERGE statements
+//
+// A MERGE writes its target through up to three kinds of WHEN clause. The DML
+// pass records it as one write: the USING source (a table, or the tables a
+// subquery reads), the columns its UPDATE SET and INSERT column lists assign,
+// and each WHEN clause in order as {when, condition, action}. The UPDATE,
+// DELETE, and INSERT inside the clauses are not separate statements.
+
+use super::dml::{
+    column_list, last_part, set_columns, source_tables, statement_end, NameRef, Operation,
+    Statement, PART, PART_RE,
+};
+use super::SQL;
+use crate::enrichment::base::{collapse_whitespace, find_matching_delimiter};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static MERGE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\bMERGE\s+(?:TOP\s*\([^()]*\)\s*(?:PERCENT\s+)?)?(?:INTO\s+)?(?P<table>{PART}(?:\s*\.\s*{PART})*)"
+    ))
+    .unwrap()
+});
+static USING_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bUSING\s*").unwrap());
+/// `WHEN [NOT] MATCHED [BY TARGET|SOURCE] [AND ...] THEN <action>`
+static WHEN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)\bWHEN\s+(?P<not>NOT\s+)?MATCHED\b(?:\s+BY\s+(?P<by>TARGET|SOURCE)\b)?(?P<condition>.*?)\bTHEN\s+(?P<action>UPDATE|DELETE|INSERT|DO\s+NOTHING)\b")
+        .unwrap()
+});
+
+/// One `WHEN ... THEN` clause of a MERGE
+#[derive(Debug, PartialEq)]
+pub(super) struct MergeAction {
+    pub(super) when: &'static str,
+    pub(super) condition: Option<String>,
+    pub(super) action: &'static str,
+}
+
+/// Every MERGE statement in the file
+pub(super) fn find_merges(content: &str, code: &[bool]) -> Vec<Statement> {
+    let mut statements = Vec::new();
+    for captures in MERGE_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        let table = captures.name("table").unwrap();
+        if !code[whole.start()] {
+            continue;
+        }
+        let Some(table_ref) = last_part(table.as_str(), table.start()) else {
+            continue;
+        };
+        let end = statement_end(content, code, whole.end());
+        statements.push(merge_statement(
+            content,
+            code,
+            whole.start(),
+            table_ref,
+            whole.end(),
+            end,
+        ));
+    }
+    statements
+}
+
+/// The MERGE into `table` whose header ends at `from`: its USING source and
+/// WHEN clauses, with the columns its UPDATE and INSERT actions assign
+fn merge_statement(
+    content: &str,
+    code: &[bool],
+    start: usize,
+    table: NameRef,
+    from: usize,
+    end: usize,
+) -> Statement {
+    let body = &content[from..end];
+    let mut sources = Vec::new();
+    if let Some(using) = USING_RE.find_iter(body).find(|m| code[from + m.start()]) {
+        let source = from + using.end();
+        if content[source..].starts_with('(') {
+            if let Some(close) = find_matching_delimiter(content, source, SQL) {
+                sources = source_tables(&content[source..close], source, code);
+            }
+        } else if let Some(name) = PART_RE
+            .find(&content[source..end])
+            .filter(|m| m.start() == 0)
+        {
+            let mut name_end = source + name.end();
+            // Qualified names: `dbo.staging`
+            while let Some(part) = content[name_end..end]
+                .strip_prefix('.')
+                .and_then(|rest| PART_RE.find(rest).filter(|m| m.start() == 0))
+            {
+                name_end += 1 + part.end();
+            }
+            sources.extend(last_part(&content[source..name_end], source));
+        }
+    }
+
+    let clauses: Vec<_> = WHEN_RE
+        .captures_iter(body)
+        .filter(|c| code[from + c.get(0).unwrap().start()])
+        .collect();
+    let mut columns = Vec::new();
+    let mut actions = Vec::new();
+    for (index, captures) in clauses.iter().enumerate() {
+        let clause_end = clauses
+            .get(index + 1)
+            .map_or(end, |next| from + next.get(0).unwrap().start());
+        let action_end = from + captures.get(0).unwrap().end();
+        let when = match (captures.name("not"), captures.name("by")) {
+            (None, _) => "matched",
+            (Some(_), Some(by)) if by.as_str().eq_ignore_ascii_case("SOURCE") => {
+                "not matched by source"
+            }
+            (Some(_), _) => "not matched",
+        };
+        let condition = captures["condition"].trim();
+        let condition = condition
+            .get(..3)
+            .filter(|and| and.eq_ignore_ascii_case("AND"))
+            .map_or(condition, |_| condition[3..].trim_start());
+        let action = match captures["action"].to_ascii_uppercase().as_str() {
+            "UPDATE" => "update",
+            "DELETE" => "delete",
+            "INSERT" => "insert",
+            _ => "nothing",
+        };
+
+        // `SET a = ...` of an update, `(a, b)` of an insert
+        let rest = content[action_end..clause_end].trim_start();
+        let at = clause_end - rest.len();
+        match action {
+            "update" if rest.get(..3).is_some_and(|w| w.eq_ignore_ascii_case("SET")) => {
+                columns.extend(set_columns(content, at + 3, clause_end));
+            }
+            "insert" if rest.starts_with('(') => {
+                if let Some(close) = find_matching_delimiter(content, at, SQL) {
+                    columns.extend(column_list(&content[at..=close], at));
+                }
+            }
+            _ => {}
+        }
+        actions.push(MergeAction {
+            when,
+            condition: (!condition.is_empty()).then(|| collapse_whitespace(condition)),
+            action,
+        });
+    }
+
+    Statement {
+        operation: Operation::Merge,
+        start,
+        table,
+        columns,
+        sources,
+        actions,
+        end,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::dml::enrich_dml;
+    use super::super::SqlDialect;
+    use crate::enrichment::base::{test_results, EnrichmentContext};
+    use julie_extractors::{ExtractionResults, IdentifierKind};
+    use serde_json::Value;
+    use std::path::Path;
+
+    fn run(content: &str) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("db/seed.sql", "sql", content, Path::new("."));
+        let mut results = test_results(Vec::new());
+        enrich_dml(&ctx, SqlDialect::Tsql, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_merge_records_target_source_and_actions() {
+        let content = "MERGE INTO dbo.orders AS t\nUSING staging.orders_in AS s ON t.id = s.id\nWHEN MATCHED AND s.deleted = 1 THEN DELETE\nWHEN MATCHED THEN UPDATE SET t.total = s.total, status = 'x'\nWHEN NOT MATCHED BY TARGET THEN INSERT (id, total) VALUES (s.id, s.total)\nWHEN NOT MATCHED BY SOURCE THEN DELETE;\nMERGE #work w USING (SELECT * FROM orders) src ON 1 = 1 WHEN NOT MATCHED THEN INSERT VALUES (1);\n";
+        let results = run(content);
+
+        let names: Vec<(&str, IdentifierKind)> = results
+            .identifiers
+            .iter()
+            .map(|i| (i.name.as_str(), i.kind.clone()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("orders", IdentifierKind::Call),
+                ("total", IdentifierKind::MemberAccess),
+                ("status", IdentifierKind::MemberAccess),
+                ("id", IdentifierKind::MemberAccess),
+                ("total", IdentifierKind::MemberAccess),
+                ("orders_in", IdentifierKind::Call),
+                ("#work", IdentifierKind::Call),
+                ("orders", IdentifierKind::Call),
+            ]
+        );
+
+        let metadata = results.symbols[0].metadata.as_ref().unwrap();
+        let writes = metadata["tableWrites"].as_array().unwrap();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(
+            writes[0],
+            serde_json::json!({
+                "table": "orders",
+                "operation": "merge",
+                "columns": ["total", "status", "id"],
+                "line": 1,
+                "actions": [
+                    { "when": "matched", "condition": "s.deleted = 1", "action": "delete" },
+                    { "when": "matched", "action": "update" },
+                    { "when": "not matched", "action": "insert" },
+                    { "when": "not matched by source", "action": "delete" },
+                ],
+            })
+        );
+        assert_eq!(metadata["tableReads"][0]["table"], Value::from("orders_in"));
+        assert_eq!(metadata["tableReads"][1]["table"], Value::from("orders"));
+    }
+}
@@ -0,0 +1,53 @@
+// SQL enrichment passes
+//
+// Julie's SQL extractor covers the statements tree-sitter-sql parses, but
+// dialect-specific syntax (T-SQL brackets and GO batches, PL/pgSQL bodies,
+// MySQL DELIMITER blocks) often ends up in ERROR nodes. The recovery pass
+// restores the CREATE statements found there through the shared
+// error-recovery rules, with the kinds Julie uses for each object, and temp
+// tables and table variables declared in routine bodies become tables scoped
+// to the routine; the dialect pass then records the dialect and applies its
+// quoting and type names, and the DML pass records which tables
+// INSERT/UPDATE/DELETE/MERGE statements write.
+// The query-scope pass reparents view column aliases found inside CTEs and
+// subqueries to those scopes, and the permissions pass turns GRANT/REVOKE and
+// ownership statements into role symbols linked to the objects they cover.
+// Comments above CREATE statements and after column definitions become docs.
+
+mod comments;
+mod dialect;
+mod dml;
+mod merge;
+mod permissions;
+mod query_scopes;
+mod recovery;
+mod temp_tables;
+
+use super::base::{EnrichmentContext, Lexicon};
+use julie_extractors::ExtractionResults;
+
+pub use dialect::SqlDialect;
+
+pub const EXTRACTOR_VERSION: u32 = 2;
+
+/// SQL strings double their quote to escape it; comments are -- and /* */
+pub(super) const SQL: Lexicon = Lexicon {
+    quotes: b"'\"",
+    escape: None,
+    line_comment: Some("--"),
+    block_comment: Some(("/*", "*/")),
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let dialect = ctx
+        .options
+        .sql_dialect
+        .unwrap_or_else(|| SqlDialect::detect(ctx.content));
+    recovery::recover_create_statements(ctx, dialect, &mut results.symbols);
+    temp_tables::extract_temp_tables(ctx, &mut results.symbols);
+    query_scopes::nest_query_aliases(ctx, &mut results.symbols);
+    comments::fill_doc_comments(ctx, &mut results.symbols);
+    dialect::apply_dialect(ctx, dialect, &mut results.symbols);
+    permissions::enrich_permissions(ctx, dialect, results);
+    dml::enrich_dml(ctx, dialect, results);
+}
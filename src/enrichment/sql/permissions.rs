@@ -0,0 +1,493 @@
+// SQL permissions and ownership
+//
+// GRANT, REVOKE, and DENY statements and ownership changes (`ALTER TABLE t
+// OWNER TO r`, T-SQL `ALTER AUTHORIZATION ON t TO r`) are not extracted by
+// Julie, parsed or not, so this pass scans the code for them. For every
+// grantee or owner it finds (or creates) a role symbol: a Variable with
+// metadata.sqlRole, placed on the role's CREATE ROLE/USER/GROUP/LOGIN statement
+// when the file has one and on its first mention otherwise (metadata.synthetic).
+// Each statement then adds, per object named:
+//
+// - an entry in the role's metadata.permissions:
+//   {permission, privileges, object, objectType, columns, withGrantOption, line},
+//   where permission is "grant", "revoke", "deny", or "owner" and privileges
+//   are upper-cased (`ALL PRIVILEGES` is "ALL")
+// - a Call identifier for the object inside the role, targeting the object
+//   when it is declared in the same file
+// - a Uses relationship from the role to that object, carrying the same entry
+//
+// Role-to-role grants (`GRANT reporting TO alice`, no ON clause) are skipped.
+
+use super::dml::{last_part, statement_end, NameRef, PART};
+use super::{SqlDialect, SQL};
+use crate::enrichment::base::{
+    append_metadata, code_mask, set_metadata, split_top_level, trim_with_offset, EnrichmentContext,
+    SymbolOptions,
+};
+use julie_extractors::{ExtractionResults, IdentifierKind, RelationshipKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+static PERMISSION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(GRANT|REVOKE|DENY)\s").unwrap());
+static ON_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bON\b").unwrap());
+static TO_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bTO\b").unwrap());
+static FROM_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bFROM\b").unwrap());
+/// Clauses after the grantee list
+static GRANTEES_END_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(?:WITH|GRANTED\s+BY|CASCADE|RESTRICT|AS)\b").unwrap());
+static GRANT_OPTION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bWITH\s+GRANT\s+OPTION\b|^\s*GRANT\s+OPTION\s+FOR\b").unwrap()
+});
+/// `TABLE`, `ALL TABLES IN SCHEMA`, or T-SQL `OBJECT::` before the object names
+static OBJECT_TYPE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\s*(?:ALL\s+\w+\s+IN\s+(SCHEMA)\s+|(\w+)\s*::\s*|(TABLE|VIEW|FUNCTION|PROCEDURE|ROUTINE|SEQUENCE|SCHEMA|DATABASE|TYPE|DOMAIN)\s+)").unwrap()
+});
+static CREATE_ROLE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\bCREATE\s+(?:ROLE|USER|GROUP|LOGIN)\s+(?:IF\s+NOT\s+EXISTS\s+)?(?P<name>{PART})"
+    ))
+    .unwrap()
+});
+static OWNER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\bALTER\s+(?P<type>TABLE|VIEW|MATERIALIZED\s+VIEW|FUNCTION|PROCEDURE|SCHEMA|SEQUENCE|DATABASE|TYPE|DOMAIN)\s+(?:IF\s+EXISTS\s+)?(?P<object>{PART}(?:\s*\.\s*{PART})*)(?:\s*\([^()]*\))?\s+OWNER\s+TO\s+(?P<owner>{PART})"
+    ))
+    .unwrap()
+});
+static AUTHORIZATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\bALTER\s+AUTHORIZATION\s+ON\s+(?:(?P<type>\w+)\s*::\s*)?(?P<object>{PART}(?:\s*\.\s*{PART})*)\s+TO\s+(?P<owner>{PART})"
+    ))
+    .unwrap()
+});
+
+/// Kinds Julie (and recovery) give the objects permissions apply to
+const OBJECT_KINDS: &[SymbolKind] = &[
+    SymbolKind::Class,
+    SymbolKind::Interface,
+    SymbolKind::Function,
+    SymbolKind::Namespace,
+    SymbolKind::Variable,
+];
+
+/// One GRANT/REVOKE/DENY or ownership statement
+#[derive(Debug, PartialEq)]
+struct Permission {
+    permission: &'static str,
+    start: usize,
+    privileges: Vec<String>,
+    columns: Vec<String>,
+    object_type: Option<String>,
+    objects: Vec<NameRef>,
+    roles: Vec<NameRef>,
+    with_grant_option: bool,
+}
+
+pub(super) fn enrich_permissions(
+    ctx: &EnrichmentContext,
+    dialect: SqlDialect,
+    results: &mut ExtractionResults,
+) {
+    let code = code_mask(ctx.content, SQL);
+    let permissions = find_permissions(ctx.content, &code);
+    if permissions.is_empty() {
+        return;
+    }
+    let declarations = role_declarations(ctx.content, &code);
+
+    for statement in &permissions {
+        let line = ctx.line_of(statement.start);
+        for role in &statement.roles {
+            let role_id = role_symbol(ctx, dialect, results, &declarations, role);
+            for object in &statement.objects {
+                let mut entry = json!({
+                    "permission": statement.permission,
+                    "privileges": statement.privileges,
+                    "object": object.name,
+                    "objectType": statement.object_type,
+                    "line": line,
+                });
+                if !statement.columns.is_empty() {
+                    entry["columns"] = json!(statement.columns);
+                }
+                if statement.with_grant_option {
+                    entry["withGrantOption"] = Value::Bool(true);
+                }
+
+                let target_id = results
+                    .symbols
+                    .iter()
+                    .filter(|s| OBJECT_KINDS.contains(&s.kind) && !is_role(s))
+                    .find(|s| s.name.eq_ignore_ascii_case(&object.name))
+                    .map(|s| s.id.clone());
+                let mut identifier = ctx.create_identifier(
+                    &object.name,
+                    IdentifierKind::Call,
+                    object.start,
+                    object.end,
+                    Some(role_id.clone()),
+                );
+                identifier.target_symbol_id = target_id.clone();
+                results.identifiers.push(identifier);
+                if let Some(target_id) = target_id {
+                    let metadata: HashMap<String, Value> = entry
+                        .as_object()
+                        .into_iter()
+                        .flatten()
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect();
+                    results.relationships.push(ctx.create_relationship(
+                        &role_id,
+                        &target_id,
+                        RelationshipKind::Uses,
+                        statement.start,
+                        Some(metadata),
+                    ));
+                }
+
+                let role_symbol = results.symbols.iter_mut().find(|s| s.id == role_id);
+                append_metadata(role_symbol.unwrap(), "permissions", entry);
+            }
+        }
+    }
+}
+
+fn is_role(symbol: &Symbol) -> bool {
+    symbol
+        .metadata
+        .as_ref()
+        .and_then(|m| m.get("sqlRole"))
+        .is_some_and(|v| v == &Value::Bool(true))
+}
+
+/// ID of the role named `role`, creating its symbol on first use
+fn role_symbol(
+    ctx: &EnrichmentContext,
+    dialect: SqlDialect,
+    results: &mut ExtractionResults,
+    declarations: &HashMap<String, Range<usize>>,
+    role: &NameRef,
+) -> String {
+    let key = role.name.to_ascii_lowercase();
+    let declaration = declarations.get(&key);
+    let existing = results.symbols.iter_mut().find(|s| {
+        s.name.eq_ignore_ascii_case(&role.name)
+            && (is_role(s) || declaration.is_some_and(|d| d.contains(&(s.start_byte as usize))))
+    });
+    if let Some(symbol) = existing {
+        set_metadata(symbol, "sqlRole", true);
+        return symbol.id.clone();
+    }
+
+    let (start, end) = match declaration {
+        Some(statement) => (statement.start, statement.end),
+        None => (role.start, role.end),
+    };
+    let mut symbol = ctx.create_symbol(
+        &role.name,
+        SymbolKind::Variable,
+        start,
+        end,
+        SymbolOptions {
+            signature: declaration.map(|_| ctx.text(start, end).trim().to_string()),
+            ..Default::default()
+        },
+    );
+    set_metadata(&mut symbol, "sqlRole", true);
+    set_metadata(&mut symbol, "dialect", dialect.as_str());
+    if declaration.is_none() {
+        set_metadata(&mut symbol, "synthetic", true);
+    }
+    let id = symbol.id.clone();
+    results.symbols.push(symbol);
+    id
+}
+
+/// Lower-cased role name → span of its CREATE ROLE/USER/GROUP/LOGIN statement
+fn role_declarations(content: &str, code: &[bool]) -> HashMap<String, Range<usize>> {
+    let mut declarations = HashMap::new();
+    for captures in CREATE_ROLE_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        let name = captures.name("name").unwrap();
+        if !code[whole.start()] {
+            continue;
+        }
+        let Some(name) = last_part(name.as_str(), name.start()) else {
+            continue;
+        };
+        let end = statement_end(content, code, whole.end());
+        let end = whole.start() + content[whole.start()..end].trim_end().len();
+        declarations
+            .entry(name.name.to_ascii_lowercase())
+            .or_insert(whole.start()..end);
+    }
+    declarations
+}
+
+fn find_permissions(content: &str, code: &[bool]) -> Vec<Permission> {
+    let mut permissions = Vec::new();
+    for captures in PERMISSION_RE.captures_iter(content) {
+        let whole = captures.get(0).unwrap();
+        // `WITH GRANT OPTION` and `REVOKE GRANT OPTION FOR` are not statements
+        let before = content[..whole.start()].trim_end();
+        if !code[whole.start()] || before.to_ascii_uppercase().ends_with("WITH") {
+            continue;
+        }
+        if before.to_ascii_uppercase().ends_with("REVOKE") {
+            continue;
+        }
+        let end = statement_end(content, code, whole.end());
+        permissions.extend(parse_permission(content, &captures[1], whole.range(), end));
+    }
+
+    for (re, default_type) in [(&*OWNER_RE, None), (&*AUTHORIZATION_RE, Some("object"))] {
+        for captures in re.captures_iter(content) {
+            let whole = captures.get(0).unwrap();
+            if !code[whole.start()] {
+                continue;
+            }
+            let object = captures.name("object").unwrap();
+            let owner = captures.name("owner").unwrap();
+            let (Some(object), Some(owner)) = (
+                last_part(object.as_str(), object.start()),
+                last_part(owner.as_str(), owner.start()),
+            ) else {
+                continue;
+            };
+            let object_type = captures
+                .name("type")
+                .map(|t| collapse_upper(t.as_str()).to_ascii_lowercase())
+                .or(default_type.map(str::to_string));
+            permissions.push(Permission {
+                permission: "owner",
+                start: whole.start(),
+                privileges: Vec::new(),
+                columns: Vec::new(),
+                object_type,
+                objects: vec![object],
+                roles: vec![owner],
+                with_grant_option: false,
+            });
+        }
+    }
+    permissions.sort_by_key(|p| p.start);
+    permissions
+}
+
+/// The statement from GRANT/REVOKE/DENY (`keyword`) to `end`; None without an ON clause
+fn parse_permission(
+    content: &str,
+    verb: &str,
+    keyword: Range<usize>,
+    end: usize,
+) -> Option<Permission> {
+    let from = keyword.end;
+    let body = &content[from..end];
+    let on = ON_RE.find(body)?;
+    let (target_re, permission) = match verb.to_ascii_uppercase().as_str() {
+        "GRANT" => (&*TO_RE, "grant"),
+        "DENY" => (&*TO_RE, "deny"),
+        _ => (&*FROM_RE, "revoke"),
+    };
+    let target = target_re.find_at(body, on.end())?;
+
+    let privilege_text = GRANT_OPTION_RE.replace(&body[..on.start()], "");
+    let mut privileges = Vec::new();
+    let mut columns = Vec::new();
+    for (_, privilege) in split_top_level(&privilege_text, b',', SQL) {
+        let (name, column_list) = match privilege.find('(') {
+            Some(open) => (&privilege[..open], Some(&privilege[open..])),
+            None => (privilege, None),
+        };
+        let name = collapse_upper(name);
+        if name.is_empty() {
+            continue;
+        }
+        privileges.push(if name == "ALL PRIVILEGES" {
+            "ALL".to_string()
+        } else {
+            name
+        });
+        if let Some(list) = column_list {
+            let inner = list.trim().trim_start_matches('(').trim_end_matches(')');
+            for column in inner.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+                if !columns.iter().any(|c: &String| c == column) {
+                    columns.push(column.to_string());
+                }
+            }
+        }
+    }
+
+    let object_start = from + on.end();
+    let object_text = &content[object_start..from + target.start()];
+    let (object_type, names_at) = match OBJECT_TYPE_RE.captures(object_text) {
+        Some(captures) => {
+            let keyword = captures
+                .get(1)
+                .or(captures.get(2))
+                .or(captures.get(3))
+                .unwrap();
+            (
+                Some(keyword.as_str().to_ascii_lowercase()),
+                captures.get(0).unwrap().end(),
+            )
+        }
+        None => (None, 0),
+    };
+    let objects = name_list(&object_text[names_at..], object_start + names_at);
+
+    let grantee_start = from + target.end();
+    let grantee_text = &content[grantee_start..end];
+    let grantee_text = GRANTEES_END_RE
+        .find(grantee_text)
+        .map_or(grantee_text, |m| &grantee_text[..m.start()]);
+    let roles = name_list(grantee_text, grantee_start);
+
+    if objects.is_empty() || roles.is_empty() {
+        return None;
+    }
+    Some(Permission {
+        permission,
+        start: keyword.start,
+        privileges,
+        columns,
+        object_type,
+        objects,
+        roles,
+        with_grant_option: GRANT_OPTION_RE.is_match(body),
+    })
+}
+
+/// Comma-separated names (offset absolute), dropping argument lists and
+/// Postgres's `GROUP`/`ROLE` prefixes
+fn name_list(text: &str, offset: usize) -> Vec<NameRef> {
+    split_top_level(text, b',', SQL)
+        .into_iter()
+        .filter_map(|(at, segment)| {
+            let segment = segment.split('(').next().unwrap_or(segment);
+            let (start, segment) = trim_with_offset(offset + at, segment);
+            let (start, segment) = match segment.split_once(char::is_whitespace) {
+                Some((prefix, rest))
+                    if prefix.eq_ignore_ascii_case("GROUP")
+                        || prefix.eq_ignore_ascii_case("ROLE") =>
+                {
+                    trim_with_offset(start + prefix.len(), rest)
+                }
+                _ => (start, segment),
+            };
+            last_part(segment, start)
+        })
+        .collect()
+}
+
+/// Upper-cased with whitespace runs collapsed: `select` → "SELECT"
+fn collapse_upper(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    fn run(content: &str, symbols: Vec<Symbol>) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("db/grants.sql", "sql", content, Path::new("."));
+        let mut results = test_results(symbols);
+        enrich_permissions(&ctx, SqlDialect::Postgres, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_grants_link_roles_to_local_objects() {
+        let content = "CREATE TABLE users (id INT);\nCREATE ROLE reporting_role;\nGRANT SELECT, UPDATE (email, name) ON TABLE users, audit_log TO reporting_role, GROUP admins WITH GRANT OPTION;\nREVOKE GRANT OPTION FOR all privileges ON users FROM admins;\n-- GRANT DELETE ON users TO nobody;\nGRANT reporting_role TO alice;\n";
+        let ctx = EnrichmentContext::new("db/grants.sql", "sql", content, Path::new("."));
+        let users = ctx.create_symbol("users", SymbolKind::Class, 0, 27, SymbolOptions::default());
+        let results = run(content, vec![users.clone()]);
+
+        let roles: Vec<(&str, bool)> = results.symbols[1..]
+            .iter()
+            .map(|s| {
+                let synthetic = s.metadata.as_ref().unwrap().contains_key("synthetic");
+                (s.name.as_str(), synthetic)
+            })
+            .collect();
+        assert_eq!(roles, vec![("reporting_role", false), ("admins", true)]);
+        let reporting = &results.symbols[1];
+        assert_eq!(reporting.start_line, 2);
+        assert_eq!(
+            reporting.signature.as_deref(),
+            Some("CREATE ROLE reporting_role")
+        );
+
+        let grants = &reporting.metadata.as_ref().unwrap()["permissions"];
+        assert_eq!(
+            grants[0],
+            json!({
+                "permission": "grant",
+                "privileges": ["SELECT", "UPDATE"],
+                "columns": ["email", "name"],
+                "object": "users",
+                "objectType": "table",
+                "withGrantOption": true,
+                "line": 3,
+            })
+        );
+        assert_eq!(grants[1]["object"], Value::from("audit_log"));
+
+        let admins = results.symbols[2].metadata.as_ref().unwrap();
+        assert_eq!(
+            admins["permissions"][2]["permission"],
+            Value::from("revoke")
+        );
+        assert_eq!(admins["permissions"][2]["privileges"], json!(["ALL"]));
+
+        // audit_log is not declared here: identifier only, no relationship
+        let links: Vec<(&str, &str)> = results
+            .relationships
+            .iter()
+            .map(|r| (r.from_symbol_id.as_str(), r.to_symbol_id.as_str()))
+            .collect();
+        assert_eq!(
+            links,
+            vec![
+                (reporting.id.as_str(), users.id.as_str()),
+                (results.symbols[2].id.as_str(), users.id.as_str()),
+                (results.symbols[2].id.as_str(), users.id.as_str()),
+            ]
+        );
+        assert!(results.identifiers.iter().any(|i| i.name == "audit_log"
+            && i.target_symbol_id.is_none()
+            && &content[i.start_byte as usize..i.end_byte as usize] == "audit_log"));
+    }
+
+    #[test]
+    fn test_ownership_statements() {
+        let content = "ALTER TABLE public.orders OWNER TO app_owner;\nALTER FUNCTION total(int) OWNER TO app_owner;\nALTER AUTHORIZATION ON OBJECT::dbo.Orders TO [db_owner];\nDENY DELETE ON OBJECT::dbo.Orders TO [intern];\n";
+        let results = run(content, Vec::new());
+
+        let names: Vec<&str> = results.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["app_owner", "db_owner", "intern"]);
+        let owner = results.symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(
+            owner["permissions"],
+            json!([
+                {"permission": "owner", "privileges": [], "object": "orders", "objectType": "table", "line": 1},
+                {"permission": "owner", "privileges": [], "object": "total", "objectType": "function", "line": 2},
+            ])
+        );
+        let db_owner = &results.symbols[1].metadata.as_ref().unwrap()["permissions"][0];
+        assert_eq!(db_owner["objectType"], Value::from("object"));
+        let intern = &results.symbols[2].metadata.as_ref().unwrap()["permissions"][0];
+        assert_eq!(intern["permission"], Value::from("deny"));
+        assert_eq!(intern["object"], Value::from("Orders"));
+        assert_eq!(intern["objectType"], Value::from("object"));
+    }
+}
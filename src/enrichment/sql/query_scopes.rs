@@ -0,0 +1,387 @@
+// Query scopes for view columns
+//
+// Julie's extract_select_aliases parents every alias in a view's SELECT to the
+// view, so a CTE's computed column or a derived table's alias shows up as a
+// column of the view itself. This pass finds the nested query scopes inside
+// each view and reparents the Field symbols that start in them:
+//
+// - `WITH totals AS (SELECT sum(x) AS total ...)`: `total` moves under the
+//   CTE's symbol (Julie's when it extracted one, otherwise a synthesized
+//   Interface named after the CTE)
+// - `FROM (SELECT ... AS n) AS recent`: `n` moves under a synthesized
+//   Interface named after the derived table's alias ("subquery" when it has
+//   none, e.g. a scalar subquery in the select list)
+//
+// Scopes nest, so a subquery inside a CTE is parented to the CTE. Scope
+// symbols carry metadata.scope ("cte" or "subquery") and, for derived
+// tables, metadata.alias. Only top-level select-list aliases stay on the view.
+
+use super::dml::PART;
+use super::SQL;
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, set_metadata, EnrichmentContext, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+static WITH_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\bWITH(?:\s+RECURSIVE)?\s").unwrap());
+
+/// `name [(columns)] AS [[NOT] MATERIALIZED] (` at the start of the text
+static CTE_HEAD_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\A\s*(?P<name>{PART})\s*(?:\([^()]*\)\s*)?AS\s+(?:NOT\s+)?(?:MATERIALIZED\s+)?\("
+    ))
+    .unwrap()
+});
+
+static SUBQUERY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\(\s*SELECT\b").unwrap());
+
+/// `[AS] alias` after a derived table's closing parenthesis
+static ALIAS_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"(?i)\A\s*(?:AS\s+)?(?P<alias>{PART})")).unwrap());
+
+/// Keyword before `(SELECT` that makes it a derived table
+static DERIVED_TABLE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(?:FROM|JOIN|APPLY)\s*$").unwrap());
+
+/// Words that can follow a derived table's `)` without being its alias
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "WHERE",
+    "ON",
+    "USING",
+    "JOIN",
+    "INNER",
+    "LEFT",
+    "RIGHT",
+    "FULL",
+    "CROSS",
+    "OUTER",
+    "NATURAL",
+    "GROUP",
+    "ORDER",
+    "HAVING",
+    "WINDOW",
+    "LIMIT",
+    "OFFSET",
+    "UNION",
+    "EXCEPT",
+    "INTERSECT",
+    "FETCH",
+    "FOR",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum ScopeKind {
+    Cte { name: String, name_start: usize },
+    Subquery { alias: Option<String> },
+}
+
+/// A parenthesized query: `open` and `close` are the parenthesis bytes
+#[derive(Debug, Clone)]
+struct Scope {
+    open: usize,
+    close: usize,
+    kind: ScopeKind,
+}
+
+impl Scope {
+    fn contains(&self, byte: usize) -> bool {
+        self.open < byte && byte < self.close
+    }
+}
+
+pub(super) fn nest_query_aliases(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let views: HashMap<String, (usize, usize)> = symbols
+        .iter()
+        .filter(|s| s.kind == SymbolKind::Interface)
+        .map(|s| (s.id.clone(), (s.start_byte as usize, s.end_byte as usize)))
+        .collect();
+    if views.is_empty() {
+        return;
+    }
+    let scopes = find_scopes(ctx.content);
+    if scopes.is_empty() {
+        return;
+    }
+
+    let mut scope_ids: Vec<Option<String>> = vec![None; scopes.len()];
+    for index in 0..symbols.len() {
+        let symbol = &symbols[index];
+        if symbol.kind != SymbolKind::Field {
+            continue;
+        }
+        let Some(view_id) = symbol.parent_id.clone() else {
+            continue;
+        };
+        let Some(&view_span) = views.get(&view_id) else {
+            continue;
+        };
+        let start = symbol.start_byte as usize;
+        let Some(scope) = innermost_scope(&scopes, view_span, |s| s.contains(start)) else {
+            continue;
+        };
+        let parent = scope_symbol(
+            ctx,
+            symbols,
+            &scopes,
+            &mut scope_ids,
+            scope,
+            &view_id,
+            view_span,
+        );
+        symbols[index].parent_id = Some(parent);
+    }
+}
+
+/// Id of the symbol standing for `scopes[index]`, finding or creating it (and its enclosing scopes)
+fn scope_symbol(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    scopes: &[Scope],
+    scope_ids: &mut [Option<String>],
+    index: usize,
+    view_id: &str,
+    view_span: (usize, usize),
+) -> String {
+    if let Some(id) = &scope_ids[index] {
+        return id.clone();
+    }
+    let scope = &scopes[index];
+    let parent = match innermost_scope(scopes, view_span, |outer| {
+        outer.open < scope.open && scope.close < outer.close
+    }) {
+        Some(outer) => scope_symbol(ctx, symbols, scopes, scope_ids, outer, view_id, view_span),
+        None => view_id.to_string(),
+    };
+
+    let id = match &scope.kind {
+        ScopeKind::Cte { name, name_start } => {
+            let existing = symbols.iter().position(|s| {
+                s.kind != SymbolKind::Field
+                    && (*name_start..=scope.open).contains(&(s.start_byte as usize))
+                    && unquoted(&s.name).eq_ignore_ascii_case(unquoted(name))
+            });
+            let position = existing.unwrap_or_else(|| {
+                symbols.push(ctx.create_symbol(
+                    unquoted(name),
+                    SymbolKind::Interface,
+                    *name_start,
+                    scope.close + 1,
+                    SymbolOptions {
+                        metadata: Some(HashMap::from([("synthetic".to_string(), json!(true))])),
+                        ..Default::default()
+                    },
+                ));
+                symbols.len() - 1
+            });
+            let cte = &mut symbols[position];
+            cte.parent_id.get_or_insert(parent);
+            set_metadata(cte, "scope", "cte");
+            cte.id.clone()
+        }
+        ScopeKind::Subquery { alias } => {
+            let mut metadata = HashMap::from([
+                ("scope".to_string(), json!("subquery")),
+                ("synthetic".to_string(), json!(true)),
+            ]);
+            if let Some(alias) = alias {
+                metadata.insert("alias".to_string(), json!(alias));
+            }
+            let subquery = ctx.create_symbol(
+                alias.as_deref().unwrap_or("subquery"),
+                SymbolKind::Interface,
+                scope.open,
+                scope.close + 1,
+                SymbolOptions {
+                    parent_id: Some(parent),
+                    metadata: Some(metadata),
+                    ..Default::default()
+                },
+            );
+            let id = subquery.id.clone();
+            symbols.push(subquery);
+            id
+        }
+    };
+    scope_ids[index] = Some(id.clone());
+    id
+}
+
+/// Index of the smallest scope inside `view_span` that satisfies `accept`
+fn innermost_scope(
+    scopes: &[Scope],
+    view_span: (usize, usize),
+    accept: impl Fn(&Scope) -> bool,
+) -> Option<usize> {
+    scopes
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| view_span.0 <= s.open && s.close < view_span.1 && accept(s))
+        .min_by_key(|(_, s)| s.close - s.open)
+        .map(|(index, _)| index)
+}
+
+/// Every CTE body and parenthesized SELECT in the content
+fn find_scopes(content: &str) -> Vec<Scope> {
+    let code = code_mask(content, SQL);
+    let mut scopes = Vec::new();
+
+    for with in WITH_RE.find_iter(content) {
+        if !code[with.start()] {
+            continue;
+        }
+        let mut position = with.end();
+        while let Some(head) = CTE_HEAD_RE.captures(&content[position..]) {
+            let name = head.name("name").unwrap();
+            let open = position + head.get(0).unwrap().end() - 1;
+            let Some(close) = find_matching_delimiter(content, open, SQL) else {
+                break;
+            };
+            scopes.push(Scope {
+                open,
+                close,
+                kind: ScopeKind::Cte {
+                    name: name.as_str().to_string(),
+                    name_start: position + name.start(),
+                },
+            });
+            let rest = &content[close + 1..];
+            match rest.trim_start().strip_prefix(',') {
+                Some(after) => position = content.len() - after.len(),
+                None => break,
+            }
+        }
+    }
+
+    for subquery in SUBQUERY_RE.find_iter(content) {
+        let open = subquery.start();
+        if !code[open] || scopes.iter().any(|s| s.open == open) {
+            continue;
+        }
+        let Some(close) = find_matching_delimiter(content, open, SQL) else {
+            continue;
+        };
+        let alias = DERIVED_TABLE_RE
+            .is_match(&content[..open])
+            .then(|| ALIAS_RE.captures(&content[close + 1..]))
+            .flatten()
+            .map(|caps| unquoted(&caps["alias"]).to_string())
+            .filter(|alias| {
+                !CLAUSE_KEYWORDS
+                    .iter()
+                    .any(|k| k.eq_ignore_ascii_case(alias))
+            });
+        scopes.push(Scope {
+            open,
+            close,
+            kind: ScopeKind::Subquery { alias },
+        });
+    }
+    scopes
+}
+
+/// `[name]`, `"name"`, or `` `name` `` without its quotes
+fn unquoted(name: &str) -> &str {
+    name.trim_matches(|c| matches!(c, '[' | ']' | '"' | '`'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const VIEW: &str = "CREATE VIEW order_summary AS\nWITH totals AS (\n  SELECT customer_id, SUM(amount) AS total_amount\n  FROM (SELECT customer_id, amount * rate AS amount FROM orders) AS converted\n  GROUP BY customer_id\n)\nSELECT customer_id, total_amount AS lifetime_value,\n  (SELECT MAX(placed_at) AS latest FROM orders o WHERE o.customer_id = totals.customer_id) AS last_order\nFROM totals;\n";
+
+    /// The view plus one Field per alias, all parented to the view as Julie does
+    fn view_symbols(ctx: &EnrichmentContext) -> Vec<Symbol> {
+        let view = ctx.create_symbol(
+            "order_summary",
+            SymbolKind::Interface,
+            0,
+            VIEW.len() - 1,
+            SymbolOptions::default(),
+        );
+        let mut symbols = vec![view];
+        for alias in [
+            "total_amount",
+            "amount FROM",
+            "lifetime_value",
+            "latest",
+            "last_order",
+        ] {
+            let name = alias.split(' ').next().unwrap();
+            let start = VIEW.find(&format!("AS {}", alias)).unwrap() + 3;
+            symbols.push(ctx.create_symbol(
+                name,
+                SymbolKind::Field,
+                start,
+                start + name.len(),
+                SymbolOptions {
+                    parent_id: Some(symbols[0].id.clone()),
+                    ..Default::default()
+                },
+            ));
+        }
+        symbols
+    }
+
+    fn parent_name<'a>(symbols: &'a [Symbol], name: &str) -> &'a str {
+        let symbol = symbols.iter().find(|s| s.name == name).unwrap();
+        let parent = symbol.parent_id.as_deref().unwrap();
+        &symbols.iter().find(|s| s.id == parent).unwrap().name
+    }
+
+    #[test]
+    fn test_view_over_cte_nests_aliases_by_query_scope() {
+        let ctx = EnrichmentContext::new("views.sql", "sql", VIEW, Path::new("."));
+        let mut symbols = view_symbols(&ctx);
+
+        nest_query_aliases(&ctx, &mut symbols);
+
+        assert_eq!(parent_name(&symbols, "total_amount"), "totals");
+        assert_eq!(parent_name(&symbols, "amount"), "converted");
+        assert_eq!(parent_name(&symbols, "converted"), "totals");
+        assert_eq!(parent_name(&symbols, "totals"), "order_summary");
+        assert_eq!(parent_name(&symbols, "lifetime_value"), "order_summary");
+        assert_eq!(parent_name(&symbols, "last_order"), "order_summary");
+        assert_eq!(parent_name(&symbols, "latest"), "subquery");
+
+        let converted = symbols.iter().find(|s| s.name == "converted").unwrap();
+        let metadata = converted.metadata.as_ref().unwrap();
+        assert_eq!(metadata["scope"], "subquery");
+        assert_eq!(metadata["alias"], "converted");
+    }
+
+    #[test]
+    fn test_existing_cte_symbol_is_reused() {
+        let ctx = EnrichmentContext::new("views.sql", "sql", VIEW, Path::new("."));
+        let mut symbols = view_symbols(&ctx);
+        let cte_start = VIEW.find("totals AS").unwrap();
+        let cte_end = VIEW.find("\n)\n").unwrap() + 2;
+        let view_id = symbols[0].id.clone();
+        symbols.push(ctx.create_symbol(
+            "totals",
+            SymbolKind::Interface,
+            cte_start,
+            cte_end,
+            SymbolOptions {
+                parent_id: Some(view_id),
+                ..Default::default()
+            },
+        ));
+        let before = symbols.len();
+
+        nest_query_aliases(&ctx, &mut symbols);
+
+        // Only the derived table and the scalar subquery were synthesized
+        assert_eq!(symbols.len(), before + 2);
+        let totals: Vec<&Symbol> = symbols.iter().filter(|s| s.name == "totals").collect();
+        assert_eq!(totals.len(), 1);
+        assert_eq!(totals[0].metadata.as_ref().unwrap()["scope"], "cte");
+        assert_eq!(parent_name(&symbols, "total_amount"), "totals");
+    }
+}
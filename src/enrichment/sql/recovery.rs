@@ -0,0 +1,160 @@
+// SQL CREATE statements inside parse errors
+//
+// Runs one recovery rule per object type over code no symbol covers. Names may
+// be schema-qualified and quoted (`[dbo].[Users]`, `"public"."users"`); the
+// symbol is named after the last component, as Julie names parsed objects.
+//
+// In T-SQL scripts GO separates batches: regions are split at GO lines so one
+// broken batch cannot swallow the next, and a recovered procedure, function,
+// or trigger spans the rest of its batch, since T-SQL bodies have no
+// terminator of their own.
+
+use super::dialect::go_batches;
+use super::{SqlDialect, SQL};
+use crate::enrichment::base::error_recovery::{recover_symbols, unclaimed_regions, RecoveryRule};
+use crate::enrichment::base::EnrichmentContext;
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `CREATE [OR REPLACE|OR ALTER] [modifiers] <object> [IF NOT EXISTS] [schema.]name`
+fn create_pattern(object: &str) -> Regex {
+    Regex::new(&format!(
+        r#"(?i)\bCREATE\s+(?:OR\s+(?:REPLACE|ALTER)\s+)?(?:(?:TEMP|TEMPORARY|UNIQUE|MATERIALIZED|DEFINER\s*=\s*\S+)\s+)*{object}\s+(?:IF\s+NOT\s+EXISTS\s+)?(?:[\w"\[\]`]+\.)*["\[`]?(?P<name>\w+)["\]`]?"#
+    ))
+    .unwrap()
+}
+
+static TABLE_RE: LazyLock<Regex> = LazyLock::new(|| create_pattern("TABLE"));
+static VIEW_RE: LazyLock<Regex> = LazyLock::new(|| create_pattern("VIEW"));
+static FUNCTION_RE: LazyLock<Regex> = LazyLock::new(|| create_pattern("FUNCTION"));
+static PROCEDURE_RE: LazyLock<Regex> = LazyLock::new(|| create_pattern("PROC(?:EDURE)?"));
+static TRIGGER_RE: LazyLock<Regex> = LazyLock::new(|| create_pattern("TRIGGER"));
+static INDEX_RE: LazyLock<Regex> = LazyLock::new(|| create_pattern("INDEX"));
+static SCHEMA_RE: LazyLock<Regex> = LazyLock::new(|| create_pattern("SCHEMA"));
+static SEQUENCE_RE: LazyLock<Regex> = LazyLock::new(|| create_pattern("SEQUENCE"));
+
+static RULES: [RecoveryRule; 8] = [
+    RecoveryRule {
+        pattern: &TABLE_RE,
+        kind: SymbolKind::Class,
+    },
+    RecoveryRule {
+        pattern: &VIEW_RE,
+        kind: SymbolKind::Interface,
+    },
+    RecoveryRule {
+        pattern: &FUNCTION_RE,
+        kind: SymbolKind::Function,
+    },
+    RecoveryRule {
+        pattern: &PROCEDURE_RE,
+        kind: SymbolKind::Function,
+    },
+    RecoveryRule {
+        pattern: &TRIGGER_RE,
+        kind: SymbolKind::Method,
+    },
+    RecoveryRule {
+        pattern: &INDEX_RE,
+        kind: SymbolKind::Property,
+    },
+    RecoveryRule {
+        pattern: &SCHEMA_RE,
+        kind: SymbolKind::Namespace,
+    },
+    RecoveryRule {
+        pattern: &SEQUENCE_RE,
+        kind: SymbolKind::Variable,
+    },
+];
+
+pub(super) fn recover_create_statements(
+    ctx: &EnrichmentContext,
+    dialect: SqlDialect,
+    symbols: &mut Vec<Symbol>,
+) {
+    let batches = match dialect {
+        SqlDialect::Tsql => go_batches(ctx.content),
+        _ => {
+            let whole = 0..ctx.content.len();
+            vec![whole]
+        }
+    };
+
+    let mut recovered = Vec::new();
+    for region in unclaimed_regions(ctx, symbols, SQL) {
+        for batch in &batches {
+            let (start, end) = (region.start.max(batch.start), region.end.min(batch.end));
+            if start >= end {
+                continue;
+            }
+            let mut batch_symbols = recover_symbols(ctx, symbols, start..end, &RULES, SQL);
+            if dialect == SqlDialect::Tsql {
+                let batch_end = batch.start + ctx.content[batch.clone()].trim_end().len();
+                for symbol in batch_symbols
+                    .iter_mut()
+                    .filter(|s| matches!(s.kind, SymbolKind::Function | SymbolKind::Method))
+                {
+                    let end = batch_end.max(symbol.end_byte as usize);
+                    let (end_line, end_column) = ctx.position(end);
+                    symbol.end_byte = end as u32;
+                    symbol.end_line = end_line;
+                    symbol.end_column = end_column;
+                }
+            }
+            recovered.extend(batch_symbols);
+        }
+    }
+    symbols.extend(recovered);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    #[test]
+    fn test_recovers_tsql_objects_with_correct_lines() {
+        let content = "-- CREATE TABLE commented (x int)\nGO\nCREATE TABLE [dbo].[Users] (\n  Id INT\n);\nGO\nCREATE OR ALTER PROCEDURE dbo.GetUser @Id INT AS SELECT 1;\n";
+        let ctx = EnrichmentContext::new("schema.sql", "sql", content, Path::new("."));
+        let mut symbols = Vec::new();
+        recover_create_statements(&ctx, SqlDialect::Tsql, &mut symbols);
+
+        let summary: Vec<(&str, SymbolKind, u32)> = symbols
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind.clone(), s.start_line))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Users", SymbolKind::Class, 3),
+                ("GetUser", SymbolKind::Function, 7)
+            ]
+        );
+        assert_eq!(
+            ctx.symbol_text(&symbols[0]),
+            "CREATE TABLE [dbo].[Users] (\n  Id INT\n)"
+        );
+        assert_eq!(
+            ctx.symbol_text(&symbols[1]),
+            "CREATE OR ALTER PROCEDURE dbo.GetUser @Id INT AS SELECT 1;"
+        );
+    }
+
+    #[test]
+    fn test_parsed_statements_are_left_alone() {
+        let content = "CREATE VIEW active_users AS SELECT 1;\n";
+        let ctx = EnrichmentContext::new("views.sql", "sql", content, Path::new("."));
+        let mut symbols = vec![ctx.create_symbol(
+            "active_users",
+            SymbolKind::Interface,
+            0,
+            content.len() - 1,
+            SymbolOptions::default(),
+        )];
+        recover_create_statements(&ctx, SqlDialect::Generic, &mut symbols);
+        assert_eq!(symbols.len(), 1);
+    }
+}
@@ -0,0 +1,259 @@
+// SQL temporary tables and table variables
+//
+// Stored procedures stage data in `CREATE TABLE #work (...)` (T-SQL local and
+// `##global` temp tables), `CREATE TEMP[ORARY] TABLE` (PostgreSQL, MySQL,
+// SQLite), and T-SQL `DECLARE @rows TABLE (...)`. Julie's SQL extractor skips
+// the T-SQL forms, and inside procedure bodies they usually sit in ERROR nodes
+// that the recovery pass does not search, so this pass scans the code itself.
+//
+// Each declaration becomes a Class symbol (or marks the one Julie or recovery
+// already produced) with metadata.isTemporary or metadata.isTableVariable,
+// parented to the procedure, function, or trigger declaring it, and each
+// column definition becomes a Field child. Names keep their `#`/`@` sigil, as
+// the statements that use them write it, so the DML pass links those uses to
+// the declaration.
+
+use super::dml::{last_part, PART};
+use super::SQL;
+use crate::enrichment::base::{
+    code_mask, find_matching_delimiter, innermost_symbol_at, set_metadata, split_top_level,
+    trim_with_offset, EnrichmentContext, SymbolOptions,
+};
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+static TEMP_TABLE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?i)\bCREATE\s+(?:(?:GLOBAL|LOCAL)\s+)?(?P<temp>(?:TEMP|TEMPORARY)\s+)?TABLE\s+(?:IF\s+NOT\s+EXISTS\s+)?(?P<name>{PART}(?:\s*\.\s*{PART})*)\s*\("
+    ))
+    .unwrap()
+});
+
+static TABLE_VARIABLE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\bDECLARE\s+(?P<name>@[\w$#@]+)\s+(?:AS\s+)?TABLE\s*\(").unwrap()
+});
+
+/// Words opening a table constraint rather than a column definition
+const CONSTRAINTS: &[&str] = &[
+    "CONSTRAINT",
+    "PRIMARY",
+    "FOREIGN",
+    "UNIQUE",
+    "CHECK",
+    "INDEX",
+    "KEY",
+    "PERIOD",
+    "LIKE",
+    "EXCLUDE",
+];
+
+pub(super) fn extract_temp_tables(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    if !ctx.content.contains('#')
+        && !ctx.content.contains('@')
+        && !ctx.content.to_ascii_uppercase().contains("TEMP")
+    {
+        return;
+    }
+    let code = code_mask(ctx.content, SQL);
+
+    let declarations = TEMP_TABLE_RE
+        .captures_iter(ctx.content)
+        .map(|c| (c, "isTemporary"))
+        .chain(
+            TABLE_VARIABLE_RE
+                .captures_iter(ctx.content)
+                .map(|c| (c, "isTableVariable")),
+        );
+    for (captures, flag) in declarations {
+        let whole = captures.get(0).unwrap();
+        if !code[whole.start()] {
+            continue;
+        }
+        let name = captures.name("name").unwrap();
+        let Some(name_ref) = last_part(name.as_str(), name.start()) else {
+            continue;
+        };
+        if flag == "isTemporary"
+            && captures.name("temp").is_none()
+            && !name_ref.name.starts_with('#')
+        {
+            continue;
+        }
+        let open = whole.end() - 1;
+        let Some(close) = find_matching_delimiter(ctx.content, open, SQL) else {
+            continue;
+        };
+
+        let routine = innermost_symbol_at(symbols, whole.start(), |s| {
+            matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+        })
+        .map(|s| s.id.clone());
+        let table_index = match declared_table(ctx, symbols, &name_ref.name, whole.start()) {
+            Some(index) => index,
+            None => {
+                let signature = ctx.content[whole.start()..open]
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                symbols.push(ctx.create_symbol(
+                    &name_ref.name,
+                    SymbolKind::Class,
+                    whole.start(),
+                    close + 1,
+                    SymbolOptions {
+                        signature: Some(signature),
+                        ..Default::default()
+                    },
+                ));
+                symbols.len() - 1
+            }
+        };
+        let table = &mut symbols[table_index];
+        set_metadata(table, flag, true);
+        if routine.is_some() {
+            table.parent_id = routine;
+        }
+
+        let table_id = table.id.clone();
+        for (at, definition) in split_top_level(&ctx.content[open + 1..close], b',', SQL) {
+            let (start, definition) = trim_with_offset(open + 1 + at, definition);
+            let Some(column) = column_name(definition, start) else {
+                continue;
+            };
+            let exists = symbols.iter().any(|s| {
+                s.parent_id.as_deref() == Some(&table_id) && s.name.eq_ignore_ascii_case(&column)
+            });
+            if exists {
+                continue;
+            }
+            symbols.push(ctx.create_symbol(
+                &column,
+                SymbolKind::Field,
+                start,
+                start + definition.len(),
+                SymbolOptions {
+                    signature: Some(definition.split_whitespace().collect::<Vec<_>>().join(" ")),
+                    parent_id: Some(table_id.clone()),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+}
+
+/// The table symbol already extracted for a declaration at `start`
+fn declared_table(
+    ctx: &EnrichmentContext,
+    symbols: &[Symbol],
+    name: &str,
+    start: usize,
+) -> Option<usize> {
+    let line = ctx.line_of(start);
+    let bare = name.trim_start_matches(['#', '@']);
+    symbols.iter().position(|s| {
+        s.kind == SymbolKind::Class
+            && s.start_line == line
+            && (s.name.eq_ignore_ascii_case(name) || s.name.eq_ignore_ascii_case(bare))
+    })
+}
+
+/// Name of the column a definition declares, None for table constraints
+fn column_name(definition: &str, start: usize) -> Option<String> {
+    let first = definition.split_whitespace().next()?;
+    if CONSTRAINTS.iter().any(|c| c.eq_ignore_ascii_case(first)) {
+        return None;
+    }
+    let name = last_part(first, start)?;
+    Some(name.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::path::Path;
+
+    const PROCEDURE: &str = "CREATE PROCEDURE dbo.Rebuild AS\nBEGIN\n  CREATE TABLE #work (\n    id INT NOT NULL,\n    [total] DECIMAL(10, 2),\n    PRIMARY KEY (id)\n  );\n  DECLARE @changes TABLE (action NVARCHAR(10), id INT);\n  -- CREATE TABLE #commented (x INT)\n  CREATE TABLE orders (id INT);\nEND\n";
+
+    #[test]
+    fn test_temp_tables_and_table_variables_are_scoped_to_the_procedure() {
+        let ctx = EnrichmentContext::new("rebuild.sql", "sql", PROCEDURE, Path::new("."));
+        let procedure = ctx.create_symbol(
+            "Rebuild",
+            SymbolKind::Function,
+            0,
+            PROCEDURE.len() - 1,
+            SymbolOptions::default(),
+        );
+        let procedure_id = procedure.id.clone();
+        let mut symbols = vec![procedure];
+        extract_temp_tables(&ctx, &mut symbols);
+
+        let summary: Vec<(&str, SymbolKind, Option<&str>)> = symbols[1..]
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind.clone(), s.signature.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("#work", SymbolKind::Class, Some("CREATE TABLE #work")),
+                ("id", SymbolKind::Field, Some("id INT NOT NULL")),
+                ("total", SymbolKind::Field, Some("[total] DECIMAL(10, 2)")),
+                (
+                    "@changes",
+                    SymbolKind::Class,
+                    Some("DECLARE @changes TABLE")
+                ),
+                ("action", SymbolKind::Field, Some("action NVARCHAR(10)")),
+                ("id", SymbolKind::Field, Some("id INT")),
+            ]
+        );
+        let work = &symbols[1];
+        assert_eq!(work.parent_id.as_ref(), Some(&procedure_id));
+        assert_eq!(
+            work.metadata.as_ref().unwrap()["isTemporary"],
+            Value::Bool(true)
+        );
+        assert_eq!(symbols[2].parent_id.as_ref(), Some(&work.id));
+        let changes = &symbols[4];
+        assert_eq!(changes.parent_id.as_ref(), Some(&procedure_id));
+        assert_eq!(
+            changes.metadata.as_ref().unwrap()["isTableVariable"],
+            Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn test_parsed_temporary_tables_are_marked_in_place() {
+        let content = "CREATE TEMPORARY TABLE staging (id INT, name TEXT);\n";
+        let ctx = EnrichmentContext::new("staging.sql", "sql", content, Path::new("."));
+        let table = ctx.create_symbol(
+            "staging",
+            SymbolKind::Class,
+            0,
+            content.len() - 2,
+            SymbolOptions::default(),
+        );
+        let id = ctx.create_symbol(
+            "id",
+            SymbolKind::Field,
+            32,
+            38,
+            SymbolOptions {
+                parent_id: Some(table.id.clone()),
+                ..Default::default()
+            },
+        );
+        let mut symbols = vec![table, id];
+        extract_temp_tables(&ctx, &mut symbols);
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["staging", "id", "name"]);
+        assert_eq!(
+            symbols[0].metadata.as_ref().unwrap()["isTemporary"],
+            Value::Bool(true)
+        );
+        assert_eq!(symbols[0].parent_id, None);
+    }
+}
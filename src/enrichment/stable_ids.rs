@@ -0,0 +1,251 @@
+// Stable symbol IDs - content-derived IDs that survive unrelated edits
+//
+// Julie's extractors (and EnrichmentContext::generate_id) derive a symbol's
+// ID from its line and column, so inserting a function at the top of a file
+// changes the ID of every symbol below it and invalidates incremental
+// indexes. As the last enrichment step every symbol is given a new ID hashed
+// from:
+//
+//     file path, kind, name, parent's (stable) ID, occurrence index
+//
+// where the occurrence index counts earlier siblings with the same parent,
+// kind, and name (overloads, redeclarations). The parent's ID carries the
+// whole qualified path, so `A.run` and `B.run` differ, as do the children of
+// two overloads. Every reference to an old ID in the file's results is
+// rewritten: parent_id, identifier containing/target IDs, relationship
+// endpoints, pending relationship sources, and top-level metadata strings
+// (e.g. localSymbolId). Relationship IDs themselves are left as Julie made them.
+//
+// Compatibility: IDs change once for every stored symbol when this pass is
+// first deployed; indexes built by earlier versions should be rebuilt rather
+// than merged. IDs are still 32 hex characters. A symbol keeps its ID when
+// lines move, and gets a new one when it is renamed, changes kind, moves to
+// another parent, or an earlier same-named sibling is added or removed.
+
+use julie_extractors::{ExtractionResults, Symbol};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Replace every symbol ID in the results with its content-derived ID
+pub fn assign_stable_ids(results: &mut ExtractionResults) {
+    let remap = stable_ids(&results.symbols);
+    if remap.is_empty() {
+        return;
+    }
+    let rewrite = |id: &mut String| {
+        if let Some(new_id) = remap.get(id.as_str()) {
+            *id = new_id.clone();
+        }
+    };
+
+    for symbol in &mut results.symbols {
+        rewrite(&mut symbol.id);
+        if let Some(parent_id) = symbol.parent_id.as_mut() {
+            rewrite(parent_id);
+        }
+        if let Some(metadata) = symbol.metadata.as_mut() {
+            rewrite_metadata(metadata, &remap);
+        }
+    }
+    for identifier in &mut results.identifiers {
+        if let Some(id) = identifier.containing_symbol_id.as_mut() {
+            rewrite(id);
+        }
+        if let Some(id) = identifier.target_symbol_id.as_mut() {
+            rewrite(id);
+        }
+    }
+    for relationship in &mut results.relationships {
+        rewrite(&mut relationship.from_symbol_id);
+        rewrite(&mut relationship.to_symbol_id);
+        if let Some(metadata) = relationship.metadata.as_mut() {
+            rewrite_metadata(metadata, &remap);
+        }
+    }
+    for pending in &mut results.pending_relationships {
+        rewrite(&mut pending.from_symbol_id);
+    }
+}
+
+/// Old ID → stable ID for every symbol (the first symbol wins a duplicated old ID)
+fn stable_ids(symbols: &[Symbol]) -> HashMap<String, String> {
+    let mut by_id: HashMap<&str, usize> = HashMap::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        by_id.entry(symbol.id.as_str()).or_insert(index);
+    }
+
+    // Parents before children, siblings in source order
+    let depths: Vec<usize> = (0..symbols.len())
+        .map(|index| depth(symbols, &by_id, index))
+        .collect();
+    let mut order: Vec<usize> = (0..symbols.len()).collect();
+    order.sort_by_key(|&index| (depths[index], symbols[index].start_byte, index));
+
+    let mut new_ids: Vec<Option<String>> = vec![None; symbols.len()];
+    let mut occurrences: HashMap<(String, String, &str), usize> = HashMap::new();
+    for index in order {
+        let symbol = &symbols[index];
+        // A parent outside this file's symbols keeps its ID as given
+        let parent = match symbol.parent_id.as_deref() {
+            Some(parent_id) => by_id
+                .get(parent_id)
+                .and_then(|&parent| new_ids[parent].clone())
+                .unwrap_or_else(|| parent_id.to_string()),
+            None => String::new(),
+        };
+        let kind = symbol.kind.to_string();
+        let occurrence = occurrences
+            .entry((parent.clone(), kind.clone(), symbol.name.as_str()))
+            .or_insert(0);
+        let key = format!(
+            "{}\0{}\0{}\0{}\0{}",
+            symbol.file_path, kind, symbol.name, parent, occurrence
+        );
+        *occurrence += 1;
+        new_ids[index] = Some(blake3::hash(key.as_bytes()).to_hex()[..32].to_string());
+    }
+
+    let mut remap = HashMap::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        if by_id.get(symbol.id.as_str()) == Some(&index) {
+            if let Some(new_id) = new_ids[index].take() {
+                remap.insert(symbol.id.clone(), new_id);
+            }
+        }
+    }
+    remap
+}
+
+/// Number of ancestors within the file, stopping at cycles
+fn depth(symbols: &[Symbol], by_id: &HashMap<&str, usize>, index: usize) -> usize {
+    let mut depth = 0;
+    let mut current = index;
+    while let Some(&parent) = symbols[current]
+        .parent_id
+        .as_deref()
+        .and_then(|parent_id| by_id.get(parent_id))
+    {
+        if depth >= symbols.len() {
+            break;
+        }
+        depth += 1;
+        current = parent;
+    }
+    depth
+}
+
+fn rewrite_metadata(metadata: &mut HashMap<String, Value>, remap: &HashMap<String, String>) {
+    for value in metadata.values_mut() {
+        if let Value::String(text) = value {
+            if let Some(new_id) = remap.get(text.as_str()) {
+                *text = new_id.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, EnrichmentContext, SymbolOptions};
+    use julie_extractors::{IdentifierKind, RelationshipKind, SymbolKind};
+    use std::path::Path;
+
+    /// Symbols for `class Service` with methods, positioned by their names in `content`
+    fn extract(content: &str) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("src/service.py", "python", content, Path::new("."));
+        let at = |name: &str| content.find(name).unwrap();
+        let class = ctx.create_symbol(
+            "Service",
+            SymbolKind::Class,
+            at("class Service"),
+            content.len(),
+            SymbolOptions::default(),
+        );
+        let mut symbols = vec![class.clone()];
+        for name in ["helper", "run"] {
+            if let Some(start) = content.find(&format!("def {}", name)) {
+                symbols.push(ctx.create_symbol(
+                    name,
+                    SymbolKind::Method,
+                    start,
+                    start + 10,
+                    SymbolOptions {
+                        parent_id: Some(class.id.clone()),
+                        ..Default::default()
+                    },
+                ));
+            }
+        }
+        let run = symbols.iter().find(|s| s.name == "run").unwrap().clone();
+        let call = at("self.run") + 5;
+        ExtractionResults {
+            identifiers: vec![ctx.create_identifier(
+                "run",
+                IdentifierKind::Call,
+                call,
+                call + 3,
+                Some(run.id.clone()),
+            )],
+            relationships: vec![ctx.create_relationship(
+                &class.id,
+                &run.id,
+                RelationshipKind::Contains,
+                call,
+                None,
+            )],
+            ..test_results(symbols)
+        }
+    }
+
+    fn symbol<'a>(results: &'a ExtractionResults, name: &str) -> &'a Symbol {
+        results.symbols.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_inserting_a_method_above_keeps_existing_ids() {
+        let before = "class Service:\n    def run(self):\n        self.run()\n";
+        let after = "class Service:\n    def helper(self):\n        pass\n\n    def run(self):\n        self.run()\n";
+        let mut old = extract(before);
+        let mut new = extract(after);
+        assert_ne!(symbol(&old, "run").id, symbol(&new, "run").id);
+
+        assign_stable_ids(&mut old);
+        assign_stable_ids(&mut new);
+
+        let (old_run, new_run) = (symbol(&old, "run"), symbol(&new, "run"));
+        assert_eq!(old_run.id, new_run.id);
+        assert_ne!(old_run.start_line, new_run.start_line);
+        assert_eq!(symbol(&old, "Service").id, symbol(&new, "Service").id);
+        assert_eq!(
+            new_run.parent_id.as_ref(),
+            Some(&symbol(&new, "Service").id)
+        );
+        assert_eq!(new_run.id.len(), 32);
+
+        // References follow the new IDs
+        assert_eq!(
+            new.identifiers[0].containing_symbol_id.as_ref(),
+            Some(&new_run.id)
+        );
+        assert_eq!(new.relationships[0].to_symbol_id, new_run.id);
+        assert_eq!(
+            new.relationships[0].from_symbol_id,
+            symbol(&new, "Service").id
+        );
+    }
+
+    #[test]
+    fn test_same_named_siblings_are_distinguished_by_occurrence() {
+        let content = "def f(): pass\ndef f(): pass\n";
+        let ctx = EnrichmentContext::new("a.py", "python", content, Path::new("."));
+        let mut results = test_results(vec![
+            ctx.create_symbol("f", SymbolKind::Function, 0, 13, SymbolOptions::default()),
+            ctx.create_symbol("f", SymbolKind::Function, 14, 27, SymbolOptions::default()),
+        ]);
+
+        assign_stable_ids(&mut results);
+
+        assert_ne!(results.symbols[0].id, results.symbols[1].id);
+    }
+}
@@ -0,0 +1,444 @@
+// Swift access control and declaration attributes
+//
+// Julie gives Swift symbols a coarse visibility that ignores the access level
+// written on the declaration, so a `fileprivate func` reads as public. This
+// pass parses the attributes and modifiers before every declaration and sets:
+//
+// - visibility from the access level: open and public are Public, fileprivate
+//   and private are Private, and internal and package (visible inside the
+//   module, not to importers) are Protected, the level in between.
+//   metadata.accessLevel keeps the exact keyword. Without one a declaration is
+//   internal; members of a private type are fileprivate, and enum cases,
+//   protocol requirements, and members of an extension written with a level
+//   take their container's. `private(set)` lands in metadata.setterAccess
+// - metadata.attributes, the attribute names, and the attributes as written
+//   prefixed to the signature
+// - metadata.objcExposed for @objc, @objcMembers (and the members of an
+//   @objcMembers class), and the attributes that imply @objc
+// - metadata.availability, the raw arguments of each @available, and
+//   metadata.deprecated when one of them deprecates, obsoletes, or marks the
+//   declaration unavailable
+// - metadata.actorIsolation: a global actor attribute (@MainActor) or
+//   "nonisolated"; members of an isolated type inherit it
+//
+// Symbols inside function bodies (locals, nested functions) are left alone.
+
+use super::SWIFT;
+use crate::enrichment::base::{code_mask, set_metadata, EnrichmentContext};
+use julie_extractors::{Symbol, SymbolKind, Visibility};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Attribute arguments, allowing one level of nested parentheses
+const ARGUMENTS: &str = r"\((?:[^()\n]|\([^()\n]*\))*\)";
+
+/// Attributes and modifiers, then the declaration keyword and name
+static DECLARATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r"(?m)^[ \t]*((?:(?:@[\w.]+(?:{ARGUMENTS})?|(?:open|public|package|internal|fileprivate|private)(?:\(set\))?|static|class|final|override|mutating|nonmutating|nonisolated|convenience|required|dynamic|lazy|weak|unowned|optional|indirect|distributed|prefix|postfix|infix)\s+)*)(func|init|deinit|subscript|class|struct|enum|protocol|actor|extension|typealias|associatedtype|var|let|case)\b[ \t]*([A-Za-z_][\w.]*)?"
+    ))
+    .unwrap()
+});
+
+static ATTRIBUTE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(&format!(r"@([\w.]+)({ARGUMENTS})?")).unwrap());
+
+static ACCESS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(open|public|package|internal|fileprivate|private)(\(set\))?").unwrap()
+});
+
+/// Access levels from widest to narrowest
+const ACCESS_LEVELS: &[&str] = &[
+    "open",
+    "public",
+    "package",
+    "internal",
+    "fileprivate",
+    "private",
+];
+
+/// Attributes that expose a declaration to Objective-C
+const OBJC_ATTRIBUTES: &[&str] = &[
+    "objc",
+    "objcMembers",
+    "IBAction",
+    "IBOutlet",
+    "IBInspectable",
+    "NSManaged",
+    "GKInspectable",
+];
+
+/// `@available` arguments that retire a declaration
+static RETIRED_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(?:deprecated|obsoleted|unavailable)\b").unwrap());
+
+/// What the prefix of one declaration says
+#[derive(Debug, Default)]
+struct Declaration {
+    keyword: String,
+    access: Option<&'static str>,
+    setter_access: Option<&'static str>,
+    /// Attributes as written
+    attributes: Vec<String>,
+    names: Vec<String>,
+    availability: Vec<String>,
+    nonisolated: bool,
+}
+
+impl Declaration {
+    fn parse(prefix: &str, keyword: &str) -> Self {
+        let mut declaration = Declaration {
+            keyword: keyword.to_string(),
+            ..Default::default()
+        };
+        let mut words = prefix.to_string();
+        for captures in ATTRIBUTE_RE.captures_iter(prefix) {
+            let whole = captures.get(0).unwrap();
+            words.replace_range(whole.range(), &" ".repeat(whole.len()));
+            let name = captures[1].rsplit('.').next().unwrap_or(&captures[1]);
+            if name == "available" {
+                if let Some(arguments) = captures.get(2) {
+                    let arguments = arguments.as_str();
+                    declaration
+                        .availability
+                        .push(arguments[1..arguments.len() - 1].trim().to_string());
+                }
+            }
+            declaration.attributes.push(whole.as_str().to_string());
+            declaration.names.push(name.to_string());
+        }
+        for captures in ACCESS_RE.captures_iter(&words) {
+            let level = ACCESS_LEVELS.iter().find(|l| **l == &captures[1]).copied();
+            if captures.get(2).is_some() {
+                declaration.setter_access = level;
+            } else {
+                declaration.access = level;
+            }
+        }
+        declaration.nonisolated = words.split_whitespace().any(|w| w == "nonisolated");
+        declaration
+    }
+
+    fn actor(&self) -> Option<&str> {
+        self.names
+            .iter()
+            .find(|n| n.ends_with("Actor") && n.starts_with(char::is_uppercase))
+            .map(String::as_str)
+    }
+}
+
+/// What a type or extension passes on to its members
+#[derive(Debug, Clone)]
+struct Scope {
+    access: &'static str,
+    /// The access level was written on the declaration
+    explicit: bool,
+    extension: bool,
+    protocol: bool,
+    objc_members: bool,
+    actor: Option<String>,
+}
+
+pub(super) fn enrich_access(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    let code = code_mask(ctx.content, SWIFT);
+    let mut declarations: HashMap<usize, Declaration> = HashMap::new();
+    for captures in DECLARATION_RE.captures_iter(ctx.content) {
+        let keyword = captures.get(2).unwrap();
+        if !code[keyword.start()] {
+            continue;
+        }
+        let name = match captures.get(3) {
+            Some(name) if !matches!(keyword.as_str(), "init" | "deinit" | "subscript") => {
+                name.as_str().rsplit('.').next().unwrap_or(name.as_str())
+            }
+            _ => keyword.as_str(),
+        };
+        let at = captures.get(3).map_or(keyword.start(), |n| n.start());
+        let Some(index) = symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.name == name && contains(s, at))
+            .min_by_key(|(_, s)| s.end_byte - s.start_byte)
+            .map(|(index, _)| index)
+        else {
+            continue;
+        };
+        declarations
+            .entry(index)
+            .or_insert_with(|| Declaration::parse(&captures[1], keyword.as_str()));
+    }
+
+    let mut order: Vec<usize> = (0..symbols.len()).collect();
+    order.sort_by_key(|&i| {
+        (
+            symbols[i].start_byte,
+            std::cmp::Reverse(symbols[i].end_byte),
+        )
+    });
+    let mut scopes: HashMap<String, Option<Scope>> = HashMap::new();
+    for index in order {
+        let parent = symbols[index]
+            .parent_id
+            .as_ref()
+            .map(|id| scopes.get(id).cloned().flatten());
+        // Parented to something without a scope: a function body
+        if matches!(parent, Some(None)) {
+            scopes.insert(symbols[index].id.clone(), None);
+            continue;
+        }
+        let parent = parent.flatten();
+        let declaration = declarations.remove(&index).unwrap_or_default();
+        let symbol = &mut symbols[index];
+
+        let access = declaration
+            .access
+            .unwrap_or_else(|| default_access(symbol, parent.as_ref()));
+        symbol.visibility = Some(visibility(access));
+        set_metadata(symbol, "accessLevel", access);
+        if let Some(setter) = declaration.setter_access {
+            set_metadata(symbol, "setterAccess", setter);
+        }
+
+        if !declaration.attributes.is_empty() {
+            set_metadata(symbol, "attributes", declaration.names.clone());
+            if !symbol
+                .signature
+                .as_deref()
+                .is_some_and(|s| s.starts_with('@'))
+            {
+                let attributes = declaration.attributes.join(" ");
+                symbol.signature = Some(match symbol.signature.take() {
+                    Some(signature) => format!("{attributes} {signature}"),
+                    None => attributes,
+                });
+            }
+        }
+        let is_member = parent.is_some() && !is_type(&symbol.kind);
+        let objc = declaration
+            .names
+            .iter()
+            .any(|n| OBJC_ATTRIBUTES.contains(&n.as_str()))
+            || (is_member && parent.as_ref().is_some_and(|p| p.objc_members));
+        if objc {
+            set_metadata(symbol, "objcExposed", true);
+        }
+        if !declaration.availability.is_empty() {
+            if declaration
+                .availability
+                .iter()
+                .any(|a| RETIRED_RE.is_match(a))
+            {
+                set_metadata(symbol, "deprecated", true);
+            }
+            set_metadata(symbol, "availability", declaration.availability.clone());
+        }
+        let actor = if declaration.nonisolated {
+            Some("nonisolated".to_string())
+        } else {
+            declaration.actor().map(str::to_string).or_else(|| {
+                parent
+                    .as_ref()
+                    .filter(|_| is_member)
+                    .and_then(|p| p.actor.clone())
+            })
+        };
+        if let Some(actor) = &actor {
+            set_metadata(symbol, "actorIsolation", Value::from(actor.as_str()));
+        }
+
+        let scope = is_type(&symbol.kind).then(|| Scope {
+            access,
+            explicit: declaration.access.is_some(),
+            extension: declaration.keyword == "extension",
+            protocol: symbol.kind == SymbolKind::Interface,
+            objc_members: declaration.names.iter().any(|n| n == "objcMembers"),
+            actor: actor.filter(|a| a != "nonisolated"),
+        });
+        scopes.insert(symbol.id.clone(), scope);
+    }
+}
+
+/// Access level of a declaration that writes none
+fn default_access(symbol: &Symbol, parent: Option<&Scope>) -> &'static str {
+    let Some(parent) = parent else {
+        return "internal";
+    };
+    let inherits = symbol.kind == SymbolKind::EnumMember
+        || parent.protocol
+        || (parent.extension && parent.explicit);
+    match parent.access {
+        // An open type's members are not open unless they say so
+        "open" if inherits => "public",
+        access if inherits => access,
+        "private" | "fileprivate" => "fileprivate",
+        _ => "internal",
+    }
+}
+
+fn visibility(access: &str) -> Visibility {
+    match access {
+        "open" | "public" => Visibility::Public,
+        "fileprivate" | "private" => Visibility::Private,
+        _ => Visibility::Protected,
+    }
+}
+
+fn is_type(kind: &SymbolKind) -> bool {
+    matches!(
+        kind,
+        SymbolKind::Class | SymbolKind::Struct | SymbolKind::Enum | SymbolKind::Interface
+    )
+}
+
+fn contains(symbol: &Symbol, at: usize) -> bool {
+    (symbol.start_byte as usize) <= at && at < symbol.end_byte as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::SymbolOptions;
+    use std::path::Path;
+
+    /// Symbols spanning from `from` through the next `to`, parented by index
+    fn symbols(
+        ctx: &EnrichmentContext,
+        spans: &[(&str, SymbolKind, &str, &str, Option<usize>)],
+    ) -> Vec<Symbol> {
+        let mut symbols: Vec<Symbol> = Vec::new();
+        for (name, kind, from, to, parent) in spans {
+            let start = ctx.content.find(from).unwrap();
+            let end = ctx.content[start..].find(to).unwrap() + start + to.len();
+            let parent_id = parent.map(|p| symbols[p].id.clone());
+            symbols.push(ctx.create_symbol(
+                name,
+                kind.clone(),
+                start,
+                end,
+                SymbolOptions {
+                    signature: Some(ctx.text(start, end).lines().next().unwrap().to_string()),
+                    visibility: Some(Visibility::Public),
+                    parent_id,
+                    ..Default::default()
+                },
+            ));
+        }
+        symbols
+    }
+
+    fn access(symbols: &[Symbol]) -> Vec<(&str, Option<Visibility>, &str)> {
+        symbols
+            .iter()
+            .map(|s| {
+                let level = s
+                    .metadata
+                    .as_ref()
+                    .and_then(|m| m.get("accessLevel"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("-");
+                (s.name.as_str(), s.visibility.clone(), level)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_access_levels_map_to_visibility() {
+        let content = "public enum Mode {\n    case fast\n}\n\npublic struct Store {\n    private(set) var count = 0\n    fileprivate func reset() {\n        let local = 1\n    }\n    func load() {}\n}\n\npublic extension Store {\n    func save() {}\n}\n\nprivate class Cache {\n    func clear() {}\n}\n";
+        let ctx = EnrichmentContext::new("Store.swift", "swift", content, Path::new("."));
+        let mut symbols = symbols(
+            &ctx,
+            &[
+                ("Mode", SymbolKind::Enum, "public enum", "}", None),
+                ("fast", SymbolKind::EnumMember, "case fast", "fast", Some(0)),
+                ("Store", SymbolKind::Struct, "public struct", "{}\n}", None),
+                (
+                    "count",
+                    SymbolKind::Property,
+                    "private(set)",
+                    "= 0",
+                    Some(2),
+                ),
+                (
+                    "reset",
+                    SymbolKind::Method,
+                    "fileprivate",
+                    "1\n    }",
+                    Some(2),
+                ),
+                ("local", SymbolKind::Variable, "let local", "1", Some(4)),
+                ("load", SymbolKind::Method, "func load", "{}", Some(2)),
+                ("Store", SymbolKind::Class, "public extension", "}\n}", None),
+                ("save", SymbolKind::Method, "func save", "{}", Some(7)),
+                ("Cache", SymbolKind::Class, "private class", "}\n}", None),
+                ("clear", SymbolKind::Method, "func clear", "{}", Some(9)),
+            ],
+        );
+        enrich_access(&ctx, &mut symbols);
+
+        assert_eq!(
+            access(&symbols),
+            vec![
+                ("Mode", Some(Visibility::Public), "public"),
+                ("fast", Some(Visibility::Public), "public"),
+                ("Store", Some(Visibility::Public), "public"),
+                ("count", Some(Visibility::Protected), "internal"),
+                ("reset", Some(Visibility::Private), "fileprivate"),
+                // Inside a function body: left as Julie set it
+                ("local", Some(Visibility::Public), "-"),
+                ("load", Some(Visibility::Protected), "internal"),
+                ("Store", Some(Visibility::Public), "public"),
+                ("save", Some(Visibility::Public), "public"),
+                ("Cache", Some(Visibility::Private), "private"),
+                ("clear", Some(Visibility::Private), "fileprivate"),
+            ]
+        );
+        assert_eq!(
+            symbols[3].metadata.as_ref().unwrap()["setterAccess"],
+            Value::from("private")
+        );
+    }
+
+    #[test]
+    fn test_attributes_record_objc_availability_and_isolation() {
+        let content = "@MainActor\n@objcMembers\nclass ViewModel: NSObject {\n    @available(*, deprecated, message: \"Use reload()\")\n    func refresh() {}\n    nonisolated func hash() -> Int { 0 }\n}\n";
+        let ctx = EnrichmentContext::new("ViewModel.swift", "swift", content, Path::new("."));
+        let mut symbols = symbols(
+            &ctx,
+            &[
+                (
+                    "ViewModel",
+                    SymbolKind::Class,
+                    "class ViewModel",
+                    "0 }\n}",
+                    None,
+                ),
+                ("refresh", SymbolKind::Method, "func refresh", "{}", Some(0)),
+                ("hash", SymbolKind::Method, "nonisolated", "{ 0 }", Some(0)),
+            ],
+        );
+        enrich_access(&ctx, &mut symbols);
+
+        let metadata = |i: usize| symbols[i].metadata.clone().unwrap();
+        assert_eq!(
+            symbols[0].signature.as_deref(),
+            Some("@MainActor @objcMembers class ViewModel: NSObject {")
+        );
+        assert_eq!(
+            metadata(0)["attributes"],
+            serde_json::json!(["MainActor", "objcMembers"])
+        );
+        assert_eq!(metadata(0)["objcExposed"], Value::Bool(true));
+        assert_eq!(metadata(0)["actorIsolation"], Value::from("MainActor"));
+
+        assert_eq!(
+            metadata(1)["availability"],
+            serde_json::json!(["*, deprecated, message: \"Use reload()\""])
+        );
+        assert_eq!(metadata(1)["deprecated"], Value::Bool(true));
+        assert_eq!(metadata(1)["objcExposed"], Value::Bool(true));
+        assert_eq!(metadata(1)["actorIsolation"], Value::from("MainActor"));
+        assert_eq!(metadata(2)["actorIsolation"], Value::from("nonisolated"));
+    }
+}
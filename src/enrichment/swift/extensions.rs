@@ -0,0 +1,352 @@
+// Swift extension conformances
+//
+// `extension User: Codable {}` and `extension ViewController:
+// UITableViewDelegate { ... }` are where most Swift conformances live, but
+// Julie extracts an extension as a symbol of its own, without its inheritance
+// clause and with its members parented to it. For every extension this pass:
+//
+// - emits an Implements relationship from the extended type to each listed
+//   protocol declared in the same file; the extension symbol stands in for a
+//   type declared elsewhere. Conformances always land in metadata.conformances
+//   on the same symbol, so protocols from other modules are still recorded
+// - records a conditional conformance's where clause
+//   (`extension Array: Summable where Element: Numeric`) as whereClause in the
+//   relationship metadata and on the extension symbol
+// - parents the extension's direct members to the extended type when it is
+//   declared in the file, marking them with metadata.extension
+
+use super::SWIFT;
+use crate::enrichment::base::{
+    append_metadata, code_mask, collapse_whitespace, find_matching_delimiter, set_metadata,
+    split_top_level, EnrichmentContext,
+};
+use julie_extractors::{ExtractionResults, RelationshipKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `extension` after optional attributes and access modifiers, then the type
+static EXTENSION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^[ \t]*(?:(?:@\w+|public|private|fileprivate|internal|open|package)\s+)*(extension)\s+([A-Za-z_][\w.]*)")
+        .unwrap()
+});
+
+static WHERE_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bwhere\b").unwrap());
+
+/// Kinds an extension can extend
+const TYPE_KINDS: &[SymbolKind] = &[
+    SymbolKind::Class,
+    SymbolKind::Struct,
+    SymbolKind::Enum,
+    SymbolKind::Interface,
+    SymbolKind::Type,
+];
+
+/// A parsed `extension Type: P1, P2 where ... { ... }`
+#[derive(Debug, PartialEq)]
+struct Extension {
+    keyword: usize,
+    /// Extended type as written: `Array`, `Foo.Bar`
+    type_name: String,
+    protocols: Vec<String>,
+    where_clause: Option<String>,
+    /// Byte range of the body, braces included
+    body: (usize, usize),
+}
+
+impl Extension {
+    /// Last segment of a dotted type name
+    fn base_name(&self) -> &str {
+        self.type_name.rsplit('.').next().unwrap_or(&self.type_name)
+    }
+}
+
+pub(super) fn link_extensions(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    for extension in find_extensions(ctx.content) {
+        let (body_start, body_end) = extension.body;
+        let extension_id = results
+            .symbols
+            .iter()
+            .filter(|s| s.name == extension.base_name() && contains(s, extension.keyword))
+            .filter(|s| (s.end_byte as usize) >= body_end)
+            .max_by_key(|s| s.start_byte)
+            .map(|s| s.id.clone());
+        let type_id = results
+            .symbols
+            .iter()
+            .filter(|s| TYPE_KINDS.contains(&s.kind) && s.name == extension.base_name())
+            .find(|s| Some(&s.id) != extension_id.as_ref() && !contains(s, extension.keyword))
+            .map(|s| s.id.clone());
+        let Some(owner_id) = type_id.clone().or(extension_id.clone()) else {
+            continue;
+        };
+
+        if let Some(extension_symbol) = extension_id
+            .as_ref()
+            .and_then(|id| results.symbols.iter_mut().find(|s| &s.id == id))
+        {
+            set_metadata(extension_symbol, "extension", true);
+            if let Some(where_clause) = &extension.where_clause {
+                set_metadata(extension_symbol, "whereClause", where_clause.clone());
+            }
+        }
+
+        for protocol in &extension.protocols {
+            let owner = results
+                .symbols
+                .iter_mut()
+                .find(|s| s.id == owner_id)
+                .unwrap();
+            append_metadata(owner, "conformances", protocol.clone());
+
+            let base = protocol.rsplit('.').next().unwrap_or(protocol);
+            let Some(protocol_id) = results
+                .symbols
+                .iter()
+                .find(|s| s.kind == SymbolKind::Interface && s.name == base)
+                .map(|s| s.id.clone())
+            else {
+                continue;
+            };
+            let mut metadata = HashMap::new();
+            metadata.insert("extension".to_string(), Value::Bool(true));
+            if let Some(where_clause) = &extension.where_clause {
+                metadata.insert("whereClause".to_string(), Value::from(where_clause.clone()));
+            }
+            results.relationships.push(ctx.create_relationship(
+                &owner_id,
+                &protocol_id,
+                RelationshipKind::Implements,
+                extension.keyword,
+                Some(metadata),
+            ));
+        }
+
+        let Some(type_id) = type_id else {
+            continue;
+        };
+        let members: Vec<usize> = results
+            .symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| {
+                let start = s.start_byte as usize;
+                body_start < start && (s.end_byte as usize) <= body_end
+            })
+            .filter(|(_, member)| {
+                // Direct members only: not inside a type nested in the extension
+                !results.symbols.iter().any(|s| {
+                    TYPE_KINDS.contains(&s.kind)
+                        && s.id != member.id
+                        && body_start < s.start_byte as usize
+                        && contains(s, member.start_byte as usize)
+                })
+            })
+            .map(|(index, _)| index)
+            .collect();
+        for index in members {
+            let member = &mut results.symbols[index];
+            member.parent_id = Some(type_id.clone());
+            set_metadata(member, "extension", true);
+        }
+    }
+}
+
+fn contains(symbol: &Symbol, byte: usize) -> bool {
+    (symbol.start_byte as usize) <= byte && byte < symbol.end_byte as usize
+}
+
+fn find_extensions(content: &str) -> Vec<Extension> {
+    let code = code_mask(content, SWIFT);
+    let mut extensions = Vec::new();
+    for captures in EXTENSION_RE.captures_iter(content) {
+        let keyword = captures.get(1).unwrap().start();
+        let type_name = captures.get(2).unwrap();
+        if !code[keyword] {
+            continue;
+        }
+        let Some(brace) =
+            (type_name.end()..content.len()).find(|&i| code[i] && content.as_bytes()[i] == b'{')
+        else {
+            continue;
+        };
+        let Some(close) = find_matching_delimiter(content, brace, SWIFT) else {
+            continue;
+        };
+
+        // The inheritance clause runs from `:` to `where` or the body
+        let header = &content[type_name.end()..brace];
+        let (clause, where_clause) = match WHERE_RE.find(header) {
+            Some(found) => (
+                &header[..found.start()],
+                Some(collapse_whitespace(&header[found.end()..])),
+            ),
+            None => (header, None),
+        };
+        let protocols = match clause.find(':') {
+            Some(colon) => split_top_level(&clause[colon + 1..], b',', SWIFT)
+                .into_iter()
+                .map(|(_, protocol)| collapse_whitespace(protocol))
+                .filter(|protocol| !protocol.is_empty())
+                .collect(),
+            None => Vec::new(),
+        };
+        extensions.push(Extension {
+            keyword,
+            type_name: type_name.as_str().to_string(),
+            protocols,
+            where_clause: where_clause.filter(|w| !w.is_empty()),
+            body: (brace, close),
+        });
+    }
+    extensions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use std::path::Path;
+
+    const SOURCE: &str = r#"protocol Greeter {
+    func greet() -> String
+}
+
+struct User {
+    let name: String
+}
+
+extension User: Greeter, Codable {
+    func greet() -> String { "Hi \(name)" }
+    var initials: String { String(name.prefix(1)) }
+}
+
+extension Array: Greeter where Element: Greeter {
+    func greet() -> String { map { $0.greet() }.joined() }
+}
+"#;
+
+    fn span(
+        ctx: &EnrichmentContext,
+        name: &str,
+        kind: SymbolKind,
+        text: &str,
+        nth: usize,
+    ) -> Symbol {
+        let start = SOURCE.match_indices(text).nth(nth).unwrap().0;
+        let end = if text.starts_with("extension")
+            || text.starts_with("struct")
+            || text.starts_with("protocol")
+        {
+            SOURCE[start..].find("\n}").unwrap() + start + 2
+        } else {
+            SOURCE[start..].find('\n').unwrap() + start
+        };
+        ctx.create_symbol(name, kind, start, end, SymbolOptions::default())
+    }
+
+    fn linked() -> ExtractionResults {
+        let ctx = EnrichmentContext::new("Sources/User.swift", "swift", SOURCE, Path::new("."));
+        let mut symbols = vec![
+            span(
+                &ctx,
+                "Greeter",
+                SymbolKind::Interface,
+                "protocol Greeter",
+                0,
+            ),
+            span(&ctx, "User", SymbolKind::Struct, "struct User", 0),
+            span(&ctx, "User", SymbolKind::Class, "extension User", 0),
+            span(&ctx, "Array", SymbolKind::Class, "extension Array", 0),
+        ];
+        symbols.push(span(
+            &ctx,
+            "greet",
+            SymbolKind::Method,
+            "func greet() -> String {",
+            0,
+        ));
+        symbols[4].parent_id = Some(symbols[2].id.clone());
+        symbols.push(span(
+            &ctx,
+            "initials",
+            SymbolKind::Property,
+            "var initials",
+            0,
+        ));
+        symbols[5].parent_id = Some(symbols[2].id.clone());
+        symbols.push(span(
+            &ctx,
+            "greet",
+            SymbolKind::Method,
+            "func greet() -> String {",
+            1,
+        ));
+        symbols[6].parent_id = Some(symbols[3].id.clone());
+
+        let mut results = test_results(symbols);
+        link_extensions(&ctx, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_conformances_link_the_extended_type_to_local_protocols() {
+        let results = linked();
+        let (greeter, user, array) = (
+            &results.symbols[0],
+            &results.symbols[1],
+            &results.symbols[3],
+        );
+
+        let links: Vec<(&str, &str, Option<&Value>)> = results
+            .relationships
+            .iter()
+            .map(|r| {
+                (
+                    r.from_symbol_id.as_str(),
+                    r.to_symbol_id.as_str(),
+                    r.metadata.as_ref().and_then(|m| m.get("whereClause")),
+                )
+            })
+            .collect();
+        assert_eq!(
+            links,
+            [
+                (user.id.as_str(), greeter.id.as_str(), None),
+                (
+                    array.id.as_str(),
+                    greeter.id.as_str(),
+                    Some(&Value::from("Element: Greeter"))
+                ),
+            ]
+        );
+        assert!(results
+            .relationships
+            .iter()
+            .all(|r| r.kind == RelationshipKind::Implements));
+        // Codable is not declared here, so it is only recorded
+        assert_eq!(
+            user.metadata.as_ref().unwrap()["conformances"],
+            serde_json::json!(["Greeter", "Codable"])
+        );
+    }
+
+    #[test]
+    fn test_members_move_to_the_extended_type() {
+        let results = linked();
+        let user_id = &results.symbols[1].id;
+        let array_extension_id = &results.symbols[3].id;
+        assert_eq!(results.symbols[4].parent_id.as_ref(), Some(user_id));
+        assert_eq!(results.symbols[5].parent_id.as_ref(), Some(user_id));
+        assert_eq!(
+            results.symbols[5].metadata.as_ref().unwrap()["extension"],
+            true
+        );
+        // Array is not declared in the file: its member stays on the extension
+        assert_eq!(
+            results.symbols[6].parent_id.as_ref(),
+            Some(array_extension_id)
+        );
+    }
+}
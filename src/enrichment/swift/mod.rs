@@ -0,0 +1,31 @@
+// Swift enrichment passes
+//
+// Julie's Swift extractor handles types, functions, and stored properties;
+// these passes add property wrapper attributes, accessor shapes, and result
+// builder bodies (SwiftUI's `var body: some View { ... }`) as owners of the
+// code inside them, set visibility from access control and record interop
+// attributes, and link extension conformances to the extended type.
+
+mod access;
+mod extensions;
+mod properties;
+
+use super::base::{EnrichmentContext, Lexicon};
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 2;
+
+/// Swift strings ("..." and """...""") and // and /* */ comments
+pub(super) const SWIFT: Lexicon = Lexicon {
+    quotes: b"\"",
+    escape: Some(b'\\'),
+    line_comment: Some("//"),
+    block_comment: Some(("/*", "*/")),
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    properties::enrich_properties(ctx, results);
+    // Before extensions re-parent their members to the extended type
+    access::enrich_access(ctx, &mut results.symbols);
+    extensions::link_extensions(ctx, results);
+}
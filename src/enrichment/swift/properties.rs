@@ -0,0 +1,398 @@
+// Swift property attributes and accessors
+//
+// `@Published var items: [Item] = []` and `@State private var count = 0` carry
+// the behaviour that matters (observation, view state) in the attribute, which
+// Julie drops. This pass reads the attribute list on every property
+// declaration and records:
+//
+// - metadata.attributes (all of them) and metadata.propertyWrappers (the
+//   custom wrapper types, e.g. ["Published"]); attributes are prefixed to the
+//   signature
+// - metadata.accessors (get/set/willSet/didSet/...) and metadata.computed for
+//   properties with an accessor block; a block without accessor keywords is an
+//   implicit getter
+// - metadata.resultBuilder for builder-attributed properties and for SwiftUI's
+//   implicit `var body: some View` builder
+//
+// The property's range is extended over its accessor block and identifiers in
+// the block are attributed to it, so the view types used inside `body` have a
+// containing symbol.
+
+use super::SWIFT;
+use crate::enrichment::base::{
+    find_matching_delimiter, reattribute_identifiers, set_metadata, top_level_mask, CodeBytes,
+    EnrichmentContext,
+};
+use julie_extractors::{ExtractionResults, SymbolKind};
+use regex::Regex;
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+/// Attributes and modifiers, then `var`/`let` and the property name
+static PROPERTY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*((?:(?:@[\w.]+|public|private|fileprivate|internal|open|package|static|class|final|lazy|weak|unowned|override|nonisolated|dynamic|required|optional)(?:\([^()\n]*\))?\s+)*)(?:var|let)\s+([A-Za-z_]\w*)",
+    )
+    .unwrap()
+});
+
+static ATTRIBUTE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@([\w.]+)(?:\([^()\n]*\))?").unwrap());
+
+/// `@resultBuilder struct HTMLBuilder` declares a builder usable as an attribute
+static RESULT_BUILDER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"@resultBuilder\s+(?:(?:public|internal|fileprivate|private)\s+)?(?:struct|enum|class)\s+([A-Za-z_]\w*)").unwrap()
+});
+
+/// Capitalized attributes that are language or framework features, not wrappers
+const BUILTIN_ATTRIBUTES: &[&str] = &[
+    "MainActor",
+    "IBOutlet",
+    "IBInspectable",
+    "IBAction",
+    "GKInspectable",
+    "NSManaged",
+    "NSCopying",
+    "Sendable",
+];
+
+/// SwiftUI builders with their implicit `body` types
+const SWIFTUI_BUILDERS: &[(&str, &str)] = &[
+    ("View", "ViewBuilder"),
+    ("Scene", "SceneBuilder"),
+    ("Commands", "CommandsBuilder"),
+    ("ToolbarContent", "ToolbarContentBuilder"),
+];
+
+const ACCESSOR_KEYWORDS: &[&str] = &["get", "set", "willSet", "didSet", "_read", "_modify"];
+
+pub(super) fn enrich_properties(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let mut builders: HashSet<String> = SWIFTUI_BUILDERS
+        .iter()
+        .map(|(_, builder)| builder.to_string())
+        .collect();
+    builders.extend(
+        RESULT_BUILDER_RE
+            .captures_iter(ctx.content)
+            .map(|c| c[1].to_string()),
+    );
+
+    let mut blocks = Vec::new();
+    let mut owners = HashSet::new();
+
+    for captures in PROPERTY_RE.captures_iter(ctx.content) {
+        let prefix = captures.get(1).unwrap();
+        let name = captures.get(2).unwrap();
+        let Some(symbol) = results.symbols.iter_mut().find(|s| {
+            s.name == name.as_str()
+                && matches!(
+                    s.kind,
+                    SymbolKind::Property
+                        | SymbolKind::Variable
+                        | SymbolKind::Constant
+                        | SymbolKind::Field
+                )
+                && (s.start_byte as usize) <= name.end()
+                && name.start() < s.end_byte as usize
+        }) else {
+            continue;
+        };
+
+        let attributes: Vec<&str> = ATTRIBUTE_RE
+            .find_iter(prefix.as_str())
+            .map(|m| m.as_str())
+            .collect();
+        let attribute_names: Vec<String> = ATTRIBUTE_RE
+            .captures_iter(prefix.as_str())
+            .map(|c| c[1].rsplit('.').next().unwrap_or(&c[1]).to_string())
+            .collect();
+        if !attributes.is_empty() {
+            set_metadata(symbol, "attributes", attribute_names.clone());
+            let wrappers: Vec<String> = attribute_names
+                .iter()
+                .filter(|a| a.starts_with(char::is_uppercase))
+                .filter(|a| !BUILTIN_ATTRIBUTES.contains(&a.as_str()) && !builders.contains(*a))
+                .cloned()
+                .collect();
+            if !wrappers.is_empty() {
+                set_metadata(symbol, "propertyWrappers", wrappers);
+            }
+            if !symbol
+                .signature
+                .as_deref()
+                .is_some_and(|s| s.starts_with('@'))
+            {
+                let declared = symbol
+                    .signature
+                    .take()
+                    .unwrap_or_else(|| ctx.text(prefix.end(), name.end()).to_string());
+                symbol.signature = Some(format!("{} {}", attributes.join(" "), declared));
+            }
+        }
+
+        let Some((type_text, block)) = declaration_tail(ctx.content, name.end()) else {
+            continue;
+        };
+        let explicit_builder = attribute_names.iter().find(|a| builders.contains(*a));
+        let implicit_builder = (name.as_str() == "body")
+            .then(|| {
+                let type_text = type_text.trim().strip_prefix("some ")?;
+                SWIFTUI_BUILDERS
+                    .iter()
+                    .find(|(protocol, _)| *protocol == type_text.trim())
+                    .map(|(_, builder)| builder.to_string())
+            })
+            .flatten();
+        if let Some(builder) = explicit_builder.cloned().or(implicit_builder) {
+            set_metadata(symbol, "resultBuilder", builder);
+        }
+
+        let Some((open, close)) = block else {
+            continue;
+        };
+        let accessors = accessor_keywords(ctx.text(open + 1, close));
+        let computed =
+            accessors.is_empty() || accessors.iter().any(|a| a != "willSet" && a != "didSet");
+        let accessors = if accessors.is_empty() {
+            vec!["get".to_string()]
+        } else {
+            accessors
+        };
+        set_metadata(symbol, "accessors", accessors);
+        set_metadata(symbol, "computed", computed);
+
+        if (symbol.end_byte as usize) < close + 1 {
+            let (end_line, end_column) = ctx.position(close + 1);
+            symbol.end_byte = (close + 1) as u32;
+            symbol.end_line = end_line;
+            symbol.end_column = end_column;
+        }
+        owners.insert(symbol.id.clone());
+        blocks.push((open, close + 1));
+    }
+
+    reattribute_identifiers(&mut results.identifiers, &results.symbols, &blocks, |s| {
+        owners.contains(&s.id)
+            || matches!(
+                s.kind,
+                SymbolKind::Function
+                    | SymbolKind::Method
+                    | SymbolKind::Constructor
+                    | SymbolKind::Class
+                    | SymbolKind::Struct
+                    | SymbolKind::Interface
+                    | SymbolKind::Enum
+            )
+    });
+}
+
+/// Type annotation text after the property name, and the accessor block
+/// (`{` and `}` offsets) when the declaration has one
+fn declaration_tail(content: &str, from: usize) -> Option<(&str, Option<(usize, usize)>)> {
+    let mut depth = 0usize;
+    let mut type_start = None;
+    let mut type_end = None;
+    let mut initialized = false;
+
+    for (index, byte) in CodeBytes::new(content, from, SWIFT) {
+        match byte {
+            b':' if depth == 0 && type_start.is_none() && !initialized => {
+                type_start = Some(index + 1)
+            }
+            b'(' | b'[' | b'<' => depth += 1,
+            b')' | b']' | b'>' => depth = depth.saturating_sub(1),
+            b'=' if depth == 0 && !initialized => {
+                type_end.get_or_insert(index);
+                initialized = true;
+            }
+            b'\n' if depth == 0 => {
+                type_end.get_or_insert(index);
+                break;
+            }
+            b'{' if depth == 0 => {
+                type_end.get_or_insert(index);
+                let close = find_matching_delimiter(content, index, SWIFT)?;
+                // After an initializer only observer blocks count: `= 0 { didSet { ... } }`
+                let is_block = !initialized || {
+                    let keywords = accessor_keywords(&content[index + 1..close]);
+                    !keywords.is_empty() && keywords.iter().all(|k| k == "willSet" || k == "didSet")
+                };
+                let type_text = type_start.map_or("", |start| &content[start..type_end.unwrap()]);
+                return Some((type_text, is_block.then_some((index, close))));
+            }
+            _ => {}
+        }
+    }
+    let end = type_end.unwrap_or(content.len());
+    Some((type_start.map_or("", |start| &content[start..end]), None))
+}
+
+/// Accessor keywords at the top level of a property block, if the block is
+/// an accessor list rather than an implicit getter body
+fn accessor_keywords(block: &str) -> Vec<String> {
+    let mask = top_level_mask(block, SWIFT);
+    let top_level: String = block
+        .char_indices()
+        .map(|(i, c)| {
+            if mask.get(i).copied().unwrap_or(false) {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    let words: Vec<&str> = top_level
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let first_significant = words.iter().find(|w| {
+        !matches!(
+            **w,
+            "mutating" | "nonmutating" | "private" | "fileprivate" | "internal" | "public"
+        )
+    });
+    if !first_significant.is_some_and(|w| ACCESSOR_KEYWORDS.contains(w)) {
+        return Vec::new();
+    }
+    words
+        .into_iter()
+        .filter(|w| ACCESSOR_KEYWORDS.contains(w))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, SymbolOptions};
+    use julie_extractors::IdentifierKind;
+    use serde_json::Value;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    const VIEW: &str = r#"struct CounterView: View {
+    @ObservedObject var model: CounterModel
+    @State private var count = 0 {
+        didSet { print(count) }
+    }
+    @MainActor @AppStorage("name") var name: String = ""
+    var doubled: Int { count * 2 }
+    var title: String {
+        get { model.title }
+        set { model.title = newValue }
+    }
+
+    var body: some View {
+        VStack {
+            Text(title)
+        }
+    }
+}
+"#;
+
+    fn results() -> (EnrichmentContext<'static>, ExtractionResults) {
+        let ctx = EnrichmentContext::new("CounterView.swift", "swift", VIEW, Path::new("."));
+        let mut symbols = vec![ctx.create_symbol(
+            "CounterView",
+            SymbolKind::Struct,
+            0,
+            VIEW.len() - 1,
+            SymbolOptions::default(),
+        )];
+        for name in ["model", "count", "name", "doubled", "title", "body"] {
+            let start = VIEW.find(&format!("var {}", name)).unwrap() + 4;
+            symbols.push(ctx.create_symbol(
+                name,
+                SymbolKind::Property,
+                start,
+                start + name.len(),
+                SymbolOptions {
+                    signature: Some(format!("var {}", name)),
+                    ..Default::default()
+                },
+            ));
+        }
+        let at = VIEW.find("VStack").unwrap();
+        let identifier = ctx.create_identifier(
+            "VStack",
+            IdentifierKind::Call,
+            at,
+            at + 6,
+            Some(symbols[0].id.clone()),
+        );
+        let results = ExtractionResults {
+            identifiers: vec![identifier],
+            ..test_results(symbols)
+        };
+        (ctx, results)
+    }
+
+    fn meta<'r>(results: &'r ExtractionResults, name: &str) -> &'r HashMap<String, Value> {
+        let symbol = results.symbols.iter().find(|s| s.name == name).unwrap();
+        symbol.metadata.as_ref().unwrap()
+    }
+
+    #[test]
+    fn test_property_wrappers_and_signatures() {
+        let (ctx, mut results) = results();
+        enrich_properties(&ctx, &mut results);
+
+        assert_eq!(
+            meta(&results, "model")["propertyWrappers"],
+            serde_json::json!(["ObservedObject"])
+        );
+        assert_eq!(
+            meta(&results, "name")["propertyWrappers"],
+            serde_json::json!(["AppStorage"])
+        );
+        assert_eq!(
+            meta(&results, "name")["attributes"],
+            serde_json::json!(["MainActor", "AppStorage"])
+        );
+        let name = results.symbols.iter().find(|s| s.name == "name").unwrap();
+        assert_eq!(
+            name.signature.as_deref(),
+            Some("@MainActor @AppStorage(\"name\") var name")
+        );
+    }
+
+    #[test]
+    fn test_accessors_and_observers() {
+        let (ctx, mut results) = results();
+        enrich_properties(&ctx, &mut results);
+
+        assert_eq!(
+            meta(&results, "count")["accessors"],
+            serde_json::json!(["didSet"])
+        );
+        assert_eq!(meta(&results, "count")["computed"], Value::Bool(false));
+        assert_eq!(
+            meta(&results, "doubled")["accessors"],
+            serde_json::json!(["get"])
+        );
+        assert_eq!(
+            meta(&results, "title")["accessors"],
+            serde_json::json!(["get", "set"])
+        );
+        assert_eq!(meta(&results, "title")["computed"], Value::Bool(true));
+        assert!(meta(&results, "model").get("accessors").is_none());
+    }
+
+    #[test]
+    fn test_body_owns_its_builder_closure() {
+        let (ctx, mut results) = results();
+        enrich_properties(&ctx, &mut results);
+
+        let body = results.symbols.iter().find(|s| s.name == "body").unwrap();
+        assert_eq!(
+            meta(&results, "body")["resultBuilder"],
+            Value::from("ViewBuilder")
+        );
+        assert!(ctx.symbol_text(body).trim_end().ends_with('}'));
+        assert_eq!(
+            results.identifiers[0].containing_symbol_id.as_deref(),
+            Some(body.id.as_str())
+        );
+    }
+}
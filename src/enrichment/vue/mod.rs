@@ -0,0 +1,16 @@
+// Vue enrichment passes
+//
+// Julie's Vue extractor handles the component and its script section; this
+// pass adds the classes defined in scoped and CSS-module style sections and
+// links the template's (and script's) uses of them.
+
+mod styles;
+
+use super::base::EnrichmentContext;
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 1;
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    styles::link_style_classes(ctx, results);
+}
@@ -0,0 +1,402 @@
+// Vue scoped and CSS-module style classes
+//
+// Classes defined in `<style scoped>` or `<style module>` only apply inside
+// their component, so "is this class used?" has a per-file answer. Each class
+// selector in such a section becomes a Class symbol (or Julie's symbol for it
+// gains the metadata) flagged metadata.cssClass, with metadata.scoped or
+// metadata.module (`$style`, or the name given by `module="classes"`).
+//
+// Uses are VariableRef identifiers targeting the class symbol:
+//
+//     class="card title"                      each listed class
+//     :class="{ active: isActive }"           each object key
+//     :class="['card', big ? 'lg' : '']"      each string literal
+//     $style.card / $style['card']            MemberAccess, template or script
+//
+// Object keys and string literals in bindings are candidates: the ones that
+// name a class in the component are linked, the rest are ignored. Every class
+// symbol records metadata.usageCount so unused (dead) classes are easy to find.
+
+use crate::enrichment::base::{code_mask, innermost_symbol_at, set_metadata, CodeBytes};
+use crate::enrichment::base::{EnrichmentContext, Lexicon, SymbolOptions};
+use julie_extractors::{ExtractionResults, IdentifierKind, SymbolKind};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+/// `<style attrs>css</style>`
+static STYLE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<style\b([^>]*)>(.*?)</style\s*>").unwrap());
+
+/// `scoped` attribute
+static SCOPED_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bscoped\b").unwrap());
+
+/// `module` or `module="name"` attribute
+static MODULE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\bmodule\b(?:\s*=\s*["']([A-Za-z_$][\w$]*)["'])?"#).unwrap()
+});
+
+/// `lang="scss"` attribute
+static LANG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\blang\s*=\s*["'](\w+)["']"#).unwrap());
+
+/// `.class-name` in a selector
+static CLASS_SELECTOR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.(-?[_a-zA-Z][\w-]*)").unwrap());
+
+/// The component template, from the first `<template>` to the last `</template>`
+static TEMPLATE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<template\b[^>]*>(.*)</template\s*>").unwrap());
+
+/// `class="a b"` (static) or `:class="expr"` / `v-bind:class="expr"` (bound)
+static CLASS_ATTR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\s(:|v-bind:)?class\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap());
+
+/// Object keys in a class binding: `{ active: x, 'text-danger': y }`
+static OBJECT_KEY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"[{,]\s*(?:'([^']+)'|"([^"]+)"|([A-Za-z_$][\w$-]*))\s*:"#).unwrap()
+});
+
+/// String literals in a class binding
+static STRING_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"'([^']*)'|"([^"]*)"|`([^`$]*)`"#).unwrap());
+
+/// A class defined in a scoped or module style section
+struct StyleClass {
+    name: String,
+    symbol_id: String,
+    /// `$style` or the named module, for module sections
+    module: Option<String>,
+}
+
+pub(super) fn link_style_classes(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let mut classes: Vec<StyleClass> = Vec::new();
+    let mut style_ranges = Vec::new();
+
+    for captures in STYLE_RE.captures_iter(ctx.content) {
+        let attributes = &captures[1];
+        let css = captures.get(2).unwrap();
+        style_ranges.push((captures.get(0).unwrap().start(), css.end()));
+
+        let scoped = SCOPED_RE.is_match(attributes);
+        let module = MODULE_RE.captures(attributes).map(|c| {
+            c.get(1)
+                .map_or("$style".to_string(), |name| name.as_str().to_string())
+        });
+        if !scoped && module.is_none() {
+            continue;
+        }
+        let lexicon = match LANG_RE.captures(attributes) {
+            Some(lang) if matches!(&lang[1], "scss" | "less" | "stylus") => Lexicon::C_LIKE,
+            _ => Lexicon::CSS,
+        };
+
+        let mut seen = HashSet::new();
+        for (prelude_start, prelude) in selector_preludes(css.as_str(), lexicon) {
+            let code = code_mask(prelude, lexicon);
+            // Leading comments are not part of the selector
+            let selector_start = (0..prelude.len())
+                .find(|&i| code[i] && !prelude.as_bytes()[i].is_ascii_whitespace())
+                .unwrap_or(0);
+            let selector = prelude[selector_start..].trim();
+            for class in CLASS_SELECTOR_RE.captures_iter(prelude) {
+                let dot = class.get(0).unwrap();
+                let name = &class[1];
+                if !code[dot.start()] || seen.contains(name) {
+                    continue;
+                }
+                let start = css.start() + prelude_start + dot.start();
+                let end = start + dot.len();
+                let existing = results.symbols.iter_mut().find(|s| {
+                    (s.name == name || s.name == dot.as_str())
+                        && css.start() <= s.start_byte as usize
+                        && (s.start_byte as usize) < css.end()
+                });
+                let symbol = match existing {
+                    Some(symbol) => symbol,
+                    None => {
+                        let symbol = ctx.create_symbol(
+                            name,
+                            SymbolKind::Class,
+                            start,
+                            end,
+                            SymbolOptions {
+                                signature: Some(selector.to_string()),
+                                ..Default::default()
+                            },
+                        );
+                        results.symbols.push(symbol);
+                        results.symbols.last_mut().unwrap()
+                    }
+                };
+                set_metadata(symbol, "cssClass", true);
+                set_metadata(symbol, "scoped", scoped);
+                if let Some(module) = &module {
+                    set_metadata(symbol, "module", module.clone());
+                }
+                seen.insert(name.to_string());
+                classes.push(StyleClass {
+                    name: name.to_string(),
+                    symbol_id: symbol.id.clone(),
+                    module: module.clone(),
+                });
+            }
+        }
+    }
+    if classes.is_empty() {
+        return;
+    }
+
+    let mut uses = template_class_uses(ctx.content);
+    let modules: HashSet<&str> = classes.iter().filter_map(|c| c.module.as_deref()).collect();
+    for module in modules {
+        uses.extend(module_accesses(ctx.content, module, &style_ranges));
+    }
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for class_use in uses {
+        // Plain class names only reach scoped classes; modules are reached through their object
+        let Some(class) = classes.iter().find(|c| {
+            c.name == class_use.name
+                && match &class_use.module {
+                    Some(module) => c.module.as_deref() == Some(module.as_str()),
+                    None => c.module.is_none(),
+                }
+        }) else {
+            continue;
+        };
+        let containing = innermost_symbol_at(&results.symbols, class_use.start, |s| {
+            !classes.iter().any(|c| c.symbol_id == s.id)
+        })
+        .map(|s| s.id.clone());
+        let kind = if class_use.module.is_some() {
+            IdentifierKind::MemberAccess
+        } else {
+            IdentifierKind::VariableRef
+        };
+        let mut identifier = ctx.create_identifier(
+            &class_use.name,
+            kind,
+            class_use.start,
+            class_use.start + class_use.name.len(),
+            containing,
+        );
+        identifier.target_symbol_id = Some(class.symbol_id.clone());
+        results.identifiers.push(identifier);
+        *counts.entry(class.symbol_id.clone()).or_default() += 1;
+    }
+
+    for class in &classes {
+        if let Some(symbol) = results.symbols.iter_mut().find(|s| s.id == class.symbol_id) {
+            let count = counts.get(&class.symbol_id).copied().unwrap_or(0);
+            set_metadata(symbol, "usageCount", count);
+        }
+    }
+}
+
+/// Selector text before each `{` of a stylesheet, with its offset (at-rule preludes skipped)
+fn selector_preludes(css: &str, lexicon: Lexicon) -> Vec<(usize, &str)> {
+    let mut preludes = Vec::new();
+    let mut boundary = 0;
+    for (index, byte) in CodeBytes::new(css, 0, lexicon) {
+        match byte {
+            b'{' => {
+                let prelude = &css[boundary..index];
+                if !prelude.trim_start().starts_with('@') {
+                    preludes.push((boundary, prelude));
+                }
+                boundary = index + 1;
+            }
+            b'}' | b';' => boundary = index + 1,
+            _ => {}
+        }
+    }
+    preludes
+}
+
+/// A class name used by the component, at byte `start` of the file
+#[derive(Debug, PartialEq)]
+struct ClassUse {
+    name: String,
+    start: usize,
+    /// Module object the class was read from (`$style.card`)
+    module: Option<String>,
+}
+
+/// Class names in the template's static and bound `class` attributes
+fn template_class_uses(content: &str) -> Vec<ClassUse> {
+    let Some(template) = TEMPLATE_RE.captures(content).and_then(|c| c.get(1)) else {
+        return Vec::new();
+    };
+    let mut uses = Vec::new();
+    for captures in CLASS_ATTR_RE.captures_iter(template.as_str()) {
+        let value = captures.get(2).or(captures.get(3)).unwrap();
+        let offset = template.start() + value.start();
+        let mut add = |name: &str, start: usize| {
+            if !name.is_empty() && !uses.iter().any(|u: &ClassUse| u.start == start) {
+                uses.push(ClassUse {
+                    name: name.to_string(),
+                    start,
+                    module: None,
+                });
+            }
+        };
+
+        if captures.get(1).is_none() {
+            for (start, name) in words(value.as_str()) {
+                add(name, offset + start);
+            }
+            continue;
+        }
+        for key in OBJECT_KEY_RE.captures_iter(value.as_str()) {
+            let name = key.get(1).or(key.get(2)).or(key.get(3)).unwrap();
+            add(name.as_str(), offset + name.start());
+        }
+        for literal in STRING_RE.captures_iter(value.as_str()) {
+            let text = literal
+                .get(1)
+                .or(literal.get(2))
+                .or(literal.get(3))
+                .unwrap();
+            for (start, name) in words(text.as_str()) {
+                add(name, offset + text.start() + start);
+            }
+        }
+    }
+    uses
+}
+
+/// `module.name` / `module['name']` reads outside the style sections
+fn module_accesses(content: &str, module: &str, style_ranges: &[(usize, usize)]) -> Vec<ClassUse> {
+    let access = Regex::new(&format!(
+        r#"(?:^|[^\w$]){}\s*(?:\.\s*([A-Za-z_][\w]*)|\[\s*(?:'([^']+)'|"([^"]+)")\s*\])"#,
+        regex::escape(module)
+    ))
+    .unwrap();
+    access
+        .captures_iter(content)
+        .filter_map(|captures| {
+            let name = captures.get(1).or(captures.get(2)).or(captures.get(3))?;
+            let inside_style = style_ranges
+                .iter()
+                .any(|&(start, end)| start <= name.start() && name.start() < end);
+            (!inside_style).then(|| ClassUse {
+                name: name.as_str().to_string(),
+                start: name.start(),
+                module: Some(module.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Whitespace-separated words with their offsets
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split_whitespace()
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    const COMPONENT: &str = r#"<template>
+  <div class="card shadow" :class="{ active: isActive, 'is-big': big }">
+    <p :class="[$style.title, big ? 'lg' : '']">Hi</p>
+    <span :class="classes['badge']"></span>
+  </div>
+</template>
+
+<script>
+export default { computed: { titleClass() { return this.$style.title } } }
+</script>
+
+<style scoped>
+/* .legacy { } */
+.card, .card > .lg { color: red; }
+.active { font-weight: bold; }
+.unused { display: none; background: url("a.b.png"); }
+</style>
+
+<style module>
+.title { font-size: 2em; }
+</style>
+
+<style module="classes" lang="scss">
+.badge { &:hover { color: blue; } }
+</style>
+
+<style>
+.global { margin: 0; }
+</style>
+"#;
+
+    fn run() -> ExtractionResults {
+        let ctx = EnrichmentContext::new("Card.vue", "vue", COMPONENT, Path::new("."));
+        let mut results = test_results(Vec::new());
+        link_style_classes(&ctx, &mut results);
+        results
+    }
+
+    fn usage_count(results: &ExtractionResults, name: &str) -> u64 {
+        let symbol = results.symbols.iter().find(|s| s.name == name).unwrap();
+        symbol.metadata.as_ref().unwrap()["usageCount"]
+            .as_u64()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_scoped_and_module_classes_become_symbols() {
+        let results = run();
+
+        let names: Vec<&str> = results.symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["card", "lg", "active", "unused", "title", "badge"]
+        );
+        let card = &results.symbols[0];
+        assert_eq!(card.signature.as_deref(), Some(".card, .card > .lg"));
+        let metadata = card.metadata.as_ref().unwrap();
+        assert_eq!(metadata["scoped"], true);
+        assert!(!metadata.contains_key("module"));
+        assert_eq!(
+            results.symbols[4].metadata.as_ref().unwrap()["module"],
+            "$style"
+        );
+        assert_eq!(
+            results.symbols[5].metadata.as_ref().unwrap()["module"],
+            "classes"
+        );
+    }
+
+    #[test]
+    fn test_template_and_script_uses_are_linked() {
+        let results = run();
+
+        assert_eq!(usage_count(&results, "card"), 1);
+        assert_eq!(usage_count(&results, "active"), 1);
+        assert_eq!(usage_count(&results, "lg"), 1);
+        assert_eq!(usage_count(&results, "unused"), 0);
+        // $style.title in the template and this.$style.title in the script
+        assert_eq!(usage_count(&results, "title"), 2);
+        assert_eq!(usage_count(&results, "badge"), 1);
+
+        let title_uses: Vec<_> = results
+            .identifiers
+            .iter()
+            .filter(|i| i.name == "title")
+            .collect();
+        assert!(title_uses
+            .iter()
+            .all(|i| i.kind == IdentifierKind::MemberAccess));
+        assert!(results
+            .identifiers
+            .iter()
+            .all(|i| i.target_symbol_id.is_some()));
+        assert!(!results
+            .identifiers
+            .iter()
+            .any(|i| i.name == "shadow" || i.name == "is-big"));
+    }
+}
@@ -0,0 +1,343 @@
+// Zig comptime
+//
+// Zig generics are ordinary functions taking `comptime T: type`, and data
+// structures are functions returning `type`:
+//
+//     pub fn List(comptime T: type) type {
+//         return struct { items: []T, len: usize };
+//     }
+//
+// Julie treats comptime parameters like any other and sees nothing inside the
+// returned struct. This pass:
+//
+// - marks comptime parameters (metadata.isComptime) and puts the keyword back
+//   in their signature, adding the parameter symbol when Julie has none
+// - marks functions whose return type is `type` (metadata.isTypeConstructor)
+//   and extracts the fields of the struct they return as Field children of
+//   the function
+// - adds a Function symbol named `comptime` for each top-level comptime block
+//   (metadata.isComptimeBlock) and attributes the identifiers inside to it
+
+use super::ZIG;
+use crate::enrichment::base::{
+    find_matching_delimiter, reattribute_identifiers, set_metadata, split_top_level,
+    top_level_mask, trim_with_offset, EnrichmentContext, SymbolOptions,
+};
+use julie_extractors::{ExtractionResults, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// `fn name(`
+pub(super) static FN_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bfn\s+@?([A-Za-z_]\w*)\s*\(").unwrap());
+
+/// `comptime T: type`, `noalias buf: []u8`, `x: anytype`
+static PARAMETER_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?s)^(?:(comptime|noalias)\s+)?([A-Za-z_]\w*)\s*:\s*(.+)$").unwrap()
+});
+
+/// Return type `type`, optionally after a calling convention, then the body
+static TYPE_RETURN_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:callconv\([^)]*\)\s*)?type\s*\{").unwrap());
+
+/// `return struct {`, `return packed struct(u8) {`
+static RETURN_STRUCT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\breturn\s+(?:(?:packed|extern)\s+)?struct\s*(?:\([^)]*\)\s*)?\{").unwrap()
+});
+
+/// `name: Type` at the start of a line of a container body
+static FIELD_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*([A-Za-z_]\w*)[ \t]*:").unwrap());
+
+static COMPTIME_BLOCK_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?m)^[ \t]*(comptime)\s*\{").unwrap());
+
+/// Mark comptime parameters and type constructors, extracting returned struct fields
+pub(super) fn enrich_functions(ctx: &EnrichmentContext, symbols: &mut Vec<Symbol>) {
+    let functions: Vec<(String, String, usize, usize)> = symbols
+        .iter()
+        .filter(|s| matches!(s.kind, SymbolKind::Function | SymbolKind::Method))
+        .map(|s| {
+            (
+                s.id.clone(),
+                s.name.clone(),
+                s.start_byte as usize,
+                s.end_byte as usize,
+            )
+        })
+        .collect();
+
+    for (function_id, name, start, end) in functions {
+        let text = ctx.text(start, end);
+        let Some(open) = FN_RE
+            .captures_iter(text)
+            .find(|c| c[1] == name)
+            .map(|c| c.get(0).unwrap().end() - 1)
+        else {
+            continue;
+        };
+        let Some(close) = find_matching_delimiter(text, open, ZIG) else {
+            continue;
+        };
+
+        for (offset, segment) in split_top_level(&text[open + 1..close], b',', ZIG) {
+            let (offset, segment) = trim_with_offset(start + open + 1 + offset, segment);
+            enrich_parameter(ctx, symbols, &function_id, offset, segment);
+        }
+
+        let Some(body) = TYPE_RETURN_RE
+            .find(&text[close + 1..])
+            .map(|m| close + m.end())
+        else {
+            continue;
+        };
+        if let Some(function) = symbols.iter_mut().find(|s| s.id == function_id) {
+            set_metadata(function, "isTypeConstructor", true);
+        }
+        let Some(body_end) = find_matching_delimiter(text, body, ZIG) else {
+            continue;
+        };
+        let Some(open_brace) = RETURN_STRUCT_RE
+            .find(&text[body..body_end])
+            .map(|m| body + m.end() - 1)
+        else {
+            continue;
+        };
+        let Some(close_brace) = find_matching_delimiter(text, open_brace, ZIG) else {
+            continue;
+        };
+        enrich_struct_fields(
+            ctx,
+            symbols,
+            &function_id,
+            start + open_brace + 1,
+            start + close_brace,
+        );
+    }
+}
+
+fn enrich_parameter(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    function_id: &str,
+    start: usize,
+    segment: &str,
+) {
+    let Some(captures) = PARAMETER_RE.captures(segment) else {
+        return;
+    };
+    if captures.get(1).map(|m| m.as_str()) != Some("comptime") {
+        return;
+    }
+    let name = &captures[2];
+    let signature = segment.split_whitespace().collect::<Vec<_>>().join(" ");
+    let type_name = captures[3].split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if let Some(existing) = symbols
+        .iter_mut()
+        .find(|s| s.parent_id.as_deref() == Some(function_id) && s.name == name)
+    {
+        existing.signature = Some(signature);
+        set_metadata(existing, "isComptime", true);
+        return;
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert("isParameter".to_string(), Value::Bool(true));
+    metadata.insert("isComptime".to_string(), Value::Bool(true));
+    metadata.insert("type".to_string(), Value::from(type_name));
+    symbols.push(ctx.create_symbol(
+        name,
+        SymbolKind::Variable,
+        start,
+        start + segment.len(),
+        SymbolOptions {
+            signature: Some(signature),
+            parent_id: Some(function_id.to_string()),
+            metadata: Some(metadata),
+            ..Default::default()
+        },
+    ));
+}
+
+/// Add (or re-parent) Field symbols for the fields of the struct body at `start..end`
+fn enrich_struct_fields(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    function_id: &str,
+    start: usize,
+    end: usize,
+) {
+    let body = ctx.text(start, end);
+    let top_level = top_level_mask(body, ZIG);
+
+    for captures in FIELD_RE.captures_iter(body) {
+        let name = captures.get(1).unwrap();
+        if !top_level[name.start()] {
+            continue;
+        }
+        let line_end = body[name.start()..]
+            .find('\n')
+            .map_or(body.len(), |i| name.start() + i);
+        let field_end = (name.start()..line_end)
+            .find(|&i| top_level[i] && body.as_bytes()[i] == b',')
+            .unwrap_or(line_end);
+        let declaration = body[name.start()..field_end].trim_end();
+        let after_colon = &body[captures.get(0).unwrap().end()..field_end];
+        let type_end = (0..after_colon.len())
+            .find(|&i| {
+                let at = captures.get(0).unwrap().end() + i;
+                top_level[at] && body.as_bytes()[at] == b'='
+            })
+            .unwrap_or(after_colon.len());
+        let type_name = after_colon[..type_end].trim();
+        if type_name.is_empty() {
+            continue;
+        }
+
+        let field_start = start + name.start();
+        let field_stop = field_start + declaration.len();
+        if let Some(existing) = symbols.iter_mut().find(|s| {
+            s.name == name.as_str()
+                && (s.start_byte as usize) < field_stop
+                && field_start < (s.end_byte as usize)
+        }) {
+            existing.parent_id = Some(function_id.to_string());
+            continue;
+        }
+
+        let mut metadata = HashMap::new();
+        metadata.insert("type".to_string(), Value::from(type_name));
+        symbols.push(ctx.create_symbol(
+            name.as_str(),
+            SymbolKind::Field,
+            field_start,
+            field_stop,
+            SymbolOptions {
+                signature: Some(declaration.split_whitespace().collect::<Vec<_>>().join(" ")),
+                parent_id: Some(function_id.to_string()),
+                metadata: Some(metadata),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+/// Add a scope symbol for each top-level `comptime { ... }` block
+pub(super) fn enrich_comptime_blocks(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let top_level = top_level_mask(ctx.content, ZIG);
+    let mut blocks = Vec::new();
+
+    for captures in COMPTIME_BLOCK_RE.captures_iter(ctx.content) {
+        let keyword = captures.get(1).unwrap();
+        if !top_level[keyword.start()] {
+            continue;
+        }
+        let open = captures.get(0).unwrap().end() - 1;
+        let Some(close) = find_matching_delimiter(ctx.content, open, ZIG) else {
+            continue;
+        };
+        let mut metadata = HashMap::new();
+        metadata.insert("isComptimeBlock".to_string(), Value::Bool(true));
+        results.symbols.push(ctx.create_symbol(
+            "comptime",
+            SymbolKind::Function,
+            keyword.start(),
+            close + 1,
+            SymbolOptions {
+                signature: Some("comptime".to_string()),
+                metadata: Some(metadata),
+                ..Default::default()
+            },
+        ));
+        blocks.push((open, close + 1));
+    }
+
+    reattribute_identifiers(&mut results.identifiers, &results.symbols, &blocks, |s| {
+        matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use julie_extractors::IdentifierKind;
+    use std::path::Path;
+
+    fn function(ctx: &EnrichmentContext, name: &str) -> Symbol {
+        let start = ctx.content.find("pub fn").unwrap();
+        let end = ctx.content.rfind('}').unwrap() + 1;
+        ctx.create_symbol(
+            name,
+            SymbolKind::Function,
+            start,
+            end,
+            SymbolOptions::default(),
+        )
+    }
+
+    #[test]
+    fn test_type_constructor_fields_and_comptime_parameter() {
+        let content = "pub fn List(comptime T: type, allocator: Allocator) type {\n    return struct {\n        items: []T = &.{},\n        len: usize,\n\n        pub fn append(self: *@This(), item: T) void {}\n    };\n}\n";
+        let ctx = EnrichmentContext::new("list.zig", "zig", content, Path::new("."));
+        let mut symbols = vec![function(&ctx, "List")];
+        enrich_functions(&ctx, &mut symbols);
+
+        let list = &symbols[0];
+        assert_eq!(
+            list.metadata.as_ref().unwrap()["isTypeConstructor"],
+            Value::Bool(true)
+        );
+        let names: Vec<&str> = symbols[1..].iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["T", "items", "len"],
+            "allocator is not comptime"
+        );
+
+        let t = &symbols[1];
+        assert_eq!(t.signature.as_deref(), Some("comptime T: type"));
+        assert_eq!(
+            t.metadata.as_ref().unwrap()["isComptime"],
+            Value::Bool(true)
+        );
+
+        let items = &symbols[2];
+        assert_eq!(items.kind, SymbolKind::Field);
+        assert_eq!(items.parent_id.as_deref(), Some(list.id.as_str()));
+        assert_eq!(items.signature.as_deref(), Some("items: []T = &.{}"));
+        assert_eq!(items.metadata.as_ref().unwrap()["type"], Value::from("[]T"));
+        assert_eq!(items.start_line, 3);
+    }
+
+    #[test]
+    fn test_comptime_block_owns_its_identifiers() {
+        let content =
+            "const std = @import(\"std\");\n\ncomptime {\n    assert(@sizeOf(u8) == 1);\n}\n";
+        let ctx = EnrichmentContext::new("main.zig", "zig", content, Path::new("."));
+        let call = content.find("assert").unwrap();
+        let mut results = ExtractionResults {
+            identifiers: vec![ctx.create_identifier(
+                "assert",
+                IdentifierKind::Call,
+                call,
+                call + 6,
+                None,
+            )],
+            ..test_results(Vec::new())
+        };
+        enrich_comptime_blocks(&ctx, &mut results);
+
+        assert_eq!(results.symbols.len(), 1);
+        let block = &results.symbols[0];
+        assert_eq!(block.name, "comptime");
+        assert_eq!(block.start_line, 3);
+        assert_eq!(
+            results.identifiers[0].containing_symbol_id.as_deref(),
+            Some(block.id.as_str())
+        );
+    }
+}
@@ -0,0 +1,472 @@
+// Zig errors
+//
+// Zig's error handling is spelled out in the source (error sets, `!T` return
+// types, `try`, `catch`) but Julie sees ordinary constants, types, and calls.
+// This pass:
+//
+// - marks `const E = error{ A, B };` declarations (metadata.isErrorSet,
+//   errorTags) and adds each tag as an EnumMember child, adding an Enum
+//   symbol for the set when Julie has none
+// - marks functions whose return type is an error union
+//   (metadata.returnsErrorUnion) with the set before the `!` in
+//   metadata.errorSet: a name (`FileError`, `anyerror`), an inline
+//   `error{...}`, or "inferred" for `!T`
+// - records error propagation on the enclosing function, since Julie
+//   identifiers carry no metadata: metadata.propagatesError when it uses
+//   `try`, tryCalls `{ call, line }` for each `try f(...)`, and catches
+//   `{ call, line, capture, handler, handles }` for each `catch`, where
+//   capture is the `|err|` name, handler the expression after it (absent for
+//   a block), and handles the `error.Tag`s the handler names. A Call
+//   identifier is added for a `try` callee Julie missed.
+
+use super::comptime::FN_RE;
+use super::ZIG;
+use crate::enrichment::base::{
+    append_metadata, code_mask, find_matching_delimiter, innermost_symbol_at, set_metadata,
+    EnrichmentContext, SymbolOptions,
+};
+use julie_extractors::{ExtractionResults, IdentifierKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::{json, Map, Value};
+use std::sync::LazyLock;
+
+/// `const FileError = error{`
+static ERROR_SET_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\bconst\s+([A-Za-z_]\w*)\s*(?::\s*type\s*)?=\s*error\s*\{").unwrap()
+});
+
+static TAG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[A-Za-z_]\w*").unwrap());
+
+/// Attributes between a parameter list and the return type
+static CALLING_ATTRIBUTE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:callconv|align|addrspace|linksection)\s*\(").unwrap());
+
+/// `try self.read(`
+static TRY_CALL_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\btry\s+([A-Za-z_][\w.]*)\s*\(").unwrap());
+
+static TRY_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\btry\b").unwrap());
+
+static CATCH_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\bcatch\b\s*(?:\|\s*([A-Za-z_]\w*)\s*\|)?\s*").unwrap());
+
+static ERROR_TAG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\berror\.([A-Za-z_]\w*)").unwrap());
+
+pub(super) fn enrich_errors(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    if !ctx.content.contains("error") && !ctx.content.contains('!') {
+        return;
+    }
+    let code = code_mask(ctx.content, ZIG);
+    extract_error_sets(ctx, &code, &mut results.symbols);
+    mark_error_unions(ctx, &mut results.symbols);
+    record_propagation(ctx, &code, results);
+}
+
+fn extract_error_sets(ctx: &EnrichmentContext, code: &[bool], symbols: &mut Vec<Symbol>) {
+    for captures in ERROR_SET_RE.captures_iter(ctx.content) {
+        let declaration = captures.get(0).unwrap();
+        if !code[declaration.start()] {
+            continue;
+        }
+        let open = declaration.end() - 1;
+        let Some(close) = find_matching_delimiter(ctx.content, open, ZIG) else {
+            continue;
+        };
+        let name = &captures[1];
+        let tags: Vec<(usize, &str)> = TAG_RE
+            .find_iter(&ctx.content[open + 1..close])
+            .map(|tag| (open + 1 + tag.start(), tag.as_str()))
+            .filter(|(start, _)| code[*start])
+            .collect();
+        let signature = ctx
+            .text(declaration.start(), close + 1)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let line = ctx.line_of(declaration.start());
+        let set_index = match symbols
+            .iter()
+            .position(|s| s.name == name && s.start_line == line)
+        {
+            Some(index) => index,
+            None => {
+                let end = ctx.content[close..]
+                    .find(';')
+                    .map_or(close + 1, |i| close + i + 1);
+                symbols.push(ctx.create_symbol(
+                    name,
+                    SymbolKind::Enum,
+                    declaration.start(),
+                    end,
+                    SymbolOptions {
+                        signature: Some(signature),
+                        ..Default::default()
+                    },
+                ));
+                symbols.len() - 1
+            }
+        };
+        let set = &mut symbols[set_index];
+        set_metadata(set, "isErrorSet", true);
+        set_metadata(
+            set,
+            "errorTags",
+            tags.iter().map(|(_, tag)| *tag).collect::<Vec<_>>(),
+        );
+
+        let set_id = set.id.clone();
+        for (start, tag) in tags {
+            let exists = symbols
+                .iter()
+                .any(|s| s.parent_id.as_deref() == Some(&set_id) && s.name == tag);
+            if exists {
+                continue;
+            }
+            symbols.push(ctx.create_symbol(
+                tag,
+                SymbolKind::EnumMember,
+                start,
+                start + tag.len(),
+                SymbolOptions {
+                    signature: Some(format!("error.{}", tag)),
+                    parent_id: Some(set_id.clone()),
+                    ..Default::default()
+                },
+            ));
+        }
+    }
+}
+
+fn mark_error_unions(ctx: &EnrichmentContext, symbols: &mut [Symbol]) {
+    for function in symbols
+        .iter_mut()
+        .filter(|s| matches!(s.kind, SymbolKind::Function | SymbolKind::Method))
+    {
+        let text = ctx.text(function.start_byte as usize, function.end_byte as usize);
+        let Some(open) = FN_RE
+            .captures_iter(text)
+            .find(|c| c[1] == function.name)
+            .map(|c| c.get(0).unwrap().end() - 1)
+        else {
+            continue;
+        };
+        let Some(close) = find_matching_delimiter(text, open, ZIG) else {
+            continue;
+        };
+        if let Some(error_set) = error_union_set(text, close + 1) {
+            set_metadata(function, "returnsErrorUnion", true);
+            set_metadata(function, "errorSet", error_set);
+        }
+    }
+}
+
+/// The error set of the return type starting at `start`, if it is an error union
+fn error_union_set(text: &str, start: usize) -> Option<String> {
+    let mut start = start;
+    while let Some(attribute) = CALLING_ATTRIBUTE_RE.find(&text[start..]) {
+        start = find_matching_delimiter(text, start + attribute.end() - 1, ZIG)? + 1;
+    }
+
+    let bytes = text.as_bytes();
+    let mut index = start;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'!' => {
+                let error_set = text[start..index].split_whitespace().collect::<Vec<_>>();
+                return Some(match error_set.join(" ") {
+                    set if set.is_empty() => "inferred".to_string(),
+                    set => set,
+                });
+            }
+            b'(' | b'[' => index = find_matching_delimiter(text, index, ZIG)?,
+            // An inline error set, not the body
+            b'{' if text[start..index].trim_end().ends_with("error") => {
+                index = find_matching_delimiter(text, index, ZIG)?
+            }
+            b'{' | b';' => return None,
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+fn record_propagation(ctx: &EnrichmentContext, code: &[bool], results: &mut ExtractionResults) {
+    let content = ctx.content;
+    let enclosing = |symbols: &[Symbol], at: usize| {
+        innermost_symbol_at(symbols, at, |s| {
+            matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+        })
+        .map(|s| s.id.clone())
+    };
+
+    for found in TRY_RE.find_iter(content) {
+        if !code[found.start()] {
+            continue;
+        }
+        let Some(function) = enclosing(&results.symbols, found.start()) else {
+            continue;
+        };
+        let mut entry = None;
+        if let Some(call) = TRY_CALL_RE
+            .captures_at(content, found.start())
+            .filter(|c| c.get(0).unwrap().start() == found.start())
+        {
+            let callee = call.get(1).unwrap();
+            let name_start = callee.start() + callee.as_str().rfind('.').map_or(0, |dot| dot + 1);
+            let known = results
+                .identifiers
+                .iter()
+                .any(|i| i.kind == IdentifierKind::Call && i.start_byte as usize == name_start);
+            if !known {
+                results.identifiers.push(ctx.create_identifier(
+                    &content[name_start..callee.end()],
+                    IdentifierKind::Call,
+                    name_start,
+                    callee.end(),
+                    Some(function.clone()),
+                ));
+            }
+            entry = Some(json!({ "call": callee.as_str(), "line": ctx.line_of(found.start()) }));
+        }
+        let symbol = results
+            .symbols
+            .iter_mut()
+            .find(|s| s.id == function)
+            .unwrap();
+        set_metadata(symbol, "propagatesError", true);
+        if let Some(entry) = entry {
+            append_metadata(symbol, "tryCalls", entry);
+        }
+    }
+
+    for captures in CATCH_RE.captures_iter(content) {
+        let found = captures.get(0).unwrap();
+        if !code[found.start()] {
+            continue;
+        }
+        let Some(function) = enclosing(&results.symbols, found.start()) else {
+            continue;
+        };
+        let mut entry = Map::new();
+        if let Some(call) = call_before(content, found.start()) {
+            entry.insert("call".to_string(), Value::from(call));
+        }
+        entry.insert("line".to_string(), Value::from(ctx.line_of(found.start())));
+        if let Some(capture) = captures.get(1) {
+            entry.insert("capture".to_string(), Value::from(capture.as_str()));
+        }
+        let handler = handler_text(content, found.end());
+        if !handler.starts_with('{') {
+            let expression = handler.split_whitespace().collect::<Vec<_>>().join(" ");
+            entry.insert("handler".to_string(), Value::from(expression));
+        }
+        let mut handles: Vec<&str> = Vec::new();
+        for tag in ERROR_TAG_RE.captures_iter(handler) {
+            let tag = tag.get(1).unwrap().as_str();
+            if !handles.contains(&tag) {
+                handles.push(tag);
+            }
+        }
+        if !handles.is_empty() {
+            entry.insert("handles".to_string(), json!(handles));
+        }
+        let symbol = results
+            .symbols
+            .iter_mut()
+            .find(|s| s.id == function)
+            .unwrap();
+        append_metadata(symbol, "catches", Value::Object(entry));
+    }
+}
+
+/// `self.read` of `self.read(buf) catch`
+fn call_before(content: &str, at: usize) -> Option<&str> {
+    let before = content[..at].trim_end();
+    let close = before.len().checked_sub(1)?;
+    if !before.ends_with(')') {
+        return None;
+    }
+    let bytes = content.as_bytes();
+    let mut depth = 0usize;
+    let mut open = None;
+    for index in (0..=close).rev() {
+        match bytes[index] {
+            b')' => depth += 1,
+            b'(' => {
+                depth -= 1;
+                if depth == 0 {
+                    open = Some(index);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let callee = content[..open?].trim_end();
+    let start = callee
+        .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '.'))
+        .map_or(0, |i| i + 1);
+    Some(&callee[start..]).filter(|callee| !callee.is_empty())
+}
+
+/// The handler after `catch [|err|]`: a block, or an expression up to the
+/// end of the statement or enclosing argument
+fn handler_text(content: &str, start: usize) -> &str {
+    let bytes = content.as_bytes();
+    if bytes.get(start) == Some(&b'{') {
+        return find_matching_delimiter(content, start, ZIG)
+            .map_or(&content[start..], |close| &content[start..=close]);
+    }
+    let mut index = start;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'(' | b'[' | b'{' => match find_matching_delimiter(content, index, ZIG) {
+                Some(close) => index = close,
+                None => break,
+            },
+            b';' | b',' | b')' | b']' | b'}' => break,
+            _ => {}
+        }
+        index += 1;
+    }
+    content[start..index].trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::path::Path;
+
+    const SOURCE: &str = r#"const FileError = error{
+    NotFound,
+    // retried by the caller
+    Timeout,
+};
+
+pub fn open(path: []const u8) FileError!File {
+    const file = try fs.openFile(path);
+    return file;
+}
+
+fn load() !void {
+    const f = open("a") catch |err| switch (err) {
+        error.NotFound => return,
+        error.Timeout => return error.Timeout,
+    };
+    const n = parse(f) catch 0;
+    try f.close();
+}
+
+fn inline_set() error{Oops}!u8 {
+    return 1;
+}
+
+fn plain() void {}
+"#;
+
+    fn results(ctx: &EnrichmentContext) -> ExtractionResults {
+        let function = |name: &str| {
+            let start = SOURCE.find(&format!("fn {}", name)).unwrap();
+            let start = SOURCE[..start].rfind('\n').map_or(0, |i| i + 1);
+            let end = SOURCE[start..]
+                .find("\n\n")
+                .map_or(SOURCE.len(), |i| start + i);
+            ctx.create_symbol(
+                name,
+                SymbolKind::Function,
+                start,
+                end,
+                SymbolOptions::default(),
+            )
+        };
+        test_results(
+            ["open", "load", "inline_set", "plain"]
+                .into_iter()
+                .map(function)
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_error_sets_and_error_union_returns() {
+        let ctx = EnrichmentContext::new("file.zig", "zig", SOURCE, Path::new("."));
+        let mut results = results(&ctx);
+        enrich_errors(&ctx, &mut results);
+
+        let set = results
+            .symbols
+            .iter()
+            .find(|s| s.name == "FileError")
+            .unwrap();
+        assert_eq!(set.kind, SymbolKind::Enum);
+        assert_eq!(
+            set.metadata.as_ref().unwrap()["errorTags"],
+            json!(["NotFound", "Timeout"])
+        );
+        let tags: Vec<(&str, Option<&str>)> = results
+            .symbols
+            .iter()
+            .filter(|s| s.parent_id.as_ref() == Some(&set.id))
+            .map(|s| (s.name.as_str(), s.signature.as_deref()))
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                ("NotFound", Some("error.NotFound")),
+                ("Timeout", Some("error.Timeout")),
+            ]
+        );
+
+        let error_sets: Vec<Option<&Value>> = results.symbols[..4]
+            .iter()
+            .map(|s| s.metadata.as_ref().and_then(|m| m.get("errorSet")))
+            .collect();
+        assert_eq!(
+            error_sets,
+            vec![
+                Some(&json!("FileError")),
+                Some(&json!("inferred")),
+                Some(&json!("error{Oops}")),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_and_catch_are_recorded_on_the_function() {
+        let ctx = EnrichmentContext::new("file.zig", "zig", SOURCE, Path::new("."));
+        let mut results = results(&ctx);
+        enrich_errors(&ctx, &mut results);
+
+        let open = results.symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(open["propagatesError"], json!(true));
+        assert_eq!(
+            open["tryCalls"],
+            json!([{ "call": "fs.openFile", "line": 8 }])
+        );
+        let load = results.symbols[1].metadata.as_ref().unwrap();
+        assert_eq!(
+            load["catches"],
+            json!([
+                {
+                    "call": "open",
+                    "line": 13,
+                    "capture": "err",
+                    "handler": "switch (err) { error.NotFound => return, error.Timeout => return error.Timeout, }",
+                    "handles": ["NotFound", "Timeout"],
+                },
+                { "call": "parse", "line": 17, "handler": "0" },
+            ])
+        );
+        let calls: Vec<&str> = results
+            .identifiers
+            .iter()
+            .map(|i| i.name.as_str())
+            .collect();
+        assert_eq!(calls, vec!["openFile", "close"]);
+    }
+}
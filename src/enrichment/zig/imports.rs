@@ -0,0 +1,272 @@
+// Zig @import tracking
+//
+// Zig files wire themselves together with declarations like
+// `const util = @import("util.zig");`, which Julie extracts as plain
+// constants. This pass turns each such binding into an Import symbol whose
+// metadata carries the import string: `.zig` paths are resolved relative to
+// the importing file, while std, builtin, root, and build.zig modules are
+// flagged external.
+//
+// Uses of the binding (`util.helper()`) point back at the import: the `util`
+// identifier gets the import as its target (one is added when Julie has none),
+// and each function using the import gets an Imports relationship to it.
+
+use super::ZIG;
+use crate::enrichment::base::symbol_index::SymbolIndex;
+use crate::enrichment::base::{code_mask, innermost_symbol_at, EnrichmentContext, SymbolOptions};
+use julie_extractors::{ExtractionResults, IdentifierKind, RelationshipKind, Symbol, SymbolKind};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+/// `[pub] const name[: T] = @import("path")`
+static IMPORT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?m)^[ \t]*(?:pub[ \t]+)?const[ \t]+([A-Za-z_]\w*)[ \t]*(?::[^=\n]*)?=[ \t]*@import\([ \t]*"([^"\n]*)"[ \t]*\)[ \t]*;?"#,
+    )
+    .unwrap()
+});
+
+/// Modules the compiler provides rather than the workspace
+const BUILTIN_MODULES: &[&str] = &["std", "builtin", "root"];
+
+pub(super) fn extract_imports(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    let code = code_mask(ctx.content, ZIG);
+
+    for captures in IMPORT_RE.captures_iter(ctx.content) {
+        let whole = captures.get(0).unwrap();
+        let start = whole.start() + (whole.as_str().len() - whole.as_str().trim_start().len());
+        if !code[start] {
+            continue;
+        }
+        let binding = captures.get(1).unwrap();
+        let import_path = &captures[2];
+
+        let is_file = import_path.ends_with(".zig");
+        let resolved_path = is_file
+            .then(|| ctx.resolve_relative_to_file(import_path))
+            .flatten();
+        let mut metadata = HashMap::new();
+        metadata.insert("importPath".to_string(), Value::from(import_path));
+        metadata.insert("external".to_string(), Value::Bool(!is_file));
+        if BUILTIN_MODULES.contains(&import_path) {
+            metadata.insert("builtin".to_string(), Value::Bool(true));
+        }
+        if is_file {
+            metadata.insert("resolved".to_string(), Value::Bool(resolved_path.is_some()));
+        }
+        if let Some(path) = resolved_path {
+            metadata.insert("resolvedPath".to_string(), Value::from(path));
+        }
+
+        let import_id = bind_import(
+            ctx,
+            &mut results.symbols,
+            binding.as_str(),
+            start,
+            whole.end(),
+            metadata,
+        );
+        link_uses(
+            ctx,
+            results,
+            &code,
+            binding.as_str(),
+            whole.end(),
+            &import_id,
+        );
+    }
+}
+
+/// Turn the constant Julie extracted for the binding into an Import symbol,
+/// or add one. Returns the import's ID.
+fn bind_import(
+    ctx: &EnrichmentContext,
+    symbols: &mut Vec<Symbol>,
+    name: &str,
+    start: usize,
+    end: usize,
+    metadata: HashMap<String, Value>,
+) -> String {
+    let signature = ctx.text(start, end).trim_end_matches(';').to_string();
+    if let Some(existing) = symbols
+        .iter_mut()
+        .find(|s| s.name == name && start <= s.start_byte as usize && (s.start_byte as usize) < end)
+    {
+        existing.kind = SymbolKind::Import;
+        existing.signature = Some(signature);
+        existing
+            .metadata
+            .get_or_insert_with(HashMap::new)
+            .extend(metadata);
+        return existing.id.clone();
+    }
+
+    let parent_id = innermost_symbol_at(symbols, start, |s| {
+        matches!(
+            s.kind,
+            SymbolKind::Function | SymbolKind::Method | SymbolKind::Struct
+        )
+    })
+    .map(|s| s.id.clone());
+    let import = ctx.create_symbol(
+        name,
+        SymbolKind::Import,
+        start,
+        end,
+        SymbolOptions {
+            signature: Some(signature),
+            parent_id,
+            metadata: Some(metadata),
+            ..Default::default()
+        },
+    );
+    let id = import.id.clone();
+    symbols.push(import);
+    id
+}
+
+/// Point `name.member` uses after the declaration at the import
+fn link_uses(
+    ctx: &EnrichmentContext,
+    results: &mut ExtractionResults,
+    code: &[bool],
+    name: &str,
+    declaration_end: usize,
+    import_id: &str,
+) {
+    let Ok(use_re) = Regex::new(&format!(r"\b{}\s*\.\s*[A-Za-z_@]", regex::escape(name))) else {
+        return;
+    };
+    let bytes = ctx.content.as_bytes();
+    let mut users = HashSet::new();
+    let mut owners = Vec::new();
+    {
+        let index = SymbolIndex::new(&results.symbols);
+        for found in use_re.find_iter(&ctx.content[declaration_end..]) {
+            let start = declaration_end + found.start();
+            if !code[start] || (start > 0 && bytes[start - 1] == b'.') {
+                continue;
+            }
+            let user = index
+                .innermost_at(start, |s| {
+                    matches!(s.kind, SymbolKind::Function | SymbolKind::Method)
+                })
+                .map(|s| s.id.clone());
+            owners.push((start, user));
+        }
+    }
+
+    for (start, user) in owners {
+        match results
+            .identifiers
+            .iter_mut()
+            .find(|i| i.start_byte as usize == start && i.name == name)
+        {
+            Some(identifier) => identifier.target_symbol_id = Some(import_id.to_string()),
+            None => {
+                let mut identifier = ctx.create_identifier(
+                    name,
+                    IdentifierKind::VariableRef,
+                    start,
+                    start + name.len(),
+                    user.clone(),
+                );
+                identifier.target_symbol_id = Some(import_id.to_string());
+                results.identifiers.push(identifier);
+            }
+        }
+
+        if let Some(user) = user {
+            if users.insert(user.clone()) {
+                results.relationships.push(ctx.create_relationship(
+                    &user,
+                    import_id,
+                    RelationshipKind::Imports,
+                    start,
+                    None,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn extract(content: &str, root: &Path, symbols: Vec<Symbol>) -> ExtractionResults {
+        let ctx = EnrichmentContext::new("src/main.zig", "zig", content, root);
+        let mut results = test_results(symbols);
+        extract_imports(&ctx, &mut results);
+        results
+    }
+
+    #[test]
+    fn test_imports_resolve_files_and_flag_std() {
+        let workspace = TempDir::new().unwrap();
+        fs::create_dir_all(workspace.path().join("src")).unwrap();
+        fs::write(workspace.path().join("src/util.zig"), "").unwrap();
+
+        let content = "const std = @import(\"std\");\nconst util = @import(\"util.zig\");\n";
+        let results = extract(content, workspace.path(), Vec::new());
+        assert_eq!(results.symbols.len(), 2);
+
+        let std = results.symbols[0].metadata.as_ref().unwrap();
+        assert_eq!(results.symbols[0].kind, SymbolKind::Import);
+        assert_eq!(std["external"], Value::Bool(true));
+        assert_eq!(std["builtin"], Value::Bool(true));
+
+        let util = results.symbols[1].metadata.as_ref().unwrap();
+        assert_eq!(util["importPath"], Value::from("util.zig"));
+        assert_eq!(util["resolvedPath"], Value::from("src/util.zig"));
+        assert_eq!(util["external"], Value::Bool(false));
+        assert_eq!(
+            results.symbols[1].signature.as_deref(),
+            Some("const util = @import(\"util.zig\")")
+        );
+    }
+
+    #[test]
+    fn test_member_access_links_back_to_import() {
+        let content = "const util = @import(\"util.zig\");\n\npub fn main() void {\n    util.helper();\n    util.helper();\n}\n";
+        let ctx = EnrichmentContext::new("src/main.zig", "zig", content, Path::new("."));
+        let start = content.find("const util").unwrap();
+        let constant = ctx.create_symbol(
+            "util",
+            SymbolKind::Constant,
+            start,
+            start + 33,
+            SymbolOptions::default(),
+        );
+        let main_start = content.find("pub fn").unwrap();
+        let main = ctx.create_symbol(
+            "main",
+            SymbolKind::Function,
+            main_start,
+            content.len() - 1,
+            SymbolOptions::default(),
+        );
+        let results = extract(content, Path::new("."), vec![constant, main]);
+
+        assert_eq!(results.symbols.len(), 2, "the constant becomes the import");
+        let import = &results.symbols[0];
+        assert_eq!(import.kind, SymbolKind::Import);
+        assert_eq!(results.identifiers.len(), 2);
+        assert!(results
+            .identifiers
+            .iter()
+            .all(|i| i.target_symbol_id.as_deref() == Some(import.id.as_str())));
+        assert_eq!(results.relationships.len(), 1);
+        assert_eq!(results.relationships[0].kind, RelationshipKind::Imports);
+        assert_eq!(
+            results.relationships[0].from_symbol_id,
+            results.symbols[1].id
+        );
+    }
+}
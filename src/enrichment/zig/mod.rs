@@ -0,0 +1,33 @@
+// Zig enrichment passes
+//
+// Julie's Zig extractor produces functions, structs, and variable declarations;
+// these passes add what idiomatic Zig generics need to be navigable: comptime
+// parameters, type-constructor functions and the fields of the structs they
+// return, and top-level comptime blocks as scopes of their own. @import
+// bindings become Import symbols linked to the code that uses them, and error
+// sets, error-union returns, and try/catch sites are recorded for tracing
+// error propagation.
+
+mod comptime;
+mod errors;
+mod imports;
+
+use super::base::{EnrichmentContext, Lexicon};
+use julie_extractors::ExtractionResults;
+
+pub const EXTRACTOR_VERSION: u32 = 2;
+
+/// Zig has no block comments; '...' is a character literal
+pub(super) const ZIG: Lexicon = Lexicon {
+    quotes: b"\"'",
+    escape: Some(b'\\'),
+    line_comment: Some("//"),
+    block_comment: None,
+};
+
+pub fn enrich(ctx: &EnrichmentContext, results: &mut ExtractionResults) {
+    comptime::enrich_functions(ctx, &mut results.symbols);
+    comptime::enrich_comptime_blocks(ctx, results);
+    imports::extract_imports(ctx, results);
+    errors::enrich_errors(ctx, results);
+}
@@ -0,0 +1,27 @@
+// JSON export - one file's results as a single FileRecord object
+
+use super::FileRecord;
+use crate::engine::{self, EnrichmentOptions, ExtractionLimits, FileExtraction};
+use std::path::Path;
+
+/// Serialize a file's extraction as a JSON object
+pub fn file_to_json(file: &FileExtraction) -> serde_json::Result<String> {
+    serde_json::to_string(&FileRecord::from(file))
+}
+
+/// Read and extract a file relative to the workspace root as a FileRecord
+pub fn extract_file_record(
+    rel_path: &str,
+    workspace_root: &Path,
+    options: &EnrichmentOptions,
+) -> FileRecord {
+    match engine::extract_path(
+        rel_path,
+        workspace_root,
+        options,
+        &ExtractionLimits::default(),
+    ) {
+        Ok(file) => FileRecord::from(&file),
+        Err(e) => FileRecord::failed(rel_path, &e),
+    }
+}
@@ -0,0 +1,22 @@
+// JSON export - serializable extraction results with stable field names
+//
+// Julie's Symbol, Identifier, and Relationship are foreign types whose serde
+// names follow Julie's internals, so exports use Miller-owned records that
+// mirror them with camelCase field names (the convention of the metadata keys,
+// e.g. isView). Metadata values pass through untouched; kinds and visibility
+// keep Julie's serde names ("function", "member_access"). Records convert back
+// to Julie's types for offline processing, except ExtractionResults.types,
+// which Miller never fills and does not export.
+//
+// records holds that canonical form; json and ndjson are the output formats
+// built on it.
+
+mod json;
+mod ndjson;
+mod records;
+
+pub use json::{extract_file_record, file_to_json};
+pub use ndjson::{extract_files_to_ndjson, write_ndjson_line, NdjsonSummary};
+pub use records::{
+    FileRecord, IdentifierRecord, PendingRelationshipRecord, RelationshipRecord, SymbolRecord,
+};
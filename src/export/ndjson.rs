@@ -0,0 +1,102 @@
+// NDJSON export - one FileRecord per line, streamed as files are extracted
+//
+// Files are extracted in parallel a chunk at a time and written before the
+// next chunk starts, so a large workspace is never held in memory at once.
+
+use super::{extract_file_record, FileRecord};
+use crate::engine::EnrichmentOptions;
+use rayon::prelude::*;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Files extracted in parallel before their records are written
+const NDJSON_CHUNK: usize = 64;
+
+/// Counts from an NDJSON export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NdjsonSummary {
+    /// Lines written, one per input path
+    pub files_written: usize,
+    /// Lines recording an error instead of results
+    pub files_failed: usize,
+}
+
+/// Write one record as a line of NDJSON
+pub fn write_ndjson_line<W: Write>(writer: &mut W, record: &FileRecord) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n")
+}
+
+/// Extract files in parallel and stream one JSON object per file to `output_path`
+///
+/// Lines follow the order of `rel_paths`. Files that cannot be read or
+/// extracted are written as records with `error` and `errorKind` fields; only
+/// failing to write the output is an error.
+pub fn extract_files_to_ndjson(
+    rel_paths: &[String],
+    workspace_root: &Path,
+    output_path: &Path,
+    options: &EnrichmentOptions,
+) -> io::Result<NdjsonSummary> {
+    let mut writer = BufWriter::new(File::create(output_path)?);
+    let mut summary = NdjsonSummary::default();
+
+    for chunk in rel_paths.chunks(NDJSON_CHUNK) {
+        let records: Vec<FileRecord> = chunk
+            .par_iter()
+            .map(|rel_path| extract_file_record(rel_path, workspace_root, options))
+            .collect();
+        for record in &records {
+            write_ndjson_line(&mut writer, record)?;
+            summary.files_written += 1;
+            if record.error.is_some() {
+                summary.files_failed += 1;
+            }
+        }
+    }
+
+    writer.flush()?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_ndjson_writes_one_line_per_file_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.unknownext"), "hello").unwrap();
+        let output = dir.path().join("out.ndjson");
+        let paths = vec!["notes.unknownext".to_string(), "missing.py".to_string()];
+
+        let summary =
+            extract_files_to_ndjson(&paths, dir.path(), &output, &EnrichmentOptions::default())
+                .unwrap();
+        assert_eq!(
+            summary,
+            NdjsonSummary {
+                files_written: 2,
+                files_failed: 1
+            }
+        );
+
+        let text = std::fs::read_to_string(&output).unwrap();
+        let lines: Vec<Value> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["filePath"], "notes.unknownext");
+        assert_eq!(lines[0]["language"], "text");
+        assert_eq!(lines[0]["symbols"], json!([]));
+        assert_eq!(lines[1]["filePath"], "missing.py");
+        assert!(lines[1]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Read error"));
+        assert_eq!(lines[1]["errorKind"], "read_error");
+    }
+}
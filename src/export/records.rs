@@ -0,0 +1,417 @@
+// Export records - Miller-owned, camelCase mirrors of Julie's result types
+//
+// This is the canonical form shared by every export format: kinds and
+// visibility keep Julie's serde names, metadata passes through untouched, and
+// records convert back to Julie's types for offline processing.
+
+use crate::engine::{self, Diagnostic, ExtractionError, ExtractionResults, FileExtraction};
+use julie_extractors::{
+    Identifier, IdentifierKind, PendingRelationship, Relationship, RelationshipKind, Symbol,
+    SymbolKind, Visibility,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolRecord {
+    pub id: String,
+    pub name: String,
+    pub kind: SymbolKind,
+    pub language: String,
+    pub file_path: String,
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+    pub start_byte: u32,
+    pub end_byte: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub semantic_group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_context: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentifierRecord {
+    pub id: String,
+    pub name: String,
+    pub kind: IdentifierKind,
+    pub language: String,
+    pub file_path: String,
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+    pub start_byte: u32,
+    pub end_byte: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub containing_symbol_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_symbol_id: Option<String>,
+    pub confidence: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_context: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelationshipRecord {
+    pub id: String,
+    pub from_symbol_id: String,
+    pub to_symbol_id: String,
+    pub kind: RelationshipKind,
+    pub file_path: String,
+    pub line_number: u32,
+    pub confidence: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, Value>>,
+}
+
+/// A relationship whose target is resolved across files (e.g. by name)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingRelationshipRecord {
+    pub from_symbol_id: String,
+    pub callee_name: String,
+    pub kind: RelationshipKind,
+    pub file_path: String,
+    pub line_number: u32,
+    pub confidence: f32,
+}
+
+/// Everything extracted from one file: a line of NDJSON output
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileRecord {
+    pub file_path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// blake3 hex digest of the content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(default)]
+    pub symbols: Vec<SymbolRecord>,
+    #[serde(default)]
+    pub identifiers: Vec<IdentifierRecord>,
+    #[serde(default)]
+    pub relationships: Vec<RelationshipRecord>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pending_relationships: Vec<PendingRelationshipRecord>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+    /// Why the file has no results (read error, panic, timeout, nesting limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// ExtractionError kind of `error`: "read_error", "panicked", "timed_out", ...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<String>,
+}
+
+impl FileRecord {
+    /// Record for a file's extraction results
+    pub fn from_results(file_path: &str, results: &ExtractionResults) -> Self {
+        FileRecord {
+            file_path: file_path.to_string(),
+            symbols: results.symbols.iter().map(SymbolRecord::from).collect(),
+            identifiers: results
+                .identifiers
+                .iter()
+                .map(IdentifierRecord::from)
+                .collect(),
+            relationships: results
+                .relationships
+                .iter()
+                .map(RelationshipRecord::from)
+                .collect(),
+            pending_relationships: results
+                .pending_relationships
+                .iter()
+                .map(PendingRelationshipRecord::from)
+                .collect(),
+            diagnostics: engine::diagnose(results),
+            ..FileRecord::default()
+        }
+    }
+
+    /// Record for a file that could not be read or extracted
+    pub fn failed(file_path: &str, error: &ExtractionError) -> Self {
+        FileRecord {
+            file_path: file_path.to_string(),
+            error: Some(error.to_string()),
+            error_kind: Some(error.kind().to_string()),
+            ..FileRecord::default()
+        }
+    }
+
+    /// Convert back to Julie's results (types are not exported and stay empty)
+    pub fn into_results(self) -> ExtractionResults {
+        ExtractionResults {
+            symbols: self.symbols.into_iter().map(Symbol::from).collect(),
+            identifiers: self.identifiers.into_iter().map(Identifier::from).collect(),
+            relationships: self
+                .relationships
+                .into_iter()
+                .map(Relationship::from)
+                .collect(),
+            pending_relationships: self
+                .pending_relationships
+                .into_iter()
+                .map(PendingRelationship::from)
+                .collect(),
+            types: HashMap::new(),
+        }
+    }
+}
+
+impl From<&FileExtraction> for FileRecord {
+    fn from(file: &FileExtraction) -> Self {
+        let mut record = match &file.results {
+            Some(results) => FileRecord::from_results(&file.path, results),
+            None => FileRecord {
+                file_path: file.path.clone(),
+                ..FileRecord::default()
+            },
+        };
+        record.language = Some(file.language.clone());
+        record.hash = Some(file.hash.clone());
+        record
+    }
+}
+
+impl From<&Symbol> for SymbolRecord {
+    fn from(symbol: &Symbol) -> Self {
+        SymbolRecord {
+            id: symbol.id.clone(),
+            name: symbol.name.clone(),
+            kind: symbol.kind.clone(),
+            language: symbol.language.clone(),
+            file_path: symbol.file_path.clone(),
+            start_line: symbol.start_line,
+            start_column: symbol.start_column,
+            end_line: symbol.end_line,
+            end_column: symbol.end_column,
+            start_byte: symbol.start_byte,
+            end_byte: symbol.end_byte,
+            signature: symbol.signature.clone(),
+            doc_comment: symbol.doc_comment.clone(),
+            visibility: symbol.visibility.clone(),
+            parent_id: symbol.parent_id.clone(),
+            metadata: symbol.metadata.clone(),
+            semantic_group: symbol.semantic_group.clone(),
+            confidence: symbol.confidence,
+            code_context: symbol.code_context.clone(),
+            content_type: symbol.content_type.clone(),
+        }
+    }
+}
+
+impl From<SymbolRecord> for Symbol {
+    fn from(record: SymbolRecord) -> Self {
+        Symbol {
+            id: record.id,
+            name: record.name,
+            kind: record.kind,
+            language: record.language,
+            file_path: record.file_path,
+            start_line: record.start_line,
+            start_column: record.start_column,
+            end_line: record.end_line,
+            end_column: record.end_column,
+            start_byte: record.start_byte,
+            end_byte: record.end_byte,
+            signature: record.signature,
+            doc_comment: record.doc_comment,
+            visibility: record.visibility,
+            parent_id: record.parent_id,
+            metadata: record.metadata,
+            semantic_group: record.semantic_group,
+            confidence: record.confidence,
+            code_context: record.code_context,
+            content_type: record.content_type,
+        }
+    }
+}
+
+impl From<&Identifier> for IdentifierRecord {
+    fn from(identifier: &Identifier) -> Self {
+        IdentifierRecord {
+            id: identifier.id.clone(),
+            name: identifier.name.clone(),
+            kind: identifier.kind.clone(),
+            language: identifier.language.clone(),
+            file_path: identifier.file_path.clone(),
+            start_line: identifier.start_line,
+            start_column: identifier.start_column,
+            end_line: identifier.end_line,
+            end_column: identifier.end_column,
+            start_byte: identifier.start_byte,
+            end_byte: identifier.end_byte,
+            containing_symbol_id: identifier.containing_symbol_id.clone(),
+            target_symbol_id: identifier.target_symbol_id.clone(),
+            confidence: identifier.confidence,
+            code_context: identifier.code_context.clone(),
+        }
+    }
+}
+
+impl From<IdentifierRecord> for Identifier {
+    fn from(record: IdentifierRecord) -> Self {
+        Identifier {
+            id: record.id,
+            name: record.name,
+            kind: record.kind,
+            language: record.language,
+            file_path: record.file_path,
+            start_line: record.start_line,
+            start_column: record.start_column,
+            end_line: record.end_line,
+            end_column: record.end_column,
+            start_byte: record.start_byte,
+            end_byte: record.end_byte,
+            containing_symbol_id: record.containing_symbol_id,
+            target_symbol_id: record.target_symbol_id,
+            confidence: record.confidence,
+            code_context: record.code_context,
+        }
+    }
+}
+
+impl From<&Relationship> for RelationshipRecord {
+    fn from(relationship: &Relationship) -> Self {
+        RelationshipRecord {
+            id: relationship.id.clone(),
+            from_symbol_id: relationship.from_symbol_id.clone(),
+            to_symbol_id: relationship.to_symbol_id.clone(),
+            kind: relationship.kind.clone(),
+            file_path: relationship.file_path.clone(),
+            line_number: relationship.line_number,
+            confidence: relationship.confidence,
+            metadata: relationship.metadata.clone(),
+        }
+    }
+}
+
+impl From<RelationshipRecord> for Relationship {
+    fn from(record: RelationshipRecord) -> Self {
+        Relationship {
+            id: record.id,
+            from_symbol_id: record.from_symbol_id,
+            to_symbol_id: record.to_symbol_id,
+            kind: record.kind,
+            file_path: record.file_path,
+            line_number: record.line_number,
+            confidence: record.confidence,
+            metadata: record.metadata,
+        }
+    }
+}
+
+impl From<&PendingRelationship> for PendingRelationshipRecord {
+    fn from(pending: &PendingRelationship) -> Self {
+        PendingRelationshipRecord {
+            from_symbol_id: pending.from_symbol_id.clone(),
+            callee_name: pending.callee_name.clone(),
+            kind: pending.kind.clone(),
+            file_path: pending.file_path.clone(),
+            line_number: pending.line_number,
+            confidence: pending.confidence,
+        }
+    }
+}
+
+impl From<PendingRelationshipRecord> for PendingRelationship {
+    fn from(record: PendingRelationshipRecord) -> Self {
+        PendingRelationship {
+            from_symbol_id: record.from_symbol_id,
+            callee_name: record.callee_name,
+            kind: record.kind,
+            file_path: record.file_path,
+            line_number: record.line_number,
+            confidence: record.confidence,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{test_results, EnrichmentContext, SymbolOptions};
+    use serde_json::json;
+    use std::path::Path;
+
+    fn sample_results() -> ExtractionResults {
+        let content = "class UserView: pass\n";
+        let ctx = EnrichmentContext::new("app/views.py", "python", content, Path::new("."));
+        let mut metadata = HashMap::new();
+        metadata.insert("isView".to_string(), json!(true));
+        metadata.insert("routes".to_string(), json!([{"path": "/users"}]));
+        let symbol = ctx.create_symbol(
+            "UserView",
+            SymbolKind::Class,
+            0,
+            content.len() - 1,
+            SymbolOptions {
+                metadata: Some(metadata),
+                ..Default::default()
+            },
+        );
+        let identifier = ctx.create_identifier(
+            "pass",
+            IdentifierKind::VariableRef,
+            16,
+            20,
+            Some(symbol.id.clone()),
+        );
+        let relationship =
+            ctx.create_relationship(&symbol.id, &symbol.id, RelationshipKind::Uses, 0, None);
+        ExtractionResults {
+            identifiers: vec![identifier],
+            relationships: vec![relationship],
+            ..test_results(vec![symbol])
+        }
+    }
+
+    #[test]
+    fn test_records_use_camel_case_and_keep_metadata() {
+        let record = FileRecord::from_results("app/views.py", &sample_results());
+        let json = serde_json::to_value(&record).unwrap();
+
+        assert_eq!(json["filePath"], "app/views.py");
+        let symbol = &json["symbols"][0];
+        assert_eq!(symbol["name"], "UserView");
+        assert_eq!(symbol["startLine"], 1);
+        assert_eq!(symbol["metadata"]["isView"], true);
+        assert_eq!(symbol["metadata"]["routes"], json!([{"path": "/users"}]));
+        assert!(symbol.get("start_line").is_none());
+        assert!(json["identifiers"][0]["containingSymbolId"].is_string());
+        assert!(json["relationships"][0]["fromSymbolId"].is_string());
+        assert!(json.get("error").is_none());
+
+        let round_trip: FileRecord = serde_json::from_value(json).unwrap();
+        let results = round_trip.into_results();
+        assert_eq!(results.symbols[0].name, "UserView");
+        assert_eq!(
+            results.symbols[0].metadata.as_ref().unwrap()["routes"],
+            json!([{"path": "/users"}])
+        );
+        assert_eq!(results.identifiers.len(), 1);
+        assert_eq!(results.relationships.len(), 1);
+    }
+}
@@ -7,10 +7,10 @@
 // This module replaces the Python-based graph processing which was O(V * (V + E))
 // with parallelized Rust using rayon, achieving O((V + E) / cores) practical performance.
 
-use pyo3::prelude::*;
 use petgraph::algo::kosaraju_scc;
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
+use pyo3::prelude::*;
 use rayon::prelude::*;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::VecDeque;
@@ -149,11 +149,7 @@ impl PyGraphProcessor {
 
         // Precompute out-degrees for each node
         let out_degrees: Vec<usize> = (0..node_count)
-            .map(|i| {
-                self.graph
-                    .edges(NodeIndex::new(i))
-                    .count()
-            })
+            .map(|i| self.graph.edges(NodeIndex::new(i)).count())
             .collect();
 
         // Iterative PageRank computation
@@ -226,7 +222,10 @@ impl PyGraphProcessor {
             .enumerate()
             .map(|(idx, id)| {
                 let node = NodeIndex::new(idx);
-                let in_degree = self.graph.neighbors_directed(node, petgraph::Direction::Incoming).count();
+                let in_degree = self
+                    .graph
+                    .neighbors_directed(node, petgraph::Direction::Incoming)
+                    .count();
                 let out_degree = self.graph.edges(node).count();
 
                 // Entry point: called by at least 2 others, calls fewer than called by
@@ -248,7 +247,10 @@ impl PyGraphProcessor {
             .enumerate()
             .map(|(idx, id)| {
                 let node = NodeIndex::new(idx);
-                let in_degree = self.graph.neighbors_directed(node, petgraph::Direction::Incoming).count();
+                let in_degree = self
+                    .graph
+                    .neighbors_directed(node, petgraph::Direction::Incoming)
+                    .count();
                 let out_degree = self.graph.edges(node).count();
                 (id.clone(), in_degree, out_degree)
             })
@@ -509,9 +511,15 @@ mod tests {
         assert_eq!(closure.len(), 3);
 
         // Check specific paths exist
-        let has_a_to_b = closure.iter().any(|(s, t, d)| s == "a" && t == "b" && *d == 1);
-        let has_a_to_c = closure.iter().any(|(s, t, d)| s == "a" && t == "c" && *d == 2);
-        let has_b_to_c = closure.iter().any(|(s, t, d)| s == "b" && t == "c" && *d == 1);
+        let has_a_to_b = closure
+            .iter()
+            .any(|(s, t, d)| s == "a" && t == "b" && *d == 1);
+        let has_a_to_c = closure
+            .iter()
+            .any(|(s, t, d)| s == "a" && t == "c" && *d == 2);
+        let has_b_to_c = closure
+            .iter()
+            .any(|(s, t, d)| s == "b" && t == "c" && *d == 1);
 
         assert!(has_a_to_b, "Missing a->b path");
         assert!(has_a_to_c, "Missing a->c path");
@@ -557,8 +565,12 @@ mod tests {
         let closure = processor.compute_closure(Some(2));
 
         // A can reach B(1) and C(2) but NOT D(3) or E(4)
-        let has_a_to_b = closure.iter().any(|(s, t, d)| s == "a" && t == "b" && *d == 1);
-        let has_a_to_c = closure.iter().any(|(s, t, d)| s == "a" && t == "c" && *d == 2);
+        let has_a_to_b = closure
+            .iter()
+            .any(|(s, t, d)| s == "a" && t == "b" && *d == 1);
+        let has_a_to_c = closure
+            .iter()
+            .any(|(s, t, d)| s == "a" && t == "c" && *d == 2);
         let has_a_to_d = closure.iter().any(|(s, t, _)| s == "a" && t == "d");
         let has_a_to_e = closure.iter().any(|(s, t, _)| s == "a" && t == "e");
 
@@ -678,9 +690,7 @@ mod tests {
     #[test]
     fn test_find_dead_nodes_simple_orphan() {
         // main -> A, B is orphan (no one calls B)
-        let edges = vec![
-            ("main".to_string(), "a".to_string()),
-        ];
+        let edges = vec![("main".to_string(), "a".to_string())];
         let processor = PyGraphProcessor::new(edges);
 
         // Add B as an isolated node by having it call something
@@ -689,7 +699,7 @@ mod tests {
 
         let edges = vec![
             ("main".to_string(), "a".to_string()),
-            ("b".to_string(), "c".to_string()),  // B -> C, but no one calls B
+            ("b".to_string(), "c".to_string()), // B -> C, but no one calls B
         ];
         let processor = PyGraphProcessor::new(edges);
 
@@ -699,7 +709,10 @@ mod tests {
         assert!(dead.contains(&"b".to_string()), "B should be dead");
         assert!(dead.contains(&"c".to_string()), "C should be dead");
         // main and A should NOT be dead
-        assert!(!dead.contains(&"main".to_string()), "main should not be dead");
+        assert!(
+            !dead.contains(&"main".to_string()),
+            "main should not be dead"
+        );
         assert!(!dead.contains(&"a".to_string()), "A should not be dead");
     }
 
@@ -717,8 +730,14 @@ mod tests {
         let dead = processor.find_dead_nodes(vec!["main".to_string()]);
 
         // A and B form a dead cycle - both should be dead
-        assert!(dead.contains(&"a".to_string()), "A should be dead (part of dead cycle)");
-        assert!(dead.contains(&"b".to_string()), "B should be dead (part of dead cycle)");
+        assert!(
+            dead.contains(&"a".to_string()),
+            "A should be dead (part of dead cycle)"
+        );
+        assert!(
+            dead.contains(&"b".to_string()),
+            "B should be dead (part of dead cycle)"
+        );
         // main and X should NOT be dead
         assert!(!dead.contains(&"main".to_string()));
         assert!(!dead.contains(&"x".to_string()));
@@ -776,7 +795,11 @@ mod tests {
         let dead = processor.find_dead_nodes(vec![]);
 
         // All nodes should be dead since no entry points
-        assert_eq!(dead.len(), 3, "All 3 nodes should be dead with no entry points");
+        assert_eq!(
+            dead.len(),
+            3,
+            "All 3 nodes should be dead with no entry points"
+        );
     }
 
     #[test]
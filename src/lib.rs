@@ -2,24 +2,44 @@
 //
 // This is a PyO3 extension module that wraps Julie's battle-tested extractors.
 // Architecture: "Rust Sandwich" - Rust for parsing, Python for orchestration.
+// The extraction pipeline itself lives in `engine` and builds without pyo3
+// (`--no-default-features`); everything Python-facing is behind the default
+// `python` feature.
 
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
 // Miller-specific utilities
 pub mod utils;
 
+// Plain Rust extraction API (language detection, extraction, enrichment)
+pub mod engine;
+
 // PyO3 bindings layer (Miller-specific)
+#[cfg(feature = "python")]
 pub mod bindings;
 
+// Workspace walk + extraction (ignores, size limits, symlink loops)
+pub mod workspace;
+
+// Serializable extraction records for JSON / NDJSON export
+pub mod export;
+
+// Post-extraction enrichment passes layered on Julie's extractors
+pub mod enrichment;
+
 // Rust-native file watcher (replaces Python watchdog)
+#[cfg(feature = "python")]
 pub mod watcher;
 
 // High-performance graph algorithms (transitive closure, PageRank)
+#[cfg(feature = "python")]
 pub mod graph;
 
 /// Miller Core Python module
 ///
 /// Provides tree-sitter-based symbol extraction for 31 programming languages.
+#[cfg(feature = "python")]
 #[pymodule]
 fn miller_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
@@ -28,21 +48,50 @@ fn miller_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(bindings::extract_file, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::detect_language, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::supported_languages, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::extractor_versions, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::extract_files_batch, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::extract_files_batch_with_io, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::hash_content, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::hash_contents_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::hash_files_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::cache_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::clear_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::configure_cache, m)?)?;
 
     // Arrow-based extraction (zero-copy Python data transfer)
     m.add_function(wrap_pyfunction!(bindings::extract_files_to_arrow, m)?)?;
 
+    // Whole-workspace walk and extraction in one call
+    m.add_function(wrap_pyfunction!(bindings::extract_workspace, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::is_ignored, m)?)?;
+
+    // JSON / NDJSON export (bulk indexing pipelines)
+    m.add_function(wrap_pyfunction!(bindings::extract_file_to_json, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::extract_files_to_ndjson, m)?)?;
+
+    // Cross-language correlation over many files' results
+    m.add_function(wrap_pyfunction!(bindings::correlate_endpoints, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::correlate_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(bindings::resolve_cross_file, m)?)?;
+
+    // Token-budget shaping of extraction results
+    m.add_function(wrap_pyfunction!(bindings::reduce_results, m)?)?;
+
+    // Query-driven ranking of extracted symbols
+    m.add_function(wrap_pyfunction!(bindings::rank_symbols, m)?)?;
+
     // Add Python classes
     m.add_class::<bindings::PySymbol>()?;
     m.add_class::<bindings::PyIdentifier>()?;
     m.add_class::<bindings::PyRelationship>()?;
     m.add_class::<bindings::PyExtractionResults>()?;
+    m.add_class::<bindings::PyDiagnostic>()?;
     m.add_class::<bindings::PyBatchFileResult>()?;
     m.add_class::<bindings::PyArrowExtractionBatch>()?;
+    m.add_class::<bindings::PyEndpointLink>()?;
+    m.add_class::<bindings::PySchemaLink>()?;
+    m.add_class::<bindings::PyColumnLink>()?;
+    m.add_class::<bindings::PyReductionReport>()?;
 
     // Rust-native file watcher (replaces Python watchdog)
     m.add_class::<watcher::PyFileWatcher>()?;
@@ -50,5 +99,8 @@ fn miller_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // High-performance graph algorithms
     m.add_class::<graph::PyGraphProcessor>()?;
 
+    // Exception classes raised for per-file extraction failures
+    bindings::register_exceptions(m)?;
+
     Ok(())
 }
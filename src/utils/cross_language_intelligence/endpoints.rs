@@ -0,0 +1,427 @@
+// REST endpoint correlation - frontend HTTP calls ↔ backend route handlers
+//
+// Both halves come from enrichment metadata:
+//
+// - clients: metadata.httpCalls on the calling symbol (JavaScript/TypeScript
+//   fetch, axios, HttpClient), each `{ callee, method, url, line }`
+// - servers: handler methods with metadata.fullRoutes / metadata.routes (Java
+//   Spring/JAX-RS, PHP attributes) or metadata.fullRoute / metadata.route
+//   (NestJS), plus metadata.httpMethod
+//
+// A handler without a full route has its routes composed with the routes of
+// its parent controller, so results from passes that could not see the class
+// still match. Templates are compared segment by segment after normalizing
+// every placeholder spelling ({id}, {id:int}, :id, ${id}, <id>, [id]) to one
+// parameter marker, with scheme, host, query string, and fragment dropped.
+//
+// Confidence starts at 1.0 and drops for each weaker agreement:
+//
+//   client literal vs server parameter   ×0.9  (/users/me vs /users/{id})
+//   client parameter vs server literal   ×0.7  (/users/{kind} vs /users/admins)
+//   unknown HTTP method on the server    ×0.9
+//   client base-URL placeholder skipped  ×0.8  ({API}/users vs /users)
+//
+// Differing literals, differing segment counts, or a method the server does
+// not accept rule a pair out; so does a pair with no literal segment in
+// common. Links under MIN_ENDPOINT_CONFIDENCE are dropped.
+
+use crate::enrichment::Provenance;
+use julie_extractors::{Identifier, IdentifierKind, Symbol, SymbolKind};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Links below this confidence are not reported
+pub const MIN_ENDPOINT_CONFIDENCE: f32 = 0.5;
+
+/// A client HTTP call matched to the server handler it most likely reaches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointLink {
+    /// Symbol making the call
+    pub client_symbol_id: String,
+    pub client_file_path: String,
+    /// The `fetch` / `get` call identifier, when the extractor recorded one
+    pub call_identifier_id: Option<String>,
+    /// Line of the call
+    pub line: u32,
+    pub http_method: String,
+    /// URL as written by the client, interpolations as `{expr}`
+    pub url: String,
+    /// Handler symbol
+    pub server_symbol_id: String,
+    pub server_file_path: String,
+    /// Route template as declared, including any controller prefix
+    pub route: String,
+    pub confidence: f32,
+    /// Always heuristic: URLs are matched against route templates
+    pub provenance: Provenance,
+}
+
+/// One path segment of a URL or route template
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Param,
+}
+
+struct ClientCall<'s> {
+    symbol: &'s Symbol,
+    callee: String,
+    method: String,
+    url: String,
+    line: u32,
+    segments: Vec<Segment>,
+}
+
+struct ServerRoute<'s> {
+    symbol: &'s Symbol,
+    /// Upper-case methods the handler accepts; empty when unknown
+    methods: Vec<String>,
+    route: String,
+    segments: Vec<Segment>,
+}
+
+/// Match every client HTTP call in `symbols` against every server route
+///
+/// `symbols` and `identifiers` may span any number of files (a whole
+/// workspace). Results are ordered by client file, line, then descending
+/// confidence; a call may link to several handlers.
+pub fn correlate_endpoints(symbols: &[Symbol], identifiers: &[Identifier]) -> Vec<EndpointLink> {
+    let routes = server_routes(symbols);
+    if routes.is_empty() {
+        return Vec::new();
+    }
+
+    let mut links = Vec::new();
+    for call in client_calls(symbols) {
+        for route in &routes {
+            let Some(confidence) = match_call(&call, route) else {
+                continue;
+            };
+            if confidence < MIN_ENDPOINT_CONFIDENCE {
+                continue;
+            }
+            links.push(EndpointLink {
+                client_symbol_id: call.symbol.id.clone(),
+                client_file_path: call.symbol.file_path.clone(),
+                call_identifier_id: call_identifier(&call, identifiers),
+                line: call.line,
+                http_method: call.method.clone(),
+                url: call.url.clone(),
+                server_symbol_id: route.symbol.id.clone(),
+                server_file_path: route.symbol.file_path.clone(),
+                route: route.route.clone(),
+                confidence,
+                provenance: Provenance::Heuristic,
+            });
+        }
+    }
+    links.sort_by(|a, b| {
+        (&a.client_file_path, a.line)
+            .cmp(&(&b.client_file_path, b.line))
+            .then(b.confidence.total_cmp(&a.confidence))
+    });
+    links
+}
+
+fn client_calls(symbols: &[Symbol]) -> Vec<ClientCall<'_>> {
+    let mut calls = Vec::new();
+    for symbol in symbols {
+        let Some(Value::Array(entries)) = symbol.metadata.as_ref().and_then(|m| m.get("httpCalls"))
+        else {
+            continue;
+        };
+        for entry in entries {
+            let field = |key: &str| entry.get(key).and_then(Value::as_str).unwrap_or("");
+            let url = field("url").to_string();
+            calls.push(ClientCall {
+                symbol,
+                callee: field("callee").to_string(),
+                method: field("method").to_uppercase(),
+                segments: route_segments(&url),
+                url,
+                line: entry.get("line").and_then(Value::as_u64).unwrap_or(0) as u32,
+            });
+        }
+    }
+    calls
+}
+
+fn server_routes(symbols: &[Symbol]) -> Vec<ServerRoute<'_>> {
+    let by_id: HashMap<&str, &Symbol> = symbols.iter().map(|s| (s.id.as_str(), s)).collect();
+    let mut routes = Vec::new();
+    for symbol in symbols {
+        if !matches!(symbol.kind, SymbolKind::Method | SymbolKind::Function) {
+            continue;
+        }
+        let Some(metadata) = symbol.metadata.as_ref() else {
+            continue;
+        };
+        let methods: Vec<String> = metadata
+            .get("httpMethod")
+            .and_then(Value::as_str)
+            .map(|m| {
+                m.split(',')
+                    .map(|m| m.trim().to_uppercase())
+                    .filter(|m| !m.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut templates = strings(metadata.get("fullRoutes"));
+        templates.extend(strings(metadata.get("fullRoute")));
+        if templates.is_empty() {
+            let mut own = strings(metadata.get("routes"));
+            own.extend(strings(metadata.get("route")));
+            if own.is_empty() && !methods.is_empty() {
+                // `@GetMapping` with no path serves the controller's route
+                own.push(String::new());
+            }
+            let prefixes = symbol
+                .parent_id
+                .as_deref()
+                .and_then(|id| by_id.get(id))
+                .and_then(|parent| parent.metadata.as_ref())
+                .map(|m| {
+                    let mut prefixes = strings(m.get("routes"));
+                    prefixes.extend(strings(m.get("route")));
+                    prefixes
+                })
+                .unwrap_or_default();
+            templates = if prefixes.is_empty() {
+                own.into_iter().filter(|r| !r.is_empty()).collect()
+            } else {
+                prefixes
+                    .iter()
+                    .flat_map(|prefix| own.iter().map(move |route| join_route(prefix, route)))
+                    .collect()
+            };
+        }
+
+        for route in templates {
+            routes.push(ServerRoute {
+                symbol,
+                methods: methods.clone(),
+                segments: route_segments(&route),
+                route,
+            });
+        }
+    }
+    routes
+}
+
+/// A string or array-of-strings metadata value as a list
+fn strings(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::String(s)) => vec![s.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn join_route(prefix: &str, route: &str) -> String {
+    let parts: Vec<&str> = [prefix, route]
+        .into_iter()
+        .map(|part| part.trim_matches('/'))
+        .filter(|part| !part.is_empty())
+        .collect();
+    format!("/{}", parts.join("/"))
+}
+
+/// `https://host/api/users/{id}?full=1` → [api, users, Param]
+fn route_segments(template: &str) -> Vec<Segment> {
+    let mut path = template.trim();
+    if let Some(scheme_end) = path.find("://") {
+        let after_host = &path[scheme_end + 3..];
+        path = after_host
+            .find('/')
+            .map_or("", |slash| &after_host[slash..]);
+    }
+    let path = path.split(['?', '#']).next().unwrap_or("");
+
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let is_param = segment.starts_with(':')
+                || segment.starts_with('[')
+                || segment.starts_with('<')
+                || segment.contains('{')
+                || segment == "*";
+            if is_param {
+                Segment::Param
+            } else {
+                Segment::Literal(segment.to_lowercase())
+            }
+        })
+        .collect()
+}
+
+/// Confidence that `call` reaches `route`, or None when they cannot match
+fn match_call(call: &ClientCall, route: &ServerRoute) -> Option<f32> {
+    let mut confidence = if route.methods.is_empty() {
+        0.9
+    } else if route.methods.contains(&call.method) {
+        1.0
+    } else {
+        return None;
+    };
+
+    let mut client = call.segments.as_slice();
+    // `${API_BASE}/users` - the base URL stands in front of the route
+    if client.len() == route.segments.len() + 1 && client.first() == Some(&Segment::Param) {
+        client = &client[1..];
+        confidence *= 0.8;
+    }
+    confidence *= match_segments(client, &route.segments)?;
+    Some(confidence)
+}
+
+fn match_segments(client: &[Segment], route: &[Segment]) -> Option<f32> {
+    if client.len() != route.len() {
+        return None;
+    }
+    let mut confidence = 1.0;
+    let mut literal_matches = 0;
+    for (client, route) in client.iter().zip(route) {
+        match (client, route) {
+            (Segment::Literal(a), Segment::Literal(b)) if a == b => literal_matches += 1,
+            (Segment::Literal(_), Segment::Literal(_)) => return None,
+            (Segment::Literal(_), Segment::Param) => confidence *= 0.9,
+            (Segment::Param, Segment::Literal(_)) => confidence *= 0.7,
+            (Segment::Param, Segment::Param) => {}
+        }
+    }
+    (literal_matches > 0).then_some(confidence)
+}
+
+/// The call identifier Julie recorded for this call, matched by owner, name, and line
+fn call_identifier(call: &ClientCall, identifiers: &[Identifier]) -> Option<String> {
+    identifiers
+        .iter()
+        .find(|identifier| {
+            identifier.kind == IdentifierKind::Call
+                && identifier.name == call.callee
+                && identifier.start_line == call.line
+                && identifier.file_path == call.symbol.file_path
+                && identifier.containing_symbol_id.as_deref() == Some(call.symbol.id.as_str())
+        })
+        .map(|identifier| identifier.id.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{set_metadata, EnrichmentContext, SymbolOptions};
+    use serde_json::json;
+    use std::path::Path;
+
+    fn symbol(file: &str, name: &str, kind: SymbolKind, parent: Option<&Symbol>) -> Symbol {
+        let ctx = EnrichmentContext::new(file, "", "", Path::new("."));
+        ctx.create_symbol(
+            name,
+            kind,
+            0,
+            0,
+            SymbolOptions {
+                parent_id: parent.map(|p| p.id.clone()),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn workspace() -> Vec<Symbol> {
+        // Spring controller whose handlers only carry their own routes
+        let mut controller = symbol(
+            "UserController.java",
+            "UserController",
+            SymbolKind::Class,
+            None,
+        );
+        set_metadata(&mut controller, "routes", json!(["/api/users"]));
+        let mut show = symbol(
+            "UserController.java",
+            "show",
+            SymbolKind::Method,
+            Some(&controller),
+        );
+        set_metadata(&mut show, "routes", json!(["/{id}"]));
+        set_metadata(&mut show, "httpMethod", "GET");
+        let mut me = symbol(
+            "UserController.java",
+            "me",
+            SymbolKind::Method,
+            Some(&controller),
+        );
+        set_metadata(&mut me, "routes", json!(["/me"]));
+        set_metadata(&mut me, "httpMethod", "GET");
+        let mut remove = symbol(
+            "UserController.java",
+            "remove",
+            SymbolKind::Method,
+            Some(&controller),
+        );
+        set_metadata(&mut remove, "routes", json!(["/{id}"]));
+        set_metadata(&mut remove, "httpMethod", "DELETE");
+        // NestJS handler with a composed route
+        let mut posts = symbol("posts.controller.ts", "list", SymbolKind::Method, None);
+        set_metadata(&mut posts, "fullRoute", "/api/users/:userId/posts");
+        set_metadata(&mut posts, "httpMethod", "GET");
+
+        let mut client = symbol("api.ts", "loadUser", SymbolKind::Function, None);
+        set_metadata(
+            &mut client,
+            "httpCalls",
+            json!([
+                { "callee": "fetch", "method": "GET", "url": "/api/users/{id}", "line": 2 },
+                { "callee": "get", "method": "GET", "url": "{API}/api/users/{id}/posts?page=2", "line": 3 },
+                { "callee": "fetch", "method": "GET", "url": "https://example.com/health", "line": 4 },
+            ]),
+        );
+        vec![controller, show, me, remove, posts, client]
+    }
+
+    #[test]
+    fn test_calls_match_routes_across_placeholder_styles() {
+        let symbols = workspace();
+        let links = correlate_endpoints(&symbols, &[]);
+
+        let summary: Vec<(u32, &str, &str, f32)> = links
+            .iter()
+            .map(|l| (l.line, l.url.as_str(), l.route.as_str(), l.confidence))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (2, "/api/users/{id}", "/api/users/{id}", 1.0),
+                (2, "/api/users/{id}", "/api/users/me", 0.7),
+                (
+                    3,
+                    "{API}/api/users/{id}/posts?page=2",
+                    "/api/users/:userId/posts",
+                    0.8
+                ),
+            ]
+        );
+        assert_eq!(links[0].server_symbol_id, symbols[1].id);
+        assert_eq!(links[0].server_file_path, "UserController.java");
+    }
+
+    #[test]
+    fn test_links_carry_the_call_identifier() {
+        let symbols = workspace();
+        let client = &symbols[5];
+        let ctx = EnrichmentContext::new("api.ts", "typescript", "x\n  fetch(u)\n", Path::new("."));
+        let identifier =
+            ctx.create_identifier("fetch", IdentifierKind::Call, 4, 9, Some(client.id.clone()));
+
+        let links = correlate_endpoints(&symbols, std::slice::from_ref(&identifier));
+
+        assert_eq!(links[0].call_identifier_id.as_ref(), Some(&identifier.id));
+        assert_eq!(links[2].call_identifier_id, None);
+    }
+}
@@ -74,6 +74,12 @@ use tracing::debug;
 
 use julie_extractors::SymbolKind;
 
+mod endpoints;
+mod schema;
+
+pub use endpoints::{correlate_endpoints, EndpointLink};
+pub use schema::{correlate_schema, ColumnLink, SchemaLink};
+
 //****************************//
 // Naming Convention Variants //
 //****************************//
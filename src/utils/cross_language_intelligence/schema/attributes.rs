@@ -0,0 +1,153 @@
+//! Mapping attributes and annotations: JPA, Entity Framework, SQLAlchemy, Django
+//!
+//! `@Table`/`@Column` (Java, Kotlin), `[Table]`/`[Column]` (C#), and the
+//! `Column("...")`, `mapped_column("...")`, `db_column="..."` arguments of the
+//! Python ORMs, read from a symbol's signature and recorded annotations.
+
+use julie_extractors::Symbol;
+use regex::Regex;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+/// `@Table(name = "users")`, `[Table("users", Schema = "dbo")]`
+static TABLE_ATTRIBUTE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:@|\[\s*)(?:[\w.]*\.)?Table(?:Attribute)?\s*\((?:[^)"']*\bname\s*=\s*)?\s*["']([^"']+)["']"#)
+        .unwrap()
+});
+
+/// `@Column(name = "email")`, `[Column("email")]`, `Column("email", ...)`,
+/// `mapped_column("email")`, `db_column="email"`
+static COLUMN_ATTRIBUTE_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?:(?:@|\[\s*)(?:[\w.]*\.)?Column(?:Attribute)?\s*\((?:[^)"']*\bname\s*=\s*)?|\b(?:Column|mapped_column)\(|\bdb_column\s*=)\s*["']([^"']+)["']"#,
+    )
+    .unwrap()
+});
+
+/// Table named by a model's `@Table`/`[Table]` attribute
+pub(super) fn table(model: &Symbol) -> Option<String> {
+    declarations(model)
+        .iter()
+        .find_map(|text| TABLE_ATTRIBUTE_RE.captures(text))
+        .map(|table| table[1].to_string())
+}
+
+/// Column named by a field's `@Column`/`[Column]` attribute or column call
+pub(super) fn column(field: &Symbol) -> Option<String> {
+    declarations(field)
+        .iter()
+        .find_map(|text| COLUMN_ATTRIBUTE_RE.captures(text))
+        .map(|column| column[1].to_string())
+}
+
+/// Signature plus recorded annotations, where mapping attributes appear
+fn declarations(symbol: &Symbol) -> Vec<&str> {
+    let mut texts: Vec<&str> = symbol.signature.as_deref().into_iter().collect();
+    if let Some(Value::Array(annotations)) =
+        symbol.metadata.as_ref().and_then(|m| m.get("annotations"))
+    {
+        texts.extend(annotations.iter().filter_map(Value::as_str));
+    }
+    texts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::correlate_schema;
+    use super::super::tests::symbol;
+    use crate::enrichment::base::set_metadata;
+    use julie_extractors::SymbolKind;
+    use serde_json::json;
+
+    #[test]
+    fn test_jpa_and_ef_attributes_name_tables_and_columns() {
+        let invoices = symbol(
+            "schema.sql",
+            "sql",
+            "billing.tbl_invoice",
+            SymbolKind::Class,
+            None,
+            None,
+        );
+        let total = symbol(
+            "schema.sql",
+            "sql",
+            "amount_total",
+            SymbolKind::Field,
+            Some(&invoices),
+            None,
+        );
+
+        let mut java = symbol(
+            "Invoice.java",
+            "java",
+            "Invoice",
+            SymbolKind::Class,
+            None,
+            None,
+        );
+        set_metadata(
+            &mut java,
+            "annotations",
+            json!([
+                "@Entity",
+                "@Table(name = \"tbl_invoice\", schema = \"billing\")"
+            ]),
+        );
+        let mut java_total = symbol(
+            "Invoice.java",
+            "java",
+            "total",
+            SymbolKind::Field,
+            Some(&java),
+            None,
+        );
+        set_metadata(
+            &mut java_total,
+            "annotations",
+            json!(["@Column(name = \"amount_total\")"]),
+        );
+
+        let csharp = symbol(
+            "Invoice.cs",
+            "csharp",
+            "InvoiceEntity",
+            SymbolKind::Class,
+            None,
+            Some("[Table(\"tbl_invoice\")]\npublic class InvoiceEntity"),
+        );
+        // Explicitly mapped elsewhere: no fallback to name matching
+        let other = symbol(
+            "Other.cs",
+            "csharp",
+            "TblInvoice",
+            SymbolKind::Class,
+            None,
+            Some("[Table(\"archive\")] public class TblInvoice"),
+        );
+
+        let mut symbols = vec![invoices, total, java, java_total, csharp, other];
+        let links = correlate_schema(&mut symbols);
+
+        let summary: Vec<(&str, &str, f32, usize)> = links
+            .iter()
+            .map(|l| {
+                (
+                    l.table.as_str(),
+                    l.model_language.as_str(),
+                    l.confidence,
+                    l.columns.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("tbl_invoice", "csharp", 1.0, 0),
+                ("tbl_invoice", "java", 1.0, 1)
+            ]
+        );
+        assert_eq!(links[1].columns[0].field, "total");
+        assert_eq!(links[1].columns[0].confidence, 1.0);
+    }
+}
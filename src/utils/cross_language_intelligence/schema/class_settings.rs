@@ -0,0 +1,31 @@
+//! Class-level table settings: SQLAlchemy `__tablename__`, Django
+//! `class Meta: db_table`, and `table_name` settings
+
+use julie_extractors::{Symbol, SymbolKind};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// First quoted string in a declaration (`__tablename__ = "users"`)
+static STRING_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"["']([^"']+)["']"#).unwrap());
+
+/// Class-level names holding an explicit table name
+pub(super) const TABLE_NAME_FIELDS: &[&str] = &["__tablename__", "db_table", "table_name"];
+
+/// Table named by a class-level setting among a model's fields
+pub(super) fn table<'s>(
+    fields: &[&'s Symbol],
+    children_of: &impl Fn(&Symbol) -> Vec<&'s Symbol>,
+) -> Option<String> {
+    // Django declares it on the nested `class Meta`
+    let meta_fields: Vec<&Symbol> = fields
+        .iter()
+        .filter(|f| f.kind == SymbolKind::Class && f.name == "Meta")
+        .flat_map(|meta| children_of(meta))
+        .collect();
+    fields
+        .iter()
+        .chain(&meta_fields)
+        .filter(|f| TABLE_NAME_FIELDS.contains(&f.name.as_str()))
+        .find_map(|f| STRING_RE.captures(f.signature.as_deref()?))
+        .map(|table| table[1].to_string())
+}
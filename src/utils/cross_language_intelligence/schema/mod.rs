@@ -0,0 +1,429 @@
+// Schema correlation - SQL tables and columns ↔ the ORM models that map them
+//
+// Tables are the SQL extractor's Class symbols and columns their Field
+// children. Models are Class/Struct symbols from every other language, and
+// their fields are Field/Property/Variable children. A model maps a table
+// when:
+//
+//   explicit mapping names the table           1.0   @Table(name = "users"),
+//                                                     [Table("users")],
+//                                                     __tablename__ = "users",
+//                                                     class Meta: db_table = "users"
+//   snake_case names are equal                 0.9   UserAccount ↔ user_account
+//   names are equal after singularizing table  0.75  User ↔ users, Category ↔ categories
+//
+// and a field maps a column of a matched table with 1.0 when an explicit
+// mapping names it (@Column(name = ...), [Column(...)], Column("...") /
+// mapped_column("..."), db_column=, Go `db:` and gorm `column:` tags) or 0.9
+// when the snake_case names are equal; the column's confidence is that times
+// the table's. Names compare case-insensitively without quotes or schema
+// prefixes. Each model keeps only its best tables, each column its best field.
+//
+// Every matched symbol gets a semantic_group: "table:<table>" on the table and
+// its models, "column:<table>.<column>" on a column and its fields, so search
+// and tracing can treat them as one entity. A link made through explicit
+// mappings has provenance ast; one involving a name comparison, name-match.
+
+mod attributes;
+mod class_settings;
+mod struct_tags;
+
+use super::to_snake_case;
+use crate::enrichment::Provenance;
+use julie_extractors::{Symbol, SymbolKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A model mapped to a table, with its column mappings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaLink {
+    pub table_symbol_id: String,
+    /// Normalized table name (the semantic group is "table:" + this)
+    pub table: String,
+    pub model_symbol_id: String,
+    pub model: String,
+    pub model_language: String,
+    pub confidence: f32,
+    /// Ast for an explicit mapping, name-match otherwise
+    pub provenance: Provenance,
+    pub columns: Vec<ColumnLink>,
+}
+
+/// A model field mapped to a table column
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnLink {
+    pub column_symbol_id: String,
+    /// Normalized column name
+    pub column: String,
+    pub field_symbol_id: String,
+    pub field: String,
+    pub confidence: f32,
+    /// Ast when both the table and the column are mapped explicitly, name-match otherwise
+    pub provenance: Provenance,
+}
+
+/// Match SQL tables/columns with ORM models/fields and set their semantic_group
+///
+/// `symbols` may span any number of files and languages (a whole workspace).
+/// Links are ordered by table, then model language.
+pub fn correlate_schema(symbols: &mut [Symbol]) -> Vec<SchemaLink> {
+    let links = schema_links(symbols);
+
+    let by_id: HashMap<String, usize> = symbols
+        .iter()
+        .enumerate()
+        .map(|(index, s)| (s.id.clone(), index))
+        .collect();
+    let mut set_group = |id: &str, group: &str| {
+        if let Some(&index) = by_id.get(id) {
+            symbols[index].semantic_group = Some(group.to_string());
+        }
+    };
+    for link in &links {
+        let group = format!("table:{}", link.table);
+        set_group(&link.table_symbol_id, &group);
+        set_group(&link.model_symbol_id, &group);
+        for column in &link.columns {
+            let group = format!("column:{}.{}", link.table, column.column);
+            set_group(&column.column_symbol_id, &group);
+            set_group(&column.field_symbol_id, &group);
+        }
+    }
+    links
+}
+
+fn schema_links(symbols: &[Symbol]) -> Vec<SchemaLink> {
+    let mut children: HashMap<&str, Vec<&Symbol>> = HashMap::new();
+    for symbol in symbols {
+        if let Some(parent_id) = symbol.parent_id.as_deref() {
+            children.entry(parent_id).or_default().push(symbol);
+        }
+    }
+    let children_of = |symbol: &Symbol| {
+        children
+            .get(symbol.id.as_str())
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    // Table name and singular form → tables
+    let tables: Vec<&Symbol> = symbols
+        .iter()
+        .filter(|s| s.language == "sql" && s.kind == SymbolKind::Class)
+        .collect();
+    if tables.is_empty() {
+        return Vec::new();
+    }
+    let mut by_name: HashMap<String, Vec<&Symbol>> = HashMap::new();
+    let mut by_singular: HashMap<String, Vec<&Symbol>> = HashMap::new();
+    for &table in &tables {
+        let key = sql_name(&table.name);
+        if let Some(singular) = singular(&key) {
+            by_singular.entry(singular).or_default().push(table);
+        }
+        by_name.entry(key).or_default().push(table);
+    }
+
+    let mut links = Vec::new();
+    for model in symbols
+        .iter()
+        .filter(|s| s.language != "sql" && matches!(s.kind, SymbolKind::Class | SymbolKind::Struct))
+    {
+        let fields = children_of(model);
+        let explicit = explicit_table(model, &fields, &children_of);
+        let key = name_key(&model.name);
+        let (matched, confidence) = match explicit.as_ref().and_then(|t| by_name.get(t)) {
+            Some(tables) => (tables, 1.0),
+            None if explicit.is_some() => continue,
+            None => match (by_name.get(&key), by_singular.get(&key)) {
+                (Some(tables), _) => (tables, 0.9),
+                (None, Some(tables)) => (tables, 0.75),
+                (None, None) => continue,
+            },
+        };
+
+        let provenance = if confidence == 1.0 {
+            Provenance::Ast
+        } else {
+            Provenance::NameMatch
+        };
+        for &table in matched {
+            let columns = column_links(&children_of(table), &fields, confidence);
+            links.push(SchemaLink {
+                table_symbol_id: table.id.clone(),
+                table: sql_name(&table.name),
+                model_symbol_id: model.id.clone(),
+                model: model.name.clone(),
+                model_language: model.language.clone(),
+                confidence,
+                provenance,
+                columns,
+            });
+        }
+    }
+    links.sort_by(|a, b| (&a.table, &a.model_language).cmp(&(&b.table, &b.model_language)));
+    links
+}
+
+fn column_links(columns: &[&Symbol], fields: &[&Symbol], table_confidence: f32) -> Vec<ColumnLink> {
+    let fields: Vec<(&Symbol, Option<String>, String)> = fields
+        .iter()
+        .filter(|f| {
+            matches!(
+                f.kind,
+                SymbolKind::Field | SymbolKind::Property | SymbolKind::Variable
+            )
+        })
+        .filter(|f| !class_settings::TABLE_NAME_FIELDS.contains(&f.name.as_str()))
+        .map(|&f| (f, explicit_column(f), name_key(&f.name)))
+        .collect();
+
+    let mut links = Vec::new();
+    for column in columns.iter().filter(|c| c.kind == SymbolKind::Field) {
+        let key = sql_name(&column.name);
+        let best = fields
+            .iter()
+            .filter_map(|(field, explicit, field_key)| match explicit {
+                Some(explicit) if *explicit == key => Some((*field, 1.0_f32)),
+                Some(_) => None,
+                None if *field_key == key => Some((*field, 0.9)),
+                None => None,
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((field, confidence)) = best {
+            let confidence = confidence * table_confidence;
+            links.push(ColumnLink {
+                column_symbol_id: column.id.clone(),
+                column: key,
+                field_symbol_id: field.id.clone(),
+                field: field.name.clone(),
+                confidence,
+                provenance: if confidence == 1.0 {
+                    Provenance::Ast
+                } else {
+                    Provenance::NameMatch
+                },
+            });
+        }
+    }
+    links
+}
+
+/// Table name a model declares through an attribute, annotation, or class-level setting
+fn explicit_table<'s>(
+    model: &Symbol,
+    fields: &[&'s Symbol],
+    children_of: &impl Fn(&Symbol) -> Vec<&'s Symbol>,
+) -> Option<String> {
+    attributes::table(model)
+        .or_else(|| class_settings::table(fields, children_of))
+        .map(|table| sql_name(&table))
+}
+
+/// Column name a field declares through an attribute, call argument, or struct tag
+fn explicit_column(field: &Symbol) -> Option<String> {
+    struct_tags::column(field)
+        .or_else(|| attributes::column(field))
+        .map(|column| sql_name(&column))
+}
+
+/// `"dbo"."Users"`, `[dbo].[Users]`, `` `users` `` → "users"
+fn sql_name(name: &str) -> String {
+    let last = name.rsplit('.').next().unwrap_or(name);
+    name_key(last.trim_matches(|c| matches!(c, '"' | '`' | '[' | ']' | '\'')))
+}
+
+fn name_key(name: &str) -> String {
+    to_snake_case(name).to_lowercase()
+}
+
+/// Singular of a plural snake_case name's last word: user_accounts → user_account
+fn singular(name: &str) -> Option<String> {
+    if let Some(stem) = name.strip_suffix("ies") {
+        return Some(format!("{}y", stem));
+    }
+    for suffix in ["sses", "xes", "zes", "ches", "shes"] {
+        if name.ends_with(suffix) {
+            return Some(name[..name.len() - 2].to_string());
+        }
+    }
+    name.strip_suffix('s')
+        .filter(|stem| !stem.ends_with('s') && !stem.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{set_metadata, EnrichmentContext, SymbolOptions};
+    use serde_json::json;
+    use std::path::Path;
+
+    pub(super) fn symbol(
+        file: &str,
+        language: &str,
+        name: &str,
+        kind: SymbolKind,
+        parent: Option<&Symbol>,
+        signature: Option<&str>,
+    ) -> Symbol {
+        let ctx = EnrichmentContext::new(file, language, "", Path::new("."));
+        ctx.create_symbol(
+            name,
+            kind,
+            0,
+            0,
+            SymbolOptions {
+                parent_id: parent.map(|p| p.id.clone()),
+                signature: signature.map(str::to_string),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_tables_match_models_by_name_plural_and_explicit_mapping() {
+        let users = symbol(
+            "schema.sql",
+            "sql",
+            "\"Users\"",
+            SymbolKind::Class,
+            None,
+            None,
+        );
+        let email = symbol(
+            "schema.sql",
+            "sql",
+            "email_address",
+            SymbolKind::Field,
+            Some(&users),
+            None,
+        );
+        let created = symbol(
+            "schema.sql",
+            "sql",
+            "created_at",
+            SymbolKind::Field,
+            Some(&users),
+            None,
+        );
+        let categories = symbol(
+            "schema.sql",
+            "sql",
+            "categories",
+            SymbolKind::Class,
+            None,
+            None,
+        );
+
+        // Python model with an explicit table and column
+        let account = symbol(
+            "models.py",
+            "python",
+            "Account",
+            SymbolKind::Class,
+            None,
+            None,
+        );
+        let tablename = symbol(
+            "models.py",
+            "python",
+            "__tablename__",
+            SymbolKind::Variable,
+            Some(&account),
+            Some("__tablename__ = 'users'"),
+        );
+        let mail = symbol(
+            "models.py",
+            "python",
+            "mail",
+            SymbolKind::Variable,
+            Some(&account),
+            Some("mail = Column('email_address', String)"),
+        );
+        // Go struct matched by plural name, column by tag and by name
+        let user = symbol("user.go", "go", "User", SymbolKind::Struct, None, None);
+        let mut go_email = symbol(
+            "user.go",
+            "go",
+            "Email",
+            SymbolKind::Field,
+            Some(&user),
+            None,
+        );
+        set_metadata(
+            &mut go_email,
+            "tags",
+            json!({ "db": "email_address,omitempty" }),
+        );
+        let go_created = symbol(
+            "user.go",
+            "go",
+            "CreatedAt",
+            SymbolKind::Field,
+            Some(&user),
+            None,
+        );
+        let category = symbol(
+            "category.go",
+            "go",
+            "Category",
+            SymbolKind::Struct,
+            None,
+            None,
+        );
+        let unrelated = symbol("order.go", "go", "Order", SymbolKind::Struct, None, None);
+
+        let mut symbols = vec![
+            users, email, created, categories, account, tablename, mail, user, go_email,
+            go_created, category, unrelated,
+        ];
+        let links = correlate_schema(&mut symbols);
+
+        let summary: Vec<(&str, &str, f32, usize)> = links
+            .iter()
+            .map(|l| {
+                (
+                    l.table.as_str(),
+                    l.model.as_str(),
+                    l.confidence,
+                    l.columns.len(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("categories", "Category", 0.75, 0),
+                ("users", "User", 0.75, 2),
+                ("users", "Account", 1.0, 1),
+            ]
+        );
+        let go_columns: Vec<(&str, &str)> = links[1]
+            .columns
+            .iter()
+            .map(|c| (c.column.as_str(), c.field.as_str()))
+            .collect();
+        assert_eq!(
+            go_columns,
+            vec![("email_address", "Email"), ("created_at", "CreatedAt")]
+        );
+        // Tag mapping is exact; the name match is weaker, both scaled by the plural match
+        assert!((links[1].columns[0].confidence - 0.75).abs() < 1e-6);
+        assert!((links[1].columns[1].confidence - 0.675).abs() < 1e-6);
+
+        let group = |name: &str| {
+            symbols
+                .iter()
+                .find(|s| s.name == name)
+                .and_then(|s| s.semantic_group.as_deref())
+        };
+        assert_eq!(group("\"Users\""), Some("table:users"));
+        assert_eq!(group("Account"), Some("table:users"));
+        assert_eq!(group("mail"), Some("column:users.email_address"));
+        assert_eq!(group("Email"), Some("column:users.email_address"));
+        assert_eq!(group("Order"), None);
+    }
+}
@@ -0,0 +1,22 @@
+//! Go struct tags: sqlx/sqlc `db:"email"` and gorm `gorm:"column:email"`
+
+use julie_extractors::Symbol;
+use serde_json::Value;
+
+/// Column named by a field's `db` or gorm `column:` tag; "-" means unmapped
+pub(super) fn column(field: &Symbol) -> Option<String> {
+    let Some(Value::Object(tags)) = field.metadata.as_ref().and_then(|m| m.get("tags")) else {
+        return None;
+    };
+    let db = tags
+        .get("db")
+        .and_then(Value::as_str)
+        .and_then(|tag| tag.split(',').next());
+    let gorm = tags.get("gorm").and_then(Value::as_str).and_then(|tag| {
+        tag.split(';')
+            .find_map(|option| option.trim().strip_prefix("column:"))
+    });
+    db.or(gorm)
+        .filter(|c| !c.is_empty() && *c != "-")
+        .map(str::to_string)
+}
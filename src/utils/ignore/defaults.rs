@@ -0,0 +1,84 @@
+//! Default ignore list applied to every workspace
+
+/// Default ignore patterns (always applied)
+/// Matches the Python DEFAULT_IGNORES list
+#[rustfmt::skip]
+pub const DEFAULT_IGNORES: &[&str] = &[
+    // ═══════════════════════════════════════════
+    // Version Control
+    // ═══════════════════════════════════════════
+    ".git/", ".svn/", ".hg/", ".bzr/",
+
+    // ═══════════════════════════════════════════
+    // IDE and Editor
+    // ═══════════════════════════════════════════
+    ".vs/", ".vscode/", ".idea/", ".eclipse/",
+    "*.swp", "*.swo",
+
+    // ═══════════════════════════════════════════
+    // Build and Output Directories
+    // ═══════════════════════════════════════════
+    "bin/", "obj/", "build/", "dist/", "out/",
+    "target/", "Debug/", "Release/",
+    ".next/", ".nuxt/", "DerivedData/",
+
+    // ═══════════════════════════════════════════
+    // Package Managers and Dependencies
+    // ═══════════════════════════════════════════
+    "node_modules/", "packages/", ".npm/", "bower_components/",
+    "vendor/", "Pods/",
+
+    // ═══════════════════════════════════════════
+    // Python Virtual Environments and Cache
+    // ═══════════════════════════════════════════
+    ".venv/", "venv/", "env/", ".env/",
+    "__pycache__/", "*.pyc", "*.pyo", "*.pyd",
+    ".pytest_cache/", ".mypy_cache/", ".ruff_cache/",
+    ".tox/", ".eggs/", "*.egg-info/",
+    ".coverage", "htmlcov/", ".hypothesis/",
+
+    // ═══════════════════════════════════════════
+    // Cache and Temporary Files
+    // ═══════════════════════════════════════════
+    ".cache/", ".temp/", ".tmp/", "tmp/", "temp/", ".sass-cache/",
+    "*.tmp", "*.tmp.*", "*.temp", "*.swp", "*.lock", "*.pid",
+    "*~", "*.bak", "[#]*[#]", ".#*", "*.orig", "*.rej",
+
+    // ═══════════════════════════════════════════
+    // Code Intelligence Tools (our own dirs)
+    // ═══════════════════════════════════════════
+    ".miller/", ".julie/", ".coa/", ".codenav/",
+
+    // ═══════════════════════════════════════════
+    // Binary Files (Executables, libs, media, archives)
+    // ═══════════════════════════════════════════
+    // Executables/Libs
+    "*.dll", "*.exe", "*.pdb", "*.so", "*.dylib", "*.lib",
+    "*.a", "*.o", "*.obj", "*.bin",
+    // Media
+    "*.jpg", "*.jpeg", "*.png", "*.gif", "*.bmp", "*.ico", "*.svg", "*.webp", "*.tiff",
+    "*.mp3", "*.mp4", "*.avi", "*.mov", "*.wmv", "*.flv", "*.webm", "*.mkv", "*.wav",
+    // Archives
+    "*.zip", "*.rar", "*.7z", "*.tar", "*.gz", "*.bz2", "*.xz", "*.dmg", "*.pkg",
+    // Database/Logs
+    "*.db", "*.sqlite", "*.sqlite3", "*.mdf", "*.ldf", "*.bak",
+    "*.log", "*.dump", "*.core",
+    // Fonts
+    "*.ttf", "*.otf", "*.woff", "*.woff2", "*.eot",
+    // Documents (Binary)
+    "*.pdf", "*.doc", "*.docx", "*.xls", "*.xlsx", "*.ppt", "*.pptx",
+
+    // ═══════════════════════════════════════════
+    // System Files
+    // ═══════════════════════════════════════════
+    ".DS_Store", "Thumbs.db", "desktop.ini",
+
+    // ═══════════════════════════════════════════
+    // Noisy/Generated Files
+    // ═══════════════════════════════════════════
+    // Locks
+    "package-lock.json", "yarn.lock", "pnpm-lock.yaml", "Cargo.lock",
+    "poetry.lock", "Gemfile.lock", "composer.lock", "Pipfile.lock", "bun.lockb",
+    // Minified/Bundled
+    "*.min.js", "*.min.css", "*.bundle.js", "*.chunk.js", "*.map", "*.d.ts.map",
+];
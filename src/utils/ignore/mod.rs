@@ -1,8 +1,20 @@
-//! Utilities for handling .julieignore file patterns
+//! Utilities for handling .julieignore file patterns and default ignores
 //!
 //! This module provides shared functionality for loading and matching .julieignore patterns,
-//! ensuring consistent ignore behavior across discovery and startup scanning.
+//! plus the default ignore list, ensuring consistent ignore behavior across the file
+//! watcher, workspace extraction, and startup scanning.
 //!
+//! [`IgnoreRules`] (`rules.rs`) is the gitignore-compatible matcher used by the file
+//! watcher, the workspace walk, and the `is_ignored` binding: nested .gitignore files,
+//! negations, and .julieignore on top. The default list lives in `defaults.rs`.
+//!
+
+mod defaults;
+mod rules;
+
+pub use defaults::DEFAULT_IGNORES;
+pub use rules::IgnoreRules;
+
 use anyhow::Result;
 use std::fs;
 use std::path::Path;
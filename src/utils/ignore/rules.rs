@@ -0,0 +1,213 @@
+//! Gitignore-compatible ignore rules for a workspace
+
+use super::{is_ignored_by_pattern, load_julieignore, DEFAULT_IGNORES};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use tracing::warn;
+
+/// Gitignore-compatible ignore rules for one workspace
+///
+/// From lowest to highest precedence:
+/// 1. [`DEFAULT_IGNORES`]
+/// 2. `.gitignore` files at the root and in every directory below it; a deeper file
+///    overrides a shallower one, and `!`, trailing-`/`, and `**` patterns behave as in git
+/// 3. `.julieignore` and caller patterns, which always win: a match ignores the path
+///    whatever the `.gitignore` files say, and a `!pattern` line re-includes it
+///
+/// As in git, a path inside an ignored directory stays ignored; a negation can only
+/// re-include a subpath when the directory itself is not ignored (`logs/*` then
+/// `!logs/keep/`). Hidden files and directories are ignored unless `.julieignore`
+/// re-includes them. Nested `.gitignore` files are read on first use and cached.
+pub struct IgnoreRules {
+    root: PathBuf,
+    defaults: Option<Gitignore>,
+    /// .julieignore and caller patterns, matched as in [`is_ignored_by_pattern`]
+    julie_patterns: Vec<String>,
+    /// The `!` lines of .julieignore and caller patterns
+    julie_negations: Option<Gitignore>,
+    /// Directory → its parsed .gitignore, if it has one
+    gitignores: Mutex<HashMap<PathBuf, Option<Arc<Gitignore>>>>,
+}
+
+impl IgnoreRules {
+    /// Load the workspace's ignore files, adding `extra_patterns` in .julieignore syntax
+    pub fn load(workspace: &Path, extra_patterns: &[String]) -> Self {
+        let mut patterns = load_julieignore(workspace).unwrap_or_else(|e| {
+            warn!("{}", e);
+            Vec::new()
+        });
+        patterns.extend(extra_patterns.iter().cloned());
+        let (negations, julie_patterns): (Vec<String>, Vec<String>) =
+            patterns.into_iter().partition(|p| p.starts_with('!'));
+
+        Self {
+            root: workspace.to_path_buf(),
+            defaults: build_matcher(workspace, DEFAULT_IGNORES.iter().copied()),
+            julie_patterns,
+            julie_negations: if negations.is_empty() {
+                None
+            } else {
+                build_matcher(workspace, negations.iter().map(String::as_str))
+            },
+            gitignores: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `path` (absolute, or relative to the workspace root) is ignored
+    ///
+    /// Paths outside the workspace are always ignored; the root itself never is.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let absolute = self.root.join(path);
+        let Ok(rel_path) = absolute.strip_prefix(&self.root) else {
+            return true;
+        };
+        if rel_path.as_os_str().is_empty() {
+            return false;
+        }
+
+        if let Some(negations) = &self.julie_negations {
+            if negations
+                .matched_path_or_any_parents(rel_path, is_dir)
+                .is_whitelist()
+            {
+                return false;
+            }
+        }
+        if is_ignored_by_pattern(rel_path, &self.julie_patterns) {
+            return true;
+        }
+
+        // Decide each ancestor directory, then the path, against the .gitignore
+        // files of the directories above it: an ignored directory hides everything below
+        let components: Vec<_> = rel_path.components().collect();
+        let mut current = self.root.clone();
+        let mut directories = vec![self.root.clone()];
+        for (index, component) in components.iter().enumerate() {
+            current.push(component);
+            let name = component.as_os_str().to_string_lossy();
+            if name.starts_with('.') && name != ".gitignore" && name != ".julieignore" {
+                return true;
+            }
+            let component_is_dir = index + 1 < components.len() || is_dir;
+            if self.gitignore_match(&directories, &current, component_is_dir) {
+                return true;
+            }
+            directories.push(current.clone());
+        }
+        false
+    }
+
+    /// The deepest .gitignore with an opinion decides; defaults apply when none has one
+    fn gitignore_match(&self, directories: &[PathBuf], path: &Path, is_dir: bool) -> bool {
+        for directory in directories.iter().rev() {
+            let Some(gitignore) = self.gitignore_in(directory) else {
+                continue;
+            };
+            match gitignore.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        self.defaults
+            .as_ref()
+            .is_some_and(|defaults| defaults.matched(path, is_dir).is_ignore())
+    }
+
+    fn gitignore_in(&self, directory: &Path) -> Option<Arc<Gitignore>> {
+        let mut cache = self
+            .gitignores
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        cache
+            .entry(directory.to_path_buf())
+            .or_insert_with(|| {
+                let file = directory.join(".gitignore");
+                if !file.is_file() {
+                    return None;
+                }
+                let mut builder = GitignoreBuilder::new(directory);
+                if let Some(e) = builder.add(&file) {
+                    warn!("Failed to parse {}: {:?}", file.display(), e);
+                }
+                builder
+                    .build()
+                    .map_err(|e| warn!("Failed to build matcher for {}: {:?}", file.display(), e))
+                    .ok()
+                    .map(Arc::new)
+            })
+            .clone()
+    }
+}
+
+/// A gitignore matcher rooted at `root` for the given lines
+fn build_matcher<'a>(root: &Path, lines: impl Iterator<Item = &'a str>) -> Option<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root);
+    for line in lines {
+        if let Err(e) = builder.add_line(None, line) {
+            warn!("Failed to add ignore pattern '{}': {}", line, e);
+        }
+    }
+    builder
+        .build()
+        .map_err(|e| warn!("Failed to build ignore matcher: {:?}", e))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ignore_rules_follow_gitignore_semantics() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for (path, content) in [
+            (".gitignore", "logs/*\n!logs/keep/\n*.gen.ts\n/secrets.txt\ndocs/**/draft.md\nreports/\n!reports/summary.md\n"),
+            ("src/.gitignore", "!api.gen.ts\nlocal/\n"),
+            ("vendored/.gitignore", "!node_modules/\n"),
+            (".julieignore", "scratch/\n!logs/special.log\n"),
+        ] {
+            fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+            fs::write(root.join(path), content).unwrap();
+        }
+        let rules = IgnoreRules::load(root, &["*.snap".to_string()]);
+        let ignored = |path: &str| rules.is_ignored(Path::new(path), path.ends_with('/'));
+
+        // Negation re-includes a subpath of a directory whose contents are ignored
+        assert!(ignored("logs/app.txt"));
+        assert!(!ignored("logs/keep/"));
+        assert!(!ignored("logs/keep/app.txt"));
+        // ... but not one inside a directory that is itself ignored
+        assert!(ignored("reports/summary.md"));
+        // Anchored and ** patterns
+        assert!(ignored("secrets.txt"));
+        assert!(!ignored("src/secrets.txt"));
+        assert!(ignored("docs/a/b/draft.md"));
+        assert!(!ignored("docs/a/b/final.md"));
+        // A nested .gitignore overrides the root one below its directory
+        assert!(ignored("web/client.gen.ts"));
+        assert!(!ignored("src/api.gen.ts"));
+        assert!(ignored("src/local/config.py"));
+        assert!(!ignored("local/config.py"));
+        // .gitignore negations override the defaults
+        assert!(ignored("node_modules/lib/index.js"));
+        assert!(!ignored("vendored/node_modules/lib/index.js"));
+        assert!(ignored("dist/app.js"));
+        assert!(ignored("src/app.min.js"));
+        // .julieignore and caller patterns win, both ways
+        assert!(ignored("src/scratch/notes.py"));
+        assert!(ignored("tests/__snapshots__/app.snap"));
+        assert!(!ignored("logs/special.log"));
+        // Hidden entries, and everything outside the workspace
+        assert!(ignored(".env.local"));
+        assert!(ignored("src/.cache/data.py"));
+        assert!(rules.is_ignored(Path::new("/elsewhere/app.py"), false));
+        assert!(!ignored("src/app.py"));
+    }
+}
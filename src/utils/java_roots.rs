@@ -0,0 +1,178 @@
+// Java source roots - map packages to files without a build file
+//
+// Java imports name classes (`com.acme.util.Strings`), not files, and which
+// directory a package lives under is build configuration Miller never reads.
+// It can be inferred instead: a file declaring `package com.acme.util` at
+// `src/main/java/com/acme/util/Strings.java` puts its source root at
+// `src/main/java`. Every directory whose files agree this way is a root, so
+// main/test trees and the modules of a multi-module build each get their own.
+//
+// A file whose package does not match the end of its directory is recorded as
+// a mismatch and left out of the index: guessing where its classes belong
+// would link imports to the wrong file. A class name declared by files under
+// two roots is ambiguous and does not resolve either.
+
+use std::collections::{BTreeSet, HashMap};
+
+/// A file whose package declaration disagrees with its directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageMismatch {
+    pub path: String,
+    pub package: String,
+    /// The file's directory, `/`-separated ("" for the workspace root)
+    pub directory: String,
+}
+
+/// Source roots and fully qualified class names of a set of Java files
+#[derive(Debug, Clone, Default)]
+pub struct JavaWorkspaceIndex {
+    /// Inferred source roots, sorted ("" is the workspace root)
+    pub roots: Vec<String>,
+    pub mismatches: Vec<PackageMismatch>,
+    /// Fully qualified name of each file's top-level class → files declaring it
+    classes: HashMap<String, Vec<String>>,
+}
+
+/// Where an import points: the file, its top-level class, and any nested
+/// class or static member named after it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaImport<'a> {
+    pub file_path: &'a str,
+    pub class_name: &'a str,
+    pub member: Option<&'a str>,
+}
+
+impl JavaWorkspaceIndex {
+    /// Index `(file path, package)` pairs; the default package is ""
+    pub fn build<'a>(files: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut index = JavaWorkspaceIndex::default();
+        let mut roots = BTreeSet::new();
+        for (path, package) in files {
+            let components: Vec<&str> = path.split(['/', '\\']).filter(|c| !c.is_empty()).collect();
+            let Some((file_name, directory)) = components.split_last() else {
+                continue;
+            };
+            let Some(class_name) = file_name.strip_suffix(".java") else {
+                continue;
+            };
+            let segments: Vec<&str> = package.split('.').filter(|s| !s.is_empty()).collect();
+            if !directory.ends_with(&segments) {
+                index.mismatches.push(PackageMismatch {
+                    path: path.to_string(),
+                    package: package.to_string(),
+                    directory: directory.join("/"),
+                });
+                continue;
+            }
+            roots.insert(directory[..directory.len() - segments.len()].join("/"));
+            let qualified = if package.is_empty() {
+                class_name.to_string()
+            } else {
+                format!("{}.{}", package, class_name)
+            };
+            index
+                .classes
+                .entry(qualified)
+                .or_default()
+                .push(path.to_string());
+        }
+        index.roots = roots.into_iter().collect();
+        index
+    }
+}
+
+/// The file an import names: `com.acme.Strings`, `com.acme.Outer.Inner`, or
+/// the class of a static import (`com.acme.Strings.join`). None for package
+/// wildcards, classes outside the index, and ambiguous classes.
+pub fn resolve_java_import<'a>(
+    import_name: &'a str,
+    index: &'a JavaWorkspaceIndex,
+) -> Option<JavaImport<'a>> {
+    let name = import_name.trim();
+    let name = name.strip_suffix(".*").unwrap_or(name);
+    // Longest prefix that names an indexed class
+    let mut end = name.len();
+    loop {
+        let qualified = &name[..end];
+        if let Some(files) = index.classes.get(qualified) {
+            let [file_path] = files.as_slice() else {
+                return None;
+            };
+            let member = name.get(end + 1..).filter(|member| !member.is_empty());
+            let class_name = qualified.rsplit('.').next().unwrap_or(qualified);
+            return Some(JavaImport {
+                file_path,
+                class_name,
+                member,
+            });
+        }
+        end = qualified.rfind('.')?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index() -> JavaWorkspaceIndex {
+        JavaWorkspaceIndex::build([
+            (
+                "core/src/main/java/com/acme/util/Strings.java",
+                "com.acme.util",
+            ),
+            (
+                "core/src/test/java/com/acme/util/StringsTest.java",
+                "com.acme.util",
+            ),
+            ("web/src/main/java/com/acme/web/App.java", "com.acme.web"),
+            ("web/src/main/java/com/acme/Wrong.java", "com.acme.web"),
+            ("scripts/Tool.java", ""),
+        ])
+    }
+
+    #[test]
+    fn test_roots_are_inferred_and_mismatches_flagged() {
+        let index = index();
+        assert_eq!(
+            index.roots,
+            vec![
+                "core/src/main/java",
+                "core/src/test/java",
+                "scripts",
+                "web/src/main/java",
+            ]
+        );
+        assert_eq!(
+            index.mismatches,
+            vec![PackageMismatch {
+                path: "web/src/main/java/com/acme/Wrong.java".to_string(),
+                package: "com.acme.web".to_string(),
+                directory: "web/src/main/java/com/acme".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_imports_resolve_to_the_declaring_file() {
+        let index = index();
+        assert_eq!(
+            resolve_java_import("com.acme.util.Strings", &index),
+            Some(JavaImport {
+                file_path: "core/src/main/java/com/acme/util/Strings.java",
+                class_name: "Strings",
+                member: None,
+            })
+        );
+        assert_eq!(
+            resolve_java_import("com.acme.util.Strings.join", &index).map(|i| i.member),
+            Some(Some("join"))
+        );
+        assert_eq!(
+            resolve_java_import("Tool", &index).map(|i| i.file_path),
+            Some("scripts/Tool.java")
+        );
+        assert_eq!(resolve_java_import("com.acme.util.*", &index), None);
+        assert_eq!(resolve_java_import("com.acme.web.Wrong", &index), None);
+        assert_eq!(resolve_java_import("java.util.List", &index), None);
+    }
+}
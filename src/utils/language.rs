@@ -0,0 +1,200 @@
+// Language Detection - from file extensions, and from content for extensionless files
+//
+// Extension lookup handles almost every file. Scripts without an extension
+// (./deploy, bin/build) and well-known extensionless files (Gemfile, .bashrc)
+// need the content or the file name instead: detect_language_from_content
+// checks, in order, well-known file names, a `#!` shebang on the first line,
+// and an emacs (`-*- mode: python -*-`) or vim (`vim: set ft=sh:`) modeline
+// near the start or end of the file.
+
+use std::path::Path;
+
+/// Detect programming language from file extension
+pub fn detect_language(path: &Path) -> Option<&'static str> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| match ext {
+            "rs" => Some("rust"),
+            "py" => Some("python"),
+            "js" => Some("javascript"),
+            "ts" => Some("typescript"),
+            "tsx" => Some("typescript"),
+            "jsx" => Some("javascript"),
+            "go" => Some("go"),
+            "java" => Some("java"),
+            "c" => Some("c"),
+            "cpp" | "cc" | "cxx" => Some("cpp"),
+            "h" => Some("c"),
+            "hpp" | "hxx" => Some("cpp"),
+            "cs" => Some("csharp"),
+            "php" => Some("php"),
+            "rb" => Some("ruby"),
+            "swift" => Some("swift"),
+            "kt" => Some("kotlin"),
+            "lua" => Some("lua"),
+            "gd" => Some("gdscript"),
+            "vue" => Some("vue"),
+            "html" => Some("html"),
+            "erb" => Some("erb"),
+            "css" => Some("css"),
+            "sql" => Some("sql"),
+            "sh" | "bash" => Some("bash"),
+            "qml" => Some("qml"),
+            "r" | "R" => Some("r"),
+            _ => None,
+        })
+}
+
+/// Extensionless files whose name fixes the language
+const WELL_KNOWN_FILES: &[(&str, &str)] = &[
+    (".bashrc", "bash"),
+    (".bash_profile", "bash"),
+    (".bash_aliases", "bash"),
+    (".profile", "bash"),
+    (".zshrc", "bash"),
+    (".zprofile", "bash"),
+    ("PKGBUILD", "bash"),
+    ("Gemfile", "ruby"),
+    ("Rakefile", "ruby"),
+    ("Vagrantfile", "ruby"),
+    ("Podfile", "ruby"),
+    ("Brewfile", "ruby"),
+    ("Guardfile", "ruby"),
+    ("SConstruct", "python"),
+    ("SConscript", "python"),
+];
+
+/// Lines at each end of the file searched for a modeline, as vim does by default
+const MODELINE_LINES: usize = 5;
+
+/// Detect the language of a file from its name and content
+///
+/// Meant for files detect_language cannot place: returns None when neither a
+/// well-known file name, a shebang, nor a modeline identifies the language.
+pub fn detect_language_from_content(path: &Path, content: &str) -> Option<&'static str> {
+    let file_name = path.file_name().and_then(|name| name.to_str())?;
+    if let Some(&(_, language)) = WELL_KNOWN_FILES.iter().find(|(name, _)| *name == file_name) {
+        return Some(language);
+    }
+
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    if let Some(shebang) = content.lines().next().and_then(|l| l.strip_prefix("#!")) {
+        if let Some(language) = shebang_language(shebang) {
+            return Some(language);
+        }
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let tail = lines
+        .len()
+        .saturating_sub(MODELINE_LINES)
+        .max(MODELINE_LINES);
+    lines
+        .iter()
+        .take(MODELINE_LINES)
+        .chain(lines.iter().skip(tail))
+        .find_map(|line| modeline_language(line))
+}
+
+/// Language of a shebang's interpreter: `/usr/bin/env -S python3 -u` → python
+fn shebang_language(shebang: &str) -> Option<&'static str> {
+    let mut words = shebang.split_whitespace();
+    let mut interpreter = words.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        interpreter = words.find(|word| !word.starts_with('-') && !word.contains('='))?;
+    }
+    language_for_name(interpreter)
+}
+
+/// Language named by an emacs or vim modeline in `line`
+fn modeline_language(line: &str) -> Option<&'static str> {
+    if let Some(start) = line.find("-*-") {
+        let rest = &line[start + 3..];
+        let settings = &rest[..rest.find("-*-")?];
+        // `-*- python -*-` or `-*- mode: python; coding: utf-8 -*-`
+        let mode = if settings.contains(':') {
+            settings.split(';').find_map(|setting| {
+                let (key, value) = setting.split_once(':')?;
+                key.trim().eq_ignore_ascii_case("mode").then_some(value)
+            })?
+        } else {
+            settings
+        };
+        return language_for_name(mode.trim());
+    }
+
+    let start = ["vim:", "vi:", "ex:"]
+        .iter()
+        .filter_map(|marker| line.find(marker).map(|i| i + marker.len()))
+        .min()?;
+    line[start..]
+        .split(|c: char| c == ':' || c.is_whitespace())
+        .find_map(|option| {
+            let (key, value) = option.split_once('=')?;
+            matches!(key, "ft" | "filetype" | "syntax" | "syn").then_some(value)
+        })
+        .and_then(language_for_name)
+}
+
+/// Map an interpreter, emacs mode, or vim filetype name to a language
+fn language_for_name(name: &str) -> Option<&'static str> {
+    let name = name.to_ascii_lowercase();
+    let name = name.trim_end_matches("-mode");
+    // python3.12 → python, ruby2.7 → ruby
+    let base = name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    match base {
+        "python" | "pypy" => Some("python"),
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "ash" | "shell-script" => Some("bash"),
+        "node" | "nodejs" | "js" | "javascript" | "bun" => Some("javascript"),
+        "ts-node" | "tsx" | "deno" | "typescript" => Some("typescript"),
+        "ruby" | "jruby" => Some("ruby"),
+        "php" => Some("php"),
+        "lua" | "luajit" => Some("lua"),
+        "rscript" | "r" => Some("r"),
+        "pwsh" | "powershell" | "ps1" => Some("powershell"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(path: &str, content: &str) -> Option<&'static str> {
+        detect_language_from_content(Path::new(path), content)
+    }
+
+    #[test]
+    fn test_shebangs() {
+        assert_eq!(
+            detect("build", "#!/usr/bin/env python3\nprint(1)\n"),
+            Some("python")
+        );
+        assert_eq!(detect("deploy", "#!/bin/bash -e\nset -u\n"), Some("bash"));
+        assert_eq!(
+            detect("run", "#!/usr/bin/env -S node --experimental\n"),
+            Some("javascript")
+        );
+        assert_eq!(detect("x", "#!/usr/local/bin/python3.12\n"), Some("python"));
+        assert_eq!(detect("x", "#!/usr/bin/perl\n"), None);
+    }
+
+    #[test]
+    fn test_well_known_files_and_modelines() {
+        assert_eq!(
+            detect("repo/Gemfile", "source 'https://rubygems.org'\n"),
+            Some("ruby")
+        );
+        assert_eq!(detect("home/.bashrc", "alias ll='ls -l'\n"), Some("bash"));
+        assert_eq!(
+            detect("tool", "# -*- mode: python; coding: utf-8 -*-\n"),
+            Some("python")
+        );
+        assert_eq!(detect("tool", "# -*- sh -*-\n"), Some("bash"));
+        assert_eq!(
+            detect("tool", "echo hi\n\n# vim: set ft=sh ts=4:\n"),
+            Some("bash")
+        );
+        assert_eq!(detect("notes", "just some text\n"), None);
+    }
+}
@@ -35,6 +35,7 @@ pub mod file_utils {
                     | "gd"
                     | "vue"
                     | "html"
+                    | "erb"       // ERB templates (.html.erb)
                     | "css"
                     | "sql"
                     | "sh"
@@ -164,6 +165,9 @@ pub mod context_truncation;
 /// Progressive reduction utilities
 pub mod progressive_reduction;
 
+/// Token-budget reduction of extraction results
+pub mod result_reduction;
+
 /// Cross-language intelligence utilities (THE secret sauce!)
 pub mod cross_language_intelligence;
 
@@ -179,6 +183,9 @@ pub mod query_expansion;
 /// String similarity utilities for fuzzy matching
 pub mod string_similarity;
 
+/// Query-driven ranking of extracted symbols
+pub mod symbol_ranking;
+
 /// Path conversion utilities (absolute ↔ relative Unix-style)
 pub mod paths;
 
@@ -186,41 +193,7 @@ pub mod paths;
 pub mod ignore;
 
 /// Language detection utilities
-pub mod language {
-    use std::path::Path;
-
-    /// Detect programming language from file extension
-    pub fn detect_language(path: &Path) -> Option<&'static str> {
-        path.extension()
-            .and_then(|ext| ext.to_str())
-            .and_then(|ext| match ext {
-                "rs" => Some("rust"),
-                "py" => Some("python"),
-                "js" => Some("javascript"),
-                "ts" => Some("typescript"),
-                "tsx" => Some("typescript"),
-                "jsx" => Some("javascript"),
-                "go" => Some("go"),
-                "java" => Some("java"),
-                "c" => Some("c"),
-                "cpp" | "cc" | "cxx" => Some("cpp"),
-                "h" => Some("c"),
-                "hpp" | "hxx" => Some("cpp"),
-                "cs" => Some("csharp"),
-                "php" => Some("php"),
-                "rb" => Some("ruby"),
-                "swift" => Some("swift"),
-                "kt" => Some("kotlin"),
-                "lua" => Some("lua"),
-                "gd" => Some("gdscript"),
-                "vue" => Some("vue"),
-                "html" => Some("html"),
-                "css" => Some("css"),
-                "sql" => Some("sql"),
-                "sh" | "bash" => Some("bash"),
-                "qml" => Some("qml"),
-                "r" | "R" => Some("r"),
-                _ => None,
-            })
-    }
-}
+pub mod language;
+
+/// Java source-root inference and import resolution
+pub mod java_roots;
@@ -0,0 +1,396 @@
+// Result reduction - fit one file's extraction results into a token budget
+//
+// LLM-context callers want "at most N tokens of symbols" rather than a whole
+// file's results. reduce_results measures each symbol, identifier, and
+// relationship as its JSON export record (export::SymbolRecord etc.) through
+// TokenEstimator, then drops lower-value content until the total fits:
+//
+//   1. code_context snippets
+//   2. doc comments
+//   3. identifiers
+//   4. symbols, lowest confidence first, in ProgressiveReducer steps, along
+//      with the relationships that point at them
+//
+// Each stage stops as soon as the results fit. Within a stage, items are
+// dropped lowest confidence first and, among equals, latest in the file
+// first, so the same input always reduces the same way. The report counts
+// what was dropped so callers can show a "truncated" indicator.
+
+use crate::export::{
+    IdentifierRecord, PendingRelationshipRecord, RelationshipRecord, SymbolRecord,
+};
+use crate::utils::progressive_reduction::ProgressiveReducer;
+use crate::utils::token_estimation::TokenEstimator;
+use julie_extractors::{ExtractionResults, Symbol};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// What reduce_results may drop to reach the budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ReductionStrategy {
+    /// Context, doc comments, identifiers, then symbols
+    #[default]
+    Progressive,
+    /// Context, doc comments, and identifiers only; every symbol is kept
+    KeepSymbols,
+}
+
+impl ReductionStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReductionStrategy::Progressive => "progressive",
+            ReductionStrategy::KeepSymbols => "keep_symbols",
+        }
+    }
+}
+
+impl fmt::Display for ReductionStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ReductionStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "progressive" => Ok(ReductionStrategy::Progressive),
+            "keep_symbols" => Ok(ReductionStrategy::KeepSymbols),
+            other => Err(format!(
+                "Unknown reduction strategy '{}' (expected progressive or keep_symbols)",
+                other
+            )),
+        }
+    }
+}
+
+/// What reduce_results dropped, per category
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReductionReport {
+    /// Estimated tokens before reduction
+    pub original_tokens: usize,
+    /// Estimated tokens after reduction; above the budget only when even
+    /// the strategy's last stage could not get under it
+    pub estimated_tokens: usize,
+    pub code_contexts: usize,
+    pub doc_comments: usize,
+    pub identifiers: usize,
+    pub symbols: usize,
+    /// Relationships dropped because one of their symbols was
+    pub relationships: usize,
+}
+
+impl ReductionReport {
+    /// Whether anything was dropped
+    pub fn truncated(&self) -> bool {
+        self.code_contexts
+            + self.doc_comments
+            + self.identifiers
+            + self.symbols
+            + self.relationships
+            > 0
+    }
+}
+
+/// Estimated tokens of the results as exported JSON
+pub fn estimate_results_tokens(results: &ExtractionResults) -> usize {
+    let estimator = TokenEstimator::new();
+    results
+        .symbols
+        .iter()
+        .map(|s| symbol_tokens(&estimator, s))
+        .chain(
+            results
+                .identifiers
+                .iter()
+                .map(|i| record_tokens(&estimator, &IdentifierRecord::from(i))),
+        )
+        .chain(
+            results
+                .relationships
+                .iter()
+                .map(|r| record_tokens(&estimator, &RelationshipRecord::from(r))),
+        )
+        .chain(
+            results
+                .pending_relationships
+                .iter()
+                .map(|r| record_tokens(&estimator, &PendingRelationshipRecord::from(r))),
+        )
+        .sum()
+}
+
+/// Drop lower-value content from `results` until it fits in `max_tokens`
+pub fn reduce_results(
+    results: &mut ExtractionResults,
+    max_tokens: usize,
+    strategy: ReductionStrategy,
+) -> ReductionReport {
+    let estimator = TokenEstimator::new();
+    let mut total = estimate_results_tokens(results);
+    let mut report = ReductionReport {
+        original_tokens: total,
+        ..Default::default()
+    };
+
+    report.code_contexts = drop_symbol_field(
+        &estimator,
+        &mut results.symbols,
+        &mut total,
+        max_tokens,
+        |s| &mut s.code_context,
+    );
+    report.doc_comments = drop_symbol_field(
+        &estimator,
+        &mut results.symbols,
+        &mut total,
+        max_tokens,
+        |s| &mut s.doc_comment,
+    );
+
+    if total > max_tokens {
+        let mut order: Vec<usize> = (0..results.identifiers.len()).collect();
+        order.sort_by(|&a, &b| {
+            by_confidence(
+                Some(results.identifiers[a].confidence),
+                Some(results.identifiers[b].confidence),
+            )
+            .then(b.cmp(&a))
+        });
+        let mut dropped = HashSet::new();
+        for index in order {
+            if total <= max_tokens {
+                break;
+            }
+            total -= record_tokens(
+                &estimator,
+                &IdentifierRecord::from(&results.identifiers[index]),
+            );
+            dropped.insert(index);
+        }
+        report.identifiers = dropped.len();
+        let mut index = 0;
+        results.identifiers.retain(|_| {
+            index += 1;
+            !dropped.contains(&(index - 1))
+        });
+    }
+
+    if total > max_tokens && strategy == ReductionStrategy::Progressive {
+        drop_symbols(&estimator, results, &mut total, max_tokens, &mut report);
+    }
+
+    report.estimated_tokens = total;
+    report
+}
+
+/// Clear one optional field on symbols until under budget; returns how many were cleared
+fn drop_symbol_field(
+    estimator: &TokenEstimator,
+    symbols: &mut [Symbol],
+    total: &mut usize,
+    max_tokens: usize,
+    field: impl Fn(&mut Symbol) -> &mut Option<String>,
+) -> usize {
+    if *total <= max_tokens {
+        return 0;
+    }
+    let mut cleared = 0;
+    for index in drop_order(symbols) {
+        if *total <= max_tokens {
+            break;
+        }
+        let symbol = &mut symbols[index];
+        if field(symbol).is_none() {
+            continue;
+        }
+        let before = symbol_tokens(estimator, symbol);
+        *field(symbol) = None;
+        *total -= before - symbol_tokens(estimator, symbol);
+        cleared += 1;
+    }
+    cleared
+}
+
+/// Keep the most confident symbols that fit, in ProgressiveReducer steps
+fn drop_symbols(
+    estimator: &TokenEstimator,
+    results: &mut ExtractionResults,
+    total: &mut usize,
+    max_tokens: usize,
+    report: &mut ReductionReport,
+) {
+    let costs: Vec<usize> = results
+        .symbols
+        .iter()
+        .map(|s| symbol_tokens(estimator, s))
+        .collect();
+    let symbol_total: usize = costs.iter().sum();
+    let rest = *total - symbol_total;
+
+    // Most valuable first: the reverse of the drop order
+    let mut ranked = drop_order(&results.symbols);
+    ranked.reverse();
+    let kept: HashSet<usize> = ProgressiveReducer::new()
+        .reduce(&ranked, max_tokens.saturating_sub(rest), |subset| {
+            subset.iter().map(|&i| costs[i]).sum()
+        })
+        .into_iter()
+        .collect();
+    if kept.len() == results.symbols.len() {
+        return;
+    }
+
+    let mut removed_ids = HashSet::new();
+    for (index, symbol) in results.symbols.iter().enumerate() {
+        if !kept.contains(&index) {
+            removed_ids.insert(symbol.id.clone());
+            *total -= costs[index];
+        }
+    }
+    report.symbols = removed_ids.len();
+    results.symbols.retain(|s| !removed_ids.contains(&s.id));
+
+    let before = results.relationships.len();
+    results.relationships.retain(|r| {
+        let keep =
+            !removed_ids.contains(&r.from_symbol_id) && !removed_ids.contains(&r.to_symbol_id);
+        if !keep {
+            *total -= record_tokens(estimator, &RelationshipRecord::from(r));
+        }
+        keep
+    });
+    report.relationships = before - results.relationships.len();
+    results.pending_relationships.retain(|r| {
+        let keep = !removed_ids.contains(&r.from_symbol_id);
+        if !keep {
+            *total -= record_tokens(estimator, &PendingRelationshipRecord::from(r));
+        }
+        keep
+    });
+}
+
+/// Symbol positions, first to drop first: lowest confidence, then latest in the file
+fn drop_order(symbols: &[Symbol]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..symbols.len()).collect();
+    order.sort_by(|&a, &b| {
+        by_confidence(symbols[a].confidence, symbols[b].confidence).then(b.cmp(&a))
+    });
+    order
+}
+
+/// Ascending confidence; unscored counts as fully confident
+fn by_confidence(a: Option<f32>, b: Option<f32>) -> Ordering {
+    a.unwrap_or(1.0).total_cmp(&b.unwrap_or(1.0))
+}
+
+fn symbol_tokens(estimator: &TokenEstimator, symbol: &Symbol) -> usize {
+    record_tokens(estimator, &SymbolRecord::from(symbol))
+}
+
+fn record_tokens(estimator: &TokenEstimator, record: &impl Serialize) -> usize {
+    serde_json::to_string(record)
+        .map(|json| estimator.estimate_string(&json))
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::test_results;
+    use julie_extractors::{Relationship, RelationshipKind, SymbolKind};
+
+    fn symbol(name: &str, confidence: f32) -> Symbol {
+        Symbol {
+            id: format!("id-{}", name),
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            language: "python".to_string(),
+            file_path: "app.py".to_string(),
+            start_line: 1,
+            start_column: 0,
+            end_line: 1,
+            end_column: 0,
+            start_byte: 0,
+            end_byte: 0,
+            signature: Some(format!("def {}():", name)),
+            doc_comment: Some("Explains what the function does in some detail.".to_string()),
+            visibility: None,
+            parent_id: None,
+            metadata: None,
+            semantic_group: None,
+            confidence: Some(confidence),
+            code_context: Some(format!("\ndef {}():\n    return compute(1, 2, 3)\n", name)),
+            content_type: None,
+        }
+    }
+
+    fn results() -> ExtractionResults {
+        ExtractionResults {
+            relationships: vec![Relationship {
+                id: "rel".to_string(),
+                from_symbol_id: "id-first".to_string(),
+                to_symbol_id: "id-guess".to_string(),
+                kind: RelationshipKind::Calls,
+                file_path: "app.py".to_string(),
+                line_number: 1,
+                confidence: 1.0,
+                metadata: None,
+            }],
+            ..test_results(vec![
+                symbol("first", 1.0),
+                symbol("guess", 0.5),
+                symbol("third", 1.0),
+            ])
+        }
+    }
+
+    #[test]
+    fn test_context_goes_before_doc_comments_and_symbols() {
+        let mut reduced = results();
+        let full = estimate_results_tokens(&reduced);
+        let code_context = symbol_tokens(&TokenEstimator::new(), &symbol("guess", 0.5))
+            - symbol_tokens(
+                &TokenEstimator::new(),
+                &Symbol {
+                    code_context: None,
+                    ..symbol("guess", 0.5)
+                },
+            );
+
+        let report = reduce_results(&mut reduced, full - code_context, Default::default());
+
+        // Only the least confident symbol's context had to go
+        assert_eq!(report.code_contexts, 1);
+        assert_eq!(report.doc_comments, 0);
+        assert!(reduced.symbols[1].code_context.is_none());
+        assert!(reduced.symbols[0].code_context.is_some());
+        assert!(report.estimated_tokens <= full - code_context);
+        assert!(report.truncated());
+    }
+
+    #[test]
+    fn test_low_confidence_symbols_and_their_relationships_go_last() {
+        let mut reduced = results();
+        let report = reduce_results(&mut reduced, 1, ReductionStrategy::Progressive);
+
+        assert_eq!(report.code_contexts, 3);
+        assert_eq!(report.doc_comments, 3);
+        assert_eq!(report.symbols, 2);
+        assert_eq!(report.relationships, 1);
+        assert_eq!(reduced.symbols.len(), 1);
+        assert_eq!(reduced.symbols[0].name, "first");
+
+        let mut kept = results();
+        let report = reduce_results(&mut kept, 1, ReductionStrategy::KeepSymbols);
+        assert_eq!((report.symbols, kept.symbols.len()), (0, 3));
+        assert_eq!(
+            report,
+            reduce_results(&mut results(), 1, "keep_symbols".parse().unwrap())
+        );
+    }
+}
@@ -0,0 +1,239 @@
+// Symbol ranking - score extracted symbols against a search query
+//
+// Composes the search utilities into one entry point:
+//
+//   name match   best ExactMatchBoost over the query's naming variants
+//                (query_expansion: "user service" → UserService,
+//                user_service, userService); a name no variant matches
+//                can still score its Levenshtein similarity when that is at
+//                least FUZZY_THRESHOLD, which always ranks below a real match
+//   path         PathRelevanceScorer (tests, docs, vendored code lower),
+//                times GENERATED_FACTOR for generated sources
+//   kind prior   types and callables above fields, variables, and imports
+//
+// score = name match × path × kind prior. Symbols with no name match are
+// left out. Ties break on file path, line, then id, so a fixed corpus always
+// ranks the same way; the golden tests below pin that ordering.
+
+use crate::utils::exact_match_boost::ExactMatchBoost;
+use crate::utils::path_relevance::PathRelevanceScorer;
+use crate::utils::query_expansion::expand_query;
+use crate::utils::string_similarity::levenshtein_distance;
+use julie_extractors::{Symbol, SymbolKind};
+
+/// Lowest name similarity (1 - distance / length) counted as a fuzzy hit
+pub const FUZZY_THRESHOLD: f32 = 0.8;
+
+/// Path factor for generated sources (protobuf stubs, designer files, bundles)
+pub const GENERATED_FACTOR: f32 = 0.3;
+
+/// File name suffixes and directories of generated code
+const GENERATED_MARKERS: &[&str] = &[
+    "_pb2.py",
+    "_pb2_grpc.py",
+    ".pb.go",
+    ".pb.cc",
+    ".pb.h",
+    ".g.dart",
+    ".freezed.dart",
+    ".generated.cs",
+    ".designer.cs",
+    ".g.cs",
+    ".min.js",
+    ".bundle.js",
+    "/generated/",
+    "/__generated__/",
+];
+
+/// The `limit` best-scoring symbols for `query`, as (symbol id, score), best first
+pub fn rank_symbols(query: &str, symbols: &[Symbol], limit: usize) -> Vec<(String, f32)> {
+    let query = query.trim();
+    if query.is_empty() || limit == 0 {
+        return Vec::new();
+    }
+    // Keep name-shaped variants; the wildcard/OR/fuzzy forms are FTS syntax
+    let variants: Vec<(String, ExactMatchBoost)> = expand_query(query)
+        .into_iter()
+        .filter(|v| !v.contains(['*', '(', '~', '"']))
+        .map(|v| (v.to_lowercase(), ExactMatchBoost::new(&v)))
+        .collect();
+    let paths = PathRelevanceScorer::new(query);
+
+    let mut scored: Vec<(&Symbol, f32)> = symbols
+        .iter()
+        .filter_map(|symbol| {
+            let name = name_score(&variants, &symbol.name)?;
+            let score = name * path_score(&paths, &symbol.file_path) * kind_prior(&symbol.kind);
+            Some((symbol, score))
+        })
+        .collect();
+    scored.sort_by(|(a, a_score), (b, b_score)| {
+        b_score
+            .total_cmp(a_score)
+            .then_with(|| a.file_path.cmp(&b.file_path))
+            .then(a.start_line.cmp(&b.start_line))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(symbol, score)| (symbol.id.clone(), score))
+        .collect()
+}
+
+/// Best match of `name` against any variant; None when nothing matches
+fn name_score(variants: &[(String, ExactMatchBoost)], name: &str) -> Option<f32> {
+    let boost = variants
+        .iter()
+        .map(|(_, booster)| booster.calculate_boost(name))
+        .fold(1.0_f32, f32::max);
+    if boost > 1.0 {
+        return Some(boost);
+    }
+
+    let name = name.to_lowercase();
+    let similarity = variants
+        .iter()
+        .map(|(variant, _)| {
+            let longest = variant.chars().count().max(name.chars().count()) as f32;
+            1.0 - levenshtein_distance(variant, &name) as f32 / longest
+        })
+        .fold(0.0_f32, f32::max);
+    (similarity >= FUZZY_THRESHOLD).then_some(similarity)
+}
+
+fn path_score(paths: &PathRelevanceScorer, file_path: &str) -> f32 {
+    let normalized = format!("/{}", file_path.replace('\\', "/").to_lowercase());
+    let generated = GENERATED_MARKERS.iter().any(|marker| {
+        normalized.ends_with(marker) || (marker.ends_with('/') && normalized.contains(marker))
+    });
+    let score = paths.calculate_score(file_path);
+    if generated {
+        score * GENERATED_FACTOR
+    } else {
+        score
+    }
+}
+
+/// Declarations people search for rank above the values inside them
+fn kind_prior(kind: &SymbolKind) -> f32 {
+    match kind {
+        SymbolKind::Class
+        | SymbolKind::Interface
+        | SymbolKind::Struct
+        | SymbolKind::Trait
+        | SymbolKind::Enum
+        | SymbolKind::Union
+        | SymbolKind::Type => 1.0,
+        SymbolKind::Function
+        | SymbolKind::Method
+        | SymbolKind::Constructor
+        | SymbolKind::Delegate => 1.0,
+        SymbolKind::Module | SymbolKind::Namespace => 0.9,
+        SymbolKind::Constant | SymbolKind::EnumMember | SymbolKind::Event => 0.8,
+        SymbolKind::Property | SymbolKind::Field | SymbolKind::Operator => 0.75,
+        SymbolKind::Variable | SymbolKind::Destructor => 0.7,
+        SymbolKind::Import | SymbolKind::Export => 0.5,
+        #[allow(unreachable_patterns)]
+        _ => 0.8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enrichment::base::{EnrichmentContext, SymbolOptions};
+    use std::path::Path;
+
+    /// A small mixed corpus: production code, a test, generated stubs, and near-misses
+    fn corpus() -> Vec<Symbol> {
+        let entries: &[(&str, &str, SymbolKind)] = &[
+            ("src/services/user.py", "UserService", SymbolKind::Class),
+            ("src/services/user.py", "user_service", SymbolKind::Variable),
+            ("src/services/user.py", "get_user", SymbolKind::Method),
+            (
+                "src/services/account.py",
+                "UserServiceFactory",
+                SymbolKind::Class,
+            ),
+            ("src/api/routes.py", "userServise", SymbolKind::Function),
+            (
+                "tests/test_user.py",
+                "test_user_service",
+                SymbolKind::Function,
+            ),
+            ("gen/user_pb2.py", "UserService", SymbolKind::Class),
+            ("src/models/order.py", "OrderService", SymbolKind::Class),
+            ("src/main.py", "UserService", SymbolKind::Import),
+        ];
+        entries
+            .iter()
+            .enumerate()
+            .map(|(line, (file, name, kind))| {
+                let content = "\n".repeat(line) + name;
+                let ctx = EnrichmentContext::new(file, "python", &content, Path::new("."));
+                ctx.create_symbol(
+                    name,
+                    kind.clone(),
+                    line,
+                    content.len(),
+                    SymbolOptions::default(),
+                )
+            })
+            .collect()
+    }
+
+    fn ranked(query: &str, limit: usize) -> Vec<(String, String)> {
+        let symbols = corpus();
+        rank_symbols(query, &symbols, limit)
+            .into_iter()
+            .map(|(id, _)| {
+                let symbol = symbols.iter().find(|s| s.id == id).unwrap();
+                (symbol.file_path.clone(), symbol.name.clone())
+            })
+            .collect()
+    }
+
+    fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+        expected
+            .iter()
+            .map(|(file, name)| (file.to_string(), name.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_golden_ranking_for_multi_word_query() {
+        assert_eq!(
+            ranked("user service", 20),
+            pairs(&[
+                ("src/services/user.py", "UserService"),
+                ("src/services/user.py", "user_service"),
+                ("src/services/account.py", "UserServiceFactory"),
+                ("src/main.py", "UserService"),
+                ("src/api/routes.py", "userServise"),
+                ("src/models/order.py", "OrderService"),
+                ("src/services/user.py", "get_user"),
+                ("tests/test_user.py", "test_user_service"),
+                ("gen/user_pb2.py", "UserService"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_golden_ranking_for_identifier_query_and_limit() {
+        assert_eq!(
+            ranked("UserService", 3),
+            pairs(&[
+                ("src/services/user.py", "UserService"),
+                ("src/services/user.py", "user_service"),
+                ("src/main.py", "UserService"),
+            ])
+        );
+        // A near-miss name below FUZZY_THRESHOLD is left out entirely
+        assert_eq!(
+            ranked("OrderService", 5),
+            pairs(&[("src/models/order.py", "OrderService")])
+        );
+        assert!(ranked("", 5).is_empty());
+    }
+}
@@ -7,9 +7,9 @@
 //! - Efficient for 100k+ files: Uses notify crate (same as ripgrep)
 //! - Cross-platform: Works on Linux (inotify), macOS (FSEvents), Windows (ReadDirectoryChangesW)
 
+use crate::utils::ignore::IgnoreRules;
 use anyhow::{Context, Result};
 use dashmap::DashMap;
-use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use pyo3::prelude::*;
 use pyo3::types::PyAny;
@@ -73,10 +73,8 @@ pub struct PyFileWatcher {
     stop_tx: Option<Sender<WatcherMessage>>,
     /// Handle to the watcher thread
     watcher_thread: Option<JoinHandle<()>>,
-    /// Gitignore matcher for filtering files
-    gitignore: Option<Gitignore>,
-    /// Custom ignore patterns (from .julieignore)
-    custom_ignores: Vec<String>,
+    /// Workspace ignore rules (.gitignore files, .julieignore, caller patterns)
+    ignore_rules: Arc<IgnoreRules>,
 }
 
 #[pymethods]
@@ -86,7 +84,7 @@ impl PyFileWatcher {
     /// Args:
     ///     workspace_path: Root directory to watch
     ///     initial_hashes: Dict mapping file paths to their known hashes
-    ///     ignore_patterns: List of gitignore-style patterns to exclude
+    ///     ignore_patterns: Extra patterns to exclude, in .julieignore syntax
     #[new]
     #[pyo3(signature = (workspace_path, initial_hashes=None, ignore_patterns=None))]
     fn new(
@@ -120,11 +118,11 @@ impl PyFileWatcher {
             );
         }
 
-        // Build gitignore matcher from workspace .gitignore
-        let gitignore = build_gitignore(&workspace);
-
-        // Store custom ignore patterns
-        let custom_ignores = ignore_patterns.unwrap_or_default();
+        // Same rules as the workspace walk: nested .gitignore files, then .julieignore
+        let ignore_rules = Arc::new(IgnoreRules::load(
+            &workspace,
+            &ignore_patterns.unwrap_or_default(),
+        ));
 
         Ok(PyFileWatcher {
             workspace_path: workspace,
@@ -132,8 +130,7 @@ impl PyFileWatcher {
             running: Arc::new(AtomicBool::new(false)),
             stop_tx: None,
             watcher_thread: None,
-            gitignore,
-            custom_ignores,
+            ignore_rules,
         })
     }
 
@@ -161,8 +158,7 @@ impl PyFileWatcher {
         let workspace = self.workspace_path.clone();
         let known_hashes = Arc::clone(&self.known_hashes);
         let running = Arc::clone(&self.running);
-        let gitignore = self.gitignore.clone();
-        let custom_ignores = self.custom_ignores.clone();
+        let ignore_rules = Arc::clone(&self.ignore_rules);
 
         // Mark as running
         self.running.store(true, Ordering::SeqCst);
@@ -179,8 +175,7 @@ impl PyFileWatcher {
                     running,
                     stop_rx,
                     callback,
-                    gitignore,
-                    custom_ignores,
+                    ignore_rules,
                 ) {
                     error!("File watcher error: {:?}", e);
                 }
@@ -248,159 +243,6 @@ impl PyFileWatcher {
             .collect()
     }
 }
-/// Default ignore patterns (always applied)
-/// Matches the Python DEFAULT_IGNORES list
-const DEFAULT_IGNORES: &[&str] = &[
-    // ═══════════════════════════════════════════
-    // Version Control
-    // ═══════════════════════════════════════════
-    ".git/", ".svn/", ".hg/", ".bzr/",
-
-    // ═══════════════════════════════════════════
-    // IDE and Editor
-    // ═══════════════════════════════════════════
-    ".vs/", ".vscode/", ".idea/", ".eclipse/",
-    "*.swp", "*.swo",
-
-    // ═══════════════════════════════════════════
-    // Build and Output Directories
-    // ═══════════════════════════════════════════
-    "bin/", "obj/", "build/", "dist/", "out/",
-    "target/", "Debug/", "Release/",
-    ".next/", ".nuxt/", "DerivedData/",
-
-    // ═══════════════════════════════════════════
-    // Package Managers and Dependencies
-    // ═══════════════════════════════════════════
-    "node_modules/", "packages/", ".npm/", "bower_components/",
-    "vendor/", "Pods/",
-
-    // ═══════════════════════════════════════════
-    // Python Virtual Environments and Cache
-    // ═══════════════════════════════════════════
-    ".venv/", "venv/", "env/", ".env/",
-    "__pycache__/", "*.pyc", "*.pyo", "*.pyd",
-    ".pytest_cache/", ".mypy_cache/", ".ruff_cache/",
-    ".tox/", ".eggs/", "*.egg-info/",
-    ".coverage", "htmlcov/", ".hypothesis/",
-
-    // ═══════════════════════════════════════════
-    // Cache and Temporary Files
-    // ═══════════════════════════════════════════
-    ".cache/", ".temp/", ".tmp/", "tmp/", "temp/", ".sass-cache/",
-    "*.tmp", "*.tmp.*", "*.temp", "*.swp", "*.lock", "*.pid",
-    "*~", "*.bak", "[#]*[#]", ".#*", "*.orig", "*.rej",
-
-    // ═══════════════════════════════════════════
-    // Code Intelligence Tools (our own dirs)
-    // ═══════════════════════════════════════════
-    ".miller/", ".julie/", ".coa/", ".codenav/",
-
-    // ═══════════════════════════════════════════
-    // Binary Files (Executables, libs, media, archives)
-    // ═══════════════════════════════════════════
-    // Executables/Libs
-    "*.dll", "*.exe", "*.pdb", "*.so", "*.dylib", "*.lib",
-    "*.a", "*.o", "*.obj", "*.bin",
-    // Media
-    "*.jpg", "*.jpeg", "*.png", "*.gif", "*.bmp", "*.ico", "*.svg", "*.webp", "*.tiff",
-    "*.mp3", "*.mp4", "*.avi", "*.mov", "*.wmv", "*.flv", "*.webm", "*.mkv", "*.wav",
-    // Archives
-    "*.zip", "*.rar", "*.7z", "*.tar", "*.gz", "*.bz2", "*.xz", "*.dmg", "*.pkg",
-    // Database/Logs
-    "*.db", "*.sqlite", "*.sqlite3", "*.mdf", "*.ldf", "*.bak",
-    "*.log", "*.dump", "*.core",
-    // Fonts
-    "*.ttf", "*.otf", "*.woff", "*.woff2", "*.eot",
-    // Documents (Binary)
-    "*.pdf", "*.doc", "*.docx", "*.xls", "*.xlsx", "*.ppt", "*.pptx",
-
-    // ═══════════════════════════════════════════
-    // System Files
-    // ═══════════════════════════════════════════
-    ".DS_Store", "Thumbs.db", "desktop.ini",
-
-    // ═══════════════════════════════════════════
-    // Noisy/Generated Files
-    // ═══════════════════════════════════════════
-    // Locks
-    "package-lock.json", "yarn.lock", "pnpm-lock.yaml", "Cargo.lock",
-    "poetry.lock", "Gemfile.lock", "composer.lock", "Pipfile.lock", "bun.lockb",
-    // Minified/Bundled
-    "*.min.js", "*.min.css", "*.bundle.js", "*.chunk.js", "*.map", "*.d.ts.map",
-];
-
-/// Build a gitignore matcher from defaults and the workspace .gitignore file
-fn build_gitignore(workspace: &Path) -> Option<Gitignore> {
-    let mut builder = GitignoreBuilder::new(workspace);
-
-    // 1. Add all default ignores (Global patterns)
-    for pattern in DEFAULT_IGNORES {
-        // We use None for the root so these match globally relative to workspace
-        if let Err(e) = builder.add_line(None, pattern) {
-            warn!("Failed to add default ignore pattern '{}': {}", pattern, e);
-        }
-    }
-
-    // 2. Add patterns from workspace .gitignore (if it exists)
-    let gitignore_path = workspace.join(".gitignore");
-    if gitignore_path.exists() {
-        if let Some(e) = builder.add(&gitignore_path) {
-            warn!("Failed to parse .gitignore: {:?}", e);
-        }
-    }
-
-    match builder.build() {
-        Ok(gi) => Some(gi),
-        Err(e) => {
-            warn!("Failed to build gitignore matcher: {:?}", e);
-            None
-        }
-    }
-}
-
-/// Check if a path should be ignored
-fn should_ignore(
-    path: &Path,
-    workspace: &Path,
-    gitignore: &Option<Gitignore>,
-    custom_ignores: &[String],
-) -> bool {
-    // Get relative path
-    let rel_path = match path.strip_prefix(workspace) {
-        Ok(p) => p,
-        Err(_) => return true, // Outside workspace
-    };
-
-    // Check gitignore patterns
-    // Use matched_path_or_any_parents to properly handle directory patterns like "build/"
-    if let Some(gi) = gitignore {
-        if gi
-            .matched_path_or_any_parents(rel_path, path.is_dir())
-            .is_ignore()
-        {
-            return true;
-        }
-    }
-
-    // Check custom ignore patterns (reuse existing logic)
-    if !custom_ignores.is_empty() {
-        if crate::utils::ignore::is_ignored_by_pattern(path, custom_ignores) {
-            return true;
-        }
-    }
-
-    // Skip hidden files (except .gitignore itself)
-    if let Some(name) = path.file_name() {
-        let name_str = name.to_string_lossy();
-        if name_str.starts_with('.') && name_str != ".gitignore" && name_str != ".julieignore" {
-            return true;
-        }
-    }
-
-    false
-}
-
 /// Compute Blake3 hash of file content
 fn compute_hash(path: &Path) -> Result<String> {
     let content = fs::read(path).context("Failed to read file")?;
@@ -415,8 +257,7 @@ fn run_watcher(
     running: Arc<AtomicBool>,
     stop_rx: Receiver<WatcherMessage>,
     callback: Py<PyAny>,
-    gitignore: Option<Gitignore>,
-    custom_ignores: Vec<String>,
+    ignore_rules: Arc<IgnoreRules>,
 ) -> Result<()> {
     // Create channel for notify events
     let (event_tx, event_rx) = channel::<notify::Result<Event>>();
@@ -461,7 +302,7 @@ fn run_watcher(
                     }
 
                     // Skip ignored files
-                    if should_ignore(path, &workspace, &gitignore, &custom_ignores) {
+                    if ignore_rules.is_ignored(path, false) {
                         continue;
                     }
 
@@ -580,7 +421,8 @@ fn flush_changes_to_python(
     debug!("Flushing {} file changes to Python", changes.len());
 
     // Acquire GIL and call Python
-    Python::with_gil(|py| {  // Note: with_gil is deprecated but attach is not stable yet
+    Python::with_gil(|py| {
+        // Note: with_gil is deprecated but attach is not stable yet
         // Convert changes to Python list of tuples: [(event_type, path, hash), ...]
         let events: Vec<(String, String, Option<String>)> = changes
             .iter()
@@ -663,7 +505,7 @@ mod tests {
     }
 
     #[test]
-    fn test_should_ignore_gitignore_patterns() {
+    fn test_ignore_rules_apply_gitignore_patterns() {
         let temp_dir = TempDir::new().unwrap();
         let workspace = temp_dir.path();
 
@@ -677,113 +519,69 @@ mod tests {
         fs::create_dir(workspace.join("src")).unwrap();
         fs::write(workspace.join("src/main.rs"), "fn main() {}").unwrap();
 
-        let gitignore = build_gitignore(workspace);
+        let rules = IgnoreRules::load(workspace, &[]);
 
         // Should ignore (matches *.log pattern)
-        assert!(should_ignore(
-            &workspace.join("debug.log"),
-            workspace,
-            &gitignore,
-            &[]
-        ));
+        assert!(rules.is_ignored(&workspace.join("debug.log"), false));
         // Should ignore (matches build/ directory pattern)
-        assert!(should_ignore(
-            &workspace.join("build/output.txt"),
-            workspace,
-            &gitignore,
-            &[]
-        ));
+        assert!(rules.is_ignored(&workspace.join("build/output.txt"), false));
 
         // Should NOT ignore
-        assert!(!should_ignore(
-            &workspace.join("src/main.rs"),
-            workspace,
-            &gitignore,
-            &[]
-        ));
+        assert!(!rules.is_ignored(&workspace.join("src/main.rs"), false));
+    }
+
+    #[test]
+    fn test_ignore_rules_follow_nested_gitignore_negations() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path();
+
+        // The workspace walk re-includes logs/keep.log; the watcher must agree
+        fs::write(workspace.join(".gitignore"), "*.log\n").unwrap();
+        fs::create_dir(workspace.join("logs")).unwrap();
+        fs::write(workspace.join("logs/.gitignore"), "!keep.log\n").unwrap();
+
+        let rules = IgnoreRules::load(workspace, &[]);
+        assert!(!rules.is_ignored(&workspace.join("logs/keep.log"), false));
+        assert!(rules.is_ignored(&workspace.join("logs/debug.log"), false));
     }
 
     #[test]
-    fn test_should_ignore_hidden_files() {
+    fn test_ignore_rules_skip_hidden_files() {
         let temp_dir = TempDir::new().unwrap();
         let workspace = temp_dir.path();
+        let rules = IgnoreRules::load(workspace, &[]);
 
         // Hidden files should be ignored (except .gitignore)
-        assert!(should_ignore(
-            &workspace.join(".hidden"),
-            workspace,
-            &None,
-            &[]
-        ));
-        assert!(should_ignore(
-            &workspace.join(".env"),
-            workspace,
-            &None,
-            &[]
-        ));
+        assert!(rules.is_ignored(&workspace.join(".hidden"), false));
+        assert!(rules.is_ignored(&workspace.join(".env"), false));
 
         // .gitignore and .julieignore should NOT be ignored
-        assert!(!should_ignore(
-            &workspace.join(".gitignore"),
-            workspace,
-            &None,
-            &[]
-        ));
-        assert!(!should_ignore(
-            &workspace.join(".julieignore"),
-            workspace,
-            &None,
-            &[]
-        ));
+        assert!(!rules.is_ignored(&workspace.join(".gitignore"), false));
+        assert!(!rules.is_ignored(&workspace.join(".julieignore"), false));
     }
 
     #[test]
-    fn test_should_ignore_temp_files() {
+    fn test_ignore_rules_skip_temp_files() {
         let temp_dir = TempDir::new().unwrap();
         let workspace = temp_dir.path();
-
-        // Create .gitignore to enable gitignore matching
-        fs::write(workspace.join(".gitignore"), "").unwrap();
-        let gitignore = build_gitignore(workspace);
+        let rules = IgnoreRules::load(workspace, &[]);
 
         // Should ignore .tmp files
-        assert!(should_ignore(
-            &workspace.join("file.tmp"),
-            workspace,
-            &gitignore,
-            &[]
-        ));
+        assert!(rules.is_ignored(&workspace.join("file.tmp"), false));
 
         // Should ignore pytest-style temp files (file.py.tmp.12345.67890)
-        assert!(should_ignore(
+        assert!(rules.is_ignored(
             &workspace.join("test_file.py.tmp.64850.1765224011536"),
-            workspace,
-            &gitignore,
-            &[]
+            false
         ));
 
         // Should ignore editor backup files
-        assert!(should_ignore(
-            &workspace.join("file.py~"),
-            workspace,
-            &gitignore,
-            &[]
-        ));
+        assert!(rules.is_ignored(&workspace.join("file.py~"), false));
 
         // Should ignore vim swap files
-        assert!(should_ignore(
-            &workspace.join("file.py.swp"),
-            workspace,
-            &gitignore,
-            &[]
-        ));
+        assert!(rules.is_ignored(&workspace.join("file.py.swp"), false));
 
         // Should NOT ignore normal Python files
-        assert!(!should_ignore(
-            &workspace.join("test_file.py"),
-            workspace,
-            &gitignore,
-            &[]
-        ));
+        assert!(!rules.is_ignored(&workspace.join("test_file.py"), false));
     }
 }
@@ -0,0 +1,332 @@
+// Workspace extraction - walk a directory tree and extract every supported file
+//
+// One call replaces Python's enumerate/filter/batch loop: the walk applies the
+// default ignores, root and nested .gitignore files, .julieignore, and any
+// caller patterns (utils::ignore::IgnoreRules), keeps the files utils::file_utils::is_supported_file
+// accepts, and extracts them in parallel.
+//
+// Symbolic links are followed; a link back into one of its own ancestors is
+// reported as a loop instead of being walked again. Entries that cannot be
+// walked or read, files over the size limit, and files whose extraction
+// panicked, timed out, or nested too deeply (see engine::extract_guarded) are
+// returned as skipped files rather than aborting the walk.
+// `extract_workspace_with_progress` reports extraction progress and can be
+// cancelled like any batch (engine::run_batch); files not started after a
+// cancel are skipped with kind "cancelled".
+
+use crate::engine::{
+    self, BatchProgress, EnrichmentOptions, ExtractionError, ExtractionLimits, FileExtraction,
+};
+use crate::utils::file_utils::is_supported_file;
+use crate::utils::ignore::IgnoreRules;
+use ignore::WalkBuilder;
+use std::fmt;
+use std::path::Path;
+
+/// Caller settings for a workspace walk
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceOptions {
+    /// Patterns in .julieignore syntax, applied on top of the workspace's .julieignore
+    pub ignore_patterns: Vec<String>,
+    /// Files larger than this many bytes are skipped
+    pub max_file_size: Option<u64>,
+    pub enrichment: EnrichmentOptions,
+    pub limits: ExtractionLimits,
+}
+
+/// Why a file was not extracted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    TooLarge {
+        size: u64,
+        limit: u64,
+    },
+    /// The entry could not be walked or read (permissions, symlink loops)
+    Unreadable(String),
+    /// Extraction panicked, timed out, exceeded the nesting limit, or was cancelled
+    Failed {
+        kind: &'static str,
+        message: String,
+    },
+}
+
+impl SkipReason {
+    /// Stable name for the reason: "too_large", "read_error", or an ExtractionError kind
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SkipReason::TooLarge { .. } => "too_large",
+            SkipReason::Unreadable(_) => "read_error",
+            SkipReason::Failed { kind, .. } => kind,
+        }
+    }
+}
+
+impl From<ExtractionError> for SkipReason {
+    fn from(error: ExtractionError) -> Self {
+        match error {
+            ExtractionError::Read(e) => SkipReason::Unreadable(e.to_string()),
+            error => SkipReason::Failed {
+                kind: error.kind(),
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::TooLarge { size, limit } => write!(
+                f,
+                "Skipped: {} bytes exceeds max_file_size of {} bytes",
+                size, limit
+            ),
+            SkipReason::Unreadable(message) | SkipReason::Failed { message, .. } => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+/// A file (or directory, for walk errors) that was not extracted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    /// Path relative to the workspace root, Unix-style
+    pub path: String,
+    pub reason: SkipReason,
+}
+
+/// Everything extracted from a workspace
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceExtraction {
+    /// Extracted files, sorted by path
+    pub files: Vec<FileExtraction>,
+    /// Files that were not extracted, sorted by path
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// Walk the workspace and extract every supported, non-ignored file
+pub fn extract_workspace(workspace_root: &Path, options: &WorkspaceOptions) -> WorkspaceExtraction {
+    extract_workspace_with_progress(workspace_root, options, None)
+}
+
+/// `extract_workspace`, reporting progress over the discovered files
+pub fn extract_workspace_with_progress(
+    workspace_root: &Path,
+    options: &WorkspaceOptions,
+    progress: Option<BatchProgress<'_>>,
+) -> WorkspaceExtraction {
+    let (paths, mut skipped) = discover_files(workspace_root, options);
+
+    let (extracted, _) = engine::run_batch(
+        &paths,
+        |rel_path| rel_path,
+        |rel_path| {
+            engine::extract_path(
+                rel_path,
+                workspace_root,
+                &options.enrichment,
+                &options.limits,
+            )
+        },
+        progress,
+    );
+    let results = extracted.into_iter().zip(&paths).map(|(result, rel_path)| {
+        result
+            .unwrap_or(Err(ExtractionError::Cancelled))
+            .map_err(|e| SkippedFile {
+                path: rel_path.clone(),
+                reason: SkipReason::from(e),
+            })
+    });
+
+    let mut files = Vec::with_capacity(paths.len());
+    for result in results {
+        match result {
+            Ok(file) => files.push(file),
+            Err(skip) => skipped.push(skip),
+        }
+    }
+    skipped.sort_by(|a, b| a.path.cmp(&b.path));
+    WorkspaceExtraction { files, skipped }
+}
+
+/// Relative paths of the files extract_workspace would extract, sorted, plus skipped entries
+pub fn discover_files(
+    workspace_root: &Path,
+    options: &WorkspaceOptions,
+) -> (Vec<String>, Vec<SkippedFile>) {
+    let rules = IgnoreRules::load(workspace_root, &options.ignore_patterns);
+    let mut walker = WalkBuilder::new(workspace_root);
+    walker
+        .standard_filters(false)
+        .follow_links(true)
+        .filter_entry(move |entry| {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            entry.depth() == 0 || !rules.is_ignored(entry.path(), is_dir)
+        });
+
+    let mut paths = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in walker.build() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let path = error_path(&e)
+                    .map(|path| relative_path(path, workspace_root))
+                    .unwrap_or_default();
+                skipped.push(SkippedFile {
+                    path,
+                    reason: SkipReason::Unreadable(e.to_string()),
+                });
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) || !is_supported_file(entry.path()) {
+            continue;
+        }
+
+        let rel_path = relative_path(entry.path(), workspace_root);
+        if let Some(limit) = options.max_file_size {
+            match entry.metadata() {
+                Ok(metadata) if metadata.len() > limit => {
+                    skipped.push(SkippedFile {
+                        path: rel_path,
+                        reason: SkipReason::TooLarge {
+                            size: metadata.len(),
+                            limit,
+                        },
+                    });
+                    continue;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    skipped.push(SkippedFile {
+                        path: rel_path,
+                        reason: SkipReason::Unreadable(e.to_string()),
+                    });
+                    continue;
+                }
+            }
+        }
+        paths.push(rel_path);
+    }
+
+    paths.sort();
+    (paths, skipped)
+}
+
+/// `root/src/app.py` → "src/app.py"
+fn relative_path(path: &Path, workspace_root: &Path) -> String {
+    path.strip_prefix(workspace_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// The path a walk error refers to, through the error's context wrappers
+fn error_path(error: &ignore::Error) -> Option<&Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::Loop { child, .. } => Some(child),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+            error_path(err)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_discover_files_applies_ignores_filters_and_size_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for (path, content) in [
+            ("src/app.py", "def app(): pass\n"),
+            ("src/big.py", "x = 1\n# padding padding padding\n"),
+            ("src/notes.unknownext", "not code"),
+            ("node_modules/lib/index.js", "module.exports = 1;\n"),
+            (".git/config.sh", "echo\n"),
+            ("generated/schema.rs", "fn schema() {}\n"),
+            ("custom/skip.go", "package skip\n"),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        fs::write(root.join(".julieignore"), "generated/\n").unwrap();
+
+        let options = WorkspaceOptions {
+            ignore_patterns: vec!["custom/".to_string()],
+            max_file_size: Some(20),
+            ..WorkspaceOptions::default()
+        };
+        let (paths, skipped) = discover_files(root, &options);
+
+        assert_eq!(paths, vec!["src/app.py"]);
+        assert_eq!(
+            skipped,
+            vec![SkippedFile {
+                path: "src/big.py".to_string(),
+                reason: SkipReason::TooLarge {
+                    size: 32,
+                    limit: 20
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_cancelled_files_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        for index in 0..6 {
+            fs::write(dir.path().join(format!("m{index}.py")), "x = 1\n").unwrap();
+        }
+        let mut reports = Vec::new();
+        let mut report = |done: usize, total: usize, _: &str| {
+            reports.push((done, total));
+            false
+        };
+        let progress = BatchProgress {
+            every: 1,
+            report: &mut report,
+        };
+        let extraction = extract_workspace_with_progress(
+            dir.path(),
+            &WorkspaceOptions::default(),
+            Some(progress),
+        );
+
+        assert_eq!(reports.first(), Some(&(1, 6)));
+        assert_eq!(extraction.files.len() + extraction.skipped.len(), 6);
+        assert!(extraction
+            .skipped
+            .iter()
+            .all(|skip| skip.reason.kind() == "cancelled"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_loops_are_reported_not_walked() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::write(root.join("src/app.py"), "def app(): pass\n").unwrap();
+        std::os::unix::fs::symlink(root.join("src"), root.join("src/nested/back")).unwrap();
+
+        let extraction = extract_workspace(root, &WorkspaceOptions::default());
+
+        let paths: Vec<&str> = extraction.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/app.py"]);
+        assert_eq!(extraction.skipped.len(), 1);
+        assert_eq!(extraction.skipped[0].path, "src/nested/back");
+        assert!(matches!(
+            extraction.skipped[0].reason,
+            SkipReason::Unreadable(_)
+        ));
+    }
+}